@@ -1,6 +1,7 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Only regenerate if the proto file changes
     println!("cargo:rerun-if-changed=src/proto/vllm_scheduler.proto");
+    println!("cargo:rerun-if-changed=src/proto/health.proto");
 
     // Configure protobuf compilation with custom settings
     let config = prost_build::Config::new();
@@ -22,8 +23,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "vllm.grpc.scheduler",
             "#[allow(unused, clippy::mixed_attributes_style)]",
         )
-        // Compile the proto file with the custom config
-        .compile_protos_with_config(config, &["src/proto/vllm_scheduler.proto"], &["src/proto"])?;
+        .server_mod_attribute(
+            "grpc.health.v1",
+            "#[allow(unused, clippy::mixed_attributes_style)]",
+        )
+        .client_mod_attribute(
+            "grpc.health.v1",
+            "#[allow(unused, clippy::mixed_attributes_style)]",
+        )
+        // Compile the scheduler and standard health checking protos with the custom config
+        .compile_protos_with_config(
+            config,
+            &["src/proto/vllm_scheduler.proto", "src/proto/health.proto"],
+            &["src/proto"],
+        )?;
 
     println!("cargo:warning=Protobuf compilation completed successfully");
 
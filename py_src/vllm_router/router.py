@@ -91,6 +91,11 @@ class Router:
         health_check_timeout_secs: Timeout in seconds for health check requests. Default: 5
         health_check_interval_secs: Interval in seconds between runtime health checks. Default: 60
         health_check_endpoint: Health check endpoint path. Default: '/health'
+        health_check_method: HTTP method for health check requests ('get' or 'post'). Default: 'get'
+        health_check_expected_body: Body a health check response must match: a substring, or
+            $.path.to.field=value for a JSON field. Default: None
+        health_check_generation_probe: Also require a one-token completion to succeed in runtime
+            health checks, catching workers whose model cannot generate. Uses GPU time. Default: False
         model_path: Model path for loading tokenizer (HuggingFace model ID or local path). Default: None
         tokenizer_path: Explicit tokenizer path (overrides model_path tokenizer if provided). Default: None
     """
@@ -134,6 +139,10 @@ class Router:
         )
         args_dict["prefill_policy"] = policy_from_str(args_dict["prefill_policy"])
         args_dict["decode_policy"] = policy_from_str(args_dict["decode_policy"])
+        args_dict["model_policies"] = {
+            model: policy_from_str(policy)
+            for model, policy in args_dict["model_policies"].items()
+        }
 
         # remove mini_lb parameter
         args_dict.pop("mini_lb")
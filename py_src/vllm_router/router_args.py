@@ -1,7 +1,7 @@
 import argparse
 import dataclasses
 import logging
-from typing import Dict, List, Optional
+from typing import Dict, List, Optional, Tuple
 
 logger = logging.getLogger(__name__)
 
@@ -34,6 +34,18 @@ class RouterArgs:
     eviction_interval_secs: int = 120
     max_tree_size: int = 2**26
     max_payload_size: int = 512 * 1024 * 1024  # 512MB default for large batches
+    # Per-route overrides of max_payload_size
+    max_payload_size_generate: Optional[int] = None
+    max_payload_size_chat: Optional[int] = None
+    max_payload_size_completions: Optional[int] = None
+    max_payload_size_embeddings: Optional[int] = None
+    max_payload_size_rerank: Optional[int] = None
+    max_payload_size_responses: Optional[int] = None
+    # Reject generation requests whose prompt exceeds this many characters
+    max_prompt_chars: Optional[int] = None
+    # max_tokens injected when omitted, and the cap clamping larger values (None = unset)
+    default_max_tokens: Optional[int] = None
+    max_tokens_limit: Optional[int] = None
     intra_node_data_parallel_size: int = (
         1  # Intra-node data parallel size (DP-aware routing automatically enabled when > 1)
     )
@@ -41,6 +53,7 @@ class RouterArgs:
     api_key: Optional[str] = None
     log_dir: Optional[str] = None
     log_level: Optional[str] = None
+    log_json: bool = False
     # Service discovery configuration
     service_discovery: bool = False
     selector: Dict[str, str] = dataclasses.field(default_factory=dict)
@@ -57,6 +70,14 @@ class RouterArgs:
     request_id_headers: Optional[List[str]] = None
     # Request timeout in seconds
     request_timeout_secs: int = 1800
+    # Timeout in seconds for short upstream calls (health, load, server/model info)
+    proxy_timeout_secs: int = 10
+    # Timeout in seconds for upstream generation requests (defaults to request_timeout_secs)
+    generation_timeout_secs: Optional[int] = None
+    # Cap on the generation timeout clients set with X-Router-Timeout-Secs (None = header ignored)
+    max_request_timeout_secs: Optional[int] = None
+    # Seconds without response headers before a request is logged and counted as slow (None = disabled)
+    slow_request_threshold_secs: Optional[int] = None
     # Max concurrent requests for rate limiting
     max_concurrent_requests: int = 32768
     # Queue size for pending requests when max concurrent limit reached
@@ -65,6 +86,48 @@ class RouterArgs:
     queue_timeout_secs: int = 60
     # Token bucket refill rate (tokens per second). If not set, defaults to max_concurrent_requests
     rate_limit_tokens_per_second: Optional[int] = None
+    # Per-model concurrency limits; models without an entry share max_concurrent_requests
+    model_concurrency_limits: Dict[str, int] = dataclasses.field(default_factory=dict)
+    # Adjust the global concurrency limit from observed latencies, up to max_concurrent_requests
+    adaptive_concurrency: bool = False
+    adaptive_concurrency_initial_limit: int = 32
+    adaptive_concurrency_min_limit: int = 4
+    # Load balancing policies keyed by model ID; models without an entry use policy
+    model_policies: Dict[str, str] = dataclasses.field(default_factory=dict)
+    # Order in which queued requests are served: fifo, model or client
+    queue_fairness: str = "fifo"
+    # Dequeue weights for fair queuing keyed by model or client ID; missing keys get 1
+    queue_weights: Dict[str, int] = dataclasses.field(default_factory=dict)
+    # Seconds of waiting that raise a queued request by one priority level
+    queue_priority_aging_secs: int = 10
+    # Model names clients may request, mapped to the model ID the workers serve
+    model_aliases: Dict[str, str] = dataclasses.field(default_factory=dict)
+    # Models tried in order when a model has no available workers
+    model_fallbacks: Dict[str, List[str]] = dataclasses.field(default_factory=dict)
+    # Requests for a model split by weight across worker pools serving other model IDs
+    model_traffic_splits: Dict[str, List[Tuple[str, int]]] = dataclasses.field(
+        default_factory=dict
+    )
+    # List alias names in /v1/models alongside the served models
+    advertise_model_aliases: bool = False
+    # Rewrite the model of every streamed chunk to the model the client requested
+    rewrite_stream_model: bool = False
+    # Route requests naming a model no worker serves by that model and fail them with 404
+    strict_model_routing: bool = False
+    # Maximum concurrent streaming responses; further streams get 503 (None = unlimited)
+    max_concurrent_streams: Optional[int] = None
+    # Chunks buffered per streaming response before reading from the worker pauses
+    stream_buffer_size: int = 64
+    # Seconds without a worker chunk before an SSE keep-alive comment is sent (None = disabled)
+    stream_keepalive_secs: Optional[int] = None
+    # Seconds to wait on shutdown for queued and in-flight requests to finish before exiting
+    shutdown_drain_timeout_secs: int = 30
+    # Requests of a /v1/batches job dispatched to workers at the same time
+    batch_max_concurrency: int = 8
+    # Compress responses for clients that send Accept-Encoding; streams are left uncompressed
+    enable_response_compression: bool = False
+    # Ask workers for gzip or br encoded responses and decompress them in the router
+    enable_upstream_compression: bool = False
     # CORS allowed origins
     cors_allowed_origins: List[str] = dataclasses.field(default_factory=list)
     # Retry configuration
@@ -73,6 +136,12 @@ class RouterArgs:
     retry_max_backoff_ms: int = 30_000
     retry_backoff_multiplier: float = 1.5
     retry_jitter_factor: float = 0.2
+    # Randomize each backoff over [0, delay] instead of applying retry_jitter_factor
+    retry_full_jitter: bool = False
+    # Maximum ratio of retries to requests over a rolling window; None disables the budget
+    retry_max_fraction: Optional[float] = None
+    # Failures retried on another worker: "all" or "connection_errors"
+    retry_on: str = "all"
     disable_retries: bool = False
     # Health check configuration
     health_failure_threshold: int = 3
@@ -80,12 +149,63 @@ class RouterArgs:
     health_check_timeout_secs: int = 5
     health_check_interval_secs: int = 60
     health_check_endpoint: str = "/health"
+    health_check_method: str = "get"
+    # Substring, or $.path.to.field=value for a JSON field, the health body must match
+    health_check_expected_body: Optional[str] = None
+    # Also require a one-token completion to succeed in runtime health checks
+    health_check_generation_probe: bool = False
+    # Healthy workers a model needs before the router serves it
+    min_healthy_workers: int = 1
     # Circuit breaker configuration
     cb_failure_threshold: int = 10
     cb_success_threshold: int = 3
     cb_timeout_duration_secs: int = 60
     cb_window_duration_secs: int = 120
+    cb_half_open_max_concurrent: int = 1
     disable_circuit_breaker: bool = False
+    # Request hedging (None = disabled)
+    hedge_delay_ms: Optional[int] = None
+    # Shadow workers receiving a copy of non-streaming requests (empty = disabled)
+    mirror_worker_urls: List[str] = dataclasses.field(default_factory=list)
+    mirror_fraction: float = 1.0
+    # Per-worker request rate limit (None = disabled)
+    worker_rate_limit_rps: Optional[int] = None
+    worker_rate_limit_burst: Optional[int] = None
+    # Warmup completions sent to workers added at runtime (None = disabled)
+    worker_warmup_requests: Optional[int] = None
+    worker_warmup_prompt: str = "Hello"
+    # Model version tracking across workers (None = disabled)
+    model_version_check_interval_secs: Optional[int] = None
+    model_version_mismatch: str = "warn"
+    # Error rate outlier ejection (None = disabled)
+    outlier_detection_interval_secs: Optional[int] = None
+    outlier_ejection_secs: int = 30
+    outlier_max_ejection_percent: int = 10
+    # Response cache for deterministic completions (None = disabled)
+    response_cache_size: Optional[int] = None
+    response_cache_ttl_secs: int = 300
+    # Replay of responses to retried Idempotency-Key requests (None = disabled)
+    idempotency_cache_size: Optional[int] = None
+    idempotency_ttl_secs: int = 86400
+    # Client headers forwarded to workers (empty = all not blocked)
+    forward_headers: List[str] = dataclasses.field(default_factory=list)
+    # Client headers never forwarded, in addition to host and hop-by-hop headers
+    block_headers: List[str] = dataclasses.field(default_factory=list)
+    # Routes whose bodies are logged at trace level for debugging (empty = disabled)
+    log_bodies_routes: List[str] = dataclasses.field(default_factory=list)
+    # Fields redacted from logged bodies (empty = prompt and completion text fields)
+    log_bodies_redact_fields: List[str] = dataclasses.field(default_factory=list)
+    allow_worker_pinning: bool = False
+    # Add X-Router-Worker/-Policy/-Retries headers to responses (reveals worker addresses)
+    expose_routing_headers: bool = False
+    # Worker authorization: passthrough, inject (uses api_key), or none. None picks inject when api_key is set
+    upstream_auth: Optional[str] = None
+    # Client certificate, key and CA bundle for TLS connections to workers
+    upstream_tls_cert: Optional[str] = None
+    upstream_tls_key: Optional[str] = None
+    upstream_tls_ca: Optional[str] = None
+    # Pair prefill and decode workers in the same zone when possible (vLLM PD mode)
+    pd_zone_affinity: bool = False
     # Tokenizer configuration
     model_path: Optional[str] = None
     tokenizer_path: Optional[str] = None
@@ -231,6 +351,60 @@ class RouterArgs:
             default=RouterArgs.max_payload_size,
             help="Maximum payload size in bytes",
         )
+        parser.add_argument(
+            f"--{prefix}max-payload-size-generate",
+            type=int,
+            default=None,
+            help="Maximum payload size in bytes for /generate, overriding --max-payload-size",
+        )
+        parser.add_argument(
+            f"--{prefix}max-payload-size-chat",
+            type=int,
+            default=None,
+            help="Maximum payload size in bytes for /v1/chat/completions, overriding --max-payload-size",
+        )
+        parser.add_argument(
+            f"--{prefix}max-payload-size-completions",
+            type=int,
+            default=None,
+            help="Maximum payload size in bytes for /v1/completions, overriding --max-payload-size",
+        )
+        parser.add_argument(
+            f"--{prefix}max-payload-size-embeddings",
+            type=int,
+            default=None,
+            help="Maximum payload size in bytes for /v1/embeddings, overriding --max-payload-size",
+        )
+        parser.add_argument(
+            f"--{prefix}max-payload-size-rerank",
+            type=int,
+            default=None,
+            help="Maximum payload size in bytes for /rerank and /v1/rerank, overriding --max-payload-size",
+        )
+        parser.add_argument(
+            f"--{prefix}max-payload-size-responses",
+            type=int,
+            default=None,
+            help="Maximum payload size in bytes for /v1/responses, overriding --max-payload-size",
+        )
+        parser.add_argument(
+            f"--{prefix}max-prompt-chars",
+            type=int,
+            default=RouterArgs.max_prompt_chars,
+            help="Reject generation requests whose prompt exceeds this many characters with 400 (unlimited when unset)",
+        )
+        parser.add_argument(
+            f"--{prefix}default-max-tokens",
+            type=int,
+            default=RouterArgs.default_max_tokens,
+            help="Set max_tokens to this on chat, completion and responses requests that omit it",
+        )
+        parser.add_argument(
+            f"--{prefix}max-tokens-limit",
+            type=int,
+            default=RouterArgs.max_tokens_limit,
+            help="Clamp max_tokens of chat, completion and responses requests to this; requests that omit it get this limit",
+        )
         parser.add_argument(
             f"--{prefix}intra-node-data-parallel-size",
             type=int,
@@ -261,6 +435,11 @@ class RouterArgs:
             choices=["debug", "info", "warning", "error", "critical"],
             help="Set the logging level. If not specified, defaults to INFO.",
         )
+        parser.add_argument(
+            f"--{prefix}log-json",
+            action="store_true",
+            help="Emit logs as JSON, including structured access logs on the router.access target",
+        )
         parser.add_argument(
             f"--{prefix}service-discovery",
             action="store_true",
@@ -323,6 +502,30 @@ class RouterArgs:
             default=RouterArgs.request_timeout_secs,
             help="Request timeout in seconds",
         )
+        parser.add_argument(
+            f"--{prefix}proxy-timeout-secs",
+            type=int,
+            default=RouterArgs.proxy_timeout_secs,
+            help="Timeout in seconds for short upstream calls (health, load, server and model info)",
+        )
+        parser.add_argument(
+            f"--{prefix}generation-timeout-secs",
+            type=int,
+            default=RouterArgs.generation_timeout_secs,
+            help="Timeout in seconds for upstream generation requests. Defaults to --request-timeout-secs",
+        )
+        parser.add_argument(
+            f"--{prefix}max-request-timeout-secs",
+            type=int,
+            default=RouterArgs.max_request_timeout_secs,
+            help="Honor an X-Router-Timeout-Secs request header setting the generation timeout, clamped to this many seconds (header ignored when unset)",
+        )
+        parser.add_argument(
+            f"--{prefix}slow-request-threshold-secs",
+            type=int,
+            default=RouterArgs.slow_request_threshold_secs,
+            help="Warn and count a slow request when a worker sends no response headers within this many seconds; the request is not aborted (disabled when unset)",
+        )
         # Retry configuration
         parser.add_argument(
             f"--{prefix}retry-max-retries",
@@ -349,6 +552,24 @@ class RouterArgs:
             type=float,
             default=RouterArgs.retry_jitter_factor,
         )
+        parser.add_argument(
+            f"--{prefix}retry-full-jitter",
+            action="store_true",
+            help="Randomize each retry backoff over [0, computed delay] instead of applying the jitter factor",
+        )
+        parser.add_argument(
+            f"--{prefix}retry-max-fraction",
+            type=float,
+            default=RouterArgs.retry_max_fraction,
+            help="Maximum ratio of retries to requests over a rolling window (e.g. 0.2). Unset disables the retry budget",
+        )
+        parser.add_argument(
+            f"--{prefix}retry-on",
+            type=str,
+            default=RouterArgs.retry_on,
+            choices=["all", "connection_errors"],
+            help="Failures retried on another worker: all (connection errors and retryable statuses) or connection_errors only",
+        )
         parser.add_argument(
             f"--{prefix}disable-retries",
             action="store_true",
@@ -375,11 +596,182 @@ class RouterArgs:
             type=int,
             default=RouterArgs.cb_window_duration_secs,
         )
+        parser.add_argument(
+            f"--{prefix}cb-half-open-max-concurrent",
+            type=int,
+            default=RouterArgs.cb_half_open_max_concurrent,
+        )
         parser.add_argument(
             f"--{prefix}disable-circuit-breaker",
             action="store_true",
             help="Disable circuit breaker (equivalent to setting cb_failure_threshold to u32::MAX)",
         )
+        parser.add_argument(
+            f"--{prefix}hedge-delay-ms",
+            type=int,
+            default=RouterArgs.hedge_delay_ms,
+            help="Send a backup request to another worker if a non-streaming request takes longer than this (milliseconds)",
+        )
+        parser.add_argument(
+            f"--{prefix}mirror-worker-urls",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Also send non-streaming requests to one of these shadow workers and discard their responses. Disabled when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}mirror-fraction",
+            type=float,
+            default=RouterArgs.mirror_fraction,
+            help="Share of requests mirrored to the shadow workers (0.0-1.0]",
+        )
+        parser.add_argument(
+            f"--{prefix}worker-rate-limit-rps",
+            type=int,
+            default=RouterArgs.worker_rate_limit_rps,
+            help="Maximum requests per second sent to any single worker; over-limit workers are skipped during selection. Disabled when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}worker-rate-limit-burst",
+            type=int,
+            default=RouterArgs.worker_rate_limit_burst,
+            help="Requests a single worker may receive in a burst under --worker-rate-limit-rps (defaults to the rate)",
+        )
+        parser.add_argument(
+            f"--{prefix}worker-warmup-requests",
+            type=int,
+            default=RouterArgs.worker_warmup_requests,
+            help="Send this many rounds of a small completion and chat completion to a worker added at runtime before routing to it. Disabled when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}worker-warmup-prompt",
+            type=str,
+            default=RouterArgs.worker_warmup_prompt,
+            help="Prompt of the warmup requests sent under --worker-warmup-requests",
+        )
+        parser.add_argument(
+            f"--{prefix}model-version-check-interval-secs",
+            type=int,
+            default=RouterArgs.model_version_check_interval_secs,
+            help="Check the model version each worker reports on /get_model_info at this interval. Disabled when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}model-version-mismatch",
+            type=str,
+            default=RouterArgs.model_version_mismatch,
+            choices=["warn", "split"],
+            help="What to do when workers of one model report different versions: warn, or only route to the version most of the model's workers serve. Not applied to vLLM PD service discovery instances",
+        )
+        parser.add_argument(
+            f"--{prefix}outlier-detection-interval-secs",
+            type=int,
+            default=RouterArgs.outlier_detection_interval_secs,
+            help="Eject workers whose error rate is an outlier among their model's workers, analyzing every this many seconds. Disabled when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}outlier-ejection-secs",
+            type=int,
+            default=RouterArgs.outlier_ejection_secs,
+            help="Base ejection time in seconds of an outlier worker, multiplied by its consecutive ejections",
+        )
+        parser.add_argument(
+            f"--{prefix}outlier-max-ejection-percent",
+            type=int,
+            default=RouterArgs.outlier_max_ejection_percent,
+            help="Most workers of a model ejected as outliers at once, in percent",
+        )
+        parser.add_argument(
+            f"--{prefix}response-cache-size",
+            type=int,
+            default=RouterArgs.response_cache_size,
+            help="Cache up to this many responses to deterministic (temperature 0, n=1) non-streaming requests. Disabled when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}response-cache-ttl-secs",
+            type=int,
+            default=RouterArgs.response_cache_ttl_secs,
+            help="Time in seconds a cached response stays valid",
+        )
+        parser.add_argument(
+            f"--{prefix}idempotency-cache-size",
+            type=int,
+            default=RouterArgs.idempotency_cache_size,
+            help="Remember the responses to up to this many Idempotency-Key values on POST /v1/responses and replay them to retries. Disabled when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}idempotency-ttl-secs",
+            type=int,
+            default=RouterArgs.idempotency_ttl_secs,
+            help="Time in seconds an Idempotency-Key is remembered",
+        )
+        parser.add_argument(
+            f"--{prefix}forward-headers",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Forward only these client headers to workers (case-insensitive). All headers not blocked are forwarded when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}block-headers",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Additional client headers never forwarded to workers. Host and hop-by-hop headers are always blocked",
+        )
+        parser.add_argument(
+            f"--{prefix}log-bodies-routes",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Log request and response bodies of these routes at trace level, for debugging. Regular HTTP mode only. Disabled when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}log-bodies-redact-fields",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Fields redacted from logged bodies, e.g. messages[].content. Prompt and completion text fields when unset",
+        )
+        parser.add_argument(
+            f"--{prefix}allow-worker-pinning",
+            action="store_true",
+            help="Honor the X-Router-Target-Worker header to route a request to a specific worker (for debugging and canaries)",
+        )
+        parser.add_argument(
+            f"--{prefix}expose-routing-headers",
+            action="store_true",
+            help="Add X-Router-Worker, X-Router-Policy and X-Router-Retries headers to responses (reveals worker addresses to clients)",
+        )
+        parser.add_argument(
+            f"--{prefix}upstream-auth",
+            type=str,
+            default=None,
+            choices=["passthrough", "inject", "none"],
+            help="How requests to workers are authenticated: forward the client's Authorization header (passthrough), send --api-key as a bearer token (inject), or send none. Defaults to inject when --api-key is set (or OPENAI_API_KEY in vLLM PD mode), else passthrough",
+        )
+        parser.add_argument(
+            f"--{prefix}upstream-tls-cert",
+            type=str,
+            default=None,
+            help="PEM client certificate presented to workers for mutual TLS (requires --upstream-tls-key)",
+        )
+        parser.add_argument(
+            f"--{prefix}upstream-tls-key",
+            type=str,
+            default=None,
+            help="PEM PKCS#8 private key for --upstream-tls-cert",
+        )
+        parser.add_argument(
+            f"--{prefix}upstream-tls-ca",
+            type=str,
+            default=None,
+            help="PEM CA bundle trusted for worker certificates, in addition to the system roots",
+        )
+        parser.add_argument(
+            f"--{prefix}pd-zone-affinity",
+            action="store_true",
+            help="In vLLM PD mode, prefer decode workers in the same zone as the selected prefill worker, falling back to other zones",
+        )
         # Health check configuration
         parser.add_argument(
             f"--{prefix}health-failure-threshold",
@@ -411,6 +803,30 @@ class RouterArgs:
             default=RouterArgs.health_check_endpoint,
             help="Health check endpoint path",
         )
+        parser.add_argument(
+            f"--{prefix}health-check-method",
+            type=str,
+            default=RouterArgs.health_check_method,
+            choices=["get", "post"],
+            help="HTTP method for health check requests",
+        )
+        parser.add_argument(
+            f"--{prefix}health-check-expected-body",
+            type=str,
+            default=RouterArgs.health_check_expected_body,
+            help="Body a health check response must match: a substring, or $.path.to.field=value for a JSON field",
+        )
+        parser.add_argument(
+            f"--{prefix}health-check-generation-probe",
+            action="store_true",
+            help="Also require a one-token completion to succeed in runtime health checks (uses GPU time)",
+        )
+        parser.add_argument(
+            f"--{prefix}min-healthy-workers",
+            type=int,
+            default=RouterArgs.min_healthy_workers,
+            help="Healthy workers a model needs before the router serves it; below this, readiness fails and requests get 503",
+        )
         parser.add_argument(
             f"--{prefix}max-concurrent-requests",
             type=int,
@@ -435,6 +851,133 @@ class RouterArgs:
             default=RouterArgs.rate_limit_tokens_per_second,
             help="Token bucket refill rate (tokens per second). If not set, defaults to max_concurrent_requests",
         )
+        parser.add_argument(
+            f"--{prefix}model-concurrency-limits",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Per-model concurrency limits (format: model1=limit1 model2=limit2). Models without an entry share --max-concurrent-requests",
+        )
+        parser.add_argument(
+            f"--{prefix}adaptive-concurrency",
+            action="store_true",
+            help="Adjust the global concurrency limit from observed latencies (gradient algorithm), up to --max-concurrent-requests",
+        )
+        parser.add_argument(
+            f"--{prefix}adaptive-concurrency-initial-limit",
+            type=int,
+            default=RouterArgs.adaptive_concurrency_initial_limit,
+            help="Concurrency limit adaptive concurrency starts from",
+        )
+        parser.add_argument(
+            f"--{prefix}adaptive-concurrency-min-limit",
+            type=int,
+            default=RouterArgs.adaptive_concurrency_min_limit,
+            help="Lowest concurrency limit adaptive concurrency may settle on",
+        )
+        parser.add_argument(
+            f"--{prefix}model-policies",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Per-model load balancing policies (format: model1=policy1 model2=policy2). Models without an entry use --policy",
+        )
+        parser.add_argument(
+            f"--{prefix}queue-fairness",
+            type=str,
+            default=RouterArgs.queue_fairness,
+            choices=["fifo", "model", "client", "priority"],
+            help="Order in which queued requests are served: fifo, weighted round-robin by request model or X-Client-Id header, or by X-Request-Priority header",
+        )
+        parser.add_argument(
+            f"--{prefix}queue-priority-aging-secs",
+            type=int,
+            default=RouterArgs.queue_priority_aging_secs,
+            help="Seconds of waiting that raise a queued request by one priority level with priority queuing",
+        )
+        parser.add_argument(
+            f"--{prefix}queue-weights",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Dequeue weights for fair queuing (format: key1=weight1 key2=weight2). Keys without an entry get weight 1",
+        )
+        parser.add_argument(
+            f"--{prefix}model-aliases",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Model aliases resolved before routing (format: alias1=model1 alias2=model2)",
+        )
+        parser.add_argument(
+            f"--{prefix}model-fallbacks",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Fallback models tried in order when a model has no available workers (format: model1=fallback1,fallback2)",
+        )
+        parser.add_argument(
+            f"--{prefix}model-traffic-splits",
+            type=str,
+            nargs="*",
+            default=[],
+            help="Split a model's requests by weight across pools of workers serving other model IDs, for canary rollouts (format: model1=pool1:95,pool2:5)",
+        )
+        parser.add_argument(
+            f"--{prefix}advertise-model-aliases",
+            action="store_true",
+            help="List model aliases in /v1/models alongside the served models",
+        )
+        parser.add_argument(
+            f"--{prefix}rewrite-stream-model",
+            action="store_true",
+            help="Rewrite the model of every streamed chunk to the model the client requested, e.g. an alias (parses each chunk)",
+        )
+        parser.add_argument(
+            f"--{prefix}strict-model-routing",
+            action="store_true",
+            help="Route requests naming a model no worker serves by that model and fail them with 404, instead of spreading them over all workers",
+        )
+        parser.add_argument(
+            f"--{prefix}max-concurrent-streams",
+            type=int,
+            default=RouterArgs.max_concurrent_streams,
+            help="Maximum concurrent streaming responses; further streaming requests get 503 (unlimited when unset)",
+        )
+        parser.add_argument(
+            f"--{prefix}stream-buffer-size",
+            type=int,
+            default=RouterArgs.stream_buffer_size,
+            help="Chunks buffered per streaming response before reading from the worker pauses",
+        )
+        parser.add_argument(
+            f"--{prefix}stream-keepalive-secs",
+            type=int,
+            default=RouterArgs.stream_keepalive_secs,
+            help="Send an SSE keep-alive comment after this many seconds without a chunk from the worker (disabled when unset)",
+        )
+        parser.add_argument(
+            f"--{prefix}shutdown-drain-timeout-secs",
+            type=int,
+            default=RouterArgs.shutdown_drain_timeout_secs,
+            help="Seconds to wait on shutdown for queued and in-flight requests to finish before exiting",
+        )
+        parser.add_argument(
+            f"--{prefix}batch-max-concurrency",
+            type=int,
+            default=RouterArgs.batch_max_concurrency,
+            help="Requests of a /v1/batches job dispatched to workers at the same time",
+        )
+        parser.add_argument(
+            f"--{prefix}enable-response-compression",
+            action="store_true",
+            help="Compress responses for clients that send Accept-Encoding (gzip or br); streams are left uncompressed",
+        )
+        parser.add_argument(
+            f"--{prefix}enable-upstream-compression",
+            action="store_true",
+            help="Ask workers for gzip or br encoded responses and decompress them in the router",
+        )
         parser.add_argument(
             f"--{prefix}cors-allowed-origins",
             type=str,
@@ -494,6 +1037,39 @@ class RouterArgs:
         args_dict["decode_selector"] = cls._parse_selector(
             cli_args_dict.get(f"{prefix}decode_selector", None)
         )
+        args_dict["model_concurrency_limits"] = {
+            model: int(limit)
+            for model, limit in cls._parse_selector(
+                cli_args_dict.get(f"{prefix}model_concurrency_limits", None)
+            ).items()
+        }
+        args_dict["model_policies"] = cls._parse_selector(
+            cli_args_dict.get(f"{prefix}model_policies", None)
+        )
+        args_dict["queue_weights"] = {
+            key: int(weight)
+            for key, weight in cls._parse_selector(
+                cli_args_dict.get(f"{prefix}queue_weights", None)
+            ).items()
+        }
+        args_dict["model_aliases"] = cls._parse_selector(
+            cli_args_dict.get(f"{prefix}model_aliases", None)
+        )
+        args_dict["model_fallbacks"] = {
+            model: chain.split(",")
+            for model, chain in cls._parse_selector(
+                cli_args_dict.get(f"{prefix}model_fallbacks", None)
+            ).items()
+        }
+        args_dict["model_traffic_splits"] = {
+            model: [
+                (pool, int(weight))
+                for pool, weight in (p.split(":", 1) for p in pools.split(","))
+            ]
+            for model, pools in cls._parse_selector(
+                cli_args_dict.get(f"{prefix}model_traffic_splits", None)
+            ).items()
+        }
 
         # Mooncake-specific annotation
         args_dict["bootstrap_port_annotation"] = "vllm.ai/bootstrap-port"
@@ -22,6 +22,13 @@ pub enum ConfigError {
 
     #[error("Missing required field: {field}")]
     MissingRequired { field: String },
+
+    #[error("{} configuration problems:{}", .errors.len(), list_errors(.errors))]
+    Multiple { errors: Vec<ConfigError> },
+}
+
+fn list_errors(errors: &[ConfigError]) -> String {
+    errors.iter().map(|e| format!("\n  - {}", e)).collect()
 }
 
 /// Result type for configuration operations
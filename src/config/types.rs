@@ -19,8 +19,39 @@ pub struct RouterConfig {
     pub port: u16,
     /// Maximum payload size in bytes
     pub max_payload_size: usize,
+    /// Per-route overrides of `max_payload_size` for the inference endpoints
+    #[serde(default)]
+    pub payload_limits: PayloadLimitsConfig,
+    /// Reject generation requests whose prompt is longer than this many
+    /// characters with 400 (None = unlimited)
+    #[serde(default)]
+    pub max_prompt_chars: Option<usize>,
+    /// `max_tokens` set on chat, completion and responses requests that do
+    /// not limit their output (None = leave unset)
+    #[serde(default)]
+    pub default_max_tokens: Option<u32>,
+    /// Largest `max_tokens` sent to workers; larger client values are
+    /// clamped down, and requests without one get this limit (None = unlimited)
+    #[serde(default)]
+    pub max_tokens_limit: Option<u32>,
     /// Request timeout in seconds
     pub request_timeout_secs: u64,
+    /// Timeout in seconds for short upstream calls (health, load, server and model info)
+    #[serde(default = "default_proxy_timeout_secs")]
+    pub proxy_timeout_secs: u64,
+    /// Timeout in seconds for upstream generation requests. Falls back to
+    /// `request_timeout_secs` when unset
+    #[serde(default)]
+    pub generation_timeout_secs: Option<u64>,
+    /// Largest generation timeout in seconds a client may set with the
+    /// `X-Router-Timeout-Secs` header; larger values are clamped to it
+    /// (None = header ignored)
+    #[serde(default)]
+    pub max_request_timeout_secs: Option<u64>,
+    /// Warn when a worker has not sent response headers after this many seconds;
+    /// the request keeps waiting up to the generation timeout (None = disabled)
+    #[serde(default)]
+    pub slow_request_threshold_secs: Option<u64>,
     /// Worker startup timeout in seconds
     pub worker_startup_timeout_secs: u64,
     /// Worker health check interval in seconds
@@ -35,6 +66,10 @@ pub struct RouterConfig {
     pub api_key_validation_urls: Vec<String>,
     /// Service discovery configuration (optional)
     pub discovery: Option<DiscoveryConfig>,
+    /// Seconds without any registration after which vLLM ZMQ service
+    /// discovery is considered stale and readiness fails
+    #[serde(default = "default_discovery_stale_secs")]
+    pub discovery_stale_secs: u64,
     /// Metrics configuration (optional)
     pub metrics: Option<MetricsConfig>,
     /// Log directory (None = stdout only)
@@ -51,12 +86,122 @@ pub struct RouterConfig {
     pub queue_timeout_secs: u64,
     /// Token bucket refill rate (tokens per second). If not set, defaults to max_concurrent_requests
     pub rate_limit_tokens_per_second: Option<usize>,
+    /// Per-model concurrency limits keyed by model ID. Models without an entry share the global limit
+    #[serde(default)]
+    pub model_concurrency_limits: HashMap<String, usize>,
+    /// Adjust the global concurrency limit from observed latencies, up to
+    /// `max_concurrent_requests` (None = fixed limit)
+    #[serde(default)]
+    pub adaptive_concurrency: Option<AdaptiveConcurrencyConfig>,
+    /// Load balancing policies keyed by model ID, overriding `policy` for those models
+    #[serde(default)]
+    pub model_policies: HashMap<String, PolicyConfig>,
+    /// Order in which queued requests are granted a concurrency slot
+    #[serde(default)]
+    pub queue_fairness: QueueFairness,
+    /// Dequeue weights keyed by model ID or client ID for fair queuing. Keys without an entry get weight 1
+    #[serde(default)]
+    pub queue_weights: HashMap<String, u32>,
+    /// Seconds of waiting that raise a queued request by one priority level with priority queuing
+    #[serde(default = "default_queue_priority_aging_secs")]
+    pub queue_priority_aging_secs: u64,
+    /// Model names clients may request, mapped to the model ID the workers serve
+    #[serde(default)]
+    pub model_aliases: HashMap<String, String>,
+    /// Models tried in order when a model has no available workers
+    #[serde(default)]
+    pub model_fallbacks: HashMap<String, Vec<String>>,
+    /// Requests for a model split by weight across pools of workers serving other model IDs
+    #[serde(default)]
+    pub model_traffic_splits: HashMap<String, Vec<TrafficSplitPool>>,
+    /// List alias names in /v1/models alongside the served models
+    #[serde(default)]
+    pub advertise_model_aliases: bool,
+    /// Rewrite the `model` of every streamed chunk to the model the client
+    /// requested. Costs a JSON parse per chunk
+    #[serde(default)]
+    pub rewrite_stream_model: bool,
+    /// Route requests whose model no worker is registered under by their
+    /// `model` field anyway, failing with 404 instead of spreading them over
+    /// all workers. Needs every worker to report its model ID
+    #[serde(default)]
+    pub strict_model_routing: bool,
+    /// Maximum concurrent streaming responses; further streaming requests get 503 (None = unlimited)
+    #[serde(default)]
+    pub max_concurrent_streams: Option<usize>,
+    /// Chunks buffered per streaming response before reading from the worker pauses
+    #[serde(default = "default_stream_buffer_size")]
+    pub stream_buffer_size: usize,
+    /// Send an SSE comment when a stream has been idle this long, so proxies keep it open (None = disabled)
+    #[serde(default)]
+    pub stream_keepalive_secs: Option<u64>,
+    /// Seconds to wait on shutdown for queued and in-flight requests to finish before exiting
+    #[serde(default = "default_shutdown_drain_timeout_secs")]
+    pub shutdown_drain_timeout_secs: u64,
+    /// Requests of a /v1/batches job dispatched to workers at the same time
+    #[serde(default = "default_batch_max_concurrency")]
+    pub batch_max_concurrency: usize,
+    /// Compress responses for clients that send Accept-Encoding (gzip or br); streams are left uncompressed
+    #[serde(default)]
+    pub enable_response_compression: bool,
+    /// Ask workers for gzip or br encoded responses and decompress them in the router
+    #[serde(default)]
+    pub enable_upstream_compression: bool,
     /// CORS allowed origins
     pub cors_allowed_origins: Vec<String>,
     /// Retry configuration
     pub retry: RetryConfig,
     /// Circuit breaker configuration
     pub circuit_breaker: CircuitBreakerConfig,
+    /// Request hedging configuration (None = disabled)
+    #[serde(default)]
+    pub hedging: Option<HedgingConfig>,
+    /// Mirror a share of requests to shadow workers (None = disabled)
+    #[serde(default)]
+    pub traffic_mirror: Option<TrafficMirrorConfig>,
+    /// Per-worker request rate limit (None = only the global limit applies)
+    #[serde(default)]
+    pub worker_rate_limit: Option<WorkerRateLimitConfig>,
+    /// Warm up workers added at runtime before routing to them (None = disabled)
+    #[serde(default)]
+    pub worker_warmup: Option<WorkerWarmupConfig>,
+    /// Track the model version each worker serves (None = disabled)
+    #[serde(default)]
+    pub model_version_check: Option<ModelVersionCheckConfig>,
+    /// Eject workers whose error rate is an outlier among their model's workers (None = disabled)
+    #[serde(default)]
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+    /// Response caching for deterministic completions (None = disabled)
+    #[serde(default)]
+    pub response_cache: Option<ResponseCacheConfig>,
+    /// Replay responses to retried requests carrying an Idempotency-Key (None = disabled)
+    #[serde(default)]
+    pub idempotency: Option<IdempotencyConfig>,
+    /// Which client request headers are forwarded to workers
+    #[serde(default)]
+    pub header_policy: HeaderPolicyConfig,
+    /// Log request and response bodies of some routes for debugging (None = disabled)
+    #[serde(default)]
+    pub body_logging: Option<BodyLoggingConfig>,
+    /// Honor the X-Router-Target-Worker header to route a request to a specific worker
+    #[serde(default)]
+    pub allow_worker_pinning: bool,
+    /// Add X-Router-Worker, X-Router-Policy and X-Router-Retries headers to
+    /// routed responses. Off by default as it reveals worker addresses
+    #[serde(default)]
+    pub expose_routing_headers: bool,
+    /// How requests to workers are authenticated (None = inject `api_key` when set, else passthrough)
+    #[serde(default)]
+    pub upstream_auth: Option<UpstreamAuthConfig>,
+    /// Client certificate and CA bundle for TLS connections to workers (None = system defaults)
+    #[serde(default)]
+    pub upstream_tls: Option<UpstreamTlsConfig>,
+    /// Pair prefill and decode workers in the same `zone` label when possible (vLLM PD mode)
+    #[serde(default)]
+    pub pd_zone_affinity: bool,
+    /// Healthy workers a model needs before the router serves it and reports ready (regular routing)
+    #[serde(default = "default_min_healthy_workers")]
+    pub min_healthy_workers: usize,
     /// Disable retries (overrides retry.max_retries to 1 when true)
     #[serde(default)]
     pub disable_retries: bool,
@@ -75,6 +220,9 @@ pub struct RouterConfig {
     /// History backend configuration (memory or none, default: memory)
     #[serde(default = "default_history_backend")]
     pub history_backend: HistoryBackend,
+    /// Size and retention limits for the memory history backend
+    #[serde(default)]
+    pub memory_history: MemoryHistoryConfig,
     /// Enable profiling calls to vLLM workers
     #[serde(default)]
     pub enable_profiling: bool,
@@ -87,6 +235,26 @@ fn default_profile_timeout_secs() -> u64 {
     10
 }
 
+fn default_queue_priority_aging_secs() -> u64 {
+    10
+}
+
+fn default_discovery_stale_secs() -> u64 {
+    30
+}
+
+fn default_stream_buffer_size() -> usize {
+    64
+}
+
+fn default_shutdown_drain_timeout_secs() -> u64 {
+    30
+}
+
+fn default_batch_max_concurrency() -> usize {
+    8
+}
+
 fn default_history_backend() -> HistoryBackend {
     HistoryBackend::Memory
 }
@@ -95,6 +263,14 @@ fn default_intra_node_data_parallel_size() -> usize {
     1
 }
 
+fn default_min_healthy_workers() -> usize {
+    1
+}
+
+fn default_proxy_timeout_secs() -> u64 {
+    10
+}
+
 /// History backend configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -103,6 +279,71 @@ pub enum HistoryBackend {
     Memory,
     /// No history storage
     None,
+    /// Redis storage shared by all router replicas
+    Redis {
+        /// Connection URL (redis:// or rediss://)
+        url: String,
+    },
+}
+
+/// One pool of a model's traffic split
+///
+/// Used for canary rollouts: `llama` split into `llama-v1` at weight 95 and
+/// `llama-v2` at weight 5 sends about 5% of `llama` requests to the workers
+/// serving `llama-v2`, with the request's model rewritten to the pool's.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrafficSplitPool {
+    /// Model ID served by the pool's workers
+    pub model: String,
+    /// Share of the model's requests relative to the other pools
+    pub weight: u32,
+}
+
+/// Order in which requests waiting in the concurrency queue are served
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueFairness {
+    /// Single first-in, first-out queue (default)
+    #[default]
+    Fifo,
+    /// Weighted round-robin across the request body's `model` field
+    Model,
+    /// Weighted round-robin across the `X-Client-Id` request header
+    Client,
+    /// Highest `X-Request-Priority` first, with waiting requests aging upwards
+    Priority,
+}
+
+/// Limits for the in-memory response history
+///
+/// Without limits every stored response is kept for the lifetime of the
+/// router. When full, the least recently used response is evicted; expired
+/// responses are dropped by a background task and are never returned.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemoryHistoryConfig {
+    /// Maximum number of stored responses (None = unbounded)
+    #[serde(default)]
+    pub max_responses: Option<usize>,
+    /// Seconds a stored response is kept (None = forever)
+    #[serde(default)]
+    pub response_ttl_secs: Option<u64>,
+    /// Interval in seconds between sweeps for expired responses
+    #[serde(default = "default_history_eviction_interval_secs")]
+    pub eviction_interval_secs: u64,
+}
+
+fn default_history_eviction_interval_secs() -> u64 {
+    60
+}
+
+impl Default for MemoryHistoryConfig {
+    fn default() -> Self {
+        Self {
+            max_responses: None,
+            response_ttl_secs: None,
+            eviction_interval_secs: default_history_eviction_interval_secs(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -241,6 +482,10 @@ pub enum PolicyConfig {
         eviction_interval_secs: u64,
         /// Maximum cache tree size per tenant
         max_tree_size: usize,
+        /// How to pick a worker when the best prefix match is below
+        /// `cache_threshold`
+        #[serde(default)]
+        cold_start_policy: ColdStartPolicy,
     },
 
     #[serde(rename = "power_of_two")]
@@ -249,11 +494,118 @@ pub enum PolicyConfig {
         load_check_interval_secs: u64,
     },
 
+    #[serde(rename = "load_weighted_random")]
+    LoadWeightedRandom {
+        /// Interval for load monitoring (seconds)
+        load_check_interval_secs: u64,
+    },
+
+    #[serde(rename = "latency_aware")]
+    LatencyAware {
+        /// Weight kept by the previous average for each new latency sample (0-1)
+        #[serde(default = "default_latency_decay")]
+        decay: f64,
+    },
+
     #[serde(rename = "consistent_hash")]
     ConsistentHash {
         /// Number of virtual nodes per worker for better distribution
         virtual_nodes: u32,
+        /// Scale each worker's virtual nodes by this attribute; unset gives
+        /// every worker an equal share of the ring
+        #[serde(default)]
+        weight_source: Option<WeightSource>,
+        /// Cookie-based sticky sessions for clients that cannot set headers
+        #[serde(default)]
+        session_cookie: SessionCookieConfig,
+        /// Bounded loads: skip to the next worker on the ring when the hashed
+        /// one has more than this multiple of the average load (None = unbounded)
+        #[serde(default)]
+        overload_factor: Option<f64>,
+        /// Hash on the client IP (X-Forwarded-For / X-Real-IP) when the
+        /// request carries no session or user identifier
+        #[serde(default)]
+        hash_client_ip: bool,
     },
+
+    #[serde(rename = "weighted_round_robin")]
+    WeightedRoundRobin {
+        /// Worker attribute used as the selection weight
+        #[serde(default)]
+        weight_source: WeightSource,
+    },
+
+    #[serde(rename = "header_route")]
+    HeaderRoute {
+        /// Request header whose value selects the worker pool
+        header: String,
+        /// Worker label whose value identifies a pool
+        label: String,
+        /// Header value to the label value of the pool serving it
+        #[serde(default)]
+        routes: HashMap<String, String>,
+        /// Pool for requests without a routed header value (None = all workers)
+        #[serde(default)]
+        default_route: Option<String>,
+        /// Policy selecting a worker within the pool
+        sub_policy: Box<PolicyConfig>,
+    },
+}
+
+fn default_latency_decay() -> f64 {
+    crate::policies::DEFAULT_LATENCY_DECAY
+}
+
+/// Session cookie settings for consistent-hash routing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionCookieConfig {
+    /// Name of the cookie carrying the session identifier
+    #[serde(default = "default_session_cookie_name")]
+    pub name: String,
+    /// Set the cookie on responses to requests that carry no session identifier
+    #[serde(default)]
+    pub set_on_response: bool,
+    /// Secret used to sign minted cookies. When set, cookies without a valid
+    /// signature are ignored
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+impl Default for SessionCookieConfig {
+    fn default() -> Self {
+        Self {
+            name: default_session_cookie_name(),
+            set_on_response: false,
+            secret: None,
+        }
+    }
+}
+
+fn default_session_cookie_name() -> String {
+    "router_session".to_string()
+}
+
+/// Worker selection for cache-aware requests with no useful prefix match
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColdStartPolicy {
+    /// Rotate through the available workers
+    RoundRobin,
+    /// Pick the worker with the fewest in-flight requests, rotating between ties
+    #[default]
+    LeastLoad,
+}
+
+/// Worker attribute used to weight traffic in weighted round-robin and
+/// weighted consistent hashing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeightSource {
+    /// Use the worker's `priority` label (default 50)
+    #[default]
+    Priority,
+    /// Use the worker's `cost` label (default 1.0)
+    Cost,
 }
 
 impl PolicyConfig {
@@ -263,7 +615,11 @@ impl PolicyConfig {
             PolicyConfig::RoundRobin => "round_robin",
             PolicyConfig::CacheAware { .. } => "cache_aware",
             PolicyConfig::PowerOfTwo { .. } => "power_of_two",
+            PolicyConfig::LoadWeightedRandom { .. } => "load_weighted_random",
+            PolicyConfig::LatencyAware { .. } => "latency_aware",
             PolicyConfig::ConsistentHash { .. } => "consistent_hash",
+            PolicyConfig::WeightedRoundRobin { .. } => "weighted_round_robin",
+            PolicyConfig::HeaderRoute { .. } => "header_route",
         }
     }
 }
@@ -319,6 +675,17 @@ pub struct RetryConfig {
     /// Effective delay D' = D * (1 + U[-j, +j])
     #[serde(default = "default_retry_jitter_factor")]
     pub jitter_factor: f32,
+    /// Use full jitter instead: D' = U[0, D]. Spreads out retries of requests
+    /// that failed together; `jitter_factor` is ignored.
+    #[serde(default)]
+    pub full_jitter: bool,
+    /// Maximum ratio of retries to requests over a rolling window (e.g. 0.2).
+    /// Retries beyond the budget fail fast; unset disables the budget.
+    #[serde(default)]
+    pub max_retry_fraction: Option<f32>,
+    /// Which failures are retried by the regular router
+    #[serde(default)]
+    pub retry_on: RetryOn,
 }
 
 impl Default for RetryConfig {
@@ -329,14 +696,415 @@ impl Default for RetryConfig {
             max_backoff_ms: 30000,
             backoff_multiplier: 1.5,
             jitter_factor: 0.2,
+            full_jitter: false,
+            max_retry_fraction: None,
+            retry_on: RetryOn::All,
         }
     }
 }
 
+/// Failures that trigger a retry on another worker
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryOn {
+    /// Connection errors, timeouts and retryable statuses (408, 429, 500, 502, 503, 504)
+    #[default]
+    All,
+    /// Only requests that never got a response from the worker
+    ConnectionErrors,
+}
+
 fn default_retry_jitter_factor() -> f32 {
     0.2
 }
 
+/// Request hedging configuration
+///
+/// When a non-streaming request has not completed within `hedge_delay_ms`, a
+/// backup copy is sent to a different worker and the first response wins.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HedgingConfig {
+    /// Delay in milliseconds before sending the backup request
+    pub hedge_delay_ms: u64,
+}
+
+impl Default for HedgingConfig {
+    fn default() -> Self {
+        Self {
+            hedge_delay_ms: 200,
+        }
+    }
+}
+
+/// Shadow traffic to workers under test
+///
+/// A sampled `fraction` of non-streaming generation requests is also sent
+/// to one of `worker_urls`, which are not part of the routed fleet. Shadow
+/// responses are discarded and never reach the client; only their outcome
+/// and latency are recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrafficMirrorConfig {
+    /// Shadow workers; each mirrored request goes to one of them at random
+    pub worker_urls: Vec<String>,
+    /// Share of requests mirrored (0.0-1.0]
+    #[serde(default = "default_mirror_fraction")]
+    pub fraction: f64,
+    /// Timeout in seconds of a mirrored request
+    #[serde(default = "default_mirror_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_mirror_fraction() -> f64 {
+    1.0
+}
+
+fn default_mirror_timeout_secs() -> u64 {
+    60
+}
+
+/// Per-worker request rate limit
+///
+/// Each worker gets its own token bucket. A worker that is over its limit is
+/// skipped during selection and the policy picks among the remaining workers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerRateLimitConfig {
+    /// Sustained requests per second allowed to each worker
+    pub requests_per_second: usize,
+    /// Requests a worker may receive in a burst (defaults to `requests_per_second`)
+    #[serde(default)]
+    pub burst: Option<usize>,
+}
+
+/// Warmup for workers added at runtime
+///
+/// After a new worker passes its health check, it is registered as warming
+/// and sent `requests` rounds of a small completion and chat completion
+/// before it can be selected, so the first user requests do not pay for cold
+/// caches and compilation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerWarmupConfig {
+    /// Number of warmup rounds sent to each new worker
+    pub requests: usize,
+    /// Prompt of the warmup requests
+    #[serde(default = "default_warmup_prompt")]
+    pub prompt: String,
+}
+
+fn default_warmup_prompt() -> String {
+    "Hello".to_string()
+}
+
+/// Model version tracking for rolling deployments
+///
+/// Every `check_interval_secs` the router reads the version each worker
+/// reports on `/get_model_info`. Workers of one model reporting different
+/// versions are counted as version skew and handled per `on_mismatch`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVersionCheckConfig {
+    /// Interval in seconds between version checks
+    #[serde(default = "default_model_version_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// What to do when workers of one model serve different versions
+    #[serde(default)]
+    pub on_mismatch: VersionMismatchAction,
+}
+
+fn default_model_version_check_interval_secs() -> u64 {
+    60
+}
+
+impl Default for ModelVersionCheckConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_secs: default_model_version_check_interval_secs(),
+            on_mismatch: VersionMismatchAction::default(),
+        }
+    }
+}
+
+/// Outlier detection across the workers of a model
+///
+/// Every `interval_secs` the failure rate (5xx responses and connection
+/// errors or timeouts) of each worker over the past interval is compared
+/// with the other workers of its model. A worker failing `stdev_factor`
+/// standard deviations above the mean is ejected from selection for
+/// `base_ejection_secs` times the number of times it has been ejected,
+/// even while its health check passes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutlierDetectionConfig {
+    /// Interval in seconds between analyses, and the window they look at
+    pub interval_secs: u64,
+    /// Requests a worker needs within an interval to be analyzed
+    pub min_requests: u64,
+    /// Analyzed workers a model needs for a baseline; fewer skips the model
+    ///
+    /// With n workers no failure rate is more than sqrt(n - 1) standard
+    /// deviations above the mean, so `stdev_factor` must stay below that.
+    pub min_workers: usize,
+    /// Standard deviations above the mean failure rate that make a worker an outlier
+    pub stdev_factor: f64,
+    /// Failure rate (0.0-1.0) below which a worker is never ejected
+    pub min_failure_rate: f64,
+    /// Ejection time in seconds, multiplied by the worker's consecutive ejections
+    pub base_ejection_secs: u64,
+    /// Longest ejection in seconds
+    pub max_ejection_secs: u64,
+    /// Most workers of a model ejected at once, in percent; at least one may always be ejected
+    pub max_ejection_percent: u32,
+}
+
+impl Default for OutlierDetectionConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 10,
+            min_requests: 20,
+            min_workers: 5,
+            stdev_factor: 1.9,
+            min_failure_rate: 0.1,
+            base_ejection_secs: 30,
+            max_ejection_secs: 300,
+            max_ejection_percent: 10,
+        }
+    }
+}
+
+/// Adaptive concurrency limit configuration
+///
+/// The global concurrency limit follows the gradient algorithm: each request
+/// a worker answered compares the lowest latency seen with its own latency.
+/// Near the minimum, the limit grows by about its square root; as queueing
+/// inflates latency, it shrinks by up to half. Overload responses (503, 504)
+/// shrink it by a tenth. Requests over the limit wait in the queue when
+/// `queue_size` is set, and `max_concurrent_requests` stays the upper bound.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveConcurrencyConfig {
+    /// Limit before any latency has been observed
+    pub initial_limit: usize,
+    /// Lowest limit the algorithm may settle on
+    pub min_limit: usize,
+    /// Weight (0.0-1.0) of each new estimate in the smoothed limit
+    pub smoothing: f64,
+    /// Seconds after which the minimum latency is measured afresh, so the
+    /// limit recovers after the backend has become permanently slower
+    pub min_latency_window_secs: u64,
+}
+
+impl Default for AdaptiveConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            initial_limit: 32,
+            min_limit: 4,
+            smoothing: 0.2,
+            min_latency_window_secs: 60,
+        }
+    }
+}
+
+/// Handling of workers that serve different versions of the same model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionMismatchAction {
+    /// Log the skew and keep balancing across every worker (default)
+    #[default]
+    Warn,
+    /// Only route to workers serving the version most of the model's workers
+    /// report. Instances found through vLLM PD service discovery are not
+    /// registered workers, so their versions are not checked or split.
+    Split,
+}
+
+/// Response cache configuration
+///
+/// Non-streaming requests with temperature 0 and a single choice are answered
+/// from an in-router LRU cache keyed on the route, model, and request body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseCacheConfig {
+    /// Maximum number of cached responses
+    pub max_entries: usize,
+    /// Time in seconds a cached response stays valid
+    pub ttl_secs: u64,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1024,
+            ttl_secs: 300,
+        }
+    }
+}
+
+/// Idempotency key handling for the stateful Responses API
+///
+/// The first successful response to a request with an `Idempotency-Key`
+/// header is kept for `ttl_secs`, and retries with the same key are answered
+/// from it instead of creating another stored response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyConfig {
+    /// Maximum number of remembered keys; the least recently used is dropped first
+    pub max_keys: usize,
+    /// Time in seconds a key is remembered
+    pub ttl_secs: u64,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            max_keys: 10000,
+            ttl_secs: 86400,
+        }
+    }
+}
+
+/// Authorization sent with requests to workers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpstreamAuthConfig {
+    /// Forward the client's Authorization header unchanged
+    Passthrough,
+    /// Replace the client's Authorization header with the router's bearer token
+    Inject { key: String },
+    /// Strip the client's Authorization header and send none
+    None,
+}
+
+/// Client-side TLS settings for connections to workers
+///
+/// Every HTTP client that talks to workers, including the ones used for
+/// startup and health checks, presents the same identity and trusts the same
+/// CA bundle.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UpstreamTlsConfig {
+    /// PEM client certificate presented to workers for mutual TLS
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    /// PEM PKCS#8 private key for `client_cert_path`
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+    /// PEM CA bundle trusted for worker certificates, in addition to the system roots
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+}
+
+/// Request body size limits for individual inference route groups
+///
+/// Unset groups use the global `max_payload_size`. A group's limit replaces
+/// the global one rather than narrowing it, so large embedding batches can be
+/// allowed without raising the limit for every route.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PayloadLimitsConfig {
+    /// `/generate`
+    #[serde(default)]
+    pub generate: Option<usize>,
+    /// `/v1/chat/completions`
+    #[serde(default)]
+    pub chat: Option<usize>,
+    /// `/v1/completions`
+    #[serde(default)]
+    pub completions: Option<usize>,
+    /// `/v1/embeddings`
+    #[serde(default)]
+    pub embeddings: Option<usize>,
+    /// `/rerank` and `/v1/rerank`
+    #[serde(default)]
+    pub rerank: Option<usize>,
+    /// `/v1/responses` and its sub-routes
+    #[serde(default)]
+    pub responses: Option<usize>,
+}
+
+impl PayloadLimitsConfig {
+    /// Configured limits paired with their route group names
+    pub fn entries(&self) -> [(&'static str, Option<usize>); 6] {
+        [
+            ("generate", self.generate),
+            ("chat", self.chat),
+            ("completions", self.completions),
+            ("embeddings", self.embeddings),
+            ("rerank", self.rerank),
+            ("responses", self.responses),
+        ]
+    }
+}
+
+/// Filter for client request headers forwarded to workers
+///
+/// Applied uniformly to generation, fan-out, and transparent proxy requests.
+/// Trace context headers are forwarded even when an allow list is set, unless
+/// they are denied explicitly.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HeaderPolicyConfig {
+    /// Forward only these headers (case-insensitive). Empty forwards every
+    /// header that is not denied
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Never forward these headers (case-insensitive)
+    #[serde(default = "default_header_deny")]
+    pub deny: Vec<String>,
+}
+
+impl Default for HeaderPolicyConfig {
+    fn default() -> Self {
+        Self {
+            allow: Vec::new(),
+            deny: default_header_deny(),
+        }
+    }
+}
+
+/// `host` plus the hop-by-hop headers from RFC 7230 section 6.1
+pub fn default_header_deny() -> Vec<String> {
+    [
+        "host",
+        "connection",
+        "keep-alive",
+        "proxy-authenticate",
+        "proxy-authorization",
+        "proxy-connection",
+        "te",
+        "trailer",
+        "transfer-encoding",
+        "upgrade",
+    ]
+    .iter()
+    .map(|name| name.to_string())
+    .collect()
+}
+
+/// Debug logging of request and response bodies
+///
+/// Bodies sent to and received from workers on `routes` are logged at trace
+/// level. Fields in `redact_fields` are replaced before logging; a path such
+/// as `messages[].content` applies to every element of the `messages` array.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BodyLoggingConfig {
+    /// Routes whose bodies are logged, e.g. `/v1/chat/completions`
+    pub routes: Vec<String>,
+    /// Fields replaced with a placeholder before logging
+    #[serde(default = "default_body_redact_fields")]
+    pub redact_fields: Vec<String>,
+}
+
+/// Prompt and completion text fields of the OpenAI and native APIs
+pub fn default_body_redact_fields() -> Vec<String> {
+    [
+        "messages[].content",
+        "prompt",
+        "input",
+        "text",
+        "documents",
+        "choices[].text",
+        "choices[].message.content",
+        "choices[].delta.content",
+        "output",
+    ]
+    .iter()
+    .map(|field| field.to_string())
+    .collect()
+}
+
 /// Health check configuration for worker monitoring
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthCheckConfig {
@@ -350,6 +1118,18 @@ pub struct HealthCheckConfig {
     pub check_interval_secs: u64,
     /// Health check endpoint path
     pub endpoint: String,
+    /// HTTP method used for health check requests
+    #[serde(default)]
+    pub method: HealthCheckMethod,
+    /// Body a health check response must match, besides a 2xx status
+    ///
+    /// `$.path.to.field=value` compares a JSON field with `value`; anything
+    /// else must appear as a substring of the body.
+    #[serde(default)]
+    pub expected_body: Option<String>,
+    /// Also require a one-token completion to succeed; costs GPU time on every check
+    #[serde(default)]
+    pub generation_probe: bool,
 }
 
 impl Default for HealthCheckConfig {
@@ -360,10 +1140,22 @@ impl Default for HealthCheckConfig {
             timeout_secs: 5,
             check_interval_secs: 60,
             endpoint: "/health".to_string(),
+            method: HealthCheckMethod::Get,
+            expected_body: None,
+            generation_probe: false,
         }
     }
 }
 
+/// HTTP method for worker health checks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthCheckMethod {
+    #[default]
+    Get,
+    Post,
+}
+
 /// Circuit breaker configuration for worker reliability
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CircuitBreakerConfig {
@@ -375,6 +1167,13 @@ pub struct CircuitBreakerConfig {
     pub timeout_duration_secs: u64,
     /// Window duration for failure tracking (in seconds)
     pub window_duration_secs: u64,
+    /// Maximum number of concurrent probe requests while half-open
+    #[serde(default = "default_half_open_max_concurrent")]
+    pub half_open_max_concurrent: u32,
+}
+
+fn default_half_open_max_concurrent() -> u32 {
+    1
 }
 
 impl Default for CircuitBreakerConfig {
@@ -384,6 +1183,7 @@ impl Default for CircuitBreakerConfig {
             success_threshold: 3,
             timeout_duration_secs: 60,
             window_duration_secs: 120,
+            half_open_max_concurrent: default_half_open_max_concurrent(),
         }
     }
 }
@@ -417,6 +1217,10 @@ impl Default for RouterConfig {
             port: 3001,
             max_payload_size: 536_870_912, // 512MB
             request_timeout_secs: 1800,    // 30 minutes
+            proxy_timeout_secs: 10,
+            generation_timeout_secs: None,
+            max_request_timeout_secs: None,
+            slow_request_threshold_secs: None,
             worker_startup_timeout_secs: 600,
             worker_startup_check_interval_secs: 30,
             intra_node_data_parallel_size: 1,
@@ -431,9 +1235,49 @@ impl Default for RouterConfig {
             queue_size: 100,
             queue_timeout_secs: 60,
             rate_limit_tokens_per_second: None,
+            model_concurrency_limits: HashMap::new(),
+            adaptive_concurrency: None,
+            model_policies: HashMap::new(),
+            queue_fairness: QueueFairness::Fifo,
+            queue_weights: HashMap::new(),
+            queue_priority_aging_secs: default_queue_priority_aging_secs(),
+            model_aliases: HashMap::new(),
+            model_fallbacks: HashMap::new(),
+            model_traffic_splits: HashMap::new(),
+            discovery_stale_secs: default_discovery_stale_secs(),
+            advertise_model_aliases: false,
+            rewrite_stream_model: false,
+            strict_model_routing: false,
+            enable_response_compression: false,
+            enable_upstream_compression: false,
+            max_concurrent_streams: None,
+            stream_buffer_size: default_stream_buffer_size(),
+            stream_keepalive_secs: None,
+            shutdown_drain_timeout_secs: default_shutdown_drain_timeout_secs(),
+            batch_max_concurrency: default_batch_max_concurrency(),
             cors_allowed_origins: vec![],
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            hedging: None,
+            traffic_mirror: None,
+            worker_rate_limit: None,
+            worker_warmup: None,
+            model_version_check: None,
+            outlier_detection: None,
+            response_cache: None,
+            idempotency: None,
+            header_policy: HeaderPolicyConfig::default(),
+            body_logging: None,
+            allow_worker_pinning: false,
+            expose_routing_headers: false,
+            upstream_auth: None,
+            upstream_tls: None,
+            pd_zone_affinity: false,
+            min_healthy_workers: default_min_healthy_workers(),
+            payload_limits: PayloadLimitsConfig::default(),
+            max_prompt_chars: None,
+            default_max_tokens: None,
+            max_tokens_limit: None,
             disable_retries: false,
             disable_circuit_breaker: false,
             health_check: HealthCheckConfig::default(),
@@ -442,6 +1286,7 @@ impl Default for RouterConfig {
             model_path: None,
             tokenizer_path: None,
             history_backend: default_history_backend(),
+            memory_history: MemoryHistoryConfig::default(),
             enable_profiling: false,
             profile_timeout_secs: default_profile_timeout_secs(),
         }
@@ -501,6 +1346,38 @@ impl RouterConfig {
         cfg
     }
 
+    /// Timeout for upstream generation requests, falling back to the global request timeout
+    pub fn effective_generation_timeout_secs(&self) -> u64 {
+        self.generation_timeout_secs
+            .unwrap_or(self.request_timeout_secs)
+    }
+
+    /// Resolve how requests to workers are authenticated
+    ///
+    /// Without an explicit mode the router keeps its historical behavior: a
+    /// configured `api_key` replaces the client's credentials, the vLLM PD
+    /// router sends `OPENAI_API_KEY` from the environment when it is set, and
+    /// otherwise the client's Authorization header is forwarded as is.
+    pub fn effective_upstream_auth(&self) -> UpstreamAuthConfig {
+        match (&self.upstream_auth, &self.api_key) {
+            (Some(auth), _) => auth.clone(),
+            (None, Some(key)) => UpstreamAuthConfig::Inject { key: key.clone() },
+            (None, None) => match (&self.mode, std::env::var("OPENAI_API_KEY")) {
+                (RoutingMode::VllmPrefillDecode { .. }, Ok(key)) if !key.is_empty() => {
+                    UpstreamAuthConfig::Inject { key }
+                }
+                _ => UpstreamAuthConfig::Passthrough,
+            },
+        }
+    }
+
+    /// Whether routers only select workers serving a model's active version
+    pub fn split_model_versions(&self) -> bool {
+        self.model_version_check
+            .as_ref()
+            .is_some_and(|check| check.on_mismatch == VersionMismatchAction::Split)
+    }
+
     /// Check if running in IGW (Inference Gateway) mode
     pub fn is_igw_mode(&self) -> bool {
         self.enable_igw
@@ -525,6 +1402,8 @@ mod tests {
         assert_eq!(config.port, 3001);
         assert_eq!(config.max_payload_size, 536_870_912);
         assert_eq!(config.request_timeout_secs, 1800);
+        assert_eq!(config.proxy_timeout_secs, 10);
+        assert_eq!(config.generation_timeout_secs, None);
         assert_eq!(config.worker_startup_timeout_secs, 600);
         assert_eq!(config.worker_startup_check_interval_secs, 30);
         assert!(config.discovery.is_none());
@@ -670,6 +1549,7 @@ mod tests {
             balance_rel_threshold: 1.5,
             eviction_interval_secs: 300,
             max_tree_size: 1000,
+            cold_start_policy: ColdStartPolicy::default(),
         };
         assert_eq!(cache_aware.name(), "cache_aware");
 
@@ -677,6 +1557,119 @@ mod tests {
             load_check_interval_secs: 60,
         };
         assert_eq!(power_of_two.name(), "power_of_two");
+
+        let load_weighted = PolicyConfig::LoadWeightedRandom {
+            load_check_interval_secs: 5,
+        };
+        assert_eq!(load_weighted.name(), "load_weighted_random");
+
+        let latency_aware = PolicyConfig::LatencyAware { decay: 0.8 };
+        assert_eq!(latency_aware.name(), "latency_aware");
+
+        let weighted = PolicyConfig::WeightedRoundRobin {
+            weight_source: WeightSource::Cost,
+        };
+        assert_eq!(weighted.name(), "weighted_round_robin");
+    }
+
+    #[test]
+    fn test_weighted_round_robin_serialization() {
+        let weighted = PolicyConfig::WeightedRoundRobin {
+            weight_source: WeightSource::Cost,
+        };
+        let json = serde_json::to_string(&weighted).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"weighted_round_robin","weight_source":"cost"}"#
+        );
+
+        // weight_source defaults to priority when omitted
+        let parsed: PolicyConfig =
+            serde_json::from_str(r#"{"type":"weighted_round_robin"}"#).unwrap();
+        match parsed {
+            PolicyConfig::WeightedRoundRobin { weight_source } => {
+                assert_eq!(weight_source, WeightSource::Priority);
+            }
+            _ => panic!("Expected WeightedRoundRobin"),
+        }
+    }
+
+    #[test]
+    fn test_header_route_serialization() {
+        let parsed: PolicyConfig = serde_json::from_str(
+            r#"{"type":"header_route","header":"X-Route-Tier","label":"tier",
+                "routes":{"premium":"premium"},"sub_policy":{"type":"round_robin"}}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.name(), "header_route");
+        match parsed {
+            PolicyConfig::HeaderRoute {
+                routes,
+                default_route,
+                sub_policy,
+                ..
+            } => {
+                assert_eq!(routes["premium"], "premium");
+                assert_eq!(default_route, None);
+                assert_eq!(sub_policy.name(), "round_robin");
+            }
+            _ => panic!("Expected HeaderRoute"),
+        }
+    }
+
+    #[test]
+    fn test_latency_aware_serialization() {
+        // decay defaults when omitted
+        let parsed: PolicyConfig = serde_json::from_str(r#"{"type":"latency_aware"}"#).unwrap();
+        match parsed {
+            PolicyConfig::LatencyAware { decay } => {
+                assert_eq!(decay, crate::policies::DEFAULT_LATENCY_DECAY);
+            }
+            _ => panic!("Expected LatencyAware"),
+        }
+    }
+
+    #[test]
+    fn test_history_backend_serialization() {
+        let memory: HistoryBackend = serde_json::from_str(r#""memory""#).unwrap();
+        assert_eq!(memory, HistoryBackend::Memory);
+
+        let redis = HistoryBackend::Redis {
+            url: "redis://localhost:6379".to_string(),
+        };
+        let json = serde_json::to_string(&redis).unwrap();
+        assert_eq!(json, r#"{"redis":{"url":"redis://localhost:6379"}}"#);
+        let parsed: HistoryBackend = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, redis);
+    }
+
+    #[test]
+    fn test_effective_upstream_auth() {
+        let mut config = RouterConfig::default();
+        assert_eq!(
+            config.effective_upstream_auth(),
+            UpstreamAuthConfig::Passthrough
+        );
+
+        config.api_key = Some("sk-router".to_string());
+        assert_eq!(
+            config.effective_upstream_auth(),
+            UpstreamAuthConfig::Inject {
+                key: "sk-router".to_string()
+            }
+        );
+
+        config.upstream_auth = Some(UpstreamAuthConfig::None);
+        assert_eq!(config.effective_upstream_auth(), UpstreamAuthConfig::None);
+
+        let parsed: UpstreamAuthConfig =
+            serde_json::from_str(r#"{"inject":{"key":"sk-1"}}"#).unwrap();
+        assert_eq!(
+            parsed,
+            UpstreamAuthConfig::Inject {
+                key: "sk-1".to_string()
+            }
+        );
     }
 
     #[test]
@@ -693,6 +1686,7 @@ mod tests {
             balance_rel_threshold: 1.5,
             eviction_interval_secs: 300,
             max_tree_size: 1000,
+            cold_start_policy: ColdStartPolicy::default(),
         };
         let json = serde_json::to_string(&cache_aware).unwrap();
         assert!(json.contains("\"type\":\"cache_aware\""));
@@ -716,6 +1710,7 @@ mod tests {
             balance_rel_threshold: 2.0,
             eviction_interval_secs: 600,
             max_tree_size: 5000,
+            cold_start_policy: ColdStartPolicy::RoundRobin,
         };
 
         match cache_aware {
@@ -725,12 +1720,14 @@ mod tests {
                 balance_rel_threshold,
                 eviction_interval_secs,
                 max_tree_size,
+                cold_start_policy,
             } => {
                 assert!((cache_threshold - 0.75).abs() < 0.0001);
                 assert_eq!(balance_abs_threshold, 20);
                 assert!((balance_rel_threshold - 2.0).abs() < 0.0001);
                 assert_eq!(eviction_interval_secs, 600);
                 assert_eq!(max_tree_size, 5000);
+                assert_eq!(cold_start_policy, ColdStartPolicy::RoundRobin);
             }
             _ => panic!("Expected CacheAware"),
         }
@@ -981,6 +1978,10 @@ mod tests {
             port: 3000,
             max_payload_size: 1048576,
             request_timeout_secs: 120,
+            proxy_timeout_secs: 10,
+            generation_timeout_secs: None,
+            max_request_timeout_secs: None,
+            slow_request_threshold_secs: None,
             worker_startup_timeout_secs: 60,
             worker_startup_check_interval_secs: 5,
             intra_node_data_parallel_size: 1,
@@ -1002,6 +2003,26 @@ mod tests {
             cors_allowed_origins: vec![],
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            hedging: None,
+            traffic_mirror: None,
+            worker_rate_limit: None,
+            worker_warmup: None,
+            model_version_check: None,
+            outlier_detection: None,
+            response_cache: None,
+            idempotency: None,
+            header_policy: HeaderPolicyConfig::default(),
+            body_logging: None,
+            allow_worker_pinning: false,
+            expose_routing_headers: false,
+            upstream_auth: None,
+            upstream_tls: None,
+            pd_zone_affinity: false,
+            min_healthy_workers: 1,
+            payload_limits: PayloadLimitsConfig::default(),
+            max_prompt_chars: None,
+            default_max_tokens: None,
+            max_tokens_limit: None,
             disable_retries: false,
             disable_circuit_breaker: false,
             health_check: HealthCheckConfig::default(),
@@ -1009,10 +2030,31 @@ mod tests {
             queue_size: 100,
             queue_timeout_secs: 60,
             rate_limit_tokens_per_second: None,
+            model_concurrency_limits: HashMap::new(),
+            adaptive_concurrency: None,
+            model_policies: HashMap::new(),
+            queue_fairness: QueueFairness::Fifo,
+            queue_weights: HashMap::new(),
+            queue_priority_aging_secs: default_queue_priority_aging_secs(),
+            model_aliases: HashMap::new(),
+            model_fallbacks: HashMap::new(),
+            model_traffic_splits: HashMap::new(),
+            discovery_stale_secs: default_discovery_stale_secs(),
+            advertise_model_aliases: false,
+            rewrite_stream_model: false,
+            strict_model_routing: false,
+            enable_response_compression: false,
+            enable_upstream_compression: false,
+            max_concurrent_streams: None,
+            stream_buffer_size: default_stream_buffer_size(),
+            stream_keepalive_secs: None,
+            shutdown_drain_timeout_secs: default_shutdown_drain_timeout_secs(),
+            batch_max_concurrency: default_batch_max_concurrency(),
             connection_mode: ConnectionMode::Http,
             model_path: None,
             tokenizer_path: None,
             history_backend: default_history_backend(),
+            memory_history: MemoryHistoryConfig::default(),
             enable_profiling: false,
             profile_timeout_secs: default_profile_timeout_secs(),
         };
@@ -1043,11 +2085,16 @@ mod tests {
                 balance_rel_threshold: 1.2,
                 eviction_interval_secs: 600,
                 max_tree_size: 10000,
+                cold_start_policy: ColdStartPolicy::default(),
             },
             host: "0.0.0.0".to_string(),
             port: 3001,
             max_payload_size: 536870912,
             request_timeout_secs: 300,
+            proxy_timeout_secs: 10,
+            generation_timeout_secs: None,
+            max_request_timeout_secs: None,
+            slow_request_threshold_secs: None,
             worker_startup_timeout_secs: 180,
             worker_startup_check_interval_secs: 15,
             intra_node_data_parallel_size: 1,
@@ -1069,6 +2116,26 @@ mod tests {
             cors_allowed_origins: vec![],
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            hedging: None,
+            traffic_mirror: None,
+            worker_rate_limit: None,
+            worker_warmup: None,
+            model_version_check: None,
+            outlier_detection: None,
+            response_cache: None,
+            idempotency: None,
+            header_policy: HeaderPolicyConfig::default(),
+            body_logging: None,
+            allow_worker_pinning: false,
+            expose_routing_headers: false,
+            upstream_auth: None,
+            upstream_tls: None,
+            pd_zone_affinity: false,
+            min_healthy_workers: 1,
+            payload_limits: PayloadLimitsConfig::default(),
+            max_prompt_chars: None,
+            default_max_tokens: None,
+            max_tokens_limit: None,
             disable_retries: false,
             disable_circuit_breaker: false,
             health_check: HealthCheckConfig::default(),
@@ -1076,10 +2143,31 @@ mod tests {
             queue_size: 100,
             queue_timeout_secs: 60,
             rate_limit_tokens_per_second: None,
+            model_concurrency_limits: HashMap::new(),
+            adaptive_concurrency: None,
+            model_policies: HashMap::new(),
+            queue_fairness: QueueFairness::Fifo,
+            queue_weights: HashMap::new(),
+            queue_priority_aging_secs: default_queue_priority_aging_secs(),
+            model_aliases: HashMap::new(),
+            model_fallbacks: HashMap::new(),
+            model_traffic_splits: HashMap::new(),
+            discovery_stale_secs: default_discovery_stale_secs(),
+            advertise_model_aliases: false,
+            rewrite_stream_model: false,
+            strict_model_routing: false,
+            enable_response_compression: false,
+            enable_upstream_compression: false,
+            max_concurrent_streams: None,
+            stream_buffer_size: default_stream_buffer_size(),
+            stream_keepalive_secs: None,
+            shutdown_drain_timeout_secs: default_shutdown_drain_timeout_secs(),
+            batch_max_concurrency: default_batch_max_concurrency(),
             connection_mode: ConnectionMode::Http,
             model_path: None,
             tokenizer_path: None,
             history_backend: default_history_backend(),
+            memory_history: MemoryHistoryConfig::default(),
             enable_profiling: false,
             profile_timeout_secs: default_profile_timeout_secs(),
         };
@@ -1106,6 +2194,10 @@ mod tests {
             port: 8888,
             max_payload_size: 1024 * 1024 * 512, // 512MB
             request_timeout_secs: 900,
+            proxy_timeout_secs: 10,
+            generation_timeout_secs: None,
+            max_request_timeout_secs: None,
+            slow_request_threshold_secs: None,
             worker_startup_timeout_secs: 600,
             worker_startup_check_interval_secs: 20,
             intra_node_data_parallel_size: 1,
@@ -1132,6 +2224,26 @@ mod tests {
             cors_allowed_origins: vec![],
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            hedging: None,
+            traffic_mirror: None,
+            worker_rate_limit: None,
+            worker_warmup: None,
+            model_version_check: None,
+            outlier_detection: None,
+            response_cache: None,
+            idempotency: None,
+            header_policy: HeaderPolicyConfig::default(),
+            body_logging: None,
+            allow_worker_pinning: false,
+            expose_routing_headers: false,
+            upstream_auth: None,
+            upstream_tls: None,
+            pd_zone_affinity: false,
+            min_healthy_workers: 1,
+            payload_limits: PayloadLimitsConfig::default(),
+            max_prompt_chars: None,
+            default_max_tokens: None,
+            max_tokens_limit: None,
             disable_retries: false,
             disable_circuit_breaker: false,
             health_check: HealthCheckConfig::default(),
@@ -1139,10 +2251,31 @@ mod tests {
             queue_size: 100,
             queue_timeout_secs: 60,
             rate_limit_tokens_per_second: None,
+            model_concurrency_limits: HashMap::new(),
+            adaptive_concurrency: None,
+            model_policies: HashMap::new(),
+            queue_fairness: QueueFairness::Fifo,
+            queue_weights: HashMap::new(),
+            queue_priority_aging_secs: default_queue_priority_aging_secs(),
+            model_aliases: HashMap::new(),
+            model_fallbacks: HashMap::new(),
+            model_traffic_splits: HashMap::new(),
+            discovery_stale_secs: default_discovery_stale_secs(),
+            advertise_model_aliases: false,
+            rewrite_stream_model: false,
+            strict_model_routing: false,
+            enable_response_compression: false,
+            enable_upstream_compression: false,
+            max_concurrent_streams: None,
+            stream_buffer_size: default_stream_buffer_size(),
+            stream_keepalive_secs: None,
+            shutdown_drain_timeout_secs: default_shutdown_drain_timeout_secs(),
+            batch_max_concurrency: default_batch_max_concurrency(),
             connection_mode: ConnectionMode::Http,
             model_path: None,
             tokenizer_path: None,
             history_backend: default_history_backend(),
+            memory_history: MemoryHistoryConfig::default(),
             enable_profiling: false,
             profile_timeout_secs: default_profile_timeout_secs(),
         };
@@ -1177,6 +2310,7 @@ mod tests {
                 balance_rel_threshold: 1.1,
                 eviction_interval_secs: 60,
                 max_tree_size: 1000,
+                cold_start_policy: ColdStartPolicy::default(),
             }),
             decode_policy: Some(PolicyConfig::PowerOfTwo {
                 load_check_interval_secs: 60,
@@ -1209,6 +2343,7 @@ mod tests {
                 balance_rel_threshold: 1.1,
                 eviction_interval_secs: 60,
                 max_tree_size: 1000,
+                cold_start_policy: ColdStartPolicy::default(),
             }),
             decode_policy: None,
         };
@@ -1271,6 +2406,7 @@ mod tests {
             balance_rel_threshold: 1.5,
             eviction_interval_secs: 300,
             max_tree_size: 2000,
+            cold_start_policy: ColdStartPolicy::default(),
         };
 
         // Both should fall back to main policy
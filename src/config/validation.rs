@@ -5,7 +5,18 @@ pub struct ConfigValidator;
 
 impl ConfigValidator {
     /// Validate a complete router configuration
+    ///
+    /// Every section is checked, so a configuration with several problems
+    /// reports all of them at once (as [`ConfigError::Multiple`]) instead of
+    /// only the first.
     pub fn validate(config: &RouterConfig) -> ConfigResult<()> {
+        let mut errors = Vec::new();
+        let mut check = |result: ConfigResult<()>| {
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        };
+
         // Check if service discovery is enabled (either via discovery config or vLLM mode)
         let has_service_discovery = config.discovery.as_ref().is_some_and(|d| d.enabled)
             || matches!(
@@ -16,27 +27,93 @@ impl ConfigValidator {
                 }
             );
 
-        Self::validate_mode(&config.mode, has_service_discovery)?;
-        Self::validate_policy(&config.policy)?;
-        Self::validate_server_settings(config)?;
+        check(Self::validate_mode(&config.mode, has_service_discovery));
+        check(Self::validate_policy(&config.policy));
+        check(Self::validate_server_settings(config));
 
         if let Some(discovery) = &config.discovery {
-            Self::validate_discovery(discovery, &config.mode)?;
+            check(Self::validate_discovery(discovery, &config.mode));
         }
 
         if let Some(metrics) = &config.metrics {
-            Self::validate_metrics(metrics)?;
+            check(Self::validate_metrics(metrics));
         }
 
-        Self::validate_compatibility(config)?;
+        check(Self::validate_compatibility(config));
 
         // Validate effective retry/CB configs (respect disable flags)
         let retry_cfg = config.effective_retry_config();
         let cb_cfg = config.effective_circuit_breaker_config();
-        Self::validate_retry(&retry_cfg)?;
-        Self::validate_circuit_breaker(&cb_cfg)?;
+        check(Self::validate_retry(&retry_cfg));
+        check(Self::validate_circuit_breaker(&cb_cfg));
 
-        Ok(())
+        if let Some(hedging) = &config.hedging {
+            check(Self::validate_hedging(hedging));
+        }
+        if let Some(mirror) = &config.traffic_mirror {
+            check(Self::validate_traffic_mirror(mirror));
+        }
+        if let Some(limit) = &config.worker_rate_limit {
+            check(Self::validate_worker_rate_limit(limit));
+        }
+        if let Some(warmup) = &config.worker_warmup {
+            check(Self::validate_worker_warmup(warmup));
+        }
+        if let Some(version_check) = &config.model_version_check {
+            if version_check.check_interval_secs == 0 {
+                check(Err(ConfigError::InvalidValue {
+                    field: "model_version_check.check_interval_secs".to_string(),
+                    value: "0".to_string(),
+                    reason: "Must be > 0".to_string(),
+                }));
+            }
+        }
+        if let Some(outlier_detection) = &config.outlier_detection {
+            check(Self::validate_outlier_detection(outlier_detection));
+        }
+        if let Some(adaptive) = &config.adaptive_concurrency {
+            check(Self::validate_adaptive_concurrency(
+                adaptive,
+                config.max_concurrent_requests,
+            ));
+        }
+        if let Some(body_logging) = &config.body_logging {
+            check(Self::validate_body_logging(body_logging));
+            check(Self::validate_body_logging_mode(config));
+        }
+        if let Some(cache) = &config.response_cache {
+            check(Self::validate_response_cache(cache));
+        }
+        if let Some(idempotency) = &config.idempotency {
+            check(Self::validate_idempotency(idempotency));
+        }
+        check(Self::validate_header_policy(&config.header_policy));
+        if let Some(UpstreamAuthConfig::Inject { key }) = &config.upstream_auth {
+            check(Self::validate_upstream_auth_key(key));
+        }
+        if let Some(tls) = &config.upstream_tls {
+            check(Self::validate_upstream_tls(tls));
+        }
+        check(Self::validate_memory_history(&config.memory_history));
+        if let Some(expected) = &config.health_check.expected_body {
+            check(Self::validate_health_expected_body(expected));
+        }
+        if config.min_healthy_workers == 0 {
+            check(Err(ConfigError::InvalidValue {
+                field: "min_healthy_workers".to_string(),
+                value: "0".to_string(),
+                reason: "Must be >= 1".to_string(),
+            }));
+        }
+        if let HistoryBackend::Redis { url } = &config.history_backend {
+            check(Self::validate_redis_url(url));
+        }
+
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(ConfigError::Multiple { errors }),
+        }
     }
 
     /// Validate routing mode configuration
@@ -80,6 +157,7 @@ impl ConfigValidator {
                 if !decode_urls.is_empty() {
                     Self::validate_urls(decode_urls)?;
                 }
+                Self::validate_disjoint_pd_urls(prefill_urls, decode_urls)?;
 
                 // Validate bootstrap ports
                 for (_url, port) in prefill_urls {
@@ -134,6 +212,7 @@ impl ConfigValidator {
                 if !decode_urls.is_empty() {
                     Self::validate_urls(decode_urls)?;
                 }
+                Self::validate_disjoint_pd_urls(prefill_urls, decode_urls)?;
 
                 // Validate bootstrap ports
                 for (_url, port) in prefill_urls {
@@ -177,7 +256,9 @@ impl ConfigValidator {
     /// Validate policy configuration
     fn validate_policy(policy: &PolicyConfig) -> ConfigResult<()> {
         match policy {
-            PolicyConfig::Random | PolicyConfig::RoundRobin => {
+            PolicyConfig::Random
+            | PolicyConfig::RoundRobin
+            | PolicyConfig::WeightedRoundRobin { .. } => {
                 // No specific validation needed
             }
             PolicyConfig::CacheAware {
@@ -186,6 +267,7 @@ impl ConfigValidator {
                 balance_rel_threshold,
                 eviction_interval_secs,
                 max_tree_size,
+                cold_start_policy: _,
             } => {
                 if !(0.0..=1.0).contains(cache_threshold) {
                     return Err(ConfigError::InvalidValue {
@@ -221,6 +303,9 @@ impl ConfigValidator {
             }
             PolicyConfig::PowerOfTwo {
                 load_check_interval_secs,
+            }
+            | PolicyConfig::LoadWeightedRandom {
+                load_check_interval_secs,
             } => {
                 if *load_check_interval_secs == 0 {
                     return Err(ConfigError::InvalidValue {
@@ -230,7 +315,21 @@ impl ConfigValidator {
                     });
                 }
             }
-            PolicyConfig::ConsistentHash { virtual_nodes } => {
+            PolicyConfig::LatencyAware { decay } => {
+                if !(0.0..1.0).contains(decay) {
+                    return Err(ConfigError::InvalidValue {
+                        field: "decay".to_string(),
+                        value: decay.to_string(),
+                        reason: "Must be >= 0 and < 1".to_string(),
+                    });
+                }
+            }
+            PolicyConfig::ConsistentHash {
+                virtual_nodes,
+                session_cookie,
+                overload_factor,
+                ..
+            } => {
                 if *virtual_nodes == 0 {
                     return Err(ConfigError::InvalidValue {
                         field: "virtual_nodes".to_string(),
@@ -238,6 +337,63 @@ impl ConfigValidator {
                         reason: "Must be > 0".to_string(),
                     });
                 }
+                if let Some(factor) = overload_factor {
+                    if !factor.is_finite() || *factor < 1.0 {
+                        return Err(ConfigError::InvalidValue {
+                            field: "overload_factor".to_string(),
+                            value: factor.to_string(),
+                            reason: "Must be >= 1.0".to_string(),
+                        });
+                    }
+                }
+                let name = &session_cookie.name;
+                if name.is_empty()
+                    || !name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+                {
+                    return Err(ConfigError::InvalidValue {
+                        field: "session_cookie.name".to_string(),
+                        value: name.clone(),
+                        reason: "Must be a non-empty cookie token".to_string(),
+                    });
+                }
+                if session_cookie.secret.as_deref() == Some("") {
+                    return Err(ConfigError::InvalidValue {
+                        field: "session_cookie.secret".to_string(),
+                        value: String::new(),
+                        reason: "Must not be empty when set".to_string(),
+                    });
+                }
+            }
+            PolicyConfig::HeaderRoute {
+                header,
+                label,
+                routes,
+                default_route,
+                sub_policy,
+            } => {
+                if header.is_empty() || label.is_empty() {
+                    return Err(ConfigError::InvalidValue {
+                        field: "header_route".to_string(),
+                        value: format!("header={header} label={label}"),
+                        reason: "Header and label must be non-empty".to_string(),
+                    });
+                }
+                if routes.values().chain(default_route).any(String::is_empty) {
+                    return Err(ConfigError::InvalidValue {
+                        field: "header_route.routes".to_string(),
+                        value: String::new(),
+                        reason: "Pool label values must be non-empty".to_string(),
+                    });
+                }
+                if matches!(**sub_policy, PolicyConfig::HeaderRoute { .. }) {
+                    return Err(ConfigError::IncompatibleConfig {
+                        reason: "header_route cannot use header_route as its sub-policy"
+                            .to_string(),
+                    });
+                }
+                Self::validate_policy(sub_policy)?;
             }
         }
         Ok(())
@@ -261,6 +417,54 @@ impl ConfigValidator {
             });
         }
 
+        for (group, limit) in config.payload_limits.entries() {
+            if limit == Some(0) {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("payload_limits.{}", group),
+                    value: "0".to_string(),
+                    reason: "Must be > 0".to_string(),
+                });
+            }
+        }
+
+        if config.batch_max_concurrency == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "batch_max_concurrency".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
+        if config.max_prompt_chars == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "max_prompt_chars".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
+        for (field, value) in [
+            ("default_max_tokens", config.default_max_tokens),
+            ("max_tokens_limit", config.max_tokens_limit),
+        ] {
+            if value == Some(0) {
+                return Err(ConfigError::InvalidValue {
+                    field: field.to_string(),
+                    value: "0".to_string(),
+                    reason: "Must be > 0".to_string(),
+                });
+            }
+        }
+        if let (Some(default), Some(limit)) = (config.default_max_tokens, config.max_tokens_limit) {
+            if default > limit {
+                return Err(ConfigError::InvalidValue {
+                    field: "default_max_tokens".to_string(),
+                    value: default.to_string(),
+                    reason: format!("Must be <= max_tokens_limit ({})", limit),
+                });
+            }
+        }
+
         if config.request_timeout_secs == 0 {
             return Err(ConfigError::InvalidValue {
                 field: "request_timeout_secs".to_string(),
@@ -269,6 +473,38 @@ impl ConfigValidator {
             });
         }
 
+        if config.proxy_timeout_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "proxy_timeout_secs".to_string(),
+                value: config.proxy_timeout_secs.to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
+        if config.generation_timeout_secs == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "generation_timeout_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
+        if config.max_request_timeout_secs == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "max_request_timeout_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
+        if config.slow_request_threshold_secs == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "slow_request_threshold_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
         if config.worker_startup_timeout_secs == 0 {
             return Err(ConfigError::InvalidValue {
                 field: "worker_startup_timeout_secs".to_string(),
@@ -285,6 +521,121 @@ impl ConfigValidator {
             });
         }
 
+        for (model_id, limit) in &config.model_concurrency_limits {
+            if *limit == 0 {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("model_concurrency_limits.{model_id}"),
+                    value: limit.to_string(),
+                    reason: "Must be > 0".to_string(),
+                });
+            }
+        }
+
+        for (model_id, policy) in &config.model_policies {
+            if model_id.is_empty() {
+                return Err(ConfigError::InvalidValue {
+                    field: "model_policies".to_string(),
+                    value: model_id.clone(),
+                    reason: "Model ID must be non-empty".to_string(),
+                });
+            }
+            Self::validate_policy(policy)?;
+        }
+
+        for (alias, model_id) in &config.model_aliases {
+            if alias.is_empty() || model_id.is_empty() || alias == model_id {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("model_aliases.{alias}"),
+                    value: model_id.clone(),
+                    reason: "Alias and model ID must be non-empty and differ".to_string(),
+                });
+            }
+        }
+
+        for (model_id, fallbacks) in &config.model_fallbacks {
+            if model_id.is_empty()
+                || fallbacks.is_empty()
+                || fallbacks.iter().any(|f| f.is_empty() || f == model_id)
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("model_fallbacks.{model_id}"),
+                    value: fallbacks.join(","),
+                    reason: "Must list non-empty models other than the model itself".to_string(),
+                });
+            }
+        }
+
+        for (model_id, pools) in &config.model_traffic_splits {
+            let mut names = std::collections::HashSet::new();
+            if model_id.is_empty()
+                || pools.iter().all(|pool| pool.weight == 0)
+                || pools
+                    .iter()
+                    .any(|pool| pool.model.is_empty() || !names.insert(pool.model.as_str()))
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("model_traffic_splits.{model_id}"),
+                    value: pools
+                        .iter()
+                        .map(|pool| format!("{}:{}", pool.model, pool.weight))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                    reason: "Must list distinct non-empty models with a positive total weight"
+                        .to_string(),
+                });
+            }
+        }
+
+        if config.discovery_stale_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "discovery_stale_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
+        if config.queue_priority_aging_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "queue_priority_aging_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
+        for (key, weight) in &config.queue_weights {
+            if *weight == 0 {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("queue_weights.{key}"),
+                    value: weight.to_string(),
+                    reason: "Must be > 0".to_string(),
+                });
+            }
+        }
+
+        if config.max_concurrent_streams == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "max_concurrent_streams".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
+        if config.stream_buffer_size == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "stream_buffer_size".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
+        if config.stream_keepalive_secs == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "stream_keepalive_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+
         Ok(())
     }
 
@@ -403,6 +754,15 @@ impl ConfigValidator {
                 reason: "Must be between 0.0 and 1.0".to_string(),
             });
         }
+        if let Some(fraction) = retry.max_retry_fraction {
+            if !fraction.is_finite() || fraction <= 0.0 {
+                return Err(ConfigError::InvalidValue {
+                    field: "retry.max_retry_fraction".to_string(),
+                    value: fraction.to_string(),
+                    reason: "Must be > 0.0".to_string(),
+                });
+            }
+        }
         Ok(())
     }
 
@@ -436,83 +796,471 @@ impl ConfigValidator {
                 reason: "Must be > 0".to_string(),
             });
         }
+        if cb.half_open_max_concurrent < 1 {
+            return Err(ConfigError::InvalidValue {
+                field: "circuit_breaker.half_open_max_concurrent".to_string(),
+                value: cb.half_open_max_concurrent.to_string(),
+                reason: "Must be >= 1".to_string(),
+            });
+        }
         Ok(())
     }
 
-    /// Validate compatibility between different configuration sections
-    fn validate_compatibility(config: &RouterConfig) -> ConfigResult<()> {
-        // IGW mode is independent - skip other compatibility checks when enabled
-        if config.enable_igw {
-            return Ok(());
+    /// Validate request hedging configuration
+    fn validate_hedging(hedging: &HedgingConfig) -> ConfigResult<()> {
+        if hedging.hedge_delay_ms == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "hedging.hedge_delay_ms".to_string(),
+                value: hedging.hedge_delay_ms.to_string(),
+                reason: "Must be > 0".to_string(),
+            });
         }
+        Ok(())
+    }
 
-        // Validate gRPC connection mode requires tokenizer configuration
-        if config.connection_mode == ConnectionMode::Grpc
-            && config.tokenizer_path.is_none()
-            && config.model_path.is_none()
-        {
+    /// Validate shadow workers and the mirrored share of requests
+    fn validate_traffic_mirror(mirror: &TrafficMirrorConfig) -> ConfigResult<()> {
+        if mirror.worker_urls.is_empty() {
             return Err(ConfigError::ValidationFailed {
-                reason: "gRPC connection mode requires either --tokenizer-path or --model-path to be specified".to_string(),
+                reason: "traffic_mirror.worker_urls must list at least one shadow worker"
+                    .to_string(),
             });
         }
-
-        // All policies are now supported for both router types thanks to the unified trait design
-        // No mode/policy restrictions needed anymore
-
-        // Check if service discovery is enabled for worker count validation
-        let has_service_discovery = config.discovery.as_ref().is_some_and(|d| d.enabled);
-
-        // Only validate worker counts if service discovery is disabled
-        if !has_service_discovery {
-            // Check if power-of-two policy makes sense with insufficient workers
-            if let PolicyConfig::PowerOfTwo { .. } = &config.policy {
-                let worker_count = config.mode.worker_count();
-                if worker_count < 2 {
-                    return Err(ConfigError::IncompatibleConfig {
-                        reason: "Power-of-two policy requires at least 2 workers".to_string(),
-                    });
-                }
-            }
-
-            // For PD mode, validate that policies have sufficient workers
-            if let RoutingMode::PrefillDecode {
-                prefill_urls,
-                decode_urls,
-                prefill_policy,
-                decode_policy,
-            } = &config.mode
-            {
-                // Check power-of-two for prefill
-                if let Some(PolicyConfig::PowerOfTwo { .. }) = prefill_policy {
-                    if prefill_urls.len() < 2 {
-                        return Err(ConfigError::IncompatibleConfig {
-                            reason: "Power-of-two policy for prefill requires at least 2 prefill workers".to_string(),
-                        });
-                    }
-                }
-
-                // Check power-of-two for decode
-                if let Some(PolicyConfig::PowerOfTwo { .. }) = decode_policy {
-                    if decode_urls.len() < 2 {
-                        return Err(ConfigError::IncompatibleConfig {
-                            reason:
-                                "Power-of-two policy for decode requires at least 2 decode workers"
-                                    .to_string(),
-                        });
-                    }
-                }
-            }
+        Self::validate_urls(&mirror.worker_urls)?;
+        if let Some(url) = mirror.worker_urls.iter().find(|u| u.starts_with("grpc://")) {
+            return Err(ConfigError::InvalidValue {
+                field: "traffic_mirror.worker_urls".to_string(),
+                value: url.clone(),
+                reason: "Shadow workers must be HTTP workers".to_string(),
+            });
+        }
+        if !(mirror.fraction > 0.0 && mirror.fraction <= 1.0) {
+            return Err(ConfigError::InvalidValue {
+                field: "traffic_mirror.fraction".to_string(),
+                value: mirror.fraction.to_string(),
+                reason: "Must be > 0.0 and <= 1.0".to_string(),
+            });
+        }
+        if mirror.timeout_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "traffic_mirror.timeout_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
         }
+        Ok(())
+    }
 
-        // DP-aware routing is now automatically enabled when data_parallel_size > 1
-        // and is compatible with service discovery
+    /// Validate per-worker rate limit configuration
+    fn validate_worker_rate_limit(limit: &WorkerRateLimitConfig) -> ConfigResult<()> {
+        if limit.requests_per_second == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "worker_rate_limit.requests_per_second".to_string(),
+                value: limit.requests_per_second.to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        if limit.burst == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "worker_rate_limit.burst".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        Ok(())
+    }
 
+    /// Validate worker warmup configuration
+    fn validate_worker_warmup(warmup: &WorkerWarmupConfig) -> ConfigResult<()> {
+        if warmup.requests == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "worker_warmup.requests".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        if warmup.prompt.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "worker_warmup.prompt".to_string(),
+                value: String::new(),
+                reason: "Must not be empty".to_string(),
+            });
+        }
         Ok(())
     }
 
-    /// Validate URL format
-    fn validate_urls(urls: &[String]) -> ConfigResult<()> {
-        for url in urls {
+    /// Validate body logging configuration
+    fn validate_body_logging(body_logging: &BodyLoggingConfig) -> ConfigResult<()> {
+        if body_logging.routes.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "body_logging.routes".to_string(),
+                value: "[]".to_string(),
+                reason: "At least one route is required".to_string(),
+            });
+        }
+        if let Some(route) = body_logging.routes.iter().find(|r| !r.starts_with('/')) {
+            return Err(ConfigError::InvalidValue {
+                field: "body_logging.routes".to_string(),
+                value: route.clone(),
+                reason: "Routes must start with '/'".to_string(),
+            });
+        }
+        let malformed = |field: &String| {
+            field
+                .split('.')
+                .any(|segment| segment.trim_end_matches("[]").is_empty())
+        };
+        if let Some(field) = body_logging.redact_fields.iter().find(|f| malformed(f)) {
+            return Err(ConfigError::InvalidValue {
+                field: "body_logging.redact_fields".to_string(),
+                value: field.clone(),
+                reason: "Expected dotted field names, e.g. messages[].content".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Bodies are only logged by the regular HTTP router
+    fn validate_body_logging_mode(config: &RouterConfig) -> ConfigResult<()> {
+        let regular_http = matches!(config.mode, RoutingMode::Regular { .. })
+            && config.connection_mode == ConnectionMode::Http;
+        if regular_http {
+            return Ok(());
+        }
+        Err(ConfigError::IncompatibleConfig {
+            reason: "Body logging is only supported in regular mode over HTTP".to_string(),
+        })
+    }
+
+    /// Validate response cache configuration
+    fn validate_response_cache(cache: &ResponseCacheConfig) -> ConfigResult<()> {
+        if cache.max_entries == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "response_cache.max_entries".to_string(),
+                value: cache.max_entries.to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        if cache.ttl_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "response_cache.ttl_secs".to_string(),
+                value: cache.ttl_secs.to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate adaptive concurrency limits against the fixed maximum
+    fn validate_adaptive_concurrency(
+        adaptive: &AdaptiveConcurrencyConfig,
+        max_concurrent_requests: usize,
+    ) -> ConfigResult<()> {
+        if adaptive.min_limit == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "adaptive_concurrency.min_limit".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        if adaptive.min_limit > max_concurrent_requests {
+            return Err(ConfigError::InvalidValue {
+                field: "adaptive_concurrency.min_limit".to_string(),
+                value: adaptive.min_limit.to_string(),
+                reason: format!(
+                    "Must be <= max_concurrent_requests ({})",
+                    max_concurrent_requests
+                ),
+            });
+        }
+        if !(adaptive.min_limit..=max_concurrent_requests).contains(&adaptive.initial_limit) {
+            return Err(ConfigError::InvalidValue {
+                field: "adaptive_concurrency.initial_limit".to_string(),
+                value: adaptive.initial_limit.to_string(),
+                reason: format!(
+                    "Must be between min_limit ({}) and max_concurrent_requests ({})",
+                    adaptive.min_limit, max_concurrent_requests
+                ),
+            });
+        }
+        if !(adaptive.smoothing > 0.0 && adaptive.smoothing <= 1.0) {
+            return Err(ConfigError::InvalidValue {
+                field: "adaptive_concurrency.smoothing".to_string(),
+                value: adaptive.smoothing.to_string(),
+                reason: "Must be in (0.0, 1.0]".to_string(),
+            });
+        }
+        if adaptive.min_latency_window_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "adaptive_concurrency.min_latency_window_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate outlier detection intervals, thresholds and ejection limits
+    fn validate_outlier_detection(outlier: &OutlierDetectionConfig) -> ConfigResult<()> {
+        let positive = [
+            ("interval_secs", outlier.interval_secs),
+            ("min_requests", outlier.min_requests),
+            ("base_ejection_secs", outlier.base_ejection_secs),
+        ];
+        for (field, value) in positive {
+            if value == 0 {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("outlier_detection.{}", field),
+                    value: "0".to_string(),
+                    reason: "Must be > 0".to_string(),
+                });
+            }
+        }
+        if outlier.min_workers < 2 {
+            return Err(ConfigError::InvalidValue {
+                field: "outlier_detection.min_workers".to_string(),
+                value: outlier.min_workers.to_string(),
+                reason: "Must be >= 2 to have a baseline to compare against".to_string(),
+            });
+        }
+        if !(outlier.stdev_factor.is_finite() && outlier.stdev_factor > 0.0) {
+            return Err(ConfigError::InvalidValue {
+                field: "outlier_detection.stdev_factor".to_string(),
+                value: outlier.stdev_factor.to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        if !(0.0..=1.0).contains(&outlier.min_failure_rate) {
+            return Err(ConfigError::InvalidValue {
+                field: "outlier_detection.min_failure_rate".to_string(),
+                value: outlier.min_failure_rate.to_string(),
+                reason: "Must be between 0.0 and 1.0".to_string(),
+            });
+        }
+        if outlier.max_ejection_secs < outlier.base_ejection_secs {
+            return Err(ConfigError::InvalidValue {
+                field: "outlier_detection.max_ejection_secs".to_string(),
+                value: outlier.max_ejection_secs.to_string(),
+                reason: "Must be >= base_ejection_secs".to_string(),
+            });
+        }
+        if outlier.max_ejection_percent > 100 {
+            return Err(ConfigError::InvalidValue {
+                field: "outlier_detection.max_ejection_percent".to_string(),
+                value: outlier.max_ejection_percent.to_string(),
+                reason: "Must be <= 100".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate idempotency key cache limits
+    fn validate_idempotency(idempotency: &IdempotencyConfig) -> ConfigResult<()> {
+        if idempotency.max_keys == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "idempotency.max_keys".to_string(),
+                value: idempotency.max_keys.to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        if idempotency.ttl_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "idempotency.ttl_secs".to_string(),
+                value: idempotency.ttl_secs.to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate memory history backend limits
+    fn validate_memory_history(history: &MemoryHistoryConfig) -> ConfigResult<()> {
+        if history.max_responses == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "memory_history.max_responses".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        if history.response_ttl_secs == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "memory_history.response_ttl_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Must be > 0".to_string(),
+            });
+        }
+        if history.response_ttl_secs.is_some() && history.eviction_interval_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "memory_history.eviction_interval_secs".to_string(),
+                value: history.eviction_interval_secs.to_string(),
+                reason: "Must be > 0 when a response TTL is set".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate the Redis history backend URL
+    fn validate_redis_url(url: &str) -> ConfigResult<()> {
+        if !url.starts_with("redis://") && !url.starts_with("rediss://") {
+            return Err(ConfigError::InvalidValue {
+                field: "history_backend.url".to_string(),
+                value: url.to_string(),
+                reason: "Must start with redis:// or rediss://".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate header forwarding configuration
+    fn validate_header_policy(policy: &HeaderPolicyConfig) -> ConfigResult<()> {
+        let lists = [
+            ("header_policy.allow", &policy.allow),
+            ("header_policy.deny", &policy.deny),
+        ];
+        for (field, names) in lists {
+            if let Some(name) = names
+                .iter()
+                .find(|name| axum::http::HeaderName::from_bytes(name.as_bytes()).is_err())
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: field.to_string(),
+                    value: name.clone(),
+                    reason: "Must be a valid HTTP header name".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate the bearer token injected into requests to workers
+    fn validate_upstream_auth_key(key: &str) -> ConfigResult<()> {
+        if key.is_empty() {
+            return Err(ConfigError::MissingRequired {
+                field: "upstream_auth.inject.key".to_string(),
+            });
+        }
+        if axum::http::HeaderValue::from_str(&format!("Bearer {}", key)).is_err() {
+            return Err(ConfigError::InvalidValue {
+                field: "upstream_auth.inject.key".to_string(),
+                value: "<redacted>".to_string(),
+                reason: "Must be a valid HTTP header value".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_health_expected_body(expected: &str) -> ConfigResult<()> {
+        crate::core::HealthBodyMatcher::parse(expected)
+            .map(|_| ())
+            .map_err(|reason| ConfigError::InvalidValue {
+                field: "health_check.expected_body".to_string(),
+                value: expected.to_string(),
+                reason,
+            })
+    }
+
+    fn validate_upstream_tls(tls: &UpstreamTlsConfig) -> ConfigResult<()> {
+        match (&tls.client_cert_path, &tls.client_key_path) {
+            (Some(_), None) => Err(ConfigError::MissingRequired {
+                field: "upstream_tls.client_key_path".to_string(),
+            }),
+            (None, Some(_)) => Err(ConfigError::MissingRequired {
+                field: "upstream_tls.client_cert_path".to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate compatibility between different configuration sections
+    fn validate_compatibility(config: &RouterConfig) -> ConfigResult<()> {
+        // IGW mode is independent - skip other compatibility checks when enabled
+        if config.enable_igw {
+            return Ok(());
+        }
+
+        // Validate gRPC connection mode requires tokenizer configuration
+        if config.connection_mode == ConnectionMode::Grpc
+            && config.tokenizer_path.is_none()
+            && config.model_path.is_none()
+        {
+            return Err(ConfigError::ValidationFailed {
+                reason: "gRPC connection mode requires either --tokenizer-path or --model-path to be specified".to_string(),
+            });
+        }
+
+        // All policies are now supported for both router types thanks to the unified trait design
+        // No mode/policy restrictions needed anymore
+
+        // Check if service discovery is enabled for worker count validation
+        let has_service_discovery = config.discovery.as_ref().is_some_and(|d| d.enabled);
+
+        // Only validate worker counts if service discovery is disabled
+        if !has_service_discovery {
+            // Check if power-of-two policy makes sense with insufficient workers
+            if let PolicyConfig::PowerOfTwo { .. } = &config.policy {
+                let worker_count = config.mode.worker_count();
+                if worker_count < 2 {
+                    return Err(ConfigError::IncompatibleConfig {
+                        reason: "Power-of-two policy requires at least 2 workers".to_string(),
+                    });
+                }
+            }
+
+            // For PD mode, validate that policies have sufficient workers
+            if let RoutingMode::PrefillDecode {
+                prefill_urls,
+                decode_urls,
+                prefill_policy,
+                decode_policy,
+            } = &config.mode
+            {
+                // Check power-of-two for prefill
+                if let Some(PolicyConfig::PowerOfTwo { .. }) = prefill_policy {
+                    if prefill_urls.len() < 2 {
+                        return Err(ConfigError::IncompatibleConfig {
+                            reason: "Power-of-two policy for prefill requires at least 2 prefill workers".to_string(),
+                        });
+                    }
+                }
+
+                // Check power-of-two for decode
+                if let Some(PolicyConfig::PowerOfTwo { .. }) = decode_policy {
+                    if decode_urls.len() < 2 {
+                        return Err(ConfigError::IncompatibleConfig {
+                            reason:
+                                "Power-of-two policy for decode requires at least 2 decode workers"
+                                    .to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        // DP-aware routing is now automatically enabled when data_parallel_size > 1
+        // and is compatible with service discovery
+
+        Ok(())
+    }
+
+    /// A worker cannot serve as both prefill and decode
+    fn validate_disjoint_pd_urls(
+        prefill_urls: &[(String, Option<u16>)],
+        decode_urls: &[String],
+    ) -> ConfigResult<()> {
+        match prefill_urls
+            .iter()
+            .find(|(url, _)| decode_urls.contains(url))
+        {
+            Some((url, _)) => Err(ConfigError::IncompatibleConfig {
+                reason: format!("Worker {} is listed as both prefill and decode", url),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Validate URL format
+    fn validate_urls(urls: &[String]) -> ConfigResult<()> {
+        for url in urls {
             if url.is_empty() {
                 return Err(ConfigError::InvalidValue {
                     field: "worker_url".to_string(),
@@ -521,56 +1269,798 @@ impl ConfigValidator {
                 });
             }
 
-            if !url.starts_with("http://")
-                && !url.starts_with("https://")
-                && !url.starts_with("grpc://")
-            {
-                return Err(ConfigError::InvalidValue {
-                    field: "worker_url".to_string(),
-                    value: url.clone(),
-                    reason: "URL must start with http://, https://, or grpc://".to_string(),
-                });
-            }
+            if !url.starts_with("http://")
+                && !url.starts_with("https://")
+                && !url.starts_with("grpc://")
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: "worker_url".to_string(),
+                    value: url.clone(),
+                    reason: "URL must start with http://, https://, or grpc://".to_string(),
+                });
+            }
+
+            // Basic URL validation
+            match ::url::Url::parse(url) {
+                Ok(parsed) => {
+                    // Additional validation
+                    if parsed.host_str().is_none() {
+                        return Err(ConfigError::InvalidValue {
+                            field: "worker_url".to_string(),
+                            value: url.clone(),
+                            reason: "URL must have a valid host".to_string(),
+                        });
+                    }
+                }
+                Err(e) => {
+                    return Err(ConfigError::InvalidValue {
+                        field: "worker_url".to_string(),
+                        value: url.clone(),
+                        reason: format!("Invalid URL format: {}", e),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_regular_mode() {
+        let config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        assert!(ConfigValidator::validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_retry_budget_fraction() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.retry.max_retry_fraction = Some(0.2);
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.retry.max_retry_fraction = Some(0.0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(e.to_string().contains("max_retry_fraction"));
+        }
+    }
+
+    #[test]
+    fn test_validate_hedging() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.hedging = Some(HedgingConfig { hedge_delay_ms: 50 });
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.hedging = Some(HedgingConfig { hedge_delay_ms: 0 });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(e.to_string().contains("hedge_delay_ms"));
+        }
+    }
+
+    #[test]
+    fn test_validate_worker_rate_limit() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.worker_rate_limit = Some(WorkerRateLimitConfig {
+            requests_per_second: 10,
+            burst: Some(20),
+        });
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.worker_rate_limit = Some(WorkerRateLimitConfig {
+            requests_per_second: 0,
+            burst: None,
+        });
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("requests_per_second"));
+
+        config.worker_rate_limit = Some(WorkerRateLimitConfig {
+            requests_per_second: 10,
+            burst: Some(0),
+        });
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("worker_rate_limit.burst"));
+    }
+
+    #[test]
+    fn test_validate_worker_warmup() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.worker_warmup = Some(WorkerWarmupConfig {
+            requests: 2,
+            prompt: "Hello".to_string(),
+        });
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.worker_warmup = Some(WorkerWarmupConfig {
+            requests: 0,
+            prompt: "Hello".to_string(),
+        });
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("worker_warmup.requests"));
+
+        config.worker_warmup = Some(WorkerWarmupConfig {
+            requests: 2,
+            prompt: String::new(),
+        });
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("worker_warmup.prompt"));
+    }
+
+    #[test]
+    fn test_validate_model_version_check() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.model_version_check = Some(ModelVersionCheckConfig::default());
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.model_version_check = Some(ModelVersionCheckConfig {
+            check_interval_secs: 0,
+            on_mismatch: VersionMismatchAction::Split,
+        });
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("model_version_check.check_interval_secs"));
+    }
+
+    #[test]
+    fn test_validate_traffic_mirror() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+        let mirror = TrafficMirrorConfig {
+            worker_urls: vec!["http://shadow:8000".to_string()],
+            fraction: 0.05,
+            timeout_secs: 60,
+        };
+
+        config.traffic_mirror = Some(mirror.clone());
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.traffic_mirror = Some(TrafficMirrorConfig {
+            fraction: 0.0,
+            ..mirror.clone()
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("traffic_mirror.fraction")));
+
+        config.traffic_mirror = Some(TrafficMirrorConfig {
+            worker_urls: vec!["grpc://shadow:9000".to_string()],
+            ..mirror.clone()
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("traffic_mirror.worker_urls")));
+
+        config.traffic_mirror = Some(TrafficMirrorConfig {
+            worker_urls: vec![],
+            ..mirror
+        });
+        assert!(ConfigValidator::validate(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_adaptive_concurrency() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+        config.max_concurrent_requests = 256;
+
+        config.adaptive_concurrency = Some(AdaptiveConcurrencyConfig::default());
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.adaptive_concurrency = Some(AdaptiveConcurrencyConfig {
+            initial_limit: 512,
+            ..Default::default()
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("adaptive_concurrency.initial_limit")));
+
+        config.adaptive_concurrency = Some(AdaptiveConcurrencyConfig {
+            min_limit: 0,
+            ..Default::default()
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("adaptive_concurrency.min_limit")));
+
+        config.adaptive_concurrency = Some(AdaptiveConcurrencyConfig {
+            smoothing: 0.0,
+            ..Default::default()
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("adaptive_concurrency.smoothing")));
+    }
+
+    #[test]
+    fn test_validate_outlier_detection() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.outlier_detection = Some(OutlierDetectionConfig::default());
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.outlier_detection = Some(OutlierDetectionConfig {
+            min_workers: 1,
+            ..Default::default()
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("outlier_detection.min_workers")));
+
+        config.outlier_detection = Some(OutlierDetectionConfig {
+            base_ejection_secs: 600,
+            ..Default::default()
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e
+            .to_string()
+            .contains("outlier_detection.max_ejection_secs")));
+
+        config.outlier_detection = Some(OutlierDetectionConfig {
+            min_failure_rate: 1.5,
+            ..Default::default()
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("outlier_detection.min_failure_rate")));
+    }
+
+    #[test]
+    fn test_validate_body_logging() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.body_logging = Some(BodyLoggingConfig {
+            routes: vec!["/v1/chat/completions".to_string()],
+            redact_fields: default_body_redact_fields(),
+        });
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.body_logging = Some(BodyLoggingConfig {
+            routes: vec!["v1/completions".to_string()],
+            redact_fields: vec![],
+        });
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("body_logging.routes"));
+
+        config.body_logging = Some(BodyLoggingConfig {
+            routes: vec!["/v1/completions".to_string()],
+            redact_fields: vec!["messages[]..content".to_string()],
+        });
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("body_logging.redact_fields"));
+
+        // Other routers do not log bodies, so the setting is refused there
+        config.body_logging = Some(BodyLoggingConfig {
+            routes: vec!["/v1/completions".to_string()],
+            redact_fields: default_body_redact_fields(),
+        });
+        config.mode = RoutingMode::PrefillDecode {
+            prefill_urls: vec![("http://prefill:8000".to_string(), None)],
+            decode_urls: vec!["http://decode:8000".to_string()],
+            prefill_policy: None,
+            decode_policy: None,
+        };
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("Body logging"));
+    }
+
+    #[test]
+    fn test_validate_response_cache() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.response_cache = Some(ResponseCacheConfig::default());
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.response_cache = Some(ResponseCacheConfig {
+            max_entries: 0,
+            ttl_secs: 60,
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("response_cache.max_entries")));
+
+        config.response_cache = Some(ResponseCacheConfig {
+            max_entries: 16,
+            ttl_secs: 0,
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("response_cache.ttl_secs")));
+    }
+
+    #[test]
+    fn test_validate_idempotency() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.idempotency = Some(IdempotencyConfig::default());
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.idempotency = Some(IdempotencyConfig {
+            max_keys: 0,
+            ttl_secs: 60,
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("idempotency.max_keys")));
+    }
+
+    #[test]
+    fn test_validate_memory_history() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.memory_history = MemoryHistoryConfig {
+            max_responses: Some(1000),
+            response_ttl_secs: Some(3600),
+            eviction_interval_secs: 60,
+        };
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.memory_history.max_responses = Some(0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("memory_history.max_responses")));
+
+        config.memory_history.max_responses = None;
+        config.memory_history.eviction_interval_secs = 0;
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e
+            .to_string()
+            .contains("memory_history.eviction_interval_secs")));
+    }
+
+    #[test]
+    fn test_validate_redis_history_backend() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.history_backend = HistoryBackend::Redis {
+            url: "redis://localhost:6379/0".to_string(),
+        };
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.history_backend = HistoryBackend::Redis {
+            url: "localhost:6379".to_string(),
+        };
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("history_backend.url")));
+    }
+
+    #[test]
+    fn test_validate_header_policy() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.header_policy.allow = vec!["X-Tenant-Id".to_string(), "authorization".to_string()];
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.header_policy.deny.push("bad header".to_string());
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("header_policy.deny")));
+    }
+
+    #[test]
+    fn test_validate_upstream_auth() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.upstream_auth = Some(UpstreamAuthConfig::Inject {
+            key: "sk-router".to_string(),
+        });
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.upstream_auth = Some(UpstreamAuthConfig::Inject { key: String::new() });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("upstream_auth.inject.key")));
+
+        config.upstream_auth = Some(UpstreamAuthConfig::Inject {
+            key: "bad\nkey".to_string(),
+        });
+        assert!(ConfigValidator::validate(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_payload_limits() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.payload_limits.embeddings = Some(1024 * 1024 * 1024);
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.payload_limits.chat = Some(0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("payload_limits.chat")));
+
+        config.payload_limits.chat = None;
+        config.max_prompt_chars = Some(0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("max_prompt_chars")));
+
+        config.max_prompt_chars = Some(100_000);
+        assert!(ConfigValidator::validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_max_tokens_policy() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.default_max_tokens = Some(1024);
+        config.max_tokens_limit = Some(4096);
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.max_tokens_limit = Some(0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("max_tokens_limit")));
+
+        config.max_tokens_limit = Some(512);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("default_max_tokens")));
+    }
+
+    #[test]
+    fn test_validate_upstream_timeouts() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
 
-            // Basic URL validation
-            match ::url::Url::parse(url) {
-                Ok(parsed) => {
-                    // Additional validation
-                    if parsed.host_str().is_none() {
-                        return Err(ConfigError::InvalidValue {
-                            field: "worker_url".to_string(),
-                            value: url.clone(),
-                            reason: "URL must have a valid host".to_string(),
-                        });
-                    }
-                }
-                Err(e) => {
-                    return Err(ConfigError::InvalidValue {
-                        field: "worker_url".to_string(),
-                        value: url.clone(),
-                        reason: format!("Invalid URL format: {}", e),
-                    });
-                }
-            }
+        config.generation_timeout_secs = Some(3600);
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.generation_timeout_secs = Some(0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("generation_timeout_secs")));
+
+        config.generation_timeout_secs = None;
+        config.max_request_timeout_secs = Some(0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("max_request_timeout_secs")));
+
+        config.max_request_timeout_secs = Some(7200);
+        config.slow_request_threshold_secs = Some(0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("slow_request_threshold_secs")));
+
+        config.slow_request_threshold_secs = Some(30);
+        config.proxy_timeout_secs = 0;
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("proxy_timeout_secs")));
+    }
+
+    #[test]
+    fn test_validate_model_aliases() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config
+            .model_aliases
+            .insert("gpt-4o".to_string(), "llama-3.1-70b".to_string());
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config
+            .model_aliases
+            .insert("llama".to_string(), "llama".to_string());
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("model_aliases.llama"));
+    }
+
+    #[test]
+    fn test_validate_model_fallbacks() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.model_fallbacks.insert(
+            "llama-70b".to_string(),
+            vec!["llama-8b".to_string(), "llama-1b".to_string()],
+        );
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config
+            .model_fallbacks
+            .insert("llama-8b".to_string(), vec!["llama-8b".to_string()]);
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("model_fallbacks.llama-8b"));
+    }
+
+    #[test]
+    fn test_validate_model_traffic_splits() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+        let pool = |model: &str, weight| TrafficSplitPool {
+            model: model.to_string(),
+            weight,
+        };
+
+        config.model_traffic_splits.insert(
+            "llama".to_string(),
+            vec![pool("llama-v1", 95), pool("llama-v2", 5)],
+        );
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.model_traffic_splits.insert(
+            "llama".to_string(),
+            vec![pool("llama-v1", 0), pool("llama-v2", 0)],
+        );
+        let err = ConfigValidator::validate(&config).unwrap_err();
+        assert!(err.to_string().contains("model_traffic_splits.llama"));
+
+        config.model_traffic_splits.insert(
+            "llama".to_string(),
+            vec![pool("llama-v1", 50), pool("llama-v1", 50)],
+        );
+        assert!(ConfigValidator::validate(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_model_concurrency_limits() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config
+            .model_concurrency_limits
+            .insert("llama-3".to_string(), 8);
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config
+            .model_concurrency_limits
+            .insert("mistral".to_string(), 0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(e.to_string().contains("model_concurrency_limits.mistral"));
         }
-        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_validate_model_policies() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.model_policies.insert(
+            "rag-model".to_string(),
+            PolicyConfig::LatencyAware { decay: 0.8 },
+        );
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.model_policies.insert(
+            "chat-model".to_string(),
+            PolicyConfig::LatencyAware { decay: 1.0 },
+        );
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("decay")));
+    }
 
     #[test]
-    fn test_validate_regular_mode() {
-        let config = RouterConfig::new(
+    fn test_validate_queue_weights() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.queue_fairness = QueueFairness::Client;
+        config.queue_weights.insert("batch".to_string(), 1);
+        config.queue_weights.insert("interactive".to_string(), 4);
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.queue_weights.insert("idle".to_string(), 0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("queue_weights.idle")));
+    }
+
+    #[test]
+    fn test_validate_discovery_stale_secs() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.discovery_stale_secs = 0;
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("discovery_stale_secs")));
+    }
+
+    #[test]
+    fn test_validate_queue_priority_aging() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.queue_fairness = QueueFairness::Priority;
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.queue_priority_aging_secs = 0;
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("queue_priority_aging_secs")));
+    }
+
+    #[test]
+    fn test_validate_stream_limits() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.max_concurrent_streams = Some(128);
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.max_concurrent_streams = Some(0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("max_concurrent_streams")));
+
+        config.max_concurrent_streams = None;
+        config.stream_buffer_size = 0;
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("stream_buffer_size")));
+
+        config.stream_buffer_size = 64;
+        config.stream_keepalive_secs = Some(15);
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.stream_keepalive_secs = Some(0);
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("stream_keepalive_secs")));
+    }
+
+    #[test]
+    fn test_validate_upstream_tls() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["https://worker:8000".to_string()],
+            },
+            PolicyConfig::Random,
+        );
+
+        config.upstream_tls = Some(UpstreamTlsConfig {
+            ca_cert_path: Some("/etc/router/ca.pem".to_string()),
+            ..Default::default()
+        });
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.upstream_tls = Some(UpstreamTlsConfig {
+            client_cert_path: Some("/etc/router/client.pem".to_string()),
+            ..Default::default()
+        });
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("upstream_tls.client_key_path")));
+
+        config.upstream_tls = Some(UpstreamTlsConfig {
+            client_cert_path: Some("/etc/router/client.pem".to_string()),
+            client_key_path: Some("/etc/router/client.key".to_string()),
+            ca_cert_path: None,
+        });
+        assert!(ConfigValidator::validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_health_expected_body() {
+        let mut config = RouterConfig::new(
             RoutingMode::Regular {
                 worker_urls: vec!["http://worker:8000".to_string()],
             },
             PolicyConfig::Random,
         );
 
+        config.health_check.expected_body = Some("$.status=ready".to_string());
         assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.health_check.expected_body = Some("$.status".to_string());
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("health_check.expected_body")));
     }
 
     #[test]
@@ -635,12 +2125,61 @@ mod tests {
                 balance_rel_threshold: 1.1,
                 eviction_interval_secs: 60,
                 max_tree_size: 1000,
+                cold_start_policy: ColdStartPolicy::default(),
+            },
+        );
+
+        assert!(ConfigValidator::validate(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_header_route() {
+        let header_route = |header: &str, sub_policy: PolicyConfig| PolicyConfig::HeaderRoute {
+            header: header.to_string(),
+            label: "tier".to_string(),
+            routes: std::collections::HashMap::from([(
+                "premium".to_string(),
+                "premium".to_string(),
+            )]),
+            default_route: None,
+            sub_policy: Box::new(sub_policy),
+        };
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker1:8000".to_string()],
             },
+            header_route("x-route-tier", PolicyConfig::RoundRobin),
         );
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.policy = header_route("", PolicyConfig::RoundRobin);
+        assert!(ConfigValidator::validate(&config).is_err());
 
+        // Sub-policies are validated and cannot nest
+        config.policy = header_route("x-route-tier", PolicyConfig::LatencyAware { decay: 1.0 });
+        assert!(ConfigValidator::validate(&config).is_err());
+        config.policy = header_route(
+            "x-route-tier",
+            header_route("x-other", PolicyConfig::RoundRobin),
+        );
         assert!(ConfigValidator::validate(&config).is_err());
     }
 
+    #[test]
+    fn test_validate_latency_decay() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["http://worker1:8000".to_string()],
+            },
+            PolicyConfig::LatencyAware { decay: 0.8 },
+        );
+        assert!(ConfigValidator::validate(&config).is_ok());
+
+        config.policy = PolicyConfig::LatencyAware { decay: 1.0 };
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e.to_string().contains("decay")));
+    }
+
     #[test]
     fn test_validate_cache_aware_single_worker() {
         // Cache-aware with single worker should be allowed (even if not optimal)
@@ -654,6 +2193,7 @@ mod tests {
                 balance_rel_threshold: 1.1,
                 eviction_interval_secs: 60,
                 max_tree_size: 1000,
+                cold_start_policy: ColdStartPolicy::default(),
             },
         );
 
@@ -675,6 +2215,57 @@ mod tests {
         assert!(ConfigValidator::validate(&config).is_ok());
     }
 
+    #[test]
+    fn test_validate_pd_worker_in_both_roles() {
+        let config = RouterConfig::new(
+            RoutingMode::PrefillDecode {
+                prefill_urls: vec![("http://worker:8000".to_string(), None)],
+                decode_urls: vec!["http://worker:8000".to_string()],
+                prefill_policy: None,
+                decode_policy: None,
+            },
+            PolicyConfig::Random,
+        );
+
+        let result = ConfigValidator::validate(&config);
+        assert!(result.is_err_and(|e| e
+            .to_string()
+            .contains("http://worker:8000 is listed as both prefill and decode")));
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let mut config = RouterConfig::new(
+            RoutingMode::Regular {
+                worker_urls: vec!["invalid-url".to_string()],
+            },
+            PolicyConfig::CacheAware {
+                cache_threshold: 1.5,
+                balance_abs_threshold: 32,
+                balance_rel_threshold: 1.1,
+                eviction_interval_secs: 60,
+                max_tree_size: 1000,
+                cold_start_policy: ColdStartPolicy::default(),
+            },
+        );
+        config.min_healthy_workers = 0;
+
+        let Err(ConfigError::Multiple { errors }) = ConfigValidator::validate(&config) else {
+            panic!("expected several configuration problems");
+        };
+        assert_eq!(errors.len(), 3);
+
+        let message = ConfigError::Multiple { errors }.to_string();
+        assert!(
+            message.starts_with("3 configuration problems:"),
+            "{}",
+            message
+        );
+        for field in ["worker_url", "cache_threshold", "min_healthy_workers"] {
+            assert!(message.contains(field), "{}", message);
+        }
+    }
+
     #[test]
     fn test_validate_roundrobin_with_pd_mode() {
         // RoundRobin with PD mode is now supported
@@ -708,6 +2299,7 @@ mod tests {
                 balance_rel_threshold: 1.1,
                 eviction_interval_secs: 60,
                 max_tree_size: 1000,
+                cold_start_policy: ColdStartPolicy::default(),
             },
         );
 
@@ -753,6 +2345,7 @@ mod tests {
                     balance_rel_threshold: 1.1,
                     eviction_interval_secs: 60,
                     max_tree_size: 1000,
+                    cold_start_policy: ColdStartPolicy::default(),
                 }),
                 decode_policy: Some(PolicyConfig::PowerOfTwo {
                     load_check_interval_secs: 60,
@@ -0,0 +1,285 @@
+//! Adaptive concurrency limit
+//!
+//! A fixed `max_concurrent_requests` is hard to tune: too low wastes worker
+//! capacity, too high lets requests queue inside the workers. This limiter
+//! finds the limit from latencies with the gradient algorithm. The lowest
+//! latency seen approximates an unloaded backend; while requests finish near
+//! it the limit grows, and as queueing inflates latency the limit shrinks in
+//! proportion. Only responses a worker produced are sampled, so fast local
+//! rejections and cache hits cannot pose as the unloaded latency. The global
+//! [`TokenBucket`](super::token_bucket::TokenBucket) stays in place behind it
+//! as the hard upper bound.
+
+use crate::config::AdaptiveConcurrencyConfig;
+use crate::metrics::RouterMetrics;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tracing::debug;
+
+/// Share of the limit kept when a request fails from overload
+const BACKOFF_RATIO: f64 = 0.9;
+
+/// Marks a response a worker produced, as opposed to one the router
+/// generated itself
+///
+/// Stored in the response extensions; only marked responses feed the
+/// adaptive limit.
+#[derive(Debug, Clone, Copy)]
+pub struct WorkerResponse;
+
+#[derive(Debug)]
+struct LimitState {
+    /// Smoothed limit; the admitted concurrency is its integer part
+    limit: f64,
+    /// Lowest latency within the current window
+    min_latency: Option<Duration>,
+    window_start: Instant,
+}
+
+/// Concurrency limit adjusted from the latencies of finished requests
+#[derive(Debug)]
+pub struct AdaptiveConcurrencyLimiter {
+    min_limit: f64,
+    max_limit: f64,
+    smoothing: f64,
+    min_latency_window: Duration,
+    in_flight: AtomicUsize,
+    waiting: AtomicUsize,
+    /// Woken when a slot frees up or the limit grows
+    released: Notify,
+    state: Mutex<LimitState>,
+}
+
+/// An admitted request holding a slot until it is dropped
+///
+/// The limit is only adjusted through [`record_latency`](Self::record_latency);
+/// a permit dropped without it, as for requests that never reached a worker,
+/// frees the slot unsampled.
+#[derive(Debug)]
+pub struct AdaptivePermit {
+    limiter: Arc<AdaptiveConcurrencyLimiter>,
+    start: Instant,
+}
+
+impl AdaptiveConcurrencyLimiter {
+    pub fn new(config: &AdaptiveConcurrencyConfig, max_limit: usize) -> Self {
+        let max_limit = max_limit.max(1) as f64;
+        let min_limit = (config.min_limit.max(1) as f64).min(max_limit);
+        let limit = (config.initial_limit as f64).clamp(min_limit, max_limit);
+        RouterMetrics::set_adaptive_concurrency_limit(limit as usize);
+        Self {
+            min_limit,
+            max_limit,
+            smoothing: config.smoothing.clamp(f64::MIN_POSITIVE, 1.0),
+            min_latency_window: Duration::from_secs(config.min_latency_window_secs),
+            in_flight: AtomicUsize::new(0),
+            waiting: AtomicUsize::new(0),
+            released: Notify::new(),
+            state: Mutex::new(LimitState {
+                limit,
+                min_latency: None,
+                window_start: Instant::now(),
+            }),
+        }
+    }
+
+    /// Current concurrency limit
+    pub fn limit(&self) -> usize {
+        self.state.lock().unwrap().limit as usize
+    }
+
+    /// Requests admitted and not yet finished
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Requests waiting in [`acquire`](Self::acquire) for a slot
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::Acquire)
+    }
+
+    /// Admit a request, or return None when the limit is reached
+    pub fn try_acquire(self: &Arc<Self>) -> Option<AdaptivePermit> {
+        let limit = self.limit();
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |in_flight| {
+                (in_flight < limit).then_some(in_flight + 1)
+            })
+            .ok()?;
+        Some(AdaptivePermit {
+            limiter: Arc::clone(self),
+            start: Instant::now(),
+        })
+    }
+
+    /// Admit a request, waiting up to `timeout` for a slot
+    pub async fn acquire(self: &Arc<Self>, timeout: Duration) -> Option<AdaptivePermit> {
+        self.waiting.fetch_add(1, Ordering::AcqRel);
+        let permit = tokio::time::timeout(timeout, async {
+            loop {
+                // Register before checking so a release in between is not missed
+                let released = self.released.notified();
+                if let Some(permit) = self.try_acquire() {
+                    return permit;
+                }
+                released.await;
+            }
+        })
+        .await
+        .ok();
+        self.waiting.fetch_sub(1, Ordering::AcqRel);
+        permit
+    }
+
+    /// Adjust the limit from one finished request
+    fn record(&self, latency: Duration, in_flight: usize, overloaded: bool) {
+        let mut state = self.state.lock().unwrap();
+
+        let now = Instant::now();
+        if now.duration_since(state.window_start) >= self.min_latency_window {
+            state.min_latency = None;
+            state.window_start = now;
+        }
+        let min_latency = state.min_latency.map_or(latency, |min| min.min(latency));
+        state.min_latency = Some(min_latency);
+
+        let estimate = if overloaded {
+            state.limit * BACKOFF_RATIO
+        } else if (in_flight as f64) < state.limit / 2.0 {
+            // Demand is well below the limit, so latency says nothing about it
+            return;
+        } else {
+            let gradient = if latency.is_zero() {
+                1.0
+            } else {
+                (min_latency.as_secs_f64() / latency.as_secs_f64()).clamp(0.5, 1.0)
+            };
+            state.limit * gradient + state.limit.sqrt()
+        };
+
+        let limit = (state.limit * (1.0 - self.smoothing) + estimate * self.smoothing)
+            .clamp(self.min_limit, self.max_limit);
+        if limit as usize != state.limit as usize {
+            debug!(
+                "Adaptive concurrency limit {} -> {} (latency {:?}, min {:?})",
+                state.limit as usize, limit as usize, latency, min_latency
+            );
+            RouterMetrics::set_adaptive_concurrency_limit(limit as usize);
+        }
+        if limit as usize > state.limit as usize {
+            self.released.notify_waiters();
+        }
+        state.limit = limit;
+    }
+}
+
+impl AdaptivePermit {
+    /// Measure the latency from now on
+    ///
+    /// Called once the request clears the limits queued behind this one, so
+    /// time spent waiting for them is not taken for backend latency.
+    pub fn start_clock(&mut self) {
+        self.start = Instant::now();
+    }
+
+    /// Adjust the limit from the latency so far; the slot stays held
+    ///
+    /// `overloaded` marks a request that failed because the backend was
+    /// overloaded (503, 504), which shrinks the limit.
+    pub fn record_latency(&self, overloaded: bool) {
+        let in_flight = self.limiter.in_flight();
+        self.limiter
+            .record(self.start.elapsed(), in_flight, overloaded);
+    }
+}
+
+impl Drop for AdaptivePermit {
+    fn drop(&mut self) {
+        self.limiter.in_flight.fetch_sub(1, Ordering::AcqRel);
+        self.limiter.released.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(initial_limit: usize, max_limit: usize) -> Arc<AdaptiveConcurrencyLimiter> {
+        Arc::new(AdaptiveConcurrencyLimiter::new(
+            &AdaptiveConcurrencyConfig {
+                initial_limit,
+                min_limit: 2,
+                smoothing: 1.0,
+                min_latency_window_secs: 60,
+            },
+            max_limit,
+        ))
+    }
+
+    #[test]
+    fn test_admits_up_to_limit() {
+        let limiter = limiter(2, 100);
+        let first = limiter.try_acquire().unwrap();
+        let _second = limiter.try_acquire().unwrap();
+        assert!(limiter.try_acquire().is_none());
+        assert_eq!(limiter.in_flight(), 2);
+
+        // A dropped permit frees its slot without touching the limit
+        drop(first);
+        assert!(limiter.try_acquire().is_some());
+        assert_eq!(limiter.limit(), 2);
+    }
+
+    #[test]
+    fn test_limit_grows_at_min_latency_and_shrinks_under_queueing() {
+        let limiter = limiter(16, 100);
+        limiter.record(Duration::from_millis(100), 16, false);
+        // At the minimum latency the limit grows by its square root
+        assert_eq!(limiter.limit(), 20);
+
+        // Twice the minimum latency halves the limit before adding headroom
+        limiter.record(Duration::from_millis(200), 20, false);
+        assert_eq!(limiter.limit(), 14);
+    }
+
+    #[test]
+    fn test_limit_is_bounded() {
+        let limiter = limiter(16, 20);
+        for _ in 0..10 {
+            limiter.record(Duration::from_millis(100), 20, false);
+        }
+        assert_eq!(limiter.limit(), 20);
+
+        for _ in 0..50 {
+            limiter.record(Duration::from_millis(100), 20, true);
+        }
+        assert_eq!(limiter.limit(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_released_slot() {
+        let limiter = limiter(2, 100);
+        let first = limiter.try_acquire().unwrap();
+        let _second = limiter.try_acquire().unwrap();
+        assert!(limiter.acquire(Duration::from_millis(20)).await.is_none());
+        assert_eq!(limiter.waiting(), 0);
+
+        let waiter = {
+            let limiter = Arc::clone(&limiter);
+            tokio::spawn(async move { limiter.acquire(Duration::from_secs(5)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(limiter.waiting(), 1);
+        drop(first);
+        assert!(waiter.await.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_idle_demand_does_not_grow_limit() {
+        let limiter = limiter(16, 100);
+        limiter.record(Duration::from_millis(100), 2, false);
+        assert_eq!(limiter.limit(), 16);
+    }
+}
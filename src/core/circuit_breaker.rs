@@ -1,7 +1,8 @@
+use crate::metrics::RouterMetrics;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
-use tracing::info;
+use tracing::{info, warn};
 
 /// Circuit breaker configuration
 #[derive(Debug, Clone)]
@@ -14,6 +15,8 @@ pub struct CircuitBreakerConfig {
     pub timeout_duration: Duration,
     /// Time window for failure counting
     pub window_duration: Duration,
+    /// Maximum number of concurrent probe requests admitted while half-open
+    pub half_open_max_concurrent: u32,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -23,6 +26,7 @@ impl Default for CircuitBreakerConfig {
             success_threshold: 2,
             timeout_duration: Duration::from_secs(30),
             window_duration: Duration::from_secs(60),
+            half_open_max_concurrent: 1,
         }
     }
 }
@@ -38,6 +42,26 @@ pub enum CircuitState {
     HalfOpen,
 }
 
+impl CircuitState {
+    /// Label used for this state in metrics and logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+
+    /// Numeric code exported by the `vllm_router_cb_state` gauge
+    pub fn code(&self) -> u8 {
+        match self {
+            CircuitState::Closed => 0,
+            CircuitState::Open => 1,
+            CircuitState::HalfOpen => 2,
+        }
+    }
+}
+
 impl std::fmt::Display for CircuitState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -56,9 +80,12 @@ pub struct CircuitBreaker {
     consecutive_successes: Arc<AtomicU32>,
     total_failures: Arc<AtomicU64>,
     total_successes: Arc<AtomicU64>,
+    half_open_in_flight: Arc<AtomicU32>,
     last_failure_time: Arc<RwLock<Option<Instant>>>,
     last_state_change: Arc<RwLock<Instant>>,
     config: CircuitBreakerConfig,
+    /// Worker URL used to label transition events, if this breaker guards a worker
+    worker_url: Option<String>,
 }
 
 impl CircuitBreaker {
@@ -75,13 +102,31 @@ impl CircuitBreaker {
             consecutive_successes: Arc::new(AtomicU32::new(0)),
             total_failures: Arc::new(AtomicU64::new(0)),
             total_successes: Arc::new(AtomicU64::new(0)),
+            half_open_in_flight: Arc::new(AtomicU32::new(0)),
             last_failure_time: Arc::new(RwLock::new(None)),
             last_state_change: Arc::new(RwLock::new(Instant::now())),
             config,
+            worker_url: None,
         }
     }
 
+    /// Attach the worker URL that labels this breaker's transition events
+    ///
+    /// Without a worker URL, transitions are still logged but no metrics are recorded.
+    pub fn for_worker(mut self, worker_url: impl Into<String>) -> Self {
+        self.worker_url = Some(worker_url.into());
+        self
+    }
+
+    /// Get the worker URL this breaker reports transitions for
+    pub fn worker_url(&self) -> Option<&str> {
+        self.worker_url.as_deref()
+    }
+
     /// Check if a request can be executed
+    ///
+    /// In the half-open state this returns false once all probe slots are
+    /// taken. It does not claim a slot; use `try_acquire` before sending.
     pub fn can_execute(&self) -> bool {
         // First check if we need to transition from Open to HalfOpen
         self.check_and_update_state();
@@ -90,10 +135,54 @@ impl CircuitBreaker {
         match state {
             CircuitState::Closed => true,
             CircuitState::Open => false,
-            CircuitState::HalfOpen => true, // Allow limited requests in half-open state
+            CircuitState::HalfOpen => {
+                self.half_open_in_flight.load(Ordering::Acquire)
+                    < self.config.half_open_max_concurrent
+            }
+        }
+    }
+
+    /// Claim permission to send a request
+    ///
+    /// In the half-open state this takes one of the `half_open_max_concurrent`
+    /// probe slots, which is given back when the outcome is recorded (or via
+    /// `release`). Returns false if the circuit is open or no slot is free.
+    pub fn try_acquire(&self) -> bool {
+        self.check_and_update_state();
+
+        let state = *self.state.read().unwrap();
+        match state {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => self
+                .half_open_in_flight
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |in_flight| {
+                    (in_flight < self.config.half_open_max_concurrent).then_some(in_flight + 1)
+                })
+                .is_ok(),
         }
     }
 
+    /// Give back a slot claimed by `try_acquire` without recording an outcome
+    pub fn release(&self) {
+        if *self.state.read().unwrap() == CircuitState::HalfOpen {
+            self.release_probe_slot();
+        }
+    }
+
+    fn release_probe_slot(&self) {
+        let _ = self.half_open_in_flight.fetch_update(
+            Ordering::AcqRel,
+            Ordering::Acquire,
+            |in_flight| in_flight.checked_sub(1),
+        );
+    }
+
+    /// Get the number of probe requests currently in flight while half-open
+    pub fn half_open_in_flight(&self) -> u32 {
+        self.half_open_in_flight.load(Ordering::Acquire)
+    }
+
     /// Get the current state
     pub fn state(&self) -> CircuitState {
         self.check_and_update_state();
@@ -120,6 +209,7 @@ impl CircuitBreaker {
 
         match current_state {
             CircuitState::HalfOpen => {
+                self.release_probe_slot();
                 // Check if we've reached the success threshold to close the circuit
                 if successes >= self.config.success_threshold {
                     self.transition_to(CircuitState::Closed);
@@ -158,6 +248,7 @@ impl CircuitBreaker {
                 }
             }
             CircuitState::HalfOpen => {
+                self.release_probe_slot();
                 // Single failure in half-open state reopens the circuit
                 self.transition_to(CircuitState::Open);
             }
@@ -192,6 +283,9 @@ impl CircuitBreaker {
             let mut last_change = self.last_state_change.write().unwrap();
             *last_change = Instant::now();
 
+            // Probe slots only apply to a single half-open period
+            self.half_open_in_flight.store(0, Ordering::Release);
+
             // Reset counters based on transition
             match new_state {
                 CircuitState::Closed => {
@@ -207,18 +301,32 @@ impl CircuitBreaker {
                 }
             }
 
-            let from = match old_state {
-                CircuitState::Closed => "closed",
-                CircuitState::Open => "open",
-                CircuitState::HalfOpen => "half_open",
-            };
-            let to = match new_state {
-                CircuitState::Closed => "closed",
-                CircuitState::Open => "open",
-                CircuitState::HalfOpen => "half_open",
-            };
-            info!("Circuit breaker state transition: {} -> {}", from, to);
-            // Transition metrics are recorded at the worker level where the worker label is known
+            self.emit_transition(old_state, new_state);
+        }
+    }
+
+    /// Log a state transition and record it against the worker's metrics
+    fn emit_transition(&self, from: CircuitState, to: CircuitState) {
+        let worker = self.worker_url.as_deref().unwrap_or("unknown");
+        if to == CircuitState::Open {
+            warn!(
+                worker = worker,
+                from = from.as_str(),
+                to = to.as_str(),
+                "Circuit breaker opened"
+            );
+        } else {
+            info!(
+                worker = worker,
+                from = from.as_str(),
+                to = to.as_str(),
+                "Circuit breaker state transition"
+            );
+        }
+
+        if let Some(url) = &self.worker_url {
+            RouterMetrics::record_circuit_transition(url, from.as_str(), to.as_str());
+            RouterMetrics::set_cb_state(url, to.code());
         }
     }
 
@@ -305,9 +413,11 @@ impl Clone for CircuitBreaker {
             consecutive_successes: Arc::clone(&self.consecutive_successes),
             total_failures: Arc::clone(&self.total_failures),
             total_successes: Arc::clone(&self.total_successes),
+            half_open_in_flight: Arc::clone(&self.half_open_in_flight),
             last_failure_time: Arc::clone(&self.last_failure_time),
             last_state_change: Arc::clone(&self.last_state_change),
             config: self.config.clone(),
+            worker_url: self.worker_url.clone(),
         }
     }
 }
@@ -440,6 +550,71 @@ mod tests {
         assert!(!cb.can_execute());
     }
 
+    #[test]
+    fn test_half_open_limits_concurrent_probes() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            success_threshold: 2,
+            timeout_duration: Duration::from_millis(50),
+            half_open_max_concurrent: 2,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::with_config(config);
+
+        cb.record_failure();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+
+        // Two probes are admitted, the third is rejected
+        assert!(cb.try_acquire());
+        assert!(cb.try_acquire());
+        assert_eq!(cb.half_open_in_flight(), 2);
+        assert!(!cb.can_execute());
+        assert!(!cb.try_acquire());
+
+        // A finished probe frees its slot
+        cb.record_success();
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+        assert!(cb.can_execute());
+        assert!(cb.try_acquire());
+
+        // A released slot without an outcome is also freed
+        cb.release();
+        assert_eq!(cb.half_open_in_flight(), 1);
+
+        // Closing the circuit lifts the limit
+        cb.record_success();
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert_eq!(cb.half_open_in_flight(), 0);
+        for _ in 0..5 {
+            assert!(cb.try_acquire());
+        }
+    }
+
+    #[test]
+    fn test_half_open_probe_failure_reopens_and_clears_slots() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            timeout_duration: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::with_config(config);
+
+        cb.record_failure();
+        thread::sleep(Duration::from_millis(100));
+        assert!(cb.try_acquire());
+        assert!(!cb.try_acquire());
+
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        assert_eq!(cb.half_open_in_flight(), 0);
+        assert!(!cb.try_acquire());
+
+        // The next half-open period starts with a fresh slot
+        thread::sleep(Duration::from_millis(100));
+        assert!(cb.try_acquire());
+    }
+
     #[test]
     fn test_success_resets_failure_count() {
         let config = CircuitBreakerConfig {
@@ -526,6 +701,35 @@ mod tests {
         assert_eq!(cb2.failure_count(), 2);
     }
 
+    #[test]
+    fn test_worker_label_follows_clone() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            timeout_duration: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::with_config(config).for_worker("http://worker1:8000");
+        assert_eq!(cb.worker_url(), Some("http://worker1:8000"));
+        assert_eq!(cb.clone().worker_url(), Some("http://worker1:8000"));
+        assert_eq!(CircuitBreaker::new().worker_url(), None);
+
+        // Every transition (including the timed Open -> HalfOpen) reports without panicking
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+        cb.reset();
+        assert_eq!(cb.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_state_labels() {
+        assert_eq!(CircuitState::Closed.as_str(), "closed");
+        assert_eq!(CircuitState::Open.as_str(), "open");
+        assert_eq!(CircuitState::HalfOpen.as_str(), "half_open");
+        assert_eq!(CircuitState::HalfOpen.code(), 2);
+    }
+
     #[test]
     fn test_thread_safety() {
         use std::sync::Arc;
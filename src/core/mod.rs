@@ -4,23 +4,38 @@
 //! - Worker trait and implementations
 //! - Error types
 //! - Circuit breaker for reliability
+//! - TLS settings for connections to workers
+//! - Per-worker request rate limits
+//! - Adaptive global concurrency limit
+//! - Model version tracking across workers
 //! - Common utilities
 
+pub mod adaptive_concurrency;
 pub mod circuit_breaker;
 pub mod error;
+pub mod model_version;
+pub mod outlier_detection;
 pub mod retry;
 pub mod token_bucket;
+pub mod upstream_tls;
 pub mod worker;
+pub mod worker_rate_limiter;
 pub mod worker_registry;
 
 // Re-export commonly used types at the module level
+pub use adaptive_concurrency::{AdaptiveConcurrencyLimiter, AdaptivePermit, WorkerResponse};
 pub use circuit_breaker::{
     CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitState,
 };
 pub use error::{WorkerError, WorkerResult};
-pub use retry::{is_retryable_status, BackoffCalculator, RetryError, RetryExecutor};
+pub use retry::{
+    connection_failure_response, is_retryable_response, is_retryable_status, BackoffCalculator,
+    ConnectionFailure, RetryBudget, RetryError, RetryExecutor,
+};
 pub use worker::{
-    start_health_checker, BasicWorker, ConnectionMode, DPAwareWorker, HealthChecker, HealthConfig,
-    Worker, WorkerCollection, WorkerFactory, WorkerLoadGuard, WorkerType,
+    drain_workers, start_health_checker, BasicWorker, ConnectionMode, DPAwareWorker,
+    HealthBodyMatcher, HealthChecker, HealthConfig, LatencyEwma, OwnedWorkerLoadGuard, Worker,
+    WorkerCollection, WorkerFactory, WorkerLoadGuard, WorkerType,
 };
-pub use worker_registry::{WorkerId, WorkerRegistry, WorkerRegistryStats};
+pub use worker_rate_limiter::WorkerRateLimiter;
+pub use worker_registry::{WorkerFilter, WorkerId, WorkerRegistry, WorkerRegistryStats};
@@ -0,0 +1,297 @@
+//! Model version tracking across workers
+//!
+//! During a rolling deployment the workers of one model can briefly serve
+//! different weights. A background task reads the version each worker
+//! reports on `/get_model_info` and records it on the worker. Models whose
+//! workers disagree are logged and reported through the
+//! `vllm_router_model_versions` gauge. Under
+//! [`VersionMismatchAction::Split`](crate::config::VersionMismatchAction::Split)
+//! routers only select workers serving the model's active version, so clients
+//! do not get outputs from two versions mid-deploy.
+
+use super::{ConnectionMode, HealthChecker, Worker, WorkerRegistry};
+use crate::metrics::RouterMetrics;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Fields of a `/get_model_info` response naming the served weights, most specific first
+const VERSION_FIELDS: &[&str] = &["weight_version", "model_version", "model_hash"];
+
+/// Timeout of a single `/get_model_info` request
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+static VERSION_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    super::upstream_tls::client_builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .expect("Failed to create model version HTTP client")
+});
+
+/// Extract the served model version from a `/get_model_info` response
+pub fn parse_model_version(info: &Value) -> Option<String> {
+    VERSION_FIELDS.iter().find_map(|field| {
+        let version = match info.get(field)? {
+            Value::String(version) => version.trim().to_string(),
+            Value::Number(version) => version.to_string(),
+            _ => return None,
+        };
+        Some(version).filter(|v| !v.is_empty())
+    })
+}
+
+/// Version reported by most of `workers`
+///
+/// Ties go to the greater version string, which favours the newer version
+/// for the usual date or counter based schemes. Workers that report no
+/// version are not counted; None means none of them reports one.
+pub fn active_version<'a>(
+    workers: impl IntoIterator<Item = &'a Arc<dyn Worker>>,
+) -> Option<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for version in workers.into_iter().filter_map(|w| w.model_version()) {
+        *counts.entry(version).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then_with(|| a.cmp(b)))
+        .map(|(version, _)| version)
+}
+
+/// Active version of each model served by `workers`
+///
+/// Models whose workers report no version are left out.
+pub fn active_versions<'a>(
+    workers: impl IntoIterator<Item = &'a Arc<dyn Worker>>,
+) -> HashMap<String, String> {
+    let mut by_model: HashMap<&str, Vec<&'a Arc<dyn Worker>>> = HashMap::new();
+    for worker in workers {
+        by_model.entry(worker.model_id()).or_default().push(worker);
+    }
+    by_model
+        .into_iter()
+        .filter_map(|(model_id, workers)| {
+            active_version(workers).map(|version| (model_id.to_string(), version))
+        })
+        .collect()
+}
+
+/// Keep only the workers serving the active version of their model
+///
+/// Workers that report no version cannot be told apart and are kept.
+pub fn retain_active_version(workers: &mut Vec<Arc<dyn Worker>>) {
+    let active = active_versions(workers.iter());
+    retain_versions(workers, &active);
+}
+
+/// Keep only the workers serving their model's version in `active`, or reporting no version
+pub fn retain_versions(workers: &mut Vec<Arc<dyn Worker>>, active: &HashMap<String, String>) {
+    workers.retain(|w| match (active.get(w.model_id()), w.model_version()) {
+        (Some(active), Some(version)) => *active == version,
+        _ => true,
+    });
+}
+
+/// Distinct versions served per model, with the number of workers on each
+pub fn versions_by_model(workers: &[Arc<dyn Worker>]) -> HashMap<String, BTreeMap<String, usize>> {
+    let mut versions: HashMap<String, BTreeMap<String, usize>> = HashMap::new();
+    for worker in workers {
+        if let Some(version) = worker.model_version() {
+            *versions
+                .entry(worker.model_id().to_string())
+                .or_default()
+                .entry(version)
+                .or_default() += 1;
+        }
+    }
+    versions
+}
+
+/// Fetch the version served at `base_url`; None when it cannot be read
+async fn fetch_model_version(base_url: &str) -> Option<Option<String>> {
+    let url = format!("{}/get_model_info", base_url);
+    let response = match VERSION_CLIENT.get(&url).send().await {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            debug!("{} returned status {}", url, response.status());
+            return None;
+        }
+        Err(e) => {
+            debug!("Failed to fetch {}: {}", url, e);
+            return None;
+        }
+    };
+    match response.json::<Value>().await {
+        Ok(info) => Some(parse_model_version(&info)),
+        Err(e) => {
+            debug!("Failed to parse {}: {}", url, e);
+            None
+        }
+    }
+}
+
+/// Refresh the model version of every HTTP worker and report version skew
+///
+/// DP ranks share a server, so each base URL is queried once. A worker that
+/// cannot be reached keeps its last known version.
+pub async fn check_model_versions(workers: &[Arc<dyn Worker>]) {
+    let mut by_base_url: HashMap<&str, Vec<&Arc<dyn Worker>>> = HashMap::new();
+    for worker in workers {
+        if worker.speaks(&ConnectionMode::Http) {
+            by_base_url
+                .entry(worker.base_url())
+                .or_default()
+                .push(worker);
+        }
+    }
+
+    let fetches = by_base_url
+        .into_iter()
+        .map(|(base_url, workers)| async move {
+            if let Some(version) = fetch_model_version(base_url).await {
+                for worker in workers {
+                    worker.set_model_version(version.clone());
+                }
+            }
+        });
+    futures_util::future::join_all(fetches).await;
+
+    for (model_id, versions) in versions_by_model(workers) {
+        RouterMetrics::set_model_versions(&model_id, versions.len());
+        if versions.len() > 1 {
+            warn!(
+                "Workers of model '{}' serve {} different versions (version: workers): {:?}",
+                model_id,
+                versions.len(),
+                versions
+            );
+        }
+    }
+}
+
+/// Start a background task that checks worker model versions at a fixed interval
+pub fn start_model_version_checker(
+    registry: Arc<WorkerRegistry>,
+    check_interval_secs: u64,
+) -> HealthChecker {
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let shutdown_clone = shutdown.clone();
+
+    let handle = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(check_interval_secs));
+        loop {
+            interval.tick().await;
+            if shutdown_clone.load(Ordering::Acquire) {
+                debug!("Model version checker shutting down");
+                break;
+            }
+            check_model_versions(&registry.get_all()).await;
+        }
+    });
+
+    HealthChecker::new(handle, shutdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{BasicWorker, WorkerType};
+    use serde_json::json;
+
+    fn worker(url: &str, version: Option<&str>) -> Arc<dyn Worker> {
+        let worker = BasicWorker::new(url.to_string(), WorkerType::Regular);
+        worker.set_model_version(version.map(str::to_string));
+        Arc::new(worker)
+    }
+
+    #[test]
+    fn test_parse_model_version() {
+        assert_eq!(
+            parse_model_version(&json!({"model_path": "m", "weight_version": "v2"})),
+            Some("v2".to_string())
+        );
+        assert_eq!(
+            parse_model_version(&json!({"model_version": 3, "model_hash": "abc"})),
+            Some("3".to_string())
+        );
+        assert_eq!(parse_model_version(&json!({"weight_version": " "})), None);
+        assert_eq!(parse_model_version(&json!({"model_path": "m"})), None);
+    }
+
+    #[test]
+    fn test_retain_active_version_keeps_majority_and_unversioned() {
+        let mut workers = vec![
+            worker("http://w1:8000", Some("v1")),
+            worker("http://w2:8000", Some("v2")),
+            worker("http://w3:8000", Some("v1")),
+            worker("http://w4:8000", None),
+        ];
+        retain_active_version(&mut workers);
+
+        let urls: Vec<&str> = workers.iter().map(|w| w.url()).collect();
+        assert_eq!(
+            urls,
+            vec!["http://w1:8000", "http://w3:8000", "http://w4:8000"]
+        );
+    }
+
+    #[test]
+    fn test_retain_active_version_per_model() {
+        let model_worker = |url: &str, model: &str, version: &str| -> Arc<dyn Worker> {
+            let worker = BasicWorker::new(url.to_string(), WorkerType::Regular).with_labels(
+                std::collections::HashMap::from([("model_id".to_string(), model.to_string())]),
+            );
+            worker.set_model_version(Some(version.to_string()));
+            Arc::new(worker)
+        };
+        // model-b has fewer workers overall but its own active version
+        let mut workers = vec![
+            model_worker("http://a1:8000", "model-a", "v1"),
+            model_worker("http://a2:8000", "model-a", "v1"),
+            model_worker("http://a3:8000", "model-a", "v1"),
+            model_worker("http://b1:8000", "model-b", "v7"),
+            model_worker("http://b2:8000", "model-b", "v7"),
+            model_worker("http://b3:8000", "model-b", "v6"),
+        ];
+        retain_active_version(&mut workers);
+
+        let urls: Vec<&str> = workers.iter().map(|w| w.url()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "http://a1:8000",
+                "http://a2:8000",
+                "http://a3:8000",
+                "http://b1:8000",
+                "http://b2:8000"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_active_version_tie_prefers_greater_version() {
+        let workers = vec![
+            worker("http://w1:8000", Some("2025-01-01")),
+            worker("http://w2:8000", Some("2025-02-01")),
+        ];
+        assert_eq!(active_version(&workers), Some("2025-02-01".to_string()));
+        assert_eq!(active_version(&[worker("http://w3:8000", None)]), None);
+    }
+
+    #[test]
+    fn test_versions_by_model() {
+        let workers = vec![
+            worker("http://w1:8000", Some("v1")),
+            worker("http://w2:8000", Some("v2")),
+            worker("http://w3:8000", Some("v2")),
+        ];
+        let versions = versions_by_model(&workers);
+        assert_eq!(
+            versions["unknown"],
+            BTreeMap::from([("v1".to_string(), 1), ("v2".to_string(), 2)])
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Outlier detection across the workers of a model
+//!
+//! A worker can pass its health check while failing a large share of real
+//! requests, and its circuit breaker only opens on consecutive failures.
+//! A background task compares the failure rate of each worker over the last
+//! interval with the other workers of its model, using the outcome counts
+//! the circuit breaker already keeps. Workers failing far above the mean are
+//! ejected from selection for a cooldown that grows each time they are
+//! ejected again, and come back on their own when it ends.
+
+use super::{HealthChecker, Worker, WorkerRegistry};
+use crate::config::OutlierDetectionConfig;
+use crate::metrics::RouterMetrics;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Outcome counts and ejection history of one worker
+#[derive(Debug, Default)]
+struct WorkerWindow {
+    successes: u64,
+    failures: u64,
+    /// Consecutive ejections, scaling the next ejection time
+    ejections: u32,
+}
+
+/// A worker with its failure rate over the interval, None when not analyzed
+type WorkerRate<'a> = (&'a Arc<dyn Worker>, Option<f64>);
+
+/// Failure rate analysis across the workers of each model
+#[derive(Debug)]
+pub struct OutlierDetector {
+    config: OutlierDetectionConfig,
+    windows: Mutex<HashMap<String, WorkerWindow>>,
+}
+
+impl OutlierDetector {
+    pub fn new(config: OutlierDetectionConfig) -> Self {
+        Self {
+            config,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Analyze the outcomes since the previous call and eject outliers
+    ///
+    /// Returns the URLs of the workers ejected by this analysis.
+    pub fn analyze(&self, workers: &[Arc<dyn Worker>]) -> Vec<String> {
+        let mut windows = self.windows.lock().unwrap();
+        windows.retain(|url, _| workers.iter().any(|w| w.url() == url));
+
+        let mut by_model: HashMap<&str, Vec<WorkerRate>> = HashMap::new();
+        for worker in workers {
+            let window = windows.entry(worker.url().to_string()).or_default();
+            let successes = worker.circuit_breaker().total_successes();
+            let failures = worker.circuit_breaker().total_failures();
+            let new_successes = successes.saturating_sub(window.successes);
+            let new_failures = failures.saturating_sub(window.failures);
+            window.successes = successes;
+            window.failures = failures;
+
+            self.reinstate_if_due(worker.as_ref(), window);
+            let rate = (!worker.is_ejected()
+                && new_successes + new_failures >= self.config.min_requests)
+                .then(|| new_failures as f64 / (new_successes + new_failures) as f64);
+            by_model
+                .entry(worker.model_id())
+                .or_default()
+                .push((worker, rate));
+        }
+
+        let mut ejected = Vec::new();
+        for (model_id, model_workers) in by_model {
+            let rates: Vec<f64> = model_workers.iter().filter_map(|(_, rate)| *rate).collect();
+            if rates.len() < self.config.min_workers {
+                continue;
+            }
+            let threshold = self.threshold(&rates);
+
+            let mut outliers: Vec<(&Arc<dyn Worker>, f64)> = model_workers
+                .iter()
+                .filter_map(|(worker, rate)| rate.map(|rate| (*worker, rate)))
+                .filter(|(_, rate)| *rate > threshold && *rate >= self.config.min_failure_rate)
+                .collect();
+            outliers.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+            let max_ejected =
+                (model_workers.len() * self.config.max_ejection_percent as usize / 100).max(1);
+            let mut ejected_count = model_workers.iter().filter(|(w, _)| w.is_ejected()).count();
+            for (worker, rate) in outliers {
+                if ejected_count >= max_ejected {
+                    warn!(
+                        "Worker {} of model '{}' is an outlier ({:.1}% failures) but {} of {} workers are already ejected",
+                        worker.url(),
+                        model_id,
+                        rate * 100.0,
+                        ejected_count,
+                        model_workers.len()
+                    );
+                    continue;
+                }
+                let window = windows.get_mut(worker.url()).unwrap();
+                window.ejections += 1;
+                let ejection = self.ejection_time(window.ejections);
+                worker.set_ejected_until(Some(Instant::now() + ejection));
+                RouterMetrics::set_worker_ejected(worker.url(), true);
+                RouterMetrics::record_outlier_ejection(worker.url());
+                warn!(
+                    "Ejecting worker {} of model '{}' for {:?}: {:.1}% failures, above the {:.1}% outlier threshold",
+                    worker.url(),
+                    model_id,
+                    ejection,
+                    rate * 100.0,
+                    threshold * 100.0
+                );
+                ejected_count += 1;
+                ejected.push(worker.url().to_string());
+            }
+        }
+        ejected
+    }
+
+    /// Failure rate above which a worker is an outlier
+    fn threshold(&self, rates: &[f64]) -> f64 {
+        let n = rates.len() as f64;
+        let mean = rates.iter().sum::<f64>() / n;
+        let variance = rates.iter().map(|rate| (rate - mean).powi(2)).sum::<f64>() / n;
+        mean + self.config.stdev_factor * variance.sqrt()
+    }
+
+    /// Ejection time of a worker's `ejections`-th consecutive ejection
+    fn ejection_time(&self, ejections: u32) -> Duration {
+        let secs = self
+            .config
+            .base_ejection_secs
+            .saturating_mul(ejections as u64)
+            .min(self.config.max_ejection_secs);
+        Duration::from_secs(secs)
+    }
+
+    /// Clear an ejection that has run out, and decay the ejection count of
+    /// workers that have stayed in
+    fn reinstate_if_due(&self, worker: &dyn Worker, window: &mut WorkerWindow) {
+        match worker.ejected_until() {
+            Some(until) if Instant::now() >= until => {
+                worker.set_ejected_until(None);
+                RouterMetrics::set_worker_ejected(worker.url(), false);
+                info!("Worker {} is back from outlier ejection", worker.url());
+            }
+            Some(_) => {}
+            None => window.ejections = window.ejections.saturating_sub(1),
+        }
+    }
+}
+
+/// Start a background task that ejects outlier workers at a fixed interval
+pub fn start_outlier_detector(
+    registry: Arc<WorkerRegistry>,
+    config: OutlierDetectionConfig,
+) -> HealthChecker {
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let shutdown_clone = shutdown.clone();
+
+    let handle = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+        let detector = OutlierDetector::new(config);
+        loop {
+            interval.tick().await;
+            if shutdown_clone.load(Ordering::Acquire) {
+                debug!("Outlier detector shutting down");
+                break;
+            }
+            detector.analyze(&registry.get_all());
+        }
+    });
+
+    HealthChecker::new(handle, shutdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{BasicWorker, CircuitBreakerConfig, WorkerType};
+
+    fn worker(url: &str) -> Arc<dyn Worker> {
+        // Keep the circuit closed so only outlier detection takes workers out
+        let breaker = CircuitBreakerConfig {
+            failure_threshold: u32::MAX,
+            ..Default::default()
+        };
+        Arc::new(
+            BasicWorker::new(url.to_string(), WorkerType::Regular)
+                .with_circuit_breaker_config(breaker),
+        )
+    }
+
+    fn record(worker: &Arc<dyn Worker>, successes: usize, failures: usize) {
+        for _ in 0..successes {
+            worker.record_outcome(true);
+        }
+        for _ in 0..failures {
+            worker.record_outcome(false);
+        }
+    }
+
+    #[test]
+    fn test_ejects_worker_failing_above_fleet() {
+        let workers: Vec<Arc<dyn Worker>> = (1..=5)
+            .map(|i| worker(&format!("http://w{}:8000", i)))
+            .collect();
+        let detector = OutlierDetector::new(OutlierDetectionConfig::default());
+        // The first analysis only takes the baseline counts
+        assert!(detector.analyze(&workers).is_empty());
+
+        for worker in &workers[..4] {
+            record(worker, 98, 2);
+        }
+        record(&workers[4], 50, 50);
+        assert_eq!(detector.analyze(&workers), vec!["http://w5:8000"]);
+        assert!(workers[4].is_ejected());
+        assert!(!workers[4].is_available());
+        assert!(workers[4].is_healthy());
+        assert!(workers[..4].iter().all(|w| w.is_available()));
+
+        // The ejected worker is left out of later baselines
+        for worker in &workers[..4] {
+            record(worker, 98, 2);
+        }
+        assert!(detector.analyze(&workers).is_empty());
+    }
+
+    #[test]
+    fn test_no_ejection_without_enough_traffic_or_workers() {
+        let workers: Vec<Arc<dyn Worker>> = (1..=5)
+            .map(|i| worker(&format!("http://w{}:8000", i)))
+            .collect();
+        let detector = OutlierDetector::new(OutlierDetectionConfig::default());
+        detector.analyze(&workers);
+
+        // Too few requests to judge the failing worker
+        for worker in &workers[..4] {
+            record(worker, 100, 0);
+        }
+        record(&workers[4], 5, 5);
+        assert!(detector.analyze(&workers).is_empty());
+
+        // Too few workers with traffic for a baseline
+        for worker in &workers[..3] {
+            record(worker, 100, 0);
+        }
+        record(&workers[4], 50, 50);
+        assert!(detector.analyze(&workers).is_empty());
+    }
+
+    #[test]
+    fn test_uniform_failures_are_not_outliers() {
+        let workers: Vec<Arc<dyn Worker>> = (1..=4)
+            .map(|i| worker(&format!("http://w{}:8000", i)))
+            .collect();
+        let detector = OutlierDetector::new(OutlierDetectionConfig::default());
+        detector.analyze(&workers);
+
+        // A fleet-wide outage is not any one worker's fault
+        for worker in &workers {
+            record(worker, 50, 50);
+        }
+        assert!(detector.analyze(&workers).is_empty());
+    }
+
+    #[test]
+    fn test_ejection_limit_and_reinstatement() {
+        let workers: Vec<Arc<dyn Worker>> = (1..=6)
+            .map(|i| worker(&format!("http://w{}:8000", i)))
+            .collect();
+        let detector = OutlierDetector::new(OutlierDetectionConfig {
+            stdev_factor: 0.5,
+            ..Default::default()
+        });
+        detector.analyze(&workers);
+
+        for worker in &workers[..4] {
+            record(worker, 100, 0);
+        }
+        record(&workers[4], 50, 50);
+        record(&workers[5], 40, 60);
+        // 10% of six workers rounds down, but one worker may always be ejected
+        assert_eq!(detector.analyze(&workers), vec!["http://w6:8000"]);
+        assert!(!workers[4].is_ejected());
+
+        // An ejection that has run out is cleared by the next analysis
+        workers[5].set_ejected_until(Some(Instant::now() - Duration::from_secs(1)));
+        detector.analyze(&workers);
+        assert_eq!(workers[5].ejected_until(), None);
+    }
+
+    #[test]
+    fn test_ejection_time_grows_and_is_capped() {
+        let detector = OutlierDetector::new(OutlierDetectionConfig {
+            base_ejection_secs: 30,
+            max_ejection_secs: 100,
+            ..Default::default()
+        });
+        assert_eq!(detector.ejection_time(1), Duration::from_secs(30));
+        assert_eq!(detector.ejection_time(3), Duration::from_secs(90));
+        assert_eq!(detector.ejection_time(4), Duration::from_secs(100));
+    }
+}
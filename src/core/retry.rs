@@ -1,9 +1,13 @@
-use crate::config::types::RetryConfig;
-use axum::http::StatusCode;
+use crate::config::types::{RetryConfig, RetryOn};
+use crate::metrics::RouterMetrics;
+use crate::routers::error_response::error_response;
+use axum::http::{header::RETRY_AFTER, StatusCode};
 use axum::response::Response;
+use chrono::{DateTime, Utc};
 use rand::Rng;
-use std::time::Duration;
-use tracing::debug;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
 
 /// Check if an HTTP status code indicates a retryable error
 pub fn is_retryable_status(status: StatusCode) -> bool {
@@ -18,6 +22,39 @@ pub fn is_retryable_status(status: StatusCode) -> bool {
     )
 }
 
+/// Marks a response the router produced because the worker never answered
+///
+/// Stored in the response extensions so retry decisions can tell a failed
+/// connection apart from a worker that replied with an error status.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionFailure;
+
+/// Response for a request that failed before the worker replied
+///
+/// Timeouts become 504 and other transport errors (refused or reset
+/// connections, DNS failures) become 502. Both are retryable.
+pub fn connection_failure_response(error: &reqwest::Error) -> Response {
+    let status = if error.is_timeout() {
+        StatusCode::GATEWAY_TIMEOUT
+    } else {
+        StatusCode::BAD_GATEWAY
+    };
+    let mut response = error_response(status, format!("Request failed: {}", error));
+    response.extensions_mut().insert(ConnectionFailure);
+    response
+}
+
+/// Check if a response should be retried on another worker under `retry_on`
+pub fn is_retryable_response(response: &Response, retry_on: RetryOn) -> bool {
+    if response.extensions().get::<ConnectionFailure>().is_some() {
+        return true;
+    }
+    match retry_on {
+        RetryOn::All => is_retryable_status(response.status()),
+        RetryOn::ConnectionErrors => false,
+    }
+}
+
 /// Computes exponential backoff with optional jitter.
 #[derive(Debug, Clone)]
 pub struct BackoffCalculator;
@@ -32,6 +69,11 @@ impl BackoffCalculator {
             delay_ms = config.max_backoff_ms;
         }
 
+        if config.full_jitter {
+            let mut rng = rand::rng();
+            return Duration::from_millis(rng.random_range(0..=delay_ms));
+        }
+
         // Apply jitter in range [-j, +j]
         let jitter = config.jitter_factor.clamp(0.0, 1.0);
         if jitter > 0.0 {
@@ -48,12 +90,160 @@ impl BackoffCalculator {
     }
 }
 
+/// Backoff requested by the upstream through `Retry-After` on a 429 or 503
+/// response, capped at `max_backoff_ms`
+///
+/// Accepts both the delta-seconds and the HTTP-date forms. Returns None when the
+/// header is missing or malformed, so the caller falls back to its own backoff.
+pub fn retry_after_delay(response: &Response, config: &RetryConfig) -> Option<Duration> {
+    if !matches!(
+        response.status(),
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    ) {
+        return None;
+    }
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    let delay = parse_retry_after(value, Utc::now())?;
+    Some(delay.min(Duration::from_millis(config.max_backoff_ms)))
+}
+
+/// Parse a `Retry-After` value relative to `now`; dates in the past yield zero
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Default length of the rolling window used by [`RetryBudget`]
+pub const DEFAULT_RETRY_BUDGET_WINDOW: Duration = Duration::from_secs(10);
+
+/// Retries that are always allowed per window, so low-traffic routers can still retry
+const MIN_RETRIES_PER_WINDOW: u64 = 10;
+
+/// Number of buckets the rolling window is split into
+const RETRY_BUDGET_BUCKETS: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BudgetBucket {
+    epoch: u64,
+    requests: u64,
+    retries: u64,
+}
+
+#[derive(Debug)]
+struct BudgetWindow {
+    started: Instant,
+    buckets: [BudgetBucket; RETRY_BUDGET_BUCKETS],
+}
+
+/// Caps retries to a fraction of recent requests.
+///
+/// Shared by all requests going through a router: every request is counted once,
+/// and each retry must be admitted by [`RetryBudget::try_acquire_retry`]. Once the
+/// retry/request ratio over the rolling window exceeds `max_retry_fraction`, new
+/// retries are refused so a broad outage does not multiply load on the remaining
+/// workers. A small floor of retries per window is always allowed.
+#[derive(Debug)]
+pub struct RetryBudget {
+    max_retry_fraction: f32,
+    bucket_duration: Duration,
+    window: Mutex<BudgetWindow>,
+}
+
+impl RetryBudget {
+    pub fn new(max_retry_fraction: f32, window: Duration) -> Self {
+        let bucket_duration = (window / RETRY_BUDGET_BUCKETS as u32).max(Duration::from_millis(1));
+        Self {
+            max_retry_fraction: max_retry_fraction.max(0.0),
+            bucket_duration,
+            window: Mutex::new(BudgetWindow {
+                started: Instant::now(),
+                buckets: [BudgetBucket::default(); RETRY_BUDGET_BUCKETS],
+            }),
+        }
+    }
+
+    /// Build a budget from the retry config, if `max_retry_fraction` is set
+    pub fn from_config(config: &RetryConfig) -> Option<Self> {
+        config
+            .max_retry_fraction
+            .map(|fraction| Self::new(fraction, DEFAULT_RETRY_BUDGET_WINDOW))
+    }
+
+    pub fn max_retry_fraction(&self) -> f32 {
+        self.max_retry_fraction
+    }
+
+    /// Count a new request against the budget
+    pub fn record_request(&self) {
+        let mut window = self.window.lock().unwrap();
+        self.current_bucket(&mut window).requests += 1;
+    }
+
+    /// Try to spend a retry; returns false when the budget is exhausted
+    pub fn try_acquire_retry(&self) -> bool {
+        let mut window = self.window.lock().unwrap();
+        let (requests, retries) = self.totals(&window);
+
+        let allowed =
+            (self.max_retry_fraction as f64 * requests as f64).max(MIN_RETRIES_PER_WINDOW as f64);
+        if (retries + 1) as f64 > allowed {
+            return false;
+        }
+
+        self.current_bucket(&mut window).retries += 1;
+        true
+    }
+
+    /// Requests and retries observed within the rolling window
+    pub fn stats(&self) -> (u64, u64) {
+        let window = self.window.lock().unwrap();
+        self.totals(&window)
+    }
+
+    fn epoch(&self, window: &BudgetWindow) -> u64 {
+        (window.started.elapsed().as_nanos() / self.bucket_duration.as_nanos()) as u64
+    }
+
+    fn current_bucket<'a>(&self, window: &'a mut BudgetWindow) -> &'a mut BudgetBucket {
+        let epoch = self.epoch(window);
+        let bucket = &mut window.buckets[(epoch % RETRY_BUDGET_BUCKETS as u64) as usize];
+        if bucket.epoch != epoch {
+            *bucket = BudgetBucket {
+                epoch,
+                ..Default::default()
+            };
+        }
+        bucket
+    }
+
+    fn totals(&self, window: &BudgetWindow) -> (u64, u64) {
+        let epoch = self.epoch(window);
+        window
+            .buckets
+            .iter()
+            .filter(|b| b.epoch + (RETRY_BUDGET_BUCKETS as u64) > epoch)
+            .fold((0, 0), |(requests, retries), b| {
+                (requests + b.requests, retries + b.retries)
+            })
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum RetryError {
     #[error("no available workers")]
     NoAvailableWorkers,
     #[error("maximum retry attempts exceeded")]
     MaxRetriesExceeded,
+    #[error("retry budget exhausted")]
+    RetryBudgetExhausted,
 }
 
 /// A thin async retry executor for generic operations.
@@ -100,7 +290,8 @@ impl RetryExecutor {
     /// - `should_retry(&response, attempt)`: decide if the given response should be retried
     ///   (e.g., based on HTTP status). Returning false short-circuits and returns the response.
     /// - `on_backoff(delay, next_attempt)`: called before sleeping between attempts.
-    ///   Use this to record metrics.
+    ///   Use this to record metrics. The delay honors a `Retry-After` header on 429/503
+    ///   responses (capped at `max_backoff_ms`) and otherwise follows the backoff config.
     /// - `on_exhausted()`: called when the executor has exhausted all retry attempts.
     ///
     /// Example:
@@ -120,6 +311,36 @@ impl RetryExecutor {
     /// ```
     pub async fn execute_response_with_retry<Op, Fut, ShouldRetry, OnBackoff, OnExhausted>(
         config: &RetryConfig,
+        operation: Op,
+        should_retry: ShouldRetry,
+        on_backoff: OnBackoff,
+        on_exhausted: OnExhausted,
+    ) -> Response
+    where
+        Op: FnMut(u32) -> Fut,
+        Fut: std::future::Future<Output = Response>,
+        ShouldRetry: Fn(&Response, u32) -> bool,
+        OnBackoff: Fn(Duration, u32),
+        OnExhausted: FnMut(),
+    {
+        Self::execute_response_with_retry_budget(
+            config,
+            None,
+            operation,
+            should_retry,
+            on_backoff,
+            on_exhausted,
+        )
+        .await
+    }
+
+    /// Same as [`RetryExecutor::execute_response_with_retry`], but every retry must
+    /// also be admitted by the shared `budget`. When the budget refuses a retry the
+    /// last response is returned immediately and the budget-exhausted metric is
+    /// recorded instead of calling `on_exhausted`.
+    pub async fn execute_response_with_retry_budget<Op, Fut, ShouldRetry, OnBackoff, OnExhausted>(
+        config: &RetryConfig,
+        budget: Option<&RetryBudget>,
         mut operation: Op,
         should_retry: ShouldRetry,
         on_backoff: OnBackoff,
@@ -134,6 +355,10 @@ impl RetryExecutor {
     {
         let max = config.max_retries.max(1);
 
+        if let Some(budget) = budget {
+            budget.record_request();
+        }
+
         let mut attempt: u32 = 0;
         loop {
             let response = operation(attempt).await;
@@ -149,10 +374,24 @@ impl RetryExecutor {
                 return response;
             }
 
+            if let Some(budget) = budget {
+                if !budget.try_acquire_retry() {
+                    warn!(
+                        attempt = attempt,
+                        max_retry_fraction = budget.max_retry_fraction(),
+                        "Retry budget exhausted, failing fast"
+                    );
+                    RouterMetrics::record_retry_budget_exhausted();
+                    return response;
+                }
+            }
+
             // Backoff before next attempt
             let next_attempt = attempt + 1;
-            // Compute delay based on the number of failures so far (0-indexed)
-            let delay = BackoffCalculator::calculate_delay(config, attempt);
+            // Honor the upstream's Retry-After, otherwise compute delay based on
+            // the number of failures so far (0-indexed)
+            let delay = retry_after_delay(&response, config)
+                .unwrap_or_else(|| BackoffCalculator::calculate_delay(config, attempt));
             debug!(
                 attempt = attempt,
                 next_attempt = next_attempt,
@@ -182,6 +421,9 @@ mod tests {
             max_backoff_ms: 4,
             backoff_multiplier: 2.0,
             jitter_factor: 0.0,
+            full_jitter: false,
+            max_retry_fraction: None,
+            retry_on: RetryOn::All,
         }
     }
 
@@ -193,6 +435,9 @@ mod tests {
             max_backoff_ms: 250,
             backoff_multiplier: 2.0,
             jitter_factor: 0.0,
+            full_jitter: false,
+            max_retry_fraction: None,
+            retry_on: RetryOn::All,
         };
         // attempt=0 => 100ms
         assert_eq!(
@@ -224,6 +469,9 @@ mod tests {
             max_backoff_ms: 10_000,
             backoff_multiplier: 2.0,
             jitter_factor: 0.5,
+            full_jitter: false,
+            max_retry_fraction: None,
+            retry_on: RetryOn::All,
         };
         // attempt=2 => base 400ms, jitter in [0.5x, 1.5x]
         let base = 400.0;
@@ -233,6 +481,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_backoff_with_full_jitter_within_bounds() {
+        let cfg = RetryConfig {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 10_000,
+            jitter_factor: 0.5,
+            full_jitter: true,
+            ..base_retry_config()
+        };
+        // attempt=2 => base 400ms, full jitter in [0, 400ms]
+        let delays: Vec<Duration> = (0..50)
+            .map(|_| BackoffCalculator::calculate_delay(&cfg, 2))
+            .collect();
+        assert!(delays.iter().all(|d| *d <= Duration::from_millis(400)));
+        assert!(
+            delays.iter().any(|d| *d < Duration::from_millis(200)),
+            "full jitter reaches below the proportional jitter range"
+        );
+    }
+
     #[tokio::test]
     async fn test_execute_with_retry_success_after_failures() {
         let cfg = base_retry_config();
@@ -406,4 +674,208 @@ mod tests {
         assert_eq!(backoffs.load(Ordering::Relaxed), cfg.max_retries - 1);
         assert_eq!(exhausted.load(Ordering::Relaxed), 1);
     }
+
+    #[tokio::test]
+    async fn test_connection_failures_are_retryable() {
+        // Nothing listens on port 1, so the connection is refused
+        let error = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+        let response = connection_failure_response(&error);
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert!(is_retryable_response(&response, RetryOn::All));
+        assert!(is_retryable_response(&response, RetryOn::ConnectionErrors));
+
+        // A worker that answers with an error status is only retried under `All`
+        let response = (StatusCode::BAD_GATEWAY, "upstream error").into_response();
+        assert!(is_retryable_response(&response, RetryOn::All));
+        assert!(!is_retryable_response(&response, RetryOn::ConnectionErrors));
+        let response = (StatusCode::BAD_REQUEST, "bad request").into_response();
+        assert!(!is_retryable_response(&response, RetryOn::All));
+    }
+
+    #[test]
+    fn test_parse_retry_after_forms() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        // Dates in the past mean "retry now"
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("-5", now), None);
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_retry_after_delay_status_and_cap() {
+        let cfg = RetryConfig {
+            max_backoff_ms: 2000,
+            ..base_retry_config()
+        };
+        let response = |status: StatusCode, retry_after: &str| {
+            let mut response = status.into_response();
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, retry_after.parse().unwrap());
+            response
+        };
+
+        assert_eq!(
+            retry_after_delay(&response(StatusCode::SERVICE_UNAVAILABLE, "1"), &cfg),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            retry_after_delay(&response(StatusCode::TOO_MANY_REQUESTS, "60"), &cfg),
+            Some(Duration::from_millis(2000))
+        );
+        assert_eq!(
+            retry_after_delay(&response(StatusCode::BAD_GATEWAY, "1"), &cfg),
+            None
+        );
+        assert_eq!(
+            retry_after_delay(&StatusCode::SERVICE_UNAVAILABLE.into_response(), &cfg),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_response_with_retry_honors_retry_after() {
+        let cfg = RetryConfig {
+            max_retries: 2,
+            initial_backoff_ms: 5_000,
+            max_backoff_ms: 10_000,
+            ..base_retry_config()
+        };
+        let delays = Arc::new(Mutex::new(Vec::new()));
+
+        let start = Instant::now();
+        let response = RetryExecutor::execute_response_with_retry(
+            &cfg,
+            |attempt| async move {
+                if attempt == 0 {
+                    let mut response = (StatusCode::SERVICE_UNAVAILABLE, "busy").into_response();
+                    response
+                        .headers_mut()
+                        .insert(RETRY_AFTER, "0".parse().unwrap());
+                    response
+                } else {
+                    (StatusCode::OK, "ok").into_response()
+                }
+            },
+            |res, _attempt| is_retryable_status(res.status()),
+            {
+                let delays = delays.clone();
+                move |delay, _next_attempt| delays.lock().unwrap().push(delay)
+            },
+            || {},
+        )
+        .await;
+
+        // The worker's Retry-After replaces the 5s configured backoff
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*delays.lock().unwrap(), vec![Duration::ZERO]);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_retry_budget_allows_floor_then_caps() {
+        let budget = RetryBudget::new(0.1, Duration::from_secs(60));
+        for _ in 0..50 {
+            budget.record_request();
+        }
+
+        // 10% of 50 requests is below the floor, so the floor applies
+        for _ in 0..MIN_RETRIES_PER_WINDOW {
+            assert!(budget.try_acquire_retry());
+        }
+        assert!(!budget.try_acquire_retry());
+
+        // More traffic raises the cap: 10% of 200 requests = 20 retries
+        for _ in 0..150 {
+            budget.record_request();
+        }
+        for _ in 0..10 {
+            assert!(budget.try_acquire_retry());
+        }
+        assert!(!budget.try_acquire_retry());
+        assert_eq!(budget.stats(), (200, 20));
+    }
+
+    #[test]
+    fn test_retry_budget_window_expires() {
+        let budget = RetryBudget::new(0.0, Duration::from_millis(50));
+        for _ in 0..MIN_RETRIES_PER_WINDOW {
+            assert!(budget.try_acquire_retry());
+        }
+        assert!(!budget.try_acquire_retry());
+
+        std::thread::sleep(Duration::from_millis(80));
+        assert_eq!(budget.stats(), (0, 0));
+        assert!(budget.try_acquire_retry());
+    }
+
+    #[test]
+    fn test_retry_budget_from_config() {
+        let cfg = base_retry_config();
+        assert!(RetryBudget::from_config(&cfg).is_none());
+
+        let cfg = RetryConfig {
+            max_retry_fraction: Some(0.2),
+            retry_on: RetryOn::All,
+            ..base_retry_config()
+        };
+        let budget = RetryBudget::from_config(&cfg).unwrap();
+        assert!((budget.max_retry_fraction() - 0.2).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_execute_response_with_retry_budget_fails_fast() {
+        let cfg = RetryConfig {
+            max_retries: 5,
+            ..base_retry_config()
+        };
+        let budget = RetryBudget::new(0.0, Duration::from_secs(60));
+        // Spend the whole floor up front
+        while budget.try_acquire_retry() {}
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let exhausted = Arc::new(AtomicU32::new(0));
+
+        let response = RetryExecutor::execute_response_with_retry_budget(
+            &cfg,
+            Some(&budget),
+            {
+                let calls = calls.clone();
+                move |_attempt| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    async move { (StatusCode::SERVICE_UNAVAILABLE, "fail").into_response() }
+                }
+            },
+            |_res, _attempt| true,
+            |_delay, _next_attempt| {},
+            {
+                let exhausted = exhausted.clone();
+                move || {
+                    exhausted.fetch_add(1, Ordering::Relaxed);
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        // Only the initial attempt runs; the retry is refused by the budget
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        assert_eq!(exhausted.load(Ordering::Relaxed), 0);
+        assert_eq!(budget.stats().0, 1);
+    }
 }
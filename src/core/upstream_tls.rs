@@ -0,0 +1,146 @@
+//! TLS identity shared by all HTTP clients that talk to workers
+//!
+//! Besides the main proxy client, workers are contacted by separately built
+//! clients for startup probes, health checks and server info queries. All of
+//! them start from [`client_builder`], so once [`configure`] has run they
+//! present the same client certificate and trust the same CA bundle.
+
+use crate::config::UpstreamTlsConfig;
+use reqwest::{Certificate, ClientBuilder, Identity};
+use std::sync::OnceLock;
+use tracing::{info, warn};
+
+static UPSTREAM_TLS: OnceLock<UpstreamTls> = OnceLock::new();
+
+/// Loaded client identity and extra trust roots
+#[derive(Clone, Default)]
+pub struct UpstreamTls {
+    identity: Option<Identity>,
+    roots: Vec<Certificate>,
+}
+
+impl UpstreamTls {
+    /// Read the certificate, key and CA files named in `config`
+    pub fn load(config: &UpstreamTlsConfig) -> Result<Self, String> {
+        let identity = match (&config.client_cert_path, &config.client_key_path) {
+            (Some(cert_path), Some(key_path)) => {
+                let cert = read_pem(cert_path)?;
+                let key = read_pem(key_path)?;
+                Some(Identity::from_pkcs8_pem(&cert, &key).map_err(|e| {
+                    format!(
+                        "Invalid client certificate {} or key {}: {}",
+                        cert_path, key_path, e
+                    )
+                })?)
+            }
+            (None, None) => None,
+            _ => {
+                return Err(
+                    "Upstream TLS client certificate and key must be set together".to_string(),
+                )
+            }
+        };
+
+        let roots = match &config.ca_cert_path {
+            Some(ca_path) => Certificate::from_pem_bundle(&read_pem(ca_path)?)
+                .map_err(|e| format!("Invalid CA bundle {}: {}", ca_path, e))?,
+            None => Vec::new(),
+        };
+
+        Ok(Self { identity, roots })
+    }
+
+    /// Apply the identity and trust roots to a client builder
+    pub fn apply(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        if let Some(identity) = &self.identity {
+            builder = builder.identity(identity.clone());
+        }
+        for root in &self.roots {
+            builder = builder.add_root_certificate(root.clone());
+        }
+        builder
+    }
+}
+
+fn read_pem(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))
+}
+
+/// Load the upstream TLS settings for the rest of the process
+///
+/// Must run before any worker client is built. Later calls keep the settings
+/// from the first one.
+pub fn configure(config: &UpstreamTlsConfig) -> Result<(), String> {
+    let tls = UpstreamTls::load(config)?;
+    let has_identity = tls.identity.is_some();
+    let roots = tls.roots.len();
+    if UPSTREAM_TLS.set(tls).is_err() {
+        warn!("Upstream TLS is already configured, ignoring new settings");
+        return Ok(());
+    }
+    info!(
+        "Upstream TLS configured (client certificate: {}, extra CA certificates: {})",
+        has_identity, roots
+    );
+    Ok(())
+}
+
+/// A `reqwest` client builder carrying the configured upstream TLS settings
+///
+/// Response decompression starts disabled so workers are only asked for
+/// compressed bodies where a caller opts in with `.gzip(true)`/`.brotli(true)`.
+pub fn client_builder() -> ClientBuilder {
+    let builder = reqwest::Client::builder().no_gzip().no_brotli();
+    match UPSTREAM_TLS.get() {
+        Some(tls) => tls.apply(builder),
+        None => builder,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_without_files_is_empty() {
+        let tls = UpstreamTls::load(&UpstreamTlsConfig::default()).unwrap();
+        assert!(tls.identity.is_none());
+        assert!(tls.roots.is_empty());
+        assert!(tls.apply(reqwest::Client::builder()).build().is_ok());
+    }
+
+    #[test]
+    fn test_load_reports_missing_files() {
+        let err = UpstreamTls::load(&UpstreamTlsConfig {
+            ca_cert_path: Some("/nonexistent/ca.pem".to_string()),
+            ..Default::default()
+        })
+        .err()
+        .unwrap();
+        assert!(err.contains("/nonexistent/ca.pem"));
+
+        let err = UpstreamTls::load(&UpstreamTlsConfig {
+            client_cert_path: Some("/nonexistent/client.pem".to_string()),
+            ..Default::default()
+        })
+        .err()
+        .unwrap();
+        assert!(err.contains("must be set together"));
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_ca_bundle() {
+        let path = std::env::temp_dir().join(format!("upstream-tls-{}.pem", std::process::id()));
+        std::fs::write(
+            &path,
+            "-----BEGIN CERTIFICATE-----\nbm90IGEgY2VydA==\n-----END CERTIFICATE-----\n",
+        )
+        .unwrap();
+        let result = UpstreamTls::load(&UpstreamTlsConfig {
+            ca_cert_path: Some(path.to_string_lossy().into_owned()),
+            ..Default::default()
+        });
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err_and(|e| e.contains("Invalid CA bundle")));
+    }
+}
@@ -1,17 +1,20 @@
 use super::{CircuitBreaker, CircuitBreakerConfig, WorkerError, WorkerResult};
+use crate::config::HealthCheckMethod;
+use crate::grpc::health_proto::health_check_response::ServingStatus;
 use crate::grpc::VllmSchedulerClient;
 use crate::metrics::RouterMetrics;
 use async_trait::async_trait;
 use futures;
 use serde_json;
 use std::fmt;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::{Arc, LazyLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
 // Shared HTTP client for worker operations (health checks, server info, etc.)
 static WORKER_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
-    reqwest::Client::builder()
+    super::upstream_tls::client_builder()
         .timeout(std::time::Duration::from_secs(30)) // Default timeout, overridden per request
         .build()
         .expect("Failed to create worker HTTP client")
@@ -35,6 +38,44 @@ pub trait Worker: Send + Sync + fmt::Debug {
     /// Set the worker's health status
     fn set_healthy(&self, healthy: bool);
 
+    /// Check if the worker is draining (kept registered but excluded from new selections)
+    fn is_draining(&self) -> bool;
+
+    /// Mark the worker as draining so it stops receiving new requests
+    fn set_draining(&self, draining: bool);
+
+    /// Check if the worker is enabled (disabled workers stay registered and
+    /// health-checked but are never selected)
+    fn is_enabled(&self) -> bool;
+
+    /// Enable or disable routing to the worker
+    fn set_enabled(&self, enabled: bool);
+
+    /// Check if the worker is warming up (registered but not selected until
+    /// its warmup requests finish)
+    fn is_warming(&self) -> bool;
+
+    /// Mark the worker as warming up or done warming up
+    fn set_warming(&self, warming: bool);
+
+    /// Model version last reported by the worker, if it reports one
+    fn model_version(&self) -> Option<String>;
+
+    /// Record the model version reported by the worker
+    fn set_model_version(&self, version: Option<String>);
+
+    /// When the worker's outlier ejection ends, if it was ever ejected
+    fn ejected_until(&self) -> Option<Instant>;
+
+    /// Eject the worker from selection until `until` (None = reinstate)
+    fn set_ejected_until(&self, until: Option<Instant>);
+
+    /// Check if the worker is ejected as an outlier (see [`crate::core::outlier_detection`])
+    fn is_ejected(&self) -> bool {
+        self.ejected_until()
+            .is_some_and(|until| Instant::now() < until)
+    }
+
     /// Perform an async health check on the worker
     async fn check_health_async(&self) -> WorkerResult<()>;
 
@@ -60,6 +101,20 @@ pub trait Worker: Send + Sync + fmt::Debug {
     /// Decrement the load counter
     fn decrement_load(&self);
 
+    /// Add `weight` to the load counter, for requests that count as several
+    fn increment_load_by(&self, weight: usize) {
+        for _ in 0..weight {
+            self.increment_load();
+        }
+    }
+
+    /// Remove `weight` from the load counter
+    fn decrement_load_by(&self, weight: usize) {
+        for _ in 0..weight {
+            self.decrement_load();
+        }
+    }
+
     /// Reset the load counter to 0 (for sync/recovery)
     fn reset_load(&self) {
         // Default implementation - does nothing
@@ -78,9 +133,30 @@ pub trait Worker: Send + Sync + fmt::Debug {
     /// Get the circuit breaker for this worker
     fn circuit_breaker(&self) -> &CircuitBreaker;
 
-    /// Check if the worker is available (healthy + circuit closed/half-open)
+    /// Get the moving average of this worker's request latency
+    fn latency_ewma(&self) -> &LatencyEwma;
+
+    /// Fold a completed request's latency into the worker's moving average
+    ///
+    /// `decay` is the weight kept by the previous average, in (0, 1).
+    fn record_latency(&self, latency: Duration, decay: f64) {
+        let average = self.latency_ewma().observe(latency, decay);
+        RouterMetrics::set_worker_latency_ewma(self.url(), average);
+    }
+
+    /// Moving average request latency, or None before any request completed
+    fn ewma_latency(&self) -> Option<Duration> {
+        self.latency_ewma().get()
+    }
+
+    /// Check if the worker is available (healthy + enabled + not draining + not warming + not ejected + circuit closed/half-open)
     fn is_available(&self) -> bool {
-        self.is_healthy() && self.circuit_breaker().can_execute()
+        self.is_healthy()
+            && self.is_enabled()
+            && !self.is_draining()
+            && !self.is_warming()
+            && !self.is_ejected()
+            && self.circuit_breaker().can_execute()
     }
 
     /// Record the outcome of a request to this worker
@@ -89,31 +165,16 @@ pub trait Worker: Send + Sync + fmt::Debug {
         let outcome_str = if success { "success" } else { "failure" };
         RouterMetrics::record_cb_outcome(self.url(), outcome_str);
 
-        // Record into circuit breaker and infer state change for metrics
-        let before = self.circuit_breaker().state();
+        // State transitions are reported by the circuit breaker itself
         self.circuit_breaker().record_outcome(success);
-        let after = self.circuit_breaker().state();
-
-        if before != after {
-            let from = match before {
-                crate::core::CircuitState::Closed => "closed",
-                crate::core::CircuitState::Open => "open",
-                crate::core::CircuitState::HalfOpen => "half_open",
-            };
-            let to = match after {
-                crate::core::CircuitState::Closed => "closed",
-                crate::core::CircuitState::Open => "open",
-                crate::core::CircuitState::HalfOpen => "half_open",
-            };
-            RouterMetrics::record_cb_state_transition(self.url(), from, to);
-        }
+    }
 
-        let state_code = match self.circuit_breaker().state() {
-            crate::core::CircuitState::Closed => 0u8,
-            crate::core::CircuitState::Open => 1u8,
-            crate::core::CircuitState::HalfOpen => 2u8,
-        };
-        RouterMetrics::set_cb_state(self.url(), state_code);
+    /// Force the circuit breaker closed and clear its failure counts
+    ///
+    /// For operators who know the worker has recovered before the breaker's
+    /// open timeout has elapsed.
+    fn reset_circuit_breaker(&self) {
+        self.circuit_breaker().reset();
     }
 
     // === DP-aware methods ===
@@ -212,6 +273,26 @@ pub trait Worker: Send + Sync + fmt::Debug {
             .get("chat_template")
             .map(|s| s.as_str())
     }
+
+    /// Whether the worker carries every required tag
+    ///
+    /// A tag with a value must match exactly; a tag without one only needs
+    /// to be present.
+    fn has_tags(&self, required: &[(String, Option<String>)]) -> bool {
+        let labels = &self.metadata().labels;
+        required.iter().all(|(key, value)| match value {
+            Some(value) => labels.get(key) == Some(value),
+            None => labels.contains_key(key),
+        })
+    }
+
+    /// Whether the worker is reached over `mode`, ignoring any gRPC port
+    ///
+    /// Routers share one registry in IGW mode, so each skips workers it
+    /// cannot talk to.
+    fn speaks(&self, mode: &ConnectionMode) -> bool {
+        std::mem::discriminant(&self.connection_mode()) == std::mem::discriminant(mode)
+    }
 }
 
 /// Connection mode for worker communication
@@ -265,6 +346,56 @@ impl fmt::Display for WorkerType {
     }
 }
 
+/// Exponentially weighted moving average of request latency
+///
+/// The average is kept as f64 seconds in an atomic so request paths can
+/// update it without locking; NaN means no sample has been recorded.
+#[derive(Debug)]
+pub struct LatencyEwma {
+    secs_bits: AtomicU64,
+}
+
+impl LatencyEwma {
+    pub fn new() -> Self {
+        Self {
+            secs_bits: AtomicU64::new(f64::NAN.to_bits()),
+        }
+    }
+
+    /// Fold in a sample and return the new average
+    ///
+    /// The first sample becomes the average; later ones are weighted by
+    /// `1 - decay`.
+    pub fn observe(&self, sample: Duration, decay: f64) -> Duration {
+        let sample = sample.as_secs_f64();
+        let decay = decay.clamp(0.0, 1.0);
+        let mut average = sample;
+        let _ = self
+            .secs_bits
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                let current = f64::from_bits(bits);
+                average = if current.is_nan() {
+                    sample
+                } else {
+                    decay * current + (1.0 - decay) * sample
+                };
+                Some(average.to_bits())
+            });
+        Duration::from_secs_f64(average)
+    }
+
+    pub fn get(&self) -> Option<Duration> {
+        let current = f64::from_bits(self.secs_bits.load(Ordering::Relaxed));
+        (!current.is_nan()).then(|| Duration::from_secs_f64(current))
+    }
+}
+
+impl Default for LatencyEwma {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Health check configuration
 #[derive(Debug, Clone)]
 pub struct HealthConfig {
@@ -278,6 +409,12 @@ pub struct HealthConfig {
     pub failure_threshold: u32,
     /// Number of consecutive successes before marking healthy
     pub success_threshold: u32,
+    /// HTTP method for the health check request
+    pub method: HealthCheckMethod,
+    /// Body the response must match (see [`HealthBodyMatcher::parse`])
+    pub expected_body: Option<String>,
+    /// Also require a one-token completion (see [`HealthConfig::probe_generation`])
+    pub generation_probe: bool,
 }
 
 impl Default for HealthConfig {
@@ -288,6 +425,142 @@ impl Default for HealthConfig {
             endpoint: "/health".to_string(),
             failure_threshold: 3,
             success_threshold: 2,
+            method: HealthCheckMethod::Get,
+            expected_body: None,
+            generation_probe: false,
+        }
+    }
+}
+
+impl HealthConfig {
+    /// Build the health check request for a worker base URL
+    pub fn request(&self, client: &reqwest::Client, base_url: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}{}", base_url, self.endpoint);
+        match self.method {
+            HealthCheckMethod::Get => client.get(url),
+            HealthCheckMethod::Post => client.post(url),
+        }
+    }
+
+    /// Check a health check response, returning why it is unhealthy if it is
+    ///
+    /// The status must be 2xx and, when an expected body is configured, the
+    /// body must match it.
+    pub async fn check_response(&self, response: reqwest::Response) -> Result<(), String> {
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("status: {}", status));
+        }
+        let Some(expected) = &self.expected_body else {
+            return Ok(());
+        };
+        let matcher = HealthBodyMatcher::parse(expected)?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("failed to read body: {}", e))?;
+        if matcher.matches(&body) {
+            Ok(())
+        } else {
+            Err(format!("body does not match '{}'", expected))
+        }
+    }
+
+    /// Ask the worker for a one-token completion, returning why it failed if it did
+    ///
+    /// A worker process can answer `/health` while its model is unable to
+    /// generate; this only passes when tokens actually come back within the
+    /// health check timeout.
+    pub async fn probe_generation(
+        &self,
+        client: &reqwest::Client,
+        base_url: &str,
+        model_id: Option<&str>,
+    ) -> Result<(), String> {
+        let mut body = serde_json::json!({
+            "prompt": "ping",
+            "max_tokens": 1,
+            "temperature": 0,
+        });
+        if let Some(model_id) = model_id {
+            body["model"] = serde_json::Value::from(model_id);
+        }
+        let response = client
+            .post(format!("{}/v1/completions", base_url))
+            .json(&body)
+            .timeout(std::time::Duration::from_secs(self.timeout_secs))
+            .send()
+            .await
+            .map_err(|e| format!("generation probe failed: {}", e))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("generation probe status: {}", status));
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("generation probe returned invalid JSON: {}", e))?;
+        let generated = match body.pointer("/usage/completion_tokens") {
+            Some(tokens) => tokens.as_u64().is_some_and(|tokens| tokens > 0),
+            None => body["choices"].as_array().is_some_and(|c| !c.is_empty()),
+        };
+        if generated {
+            Ok(())
+        } else {
+            Err("generation probe returned no tokens".to_string())
+        }
+    }
+}
+
+/// Expected content of a health check response body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthBodyMatcher {
+    /// The body must contain this text
+    Contains(String),
+    /// The JSON value at this pointer must equal the text
+    JsonField { pointer: String, value: String },
+}
+
+impl HealthBodyMatcher {
+    /// Parse `$.path.to.field=value` as a JSON field match, anything else as a substring
+    ///
+    /// Path segments are object keys or array indices. A string field is
+    /// compared with `value` as is, any other JSON value with `value` parsed as JSON.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let Some(path) = spec.strip_prefix("$.") else {
+            if spec.is_empty() {
+                return Err("expected body must not be empty".to_string());
+            }
+            return Ok(Self::Contains(spec.to_string()));
+        };
+        let invalid = || format!("JSON path matcher '{}' must look like $.field=value", spec);
+        let (path, value) = path.split_once('=').ok_or_else(invalid)?;
+        if path.split('.').any(str::is_empty) {
+            return Err(invalid());
+        }
+        let pointer = path
+            .split('.')
+            .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+            .collect();
+        Ok(Self::JsonField {
+            pointer,
+            value: value.to_string(),
+        })
+    }
+
+    pub fn matches(&self, body: &str) -> bool {
+        match self {
+            Self::Contains(text) => body.contains(text.as_str()),
+            Self::JsonField { pointer, value } => serde_json::from_str::<serde_json::Value>(body)
+                .ok()
+                .and_then(|json| {
+                    json.pointer(pointer).map(|found| match found {
+                        serde_json::Value::String(s) => s == value,
+                        other => serde_json::from_str::<serde_json::Value>(value)
+                            .is_ok_and(|expected| expected == *other),
+                    })
+                })
+                .unwrap_or(false),
         }
     }
 }
@@ -314,9 +587,15 @@ pub struct BasicWorker {
     load_counter: Arc<AtomicUsize>,
     processed_counter: Arc<AtomicUsize>,
     healthy: Arc<AtomicBool>,
+    draining: Arc<AtomicBool>,
+    enabled: Arc<AtomicBool>,
+    warming: Arc<AtomicBool>,
+    model_version: Arc<RwLock<Option<String>>>,
+    ejected_until: Arc<RwLock<Option<Instant>>>,
     consecutive_failures: Arc<AtomicUsize>,
     consecutive_successes: Arc<AtomicUsize>,
     circuit_breaker: CircuitBreaker,
+    latency_ewma: Arc<LatencyEwma>,
     /// Optional gRPC client for gRPC workers
     grpc_client: Option<Arc<Mutex<VllmSchedulerClient>>>,
 }
@@ -326,6 +605,8 @@ impl fmt::Debug for BasicWorker {
         f.debug_struct("BasicWorker")
             .field("metadata", &self.metadata)
             .field("healthy", &self.healthy.load(Ordering::Relaxed))
+            .field("draining", &self.draining.load(Ordering::Relaxed))
+            .field("enabled", &self.enabled.load(Ordering::Relaxed))
             .field("circuit_breaker", &self.circuit_breaker)
             .field("has_grpc_client", &self.grpc_client.is_some())
             .finish()
@@ -355,9 +636,15 @@ impl BasicWorker {
             load_counter: Arc::new(AtomicUsize::new(0)),
             processed_counter: Arc::new(AtomicUsize::new(0)),
             healthy: Arc::new(AtomicBool::new(true)),
+            draining: Arc::new(AtomicBool::new(false)),
+            enabled: Arc::new(AtomicBool::new(true)),
+            warming: Arc::new(AtomicBool::new(false)),
+            model_version: Arc::new(RwLock::new(None)),
+            ejected_until: Arc::new(RwLock::new(None)),
             consecutive_failures: Arc::new(AtomicUsize::new(0)),
             consecutive_successes: Arc::new(AtomicUsize::new(0)),
-            circuit_breaker: CircuitBreaker::new(),
+            circuit_breaker: CircuitBreaker::new().for_worker(url),
+            latency_ewma: Arc::new(LatencyEwma::new()),
             grpc_client: None,
         }
     }
@@ -373,7 +660,8 @@ impl BasicWorker {
     }
 
     pub fn with_circuit_breaker_config(mut self, config: CircuitBreakerConfig) -> Self {
-        self.circuit_breaker = CircuitBreaker::with_config(config);
+        self.circuit_breaker =
+            CircuitBreaker::with_config(config).for_worker(self.metadata.url.clone());
         self
     }
 
@@ -428,6 +716,46 @@ impl Worker for BasicWorker {
         RouterMetrics::set_worker_health(self.url(), healthy);
     }
 
+    fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+
+    fn set_draining(&self, draining: bool) {
+        self.draining.store(draining, Ordering::Release);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Acquire)
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Release);
+    }
+
+    fn is_warming(&self) -> bool {
+        self.warming.load(Ordering::Acquire)
+    }
+
+    fn set_warming(&self, warming: bool) {
+        self.warming.store(warming, Ordering::Release);
+    }
+
+    fn model_version(&self) -> Option<String> {
+        self.model_version.read().unwrap().clone()
+    }
+
+    fn set_model_version(&self, version: Option<String>) {
+        *self.model_version.write().unwrap() = version;
+    }
+
+    fn ejected_until(&self) -> Option<Instant> {
+        *self.ejected_until.read().unwrap()
+    }
+
+    fn set_ejected_until(&self, until: Option<Instant>) {
+        *self.ejected_until.write().unwrap() = until;
+    }
+
     async fn check_health_async(&self) -> WorkerResult<()> {
         use std::time::Duration;
 
@@ -435,33 +763,88 @@ impl Worker for BasicWorker {
             ConnectionMode::Http => {
                 // Perform HTTP health check
                 let url = self.normalised_url()?;
-                let health_url = format!("{}{}", url, self.metadata.health_config.endpoint);
-                let timeout = Duration::from_secs(self.metadata.health_config.timeout_secs);
+                let health_config = &self.metadata.health_config;
+                let timeout = Duration::from_secs(health_config.timeout_secs);
 
                 // Use the shared client with a custom timeout for this request
-                match WORKER_CLIENT.get(&health_url).timeout(timeout).send().await {
-                    Ok(response) => response.status().is_success(),
+                match health_config
+                    .request(&WORKER_CLIENT, url)
+                    .timeout(timeout)
+                    .send()
+                    .await
+                {
+                    Ok(response) => match health_config.check_response(response).await {
+                        Ok(()) if health_config.generation_probe => {
+                            let model_id = Some(self.model_id()).filter(|m| *m != "unknown");
+                            match health_config
+                                .probe_generation(&WORKER_CLIENT, url, model_id)
+                                .await
+                            {
+                                Ok(()) => true,
+                                Err(reason) => {
+                                    tracing::debug!(
+                                        "Health check failed for {}: {}",
+                                        self.metadata.url,
+                                        reason
+                                    );
+                                    false
+                                }
+                            }
+                        }
+                        Ok(()) => true,
+                        Err(reason) => {
+                            tracing::debug!(
+                                "Health check failed for {}: {}",
+                                self.metadata.url,
+                                reason
+                            );
+                            false
+                        }
+                    },
                     Err(_) => false,
                 }
             }
             ConnectionMode::Grpc { .. } => {
-                // Perform gRPC health check
+                // Perform gRPC health check with the standard grpc.health.v1
+                // protocol, falling back to the scheduler's HealthCheck RPC
+                // for servers that do not implement it
                 if let Some(grpc_client) = &self.grpc_client {
                     let mut client = grpc_client.lock().await;
-                    match client.health_check().await {
-                        Ok(response) => {
+                    let timeout = Duration::from_secs(self.metadata.health_config.timeout_secs);
+                    match client.check_serving("", timeout).await {
+                        Ok(status) => {
                             tracing::debug!(
-                                "gRPC health check succeeded for {}: healthy={}",
+                                "gRPC health check for {}: {:?}",
                                 self.metadata.url,
-                                response.healthy
+                                status
                             );
-                            response.healthy
+                            status == ServingStatus::Serving
                         }
-                        Err(e) => {
+                        Err(status) if status.code() == tonic::Code::Unimplemented => {
+                            match client.health_check().await {
+                                Ok(response) => {
+                                    tracing::debug!(
+                                        "gRPC health check succeeded for {}: healthy={}",
+                                        self.metadata.url,
+                                        response.healthy
+                                    );
+                                    response.healthy
+                                }
+                                Err(e) => {
+                                    tracing::warn!(
+                                        "gRPC health check RPC failed for {}: {:?}",
+                                        self.metadata.url,
+                                        e
+                                    );
+                                    false
+                                }
+                            }
+                        }
+                        Err(status) => {
                             tracing::warn!(
-                                "gRPC health check RPC failed for {}: {:?}",
+                                "gRPC health check RPC failed for {}: {}",
                                 self.metadata.url,
-                                e
+                                status
                             );
                             false
                         }
@@ -522,6 +905,18 @@ impl Worker for BasicWorker {
             .ok();
     }
 
+    fn increment_load_by(&self, weight: usize) {
+        self.load_counter.fetch_add(weight, Ordering::Relaxed);
+    }
+
+    fn decrement_load_by(&self, weight: usize) {
+        self.load_counter
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                Some(current.saturating_sub(weight))
+            })
+            .ok();
+    }
+
     fn reset_load(&self) {
         self.load_counter.store(0, Ordering::Relaxed);
     }
@@ -541,6 +936,10 @@ impl Worker for BasicWorker {
     fn circuit_breaker(&self) -> &CircuitBreaker {
         &self.circuit_breaker
     }
+
+    fn latency_ewma(&self) -> &LatencyEwma {
+        &self.latency_ewma
+    }
 }
 
 /// A DP-aware worker that handles data-parallel routing
@@ -594,6 +993,46 @@ impl Worker for DPAwareWorker {
         self.base_worker.set_healthy(healthy);
     }
 
+    fn is_draining(&self) -> bool {
+        self.base_worker.is_draining()
+    }
+
+    fn set_draining(&self, draining: bool) {
+        self.base_worker.set_draining(draining);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.base_worker.is_enabled()
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.base_worker.set_enabled(enabled);
+    }
+
+    fn is_warming(&self) -> bool {
+        self.base_worker.is_warming()
+    }
+
+    fn set_warming(&self, warming: bool) {
+        self.base_worker.set_warming(warming);
+    }
+
+    fn model_version(&self) -> Option<String> {
+        self.base_worker.model_version()
+    }
+
+    fn set_model_version(&self, version: Option<String>) {
+        self.base_worker.set_model_version(version);
+    }
+
+    fn ejected_until(&self) -> Option<Instant> {
+        self.base_worker.ejected_until()
+    }
+
+    fn set_ejected_until(&self, until: Option<Instant>) {
+        self.base_worker.set_ejected_until(until);
+    }
+
     async fn check_health_async(&self) -> WorkerResult<()> {
         // Delegate to the base worker's health check logic
         self.base_worker.check_health_async().await
@@ -611,6 +1050,14 @@ impl Worker for DPAwareWorker {
         self.base_worker.decrement_load();
     }
 
+    fn increment_load_by(&self, weight: usize) {
+        self.base_worker.increment_load_by(weight);
+    }
+
+    fn decrement_load_by(&self, weight: usize) {
+        self.base_worker.decrement_load_by(weight);
+    }
+
     fn reset_load(&self) {
         self.base_worker.reset_load();
     }
@@ -631,6 +1078,10 @@ impl Worker for DPAwareWorker {
         self.base_worker.circuit_breaker()
     }
 
+    fn latency_ewma(&self) -> &LatencyEwma {
+        self.base_worker.latency_ewma()
+    }
+
     // DP-aware specific implementations
 
     fn is_dp_aware(&self) -> bool {
@@ -886,17 +1337,47 @@ pub fn workers_to_urls(workers: &[Box<dyn Worker>]) -> Vec<String> {
     workers.iter().map(|w| w.url().to_string()).collect()
 }
 
+/// How often draining workers are polled for in-flight requests
+const DRAIN_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Mark workers as draining and wait until none of them has in-flight requests
+///
+/// Returns the number of requests still in flight when the wait ended, so `0`
+/// means the workers drained before `timeout` elapsed. The workers stay
+/// registered; removing them is left to the caller.
+pub async fn drain_workers(workers: &[Arc<dyn Worker>], timeout: std::time::Duration) -> usize {
+    for worker in workers {
+        worker.set_draining(true);
+    }
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let in_flight: usize = workers.iter().map(|w| w.load()).sum();
+        if in_flight == 0 || tokio::time::Instant::now() >= deadline {
+            return in_flight;
+        }
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+}
+
 /// RAII guard for worker load management
 pub struct WorkerLoadGuard<'a> {
     workers: Vec<&'a dyn Worker>,
+    weight: usize,
 }
 
 impl<'a> WorkerLoadGuard<'a> {
     /// Create a new load guard for a single worker
     pub fn new(worker: &'a dyn Worker) -> Self {
-        worker.increment_load();
+        Self::with_weight(worker, 1)
+    }
+
+    /// Create a load guard that counts as `weight` requests on the worker
+    pub fn with_weight(worker: &'a dyn Worker, weight: usize) -> Self {
+        worker.increment_load_by(weight);
         Self {
             workers: vec![worker],
+            weight,
         }
     }
 
@@ -906,7 +1387,7 @@ impl<'a> WorkerLoadGuard<'a> {
         for worker in &workers {
             worker.increment_load();
         }
-        Self { workers }
+        Self { workers, weight: 1 }
     }
 }
 
@@ -914,11 +1395,44 @@ impl<'a> Drop for WorkerLoadGuard<'a> {
     fn drop(&mut self) {
         // Decrement load counters for all workers
         for worker in &self.workers {
-            worker.decrement_load();
+            worker.decrement_load_by(self.weight);
         }
     }
 }
 
+/// RAII guard holding load on a shared worker
+///
+/// Unlike [`WorkerLoadGuard`] it owns its worker, so it can travel with a
+/// streamed response body and release the load when the body ends.
+pub struct OwnedWorkerLoadGuard {
+    worker: Arc<dyn Worker>,
+    weight: usize,
+}
+
+impl OwnedWorkerLoadGuard {
+    /// Create a load guard that counts as `weight` requests on the worker
+    pub fn with_weight(worker: Arc<dyn Worker>, weight: usize) -> Self {
+        worker.increment_load_by(weight);
+        RouterMetrics::set_running_requests(worker.url(), worker.load());
+        Self { worker, weight }
+    }
+
+    pub fn worker(&self) -> &Arc<dyn Worker> {
+        &self.worker
+    }
+
+    pub fn weight(&self) -> usize {
+        self.weight
+    }
+}
+
+impl Drop for OwnedWorkerLoadGuard {
+    fn drop(&mut self) {
+        self.worker.decrement_load_by(self.weight);
+        RouterMetrics::set_running_requests(self.worker.url(), self.worker.load());
+    }
+}
+
 /// Health checker handle with graceful shutdown
 pub struct HealthChecker {
     handle: tokio::task::JoinHandle<()>,
@@ -1033,6 +1547,8 @@ pub fn start_health_checker(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grpc::health_proto::health_server::{Health, HealthServer};
+    use crate::grpc::health_proto::{HealthCheckRequest, HealthCheckResponse};
     use std::thread;
     use std::time::Duration;
 
@@ -1107,12 +1623,39 @@ mod tests {
             endpoint: "/healthz".to_string(),
             failure_threshold: 5,
             success_threshold: 3,
+            method: HealthCheckMethod::Post,
+            expected_body: Some("$.status=ready".to_string()),
+            generation_probe: true,
         };
         assert_eq!(config.timeout_secs, 10);
         assert_eq!(config.check_interval_secs, 60);
         assert_eq!(config.endpoint, "/healthz");
         assert_eq!(config.failure_threshold, 5);
         assert_eq!(config.success_threshold, 3);
+        assert_eq!(config.method, HealthCheckMethod::Post);
+        assert!(config.generation_probe);
+    }
+
+    #[test]
+    fn test_health_body_matcher() {
+        let substring = HealthBodyMatcher::parse("ready").unwrap();
+        assert!(substring.matches(r#"{"status":"ready"}"#));
+        assert!(!substring.matches(r#"{"status":"starting"}"#));
+
+        let field = HealthBodyMatcher::parse("$.status=ready").unwrap();
+        assert!(field.matches(r#"{"status":"ready"}"#));
+        assert!(!field.matches(r#"{"status":"starting"}"#));
+        assert!(!field.matches(r#"{"state":"ready"}"#));
+        assert!(!field.matches("ready"));
+
+        let nested = HealthBodyMatcher::parse("$.engines.0.ok=true").unwrap();
+        assert!(nested.matches(r#"{"engines":[{"ok":true}]}"#));
+        assert!(!nested.matches(r#"{"engines":[{"ok":false}]}"#));
+
+        assert!(HealthBodyMatcher::parse("").is_err());
+        assert!(HealthBodyMatcher::parse("$.status").is_err());
+        assert!(HealthBodyMatcher::parse("$.=ready").is_err());
+        assert!(HealthBodyMatcher::parse("$.a..b=1").is_err());
     }
 
     // Test BasicWorker
@@ -1126,6 +1669,36 @@ mod tests {
         assert_eq!(worker.processed_requests(), 0);
     }
 
+    #[test]
+    fn test_has_tags() {
+        let worker = BasicWorker::new("http://test:8080".to_string(), WorkerType::Regular)
+            .with_labels(std::collections::HashMap::from([
+                ("quant".to_string(), "fp8".to_string()),
+                ("region".to_string(), "us-east".to_string()),
+            ]));
+        let tag = |key: &str, value: Option<&str>| (key.to_string(), value.map(String::from));
+
+        assert!(worker.has_tags(&[]));
+        assert!(worker.has_tags(&[tag("quant", Some("fp8")), tag("region", None)]));
+        assert!(!worker.has_tags(&[tag("quant", Some("fp16"))]));
+        assert!(!worker.has_tags(&[tag("quant", Some("fp8")), tag("tools", None)]));
+    }
+
+    #[test]
+    fn test_speaks_connection_mode() {
+        let http = BasicWorker::new("http://test:8080".to_string(), WorkerType::Regular);
+        let grpc = WorkerFactory::create_grpc(
+            "grpc://test:9000".to_string(),
+            WorkerType::Regular,
+            Some(9001),
+        );
+
+        assert!(http.speaks(&ConnectionMode::Http));
+        assert!(!http.speaks(&ConnectionMode::Grpc { port: None }));
+        assert!(grpc.speaks(&ConnectionMode::Grpc { port: None }));
+        assert!(!grpc.speaks(&ConnectionMode::Http));
+    }
+
     #[test]
     fn test_worker_with_labels() {
         let mut labels = std::collections::HashMap::new();
@@ -1146,6 +1719,7 @@ mod tests {
             endpoint: "/custom-health".to_string(),
             failure_threshold: 4,
             success_threshold: 2,
+            ..Default::default()
         };
 
         let worker = BasicWorker::new("http://test:8080".to_string(), WorkerType::Regular)
@@ -1484,6 +2058,22 @@ mod tests {
         assert_eq!(worker.load(), 0);
     }
 
+    #[test]
+    fn test_load_guard_with_weight() {
+        let worker = BasicWorker::new("http://test:8080".to_string(), WorkerType::Regular);
+        worker.increment_load();
+
+        {
+            let _guard = WorkerLoadGuard::with_weight(&worker, 4);
+            assert_eq!(worker.load(), 5);
+        }
+        assert_eq!(worker.load(), 1);
+
+        // Removing more than is left clamps at zero
+        worker.decrement_load_by(3);
+        assert_eq!(worker.load(), 0);
+    }
+
     #[test]
     fn test_load_guard_multiple_workers() {
         let workers: Vec<Box<dyn Worker>> = vec![
@@ -1859,6 +2449,7 @@ mod tests {
             success_threshold: 1,
             timeout_duration: Duration::from_millis(100),
             window_duration: Duration::from_secs(60),
+            half_open_max_concurrent: 1,
         };
 
         let worker = BasicWorker::new("http://test:8080".to_string(), WorkerType::Regular)
@@ -1888,6 +2479,186 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_draining_worker_is_unavailable() {
+        let worker = BasicWorker::new("http://test:8080".to_string(), WorkerType::Regular);
+        assert!(!worker.is_draining());
+
+        worker.set_draining(true);
+        assert!(worker.is_draining());
+        assert!(worker.is_healthy());
+        assert!(!worker.is_available());
+
+        worker.set_draining(false);
+        assert!(worker.is_available());
+
+        let dp_worker =
+            DPAwareWorker::new("http://worker:8080".to_string(), 0, 2, WorkerType::Regular);
+        dp_worker.set_draining(true);
+        assert!(!dp_worker.is_available());
+    }
+
+    #[test]
+    fn test_ejected_worker_is_unavailable_until_ejection_ends() {
+        let worker = BasicWorker::new("http://test:8080".to_string(), WorkerType::Regular);
+        assert!(!worker.is_ejected());
+
+        worker.set_ejected_until(Some(Instant::now() + Duration::from_secs(60)));
+        assert!(worker.is_ejected());
+        assert!(worker.is_healthy());
+        assert!(!worker.is_available());
+
+        // An ejection that has run out no longer counts
+        worker.set_ejected_until(Some(Instant::now() - Duration::from_secs(1)));
+        assert!(!worker.is_ejected());
+        assert!(worker.is_available());
+
+        let dp_worker =
+            DPAwareWorker::new("http://worker:8080".to_string(), 0, 2, WorkerType::Regular);
+        dp_worker.set_ejected_until(Some(Instant::now() + Duration::from_secs(60)));
+        assert!(!dp_worker.is_available());
+    }
+
+    #[test]
+    fn test_disabled_worker_is_unavailable() {
+        let worker = BasicWorker::new("http://test:8080".to_string(), WorkerType::Regular);
+        assert!(worker.is_enabled());
+
+        worker.set_enabled(false);
+        assert!(!worker.is_enabled());
+        assert!(worker.is_healthy());
+        assert!(!worker.is_available());
+
+        // Health and circuit breaker state are kept while disabled
+        worker.record_outcome(false);
+        assert_eq!(worker.circuit_breaker().stats().total_failures, 1);
+
+        worker.set_enabled(true);
+        assert!(worker.is_available());
+
+        let dp_worker =
+            DPAwareWorker::new("http://worker:8080".to_string(), 0, 2, WorkerType::Regular);
+        dp_worker.set_enabled(false);
+        assert!(!dp_worker.is_available());
+    }
+
+    #[test]
+    fn test_worker_latency_ewma() {
+        let worker = BasicWorker::new("http://test:8080".to_string(), WorkerType::Regular);
+        assert_eq!(worker.ewma_latency(), None);
+
+        // The first sample seeds the average
+        worker.record_latency(Duration::from_millis(100), 0.5);
+        assert_eq!(worker.ewma_latency(), Some(Duration::from_millis(100)));
+
+        worker.record_latency(Duration::from_millis(300), 0.5);
+        assert_eq!(worker.ewma_latency(), Some(Duration::from_millis(200)));
+
+        // A high decay keeps most of the history
+        worker.record_latency(Duration::from_millis(1200), 0.9);
+        let average = worker.ewma_latency().unwrap().as_secs_f64();
+        assert!((average - 0.3).abs() < 1e-9, "average: {}", average);
+
+        // Clones and DP ranks share the underlying tracker
+        let clone = worker.clone();
+        assert_eq!(clone.ewma_latency(), worker.ewma_latency());
+        let dp_worker =
+            DPAwareWorker::new("http://worker:8080".to_string(), 0, 2, WorkerType::Regular);
+        dp_worker.record_latency(Duration::from_millis(50), 0.5);
+        assert_eq!(dp_worker.ewma_latency(), Some(Duration::from_millis(50)));
+    }
+
+    #[tokio::test]
+    async fn test_drain_workers_waits_for_in_flight_requests() {
+        let worker: Arc<dyn Worker> = Arc::new(BasicWorker::new(
+            "http://test:8080".to_string(),
+            WorkerType::Regular,
+        ));
+        worker.increment_load();
+
+        let in_flight = Arc::clone(&worker);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            in_flight.decrement_load();
+        });
+
+        let start = std::time::Instant::now();
+        assert_eq!(
+            drain_workers(&[Arc::clone(&worker)], Duration::from_secs(5)).await,
+            0
+        );
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert!(worker.is_draining());
+    }
+
+    #[tokio::test]
+    async fn test_drain_workers_times_out() {
+        let worker: Arc<dyn Worker> = Arc::new(BasicWorker::new(
+            "http://test:8080".to_string(),
+            WorkerType::Regular,
+        ));
+        worker.increment_load();
+
+        assert_eq!(
+            drain_workers(&[Arc::clone(&worker)], Duration::from_millis(200)).await,
+            1
+        );
+        assert_eq!(worker.load(), 1);
+    }
+
+    /// grpc.health.v1 server reporting a fixed status
+    struct FixedHealth(ServingStatus);
+
+    #[tonic::async_trait]
+    impl Health for FixedHealth {
+        async fn check(
+            &self,
+            _request: tonic::Request<HealthCheckRequest>,
+        ) -> Result<tonic::Response<HealthCheckResponse>, tonic::Status> {
+            Ok(tonic::Response::new(HealthCheckResponse {
+                status: self.0 as i32,
+            }))
+        }
+
+        type WatchStream = futures::stream::Empty<Result<HealthCheckResponse, tonic::Status>>;
+
+        async fn watch(
+            &self,
+            _request: tonic::Request<HealthCheckRequest>,
+        ) -> Result<tonic::Response<Self::WatchStream>, tonic::Status> {
+            Err(tonic::Status::unimplemented("watch"))
+        }
+    }
+
+    async fn grpc_worker(status: ServingStatus) -> BasicWorker {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("grpc://{}", listener.local_addr().unwrap());
+        let incoming =
+            tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(HealthServer::new(FixedHealth(status)))
+                .serve_with_incoming(incoming),
+        );
+
+        let client = VllmSchedulerClient::connect(&url).await.unwrap();
+        BasicWorker::with_connection_mode(
+            url,
+            WorkerType::Regular,
+            ConnectionMode::Grpc { port: None },
+        )
+        .with_grpc_client(client)
+    }
+
+    #[tokio::test]
+    async fn test_grpc_worker_uses_standard_health_protocol() {
+        let serving = grpc_worker(ServingStatus::Serving).await;
+        assert!(serving.check_health_async().await.is_ok());
+
+        let not_serving = grpc_worker(ServingStatus::NotServing).await;
+        assert!(not_serving.check_health_async().await.is_err());
+    }
+
     #[test]
     fn test_dp_aware_worker_circuit_breaker() {
         let dp_worker =
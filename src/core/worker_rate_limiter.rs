@@ -0,0 +1,107 @@
+//! Per-worker request rate limits
+//!
+//! The global [`TokenBucket`](super::token_bucket::TokenBucket) caps the router as a whole,
+//! but a policy can still send a burst to a single worker. This keeps one
+//! token bucket per worker URL, checked synchronously during worker
+//! selection, so an over-limit worker is skipped in favour of the next one
+//! the policy picks.
+
+use crate::config::WorkerRateLimitConfig;
+use dashmap::DashMap;
+use std::time::Instant;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token buckets keyed by worker URL, created on first use
+#[derive(Debug)]
+pub struct WorkerRateLimiter {
+    capacity: f64,
+    refill_rate: f64,
+    buckets: DashMap<String, Bucket>,
+}
+
+impl WorkerRateLimiter {
+    pub fn new(config: &WorkerRateLimitConfig) -> Self {
+        let refill_rate = config.requests_per_second.max(1) as f64;
+        let capacity = config
+            .burst
+            .map(|burst| burst.max(1) as f64)
+            .unwrap_or(refill_rate);
+        Self {
+            capacity,
+            refill_rate,
+            buckets: DashMap::new(),
+        }
+    }
+
+    /// Take one request slot from the worker's bucket
+    ///
+    /// Returns false, without consuming anything, when the worker is over its limit.
+    pub fn try_acquire(&self, worker_url: &str) -> bool {
+        let now = Instant::now();
+        let mut bucket = match self.buckets.get_mut(worker_url) {
+            Some(bucket) => bucket,
+            None => self
+                .buckets
+                .entry(worker_url.to_string())
+                .or_insert(Bucket {
+                    tokens: self.capacity,
+                    last_refill: now,
+                }),
+        };
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_rate).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Drop the bucket of a worker that left the pool
+    pub fn remove(&self, worker_url: &str) {
+        self.buckets.remove(worker_url);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_limits_each_worker_independently() {
+        let limiter = WorkerRateLimiter::new(&WorkerRateLimitConfig {
+            requests_per_second: 1,
+            burst: Some(2),
+        });
+
+        assert!(limiter.try_acquire("http://w1:8000"));
+        assert!(limiter.try_acquire("http://w1:8000"));
+        assert!(!limiter.try_acquire("http://w1:8000"));
+
+        // Another worker has its own budget
+        assert!(limiter.try_acquire("http://w2:8000"));
+    }
+
+    #[test]
+    fn test_refills_over_time() {
+        let limiter = WorkerRateLimiter::new(&WorkerRateLimitConfig {
+            requests_per_second: 20,
+            burst: Some(1),
+        });
+
+        assert!(limiter.try_acquire("http://w1:8000"));
+        assert!(!limiter.try_acquire("http://w1:8000"));
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(limiter.try_acquire("http://w1:8000"));
+    }
+}
@@ -34,6 +34,37 @@ impl Default for WorkerId {
     }
 }
 
+/// Criteria for listing workers; unset fields match every worker
+#[derive(Debug, Clone, Default)]
+pub struct WorkerFilter {
+    pub model_id: Option<String>,
+    /// Prefill matches regardless of bootstrap_port
+    pub worker_type: Option<WorkerType>,
+    pub healthy: Option<bool>,
+}
+
+impl WorkerFilter {
+    fn matches(&self, worker: &dyn Worker) -> bool {
+        if let Some(model) = &self.model_id {
+            if worker.model_id() != model {
+                return false;
+            }
+        }
+        if let Some(worker_type) = &self.worker_type {
+            if std::mem::discriminant(&worker.worker_type()) != std::mem::discriminant(worker_type)
+            {
+                return false;
+            }
+        }
+        if let Some(healthy) = self.healthy {
+            if worker.is_healthy() != healthy {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 /// Type alias for the model index to reduce complexity
 type ModelIndex = Arc<DashMap<String, Arc<RwLock<Vec<Arc<dyn Worker>>>>>>;
 
@@ -315,6 +346,29 @@ impl WorkerRegistry {
             .collect()
     }
 
+    /// Get workers with their IDs matching `filter`, sorted by URL
+    ///
+    /// The stable order lets callers page through the result.
+    pub fn get_filtered_with_ids(&self, filter: &WorkerFilter) -> Vec<(WorkerId, Arc<dyn Worker>)> {
+        let workers = match (&filter.model_id, &filter.worker_type) {
+            (Some(model), _) => self.get_by_model_fast(model),
+            (None, Some(WorkerType::Prefill { .. })) => self.get_prefill_workers(),
+            (None, Some(worker_type)) => self.get_by_type(worker_type),
+            (None, None) => self.get_all(),
+        };
+
+        let mut matched: Vec<(WorkerId, Arc<dyn Worker>)> = workers
+            .into_iter()
+            .filter(|w| filter.matches(w.as_ref()))
+            .filter_map(|w| {
+                let id = self.url_to_id.get(w.url())?.clone();
+                Some((id, w))
+            })
+            .collect();
+        matched.sort_by(|(_, a), (_, b)| a.url().cmp(b.url()));
+        matched
+    }
+
     /// Get worker statistics
     pub fn stats(&self) -> WorkerRegistryStats {
         let total_workers = self.workers.len();
@@ -523,4 +577,67 @@ mod tests {
         assert_eq!(llama_workers_after.len(), 1);
         assert_eq!(llama_workers_after[0].url(), "http://worker2:8080");
     }
+
+    #[test]
+    fn test_get_filtered_with_ids() {
+        let registry = WorkerRegistry::new();
+        registry.register(Arc::from(WorkerFactory::create_prefill(
+            "http://prefill1:8080".to_string(),
+            Some(9000),
+        )));
+        registry.register(Arc::from(WorkerFactory::create_prefill(
+            "http://prefill2:8080".to_string(),
+            None,
+        )));
+        registry.register(Arc::from(WorkerFactory::create_decode(
+            "http://decode2:8080".to_string(),
+        )));
+        registry.register(Arc::from(WorkerFactory::create_decode(
+            "http://decode1:8080".to_string(),
+        )));
+        registry
+            .get_by_url("http://decode2:8080")
+            .unwrap()
+            .set_healthy(false);
+
+        let urls = |filter: WorkerFilter| -> Vec<String> {
+            registry
+                .get_filtered_with_ids(&filter)
+                .into_iter()
+                .map(|(_, w)| w.url().to_string())
+                .collect()
+        };
+
+        // Prefill matches whatever the bootstrap port
+        assert_eq!(
+            urls(WorkerFilter {
+                worker_type: Some(WorkerType::Prefill {
+                    bootstrap_port: None
+                }),
+                ..Default::default()
+            }),
+            vec!["http://prefill1:8080", "http://prefill2:8080"]
+        );
+        assert_eq!(
+            urls(WorkerFilter {
+                worker_type: Some(WorkerType::Decode),
+                healthy: Some(true),
+                ..Default::default()
+            }),
+            vec!["http://decode1:8080"]
+        );
+        assert_eq!(
+            urls(WorkerFilter {
+                healthy: Some(false),
+                ..Default::default()
+            }),
+            vec!["http://decode2:8080"]
+        );
+        assert!(urls(WorkerFilter {
+            model_id: Some("llama-3".to_string()),
+            ..Default::default()
+        })
+        .is_empty());
+        assert_eq!(urls(WorkerFilter::default()).len(), 4);
+    }
 }
@@ -1,10 +1,12 @@
 // Data connector module for response storage
 pub mod response_memory_store;
 pub mod response_noop_store;
+pub mod response_redis_store;
 pub mod responses;
 
 pub use response_memory_store::MemoryResponseStorage;
 pub use response_noop_store::NoOpResponseStorage;
+pub use response_redis_store::RedisResponseStorage;
 pub use responses::{
     ResponseChain, ResponseId, ResponseStorage, ResponseStorageError, SharedResponseStorage,
     StoredResponse,
@@ -1,9 +1,21 @@
 use async_trait::async_trait;
 use parking_lot::RwLock;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::debug;
 
 use super::responses::{ResponseChain, ResponseId, ResponseStorage, Result, StoredResponse};
+use crate::config::MemoryHistoryConfig;
+use crate::metrics::RouterMetrics;
+
+/// Eviction bookkeeping for a stored response
+struct EntryMeta {
+    stored_at: Instant,
+    /// Position in the LRU order
+    last_used: u64,
+}
 
 /// Internal store structure holding both maps together
 #[derive(Default)]
@@ -12,18 +24,172 @@ struct InnerStore {
     responses: HashMap<ResponseId, StoredResponse>,
     /// Index of response IDs by user
     user_index: HashMap<String, Vec<ResponseId>>,
+    /// Insertion time and recency of each stored response
+    entries: HashMap<ResponseId, EntryMeta>,
+    /// Response IDs ordered from least to most recently used
+    lru: BTreeMap<u64, ResponseId>,
+    tick: u64,
+}
+
+impl InnerStore {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn insert(&mut self, response: StoredResponse) {
+        let response_id = response.id.clone();
+        self.remove(&response_id);
+
+        // Update user index if user is specified
+        if let Some(ref user) = response.user {
+            self.user_index
+                .entry(user.clone())
+                .or_default()
+                .push(response_id.clone());
+        }
+
+        let tick = self.next_tick();
+        self.lru.insert(tick, response_id.clone());
+        self.entries.insert(
+            response_id.clone(),
+            EntryMeta {
+                stored_at: Instant::now(),
+                last_used: tick,
+            },
+        );
+        self.responses.insert(response_id, response);
+    }
+
+    /// Remove a response along with its index and eviction entries
+    fn remove(&mut self, response_id: &ResponseId) -> Option<StoredResponse> {
+        if let Some(meta) = self.entries.remove(response_id) {
+            self.lru.remove(&meta.last_used);
+        }
+
+        let response = self.responses.remove(response_id)?;
+        if let Some(ref user) = response.user {
+            if let Some(user_responses) = self.user_index.get_mut(user) {
+                user_responses.retain(|id| id != response_id);
+                if user_responses.is_empty() {
+                    self.user_index.remove(user);
+                }
+            }
+        }
+        Some(response)
+    }
+
+    fn touch(&mut self, response_id: &ResponseId) {
+        let tick = self.next_tick();
+        if let Some(meta) = self.entries.get_mut(response_id) {
+            self.lru.remove(&meta.last_used);
+            meta.last_used = tick;
+            self.lru.insert(tick, response_id.clone());
+        }
+    }
+
+    fn is_expired(&self, response_id: &ResponseId, ttl: Option<Duration>) -> bool {
+        match (ttl, self.entries.get(response_id)) {
+            (Some(ttl), Some(meta)) => meta.stored_at.elapsed() >= ttl,
+            _ => false,
+        }
+    }
+
+    /// Look up a response that has not expired
+    fn get_live(&self, response_id: &ResponseId, ttl: Option<Duration>) -> Option<&StoredResponse> {
+        if self.is_expired(response_id, ttl) {
+            return None;
+        }
+        self.responses.get(response_id)
+    }
+
+    /// Evict least recently used responses until at most `max` remain
+    fn evict_to_capacity(&mut self, max: usize) -> usize {
+        let mut evicted = 0;
+        while self.responses.len() > max {
+            let Some((_, oldest)) = self.lru.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            if self.remove(&oldest).is_some() {
+                evicted += 1;
+            }
+        }
+        evicted
+    }
+
+    fn evict_expired(&mut self, ttl: Duration) -> usize {
+        let expired: Vec<ResponseId> = self
+            .entries
+            .iter()
+            .filter(|(_, meta)| meta.stored_at.elapsed() >= ttl)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &expired {
+            self.remove(id);
+        }
+        expired.len()
+    }
 }
 
 /// In-memory implementation of response storage
+///
+/// Unbounded by default. With a capacity, storing a new response evicts the
+/// least recently used one; with a TTL, expired responses are never returned
+/// and a background thread drops them every eviction interval.
 pub struct MemoryResponseStorage {
     /// Single lock wrapping both maps to prevent deadlocks and ensure atomic updates
     store: Arc<RwLock<InnerStore>>,
+    max_responses: Option<usize>,
+    ttl: Option<Duration>,
 }
 
 impl MemoryResponseStorage {
     pub fn new() -> Self {
+        Self::with_limits(None, None, Duration::ZERO)
+    }
+
+    pub fn with_config(config: &MemoryHistoryConfig) -> Self {
+        Self::with_limits(
+            config.max_responses,
+            config.response_ttl_secs.map(Duration::from_secs),
+            Duration::from_secs(config.eviction_interval_secs),
+        )
+    }
+
+    fn with_limits(
+        max_responses: Option<usize>,
+        ttl: Option<Duration>,
+        eviction_interval: Duration,
+    ) -> Self {
+        let store = Arc::new(RwLock::new(InnerStore::default()));
+
+        // The sweeper holds a weak reference so it exits once the storage is dropped
+        if let Some(ttl) = ttl.filter(|_| !eviction_interval.is_zero()) {
+            let weak = Arc::downgrade(&store);
+            thread::spawn(move || Self::sweep_expired(weak, ttl, eviction_interval));
+        }
+
         Self {
-            store: Arc::new(RwLock::new(InnerStore::default())),
+            store,
+            max_responses,
+            ttl,
+        }
+    }
+
+    fn sweep_expired(store: Weak<RwLock<InnerStore>>, ttl: Duration, interval: Duration) {
+        loop {
+            thread::sleep(interval);
+            let Some(store) = store.upgrade() else {
+                break;
+            };
+
+            let mut store = store.write();
+            let evicted = store.evict_expired(ttl);
+            if evicted > 0 {
+                debug!("Evicted {} expired responses from history", evicted);
+            }
+            RouterMetrics::set_stored_responses(store.responses.len());
         }
     }
 
@@ -38,9 +204,8 @@ impl MemoryResponseStorage {
 
     /// Clear all data (useful for testing)
     pub fn clear(&self) {
-        let mut store = self.store.write();
-        store.responses.clear();
-        store.user_index.clear();
+        *self.store.write() = InnerStore::default();
+        RouterMetrics::set_stored_responses(0);
     }
 }
 
@@ -62,38 +227,35 @@ impl ResponseStorage for MemoryResponseStorage {
 
         // Single lock acquisition for atomic update
         let mut store = self.store.write();
-
-        // Update user index if user is specified
-        if let Some(ref user) = response.user {
-            store
-                .user_index
-                .entry(user.clone())
-                .or_default()
-                .push(response_id.clone());
+        store.insert(response);
+
+        if let Some(max) = self.max_responses {
+            let evicted = store.evict_to_capacity(max);
+            if evicted > 0 {
+                debug!(
+                    "Evicted {} least recently used responses from history",
+                    evicted
+                );
+            }
         }
-
-        // Store the response
-        store.responses.insert(response_id.clone(), response);
+        RouterMetrics::set_stored_responses(store.responses.len());
 
         Ok(response_id)
     }
 
     async fn get_response(&self, response_id: &ResponseId) -> Result<Option<StoredResponse>> {
-        let store = self.store.read();
-        Ok(store.responses.get(response_id).cloned())
+        let mut store = self.store.write();
+        let response = store.get_live(response_id, self.ttl).cloned();
+        if response.is_some() {
+            store.touch(response_id);
+        }
+        Ok(response)
     }
 
     async fn delete_response(&self, response_id: &ResponseId) -> Result<()> {
         let mut store = self.store.write();
-
-        // Remove the response and update user index if needed
-        if let Some(response) = store.responses.remove(response_id) {
-            if let Some(ref user) = response.user {
-                if let Some(user_responses) = store.user_index.get_mut(user) {
-                    user_responses.retain(|id| id != response_id);
-                }
-            }
-        }
+        store.remove(response_id);
+        RouterMetrics::set_stored_responses(store.responses.len());
 
         Ok(())
     }
@@ -119,7 +281,7 @@ impl ResponseStorage for MemoryResponseStorage {
                     break;
                 }
 
-                if let Some(response) = store.responses.get(&id) {
+                if let Some(response) = store.get_live(&id, self.ttl) {
                     response_ids.push(id);
                     current_id = response.previous_response_id.clone();
                     depth += 1;
@@ -154,7 +316,7 @@ impl ResponseStorage for MemoryResponseStorage {
             // Collect responses with their timestamps for sorting
             let mut responses_with_time: Vec<_> = user_response_ids
                 .iter()
-                .filter_map(|id| store.responses.get(id).map(|r| (r.created_at, id)))
+                .filter_map(|id| store.get_live(id, self.ttl).map(|r| (r.created_at, id)))
                 .collect();
 
             // Sort by creation time (newest first)
@@ -180,8 +342,9 @@ impl ResponseStorage for MemoryResponseStorage {
         if let Some(user_response_ids) = store.user_index.remove(user) {
             let count = user_response_ids.len();
             for id in user_response_ids {
-                store.responses.remove(&id);
+                store.remove(&id);
             }
+            RouterMetrics::set_stored_responses(store.responses.len());
             Ok(count)
         } else {
             Ok(0)
@@ -322,4 +485,91 @@ mod tests {
         assert_eq!(stats.response_count, 2);
         assert_eq!(stats.user_count, 2);
     }
+
+    #[tokio::test]
+    async fn test_capacity_evicts_least_recently_used() {
+        let store = MemoryResponseStorage::with_limits(Some(2), None, Duration::ZERO);
+
+        let mut first = StoredResponse::new("1".to_string(), "a".to_string(), None);
+        first.user = Some("user1".to_string());
+        let id1 = store.store_response(first).await.unwrap();
+        let id2 = store
+            .store_response(StoredResponse::new("2".to_string(), "b".to_string(), None))
+            .await
+            .unwrap();
+
+        // Reading the first response makes the second the eviction candidate
+        assert!(store.get_response(&id1).await.unwrap().is_some());
+        let id3 = store
+            .store_response(StoredResponse::new("3".to_string(), "c".to_string(), None))
+            .await
+            .unwrap();
+
+        assert_eq!(store.stats().response_count, 2);
+        assert!(store.get_response(&id1).await.unwrap().is_some());
+        assert!(store.get_response(&id2).await.unwrap().is_none());
+        assert!(store.get_response(&id3).await.unwrap().is_some());
+
+        // Evicting a user's response also drops it from the user index
+        store
+            .store_response(StoredResponse::new("4".to_string(), "d".to_string(), None))
+            .await
+            .unwrap();
+        assert!(store.get_response(&id1).await.unwrap().is_none());
+        assert_eq!(store.stats().user_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_responses_are_hidden() {
+        // A zero interval disables the sweeper, so only the read path hides entries
+        let store = MemoryResponseStorage::with_limits(
+            None,
+            Some(Duration::from_millis(10)),
+            Duration::ZERO,
+        );
+
+        let mut response = StoredResponse::new("Hello".to_string(), "Hi".to_string(), None);
+        response.user = Some("user1".to_string());
+        let id = store.store_response(response).await.unwrap();
+        assert!(store.get_response(&id).await.unwrap().is_some());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(store.get_response(&id).await.unwrap().is_none());
+        assert!(store
+            .get_response_chain(&id, None)
+            .await
+            .unwrap()
+            .responses
+            .is_empty());
+        assert!(store
+            .list_user_responses("user1", None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let evicted = store.store.write().evict_expired(Duration::from_millis(10));
+        assert_eq!(evicted, 1);
+        assert_eq!(store.stats().response_count, 0);
+        assert_eq!(store.stats().user_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_background_sweep_drops_expired_responses() {
+        let store = MemoryResponseStorage::with_limits(
+            None,
+            Some(Duration::from_millis(10)),
+            Duration::from_millis(10),
+        );
+        store
+            .store_response(StoredResponse::new(
+                "Hello".to_string(),
+                "Hi".to_string(),
+                None,
+            ))
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(store.stats().response_count, 0);
+    }
 }
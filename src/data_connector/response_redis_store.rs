@@ -0,0 +1,259 @@
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use tokio::sync::OnceCell;
+
+use super::responses::{
+    ResponseChain, ResponseId, ResponseStorage, ResponseStorageError, Result, StoredResponse,
+};
+
+/// Default prefix for every key written by the router
+const KEY_PREFIX: &str = "vllm_router";
+
+/// Redis implementation of response storage
+///
+/// Responses are stored as JSON strings under `vllm_router:response:{id}`, and
+/// each user's response IDs are kept in the sorted set `vllm_router:user:{user}`
+/// scored by creation time, so every router replica pointed at the same Redis
+/// sees the same history.
+pub struct RedisResponseStorage {
+    client: redis::Client,
+    /// Connection opened on first use so the router can start before Redis is up
+    conn: OnceCell<ConnectionManager>,
+    key_prefix: String,
+}
+
+impl RedisResponseStorage {
+    /// Create a storage backend for a `redis://` or `rediss://` URL
+    pub fn new(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url).map_err(storage_error)?;
+        Ok(Self {
+            client,
+            conn: OnceCell::new(),
+            key_prefix: KEY_PREFIX.to_string(),
+        })
+    }
+
+    /// Keep the entries of this store apart from others on the same Redis,
+    /// under `vllm_router:{namespace}:...`
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.key_prefix = format!("{}:{}", KEY_PREFIX, namespace);
+        self
+    }
+
+    async fn conn(&self) -> Result<ConnectionManager> {
+        self.conn
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await
+            .cloned()
+            .map_err(storage_error)
+    }
+
+    fn response_key(&self, response_id: &ResponseId) -> String {
+        format!("{}:response:{}", self.key_prefix, response_id.0)
+    }
+
+    fn user_key(&self, user: &str) -> String {
+        format!("{}:user:{}", self.key_prefix, user)
+    }
+
+    fn decode(raw: Option<String>) -> Result<Option<StoredResponse>> {
+        raw.map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+fn storage_error(e: redis::RedisError) -> ResponseStorageError {
+    ResponseStorageError::StorageError(e.to_string())
+}
+
+#[async_trait]
+impl ResponseStorage for RedisResponseStorage {
+    async fn store_response(&self, mut response: StoredResponse) -> Result<ResponseId> {
+        // Generate ID if not set
+        if response.id.0.is_empty() {
+            response.id = ResponseId::new();
+        }
+
+        let response_id = response.id.clone();
+        let json = serde_json::to_string(&response)?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().set(self.response_key(&response_id), json);
+        if let Some(ref user) = response.user {
+            pipe.zadd(
+                self.user_key(user),
+                &response_id.0,
+                response.created_at.timestamp_millis(),
+            );
+        }
+
+        let mut conn = self.conn().await?;
+        pipe.query_async::<()>(&mut conn)
+            .await
+            .map_err(storage_error)?;
+
+        Ok(response_id)
+    }
+
+    async fn get_response(&self, response_id: &ResponseId) -> Result<Option<StoredResponse>> {
+        let mut conn = self.conn().await?;
+        let raw: Option<String> = conn
+            .get(self.response_key(response_id))
+            .await
+            .map_err(storage_error)?;
+        Self::decode(raw)
+    }
+
+    async fn delete_response(&self, response_id: &ResponseId) -> Result<()> {
+        // Look up the owner first so the user index can be cleaned up too
+        let Some(response) = self.get_response(response_id).await? else {
+            return Ok(());
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().del(self.response_key(response_id));
+        if let Some(ref user) = response.user {
+            pipe.zrem(self.user_key(user), &response_id.0);
+        }
+
+        let mut conn = self.conn().await?;
+        pipe.query_async::<()>(&mut conn)
+            .await
+            .map_err(storage_error)
+    }
+
+    async fn get_response_chain(
+        &self,
+        response_id: &ResponseId,
+        max_depth: Option<usize>,
+    ) -> Result<ResponseChain> {
+        let max_depth = max_depth.unwrap_or(100); // Default max depth to prevent infinite loops
+
+        let mut responses = Vec::new();
+        let mut current_id = Some(response_id.clone());
+        while let Some(id) = current_id {
+            if responses.len() >= max_depth {
+                break;
+            }
+            let Some(response) = self.get_response(&id).await? else {
+                break;
+            };
+            current_id = response.previous_response_id.clone();
+            responses.push(response);
+        }
+
+        // Reverse to get chronological order (oldest first)
+        let mut chain = ResponseChain::new();
+        for response in responses.into_iter().rev() {
+            chain.add_response(response);
+        }
+
+        Ok(chain)
+    }
+
+    async fn list_user_responses(
+        &self,
+        user: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<StoredResponse>> {
+        // Newest first; -1 selects to the end of the set
+        let stop = match limit {
+            Some(0) => return Ok(Vec::new()),
+            Some(limit) => limit as isize - 1,
+            None => -1,
+        };
+
+        let mut conn = self.conn().await?;
+        let ids: Vec<String> = conn
+            .zrevrange(self.user_key(user), 0, stop)
+            .await
+            .map_err(storage_error)?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<String> = ids
+            .into_iter()
+            .map(|id| self.response_key(&ResponseId::from_string(id)))
+            .collect();
+        let raw: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(&keys)
+            .query_async(&mut conn)
+            .await
+            .map_err(storage_error)?;
+
+        // Entries deleted or expired out from under the index are skipped
+        let mut responses = Vec::with_capacity(raw.len());
+        for json in raw {
+            if let Some(response) = Self::decode(json)? {
+                responses.push(response);
+            }
+        }
+        Ok(responses)
+    }
+
+    async fn delete_user_responses(&self, user: &str) -> Result<usize> {
+        let mut conn = self.conn().await?;
+        let user_key = self.user_key(user);
+        let ids: Vec<String> = conn.zrange(&user_key, 0, -1).await.map_err(storage_error)?;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for id in &ids {
+            pipe.del(self.response_key(&ResponseId::from_string(id.clone())));
+        }
+        pipe.del(&user_key);
+        pipe.query_async::<()>(&mut conn)
+            .await
+            .map_err(storage_error)?;
+
+        Ok(ids.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_invalid_url() {
+        assert!(RedisResponseStorage::new("redis://localhost:6379").is_ok());
+        assert!(RedisResponseStorage::new("rediss://cache.internal:6380/2").is_ok());
+        assert!(matches!(
+            RedisResponseStorage::new("http://localhost:6379"),
+            Err(ResponseStorageError::StorageError(_))
+        ));
+    }
+
+    #[test]
+    fn test_key_layout() {
+        let id = ResponseId::from_string("resp_123".to_string());
+        let storage = RedisResponseStorage::new("redis://localhost:6379").unwrap();
+        assert_eq!(storage.response_key(&id), "vllm_router:response:resp_123");
+        assert_eq!(storage.user_key("alice"), "vllm_router:user:alice");
+
+        let storage = storage.with_namespace("batch");
+        assert_eq!(
+            storage.response_key(&id),
+            "vllm_router:batch:response:resp_123"
+        );
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let mut response = StoredResponse::new("Hello".to_string(), "Hi".to_string(), None);
+        response.user = Some("alice".to_string());
+        let json = serde_json::to_string(&response).unwrap();
+
+        let decoded = RedisResponseStorage::decode(Some(json)).unwrap().unwrap();
+        assert_eq!(decoded.id, response.id);
+        assert_eq!(decoded.user.as_deref(), Some("alice"));
+        assert!(RedisResponseStorage::decode(None).unwrap().is_none());
+        assert!(RedisResponseStorage::decode(Some("not json".to_string())).is_err());
+    }
+}
@@ -173,5 +173,11 @@ pub trait ResponseStorage: Send + Sync {
     async fn delete_user_responses(&self, user: &str) -> Result<usize>;
 }
 
+impl std::fmt::Debug for dyn ResponseStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ResponseStorage")
+    }
+}
+
 /// Type alias for shared storage
 pub type SharedResponseStorage = Arc<dyn ResponseStorage>;
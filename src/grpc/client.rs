@@ -10,9 +10,17 @@ pub mod proto {
 // The generated module structure depends on the package name in the .proto file
 // package vllm.grpc.scheduler; generates a nested module structure
 
+// Standard gRPC health checking protocol (grpc.health.v1)
+pub mod health_proto {
+    tonic::include_proto!("grpc.health.v1");
+}
+
+use health_proto::health_check_response::ServingStatus;
+
 /// gRPC client for VLLM scheduler
 pub struct VllmSchedulerClient {
     client: proto::vllm_scheduler_client::VllmSchedulerClient<Channel>,
+    health_client: health_proto::health_client::HealthClient<Channel>,
 }
 
 impl VllmSchedulerClient {
@@ -32,9 +40,13 @@ impl VllmSchedulerClient {
             .connect()
             .await?;
 
-        let client = proto::vllm_scheduler_client::VllmSchedulerClient::new(channel);
+        let client = proto::vllm_scheduler_client::VllmSchedulerClient::new(channel.clone());
+        let health_client = health_proto::health_client::HealthClient::new(channel);
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            health_client,
+        })
     }
 
     /// Submit a generation request (returns streaming response)
@@ -64,6 +76,25 @@ impl VllmSchedulerClient {
         Ok(response.into_inner())
     }
 
+    /// Check a service with the standard gRPC health checking protocol
+    /// (`grpc.health.v1.Health/Check`); an empty `service` checks the whole server
+    ///
+    /// Servers that do not implement the protocol fail with `Code::Unimplemented`.
+    pub async fn check_serving(
+        &mut self,
+        service: &str,
+        timeout: Duration,
+    ) -> Result<ServingStatus, tonic::Status> {
+        debug!("Sending grpc.health.v1 check for service '{}'", service);
+        let mut request = Request::new(health_proto::HealthCheckRequest {
+            service: service.to_string(),
+        });
+        request.set_timeout(timeout);
+
+        let response = self.health_client.check(request).await?;
+        Ok(response.into_inner().status())
+    }
+
     /// Abort a request
     pub async fn abort_request(
         &mut self,
@@ -5,4 +5,4 @@
 pub mod client;
 
 // Re-export the client
-pub use client::{proto, VllmSchedulerClient};
+pub use client::{health_proto, proto, VllmSchedulerClient};
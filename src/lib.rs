@@ -42,12 +42,22 @@ struct Router {
     eviction_interval_secs: u64,
     max_tree_size: usize,
     max_payload_size: usize,
+    max_payload_size_generate: Option<usize>,
+    max_payload_size_chat: Option<usize>,
+    max_payload_size_completions: Option<usize>,
+    max_payload_size_embeddings: Option<usize>,
+    max_payload_size_rerank: Option<usize>,
+    max_payload_size_responses: Option<usize>,
+    max_prompt_chars: Option<usize>,
+    default_max_tokens: Option<u32>,
+    max_tokens_limit: Option<u32>,
     intra_node_data_parallel_size: usize,
     api_key: Option<String>,
     api_key_validation_urls: Vec<String>,
     log_dir: Option<String>,
 
     log_level: Option<String>,
+    log_json: bool,
     service_discovery: bool,
     selector: HashMap<String, String>,
     service_discovery_port: u16,
@@ -58,6 +68,10 @@ struct Router {
     prometheus_port: Option<u16>,
     prometheus_host: Option<String>,
     request_timeout_secs: u64,
+    proxy_timeout_secs: u64,
+    generation_timeout_secs: Option<u64>,
+    max_request_timeout_secs: Option<u64>,
+    slow_request_threshold_secs: Option<u64>,
     request_id_headers: Option<Vec<String>>,
     pd_disaggregation: bool,
     vllm_pd_disaggregation: bool,
@@ -73,24 +87,81 @@ struct Router {
     retry_max_backoff_ms: u64,
     retry_backoff_multiplier: f32,
     retry_jitter_factor: f32,
+    retry_full_jitter: bool,
+    retry_max_fraction: Option<f32>,
+    retry_on: String,
     disable_retries: bool,
     // Circuit breaker configuration
     cb_failure_threshold: u32,
     cb_success_threshold: u32,
     cb_timeout_duration_secs: u64,
     cb_window_duration_secs: u64,
+    cb_half_open_max_concurrent: u32,
     disable_circuit_breaker: bool,
+    hedge_delay_ms: Option<u64>,
+    mirror_worker_urls: Vec<String>,
+    mirror_fraction: f64,
+    worker_rate_limit_rps: Option<usize>,
+    worker_rate_limit_burst: Option<usize>,
+    worker_warmup_requests: Option<usize>,
+    worker_warmup_prompt: String,
+    model_version_check_interval_secs: Option<u64>,
+    model_version_mismatch: String,
+    outlier_detection_interval_secs: Option<u64>,
+    outlier_ejection_secs: u64,
+    outlier_max_ejection_percent: u32,
+    response_cache_size: Option<usize>,
+    response_cache_ttl_secs: u64,
+    idempotency_cache_size: Option<usize>,
+    idempotency_ttl_secs: u64,
+    forward_headers: Vec<String>,
+    block_headers: Vec<String>,
+    log_bodies_routes: Vec<String>,
+    log_bodies_redact_fields: Vec<String>,
+    allow_worker_pinning: bool,
+    expose_routing_headers: bool,
+    upstream_auth: Option<String>,
+    upstream_tls_cert: Option<String>,
+    upstream_tls_key: Option<String>,
+    upstream_tls_ca: Option<String>,
+    pd_zone_affinity: bool,
     // Health check configuration
     health_failure_threshold: u32,
     health_success_threshold: u32,
     health_check_timeout_secs: u64,
     health_check_interval_secs: u64,
     health_check_endpoint: String,
+    health_check_method: String,
+    health_check_expected_body: Option<String>,
+    health_check_generation_probe: bool,
+    min_healthy_workers: usize,
     // IGW (Inference Gateway) configuration
     enable_igw: bool,
     queue_size: usize,
     queue_timeout_secs: u64,
     rate_limit_tokens_per_second: Option<usize>,
+    model_concurrency_limits: HashMap<String, usize>,
+    adaptive_concurrency: bool,
+    adaptive_concurrency_initial_limit: usize,
+    adaptive_concurrency_min_limit: usize,
+    model_policies: HashMap<String, PolicyType>,
+    queue_fairness: String,
+    queue_weights: HashMap<String, u32>,
+    queue_priority_aging_secs: u64,
+    vllm_discovery_stale_secs: u64,
+    model_aliases: HashMap<String, String>,
+    model_fallbacks: HashMap<String, Vec<String>>,
+    model_traffic_splits: HashMap<String, Vec<(String, u32)>>,
+    advertise_model_aliases: bool,
+    rewrite_stream_model: bool,
+    strict_model_routing: bool,
+    max_concurrent_streams: Option<usize>,
+    stream_buffer_size: usize,
+    stream_keepalive_secs: Option<u64>,
+    shutdown_drain_timeout_secs: u64,
+    batch_max_concurrency: usize,
+    enable_response_compression: bool,
+    enable_upstream_compression: bool,
     // Connection mode (determined from worker URLs)
     connection_mode: config::ConnectionMode,
     // Model path for tokenizer
@@ -129,6 +200,7 @@ impl Router {
                     balance_rel_threshold: self.balance_rel_threshold,
                     eviction_interval_secs: self.eviction_interval_secs,
                     max_tree_size: self.max_tree_size,
+                    cold_start_policy: config::ColdStartPolicy::default(),
                 },
                 PolicyType::PowerOfTwo => ConfigPolicyConfig::PowerOfTwo {
                     load_check_interval_secs: 5, // Default value
@@ -199,6 +271,10 @@ impl Router {
             connection_mode: self.connection_mode.clone(),
             max_payload_size: self.max_payload_size,
             request_timeout_secs: self.request_timeout_secs,
+            proxy_timeout_secs: self.proxy_timeout_secs,
+            generation_timeout_secs: self.generation_timeout_secs,
+            max_request_timeout_secs: self.max_request_timeout_secs,
+            slow_request_threshold_secs: self.slow_request_threshold_secs,
             worker_startup_timeout_secs: self.worker_startup_timeout_secs,
             worker_startup_check_interval_secs: self.worker_startup_check_interval,
             intra_node_data_parallel_size: self.intra_node_data_parallel_size,
@@ -213,6 +289,54 @@ impl Router {
             queue_size: self.queue_size,
             queue_timeout_secs: self.queue_timeout_secs,
             rate_limit_tokens_per_second: self.rate_limit_tokens_per_second,
+            model_concurrency_limits: self.model_concurrency_limits.clone(),
+            adaptive_concurrency: self.adaptive_concurrency.then(|| {
+                config::AdaptiveConcurrencyConfig {
+                    initial_limit: self.adaptive_concurrency_initial_limit,
+                    min_limit: self.adaptive_concurrency_min_limit,
+                    ..Default::default()
+                }
+            }),
+            model_policies: self
+                .model_policies
+                .iter()
+                .map(|(model, policy)| (model.clone(), convert_policy(policy)))
+                .collect(),
+            queue_fairness: match self.queue_fairness.as_str() {
+                "model" => config::QueueFairness::Model,
+                "client" => config::QueueFairness::Client,
+                "priority" => config::QueueFairness::Priority,
+                _ => config::QueueFairness::Fifo,
+            },
+            queue_weights: self.queue_weights.clone(),
+            queue_priority_aging_secs: self.queue_priority_aging_secs,
+            discovery_stale_secs: self.vllm_discovery_stale_secs,
+            model_aliases: self.model_aliases.clone(),
+            model_fallbacks: self.model_fallbacks.clone(),
+            model_traffic_splits: self
+                .model_traffic_splits
+                .iter()
+                .map(|(model, pools)| {
+                    let pools = pools
+                        .iter()
+                        .map(|(pool, weight)| config::TrafficSplitPool {
+                            model: pool.clone(),
+                            weight: *weight,
+                        })
+                        .collect();
+                    (model.clone(), pools)
+                })
+                .collect(),
+            advertise_model_aliases: self.advertise_model_aliases,
+            rewrite_stream_model: self.rewrite_stream_model,
+            strict_model_routing: self.strict_model_routing,
+            max_concurrent_streams: self.max_concurrent_streams,
+            stream_buffer_size: self.stream_buffer_size,
+            stream_keepalive_secs: self.stream_keepalive_secs,
+            shutdown_drain_timeout_secs: self.shutdown_drain_timeout_secs,
+            batch_max_concurrency: self.batch_max_concurrency,
+            enable_response_compression: self.enable_response_compression,
+            enable_upstream_compression: self.enable_upstream_compression,
             cors_allowed_origins: self.cors_allowed_origins.clone(),
             retry: config::RetryConfig {
                 max_retries: self.retry_max_retries,
@@ -220,13 +344,127 @@ impl Router {
                 max_backoff_ms: self.retry_max_backoff_ms,
                 backoff_multiplier: self.retry_backoff_multiplier,
                 jitter_factor: self.retry_jitter_factor,
+                full_jitter: self.retry_full_jitter,
+                max_retry_fraction: self.retry_max_fraction,
+                retry_on: match self.retry_on.as_str() {
+                    "connection_errors" => config::RetryOn::ConnectionErrors,
+                    _ => config::RetryOn::All,
+                },
             },
             circuit_breaker: config::CircuitBreakerConfig {
                 failure_threshold: self.cb_failure_threshold,
                 success_threshold: self.cb_success_threshold,
                 timeout_duration_secs: self.cb_timeout_duration_secs,
                 window_duration_secs: self.cb_window_duration_secs,
+                half_open_max_concurrent: self.cb_half_open_max_concurrent,
             },
+            hedging: self
+                .hedge_delay_ms
+                .map(|hedge_delay_ms| config::HedgingConfig { hedge_delay_ms }),
+            traffic_mirror: (!self.mirror_worker_urls.is_empty()).then(|| {
+                config::TrafficMirrorConfig {
+                    worker_urls: self.mirror_worker_urls.clone(),
+                    fraction: self.mirror_fraction,
+                    timeout_secs: 60,
+                }
+            }),
+            worker_rate_limit: self.worker_rate_limit_rps.map(|requests_per_second| {
+                config::WorkerRateLimitConfig {
+                    requests_per_second,
+                    burst: self.worker_rate_limit_burst,
+                }
+            }),
+            worker_warmup: self
+                .worker_warmup_requests
+                .map(|requests| config::WorkerWarmupConfig {
+                    requests,
+                    prompt: self.worker_warmup_prompt.clone(),
+                }),
+            model_version_check: self.model_version_check_interval_secs.map(
+                |check_interval_secs| config::ModelVersionCheckConfig {
+                    check_interval_secs,
+                    on_mismatch: match self.model_version_mismatch.as_str() {
+                        "split" => config::VersionMismatchAction::Split,
+                        _ => config::VersionMismatchAction::Warn,
+                    },
+                },
+            ),
+            outlier_detection: self.outlier_detection_interval_secs.map(|interval_secs| {
+                let defaults = config::OutlierDetectionConfig::default();
+                config::OutlierDetectionConfig {
+                    interval_secs,
+                    base_ejection_secs: self.outlier_ejection_secs,
+                    max_ejection_secs: defaults.max_ejection_secs.max(self.outlier_ejection_secs),
+                    max_ejection_percent: self.outlier_max_ejection_percent,
+                    ..defaults
+                }
+            }),
+            response_cache: self.response_cache_size.map(|max_entries| {
+                config::ResponseCacheConfig {
+                    max_entries,
+                    ttl_secs: self.response_cache_ttl_secs,
+                }
+            }),
+            idempotency: self
+                .idempotency_cache_size
+                .map(|max_keys| config::IdempotencyConfig {
+                    max_keys,
+                    ttl_secs: self.idempotency_ttl_secs,
+                }),
+            header_policy: config::HeaderPolicyConfig {
+                allow: self.forward_headers.clone(),
+                deny: config::default_header_deny()
+                    .into_iter()
+                    .chain(self.block_headers.iter().cloned())
+                    .collect(),
+            },
+            body_logging: (!self.log_bodies_routes.is_empty()).then(|| config::BodyLoggingConfig {
+                routes: self.log_bodies_routes.clone(),
+                redact_fields: if self.log_bodies_redact_fields.is_empty() {
+                    config::default_body_redact_fields()
+                } else {
+                    self.log_bodies_redact_fields.clone()
+                },
+            }),
+            allow_worker_pinning: self.allow_worker_pinning,
+            expose_routing_headers: self.expose_routing_headers,
+            upstream_auth: match self.upstream_auth.as_deref() {
+                Some("passthrough") => Some(config::UpstreamAuthConfig::Passthrough),
+                Some("inject") => Some(config::UpstreamAuthConfig::Inject {
+                    key: self.api_key.clone().ok_or_else(|| {
+                        config::ConfigError::MissingRequired {
+                            field: "api_key".to_string(),
+                        }
+                    })?,
+                }),
+                Some("none") => Some(config::UpstreamAuthConfig::None),
+                _ => None,
+            },
+            upstream_tls: match (
+                &self.upstream_tls_cert,
+                &self.upstream_tls_key,
+                &self.upstream_tls_ca,
+            ) {
+                (None, None, None) => None,
+                _ => Some(config::UpstreamTlsConfig {
+                    client_cert_path: self.upstream_tls_cert.clone(),
+                    client_key_path: self.upstream_tls_key.clone(),
+                    ca_cert_path: self.upstream_tls_ca.clone(),
+                }),
+            },
+            pd_zone_affinity: self.pd_zone_affinity,
+            min_healthy_workers: self.min_healthy_workers,
+            payload_limits: config::PayloadLimitsConfig {
+                generate: self.max_payload_size_generate,
+                chat: self.max_payload_size_chat,
+                completions: self.max_payload_size_completions,
+                embeddings: self.max_payload_size_embeddings,
+                rerank: self.max_payload_size_rerank,
+                responses: self.max_payload_size_responses,
+            },
+            max_prompt_chars: self.max_prompt_chars,
+            default_max_tokens: self.default_max_tokens,
+            max_tokens_limit: self.max_tokens_limit,
             disable_retries: self.disable_retries,
             disable_circuit_breaker: self.disable_circuit_breaker,
             health_check: config::HealthCheckConfig {
@@ -235,11 +473,18 @@ impl Router {
                 timeout_secs: self.health_check_timeout_secs,
                 check_interval_secs: self.health_check_interval_secs,
                 endpoint: self.health_check_endpoint.clone(),
+                method: match self.health_check_method.as_str() {
+                    "post" => config::HealthCheckMethod::Post,
+                    _ => config::HealthCheckMethod::Get,
+                },
+                expected_body: self.health_check_expected_body.clone(),
+                generation_probe: self.health_check_generation_probe,
             },
             enable_igw: self.enable_igw,
             model_path: self.model_path.clone(),
             tokenizer_path: self.tokenizer_path.clone(),
             history_backend: config::HistoryBackend::Memory,
+            memory_history: config::MemoryHistoryConfig::default(),
             enable_profiling: false, // Profiling disabled in Python binding by default
             profile_timeout_secs: 10, // Default profiling timeout
         })
@@ -262,11 +507,21 @@ impl Router {
         eviction_interval_secs = 120,
         max_tree_size = 2usize.pow(26),
         max_payload_size = 512 * 1024 * 1024,  // 512MB default for large batches
+        max_payload_size_generate = None,
+        max_payload_size_chat = None,
+        max_payload_size_completions = None,
+        max_payload_size_embeddings = None,
+        max_payload_size_rerank = None,
+        max_payload_size_responses = None,
+        max_prompt_chars = None,
+        default_max_tokens = None,
+        max_tokens_limit = None,
         intra_node_data_parallel_size = 1,
         api_key = None,
         api_key_validation_urls = vec![],
         log_dir = None,
         log_level = None,
+        log_json = false,
         service_discovery = false,
         selector = HashMap::new(),
         service_discovery_port = 80,
@@ -277,6 +532,10 @@ impl Router {
         prometheus_port = None,
         prometheus_host = None,
         request_timeout_secs = 1800,  // Add configurable request timeout
+        proxy_timeout_secs = 10,
+        generation_timeout_secs = None,
+        max_request_timeout_secs = None,
+        slow_request_threshold_secs = None,
         request_id_headers = None,  // Custom request ID headers
         pd_disaggregation = false,  // New flag for PD mode
         vllm_pd_disaggregation = false,  // New flag for PD mode
@@ -292,24 +551,81 @@ impl Router {
         retry_max_backoff_ms = 30_000,
         retry_backoff_multiplier = 1.5,
         retry_jitter_factor = 0.2,
+        retry_full_jitter = false,
+        retry_max_fraction = None,
+        retry_on = String::from("all"),
         disable_retries = false,
         // Circuit breaker defaults
         cb_failure_threshold = 10,
         cb_success_threshold = 3,
         cb_timeout_duration_secs = 60,
         cb_window_duration_secs = 120,
+        cb_half_open_max_concurrent = 1,
         disable_circuit_breaker = false,
+        hedge_delay_ms = None,
+        mirror_worker_urls = vec![],
+        mirror_fraction = 1.0,
+        worker_rate_limit_rps = None,
+        worker_rate_limit_burst = None,
+        worker_warmup_requests = None,
+        worker_warmup_prompt = String::from("Hello"),
+        model_version_check_interval_secs = None,
+        model_version_mismatch = String::from("warn"),
+        outlier_detection_interval_secs = None,
+        outlier_ejection_secs = 30,
+        outlier_max_ejection_percent = 10,
+        response_cache_size = None,
+        response_cache_ttl_secs = 300,
+        idempotency_cache_size = None,
+        idempotency_ttl_secs = 86400,
+        forward_headers = vec![],
+        block_headers = vec![],
+        log_bodies_routes = vec![],
+        log_bodies_redact_fields = vec![],
+        allow_worker_pinning = false,
+        expose_routing_headers = false,
+        upstream_auth = None,
+        upstream_tls_cert = None,
+        upstream_tls_key = None,
+        upstream_tls_ca = None,
+        pd_zone_affinity = false,
         // Health check defaults
         health_failure_threshold = 3,
         health_success_threshold = 2,
         health_check_timeout_secs = 5,
         health_check_interval_secs = 60,
         health_check_endpoint = String::from("/health"),
+        health_check_method = String::from("get"),
+        health_check_expected_body = None,
+        health_check_generation_probe = false,
+        min_healthy_workers = 1,
         // IGW defaults
         enable_igw = false,
         queue_size = 100,
         queue_timeout_secs = 60,
         rate_limit_tokens_per_second = None,
+        model_concurrency_limits = HashMap::new(),
+        adaptive_concurrency = false,
+        adaptive_concurrency_initial_limit = 32,
+        adaptive_concurrency_min_limit = 4,
+        model_policies = HashMap::new(),
+        queue_fairness = String::from("fifo"),
+        queue_weights = HashMap::new(),
+        queue_priority_aging_secs = 10,
+        vllm_discovery_stale_secs = 30,
+        model_aliases = HashMap::new(),
+        model_fallbacks = HashMap::new(),
+        model_traffic_splits = HashMap::new(),
+        advertise_model_aliases = false,
+        rewrite_stream_model = false,
+        strict_model_routing = false,
+        max_concurrent_streams = None,
+        stream_buffer_size = 64,
+        stream_keepalive_secs = None,
+        shutdown_drain_timeout_secs = 30,
+        batch_max_concurrency = 8,
+        enable_response_compression = false,
+        enable_upstream_compression = false,
         // Tokenizer defaults
         model_path = None,
         tokenizer_path = None,
@@ -328,11 +644,21 @@ impl Router {
         eviction_interval_secs: u64,
         max_tree_size: usize,
         max_payload_size: usize,
+        max_payload_size_generate: Option<usize>,
+        max_payload_size_chat: Option<usize>,
+        max_payload_size_completions: Option<usize>,
+        max_payload_size_embeddings: Option<usize>,
+        max_payload_size_rerank: Option<usize>,
+        max_payload_size_responses: Option<usize>,
+        max_prompt_chars: Option<usize>,
+        default_max_tokens: Option<u32>,
+        max_tokens_limit: Option<u32>,
         intra_node_data_parallel_size: usize,
         api_key: Option<String>,
         api_key_validation_urls: Vec<String>,
         log_dir: Option<String>,
         log_level: Option<String>,
+        log_json: bool,
         service_discovery: bool,
         selector: HashMap<String, String>,
         service_discovery_port: u16,
@@ -343,6 +669,10 @@ impl Router {
         prometheus_port: Option<u16>,
         prometheus_host: Option<String>,
         request_timeout_secs: u64,
+        proxy_timeout_secs: u64,
+        generation_timeout_secs: Option<u64>,
+        max_request_timeout_secs: Option<u64>,
+        slow_request_threshold_secs: Option<u64>,
         request_id_headers: Option<Vec<String>>,
         pd_disaggregation: bool,
         vllm_pd_disaggregation: bool,
@@ -357,21 +687,78 @@ impl Router {
         retry_max_backoff_ms: u64,
         retry_backoff_multiplier: f32,
         retry_jitter_factor: f32,
+        retry_full_jitter: bool,
+        retry_max_fraction: Option<f32>,
+        retry_on: String,
         disable_retries: bool,
         cb_failure_threshold: u32,
         cb_success_threshold: u32,
         cb_timeout_duration_secs: u64,
         cb_window_duration_secs: u64,
+        cb_half_open_max_concurrent: u32,
         disable_circuit_breaker: bool,
+        hedge_delay_ms: Option<u64>,
+        mirror_worker_urls: Vec<String>,
+        mirror_fraction: f64,
+        worker_rate_limit_rps: Option<usize>,
+        worker_rate_limit_burst: Option<usize>,
+        worker_warmup_requests: Option<usize>,
+        worker_warmup_prompt: String,
+        model_version_check_interval_secs: Option<u64>,
+        model_version_mismatch: String,
+        outlier_detection_interval_secs: Option<u64>,
+        outlier_ejection_secs: u64,
+        outlier_max_ejection_percent: u32,
+        response_cache_size: Option<usize>,
+        response_cache_ttl_secs: u64,
+        idempotency_cache_size: Option<usize>,
+        idempotency_ttl_secs: u64,
+        forward_headers: Vec<String>,
+        block_headers: Vec<String>,
+        log_bodies_routes: Vec<String>,
+        log_bodies_redact_fields: Vec<String>,
+        allow_worker_pinning: bool,
+        expose_routing_headers: bool,
+        upstream_auth: Option<String>,
+        upstream_tls_cert: Option<String>,
+        upstream_tls_key: Option<String>,
+        upstream_tls_ca: Option<String>,
+        pd_zone_affinity: bool,
         health_failure_threshold: u32,
         health_success_threshold: u32,
         health_check_timeout_secs: u64,
         health_check_interval_secs: u64,
         health_check_endpoint: String,
+        health_check_method: String,
+        health_check_expected_body: Option<String>,
+        health_check_generation_probe: bool,
+        min_healthy_workers: usize,
         enable_igw: bool,
         queue_size: usize,
         queue_timeout_secs: u64,
         rate_limit_tokens_per_second: Option<usize>,
+        model_concurrency_limits: HashMap<String, usize>,
+        adaptive_concurrency: bool,
+        adaptive_concurrency_initial_limit: usize,
+        adaptive_concurrency_min_limit: usize,
+        model_policies: HashMap<String, PolicyType>,
+        queue_fairness: String,
+        queue_weights: HashMap<String, u32>,
+        queue_priority_aging_secs: u64,
+        vllm_discovery_stale_secs: u64,
+        model_aliases: HashMap<String, String>,
+        model_fallbacks: HashMap<String, Vec<String>>,
+        model_traffic_splits: HashMap<String, Vec<(String, u32)>>,
+        advertise_model_aliases: bool,
+        rewrite_stream_model: bool,
+        strict_model_routing: bool,
+        max_concurrent_streams: Option<usize>,
+        stream_buffer_size: usize,
+        stream_keepalive_secs: Option<u64>,
+        shutdown_drain_timeout_secs: u64,
+        batch_max_concurrency: usize,
+        enable_response_compression: bool,
+        enable_upstream_compression: bool,
         model_path: Option<String>,
         tokenizer_path: Option<String>,
     ) -> PyResult<Self> {
@@ -405,11 +792,21 @@ impl Router {
             eviction_interval_secs,
             max_tree_size,
             max_payload_size,
+            max_payload_size_generate,
+            max_payload_size_chat,
+            max_payload_size_completions,
+            max_payload_size_embeddings,
+            max_payload_size_rerank,
+            max_payload_size_responses,
+            max_prompt_chars,
+            default_max_tokens,
+            max_tokens_limit,
             intra_node_data_parallel_size,
             api_key,
             api_key_validation_urls,
             log_dir,
             log_level,
+            log_json,
             service_discovery,
             selector,
             service_discovery_port,
@@ -420,6 +817,10 @@ impl Router {
             prometheus_port,
             prometheus_host,
             request_timeout_secs,
+            proxy_timeout_secs,
+            generation_timeout_secs,
+            max_request_timeout_secs,
+            slow_request_threshold_secs,
             request_id_headers,
             pd_disaggregation,
             vllm_pd_disaggregation,
@@ -434,21 +835,78 @@ impl Router {
             retry_max_backoff_ms,
             retry_backoff_multiplier,
             retry_jitter_factor,
+            retry_full_jitter,
+            retry_max_fraction,
+            retry_on,
             disable_retries,
             cb_failure_threshold,
             cb_success_threshold,
             cb_timeout_duration_secs,
             cb_window_duration_secs,
+            cb_half_open_max_concurrent,
             disable_circuit_breaker,
+            hedge_delay_ms,
+            mirror_worker_urls,
+            mirror_fraction,
+            worker_rate_limit_rps,
+            worker_rate_limit_burst,
+            worker_warmup_requests,
+            worker_warmup_prompt,
+            model_version_check_interval_secs,
+            model_version_mismatch,
+            outlier_detection_interval_secs,
+            outlier_ejection_secs,
+            outlier_max_ejection_percent,
+            response_cache_size,
+            response_cache_ttl_secs,
+            idempotency_cache_size,
+            idempotency_ttl_secs,
+            forward_headers,
+            block_headers,
+            log_bodies_routes,
+            log_bodies_redact_fields,
+            allow_worker_pinning,
+            expose_routing_headers,
+            upstream_auth,
+            upstream_tls_cert,
+            upstream_tls_key,
+            upstream_tls_ca,
+            pd_zone_affinity,
             health_failure_threshold,
             health_success_threshold,
             health_check_timeout_secs,
             health_check_interval_secs,
             health_check_endpoint,
+            health_check_method,
+            health_check_expected_body,
+            health_check_generation_probe,
+            min_healthy_workers,
             enable_igw,
             queue_size,
             queue_timeout_secs,
             rate_limit_tokens_per_second,
+            model_concurrency_limits,
+            adaptive_concurrency,
+            adaptive_concurrency_initial_limit,
+            adaptive_concurrency_min_limit,
+            model_policies,
+            queue_fairness,
+            queue_weights,
+            queue_priority_aging_secs,
+            vllm_discovery_stale_secs,
+            model_aliases,
+            model_fallbacks,
+            model_traffic_splits,
+            advertise_model_aliases,
+            rewrite_stream_model,
+            strict_model_routing,
+            max_concurrent_streams,
+            stream_buffer_size,
+            stream_keepalive_secs,
+            shutdown_drain_timeout_secs,
+            batch_max_concurrency,
+            enable_response_compression,
+            enable_upstream_compression,
             connection_mode,
             model_path,
             tokenizer_path,
@@ -508,6 +966,7 @@ impl Router {
                 max_payload_size: self.max_payload_size,
                 log_dir: self.log_dir.clone(),
                 log_level: self.log_level.clone(),
+                log_json: self.log_json,
                 service_discovery_config,
                 prometheus_config,
                 request_timeout_secs: self.request_timeout_secs,
@@ -1,4 +1,6 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use tracing::Level;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
@@ -6,7 +8,13 @@ use tracing_log::LogTracer;
 use tracing_subscriber::fmt::time::ChronoUtc;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::{EnvFilter, Layer};
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+/// Tracing target for structured per-request access logs
+///
+/// Access logs are only enabled when `json_format` is set, so they can be
+/// shipped to an observability pipeline without cluttering human-readable output.
+pub const ACCESS_LOG_TARGET: &str = "router.access";
 
 /// Configuration for the logging system
 #[derive(Debug, Clone)]
@@ -45,6 +53,108 @@ impl Default for LoggingConfig {
 #[allow(dead_code)]
 pub struct LogGuard {
     _file_guard: Option<WorkerGuard>,
+    level_handle: Option<LogLevelHandle>,
+}
+
+impl LogGuard {
+    /// Handle for changing the log filter at runtime, if this call installed the subscriber
+    pub fn level_handle(&self) -> Option<LogLevelHandle> {
+        self.level_handle.clone()
+    }
+}
+
+/// Changes the log filter of the running subscriber
+///
+/// The filter is rebuilt from the default directives plus one directive per
+/// target override, so raising one target to `debug` leaves the rest of the
+/// router at its configured level.
+#[derive(Clone)]
+pub struct LogLevelHandle {
+    reload: reload::Handle<EnvFilter, Registry>,
+    state: Arc<Mutex<FilterState>>,
+}
+
+#[derive(Clone)]
+struct FilterState {
+    targets: Vec<String>,
+    access_log: bool,
+    /// Directives for the default targets (or `RUST_LOG` until the level is first changed)
+    base: String,
+    /// Per-target levels set at runtime
+    overrides: BTreeMap<String, String>,
+}
+
+impl FilterState {
+    fn directives(&self) -> String {
+        std::iter::once(self.base.clone())
+            .chain(
+                self.overrides
+                    .iter()
+                    .map(|(target, level)| format!("{}={}", target, level)),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl std::fmt::Debug for LogLevelHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogLevelHandle")
+            .field("filter", &self.current())
+            .finish()
+    }
+}
+
+impl LogLevelHandle {
+    /// The filter directives currently in effect
+    pub fn current(&self) -> String {
+        self.state.lock().unwrap().directives()
+    }
+
+    /// Set the level for `target`, or for the default targets when `target` is None
+    ///
+    /// `level` is one of trace, debug, info, warn, error or off. Targets are
+    /// tracing targets, i.e. module paths such as
+    /// `vllm_router_rs::policies::consistent_hash`. Returns the new directives.
+    pub fn set_level(&self, level: &str, target: Option<&str>) -> Result<String, String> {
+        let level = level.to_ascii_lowercase();
+        if !matches!(
+            level.as_str(),
+            "trace" | "debug" | "info" | "warn" | "error" | "off"
+        ) {
+            return Err(format!("Invalid log level '{}'", level));
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let mut next = state.clone();
+        match target {
+            Some(target) => {
+                if target.is_empty() || target.contains([',', '=', ' ']) {
+                    return Err(format!("Invalid log target '{}'", target));
+                }
+                next.overrides.insert(target.to_string(), level);
+            }
+            None => next.base = target_directives(&next.targets, &level, next.access_log),
+        }
+
+        let directives = next.directives();
+        let filter = EnvFilter::try_new(&directives).map_err(|e| e.to_string())?;
+        self.reload.reload(filter).map_err(|e| e.to_string())?;
+        *state = next;
+        Ok(directives)
+    }
+}
+
+/// Filter directives giving each target `level`, plus access logs when enabled
+fn target_directives(targets: &[String], level: &str, access_log: bool) -> String {
+    let mut directives: Vec<String> = targets
+        .iter()
+        .map(|target| format!("{}={}", target, level))
+        .collect();
+    if access_log {
+        directives.push(format!("{}=info", ACCESS_LOG_TARGET));
+    }
+    directives.join(",")
 }
 
 /// Initialize the logging system with the given configuration
@@ -71,26 +181,26 @@ pub fn init_logging(config: LoggingConfig) -> LogGuard {
     };
 
     // Create env filter
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-        // Format: <target>=<level>,<target2>=<level2>,...
-        let filter_string = if let Some(targets) = &config.log_targets {
-            targets
-                .iter()
-                .enumerate()
-                .map(|(i, target)| {
-                    if i > 0 {
-                        format!(",{}={}", target, level_filter)
-                    } else {
-                        format!("{}={}", target, level_filter)
-                    }
-                })
-                .collect::<String>()
-        } else {
-            format!("vllm_router_rs={}", level_filter)
-        };
-
-        EnvFilter::new(filter_string)
-    });
+    // Format: <target>=<level>,<target2>=<level2>,...
+    let targets = config
+        .log_targets
+        .clone()
+        .unwrap_or_else(|| vec!["vllm_router_rs".to_string()]);
+    let default_directives = target_directives(&targets, level_filter, config.json_format);
+    let (env_filter, base) = match std::env::var(EnvFilter::DEFAULT_ENV) {
+        Ok(directives) => match EnvFilter::try_new(&directives) {
+            Ok(filter) => (filter, directives),
+            Err(_) => (
+                EnvFilter::new(&default_directives),
+                default_directives.clone(),
+            ),
+        },
+        Err(_) => (
+            EnvFilter::new(&default_directives),
+            default_directives.clone(),
+        ),
+    };
+    let (env_filter, reload_handle) = reload::Layer::new(env_filter);
 
     // Setup stdout/stderr layer
     let mut layers = Vec::new();
@@ -124,7 +234,10 @@ pub fn init_logging(config: LoggingConfig) -> LogGuard {
         if !log_dir.exists() {
             if let Err(e) = std::fs::create_dir_all(&log_dir) {
                 eprintln!("Failed to create log directory: {}", e);
-                return LogGuard { _file_guard: None };
+                return LogGuard {
+                    _file_guard: None,
+                    level_handle: None,
+                };
             }
         }
 
@@ -151,13 +264,104 @@ pub fn init_logging(config: LoggingConfig) -> LogGuard {
 
     // Initialize the subscriber with all layers
     // Use try_init to handle errors gracefully in case another subscriber is already set
-    let _ = tracing_subscriber::registry()
+    let installed = tracing_subscriber::registry()
         .with(env_filter)
         .with(layers)
-        .try_init();
+        .try_init()
+        .is_ok();
+
+    let level_handle = installed.then(|| LogLevelHandle {
+        reload: reload_handle,
+        state: Arc::new(Mutex::new(FilterState {
+            targets,
+            access_log: config.json_format,
+            base,
+            overrides: BTreeMap::new(),
+        })),
+    });
 
     // Return the guard to keep the file appender worker thread alive
     LogGuard {
         _file_guard: file_guard,
+        level_handle,
+    }
+}
+
+/// Routing decision metadata recorded for each proxied request
+#[derive(Debug)]
+pub struct AccessLogEntry<'a> {
+    pub route: &'a str,
+    pub model_id: Option<&'a str>,
+    pub worker_url: Option<&'a str>,
+    pub policy: Option<&'a str>,
+    pub retries: u32,
+    pub status: u16,
+    pub latency_ms: u64,
+    pub stream: bool,
+}
+
+/// Emit a structured access log event on the `router.access` target
+pub fn log_access(entry: &AccessLogEntry<'_>) {
+    tracing::info!(
+        target: ACCESS_LOG_TARGET,
+        route = entry.route,
+        model_id = entry.model_id,
+        worker_url = entry.worker_url,
+        policy = entry.policy,
+        retries = entry.retries,
+        status = entry.status,
+        latency_ms = entry.latency_ms,
+        stream = entry.stream,
+        "request routed"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_handle(base: &str) -> (LogLevelHandle, reload::Layer<EnvFilter, Registry>) {
+        let (layer, reload) = reload::Layer::new(EnvFilter::new(base));
+        let handle = LogLevelHandle {
+            reload,
+            state: Arc::new(Mutex::new(FilterState {
+                targets: vec!["vllm_router_rs".to_string()],
+                access_log: true,
+                base: base.to_string(),
+                overrides: BTreeMap::new(),
+            })),
+        };
+        (handle, layer)
+    }
+
+    #[test]
+    fn test_set_level_for_target_keeps_defaults() {
+        let (handle, _layer) = test_handle("vllm_router_rs=info");
+
+        let filter = handle
+            .set_level("DEBUG", Some("vllm_router_rs::policies::consistent_hash"))
+            .unwrap();
+        assert_eq!(
+            filter,
+            "vllm_router_rs=info,vllm_router_rs::policies::consistent_hash=debug"
+        );
+
+        // Changing the default level keeps target overrides
+        let filter = handle.set_level("warn", None).unwrap();
+        assert_eq!(
+            filter,
+            "vllm_router_rs=warn,router.access=info,vllm_router_rs::policies::consistent_hash=debug"
+        );
+        assert_eq!(handle.current(), filter);
+    }
+
+    #[test]
+    fn test_set_level_rejects_invalid_input() {
+        let (handle, _layer) = test_handle("vllm_router_rs=info");
+
+        assert!(handle.set_level("verbose", None).is_err());
+        assert!(handle.set_level("debug", Some("a=b")).is_err());
+        assert!(handle.set_level("debug", Some("")).is_err());
+        assert_eq!(handle.current(), "vllm_router_rs=info");
     }
 }
@@ -1,9 +1,14 @@
 use clap::{ArgAction, Parser, ValueEnum};
 use std::collections::HashMap;
 use vllm_router_rs::config::{
-    CircuitBreakerConfig, ConfigError, ConfigResult, ConnectionMode, DiscoveryConfig,
-    HealthCheckConfig, HistoryBackend, MetricsConfig, PolicyConfig, RetryConfig, RouterConfig,
-    RoutingMode,
+    default_body_redact_fields, default_header_deny, AdaptiveConcurrencyConfig, BodyLoggingConfig,
+    CircuitBreakerConfig, ColdStartPolicy, ConfigError, ConfigResult, ConnectionMode,
+    DiscoveryConfig, HeaderPolicyConfig, HealthCheckConfig, HealthCheckMethod, HedgingConfig,
+    HistoryBackend, IdempotencyConfig, MemoryHistoryConfig, MetricsConfig, ModelVersionCheckConfig,
+    OutlierDetectionConfig, PayloadLimitsConfig, PolicyConfig, QueueFairness, ResponseCacheConfig,
+    RetryConfig, RetryOn, RouterConfig, RoutingMode, SessionCookieConfig, TrafficMirrorConfig,
+    TrafficSplitPool, UpstreamAuthConfig, UpstreamTlsConfig, VersionMismatchAction, WeightSource,
+    WorkerRateLimitConfig, WorkerWarmupConfig,
 };
 use vllm_router_rs::metrics::PrometheusConfig;
 use vllm_router_rs::server::{self, ServerConfig};
@@ -68,6 +73,19 @@ impl std::fmt::Display for Backend {
     }
 }
 
+/// Load balancing policies accepted by --policy and --model-policies
+const POLICY_NAMES: [&str; 9] = [
+    "random",
+    "round_robin",
+    "cache_aware",
+    "power_of_two",
+    "load_weighted_random",
+    "latency_aware",
+    "consistent_hash",
+    "weighted_round_robin",
+    "header_route",
+];
+
 #[derive(Parser, Debug)]
 #[command(name = "vllm-router")]
 #[command(version)]
@@ -123,7 +141,7 @@ struct CliArgs {
     worker_urls: Vec<String>,
 
     /// Load balancing policy to use
-    #[arg(long, default_value = "cache_aware", value_parser = ["random", "round_robin", "cache_aware", "power_of_two", "consistent_hash"])]
+    #[arg(long, default_value = "cache_aware", value_parser = POLICY_NAMES)]
     policy: String,
 
     /// Enable PD (Prefill-Decode) disaggregated mode
@@ -139,16 +157,20 @@ struct CliArgs {
     #[arg(long)]
     vllm_discovery_address: Option<String>,
 
+    /// Seconds without any ZMQ service discovery registration after which discovery is reported stale and readiness fails
+    #[arg(long, default_value_t = 30)]
+    vllm_discovery_stale_secs: u64,
+
     /// Decode server URL (can be specified multiple times)
     #[arg(long, action = ArgAction::Append)]
     decode: Vec<String>,
 
     /// Specific policy for prefill nodes in PD mode
-    #[arg(long, value_parser = ["random", "round_robin", "cache_aware", "power_of_two", "consistent_hash"])]
+    #[arg(long, value_parser = ["random", "round_robin", "cache_aware", "power_of_two", "load_weighted_random", "latency_aware", "consistent_hash", "weighted_round_robin"])]
     prefill_policy: Option<String>,
 
     /// Specific policy for decode nodes in PD mode
-    #[arg(long, value_parser = ["random", "round_robin", "cache_aware", "power_of_two", "consistent_hash"])]
+    #[arg(long, value_parser = ["random", "round_robin", "cache_aware", "power_of_two", "load_weighted_random", "latency_aware", "consistent_hash", "weighted_round_robin"])]
     decode_policy: Option<String>,
 
     /// Timeout in seconds for worker startup
@@ -179,10 +201,106 @@ struct CliArgs {
     #[arg(long, default_value_t = 67108864)] // 2^26
     max_tree_size: usize,
 
+    /// Worker selection for cache_aware requests whose best prefix match is below --cache-threshold
+    #[arg(long, default_value = "least_load", value_parser = ["round_robin", "least_load"])]
+    cold_start_policy: String,
+
+    /// Weight kept by the previous average when latency_aware routing folds in a new latency sample (0-1)
+    #[arg(long, default_value_t = 0.8)]
+    latency_decay: f64,
+
+    /// Worker attribute used as the weight for weighted_round_robin routing
+    #[arg(long, default_value = "priority", value_parser = ["priority", "cost"])]
+    weight_source: String,
+
+    /// Virtual nodes per worker on the consistent_hash ring. More nodes spread keys more evenly at the cost of a larger ring
+    #[arg(long, default_value_t = 160)]
+    virtual_nodes: u32,
+
+    /// Scale each worker's consistent_hash virtual nodes by this attribute, relative to its default (priority 50, cost 1.0)
+    #[arg(long, value_parser = ["priority", "cost"])]
+    hash_weight_source: Option<String>,
+
+    /// Bounded loads for consistent_hash: a worker above this multiple of the average load passes its keys to the next worker on the ring (e.g. 1.25)
+    #[arg(long)]
+    hash_overload_factor: Option<f64>,
+
+    /// Hash consistent_hash requests without a session identifier on the client IP from X-Forwarded-For / X-Real-IP
+    #[arg(long, default_value_t = false)]
+    hash_client_ip: bool,
+
+    /// Request header whose value selects the worker pool for header_route routing
+    #[arg(long, default_value = "x-route-tier")]
+    route_header: String,
+
+    /// Worker label whose value identifies a header_route pool
+    #[arg(long, default_value = "tier")]
+    route_label: String,
+
+    /// header_route rules mapping header values to pools (format: header_value1=label_value1 header_value2=label_value2)
+    #[arg(long, num_args = 0..)]
+    header_routes: Vec<String>,
+
+    /// header_route pool for requests without a routed header value; all workers when unset
+    #[arg(long)]
+    header_route_default: Option<String>,
+
+    /// Policy selecting a worker within the header_route pool
+    #[arg(long, default_value = "round_robin", value_parser = POLICY_NAMES)]
+    header_route_sub_policy: String,
+
+    /// Cookie carrying the session identifier for consistent_hash routing
+    #[arg(long, default_value = "router_session")]
+    session_cookie_name: String,
+
+    /// Set the session cookie on responses to requests without a session identifier (consistent_hash)
+    #[arg(long, default_value_t = false)]
+    set_session_cookie: bool,
+
+    /// Secret used to sign session cookies; unsigned or tampered cookies are ignored when set
+    #[arg(long)]
+    session_cookie_secret: Option<String>,
+
     /// Maximum payload size in bytes
     #[arg(long, default_value_t = 536870912)] // 512MB
     max_payload_size: usize,
 
+    /// Maximum payload size in bytes for /generate, overriding --max-payload-size
+    #[arg(long)]
+    max_payload_size_generate: Option<usize>,
+
+    /// Maximum payload size in bytes for /v1/chat/completions, overriding --max-payload-size
+    #[arg(long)]
+    max_payload_size_chat: Option<usize>,
+
+    /// Maximum payload size in bytes for /v1/completions, overriding --max-payload-size
+    #[arg(long)]
+    max_payload_size_completions: Option<usize>,
+
+    /// Maximum payload size in bytes for /v1/embeddings, overriding --max-payload-size
+    #[arg(long)]
+    max_payload_size_embeddings: Option<usize>,
+
+    /// Maximum payload size in bytes for /rerank and /v1/rerank, overriding --max-payload-size
+    #[arg(long)]
+    max_payload_size_rerank: Option<usize>,
+
+    /// Maximum payload size in bytes for /v1/responses, overriding --max-payload-size
+    #[arg(long)]
+    max_payload_size_responses: Option<usize>,
+
+    /// Reject /generate, /v1/chat/completions, /v1/completions and /v1/responses requests whose prompt exceeds this many characters with 400
+    #[arg(long)]
+    max_prompt_chars: Option<usize>,
+
+    /// Set max_tokens to this on chat, completion and responses requests that omit it
+    #[arg(long)]
+    default_max_tokens: Option<u32>,
+
+    /// Clamp max_tokens of chat, completion and responses requests to this; requests that omit it get this limit
+    #[arg(long)]
+    max_tokens_limit: Option<u32>,
+
     /// Intra-node data parallel size (number of DP replicas per worker URL). When > 1, the router will create multiple worker instances per URL, one for each DP rank.
     #[arg(long, default_value_t = 1)]
     intra_node_data_parallel_size: usize,
@@ -191,6 +309,22 @@ struct CliArgs {
     #[arg(long)]
     api_key: Option<String>,
 
+    /// How requests to workers are authenticated: forward the client's Authorization header (passthrough), send --api-key as a bearer token (inject), or send none. Defaults to inject when --api-key is set (or OPENAI_API_KEY in vLLM PD mode), else passthrough
+    #[arg(long, value_parser = ["passthrough", "inject", "none"])]
+    upstream_auth: Option<String>,
+
+    /// PEM client certificate presented to workers for mutual TLS (requires --upstream-tls-key)
+    #[arg(long)]
+    upstream_tls_cert: Option<String>,
+
+    /// PEM PKCS#8 private key for --upstream-tls-cert
+    #[arg(long)]
+    upstream_tls_key: Option<String>,
+
+    /// PEM CA bundle trusted for worker certificates, in addition to the system roots
+    #[arg(long)]
+    upstream_tls_ca: Option<String>,
+
     /// API key validation URLs (defaults to env file)
     #[arg(long, num_args = 0..)]
     api_key_validation_urls: Vec<String>,
@@ -207,6 +341,10 @@ struct CliArgs {
     #[arg(long, default_value = "info", value_parser = ["debug", "info", "warn", "error"])]
     log_level: String,
 
+    /// Emit logs as JSON, including structured access logs on the router.access target
+    #[arg(long, default_value_t = false)]
+    log_json: bool,
+
     /// Enable Kubernetes service discovery
     #[arg(long, default_value_t = false)]
     service_discovery: bool,
@@ -247,10 +385,110 @@ struct CliArgs {
     #[arg(long, default_value_t = 1800)]
     request_timeout_secs: u64,
 
+    /// Timeout in seconds for short upstream calls (health, load, server and model info)
+    #[arg(long, default_value_t = 10)]
+    proxy_timeout_secs: u64,
+
+    /// Timeout in seconds for upstream generation requests (defaults to --request-timeout-secs)
+    #[arg(long)]
+    generation_timeout_secs: Option<u64>,
+
+    /// Honor an X-Router-Timeout-Secs request header setting the generation timeout, clamped to this many seconds (header ignored when unset)
+    #[arg(long)]
+    max_request_timeout_secs: Option<u64>,
+
+    /// Warn and count a slow request when a worker sends no response headers within this many seconds; the request is not aborted (disabled when unset)
+    #[arg(long)]
+    slow_request_threshold_secs: Option<u64>,
+
     /// Maximum number of concurrent requests allowed
     #[arg(long, default_value_t = 32768)]
     max_concurrent_requests: usize,
 
+    /// Per-model concurrency limits (format: model1=limit1 model2=limit2). Models without an entry share --max-concurrent-requests
+    #[arg(long, num_args = 0..)]
+    model_concurrency_limits: Vec<String>,
+
+    /// Adjust the global concurrency limit from observed latencies (gradient algorithm), up to --max-concurrent-requests
+    #[arg(long, default_value_t = false)]
+    adaptive_concurrency: bool,
+
+    /// Concurrency limit adaptive concurrency starts from
+    #[arg(long, default_value_t = 32)]
+    adaptive_concurrency_initial_limit: usize,
+
+    /// Lowest concurrency limit adaptive concurrency may settle on
+    #[arg(long, default_value_t = 4)]
+    adaptive_concurrency_min_limit: usize,
+
+    /// Per-model load balancing policies (format: model1=policy1 model2=policy2). Models without an entry use --policy
+    #[arg(long, num_args = 0..)]
+    model_policies: Vec<String>,
+
+    /// Order in which queued requests are served: fifo, weighted round-robin by request model or X-Client-Id header, or by X-Request-Priority header
+    #[arg(long, default_value = "fifo", value_parser = ["fifo", "model", "client", "priority"])]
+    queue_fairness: String,
+
+    /// Dequeue weights for fair queuing (format: key1=weight1 key2=weight2). Keys without an entry get weight 1
+    #[arg(long, num_args = 0..)]
+    queue_weights: Vec<String>,
+
+    /// Seconds of waiting that raise a queued request by one priority level with --queue-fairness priority
+    #[arg(long, default_value_t = 10)]
+    queue_priority_aging_secs: u64,
+
+    /// Model aliases resolved before routing (format: alias1=model1 alias2=model2)
+    #[arg(long, num_args = 0..)]
+    model_aliases: Vec<String>,
+
+    /// Fallback models tried in order when a model has no available workers (format: model1=fallback1,fallback2)
+    #[arg(long, num_args = 0..)]
+    model_fallbacks: Vec<String>,
+
+    /// Split a model's requests by weight across pools of workers serving other model IDs, for canary rollouts (format: model1=pool1:95,pool2:5)
+    #[arg(long, num_args = 0..)]
+    model_traffic_splits: Vec<String>,
+
+    /// List model aliases in /v1/models alongside the served models
+    #[arg(long, default_value_t = false)]
+    advertise_model_aliases: bool,
+
+    /// Rewrite the model of every streamed chunk to the model the client requested, e.g. an alias (parses each chunk)
+    #[arg(long, default_value_t = false)]
+    rewrite_stream_model: bool,
+
+    /// Route requests naming a model no worker serves by that model and fail them with 404, instead of spreading them over all workers
+    #[arg(long, default_value_t = false)]
+    strict_model_routing: bool,
+
+    /// Maximum concurrent streaming responses; further streaming requests get 503 (unlimited when unset)
+    #[arg(long)]
+    max_concurrent_streams: Option<usize>,
+
+    /// Chunks buffered per streaming response before reading from the worker pauses
+    #[arg(long, default_value_t = 64)]
+    stream_buffer_size: usize,
+
+    /// Send an SSE keep-alive comment after this many seconds without a chunk from the worker (disabled when unset)
+    #[arg(long)]
+    stream_keepalive_secs: Option<u64>,
+
+    /// Seconds to wait on shutdown for queued and in-flight requests to finish before exiting
+    #[arg(long, default_value_t = 30)]
+    shutdown_drain_timeout_secs: u64,
+
+    /// Requests of a /v1/batches job dispatched to workers at the same time
+    #[arg(long, default_value_t = 8)]
+    batch_max_concurrency: usize,
+
+    /// Compress responses for clients that send Accept-Encoding (gzip or br); streams are left uncompressed
+    #[arg(long, default_value_t = false)]
+    enable_response_compression: bool,
+
+    /// Ask workers for gzip or br encoded responses and decompress them in the router
+    #[arg(long, default_value_t = false)]
+    enable_upstream_compression: bool,
+
     /// CORS allowed origins
     #[arg(long, num_args = 0..)]
     cors_allowed_origins: Vec<String>,
@@ -276,6 +514,18 @@ struct CliArgs {
     #[arg(long, default_value_t = 0.2)]
     retry_jitter_factor: f32,
 
+    /// Randomize each retry backoff over [0, computed delay] instead of applying the jitter factor
+    #[arg(long, default_value_t = false)]
+    retry_full_jitter: bool,
+
+    /// Maximum ratio of retries to requests over a rolling window (e.g. 0.2); unset disables the retry budget
+    #[arg(long)]
+    retry_max_fraction: Option<f32>,
+
+    /// Failures retried on another worker: all (connection errors and retryable statuses) or connection_errors only
+    #[arg(long, default_value = "all", value_parser = ["all", "connection_errors"])]
+    retry_on: String,
+
     /// Disable retries
     #[arg(long, default_value_t = false)]
     disable_retries: bool,
@@ -297,10 +547,106 @@ struct CliArgs {
     #[arg(long, default_value_t = 120)]
     cb_window_duration_secs: u64,
 
+    /// Maximum concurrent probe requests while the circuit breaker is half-open
+    #[arg(long, default_value_t = 1)]
+    cb_half_open_max_concurrent: u32,
+
     /// Disable circuit breaker
     #[arg(long, default_value_t = false)]
     disable_circuit_breaker: bool,
 
+    /// Send a backup request to another worker if a non-streaming request takes longer than this (milliseconds). Disabled when unset
+    #[arg(long)]
+    hedge_delay_ms: Option<u64>,
+
+    /// Also send non-streaming requests to one of these shadow workers and discard their responses. Disabled when unset
+    #[arg(long, num_args = 0..)]
+    mirror_worker_urls: Vec<String>,
+
+    /// Share of requests mirrored to the shadow workers (0.0-1.0]
+    #[arg(long, default_value_t = 1.0)]
+    mirror_fraction: f64,
+
+    /// Maximum requests per second sent to any single worker; over-limit workers are skipped during selection. Disabled when unset
+    #[arg(long)]
+    worker_rate_limit_rps: Option<usize>,
+
+    /// Requests a single worker may receive in a burst under --worker-rate-limit-rps (defaults to the rate)
+    #[arg(long)]
+    worker_rate_limit_burst: Option<usize>,
+
+    /// Send this many rounds of a small completion and chat completion to a worker added at runtime before routing to it. Disabled when unset
+    #[arg(long)]
+    worker_warmup_requests: Option<usize>,
+
+    /// Prompt of the warmup requests sent under --worker-warmup-requests
+    #[arg(long, default_value = "Hello")]
+    worker_warmup_prompt: String,
+
+    /// Check the model version each worker reports on /get_model_info at this interval. Disabled when unset
+    #[arg(long)]
+    model_version_check_interval_secs: Option<u64>,
+
+    /// What to do when workers of one model report different versions: warn, or only route to the version most of the model's workers serve. Not applied to vLLM PD service discovery instances
+    #[arg(long, default_value = "warn", value_parser = ["warn", "split"])]
+    model_version_mismatch: String,
+
+    /// Eject workers whose error rate is an outlier among their model's workers, analyzing every this many seconds. Disabled when unset
+    #[arg(long)]
+    outlier_detection_interval_secs: Option<u64>,
+
+    /// Base ejection time in seconds of an outlier worker, multiplied by its consecutive ejections
+    #[arg(long, default_value_t = 30)]
+    outlier_ejection_secs: u64,
+
+    /// Most workers of a model ejected as outliers at once, in percent
+    #[arg(long, default_value_t = 10)]
+    outlier_max_ejection_percent: u32,
+
+    /// Cache up to this many responses to deterministic (temperature 0, n=1) non-streaming requests. Disabled when unset
+    #[arg(long)]
+    response_cache_size: Option<usize>,
+
+    /// Time in seconds a cached response stays valid
+    #[arg(long, default_value_t = 300)]
+    response_cache_ttl_secs: u64,
+
+    /// Remember the responses to up to this many Idempotency-Key values on POST /v1/responses and replay them to retries. Disabled when unset
+    #[arg(long)]
+    idempotency_cache_size: Option<usize>,
+
+    /// Time in seconds an Idempotency-Key is remembered
+    #[arg(long, default_value_t = 86400)]
+    idempotency_ttl_secs: u64,
+
+    /// Forward only these client headers to workers (case-insensitive). All headers not blocked are forwarded when unset
+    #[arg(long, num_args = 0..)]
+    forward_headers: Vec<String>,
+
+    /// Additional client headers never forwarded to workers. Host and hop-by-hop headers are always blocked
+    #[arg(long, num_args = 0..)]
+    block_headers: Vec<String>,
+
+    /// Log request and response bodies of these routes at trace level, for debugging. Regular HTTP mode only. Disabled when unset
+    #[arg(long, num_args = 0..)]
+    log_bodies_routes: Vec<String>,
+
+    /// Fields redacted from logged bodies, e.g. messages[].content. Prompt and completion text fields when unset
+    #[arg(long, num_args = 0..)]
+    log_bodies_redact_fields: Vec<String>,
+
+    /// In vLLM PD mode, prefer decode workers in the same zone as the selected prefill worker, falling back to other zones
+    #[arg(long, default_value_t = false)]
+    pd_zone_affinity: bool,
+
+    /// Honor the X-Router-Target-Worker header to route a request to a specific worker (for debugging and canaries)
+    #[arg(long, default_value_t = false)]
+    allow_worker_pinning: bool,
+
+    /// Add X-Router-Worker, X-Router-Policy and X-Router-Retries headers to responses (reveals worker addresses to clients)
+    #[arg(long, default_value_t = false)]
+    expose_routing_headers: bool,
+
     // Health check configuration
     /// Number of consecutive health check failures before marking worker unhealthy
     #[arg(long, default_value_t = 3)]
@@ -322,6 +668,22 @@ struct CliArgs {
     #[arg(long, default_value = "/health")]
     health_check_endpoint: String,
 
+    /// HTTP method for health check requests
+    #[arg(long, default_value = "get", value_parser = ["get", "post"])]
+    health_check_method: String,
+
+    /// Body a health check response must match: a substring, or $.path.to.field=value for a JSON field
+    #[arg(long)]
+    health_check_expected_body: Option<String>,
+
+    /// Also require a one-token completion to succeed in runtime health checks (uses GPU time)
+    #[arg(long, default_value_t = false)]
+    health_check_generation_probe: bool,
+
+    /// Healthy workers a model needs before the router serves it; below this, readiness fails and requests get 503
+    #[arg(long, default_value_t = 1)]
+    min_healthy_workers: usize,
+
     // IGW (Inference Gateway) configuration
     /// Enable Inference Gateway mode
     #[arg(long, default_value_t = false)]
@@ -336,10 +698,26 @@ struct CliArgs {
     #[arg(long)]
     tokenizer_path: Option<String>,
 
-    /// History backend configuration (memory or none)
-    #[arg(long, default_value = "memory", value_parser = ["memory", "none"])]
+    /// History backend configuration (memory, none, or redis)
+    #[arg(long, default_value = "memory", value_parser = ["memory", "none", "redis"])]
     history_backend: String,
 
+    /// Redis URL for the redis history backend (e.g. redis://localhost:6379/0)
+    #[arg(long)]
+    history_redis_url: Option<String>,
+
+    /// Keep at most this many responses in the memory history backend, evicting the least recently used. Unbounded when unset
+    #[arg(long)]
+    history_max_responses: Option<usize>,
+
+    /// Drop responses from the memory history backend after this many seconds. Kept forever when unset
+    #[arg(long)]
+    history_response_ttl_secs: Option<u64>,
+
+    /// Interval in seconds between sweeps for expired history responses
+    #[arg(long, default_value_t = 60)]
+    history_eviction_interval_secs: u64,
+
     /// Enable profiling calls to vLLM workers
     #[arg(long, default_value_t = false)]
     profile: bool,
@@ -371,6 +749,156 @@ impl CliArgs {
         map
     }
 
+    /// Parse model=limit strings into per-model concurrency limits
+    fn parse_model_concurrency_limits(items: &[String]) -> ConfigResult<HashMap<String, usize>> {
+        let mut limits = HashMap::new();
+        for item in items {
+            let parsed = item
+                .split_once('=')
+                .and_then(|(model, limit)| Some((model, limit.parse::<usize>().ok()?)));
+            match parsed {
+                Some((model, limit)) if !model.is_empty() => {
+                    limits.insert(model.to_string(), limit);
+                }
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        field: "model_concurrency_limits".to_string(),
+                        value: item.clone(),
+                        reason: "Expected format model=limit".to_string(),
+                    });
+                }
+            }
+        }
+        Ok(limits)
+    }
+
+    /// Parse model=policy strings into per-model policies
+    ///
+    /// Policy parameters such as cache thresholds come from the global flags.
+    fn parse_model_policies(
+        &self,
+        items: &[String],
+    ) -> ConfigResult<HashMap<String, PolicyConfig>> {
+        let mut policies = HashMap::new();
+        for item in items {
+            match item.split_once('=') {
+                Some((model, policy)) if !model.is_empty() && POLICY_NAMES.contains(&policy) => {
+                    policies.insert(model.to_string(), self.parse_policy(policy));
+                }
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        field: "model_policies".to_string(),
+                        value: item.clone(),
+                        reason: format!(
+                            "Expected format model=policy with policy one of: {}",
+                            POLICY_NAMES.join(", ")
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(policies)
+    }
+
+    /// Parse alias=model strings into model aliases
+    fn parse_model_aliases(items: &[String]) -> ConfigResult<HashMap<String, String>> {
+        let mut aliases = HashMap::new();
+        for item in items {
+            match item.split_once('=') {
+                Some((alias, model)) if !alias.is_empty() && !model.is_empty() => {
+                    aliases.insert(alias.to_string(), model.to_string());
+                }
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        field: "model_aliases".to_string(),
+                        value: item.clone(),
+                        reason: "Expected format alias=model".to_string(),
+                    });
+                }
+            }
+        }
+        Ok(aliases)
+    }
+
+    /// Parse model=fallback1,fallback2 strings into fallback chains
+    fn parse_model_fallbacks(items: &[String]) -> ConfigResult<HashMap<String, Vec<String>>> {
+        let mut fallbacks = HashMap::new();
+        for item in items {
+            match item.split_once('=') {
+                Some((model, chain)) if !model.is_empty() && !chain.is_empty() => {
+                    let chain = chain.split(',').map(|m| m.trim().to_string()).collect();
+                    fallbacks.insert(model.to_string(), chain);
+                }
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        field: "model_fallbacks".to_string(),
+                        value: item.clone(),
+                        reason: "Expected format model=fallback1,fallback2".to_string(),
+                    });
+                }
+            }
+        }
+        Ok(fallbacks)
+    }
+
+    /// Parse model=pool1:weight1,pool2:weight2 strings into traffic splits
+    fn parse_model_traffic_splits(
+        items: &[String],
+    ) -> ConfigResult<HashMap<String, Vec<TrafficSplitPool>>> {
+        let mut splits = HashMap::new();
+        for item in items {
+            let parsed = item.split_once('=').and_then(|(model, pools)| {
+                let pools = pools
+                    .split(',')
+                    .map(|pool| {
+                        let (name, weight) = pool.trim().split_once(':')?;
+                        Some(TrafficSplitPool {
+                            model: name.to_string(),
+                            weight: weight.parse().ok()?,
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some((model, pools))
+            });
+            match parsed {
+                Some((model, pools)) if !model.is_empty() => {
+                    splits.insert(model.to_string(), pools);
+                }
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        field: "model_traffic_splits".to_string(),
+                        value: item.clone(),
+                        reason: "Expected format model=pool1:weight1,pool2:weight2".to_string(),
+                    });
+                }
+            }
+        }
+        Ok(splits)
+    }
+
+    /// Parse key=weight strings into fair-queue weights
+    fn parse_queue_weights(items: &[String]) -> ConfigResult<HashMap<String, u32>> {
+        let mut weights = HashMap::new();
+        for item in items {
+            let parsed = item
+                .split_once('=')
+                .and_then(|(key, weight)| Some((key, weight.parse::<u32>().ok()?)));
+            match parsed {
+                Some((key, weight)) if !key.is_empty() => {
+                    weights.insert(key.to_string(), weight);
+                }
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        field: "queue_weights".to_string(),
+                        value: item.clone(),
+                        reason: "Expected format key=weight".to_string(),
+                    });
+                }
+            }
+        }
+        Ok(weights)
+    }
+
     /// Convert policy string to PolicyConfig
     fn parse_policy(&self, policy_str: &str) -> PolicyConfig {
         match policy_str {
@@ -382,12 +910,49 @@ impl CliArgs {
                 balance_rel_threshold: self.balance_rel_threshold,
                 eviction_interval_secs: self.eviction_interval,
                 max_tree_size: self.max_tree_size,
+                cold_start_policy: match self.cold_start_policy.as_str() {
+                    "round_robin" => ColdStartPolicy::RoundRobin,
+                    _ => ColdStartPolicy::LeastLoad,
+                },
             },
             "power_of_two" => PolicyConfig::PowerOfTwo {
                 load_check_interval_secs: 5, // Default value
             },
+            "load_weighted_random" => PolicyConfig::LoadWeightedRandom {
+                load_check_interval_secs: 5, // Default value
+            },
+            "latency_aware" => PolicyConfig::LatencyAware {
+                decay: self.latency_decay,
+            },
             "consistent_hash" => PolicyConfig::ConsistentHash {
-                virtual_nodes: 160, // Default value
+                virtual_nodes: self.virtual_nodes,
+                weight_source: self
+                    .hash_weight_source
+                    .as_deref()
+                    .map(|source| match source {
+                        "cost" => WeightSource::Cost,
+                        _ => WeightSource::Priority,
+                    }),
+                session_cookie: SessionCookieConfig {
+                    name: self.session_cookie_name.clone(),
+                    set_on_response: self.set_session_cookie,
+                    secret: self.session_cookie_secret.clone(),
+                },
+                overload_factor: self.hash_overload_factor,
+                hash_client_ip: self.hash_client_ip,
+            },
+            "weighted_round_robin" => PolicyConfig::WeightedRoundRobin {
+                weight_source: match self.weight_source.as_str() {
+                    "cost" => WeightSource::Cost,
+                    _ => WeightSource::Priority,
+                },
+            },
+            "header_route" => PolicyConfig::HeaderRoute {
+                header: self.route_header.clone(),
+                label: self.route_label.clone(),
+                routes: Self::parse_selector(&self.header_routes),
+                default_route: self.header_route_default.clone(),
+                sub_policy: Box::new(self.parse_policy(&self.header_route_sub_policy)),
             },
             _ => PolicyConfig::RoundRobin, // Fallback
         }
@@ -565,6 +1130,10 @@ impl CliArgs {
             port: self.port,
             max_payload_size: self.max_payload_size,
             request_timeout_secs: self.request_timeout_secs,
+            proxy_timeout_secs: self.proxy_timeout_secs,
+            generation_timeout_secs: self.generation_timeout_secs,
+            max_request_timeout_secs: self.max_request_timeout_secs,
+            slow_request_threshold_secs: self.slow_request_threshold_secs,
             worker_startup_timeout_secs: self.worker_startup_timeout_secs,
             worker_startup_check_interval_secs: self.worker_startup_check_interval,
             intra_node_data_parallel_size: self.intra_node_data_parallel_size,
@@ -589,13 +1158,126 @@ impl CliArgs {
                 max_backoff_ms: self.retry_max_backoff_ms,
                 backoff_multiplier: self.retry_backoff_multiplier,
                 jitter_factor: self.retry_jitter_factor,
+                full_jitter: self.retry_full_jitter,
+                max_retry_fraction: self.retry_max_fraction,
+                retry_on: match self.retry_on.as_str() {
+                    "connection_errors" => RetryOn::ConnectionErrors,
+                    _ => RetryOn::All,
+                },
             },
             circuit_breaker: CircuitBreakerConfig {
                 failure_threshold: self.cb_failure_threshold,
                 success_threshold: self.cb_success_threshold,
                 timeout_duration_secs: self.cb_timeout_duration_secs,
                 window_duration_secs: self.cb_window_duration_secs,
+                half_open_max_concurrent: self.cb_half_open_max_concurrent,
             },
+            hedging: self
+                .hedge_delay_ms
+                .map(|hedge_delay_ms| HedgingConfig { hedge_delay_ms }),
+            traffic_mirror: (!self.mirror_worker_urls.is_empty()).then(|| TrafficMirrorConfig {
+                worker_urls: self.mirror_worker_urls.clone(),
+                fraction: self.mirror_fraction,
+                timeout_secs: 60,
+            }),
+            worker_rate_limit: self.worker_rate_limit_rps.map(|requests_per_second| {
+                WorkerRateLimitConfig {
+                    requests_per_second,
+                    burst: self.worker_rate_limit_burst,
+                }
+            }),
+            worker_warmup: self
+                .worker_warmup_requests
+                .map(|requests| WorkerWarmupConfig {
+                    requests,
+                    prompt: self.worker_warmup_prompt.clone(),
+                }),
+            model_version_check: self.model_version_check_interval_secs.map(
+                |check_interval_secs| ModelVersionCheckConfig {
+                    check_interval_secs,
+                    on_mismatch: match self.model_version_mismatch.as_str() {
+                        "split" => VersionMismatchAction::Split,
+                        _ => VersionMismatchAction::Warn,
+                    },
+                },
+            ),
+            outlier_detection: self.outlier_detection_interval_secs.map(|interval_secs| {
+                let defaults = OutlierDetectionConfig::default();
+                OutlierDetectionConfig {
+                    interval_secs,
+                    base_ejection_secs: self.outlier_ejection_secs,
+                    max_ejection_secs: defaults.max_ejection_secs.max(self.outlier_ejection_secs),
+                    max_ejection_percent: self.outlier_max_ejection_percent,
+                    ..defaults
+                }
+            }),
+            response_cache: self
+                .response_cache_size
+                .map(|max_entries| ResponseCacheConfig {
+                    max_entries,
+                    ttl_secs: self.response_cache_ttl_secs,
+                }),
+            idempotency: self
+                .idempotency_cache_size
+                .map(|max_keys| IdempotencyConfig {
+                    max_keys,
+                    ttl_secs: self.idempotency_ttl_secs,
+                }),
+            header_policy: HeaderPolicyConfig {
+                allow: self.forward_headers.clone(),
+                deny: default_header_deny()
+                    .into_iter()
+                    .chain(self.block_headers.iter().cloned())
+                    .collect(),
+            },
+            body_logging: (!self.log_bodies_routes.is_empty()).then(|| BodyLoggingConfig {
+                routes: self.log_bodies_routes.clone(),
+                redact_fields: if self.log_bodies_redact_fields.is_empty() {
+                    default_body_redact_fields()
+                } else {
+                    self.log_bodies_redact_fields.clone()
+                },
+            }),
+            allow_worker_pinning: self.allow_worker_pinning,
+            expose_routing_headers: self.expose_routing_headers,
+            upstream_auth: match self.upstream_auth.as_deref() {
+                Some("passthrough") => Some(UpstreamAuthConfig::Passthrough),
+                Some("inject") => Some(UpstreamAuthConfig::Inject {
+                    key: self
+                        .api_key
+                        .clone()
+                        .ok_or_else(|| ConfigError::MissingRequired {
+                            field: "api_key".to_string(),
+                        })?,
+                }),
+                Some("none") => Some(UpstreamAuthConfig::None),
+                _ => None,
+            },
+            upstream_tls: match (
+                &self.upstream_tls_cert,
+                &self.upstream_tls_key,
+                &self.upstream_tls_ca,
+            ) {
+                (None, None, None) => None,
+                _ => Some(UpstreamTlsConfig {
+                    client_cert_path: self.upstream_tls_cert.clone(),
+                    client_key_path: self.upstream_tls_key.clone(),
+                    ca_cert_path: self.upstream_tls_ca.clone(),
+                }),
+            },
+            pd_zone_affinity: self.pd_zone_affinity,
+            min_healthy_workers: self.min_healthy_workers,
+            payload_limits: PayloadLimitsConfig {
+                generate: self.max_payload_size_generate,
+                chat: self.max_payload_size_chat,
+                completions: self.max_payload_size_completions,
+                embeddings: self.max_payload_size_embeddings,
+                rerank: self.max_payload_size_rerank,
+                responses: self.max_payload_size_responses,
+            },
+            max_prompt_chars: self.max_prompt_chars,
+            default_max_tokens: self.default_max_tokens,
+            max_tokens_limit: self.max_tokens_limit,
             disable_retries: self.disable_retries,
             disable_circuit_breaker: self.disable_circuit_breaker,
             health_check: HealthCheckConfig {
@@ -604,15 +1286,66 @@ impl CliArgs {
                 timeout_secs: self.health_check_timeout_secs,
                 check_interval_secs: self.health_check_interval_secs,
                 endpoint: self.health_check_endpoint.clone(),
+                method: match self.health_check_method.as_str() {
+                    "post" => HealthCheckMethod::Post,
+                    _ => HealthCheckMethod::Get,
+                },
+                expected_body: self.health_check_expected_body.clone(),
+                generation_probe: self.health_check_generation_probe,
             },
             enable_igw: self.enable_igw,
             rate_limit_tokens_per_second: None,
+            model_concurrency_limits: Self::parse_model_concurrency_limits(
+                &self.model_concurrency_limits,
+            )?,
+            adaptive_concurrency: self
+                .adaptive_concurrency
+                .then(|| AdaptiveConcurrencyConfig {
+                    initial_limit: self.adaptive_concurrency_initial_limit,
+                    min_limit: self.adaptive_concurrency_min_limit,
+                    ..Default::default()
+                }),
+            model_policies: self.parse_model_policies(&self.model_policies)?,
+            queue_fairness: match self.queue_fairness.as_str() {
+                "model" => QueueFairness::Model,
+                "client" => QueueFairness::Client,
+                "priority" => QueueFairness::Priority,
+                _ => QueueFairness::Fifo,
+            },
+            queue_weights: Self::parse_queue_weights(&self.queue_weights)?,
+            queue_priority_aging_secs: self.queue_priority_aging_secs,
+            discovery_stale_secs: self.vllm_discovery_stale_secs,
+            model_aliases: Self::parse_model_aliases(&self.model_aliases)?,
+            model_fallbacks: Self::parse_model_fallbacks(&self.model_fallbacks)?,
+            model_traffic_splits: Self::parse_model_traffic_splits(&self.model_traffic_splits)?,
+            advertise_model_aliases: self.advertise_model_aliases,
+            rewrite_stream_model: self.rewrite_stream_model,
+            strict_model_routing: self.strict_model_routing,
+            max_concurrent_streams: self.max_concurrent_streams,
+            stream_buffer_size: self.stream_buffer_size,
+            stream_keepalive_secs: self.stream_keepalive_secs,
+            shutdown_drain_timeout_secs: self.shutdown_drain_timeout_secs,
+            batch_max_concurrency: self.batch_max_concurrency,
+            enable_response_compression: self.enable_response_compression,
+            enable_upstream_compression: self.enable_upstream_compression,
             model_path: self.model_path.clone(),
             tokenizer_path: self.tokenizer_path.clone(),
             history_backend: match self.history_backend.as_str() {
                 "none" => HistoryBackend::None,
+                "redis" => HistoryBackend::Redis {
+                    url: self.history_redis_url.clone().ok_or_else(|| {
+                        ConfigError::MissingRequired {
+                            field: "history_redis_url".to_string(),
+                        }
+                    })?,
+                },
                 _ => HistoryBackend::Memory,
             },
+            memory_history: MemoryHistoryConfig {
+                max_responses: self.history_max_responses,
+                response_ttl_secs: self.history_response_ttl_secs,
+                eviction_interval_secs: self.history_eviction_interval_secs,
+            },
             enable_profiling: self.profile,
             profile_timeout_secs: 10, // Default profiling timeout
         })
@@ -650,6 +1383,7 @@ impl CliArgs {
             max_payload_size: self.max_payload_size,
             log_dir: self.log_dir.clone(),
             log_level: Some(self.log_level.clone()),
+            log_json: self.log_json,
             service_discovery_config,
             prometheus_config,
             request_timeout_secs: self.request_timeout_secs,
@@ -751,7 +1485,10 @@ Provide --worker-urls or PD flags as usual.",
 
     // Validate configuration
     println!("DEBUG: Validating configuration");
-    router_config.validate()?;
+    if let Err(e) = router_config.validate() {
+        eprintln!("Invalid configuration: {}", e);
+        std::process::exit(1);
+    }
     println!("DEBUG: Configuration validated successfully");
 
     // Create ServerConfig
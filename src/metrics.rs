@@ -32,6 +32,30 @@ pub fn init_metrics() {
         "vllm_router_request_errors_total",
         "Total number of request errors by route and error type"
     );
+    describe_counter!(
+        "vllm_router_model_requests_total",
+        "Total number of successful requests by route and model"
+    );
+    describe_histogram!(
+        "vllm_router_model_request_duration_seconds",
+        "Successful request duration in seconds by route and model"
+    );
+    describe_counter!(
+        "vllm_router_model_request_errors_total",
+        "Total number of request errors by route, model and error type"
+    );
+    describe_counter!(
+        "vllm_router_traffic_split_requests_total",
+        "Total number of requests routed to each pool of a model's traffic split"
+    );
+    describe_counter!(
+        "vllm_router_mirror_requests_total",
+        "Total number of requests mirrored to shadow workers by route and outcome (success/failure)"
+    );
+    describe_histogram!(
+        "vllm_router_mirror_duration_seconds",
+        "Latency of requests mirrored to shadow workers"
+    );
     describe_counter!(
         "vllm_router_retries_total",
         "Total number of request retries by route"
@@ -44,6 +68,38 @@ pub fn init_metrics() {
         "vllm_router_retries_exhausted_total",
         "Total number of requests that exhausted retries by route"
     );
+    describe_counter!(
+        "vllm_router_retry_budget_exhausted_total",
+        "Total number of retries refused because the retry budget was exhausted"
+    );
+    describe_counter!(
+        "vllm_router_hedged_requests_total",
+        "Total number of hedged requests by route and winning copy (primary or backup)"
+    );
+    describe_counter!(
+        "vllm_router_worker_rate_limited_total",
+        "Total number of times a selected worker was skipped for exceeding its rate limit"
+    );
+    describe_counter!(
+        "vllm_router_worker_responses_total",
+        "Total number of upstream responses by worker and status class (2xx, 4xx, 5xx)"
+    );
+    describe_counter!(
+        "vllm_router_response_cache_hits_total",
+        "Total number of requests answered from the response cache by route"
+    );
+    describe_counter!(
+        "vllm_router_response_cache_misses_total",
+        "Total number of cacheable requests not found in the response cache by route"
+    );
+    describe_counter!(
+        "vllm_router_idempotent_replays_total",
+        "Total number of retried requests answered with the response stored for their Idempotency-Key by route"
+    );
+    describe_gauge!(
+        "vllm_router_stored_responses",
+        "Number of responses held by the memory history backend"
+    );
 
     // Circuit breaker metrics
     describe_gauge!(
@@ -69,6 +125,22 @@ pub fn init_metrics() {
         "Worker health status (1=healthy, 0=unhealthy)"
     );
     describe_gauge!("vllm_router_worker_load", "Current load on each worker");
+    describe_gauge!(
+        "vllm_router_worker_latency_ewma_seconds",
+        "Exponentially weighted moving average of request latency per worker"
+    );
+    describe_gauge!(
+        "vllm_router_model_versions",
+        "Distinct versions served by the workers of each model (above 1 means version skew)"
+    );
+    describe_gauge!(
+        "vllm_router_worker_ejected",
+        "Whether a worker is ejected as an error rate outlier (1=ejected, 0=not ejected)"
+    );
+    describe_counter!(
+        "vllm_router_outlier_ejections_total",
+        "Total number of times each worker was ejected as an error rate outlier"
+    );
     describe_counter!(
         "vllm_router_processed_requests_total",
         "Total requests processed by each worker"
@@ -90,6 +162,14 @@ pub fn init_metrics() {
         "Total load balancing trigger events"
     );
     describe_gauge!("vllm_router_max_load", "Maximum worker load");
+    describe_counter!(
+        "vllm_router_hash_ring_rebuilds_total",
+        "Total consistent hash ring rebuilds caused by worker set changes"
+    );
+    describe_gauge!(
+        "vllm_router_hash_ring_remapped_fraction",
+        "Estimated fraction of session keys remapped by the last consistent hash ring rebuild"
+    );
     describe_gauge!("vllm_router_min_load", "Minimum worker load");
 
     // PD-specific metrics
@@ -139,12 +219,40 @@ pub fn init_metrics() {
         "vllm_router_discovery_workers_removed",
         "Number of workers removed in last discovery update"
     );
+    describe_gauge!(
+        "vllm_router_discovery_instances",
+        "Number of instances registered through ZMQ service discovery by service type"
+    );
+    describe_gauge!(
+        "vllm_router_discovery_last_update_timestamp_seconds",
+        "Unix time of the last ZMQ service discovery registration"
+    );
+    describe_counter!(
+        "vllm_router_discovery_reconnects_total",
+        "Total number of times the ZMQ service discovery listener was restarted after a socket error"
+    );
 
     // Generate request specific metrics
     describe_histogram!(
         "vllm_router_generate_duration_seconds",
         "Generate request duration"
     );
+    describe_histogram!(
+        "vllm_router_ttft_duration_seconds",
+        "Time to first token of streaming responses in seconds by route"
+    );
+    describe_counter!(
+        "vllm_router_client_disconnects_total",
+        "Streaming responses abandoned by the client before completion, by route"
+    );
+    describe_counter!(
+        "vllm_router_stream_truncated_total",
+        "Streaming responses whose upstream ended without a [DONE] marker, by route and worker"
+    );
+    describe_counter!(
+        "vllm_router_slow_requests_total",
+        "Upstream requests still without response headers after the slow request threshold, by route and worker"
+    );
 
     // Embedding request specific metrics
     describe_counter!("vllm_router_embeddings_total", "Total embedding requests");
@@ -157,6 +265,28 @@ pub fn init_metrics() {
         "Embedding request errors"
     );
     describe_gauge!("vllm_router_embeddings_queue_size", "Embedding queue size");
+    describe_gauge!(
+        "vllm_router_adaptive_concurrency_limit",
+        "Current global concurrency limit set by adaptive concurrency"
+    );
+    describe_counter!(
+        "vllm_router_adaptive_concurrency_rejections_total",
+        "Total number of requests rejected at the adaptive concurrency limit"
+    );
+
+    // Concurrency queue metrics
+    describe_gauge!(
+        "vllm_router_queue_depth",
+        "Requests waiting in the concurrency queue for admission"
+    );
+    describe_histogram!(
+        "vllm_router_queue_wait_duration_seconds",
+        "Time requests spent in the concurrency queue before admission"
+    );
+    describe_counter!(
+        "vllm_router_queue_timeouts_total",
+        "Requests that timed out in the concurrency queue"
+    );
 
     // Running requests gauge for cache-aware policy
     describe_gauge!(
@@ -278,6 +408,18 @@ pub fn start_prometheus(config: PrometheusConfig) {
         .expect("failed to install Prometheus metrics exporter");
 }
 
+/// Status class label of an upstream response, keeping label cardinality
+/// to a handful of values
+fn status_class(status: u16) -> &'static str {
+    match status / 100 {
+        1 => "1xx",
+        2 => "2xx",
+        3 => "3xx",
+        4 => "4xx",
+        _ => "5xx",
+    }
+}
+
 pub struct RouterMetrics;
 
 pub struct TokenizerMetrics;
@@ -306,6 +448,48 @@ impl RouterMetrics {
         .increment(1);
     }
 
+    pub fn record_model_request(route: &str, model: &str, duration: Duration) {
+        counter!("vllm_router_model_requests_total",
+            "route" => route.to_string(),
+            "model" => model.to_string()
+        )
+        .increment(1);
+        histogram!("vllm_router_model_request_duration_seconds",
+            "route" => route.to_string(),
+            "model" => model.to_string()
+        )
+        .record(duration.as_secs_f64());
+    }
+
+    pub fn record_model_request_error(route: &str, model: &str, error_type: &str) {
+        counter!("vllm_router_model_request_errors_total",
+            "route" => route.to_string(),
+            "model" => model.to_string(),
+            "error_type" => error_type.to_string()
+        )
+        .increment(1);
+    }
+
+    pub fn record_traffic_split(model: &str, pool: &str) {
+        counter!("vllm_router_traffic_split_requests_total",
+            "model" => model.to_string(),
+            "pool" => pool.to_string()
+        )
+        .increment(1);
+    }
+
+    pub fn record_mirror_request(route: &str, success: bool, duration: Duration) {
+        counter!("vllm_router_mirror_requests_total",
+            "route" => route.to_string(),
+            "outcome" => if success { "success" } else { "failure" }
+        )
+        .increment(1);
+        histogram!("vllm_router_mirror_duration_seconds",
+            "route" => route.to_string()
+        )
+        .record(duration.as_secs_f64());
+    }
+
     pub fn record_retry(route: &str) {
         counter!("vllm_router_retries_total",
             "route" => route.to_string()
@@ -327,6 +511,58 @@ impl RouterMetrics {
         .increment(1);
     }
 
+    pub fn record_retry_budget_exhausted() {
+        counter!("vllm_router_retry_budget_exhausted_total").increment(1);
+    }
+
+    pub fn record_hedged_request(route: &str, winner: &str) {
+        counter!("vllm_router_hedged_requests_total",
+            "route" => route.to_string(),
+            "winner" => winner.to_string()
+        )
+        .increment(1);
+    }
+
+    pub fn record_worker_rate_limited(worker: &str) {
+        counter!("vllm_router_worker_rate_limited_total",
+            "worker" => worker.to_string()
+        )
+        .increment(1);
+    }
+
+    pub fn record_worker_response(worker: &str, status: u16) {
+        counter!("vllm_router_worker_responses_total",
+            "worker" => worker.to_string(),
+            "status_class" => status_class(status)
+        )
+        .increment(1);
+    }
+
+    pub fn record_response_cache_hit(route: &str) {
+        counter!("vllm_router_response_cache_hits_total",
+            "route" => route.to_string()
+        )
+        .increment(1);
+    }
+
+    pub fn record_response_cache_miss(route: &str) {
+        counter!("vllm_router_response_cache_misses_total",
+            "route" => route.to_string()
+        )
+        .increment(1);
+    }
+
+    pub fn record_idempotent_replay(route: &str) {
+        counter!("vllm_router_idempotent_replays_total",
+            "route" => route.to_string()
+        )
+        .increment(1);
+    }
+
+    pub fn set_stored_responses(count: usize) {
+        gauge!("vllm_router_stored_responses").set(count as f64);
+    }
+
     // Worker metrics
     pub fn set_active_workers(count: usize) {
         gauge!("vllm_router_active_workers").set(count as f64);
@@ -346,6 +582,34 @@ impl RouterMetrics {
         .set(load as f64);
     }
 
+    pub fn set_worker_latency_ewma(worker_url: &str, latency: Duration) {
+        gauge!("vllm_router_worker_latency_ewma_seconds",
+            "worker" => worker_url.to_string()
+        )
+        .set(latency.as_secs_f64());
+    }
+
+    pub fn set_model_versions(model: &str, count: usize) {
+        gauge!("vllm_router_model_versions",
+            "model" => model.to_string()
+        )
+        .set(count as f64);
+    }
+
+    pub fn set_worker_ejected(worker_url: &str, ejected: bool) {
+        gauge!("vllm_router_worker_ejected",
+            "worker" => worker_url.to_string()
+        )
+        .set(if ejected { 1.0 } else { 0.0 });
+    }
+
+    pub fn record_outlier_ejection(worker_url: &str) {
+        counter!("vllm_router_outlier_ejections_total",
+            "worker" => worker_url.to_string()
+        )
+        .increment(1);
+    }
+
     pub fn record_processed_request(worker_url: &str) {
         counter!("vllm_router_processed_requests_total",
             "worker" => worker_url.to_string()
@@ -377,6 +641,11 @@ impl RouterMetrics {
         .set(size as f64);
     }
 
+    pub fn record_hash_ring_rebuild(remapped_fraction: f64) {
+        counter!("vllm_router_hash_ring_rebuilds_total").increment(1);
+        gauge!("vllm_router_hash_ring_remapped_fraction").set(remapped_fraction);
+    }
+
     pub fn record_load_balancing_event() {
         counter!("vllm_router_load_balancing_events_total").increment(1);
     }
@@ -450,12 +719,57 @@ impl RouterMetrics {
         gauge!("vllm_router_discovery_workers_removed").set(removed as f64);
     }
 
+    pub fn set_discovery_instances(service_type: &str, count: usize) {
+        gauge!("vllm_router_discovery_instances",
+            "service_type" => service_type.to_string()
+        )
+        .set(count as f64);
+    }
+
+    pub fn set_discovery_last_update(unix_secs: u64) {
+        gauge!("vllm_router_discovery_last_update_timestamp_seconds").set(unix_secs as f64);
+    }
+
+    pub fn record_discovery_reconnect() {
+        counter!("vllm_router_discovery_reconnects_total").increment(1);
+    }
+
     // Generate request metrics
     pub fn record_generate_duration(duration: Duration) {
         histogram!("vllm_router_generate_duration_seconds").record(duration.as_secs_f64());
     }
 
+    pub fn record_ttft(route: &str, duration: Duration) {
+        histogram!("vllm_router_ttft_duration_seconds",
+            "route" => route.to_string()
+        )
+        .record(duration.as_secs_f64());
+    }
+
+    pub fn record_stream_truncated(route: &str, worker: &str) {
+        counter!("vllm_router_stream_truncated_total",
+            "route" => route.to_string(),
+            "worker" => worker.to_string()
+        )
+        .increment(1);
+    }
+
+    pub fn record_slow_request(route: &str, worker: &str) {
+        counter!("vllm_router_slow_requests_total",
+            "route" => route.to_string(),
+            "worker" => worker.to_string()
+        )
+        .increment(1);
+    }
+
     // Embeddings metrics
+    pub fn record_client_disconnect(route: &str) {
+        counter!("vllm_router_client_disconnects_total",
+            "route" => route.to_string()
+        )
+        .increment(1);
+    }
+
     pub fn record_embeddings_request() {
         counter!("vllm_router_embeddings_total").increment(1);
     }
@@ -476,6 +790,27 @@ impl RouterMetrics {
         gauge!("vllm_router_embeddings_queue_size").set(size as f64);
     }
 
+    pub fn set_adaptive_concurrency_limit(limit: usize) {
+        gauge!("vllm_router_adaptive_concurrency_limit").set(limit as f64);
+    }
+
+    pub fn record_adaptive_concurrency_rejection() {
+        counter!("vllm_router_adaptive_concurrency_rejections_total").increment(1);
+    }
+
+    // Concurrency queue metrics
+    pub fn set_queue_depth(depth: usize) {
+        gauge!("vllm_router_queue_depth").set(depth as f64);
+    }
+
+    pub fn record_queue_wait(duration: Duration) {
+        histogram!("vllm_router_queue_wait_duration_seconds").record(duration.as_secs_f64());
+    }
+
+    pub fn record_queue_timeout() {
+        counter!("vllm_router_queue_timeouts_total").increment(1);
+    }
+
     // Running requests for cache-aware policy
     pub fn set_running_requests(worker: &str, count: usize) {
         gauge!("vllm_router_running_requests",
@@ -492,7 +827,7 @@ impl RouterMetrics {
         .set(state_code as f64);
     }
 
-    pub fn record_cb_state_transition(worker: &str, from: &str, to: &str) {
+    pub fn record_circuit_transition(worker: &str, from: &str, to: &str) {
         counter!("vllm_router_cb_state_transitions_total",
             "worker" => worker.to_string(),
             "from" => from.to_string(),
@@ -854,20 +1189,47 @@ mod tests {
 
     // ============= RouterMetrics Tests =============
 
+    #[test]
+    fn test_status_class() {
+        assert_eq!(status_class(200), "2xx");
+        assert_eq!(status_class(204), "2xx");
+        assert_eq!(status_class(307), "3xx");
+        assert_eq!(status_class(429), "4xx");
+        assert_eq!(status_class(503), "5xx");
+        assert_eq!(status_class(100), "1xx");
+    }
+
     #[test]
     fn test_metrics_static_methods() {
         // Test that all static methods can be called without panic
         RouterMetrics::record_request("/generate");
+        RouterMetrics::record_model_request("/generate", "llama", Duration::from_millis(100));
+        RouterMetrics::record_model_request_error("/generate", "llama", "timeout");
         RouterMetrics::record_request_duration("/generate", Duration::from_millis(100));
         RouterMetrics::record_request_error("/generate", "timeout");
         RouterMetrics::record_retry("/generate");
+        RouterMetrics::record_retry_budget_exhausted();
+        RouterMetrics::record_hedged_request("/generate", "backup");
+        RouterMetrics::record_response_cache_hit("/v1/completions");
+        RouterMetrics::record_response_cache_miss("/v1/completions");
+        RouterMetrics::record_idempotent_replay("/v1/responses");
+        RouterMetrics::record_traffic_split("llama", "llama-v2");
+        RouterMetrics::record_mirror_request("/v1/completions", true, Duration::from_millis(80));
+        RouterMetrics::set_stored_responses(42);
 
         RouterMetrics::set_active_workers(5);
         RouterMetrics::set_worker_health("http://worker1", true);
         RouterMetrics::set_worker_load("http://worker1", 10);
+        RouterMetrics::set_worker_latency_ewma("http://worker1", Duration::from_millis(250));
+        RouterMetrics::set_worker_ejected("http://worker1", true);
+        RouterMetrics::record_outlier_ejection("http://worker1");
         RouterMetrics::record_processed_request("http://worker1");
+        RouterMetrics::record_worker_response("http://worker1", 503);
+        RouterMetrics::set_adaptive_concurrency_limit(48);
+        RouterMetrics::record_adaptive_concurrency_rejection();
 
         RouterMetrics::record_policy_decision("random", "http://worker1");
+        RouterMetrics::record_hash_ring_rebuild(0.25);
         RouterMetrics::record_cache_hit();
         RouterMetrics::record_cache_miss();
         RouterMetrics::set_tree_size("http://worker1", 1000);
@@ -884,8 +1246,23 @@ mod tests {
         RouterMetrics::record_pd_stream_error("http://decode1");
 
         RouterMetrics::record_discovery_update(3, 1);
+        RouterMetrics::set_discovery_instances("prefill", 2);
+        RouterMetrics::set_discovery_last_update(1_700_000_000);
+        RouterMetrics::record_discovery_reconnect();
         RouterMetrics::record_generate_duration(Duration::from_secs(2));
+        RouterMetrics::record_ttft("/v1/chat/completions", Duration::from_millis(150));
+        RouterMetrics::record_stream_truncated("/v1/chat/completions", "http://worker1");
+        RouterMetrics::record_slow_request("/v1/chat/completions", "http://worker1");
+        RouterMetrics::record_client_disconnect("/v1/chat/completions");
         RouterMetrics::set_running_requests("http://worker1", 15);
+
+        RouterMetrics::set_queue_depth(3);
+        RouterMetrics::record_queue_wait(Duration::from_millis(40));
+        RouterMetrics::record_queue_timeout();
+
+        RouterMetrics::set_cb_state("http://worker1", 1);
+        RouterMetrics::record_circuit_transition("http://worker1", "closed", "open");
+        RouterMetrics::record_cb_outcome("http://worker1", "failure");
     }
 
     #[test]
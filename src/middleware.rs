@@ -1,9 +1,11 @@
 use axum::{
-    extract::Request, extract::State, http::HeaderValue, http::StatusCode, middleware::Next,
-    response::IntoResponse, response::Response,
+    extract::Request, extract::State, http::header::CONTENT_LENGTH, http::HeaderValue,
+    http::StatusCode, middleware::Next, response::Response,
 };
 use rand::Rng;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
@@ -14,7 +16,10 @@ use tracing::{debug, error, field::Empty, info, info_span, warn, Span};
 
 pub use crate::core::token_bucket::TokenBucket;
 
+use crate::config::QueueFairness;
+use crate::core::{AdaptivePermit, WorkerResponse};
 use crate::metrics::RouterMetrics;
+use crate::routers::error_response::error_response;
 use crate::server::AppState;
 
 /// Generate OpenAI-compatible request ID based on endpoint
@@ -294,12 +299,77 @@ pub fn log_request(entry: RequestLogEntry) {
 
 // ============ Concurrency Limiting with Queue Support ============
 
+/// Header naming the client for `QueueFairness::Client`
+const CLIENT_ID_HEADER: &str = "x-client-id";
+
+/// Header carrying a request's priority for `QueueFairness::Priority`
+const PRIORITY_HEADER: &str = "x-request-priority";
+
+/// Priority a request asks for with the X-Request-Priority header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RequestPriority {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl RequestPriority {
+    /// Parse `high`, `normal` or `low` (case-insensitive); anything else is normal
+    fn from_request(request: &Request<axum::body::Body>) -> Self {
+        let value = request
+            .headers()
+            .get(PRIORITY_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .trim();
+        if value.eq_ignore_ascii_case("high") {
+            Self::High
+        } else if value.eq_ignore_ascii_case("low") {
+            Self::Low
+        } else {
+            Self::Normal
+        }
+    }
+
+    /// Number of levels below high
+    fn levels_below_high(self) -> u32 {
+        match self {
+            Self::High => 0,
+            Self::Normal => 1,
+            Self::Low => 2,
+        }
+    }
+}
+
 /// Request queue entry
 pub struct QueuedRequest {
     /// Time when the request was queued
     queued_at: Instant,
     /// Channel to send the permit back when acquired
     permit_tx: oneshot::Sender<Result<(), StatusCode>>,
+    /// Model-specific token bucket (None = global bucket)
+    token_bucket: Option<Arc<TokenBucket>>,
+    /// Model or client ID the request is queued under when fair queuing is enabled
+    fairness_key: Option<String>,
+    /// Priority the request is queued at with priority queuing
+    priority: RequestPriority,
+}
+
+impl QueuedRequest {
+    /// Admit the request, recording how long it waited
+    ///
+    /// Returns false if the client went away while queued.
+    fn grant(self) -> bool {
+        RouterMetrics::record_queue_wait(self.queued_at.elapsed());
+        self.permit_tx.send(Ok(())).is_ok()
+    }
+
+    /// Fail the request with 408 after it waited out the queue timeout
+    fn time_out(self) {
+        RouterMetrics::record_queue_timeout();
+        let _ = self.permit_tx.send(Err(StatusCode::REQUEST_TIMEOUT));
+    }
 }
 
 /// Queue metrics for monitoring
@@ -311,11 +381,317 @@ pub struct QueueMetrics {
     pub total_rejected: std::sync::atomic::AtomicU64,
 }
 
+/// Per-key queues served in weighted round-robin order
+///
+/// The key at the front of `order` may dequeue up to its weight in requests
+/// before moving to the back. Keys are dropped from the rotation once their
+/// queue is empty and rejoin at the back when a request arrives.
+struct FairQueue {
+    queues: HashMap<String, VecDeque<QueuedRequest>>,
+    order: VecDeque<String>,
+    weights: HashMap<String, u32>,
+    /// Requests dequeued from the front key during its current turn
+    served: u32,
+    len: usize,
+}
+
+impl FairQueue {
+    fn new(weights: HashMap<String, u32>) -> Self {
+        Self {
+            queues: HashMap::new(),
+            order: VecDeque::new(),
+            weights,
+            served: 0,
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn push(&mut self, queued: QueuedRequest) {
+        let key = queued.fairness_key.clone().unwrap_or_default();
+        let queue = self.queues.entry(key.clone()).or_default();
+        if queue.is_empty() {
+            self.order.push_back(key);
+        }
+        queue.push_back(queued);
+        self.len += 1;
+    }
+
+    fn weight(&self, key: &str) -> u32 {
+        self.weights.get(key).copied().unwrap_or(1).max(1)
+    }
+
+    /// End the front key's turn, dropping it from the rotation if it has nothing queued
+    fn rotate(&mut self) {
+        self.served = 0;
+        if let Some(key) = self.order.pop_front() {
+            if self.queues.get(&key).is_some_and(|q| !q.is_empty()) {
+                self.order.push_back(key);
+            } else {
+                self.queues.remove(&key);
+            }
+        }
+    }
+
+    /// Grant a token to the first request, in round-robin order, whose bucket has one
+    ///
+    /// Keys whose bucket is exhausted lose their turn so that a model at its
+    /// own limit does not hold up the others. Returns false if nothing could be
+    /// dispatched.
+    async fn dispatch_next(&mut self, default_bucket: &Arc<TokenBucket>) -> bool {
+        for _ in 0..self.order.len() {
+            let Some(key) = self.order.front().cloned() else {
+                return false;
+            };
+            let Some(queue) = self.queues.get_mut(&key) else {
+                self.rotate();
+                continue;
+            };
+            let Some(head) = queue.front() else {
+                self.rotate();
+                continue;
+            };
+            let token_bucket = head
+                .token_bucket
+                .clone()
+                .unwrap_or_else(|| default_bucket.clone());
+
+            if token_bucket.try_acquire(1.0).await.is_err() {
+                self.rotate();
+                continue;
+            }
+
+            let queued = queue.pop_front().expect("queue head checked above");
+            self.len -= 1;
+            self.served += 1;
+            if queue.is_empty() || self.served >= self.weight(&key) {
+                self.rotate();
+            }
+
+            debug!("Queue: granted token to request queued under {:?}", key);
+            if !queued.grant() {
+                // The client went away while queued; give the token back
+                token_bucket.return_tokens(1.0).await;
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Fail requests that have waited longer than `timeout` and drop those whose client left
+    fn expire(&mut self, timeout: Duration) {
+        for queue in self.queues.values_mut() {
+            let before = queue.len();
+            let mut kept = VecDeque::with_capacity(before);
+            for queued in queue.drain(..) {
+                if queued.permit_tx.is_closed() {
+                    continue;
+                }
+                if queued.queued_at.elapsed() >= timeout {
+                    warn!("Queue: request timed out waiting for token");
+                    queued.time_out();
+                    continue;
+                }
+                kept.push_back(queued);
+            }
+            self.len -= before - kept.len();
+            *queue = kept;
+        }
+
+        self.queues.retain(|_, queue| !queue.is_empty());
+        let queues = &self.queues;
+        let front_removed = self
+            .order
+            .front()
+            .is_some_and(|key| !queues.contains_key(key));
+        self.order.retain(|key| queues.contains_key(key));
+        if front_removed {
+            self.served = 0;
+        }
+    }
+}
+
+/// Priority queue entry, ordered by when it becomes due
+struct PrioritizedRequest {
+    /// Arrival time pushed back by the aging interval for each level below high
+    due: Instant,
+    /// Arrival order, breaking ties between requests due at the same time
+    seq: u64,
+    queued: QueuedRequest,
+}
+
+impl Ord for PrioritizedRequest {
+    // Reversed so that the max-heap yields the earliest due request
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        other
+            .due
+            .cmp(&self.due)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for PrioritizedRequest {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PrioritizedRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == CmpOrdering::Equal
+    }
+}
+
+impl Eq for PrioritizedRequest {}
+
+/// Requests served highest priority first, with aging against starvation
+///
+/// Each level below high delays a request by `aging`, which is the same as
+/// raising its effective priority by one level for every `aging` it waits: a
+/// low priority request that has waited twice `aging` goes ahead of a high
+/// priority request that just arrived.
+struct PriorityQueue {
+    heap: BinaryHeap<PrioritizedRequest>,
+    aging: Duration,
+    next_seq: u64,
+}
+
+impl PriorityQueue {
+    fn new(aging: Duration) -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            aging,
+            next_seq: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    fn push(&mut self, queued: QueuedRequest) {
+        let due = queued.queued_at + self.aging * queued.priority.levels_below_high();
+        self.heap.push(PrioritizedRequest {
+            due,
+            seq: self.next_seq,
+            queued,
+        });
+        self.next_seq += 1;
+    }
+
+    /// Grant a token to the first request, in priority order, whose bucket has one
+    ///
+    /// Returns false if nothing could be dispatched.
+    async fn dispatch_next(&mut self, default_bucket: &Arc<TokenBucket>) -> bool {
+        let mut skipped = Vec::new();
+        let mut dispatched = false;
+        while let Some(entry) = self.heap.pop() {
+            let token_bucket = entry
+                .queued
+                .token_bucket
+                .clone()
+                .unwrap_or_else(|| default_bucket.clone());
+            if token_bucket.try_acquire(1.0).await.is_err() {
+                skipped.push(entry);
+                continue;
+            }
+
+            debug!(
+                "Queue: granted token to {:?} priority request",
+                entry.queued.priority
+            );
+            if !entry.queued.grant() {
+                // The client went away while queued; give the token back
+                token_bucket.return_tokens(1.0).await;
+            }
+            dispatched = true;
+            break;
+        }
+        self.heap.extend(skipped);
+        dispatched
+    }
+
+    /// Fail requests that have waited longer than `timeout` and drop those whose client left
+    fn expire(&mut self, timeout: Duration) {
+        let mut kept = Vec::with_capacity(self.heap.len());
+        for entry in self.heap.drain() {
+            if entry.queued.permit_tx.is_closed() {
+                continue;
+            }
+            if entry.queued.queued_at.elapsed() >= timeout {
+                warn!("Queue: request timed out waiting for token");
+                entry.queued.time_out();
+                continue;
+            }
+            kept.push(entry);
+        }
+        self.heap = BinaryHeap::from(kept);
+    }
+}
+
+/// Queue held by the processor for every ordering other than FIFO
+enum OrderedQueue {
+    Fair(FairQueue),
+    Priority(PriorityQueue),
+}
+
+impl OrderedQueue {
+    fn len(&self) -> usize {
+        match self {
+            Self::Fair(queue) => queue.len(),
+            Self::Priority(queue) => queue.len(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Fair(queue) => queue.is_empty(),
+            Self::Priority(queue) => queue.is_empty(),
+        }
+    }
+
+    fn push(&mut self, queued: QueuedRequest) {
+        match self {
+            Self::Fair(queue) => queue.push(queued),
+            Self::Priority(queue) => queue.push(queued),
+        }
+    }
+
+    async fn dispatch_next(&mut self, default_bucket: &Arc<TokenBucket>) -> bool {
+        match self {
+            Self::Fair(queue) => queue.dispatch_next(default_bucket).await,
+            Self::Priority(queue) => queue.dispatch_next(default_bucket).await,
+        }
+    }
+
+    fn expire(&mut self, timeout: Duration) {
+        match self {
+            Self::Fair(queue) => queue.expire(timeout),
+            Self::Priority(queue) => queue.expire(timeout),
+        }
+    }
+}
+
 /// Queue processor that handles queued requests
 pub struct QueueProcessor {
     token_bucket: Arc<TokenBucket>,
     queue_rx: mpsc::Receiver<QueuedRequest>,
     queue_timeout: Duration,
+    queue_size: usize,
+    fairness: QueueFairness,
+    queue_weights: HashMap<String, u32>,
+    priority_aging: Duration,
 }
 
 impl QueueProcessor {
@@ -328,32 +704,77 @@ impl QueueProcessor {
             token_bucket,
             queue_rx,
             queue_timeout,
+            queue_size: 0,
+            fairness: QueueFairness::Fifo,
+            queue_weights: HashMap::new(),
+            priority_aging: Duration::from_secs(10),
         }
     }
 
-    pub async fn run(mut self) {
+    /// Serve queued requests in weighted round-robin order by model or client,
+    /// or by request priority
+    ///
+    /// `queue_size` caps the number of requests held for ordering; extra
+    /// requests are rejected with 429 just as when the FIFO queue is full.
+    pub fn with_fairness(
+        mut self,
+        fairness: QueueFairness,
+        queue_weights: HashMap<String, u32>,
+        queue_size: usize,
+    ) -> Self {
+        self.fairness = fairness;
+        self.queue_weights = queue_weights;
+        self.queue_size = queue_size;
+        self
+    }
+
+    /// Wait that raises a queued request by one priority level
+    pub fn with_priority_aging(mut self, aging: Duration) -> Self {
+        self.priority_aging = aging;
+        self
+    }
+
+    pub async fn run(self) {
+        if self.fairness == QueueFairness::Fifo {
+            self.run_fifo().await;
+        } else {
+            self.run_fair().await;
+        }
+    }
+
+    async fn run_fifo(mut self) {
         info!("Starting concurrency queue processor");
 
+        // Requests received from the channel that are still waiting for a token
+        let waiting = Arc::new(AtomicUsize::new(0));
+
         // Process requests in a single task to reduce overhead
         while let Some(queued) = self.queue_rx.recv().await {
+            RouterMetrics::set_queue_depth(waiting.load(Ordering::Relaxed) + self.queue_rx.len());
+
             // Check timeout immediately
             let elapsed = queued.queued_at.elapsed();
             if elapsed >= self.queue_timeout {
                 warn!("Request already timed out in queue");
-                let _ = queued.permit_tx.send(Err(StatusCode::REQUEST_TIMEOUT));
+                queued.time_out();
                 continue;
             }
 
             let remaining_timeout = self.queue_timeout - elapsed;
+            let token_bucket = queued
+                .token_bucket
+                .clone()
+                .unwrap_or_else(|| self.token_bucket.clone());
 
             // Try to acquire token for this request
-            if self.token_bucket.try_acquire(1.0).await.is_ok() {
+            if token_bucket.try_acquire(1.0).await.is_ok() {
                 // Got token immediately
                 debug!("Queue: acquired token immediately for queued request");
-                let _ = queued.permit_tx.send(Ok(()));
+                queued.grant();
             } else {
                 // Need to wait for token
-                let token_bucket = self.token_bucket.clone();
+                let waiting = Arc::clone(&waiting);
+                RouterMetrics::set_queue_depth(waiting.fetch_add(1, Ordering::Relaxed) + 1);
 
                 // Spawn task only when we actually need to wait
                 tokio::spawn(async move {
@@ -363,17 +784,71 @@ impl QueueProcessor {
                         .is_ok()
                     {
                         debug!("Queue: acquired token after waiting");
-                        let _ = queued.permit_tx.send(Ok(()));
+                        queued.grant();
                     } else {
                         warn!("Queue: request timed out waiting for token");
-                        let _ = queued.permit_tx.send(Err(StatusCode::REQUEST_TIMEOUT));
+                        queued.time_out();
                     }
+                    RouterMetrics::set_queue_depth(waiting.fetch_sub(1, Ordering::Relaxed) - 1);
                 });
             }
         }
 
         warn!("Concurrency queue processor shutting down");
     }
+
+    async fn run_fair(mut self) {
+        info!(
+            "Starting concurrency queue processor with {:?} fairness",
+            self.fairness
+        );
+        let mut queue = match self.fairness {
+            QueueFairness::Priority => {
+                OrderedQueue::Priority(PriorityQueue::new(self.priority_aging))
+            }
+            _ => OrderedQueue::Fair(FairQueue::new(std::mem::take(&mut self.queue_weights))),
+        };
+
+        loop {
+            if queue.is_empty() {
+                match self.queue_rx.recv().await {
+                    Some(queued) => self.admit(&mut queue, queued),
+                    None => break,
+                }
+            }
+            while let Ok(queued) = self.queue_rx.try_recv() {
+                self.admit(&mut queue, queued);
+            }
+
+            queue.expire(self.queue_timeout);
+            while queue.dispatch_next(&self.token_bucket).await {}
+            RouterMetrics::set_queue_depth(queue.len());
+            if queue.is_empty() {
+                continue;
+            }
+
+            // Wait for tokens to be returned, picking up new arrivals meanwhile
+            tokio::select! {
+                queued = self.queue_rx.recv() => match queued {
+                    Some(queued) => self.admit(&mut queue, queued),
+                    None if queue.is_empty() => break,
+                    None => {}
+                },
+                _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+            }
+        }
+
+        warn!("Concurrency queue processor shutting down");
+    }
+
+    fn admit(&self, queue: &mut OrderedQueue, queued: QueuedRequest) {
+        if queue.len() >= self.queue_size {
+            warn!("Request queue is full, returning 429");
+            let _ = queued.permit_tx.send(Err(StatusCode::TOO_MANY_REQUESTS));
+        } else {
+            queue.push(queued);
+        }
+    }
 }
 
 /// State for the concurrency limiter
@@ -387,10 +862,15 @@ impl ConcurrencyLimiter {
         token_bucket: Arc<TokenBucket>,
         queue_size: usize,
         queue_timeout: Duration,
+        fairness: QueueFairness,
+        queue_weights: HashMap<String, u32>,
+        priority_aging: Duration,
     ) -> (Self, Option<QueueProcessor>) {
         if queue_size > 0 {
             let (queue_tx, queue_rx) = mpsc::channel(queue_size);
-            let processor = QueueProcessor::new(token_bucket, queue_rx, queue_timeout);
+            let processor = QueueProcessor::new(token_bucket, queue_rx, queue_timeout)
+                .with_fairness(fairness, queue_weights, queue_size)
+                .with_priority_aging(priority_aging);
 
             (
                 Self {
@@ -404,7 +884,196 @@ impl ConcurrencyLimiter {
     }
 }
 
+/// Only the `model` field is needed to pick a per-model limiter
+#[derive(serde::Deserialize)]
+struct ModelField {
+    #[serde(default)]
+    model: Option<String>,
+}
+
+/// Read the `model` field of a request body when a concurrency feature needs it.
+///
+/// The body has to be buffered to read the `model` field, so this is skipped
+/// entirely when no per-model limits are configured and the queue is not
+/// fair by model. The request is rebuilt from the buffered bytes so
+/// downstream extractors see the original body.
+async fn resolve_request_model(
+    app_state: &AppState,
+    request: Request<axum::body::Body>,
+) -> Result<(Request<axum::body::Body>, Option<String>), Response> {
+    let needs_model = !app_state.context.model_rate_limiters.is_empty()
+        || app_state.context.router_config.queue_fairness == QueueFairness::Model;
+    if !needs_model {
+        return Ok((request, None));
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return Err(error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Failed to read request body: {}", e),
+            ));
+        }
+    };
+
+    // Limits are configured per served model, so aliases count against their target
+    let model_id = serde_json::from_slice::<ModelField>(&bytes)
+        .ok()
+        .and_then(|field| field.model)
+        .map(|model| {
+            app_state
+                .context
+                .router_config
+                .model_aliases
+                .get(&model)
+                .cloned()
+                .unwrap_or(model)
+        });
+
+    Ok((
+        Request::from_parts(parts, axum::body::Body::from(bytes)),
+        model_id,
+    ))
+}
+
+/// Key a queued request is served under for the configured queue fairness
+fn queue_fairness_key(
+    fairness: QueueFairness,
+    request: &Request<axum::body::Body>,
+    model_id: Option<&str>,
+) -> Option<String> {
+    match fairness {
+        QueueFairness::Fifo | QueueFairness::Priority => None,
+        QueueFairness::Model => model_id.map(str::to_string),
+        QueueFairness::Client => request
+            .headers()
+            .get(CLIENT_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string),
+    }
+}
+
+/// Requests that have not finished yet, awaited during shutdown
+///
+/// Queued requests count as in flight, and a streaming response stays in
+/// flight until its body has been sent or dropped, not only until the
+/// handler returns.
+#[derive(Debug, Default)]
+pub struct InFlightRequests {
+    count: AtomicUsize,
+    idle: tokio::sync::Notify,
+}
+
+impl InFlightRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of requests currently in flight
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Count a request as in flight until the returned guard is dropped
+    pub fn track(self: &Arc<Self>) -> InFlightGuard {
+        self.count.fetch_add(1, Ordering::AcqRel);
+        InFlightGuard {
+            requests: Arc::clone(self),
+        }
+    }
+
+    /// Wait until no request is in flight
+    pub async fn wait_idle(&self) {
+        loop {
+            // Register before checking so a guard dropped in between still wakes us
+            let idle = self.idle.notified();
+            if self.count() == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+/// Keeps a request counted in [`InFlightRequests`] while alive
+#[derive(Debug)]
+pub struct InFlightGuard {
+    requests: Arc<InFlightRequests>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.requests.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.requests.idle.notify_waiters();
+        }
+    }
+}
+
+/// Count a request as in flight until its response body is finished or dropped
+///
+/// Installed outside the concurrency limiter so queued requests are counted too.
+/// Bodies of known size are already buffered and are returned unwrapped.
+pub async fn in_flight_middleware(
+    State(app_state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    use axum::body::{Body, HttpBody};
+    use futures_util::StreamExt;
+
+    let guard = app_state.context.in_flight.track();
+    let response = next.run(request).await;
+    if response.body().size_hint().exact().is_some() {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = body.into_data_stream().map(move |chunk| {
+        let _in_flight = &guard;
+        chunk
+    });
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+/// Reject request bodies larger than `limit` bytes with a 413
+///
+/// A declared Content-Length is checked before the handler runs so the
+/// client learns which limit applies to the route. Chunked bodies are cut off
+/// by the `RequestBodyLimitLayer` installed below this middleware.
+pub async fn body_limit_middleware(limit: usize, request: Request, next: Next) -> Response {
+    let declared = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+
+    if let Some(length) = declared {
+        if length > limit as u64 {
+            let path = request.uri().path();
+            warn!(
+                "Rejecting {} byte request body for {} (limit {} bytes)",
+                length, path, limit
+            );
+            return error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!(
+                    "Request body of {} bytes exceeds the {} byte limit for {}",
+                    length, limit, path
+                ),
+            );
+        }
+    }
+
+    next.run(request).await
+}
+
 /// Middleware function for concurrency limiting with optional queuing
+///
+/// Requests for a model listed in `model_concurrency_limits` are limited by
+/// that model's own token bucket; all other requests share the global bucket.
+/// Queued requests are served in the order chosen by `queue_fairness`.
 pub async fn concurrency_limit_middleware(
     State(app_state): State<Arc<AppState>>,
     request: Request<axum::body::Body>,
@@ -415,11 +1084,69 @@ pub async fn concurrency_limit_middleware(
 
     // Identify if this is an embeddings request based on path
     let is_embeddings = request.uri().path().contains("/v1/embeddings");
-    let token_bucket = app_state.context.rate_limiter.clone();
+    let (request, model_id) = match resolve_request_model(&app_state, request).await {
+        Ok(resolved) => resolved,
+        Err(response) => return response,
+    };
+    let model_token_bucket = model_id.as_deref().and_then(|model_id| {
+        let bucket = app_state.context.model_rate_limiters.get(model_id).cloned();
+        if bucket.is_some() {
+            debug!("Applying concurrency limit for model {}", model_id);
+        }
+        bucket
+    });
+    let token_bucket = model_token_bucket
+        .clone()
+        .unwrap_or_else(|| app_state.context.rate_limiter.clone());
+
+    // Requests sharing the global limit pass the adaptive limit before its token
+    // bucket, waiting for a slot like the bucket's own queue when queueing is on
+    let mut adaptive_permit = match (&model_token_bucket, &app_state.context.adaptive_limiter) {
+        (None, Some(limiter)) => {
+            let config = &app_state.context.router_config;
+            let permit = match limiter.try_acquire() {
+                Some(permit) => Ok(permit),
+                None if limiter.waiting() < config.queue_size => {
+                    debug!("Adaptive concurrency limit reached, waiting for a slot");
+                    limiter
+                        .acquire(Duration::from_secs(config.queue_timeout_secs))
+                        .await
+                        .ok_or_else(|| {
+                            RouterMetrics::record_queue_timeout();
+                            error_response(
+                                StatusCode::REQUEST_TIMEOUT,
+                                "Timed out waiting for a concurrency slot",
+                            )
+                        })
+                }
+                None => {
+                    warn!(
+                        "Adaptive concurrency limit {} reached, returning 429",
+                        limiter.limit()
+                    );
+                    Err(error_response(
+                        StatusCode::TOO_MANY_REQUESTS,
+                        "Too many concurrent requests",
+                    ))
+                }
+            };
+            match permit {
+                Ok(permit) => Some(permit),
+                Err(response) => {
+                    RouterMetrics::record_adaptive_concurrency_rejection();
+                    return response;
+                }
+            }
+        }
+        _ => None,
+    };
 
     // Try to acquire token immediately
-    if token_bucket.try_acquire(1.0).await.is_ok() {
+    let response = if token_bucket.try_acquire(1.0).await.is_ok() {
         debug!("Acquired token immediately");
+        if let Some(permit) = adaptive_permit.as_mut() {
+            permit.start_clock();
+        }
         let response = next.run(request).await;
 
         // Return the token to the bucket
@@ -437,6 +1164,13 @@ pub async fn concurrency_limit_middleware(
             let queued = QueuedRequest {
                 queued_at: Instant::now(),
                 permit_tx,
+                token_bucket: model_token_bucket,
+                fairness_key: queue_fairness_key(
+                    app_state.context.router_config.queue_fairness,
+                    &request,
+                    model_id.as_deref(),
+                ),
+                priority: RequestPriority::from_request(&request),
             };
 
             // Try to send to queue
@@ -458,6 +1192,9 @@ pub async fn concurrency_limit_middleware(
                                     EMBEDDINGS_QUEUE_SIZE.fetch_sub(1, Ordering::Relaxed) - 1;
                                 RouterMetrics::set_embeddings_queue_size(new_val as usize);
                             }
+                            if let Some(permit) = adaptive_permit.as_mut() {
+                                permit.start_clock();
+                            }
 
                             let response = next.run(request).await;
 
@@ -474,7 +1211,10 @@ pub async fn concurrency_limit_middleware(
                                     EMBEDDINGS_QUEUE_SIZE.fetch_sub(1, Ordering::Relaxed) - 1;
                                 RouterMetrics::set_embeddings_queue_size(new_val as usize);
                             }
-                            status.into_response()
+                            error_response(
+                                status,
+                                status.canonical_reason().unwrap_or("Request rejected"),
+                            )
                         }
                         Err(_) => {
                             error!("Queue response channel closed");
@@ -484,18 +1224,364 @@ pub async fn concurrency_limit_middleware(
                                     EMBEDDINGS_QUEUE_SIZE.fetch_sub(1, Ordering::Relaxed) - 1;
                                 RouterMetrics::set_embeddings_queue_size(new_val as usize);
                             }
-                            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                            error_response(
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                "Request queue closed unexpectedly",
+                            )
                         }
                     }
                 }
                 Err(_) => {
                     warn!("Request queue is full, returning 429");
-                    StatusCode::TOO_MANY_REQUESTS.into_response()
+                    error_response(StatusCode::TOO_MANY_REQUESTS, "Request queue is full")
                 }
             }
         } else {
             warn!("No tokens available and queuing is disabled, returning 429");
-            StatusCode::TOO_MANY_REQUESTS.into_response()
+            error_response(
+                StatusCode::TOO_MANY_REQUESTS,
+                "Too many concurrent requests",
+            )
+        }
+    };
+
+    match adaptive_permit {
+        Some(permit) => complete_adaptive_permit(response, permit),
+        None => response,
+    }
+}
+
+/// Release an adaptive concurrency slot once the response body is finished
+///
+/// Only responses a worker produced adjust the limit: successes give a
+/// latency sample and 503/504 mark overload. Everything else (client errors,
+/// cache hits, errors generated by the router) frees the slot unsampled.
+/// The sample is the time to the response headers, or to the first chunk for
+/// a successful stream, so long generations do not read as queueing; the
+/// slot is still held until the body ends.
+fn complete_adaptive_permit(response: Response, permit: AdaptivePermit) -> Response {
+    use axum::body::{Body, HttpBody};
+    use futures_util::{stream, StreamExt};
+    use std::task::{ready, Poll};
+
+    let status = response.status();
+    let overloaded = matches!(
+        status,
+        StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    );
+    let sampled = response.extensions().get::<WorkerResponse>().is_some()
+        && (status.is_success() || overloaded);
+    let streaming = response.body().size_hint().exact().is_none();
+    if sampled && !(streaming && status.is_success()) {
+        permit.record_latency(overloaded);
+    }
+    if !streaming {
+        return response;
+    }
+
+    // A body dropped before its end (client gone) frees the slot when dropped
+    let mut permit = Some(permit);
+    let mut awaiting_first_chunk = sampled && status.is_success();
+    let (parts, body) = response.into_parts();
+    let mut data = body.into_data_stream();
+    let body = stream::poll_fn(move |cx| {
+        let item = ready!(data.poll_next_unpin(cx));
+        match &item {
+            Some(Ok(_)) if awaiting_first_chunk => {
+                awaiting_first_chunk = false;
+                if let Some(permit) = &permit {
+                    permit.record_latency(false);
+                }
+            }
+            None => permit = None,
+            _ => {}
         }
+        Poll::Ready(item)
+    });
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_flight_requests_wait_idle() {
+        let requests = Arc::new(InFlightRequests::new());
+        requests.wait_idle().await;
+
+        let first = requests.track();
+        let second = requests.track();
+        assert_eq!(requests.count(), 2);
+
+        let waiter = tokio::spawn({
+            let requests = requests.clone();
+            async move { requests.wait_idle().await }
+        });
+        drop(first);
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished(), "one request is still in flight");
+
+        drop(second);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("idle once every guard is dropped")
+            .unwrap();
+        assert_eq!(requests.count(), 0);
+    }
+
+    fn queued(
+        key: &str,
+        token_bucket: Option<Arc<TokenBucket>>,
+    ) -> (QueuedRequest, oneshot::Receiver<Result<(), StatusCode>>) {
+        let (permit_tx, permit_rx) = oneshot::channel();
+        let queued = QueuedRequest {
+            queued_at: Instant::now(),
+            permit_tx,
+            token_bucket,
+            fairness_key: Some(key.to_string()),
+            priority: RequestPriority::Normal,
+        };
+        (queued, permit_rx)
+    }
+
+    /// Dispatch everything and return the keys in the order they were granted
+    async fn drain_order(
+        queue: &mut FairQueue,
+        mut pending: Vec<(&'static str, oneshot::Receiver<Result<(), StatusCode>>)>,
+    ) -> Vec<&'static str> {
+        let bucket = Arc::new(TokenBucket::new(100, 100));
+        let mut order = Vec::new();
+        while queue.dispatch_next(&bucket).await {
+            let granted = pending
+                .iter_mut()
+                .position(|(_, rx)| matches!(rx.try_recv(), Ok(Ok(()))))
+                .expect("one request granted per dispatch");
+            order.push(pending.remove(granted).0);
+        }
+        order
+    }
+
+    #[tokio::test]
+    async fn test_fair_queue_round_robin_across_keys() {
+        let mut queue = FairQueue::new(HashMap::new());
+        let mut pending = Vec::new();
+        // A burst from model-a arrives before a single model-b request
+        for key in ["model-a", "model-a", "model-a", "model-b"] {
+            let (req, rx) = queued(key, None);
+            queue.push(req);
+            pending.push((key, rx));
+        }
+
+        let order = drain_order(&mut queue, pending).await;
+        assert_eq!(order, vec!["model-a", "model-b", "model-a", "model-a"]);
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fair_queue_honors_weights() {
+        let weights = HashMap::from([("interactive".to_string(), 2)]);
+        let mut queue = FairQueue::new(weights);
+        let mut pending = Vec::new();
+        for key in [
+            "batch",
+            "batch",
+            "interactive",
+            "interactive",
+            "interactive",
+        ] {
+            let (req, rx) = queued(key, None);
+            queue.push(req);
+            pending.push((key, rx));
+        }
+
+        let order = drain_order(&mut queue, pending).await;
+        assert_eq!(
+            order,
+            vec![
+                "batch",
+                "interactive",
+                "interactive",
+                "batch",
+                "interactive"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fair_queue_skips_key_with_exhausted_bucket() {
+        let exhausted = Arc::new(TokenBucket::new(1, 1));
+        exhausted.try_acquire(1.0).await.unwrap();
+
+        let mut queue = FairQueue::new(HashMap::new());
+        let (limited, mut limited_rx) = queued("limited", Some(exhausted));
+        let (open, mut open_rx) = queued("open", None);
+        queue.push(limited);
+        queue.push(open);
+
+        let bucket = Arc::new(TokenBucket::new(10, 10));
+        assert!(queue.dispatch_next(&bucket).await);
+        assert!(matches!(open_rx.try_recv(), Ok(Ok(()))));
+        assert!(limited_rx.try_recv().is_err());
+        assert_eq!(queue.len(), 1);
+    }
+
+    fn prioritized(
+        priority: RequestPriority,
+        waited: Duration,
+    ) -> (QueuedRequest, oneshot::Receiver<Result<(), StatusCode>>) {
+        let (mut queued, rx) = queued("", None);
+        queued.priority = priority;
+        queued.queued_at -= waited;
+        (queued, rx)
+    }
+
+    /// Dispatch everything and return the labels in the order they were granted
+    async fn drain_priority_order(
+        queue: &mut PriorityQueue,
+        mut pending: Vec<(&'static str, oneshot::Receiver<Result<(), StatusCode>>)>,
+    ) -> Vec<&'static str> {
+        let bucket = Arc::new(TokenBucket::new(100, 100));
+        let mut order = Vec::new();
+        while queue.dispatch_next(&bucket).await {
+            let granted = pending
+                .iter_mut()
+                .position(|(_, rx)| matches!(rx.try_recv(), Ok(Ok(()))))
+                .expect("one request granted per dispatch");
+            order.push(pending.remove(granted).0);
+        }
+        order
+    }
+
+    #[tokio::test]
+    async fn test_priority_queue_serves_higher_priority_first() {
+        let mut queue = PriorityQueue::new(Duration::from_secs(10));
+        let mut pending = Vec::new();
+        for (label, priority) in [
+            ("low", RequestPriority::Low),
+            ("normal-1", RequestPriority::Normal),
+            ("high", RequestPriority::High),
+            ("normal-2", RequestPriority::Normal),
+        ] {
+            let (req, rx) = prioritized(priority, Duration::ZERO);
+            queue.push(req);
+            pending.push((label, rx));
+        }
+
+        let order = drain_priority_order(&mut queue, pending).await;
+        assert_eq!(order, vec!["high", "normal-1", "normal-2", "low"]);
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_priority_queue_ages_waiting_requests() {
+        let mut queue = PriorityQueue::new(Duration::from_secs(10));
+        let mut pending = Vec::new();
+        // Waiting 25s lifts a low request past two levels, ahead of a fresh high one
+        for (label, priority, waited) in [
+            ("high", RequestPriority::High, 0),
+            ("aged-low", RequestPriority::Low, 25),
+            ("normal", RequestPriority::Normal, 5),
+        ] {
+            let (req, rx) = prioritized(priority, Duration::from_secs(waited));
+            queue.push(req);
+            pending.push((label, rx));
+        }
+
+        let order = drain_priority_order(&mut queue, pending).await;
+        // A normal request 5s in is still half a level below the fresh high one
+        assert_eq!(order, vec!["aged-low", "high", "normal"]);
+    }
+
+    #[test]
+    fn test_request_priority_from_header() {
+        let request = |value: Option<&str>| {
+            let mut builder = Request::builder();
+            if let Some(value) = value {
+                builder = builder.header(PRIORITY_HEADER, value);
+            }
+            builder.body(axum::body::Body::empty()).unwrap()
+        };
+        assert_eq!(
+            RequestPriority::from_request(&request(Some("HIGH"))),
+            RequestPriority::High
+        );
+        assert_eq!(
+            RequestPriority::from_request(&request(Some("low"))),
+            RequestPriority::Low
+        );
+        assert_eq!(
+            RequestPriority::from_request(&request(Some("urgent"))),
+            RequestPriority::Normal
+        );
+        assert_eq!(
+            RequestPriority::from_request(&request(None)),
+            RequestPriority::Normal
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fair_queue_expires_waiting_requests() {
+        let mut queue = FairQueue::new(HashMap::new());
+        let (stale, mut stale_rx) = queued("model-a", None);
+        let (gone, gone_rx) = queued("model-b", None);
+        queue.push(stale);
+        queue.push(gone);
+        drop(gone_rx);
+
+        queue.expire(Duration::ZERO);
+        assert!(queue.is_empty());
+        assert!(matches!(
+            stale_rx.try_recv(),
+            Ok(Err(StatusCode::REQUEST_TIMEOUT))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_latency_excludes_waiting_and_generation_time() {
+        use crate::config::AdaptiveConcurrencyConfig;
+        use crate::core::AdaptiveConcurrencyLimiter;
+        use axum::body::{to_bytes, Body};
+
+        let limiter = Arc::new(AdaptiveConcurrencyLimiter::new(
+            &AdaptiveConcurrencyConfig {
+                initial_limit: 4,
+                min_limit: 2,
+                smoothing: 1.0,
+                min_latency_window_secs: 60,
+            },
+            100,
+        ));
+        let worker_response = |body: Body| {
+            let mut response = Response::new(body);
+            response.extensions_mut().insert(WorkerResponse);
+            response
+        };
+        let mut permits: Vec<_> = (0..4).map(|_| limiter.try_acquire().unwrap()).collect();
+
+        // Time spent waiting for the token bucket is not latency
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let mut short = permits.pop().unwrap();
+        short.start_clock();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let response = complete_adaptive_permit(worker_response(Body::from("short")), short);
+        to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(limiter.limit(), 6, "sampled near the unloaded latency");
+
+        // A long stream is sampled at its first chunk, not at its end
+        let mut long = permits.pop().unwrap();
+        long.start_clock();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let first =
+            futures_util::stream::once(async { Ok::<_, std::convert::Infallible>("data: 1\n\n") });
+        let rest = futures_util::stream::once(async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            Ok("data: 2\n\n")
+        });
+        let chunks = futures_util::StreamExt::chain(first, rest);
+        let response = complete_adaptive_permit(worker_response(Body::from_stream(chunks)), long);
+        to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(limiter.limit() >= 6, "limit: {}", limiter.limit());
+        assert_eq!(limiter.in_flight(), 2);
     }
 }
@@ -20,14 +20,17 @@
     -------------------------------------------
     This strategy maintains an approximate radix tree for each worker based on request history,
     eliminating the need for direct cache state queries. The tree stores raw text characters
-    instead of token IDs to avoid tokenization overhead.
+    instead of token IDs to avoid tokenization overhead. When the caller already has token IDs
+    (e.g., gRPC mode with a tokenizer), they are matched in a separate per-model tree so that
+    prefixes follow the model's token boundaries.
 
     Process:
     a. For each request, find the worker with the highest prefix match
     b. If match rate > cache_threshold:
     Route to the worker with highest match (likely has relevant data cached)
-    c. If match rate ≤ cache_threshold:
-    Route to the worker with smallest tree size (most available cache capacity)
+    c. If match rate ≤ cache_threshold (or the model has no tree yet):
+    Route by cold_start_policy, so cold prompts spread across workers instead
+    of clustering on whichever worker the tree happens to return
     d. Background maintenance:
     Periodically evict least recently used leaf nodes to prevent memory overflow
 
@@ -57,16 +60,22 @@
     5. max_tree_size: (integer)
     Maximum nodes per tree. When exceeded, LRU leaf nodes are evicted
     during the next eviction cycle.
+
+    6. cold_start_policy: (round_robin | least_load)
+    Worker selection for requests below cache_threshold. round_robin rotates
+    through the healthy workers; least_load picks the least loaded worker,
+    rotating between workers with equal load.
 */
 
 use super::{get_healthy_worker_indices, CacheAwareConfig, LoadBalancingPolicy, RequestHeaders};
+use crate::config::ColdStartPolicy;
 use crate::core::Worker;
 use crate::metrics::RouterMetrics;
 use crate::policies::normalize_model_key;
 use crate::tree::Tree;
 use dashmap::DashMap;
-use rand::Rng;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
@@ -81,6 +90,8 @@ use tracing::{debug, info};
 pub struct CacheAwarePolicy {
     config: CacheAwareConfig,
     trees: Arc<DashMap<String, Arc<Tree>>>, // model_id -> Arc<Tree>
+    token_trees: Arc<DashMap<String, Arc<Tree>>>, // model_id -> Arc<Tree> keyed by token ids
+    cold_start_counter: AtomicUsize,
     eviction_handle: Option<thread::JoinHandle<()>>,
 }
 
@@ -91,10 +102,12 @@ impl CacheAwarePolicy {
 
     pub fn with_config(config: CacheAwareConfig) -> Self {
         let trees = Arc::new(DashMap::<String, Arc<Tree>>::new());
+        let token_trees = Arc::new(DashMap::<String, Arc<Tree>>::new());
 
         // Start background eviction thread if configured
         let eviction_handle = if config.eviction_interval_secs > 0 {
             let trees_clone = Arc::clone(&trees);
+            let token_trees_clone = Arc::clone(&token_trees);
             let max_tree_size = config.max_tree_size;
             let interval = config.eviction_interval_secs;
 
@@ -102,7 +115,7 @@ impl CacheAwarePolicy {
                 thread::sleep(Duration::from_secs(interval));
 
                 // Evict for all model trees
-                for entry in trees_clone.iter() {
+                for entry in trees_clone.iter().chain(token_trees_clone.iter()) {
                     let model_id = entry.key();
                     let tree = entry.value();
                     tree.evict_tenant_by_size(max_tree_size);
@@ -119,48 +132,70 @@ impl CacheAwarePolicy {
         Self {
             config,
             trees,
+            token_trees,
+            cold_start_counter: AtomicUsize::new(0),
             eviction_handle,
         }
     }
 
+    /// Text-keyed and token-keyed tree maps; workers are registered in both
+    fn tree_maps(&self) -> [&DashMap<String, Arc<Tree>>; 2] {
+        [&self.trees, &self.token_trees]
+    }
+
     /// Add a single worker to the tree (incremental update)
     pub fn add_worker(&self, worker: &dyn Worker) {
         let tree_key = normalize_model_key(worker.model_id());
-        let tree = self
-            .trees
-            .entry(tree_key.to_string())
-            .or_insert_with(|| Arc::new(Tree::new()));
-        tree.insert("", worker.url());
+        self.add_worker_by_url(worker.url(), tree_key);
     }
 
     /// Add a worker by URL and model (for backward compatibility)
     pub fn add_worker_by_url(&self, url: &str, model_id: &str) {
-        let tree = self
-            .trees
-            .entry(model_id.to_string())
-            .or_insert_with(|| Arc::new(Tree::new()));
-        tree.insert("", url);
+        for trees in self.tree_maps() {
+            let tree = trees
+                .entry(model_id.to_string())
+                .or_insert_with(|| Arc::new(Tree::new()));
+            tree.insert("", url);
+        }
     }
 
     /// Remove a worker from the tree
     pub fn remove_worker(&self, worker: &dyn Worker) {
         let tree_key = normalize_model_key(worker.model_id());
-        if let Some(tree) = self.trees.get(tree_key) {
-            tree.remove_tenant(worker.url());
+        for trees in self.tree_maps() {
+            if let Some(tree) = trees.get(tree_key) {
+                tree.remove_tenant(worker.url());
+            }
         }
     }
 
     /// Remove a worker by URL (removes from all model trees for backward compatibility)
     pub fn remove_worker_by_url(&self, url: &str) {
         // Remove from all trees since we don't know which model it belongs to
-        for tree_ref in self.trees.iter() {
+        for tree_ref in self.trees.iter().chain(self.token_trees.iter()) {
             tree_ref.value().remove_tenant(url);
         }
     }
 
+    /// Forget every cached prefix while keeping the registered workers
+    ///
+    /// Used after the workers' KV caches are flushed, so requests stop being
+    /// steered toward prefixes the workers no longer hold.
+    pub fn clear_tree(&self) {
+        for trees in self.tree_maps() {
+            for mut entry in trees.iter_mut() {
+                let tree = Tree::new();
+                for tenant in entry.value().get_tenant_char_count().keys() {
+                    tree.insert("", tenant);
+                }
+                *entry.value_mut() = Arc::new(tree);
+            }
+        }
+    }
+
     /// Run cache eviction to prevent unbounded growth
     pub fn evict_cache(&self, max_size: usize) {
-        for tree_ref in self.trees.iter() {
+        for tree_ref in self.trees.iter().chain(self.token_trees.iter()) {
             let model_id = tree_ref.key();
             let tree = tree_ref.value();
             tree.evict_tenant_by_size(max_size);
@@ -171,10 +206,12 @@ impl CacheAwarePolicy {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn select_worker_min_load(
         &self,
         workers: &[Arc<dyn Worker>],
         request_text: Option<&str>,
+        trees: &DashMap<String, Arc<Tree>>,
         healthy_indices: &[usize],
         model_id: &str,
         max_load: usize,
@@ -203,7 +240,7 @@ impl CacheAwarePolicy {
         if let Some(text) = request_text {
             // Get the tree reference without locking the entire HashMap
             // DashMap only locks the specific shard containing this key
-            let tree = self.trees.get(model_id).map(|entry| entry.value().clone());
+            let tree = trees.get(model_id).map(|entry| entry.value().clone());
 
             if let Some(tree) = tree {
                 let worker_url = workers[min_load_idx].url();
@@ -223,14 +260,28 @@ impl CacheAwarePolicy {
 
         Some(min_load_idx)
     }
-}
 
-impl LoadBalancingPolicy for CacheAwarePolicy {
-    fn select_worker_with_headers(
+    /// Pick a worker for a request with no useful prefix match
+    fn select_cold_start(&self, workers: &[Arc<dyn Worker>], healthy_indices: &[usize]) -> usize {
+        let offset =
+            self.cold_start_counter.fetch_add(1, Ordering::Relaxed) % healthy_indices.len();
+        match self.config.cold_start_policy {
+            ColdStartPolicy::RoundRobin => healthy_indices[offset],
+            ColdStartPolicy::LeastLoad => healthy_indices[offset..]
+                .iter()
+                .chain(&healthy_indices[..offset])
+                .min_by_key(|&&idx| workers[idx].load())
+                .copied()
+                .unwrap_or(healthy_indices[offset]),
+        }
+    }
+
+    /// Select a worker by prefix-matching `request_text` against the given tree map
+    fn select_worker_from_trees(
         &self,
         workers: &[Arc<dyn Worker>],
         request_text: Option<&str>,
-        _headers: Option<&RequestHeaders>,
+        trees: &DashMap<String, Arc<Tree>>,
     ) -> Option<usize> {
         let healthy_indices = get_healthy_worker_indices(workers);
 
@@ -262,6 +313,7 @@ impl LoadBalancingPolicy for CacheAwarePolicy {
             return self.select_worker_min_load(
                 workers,
                 request_text,
+                trees,
                 &healthy_indices,
                 model_id,
                 max_load,
@@ -274,21 +326,17 @@ impl LoadBalancingPolicy for CacheAwarePolicy {
 
         // Get the tree reference without locking the entire HashMap
         // DashMap only locks the specific shard containing this key
-        let tree = self.trees.get(model_id).map(|entry| entry.value().clone());
+        let tree = trees.get(model_id).map(|entry| entry.value().clone());
 
-        let keys: Vec<_> = self.trees.iter().map(|entry| entry.key().clone()).collect();
+        let keys: Vec<_> = trees.iter().map(|entry| entry.key().clone()).collect();
         debug!("Available tree keys: {:?}", keys);
 
         let Some(tree) = tree else {
-            // No tree for this model, log warning and use random selection
             debug!(
-                "Warning: No tree found for model '{}', using random worker selection",
+                "No tree found for model '{}', using cold start selection",
                 model_id
             );
-            // Return a random healthy worker
-            let mut rng = rand::rng();
-            let random_idx = rng.random_range(0..healthy_indices.len());
-            let selected_idx = healthy_indices[random_idx];
+            let selected_idx = self.select_cold_start(workers, &healthy_indices);
 
             workers[selected_idx].increment_processed();
             RouterMetrics::record_processed_request(workers[selected_idx].url());
@@ -319,11 +367,7 @@ impl LoadBalancingPolicy for CacheAwarePolicy {
                 .position(|w| w.url() == tenant_url)
                 .filter(|&idx| workers[idx].is_healthy())
         } else {
-            // Low cache match: use worker with minimum load
-            healthy_indices
-                .iter()
-                .min_by_key(|&&idx| workers[idx].load())
-                .copied()
+            Some(self.select_cold_start(workers, &healthy_indices))
         };
 
         if let Some(idx) = selected_idx {
@@ -338,11 +382,15 @@ impl LoadBalancingPolicy for CacheAwarePolicy {
             return Some(idx);
         }
 
-        // Selected worker no longer exists or unhealthy, remove stale tenant from tree
+        // Selected worker unhealthy, remove stale tenant from tree. A tenant
+        // outside `workers` may be serving another pool of a wrapping policy
+        // and is kept; removed workers leave the tree through remove_worker.
         if match_rate > self.config.cache_threshold {
             let tenant_url: &str = &result.tenant;
-            tree.remove_tenant(tenant_url);
-            debug!("Removed stale worker {} from cache tree", tenant_url);
+            if workers.iter().any(|w| w.url() == tenant_url) {
+                tree.remove_tenant(tenant_url);
+                debug!("Removed stale worker {} from cache tree", tenant_url);
+            }
         }
 
         // Fallback to first healthy worker
@@ -356,6 +404,54 @@ impl LoadBalancingPolicy for CacheAwarePolicy {
             None
         }
     }
+}
+
+/// Map token ids onto chars so the character radix tree matches on token boundaries
+///
+/// Ids are shifted past the UTF-16 surrogate range to stay valid `char`s; the
+/// mapping is one-to-one for any realistic vocabulary size.
+fn token_ids_to_tree_key(token_ids: &[u32]) -> String {
+    const SURROGATE_START: u32 = 0xD800;
+    const SURROGATE_LEN: u32 = 0x800;
+    token_ids
+        .iter()
+        .map(|&id| {
+            let code = if id < SURROGATE_START {
+                Some(id)
+            } else {
+                id.checked_add(SURROGATE_LEN)
+            };
+            code.and_then(char::from_u32)
+                .unwrap_or(char::REPLACEMENT_CHARACTER)
+        })
+        .collect()
+}
+
+impl LoadBalancingPolicy for CacheAwarePolicy {
+    fn select_worker_with_headers(
+        &self,
+        workers: &[Arc<dyn Worker>],
+        request_text: Option<&str>,
+        _headers: Option<&RequestHeaders>,
+    ) -> Option<usize> {
+        self.select_worker_from_trees(workers, request_text, &self.trees)
+    }
+
+    fn select_worker_with_tokens(
+        &self,
+        workers: &[Arc<dyn Worker>],
+        request_text: Option<&str>,
+        token_ids: Option<&[u32]>,
+        headers: Option<&RequestHeaders>,
+    ) -> Option<usize> {
+        match token_ids {
+            Some(token_ids) => {
+                let key = token_ids_to_tree_key(token_ids);
+                self.select_worker_from_trees(workers, Some(&key), &self.token_trees)
+            }
+            None => self.select_worker_with_headers(workers, request_text, headers),
+        }
+    }
 
     fn name(&self) -> &'static str {
         "cache_aware"
@@ -444,13 +540,14 @@ impl LoadBalancingPolicy for CacheAwarePolicy {
                 tree_key,
                 model_workers.len()
             );
-            let tree = self
-                .trees
-                .entry(tree_key)
-                .or_insert_with(|| Arc::new(Tree::new()))
-                .clone();
-            for worker in model_workers {
-                tree.insert("", worker.url());
+            for trees in self.tree_maps() {
+                let tree = trees
+                    .entry(tree_key.clone())
+                    .or_insert_with(|| Arc::new(Tree::new()))
+                    .clone();
+                for worker in &model_workers {
+                    tree.insert("", worker.url());
+                }
             }
         }
     }
@@ -513,6 +610,61 @@ mod tests {
         assert_eq!(idx1, idx3);
     }
 
+    fn cold_start_workers() -> Vec<Arc<dyn Worker>> {
+        (1..=3)
+            .map(|i| {
+                Arc::new(BasicWorker::new(
+                    format!("http://w{}:8000", i),
+                    WorkerType::Regular,
+                )) as Arc<dyn Worker>
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cold_start_round_robin_spreads_cold_prompts() {
+        let policy = CacheAwarePolicy::with_config(CacheAwareConfig {
+            eviction_interval_secs: 0,
+            cold_start_policy: ColdStartPolicy::RoundRobin,
+            ..Default::default()
+        });
+        let workers = cold_start_workers();
+        policy.init_workers(&workers);
+
+        let mut selected: Vec<usize> = ["alpha", "bravo", "charlie"]
+            .iter()
+            .map(|text| policy.select_worker(&workers, Some(text)).unwrap())
+            .collect();
+        selected.sort();
+        assert_eq!(selected, vec![0, 1, 2]);
+
+        // Warm prompts still follow the tree
+        let warm = policy.select_worker(&workers, Some("alpha")).unwrap();
+        assert_eq!(
+            warm,
+            policy.select_worker(&workers, Some("alphabet")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cold_start_least_load_rotates_between_ties() {
+        let policy = CacheAwarePolicy::with_config(CacheAwareConfig {
+            eviction_interval_secs: 0,
+            cold_start_policy: ColdStartPolicy::LeastLoad,
+            ..Default::default()
+        });
+        let workers = cold_start_workers();
+        policy.init_workers(&workers);
+        workers[0].increment_load();
+
+        let selected: Vec<usize> = ["alpha", "bravo", "charlie", "delta"]
+            .iter()
+            .map(|text| policy.select_worker(&workers, Some(text)).unwrap())
+            .collect();
+        assert!(!selected.contains(&0));
+        assert!(selected.contains(&1) && selected.contains(&2));
+    }
+
     #[test]
     fn test_cache_aware_with_imbalanced_load() {
         let policy = CacheAwarePolicy::with_config(CacheAwareConfig {
@@ -521,6 +673,7 @@ mod tests {
             balance_rel_threshold: 2.0,
             eviction_interval_secs: 0, // Disable eviction thread
             max_tree_size: 10000,
+            ..Default::default()
         });
 
         let worker1 = BasicWorker::new("http://w1:8000".to_string(), WorkerType::Regular);
@@ -574,4 +727,85 @@ mod tests {
         let idx = policy.select_worker(&workers, Some("test1")).unwrap();
         assert_eq!(idx, 1);
     }
+
+    #[test]
+    fn test_clear_tree_forgets_prefixes_but_keeps_workers() {
+        let policy = CacheAwarePolicy::with_config(CacheAwareConfig {
+            eviction_interval_secs: 0,
+            cold_start_policy: ColdStartPolicy::RoundRobin,
+            ..Default::default()
+        });
+        let workers = cold_start_workers();
+        policy.init_workers(&workers);
+
+        let warm = policy.select_worker(&workers, Some("hello world")).unwrap();
+        policy.clear_tree();
+
+        for tree in policy.trees.iter() {
+            let counts = tree.value().get_tenant_char_count();
+            assert_eq!(counts.len(), workers.len());
+            assert!(counts.values().all(|&count| count == 0));
+        }
+        // The prompt is cold again, so round-robin moves on to another worker
+        let cold = policy.select_worker(&workers, Some("hello world")).unwrap();
+        assert_ne!(warm, cold);
+    }
+
+    #[test]
+    fn test_cache_aware_with_token_ids() {
+        let config = CacheAwareConfig {
+            eviction_interval_secs: 0, // Disable eviction thread
+            ..Default::default()
+        };
+        let policy = CacheAwarePolicy::with_config(config);
+        let workers: Vec<Arc<dyn Worker>> = vec![
+            Arc::new(BasicWorker::new(
+                "http://w1:8000".to_string(),
+                WorkerType::Regular,
+            )),
+            Arc::new(BasicWorker::new(
+                "http://w2:8000".to_string(),
+                WorkerType::Regular,
+            )),
+        ];
+        policy.init_workers(&workers);
+
+        let prompt: &[u32] = &[101, 55296, 57343, 200_000, 7];
+        let idx1 = policy
+            .select_worker_with_tokens(&workers, Some("你好世界"), Some(prompt), None)
+            .unwrap();
+
+        // A token prefix of the same prompt sticks to the same worker
+        let idx2 = policy
+            .select_worker_with_tokens(&workers, Some("你好"), Some(&prompt[..3]), None)
+            .unwrap();
+        assert_eq!(idx1, idx2);
+
+        // Token and text trees are separate: the text tree has not seen this prompt
+        let tree = policy.trees.get("default").unwrap().clone();
+        assert_eq!(
+            tree.prefix_match_with_counts("你好世界").matched_char_count,
+            0
+        );
+
+        // Without token ids the policy falls back to text matching
+        let idx3 = policy.select_worker(&workers, Some("hello world")).unwrap();
+        let idx4 = policy
+            .select_worker_with_tokens(&workers, Some("hello world"), None, None)
+            .unwrap();
+        assert_eq!(idx3, idx4);
+    }
+
+    #[test]
+    fn test_token_ids_to_tree_key_is_one_char_per_token() {
+        let ids = [0, 65, 0xD7FF, 0xD800, 0xDFFF, 0x10F7FF];
+        let key = token_ids_to_tree_key(&ids);
+        let chars: Vec<char> = key.chars().collect();
+        assert_eq!(chars.len(), ids.len());
+        assert!(!chars.contains(&char::REPLACEMENT_CHARACTER));
+
+        // Distinct ids map to distinct chars
+        let unique: std::collections::HashSet<char> = chars.iter().copied().collect();
+        assert_eq!(unique.len(), ids.len());
+    }
 }
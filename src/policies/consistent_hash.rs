@@ -3,21 +3,44 @@
 //! This policy implements consistent hashing to route requests to workers based on
 //! session ID or user ID, ensuring that requests from the same user/session are
 //! consistently routed to the same worker for better cache locality.
+//!
+//! Clients that cannot set custom headers (e.g. browsers) can carry the session
+//! in a cookie instead. The policy can mint that cookie for new sessions, and
+//! optionally sign it with HMAC-SHA256 so forged session ids are ignored.
+//!
+//! Virtual nodes can be scaled by a worker attribute so that workers with more
+//! capacity own a proportionally larger share of the ring.
+//!
+//! With an overload factor set, the policy uses consistent hashing with
+//! bounded loads: a worker whose load would exceed that multiple of the
+//! average passes the key on to the next worker clockwise on the ring, so a
+//! single hot session cannot saturate one worker.
+//!
+//! Stateless clients behind a load balancer can be kept sticky by their IP:
+//! with client IP hashing enabled, the first `X-Forwarded-For` entry (or
+//! `X-Real-IP`) is used when the request carries no session identifier.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
+use ring::hmac;
 use tracing::debug;
 use tracing::info;
+use tracing::trace;
 
 use super::get_healthy_worker_indices;
 use super::LoadBalancingPolicy;
 use super::RequestHeaders;
+use crate::config::{SessionCookieConfig, WeightSource};
 use crate::core::Worker;
 use crate::metrics::RouterMetrics;
 
-/// Number of virtual nodes per physical worker (for better load distribution)
-const VIRTUAL_NODES_PER_WORKER: u32 = 160;
+/// Default number of virtual nodes per physical worker (for better load distribution)
+pub const DEFAULT_VIRTUAL_NODES: u32 = 160;
+
+/// Upper bound on a worker's virtual node multiplier, keeping the ring small
+/// when a weight label is set to an outlandish value
+const MAX_WEIGHT_SCALE: f64 = 64.0;
 
 /// Consistent hashing policy
 ///
@@ -27,18 +50,77 @@ const VIRTUAL_NODES_PER_WORKER: u32 = 160;
 pub struct ConsistentHashPolicy {
     /// Hash ring mapping hash values to worker URLs
     hash_ring: RwLock<BTreeMap<u64, String>>,
-    /// Current set of workers (for detecting changes)
-    current_workers: RwLock<Vec<String>>,
+    /// Current workers and their virtual node counts (for detecting changes)
+    current_workers: RwLock<Vec<(String, u32)>>,
+    /// Virtual nodes placed on the ring per worker of default weight
+    virtual_nodes: u32,
+    /// Worker attribute scaling each worker's virtual nodes, if weighted
+    weight_source: Option<WeightSource>,
+    /// Cookie used to carry the session identifier
+    session_cookie: SessionCookieConfig,
+    /// Key for signing and verifying session cookies, if a secret is configured
+    signing_key: Option<hmac::Key>,
+    /// Load cap as a multiple of the average load (None = unbounded)
+    overload_factor: Option<f64>,
+    /// Hash on the client IP when no session identifier is present
+    hash_client_ip: bool,
+}
+
+/// A newly minted session cookie
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintedSession {
+    /// `name=value` pair to use as the request's Cookie header for routing
+    pub cookie: String,
+    /// Value for the response's Set-Cookie header
+    pub set_cookie: String,
 }
 
 impl ConsistentHashPolicy {
     pub fn new() -> Self {
+        Self::with_session_cookie(SessionCookieConfig::default())
+    }
+
+    pub fn with_session_cookie(session_cookie: SessionCookieConfig) -> Self {
+        Self::with_config(DEFAULT_VIRTUAL_NODES, None, session_cookie)
+    }
+
+    pub fn with_config(
+        virtual_nodes: u32,
+        weight_source: Option<WeightSource>,
+        session_cookie: SessionCookieConfig,
+    ) -> Self {
+        let signing_key = session_cookie
+            .secret
+            .as_ref()
+            .map(|secret| hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes()));
         Self {
             hash_ring: RwLock::new(BTreeMap::new()),
             current_workers: RwLock::new(Vec::new()),
+            virtual_nodes: virtual_nodes.max(1),
+            weight_source,
+            session_cookie,
+            signing_key,
+            overload_factor: None,
+            hash_client_ip: false,
         }
     }
 
+    /// Bound each worker's load to `overload_factor` times the average
+    pub fn with_overload_factor(mut self, overload_factor: Option<f64>) -> Self {
+        self.overload_factor = overload_factor;
+        self
+    }
+
+    /// Use the client IP as the hash key for requests without a session identifier
+    pub fn with_client_ip_hashing(mut self, hash_client_ip: bool) -> Self {
+        self.hash_client_ip = hash_client_ip;
+        self
+    }
+
+    pub fn session_cookie(&self) -> &SessionCookieConfig {
+        &self.session_cookie
+    }
+
     /// MurmurHash64A implementation from Facebook's mcrouter/lib/fbi/hash.c
     fn murmur_hash_64a(key: &[u8], seed: u32) -> u64 {
         const M: u64 = 0xc6a4a7935bd1e995;
@@ -244,45 +326,117 @@ impl ConsistentHashPolicy {
         )
     }
 
-    /// Update the hash ring when workers change
+    /// Virtual nodes for a worker
+    ///
+    /// Weights are relative to the attribute's default (priority 50, cost
+    /// 1.0), so a worker at twice the default weight gets twice the nodes.
+    /// Every worker keeps at least one node so it stays reachable.
+    fn worker_virtual_nodes(&self, worker: &dyn Worker) -> u32 {
+        let scale = match self.weight_source {
+            None => return self.virtual_nodes,
+            Some(WeightSource::Priority) => worker.priority() as f64 / 50.0,
+            Some(WeightSource::Cost) => worker.cost() as f64,
+        };
+        if !scale.is_finite() || scale <= 0.0 {
+            return 1;
+        }
+        (self.virtual_nodes as f64 * scale.min(MAX_WEIGHT_SCALE))
+            .round()
+            .max(1.0) as u32
+    }
+
+    /// Update the hash ring when workers or their weights change
     fn update_hash_ring(&self, workers: &[Arc<dyn Worker>]) {
-        let worker_urls: Vec<String> = workers.iter().map(|w| w.url().to_string()).collect();
+        let worker_nodes: Vec<(String, u32)> = workers
+            .iter()
+            .map(|w| (w.url().to_string(), self.worker_virtual_nodes(w.as_ref())))
+            .collect();
 
         // Check if workers have changed
         {
             let current = self.current_workers.read().unwrap();
-            if *current == worker_urls {
+            if *current == worker_nodes {
                 return; // No change needed
             }
         }
 
-        // Rebuild hash ring
+        // Rebuild hash ring. Node keys do not depend on the weight, so a
+        // weight change only adds or removes that worker's extra nodes
         let mut new_ring = BTreeMap::new();
 
-        for worker_url in &worker_urls {
+        for (worker_url, nodes) in &worker_nodes {
             // Create virtual nodes for better distribution
-            for i in 0..VIRTUAL_NODES_PER_WORKER {
+            for i in 0..*nodes {
                 let virtual_key = format!("{}:{}", worker_url, i);
                 let hash_value = Self::fbi_hash(&virtual_key);
                 new_ring.insert(hash_value, worker_url.clone());
             }
         }
+        let total_nodes: u32 = worker_nodes.iter().map(|(_, nodes)| nodes).sum();
 
         // Update both the ring and current workers
-        {
+        let remapped = {
             let mut ring = self.hash_ring.write().unwrap();
+            let remapped = (!ring.is_empty()).then(|| Self::remapped_fraction(&ring, &new_ring));
             *ring = new_ring;
-        }
+            remapped
+        };
         {
             let mut current = self.current_workers.write().unwrap();
-            *current = worker_urls;
+            *current = worker_nodes;
         }
 
         info!(
             "Updated consistent hash ring with {} workers and {} virtual nodes",
             workers.len(),
-            workers.len() as u32 * VIRTUAL_NODES_PER_WORKER
+            total_nodes
         );
+        if let Some(fraction) = remapped {
+            info!(
+                "Consistent hash ring rebuild remaps an estimated {:.1}% of keys",
+                fraction * 100.0
+            );
+            RouterMetrics::record_hash_ring_rebuild(fraction);
+        }
+    }
+
+    /// Fraction of the hash space whose owner differs between two rings
+    ///
+    /// Keys map to the first ring point at or after their hash, so between
+    /// two consecutive points of either ring every key has the same owner in
+    /// each ring. Summing the spans whose owners differ estimates the share
+    /// of session keys that move to another worker.
+    fn remapped_fraction(old: &BTreeMap<u64, String>, new: &BTreeMap<u64, String>) -> f64 {
+        fn owner(ring: &BTreeMap<u64, String>, point: u64) -> Option<&str> {
+            ring.range(point..)
+                .next()
+                .or_else(|| ring.iter().next())
+                .map(|(_, url)| url.as_str())
+        }
+
+        let mut points: Vec<u64> = old.keys().chain(new.keys()).copied().collect();
+        points.sort_unstable();
+        points.dedup();
+        let Some(&last) = points.last() else {
+            return 0.0;
+        };
+
+        let mut remapped = 0.0;
+        let mut prev = last;
+        for &point in &points {
+            // Span (prev, point]; the first span wraps around from the last point
+            if owner(old, point) != owner(new, point) {
+                let span = point.wrapping_sub(prev);
+                // A single point everywhere means the whole ring
+                remapped += if span == 0 {
+                    u64::MAX as f64
+                } else {
+                    span as f64
+                };
+            }
+            prev = point;
+        }
+        remapped / u64::MAX as f64
     }
 
     /// Find the worker for a given hash key using consistent hashing
@@ -303,15 +457,59 @@ impl ConsistentHashPolicy {
             .map(|(_, worker_url)| worker_url.clone());
 
         if let Some(ref worker) = selected_worker {
-            debug!(
-                "Consistent hash: key='{}' hash={:016x} -> worker='{}'",
-                hash_key, hash_value, worker
+            trace!(
+                "Consistent hash: key={} hash={:016x} -> worker='{}'",
+                Self::redacted_key(hash_key),
+                hash_value,
+                worker
             );
         }
 
         selected_worker
     }
 
+    /// Find the first worker clockwise from the key's ring point that is under the load cap
+    ///
+    /// The cap is `ceil(overload_factor * (total_load + 1) / available_workers)`,
+    /// which always leaves some available worker under it. Returns None if the
+    /// ring holds no available worker.
+    fn find_worker_with_bounded_load(
+        &self,
+        hash_key: &str,
+        workers: &[Arc<dyn Worker>],
+        overload_factor: f64,
+    ) -> Option<String> {
+        let loads: HashMap<&str, usize> = workers
+            .iter()
+            .filter(|w| w.is_healthy() && w.circuit_breaker().can_execute())
+            .map(|w| (w.url(), w.load()))
+            .collect();
+        if loads.is_empty() {
+            return None;
+        }
+        let total_load: usize = loads.values().sum();
+        let cap = (overload_factor * (total_load + 1) as f64 / loads.len() as f64).ceil() as usize;
+
+        let hash_value = Self::fbi_hash(hash_key);
+        let ring = self.hash_ring.read().unwrap();
+        let mut visited = HashSet::new();
+        for (_, worker_url) in ring.range(hash_value..).chain(ring.range(..hash_value)) {
+            if !visited.insert(worker_url.as_str()) {
+                continue;
+            }
+            if loads
+                .get(worker_url.as_str())
+                .is_some_and(|&load| load < cap)
+            {
+                return Some(worker_url.clone());
+            }
+            if visited.len() == loads.len() {
+                break;
+            }
+        }
+        None
+    }
+
     /// HTTP header names to check for session ID (case-insensitive, checked in order)
     const SESSION_HEADER_NAMES: &'static [&'static str] = &[
         "x-session-id",
@@ -328,10 +526,7 @@ impl ConsistentHashPolicy {
         for header_name in Self::SESSION_HEADER_NAMES {
             if let Some(value) = headers.get(*header_name) {
                 if !value.is_empty() {
-                    info!(
-                        "CONSISTENT_HASH_DEBUG: Found session key in header '{}': {}",
-                        header_name, value
-                    );
+                    trace!("Consistent hash key taken from header '{}'", header_name);
                     return Some(format!("header:{}:{}", header_name, value));
                 }
             }
@@ -339,6 +534,105 @@ impl ConsistentHashPolicy {
         None
     }
 
+    /// Extract the session identifier from the configured cookie
+    /// Returns None if the cookie is missing, empty, or fails signature verification
+    fn extract_session_from_cookie(&self, headers: &RequestHeaders) -> Option<String> {
+        let cookie_header = headers.get("cookie")?;
+        let value = cookie_header.split(';').find_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            (name.trim() == self.session_cookie.name).then(|| value.trim().trim_matches('"'))
+        })?;
+        if value.is_empty() {
+            return None;
+        }
+
+        let Some(key) = &self.signing_key else {
+            return Some(value.to_string());
+        };
+        let (session_id, signature) = value.rsplit_once('.')?;
+        let signature = decode_hex(signature)?;
+        match hmac::verify(key, session_id.as_bytes(), &signature) {
+            Ok(()) if !session_id.is_empty() => Some(session_id.to_string()),
+            _ => {
+                debug!(
+                    "Ignoring session cookie '{}' with invalid signature",
+                    self.session_cookie.name
+                );
+                None
+            }
+        }
+    }
+
+    /// Extract hash key from the session cookie
+    fn extract_hash_key_from_cookie(&self, headers: &RequestHeaders) -> Option<String> {
+        let session_id = self.extract_session_from_cookie(headers)?;
+        trace!(
+            "Consistent hash key taken from cookie '{}'",
+            self.session_cookie.name
+        );
+        Some(format!("cookie:{}", session_id))
+    }
+
+    /// Extract hash key from the client IP set by a proxy
+    /// Uses the first (originating) `X-Forwarded-For` entry, then `X-Real-IP`
+    fn extract_hash_key_from_client_ip(&self, headers: &RequestHeaders) -> Option<String> {
+        if !self.hash_client_ip {
+            return None;
+        }
+        let forwarded = headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.split(',').next())
+            .map(str::trim)
+            .filter(|ip| !ip.is_empty());
+        let ip = forwarded.or_else(|| {
+            headers
+                .get("x-real-ip")
+                .map(|value| value.trim())
+                .filter(|ip| !ip.is_empty())
+        })?;
+        trace!("Consistent hash key taken from the client IP");
+        Some(format!("client_ip:{}", ip))
+    }
+
+    /// Mint a session cookie for a request that carries no session identifier
+    ///
+    /// Returns None when cookie minting is disabled or the request already
+    /// identifies its session through a header, the cookie, or the body.
+    pub fn mint_session(
+        &self,
+        request_text: Option<&str>,
+        headers: Option<&RequestHeaders>,
+    ) -> Option<MintedSession> {
+        if !self.session_cookie.set_on_response {
+            return None;
+        }
+        if let Some(hdrs) = headers {
+            if self.extract_hash_key_from_headers(hdrs).is_some()
+                || self.extract_session_from_cookie(hdrs).is_some()
+            {
+                return None;
+            }
+        }
+        if self.extract_hash_key_from_body(request_text).is_some() {
+            return None;
+        }
+
+        let session_id = uuid::Uuid::new_v4().simple().to_string();
+        let value = match &self.signing_key {
+            Some(key) => format!(
+                "{}.{}",
+                session_id,
+                encode_hex(hmac::sign(key, session_id.as_bytes()).as_ref())
+            ),
+            None => session_id,
+        };
+        let cookie = format!("{}={}", self.session_cookie.name, value);
+        Some(MintedSession {
+            set_cookie: format!("{}; Path=/; HttpOnly; SameSite=Lax", cookie),
+            cookie,
+        })
+    }
+
     /// Extract hash key from request text
     /// Priority: session_params.session_id > user field > legacy session_id > legacy user_id > request content
     fn extract_hash_key_from_body(&self, request_text: Option<&str>) -> Option<String> {
@@ -351,16 +645,13 @@ impl ConsistentHashPolicy {
         if let Some(session_id) =
             self.extract_nested_field_value(text, "session_params", "session_id")
         {
-            info!(
-                "CONSISTENT_HASH_DEBUG: Found session_params.session_id: {}",
-                session_id
-            );
+            trace!("Consistent hash key taken from session_params.session_id");
             return Some(format!("session:{}", session_id));
         }
 
         // 2. Try to extract direct user field (from OpenAI ChatCompletion/Completion requests)
         if let Some(user) = self.extract_field_value(text, "user") {
-            info!("CONSISTENT_HASH_DEBUG: Found user field: {}", user);
+            trace!("Consistent hash key taken from the user field");
             return Some(format!("user:{}", user));
         }
 
@@ -377,15 +668,17 @@ impl ConsistentHashPolicy {
         None
     }
 
-    /// Extract hash key with priority: HTTP headers > body fields > request content hash
+    /// Extract hash key with priority: HTTP headers > session cookie > body fields > client IP > request content hash
     ///
     /// Priority order:
     /// 1. HTTP Headers: x-session-id, x-user-id, x-tenant-id, x-request-id, x-correlation-id, x-trace-id
-    /// 2. Body: session_params.session_id
-    /// 3. Body: user field (OpenAI format)
-    /// 4. Body: session_id (legacy)
-    /// 5. Body: user_id (legacy)
-    /// 6. Fallback: hash of request body
+    /// 2. Cookie: configured session cookie (default `router_session`)
+    /// 3. Body: session_params.session_id
+    /// 4. Body: user field (OpenAI format)
+    /// 5. Body: session_id (legacy)
+    /// 6. Body: user_id (legacy)
+    /// 7. Client IP: first x-forwarded-for entry, then x-real-ip (if enabled)
+    /// 8. Fallback: hash of request body
     fn extract_hash_key(
         &self,
         request_text: Option<&str>,
@@ -396,14 +689,23 @@ impl ConsistentHashPolicy {
             if let Some(key) = self.extract_hash_key_from_headers(hdrs) {
                 return key;
             }
+            // 2. Second priority: session cookie
+            if let Some(key) = self.extract_hash_key_from_cookie(hdrs) {
+                return key;
+            }
         }
 
-        // 2. Second priority: Body fields
+        // 3. Third priority: Body fields
         if let Some(key) = self.extract_hash_key_from_body(request_text) {
             return key;
         }
 
-        // 3. Final fallback: hash of request body
+        // 4. Fourth priority: client IP, for stateless clients behind a proxy
+        if let Some(key) = headers.and_then(|hdrs| self.extract_hash_key_from_client_ip(hdrs)) {
+            return key;
+        }
+
+        // 5. Final fallback: hash of request body
         let text = request_text.unwrap_or("");
         if text.len() > 100 {
             format!("request_hash:{:016x}", Self::fbi_hash(text))
@@ -412,6 +714,20 @@ impl ConsistentHashPolicy {
         }
     }
 
+    /// Loggable form of a hash key: its source plus a digest of the value
+    ///
+    /// Keys carry session and user identifiers (or prompt text), which must not
+    /// end up in logs; the digest still lets requests of one session be matched.
+    fn redacted_key(hash_key: &str) -> String {
+        let source = match hash_key.strip_prefix("header:") {
+            Some(rest) => rest
+                .split_once(':')
+                .map_or("header", |(name, _)| &hash_key[..7 + name.len()]),
+            None => hash_key.split_once(':').map_or("", |(source, _)| source),
+        };
+        format!("{}#{:08x}", source, Self::fbi_hash(hash_key) >> 32)
+    }
+
     /// Extract nested field value like session_params.session_id from JSON text
     fn extract_nested_field_value(
         &self,
@@ -580,33 +896,42 @@ impl LoadBalancingPolicy for ConsistentHashPolicy {
         // Extract hash key with new priority: headers > body > fallback
         let hash_key = self.extract_hash_key(request_text, headers);
 
-        // DEBUG: Log the request text and extracted hash key
         if let Some(text) = request_text {
-            debug!("CONSISTENT_HASH_DEBUG: Request text length: {}", text.len());
+            trace!("Consistent hash request text length: {}", text.len());
         }
         if let Some(hdrs) = headers {
-            debug!(
-                "CONSISTENT_HASH_DEBUG: Headers available: {:?}",
+            trace!(
+                "Consistent hash headers available: {:?}",
                 hdrs.keys().collect::<Vec<_>>()
             );
         }
-        info!("CONSISTENT_HASH_DEBUG: Extracted hash key: {}", hash_key);
 
         // Find target worker using consistent hashing
         let target_worker_url = match self.find_worker_by_hash(&hash_key) {
-            Some(url) => {
-                info!(
-                    "CONSISTENT_HASH_DEBUG: Hash key '{}' mapped to worker: {}",
-                    hash_key, url
-                );
-                url
-            }
+            Some(url) => match self
+                .overload_factor
+                .and_then(|factor| self.find_worker_with_bounded_load(&hash_key, workers, factor))
+            {
+                Some(bounded_url) if bounded_url != url => {
+                    debug!(
+                        "Consistent hash target '{}' is over its load cap, using '{}'",
+                        url, bounded_url
+                    );
+                    RouterMetrics::record_load_balancing_event();
+                    Some(bounded_url)
+                }
+                _ => Some(url),
+            },
+            None => None,
+        };
+        let target_worker_url = match target_worker_url {
+            Some(url) => url,
             None => {
                 // Fallback to first healthy worker if hash ring is empty
                 let fallback_idx = healthy_indices[0];
                 let worker_url = workers[fallback_idx].url();
-                info!(
-                    "CONSISTENT_HASH_DEBUG: Hash ring empty, falling back to worker: {}",
+                debug!(
+                    "Consistent hash ring empty, falling back to worker: {}",
                     worker_url
                 );
                 RouterMetrics::record_processed_request(worker_url);
@@ -630,9 +955,10 @@ impl LoadBalancingPolicy for ConsistentHashPolicy {
             })
         };
 
-        debug!(
-            "CONSISTENT_HASH_DEBUG: Target worker URL: {}, DP rank: {:?}",
-            target_worker_url, dp_rank
+        trace!(
+            "Consistent hash target worker URL: {}, DP rank: {:?}",
+            target_worker_url,
+            dp_rank
         );
 
         match selected_idx {
@@ -641,12 +967,10 @@ impl LoadBalancingPolicy for ConsistentHashPolicy {
                 if workers[idx].is_healthy() && workers[idx].circuit_breaker().can_execute() {
                     let worker_url = workers[idx].url();
                     debug!(
-                        "CONSISTENT_HASH_DEBUG: Selected worker at index {}: {}",
-                        idx, worker_url
-                    );
-                    info!(
-                        "Consistent hash routing: key='{}' -> worker='{}' (index={})",
-                        hash_key, worker_url, idx
+                        "Consistent hash routing: key={} -> worker='{}' (index={})",
+                        Self::redacted_key(&hash_key),
+                        worker_url,
+                        idx
                     );
 
                     // Increment processed counter
@@ -743,12 +1067,41 @@ impl Default for ConsistentHashPolicy {
     }
 }
 
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::BasicWorker;
     use crate::core::WorkerType;
 
+    #[test]
+    fn test_redacted_key_hides_identifiers() {
+        let header = ConsistentHashPolicy::redacted_key("header:x-user-id:alice@example.com");
+        assert!(header.starts_with("header:x-user-id#"));
+        assert!(!header.contains("alice"));
+
+        let user = ConsistentHashPolicy::redacted_key("user:alice");
+        assert!(user.starts_with("user#"));
+        assert!(!user.contains("alice"));
+
+        // The digest is stable so one session's requests can still be correlated
+        assert_eq!(user, ConsistentHashPolicy::redacted_key("user:alice"));
+        assert_ne!(user, ConsistentHashPolicy::redacted_key("user:bob"));
+    }
+
     #[test]
     fn test_fbi_hash_consistency() {
         let key = "test_session_123";
@@ -804,4 +1157,303 @@ mod tests {
         assert_eq!(idx2, idx3);
         assert!(idx1.is_some());
     }
+
+    #[test]
+    fn test_overload_factor_skips_worker_over_load_cap() {
+        let workers: Vec<Arc<dyn Worker>> = (1..=3)
+            .map(|i| {
+                Arc::new(BasicWorker::new(
+                    format!("http://worker{}:8000", i),
+                    WorkerType::Regular,
+                )) as Arc<dyn Worker>
+            })
+            .collect();
+        let request = r#"{"session_id": "hot_session"}"#;
+
+        let unbounded = ConsistentHashPolicy::new();
+        let primary = unbounded.select_worker(&workers, Some(request)).unwrap();
+        for _ in 0..10 {
+            workers[primary].increment_load();
+        }
+        // Without a load bound the key stays on its ring owner
+        assert_eq!(
+            unbounded.select_worker(&workers, Some(request)),
+            Some(primary)
+        );
+
+        let bounded = ConsistentHashPolicy::new().with_overload_factor(Some(1.25));
+        let selected = bounded.select_worker(&workers, Some(request)).unwrap();
+        assert_ne!(selected, primary);
+        assert_eq!(workers[selected].load(), 0);
+
+        // Once the load evens out the key returns to its owner
+        for worker in &workers {
+            worker.reset_load();
+        }
+        assert_eq!(
+            bounded.select_worker(&workers, Some(request)),
+            Some(primary)
+        );
+    }
+
+    fn cookie_headers(cookie: &str) -> RequestHeaders {
+        let mut headers = RequestHeaders::new();
+        headers.insert("cookie".to_string(), cookie.to_string());
+        headers
+    }
+
+    #[test]
+    fn test_virtual_nodes_from_config() {
+        let policy = ConsistentHashPolicy::with_config(8, None, SessionCookieConfig::default());
+        let workers: Vec<Arc<dyn Worker>> = (0..3)
+            .map(|i| {
+                Arc::new(BasicWorker::new(
+                    format!("http://worker{}:8000", i),
+                    WorkerType::Regular,
+                )) as Arc<dyn Worker>
+            })
+            .collect();
+
+        policy.update_hash_ring(&workers);
+        assert_eq!(policy.hash_ring.read().unwrap().len(), 24);
+
+        let default = ConsistentHashPolicy::new();
+        default.update_hash_ring(&workers);
+        assert_eq!(
+            default.hash_ring.read().unwrap().len(),
+            3 * DEFAULT_VIRTUAL_NODES as usize
+        );
+    }
+
+    #[test]
+    fn test_weighted_virtual_nodes() {
+        let worker = |url: &str, priority: &str| -> Arc<dyn Worker> {
+            let labels =
+                std::collections::HashMap::from([("priority".to_string(), priority.to_string())]);
+            Arc::new(BasicWorker::new(url.to_string(), WorkerType::Regular).with_labels(labels))
+        };
+        let policy = ConsistentHashPolicy::with_config(
+            100,
+            Some(WeightSource::Priority),
+            SessionCookieConfig::default(),
+        );
+        let workers = vec![
+            worker("http://small:8000", "50"),
+            worker("http://large:8000", "100"),
+            worker("http://drained:8000", "0"),
+        ];
+
+        policy.update_hash_ring(&workers);
+        let owned = |url: &str| {
+            policy
+                .hash_ring
+                .read()
+                .unwrap()
+                .values()
+                .filter(|owner| owner.as_str() == url)
+                .count()
+        };
+        assert_eq!(owned("http://small:8000"), 100);
+        assert_eq!(owned("http://large:8000"), 200);
+        assert_eq!(owned("http://drained:8000"), 1);
+
+        // A 2x worker should receive roughly 2x the sessions
+        let (mut small, mut large) = (0, 0);
+        for i in 0..3000 {
+            let headers =
+                RequestHeaders::from([("x-session-id".to_string(), format!("session-{}", i))]);
+            match policy.select_worker_with_headers(&workers, None, Some(&headers)) {
+                Some(0) => small += 1,
+                Some(1) => large += 1,
+                _ => {}
+            }
+        }
+        let ratio = large as f64 / small as f64;
+        assert!((1.5..2.6).contains(&ratio), "ratio: {}", ratio);
+
+        // Weight changes rebuild the ring
+        let workers = vec![
+            worker("http://small:8000", "100"),
+            worker("http://large:8000", "100"),
+        ];
+        policy.update_hash_ring(&workers);
+        assert_eq!(owned("http://small:8000"), 200);
+    }
+
+    #[test]
+    fn test_remapped_fraction() {
+        let ring = |workers: &[&str]| {
+            let mut ring = BTreeMap::new();
+            for worker in workers {
+                for i in 0..DEFAULT_VIRTUAL_NODES {
+                    let hash = ConsistentHashPolicy::fbi_hash(&format!("{}:{}", worker, i));
+                    ring.insert(hash, worker.to_string());
+                }
+            }
+            ring
+        };
+        let three = ring(&["http://w1", "http://w2", "http://w3"]);
+        let four = ring(&["http://w1", "http://w2", "http://w3", "http://w4"]);
+
+        assert_eq!(ConsistentHashPolicy::remapped_fraction(&three, &three), 0.0);
+
+        // Adding a fourth worker should move roughly a quarter of the keys
+        let fraction = ConsistentHashPolicy::remapped_fraction(&three, &four);
+        assert!((0.15..0.35).contains(&fraction), "fraction: {}", fraction);
+
+        let replaced = ring(&["http://w4"]);
+        let single = ring(&["http://w1"]);
+        let fraction = ConsistentHashPolicy::remapped_fraction(&single, &replaced);
+        assert!((fraction - 1.0).abs() < 1e-9, "fraction: {}", fraction);
+    }
+
+    #[test]
+    fn test_session_cookie_priority() {
+        let policy = ConsistentHashPolicy::new();
+        let body = Some(r#"{"user": "body-user"}"#);
+
+        let headers = cookie_headers("theme=dark; router_session=abc123");
+        assert_eq!(
+            policy.extract_hash_key(body, Some(&headers)),
+            "cookie:abc123"
+        );
+
+        // Explicit session headers win over the cookie
+        let mut headers = headers;
+        headers.insert("x-session-id".to_string(), "hdr".to_string());
+        assert_eq!(
+            policy.extract_hash_key(body, Some(&headers)),
+            "header:x-session-id:hdr"
+        );
+
+        // Other cookies are ignored
+        let headers = cookie_headers("theme=dark; other_session=abc123");
+        assert_eq!(
+            policy.extract_hash_key(body, Some(&headers)),
+            "user:body-user"
+        );
+    }
+
+    #[test]
+    fn test_client_ip_hash_key() {
+        let policy = ConsistentHashPolicy::new().with_client_ip_hashing(true);
+        let mut headers = RequestHeaders::new();
+        headers.insert(
+            "x-forwarded-for".to_string(),
+            " 203.0.113.7, 10.0.0.2, 10.0.0.1".to_string(),
+        );
+        headers.insert("x-real-ip".to_string(), "10.0.0.1".to_string());
+        assert_eq!(
+            policy.extract_hash_key(Some(r#"{"prompt": "hi"}"#), Some(&headers)),
+            "client_ip:203.0.113.7"
+        );
+
+        // X-Real-IP is used without X-Forwarded-For
+        headers.remove("x-forwarded-for");
+        assert_eq!(
+            policy.extract_hash_key(None, Some(&headers)),
+            "client_ip:10.0.0.1"
+        );
+
+        // Session identifiers still win over the client IP
+        assert_eq!(
+            policy.extract_hash_key(Some(r#"{"user": "body-user"}"#), Some(&headers)),
+            "user:body-user"
+        );
+        headers.insert("x-session-id".to_string(), "hdr".to_string());
+        assert_eq!(
+            policy.extract_hash_key(None, Some(&headers)),
+            "header:x-session-id:hdr"
+        );
+
+        // Disabled by default
+        headers.remove("x-session-id");
+        assert_eq!(
+            ConsistentHashPolicy::new().extract_hash_key(None, Some(&headers)),
+            "request:"
+        );
+    }
+
+    #[test]
+    fn test_session_cookie_custom_name() {
+        let policy = ConsistentHashPolicy::with_session_cookie(SessionCookieConfig {
+            name: "sid".to_string(),
+            ..Default::default()
+        });
+
+        let headers = cookie_headers("router_session=ignored; sid=xyz");
+        assert_eq!(policy.extract_hash_key(None, Some(&headers)), "cookie:xyz");
+    }
+
+    #[test]
+    fn test_mint_session_only_when_absent() {
+        let disabled = ConsistentHashPolicy::new();
+        assert!(disabled.mint_session(None, None).is_none());
+
+        let policy = ConsistentHashPolicy::with_session_cookie(SessionCookieConfig {
+            set_on_response: true,
+            ..Default::default()
+        });
+
+        let minted = policy.mint_session(None, None).unwrap();
+        assert!(minted.cookie.starts_with("router_session="));
+        assert_eq!(
+            minted.set_cookie,
+            format!("{}; Path=/; HttpOnly; SameSite=Lax", minted.cookie)
+        );
+
+        // Routing by the minted cookie picks the same key as a follow-up request
+        let headers = cookie_headers(&minted.cookie);
+        let session_id = minted.cookie.trim_start_matches("router_session=");
+        assert_eq!(
+            policy.extract_hash_key(None, Some(&headers)),
+            format!("cookie:{}", session_id)
+        );
+        assert!(policy.mint_session(None, Some(&headers)).is_none());
+
+        let mut headers = RequestHeaders::new();
+        headers.insert("x-user-id".to_string(), "u1".to_string());
+        assert!(policy.mint_session(None, Some(&headers)).is_none());
+        assert!(policy
+            .mint_session(Some(r#"{"user": "u1"}"#), None)
+            .is_none());
+    }
+
+    #[test]
+    fn test_signed_session_cookie() {
+        let policy = ConsistentHashPolicy::with_session_cookie(SessionCookieConfig {
+            set_on_response: true,
+            secret: Some("s3cret".to_string()),
+            ..Default::default()
+        });
+
+        let minted = policy.mint_session(None, None).unwrap();
+        let value = minted.cookie.trim_start_matches("router_session=");
+        let (session_id, _) = value.split_once('.').unwrap();
+        let headers = cookie_headers(&minted.cookie);
+        assert_eq!(
+            policy.extract_hash_key(None, Some(&headers)),
+            format!("cookie:{}", session_id)
+        );
+
+        // Unsigned and tampered cookies are ignored and a new session is minted
+        for forged in [
+            format!("router_session={}", session_id),
+            format!("router_session=other.{}", value.split_once('.').unwrap().1),
+            format!("router_session={}.zz", session_id),
+        ] {
+            let headers = cookie_headers(&forged);
+            assert_eq!(policy.extract_hash_key(None, Some(&headers)), "request:");
+            assert!(policy.mint_session(None, Some(&headers)).is_some());
+        }
+
+        // A cookie signed with a different secret is rejected
+        let other = ConsistentHashPolicy::with_session_cookie(SessionCookieConfig {
+            set_on_response: true,
+            secret: Some("different".to_string()),
+            ..Default::default()
+        });
+        let headers = cookie_headers(&other.mint_session(None, None).unwrap().cookie);
+        assert!(policy.extract_session_from_cookie(&headers).is_none());
+    }
 }
@@ -1,8 +1,9 @@
 //! Factory for creating load balancing policies
 
 use super::{
-    CacheAwareConfig, CacheAwarePolicy, ConsistentHashPolicy, LoadBalancingPolicy,
-    PowerOfTwoPolicy, RandomPolicy, RoundRobinPolicy,
+    CacheAwareConfig, CacheAwarePolicy, ConsistentHashPolicy, HeaderRoutePolicy,
+    LatencyAwarePolicy, LoadBalancingPolicy, LoadWeightedRandomPolicy, PowerOfTwoPolicy,
+    RandomPolicy, RoundRobinPolicy, WeightedRoundRobinPolicy,
 };
 use crate::config::PolicyConfig;
 use std::sync::Arc;
@@ -10,19 +11,42 @@ use std::sync::Arc;
 /// Factory for creating policy instances
 pub struct PolicyFactory;
 
+/// Canonical names of all policies that can be created by name
+const AVAILABLE_POLICIES: &[&str] = &[
+    "random",
+    "round_robin",
+    "cache_aware",
+    "power_of_two",
+    "load_weighted_random",
+    "latency_aware",
+    "consistent_hash",
+    "weighted_round_robin",
+    "header_route",
+];
+
 impl PolicyFactory {
+    /// Names of all policies accepted by `create_by_name`
+    pub fn available_policies() -> &'static [&'static str] {
+        AVAILABLE_POLICIES
+    }
+
     /// Create a policy from configuration
     pub fn create_from_config(config: &PolicyConfig) -> Arc<dyn LoadBalancingPolicy> {
         match config {
             PolicyConfig::Random => Arc::new(RandomPolicy::new()),
             PolicyConfig::RoundRobin => Arc::new(RoundRobinPolicy::new()),
             PolicyConfig::PowerOfTwo { .. } => Arc::new(PowerOfTwoPolicy::new()),
+            PolicyConfig::LoadWeightedRandom { .. } => Arc::new(LoadWeightedRandomPolicy::new()),
+            PolicyConfig::LatencyAware { decay } => {
+                Arc::new(LatencyAwarePolicy::with_decay(*decay))
+            }
             PolicyConfig::CacheAware {
                 cache_threshold,
                 balance_abs_threshold,
                 balance_rel_threshold,
                 eviction_interval_secs,
                 max_tree_size,
+                cold_start_policy,
             } => {
                 let config = CacheAwareConfig {
                     cache_threshold: *cache_threshold,
@@ -30,14 +54,41 @@ impl PolicyFactory {
                     balance_rel_threshold: *balance_rel_threshold,
                     eviction_interval_secs: *eviction_interval_secs,
                     max_tree_size: *max_tree_size,
+                    cold_start_policy: *cold_start_policy,
                 };
                 Arc::new(CacheAwarePolicy::with_config(config))
             }
-            PolicyConfig::ConsistentHash { virtual_nodes: _ } => {
-                // Note: virtual_nodes parameter is available but not currently used
-                // The consistent hash policy uses a hardcoded value for now
-                Arc::new(ConsistentHashPolicy::new())
+            PolicyConfig::ConsistentHash {
+                virtual_nodes,
+                weight_source,
+                session_cookie,
+                overload_factor,
+                hash_client_ip,
+            } => Arc::new(
+                ConsistentHashPolicy::with_config(
+                    *virtual_nodes,
+                    *weight_source,
+                    session_cookie.clone(),
+                )
+                .with_overload_factor(*overload_factor)
+                .with_client_ip_hashing(*hash_client_ip),
+            ),
+            PolicyConfig::WeightedRoundRobin { weight_source } => {
+                Arc::new(WeightedRoundRobinPolicy::with_weight_source(*weight_source))
             }
+            PolicyConfig::HeaderRoute {
+                header,
+                label,
+                routes,
+                default_route,
+                sub_policy,
+            } => Arc::new(HeaderRoutePolicy::with_config(
+                header,
+                label,
+                routes.clone(),
+                default_route.clone(),
+                Self::create_from_config(sub_policy),
+            )),
         }
     }
 
@@ -47,8 +98,16 @@ impl PolicyFactory {
             "random" => Some(Arc::new(RandomPolicy::new())),
             "round_robin" | "roundrobin" => Some(Arc::new(RoundRobinPolicy::new())),
             "power_of_two" | "poweroftwo" => Some(Arc::new(PowerOfTwoPolicy::new())),
+            "load_weighted_random" | "loadweightedrandom" => {
+                Some(Arc::new(LoadWeightedRandomPolicy::new()))
+            }
+            "latency_aware" | "latencyaware" => Some(Arc::new(LatencyAwarePolicy::new())),
             "cache_aware" | "cacheaware" => Some(Arc::new(CacheAwarePolicy::new())),
             "consistent_hash" | "consistenthash" => Some(Arc::new(ConsistentHashPolicy::new())),
+            "weighted_round_robin" | "weightedroundrobin" => {
+                Some(Arc::new(WeightedRoundRobinPolicy::new()))
+            }
+            "header_route" | "headerroute" => Some(Arc::new(HeaderRoutePolicy::new())),
             _ => None,
         }
     }
@@ -57,6 +116,7 @@ impl PolicyFactory {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{ColdStartPolicy, WeightSource};
 
     #[test]
     fn test_create_from_config() {
@@ -73,6 +133,19 @@ mod tests {
             load_check_interval_secs: 60,
         });
         assert_eq!(policy.name(), "power_of_two");
+        assert!(policy.needs_load_monitoring());
+
+        // Test LoadWeightedRandom
+        let policy = PolicyFactory::create_from_config(&PolicyConfig::LoadWeightedRandom {
+            load_check_interval_secs: 60,
+        });
+        assert_eq!(policy.name(), "load_weighted_random");
+        assert!(policy.needs_load_monitoring());
+
+        // Test LatencyAware
+        let policy = PolicyFactory::create_from_config(&PolicyConfig::LatencyAware { decay: 0.5 });
+        assert_eq!(policy.name(), "latency_aware");
+        assert_eq!(policy.latency_decay(), Some(0.5));
 
         // Test CacheAware
         let policy = PolicyFactory::create_from_config(&PolicyConfig::CacheAware {
@@ -81,13 +154,30 @@ mod tests {
             balance_rel_threshold: 1.5,
             eviction_interval_secs: 30,
             max_tree_size: 1000,
+            cold_start_policy: ColdStartPolicy::default(),
         });
         assert_eq!(policy.name(), "cache_aware");
 
         // Test ConsistentHash
-        let policy =
-            PolicyFactory::create_from_config(&PolicyConfig::ConsistentHash { virtual_nodes: 160 });
+        let policy = PolicyFactory::create_from_config(&PolicyConfig::ConsistentHash {
+            virtual_nodes: 160,
+            weight_source: Some(WeightSource::Priority),
+            session_cookie: Default::default(),
+            overload_factor: Some(1.25),
+            hash_client_ip: true,
+        });
         assert_eq!(policy.name(), "consistent_hash");
+
+        // Test WeightedRoundRobin
+        let policy = PolicyFactory::create_from_config(&PolicyConfig::WeightedRoundRobin {
+            weight_source: WeightSource::Cost,
+        });
+        assert_eq!(policy.name(), "weighted_round_robin");
+        let weighted = policy
+            .as_any()
+            .downcast_ref::<WeightedRoundRobinPolicy>()
+            .unwrap();
+        assert_eq!(weighted.weight_source(), WeightSource::Cost);
     }
 
     #[test]
@@ -98,10 +188,25 @@ mod tests {
         assert!(PolicyFactory::create_by_name("RoundRobin").is_some());
         assert!(PolicyFactory::create_by_name("power_of_two").is_some());
         assert!(PolicyFactory::create_by_name("PowerOfTwo").is_some());
+        assert!(PolicyFactory::create_by_name("load_weighted_random").is_some());
+        assert!(PolicyFactory::create_by_name("LoadWeightedRandom").is_some());
+        assert!(PolicyFactory::create_by_name("latency_aware").is_some());
+        assert!(PolicyFactory::create_by_name("LatencyAware").is_some());
         assert!(PolicyFactory::create_by_name("cache_aware").is_some());
         assert!(PolicyFactory::create_by_name("CacheAware").is_some());
         assert!(PolicyFactory::create_by_name("consistent_hash").is_some());
         assert!(PolicyFactory::create_by_name("ConsistentHash").is_some());
+        assert!(PolicyFactory::create_by_name("weighted_round_robin").is_some());
+        assert!(PolicyFactory::create_by_name("WeightedRoundRobin").is_some());
+        assert!(PolicyFactory::create_by_name("header_route").is_some());
         assert!(PolicyFactory::create_by_name("unknown").is_none());
     }
+
+    #[test]
+    fn test_available_policies_are_creatable() {
+        for name in PolicyFactory::available_policies() {
+            let policy = PolicyFactory::create_by_name(name).unwrap();
+            assert_eq!(policy.name(), *name);
+        }
+    }
 }
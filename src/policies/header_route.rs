@@ -0,0 +1,306 @@
+//! Header-based routing policy
+//!
+//! Routes by explicit operator rules instead of hashing: the value of a
+//! configured request header picks a pool of workers, identified by a worker
+//! label, and a sub-policy selects a worker within that pool. With
+//! `X-Route-Tier: premium` routed to `tier=premium`, premium traffic only
+//! ever reaches the premium workers.
+
+use super::{
+    CacheAwarePolicy, ConsistentHashPolicy, LoadBalancingPolicy, RequestHeaders, RoundRobinPolicy,
+};
+use crate::core::Worker;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Header read when none is configured
+const DEFAULT_HEADER: &str = "x-route-tier";
+
+/// Worker label read when none is configured
+const DEFAULT_LABEL: &str = "tier";
+
+/// Header-based routing policy
+#[derive(Debug)]
+pub struct HeaderRoutePolicy {
+    /// Lowercase name of the header selecting the pool
+    header: String,
+    /// Worker label identifying the pools
+    label: String,
+    /// Header value to the label value of the pool serving it
+    routes: HashMap<String, String>,
+    /// Pool for requests without a routed header value (None = all workers)
+    default_route: Option<String>,
+    /// Selects a worker within the matched pool
+    sub_policy: Arc<dyn LoadBalancingPolicy>,
+}
+
+impl Default for HeaderRoutePolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeaderRoutePolicy {
+    /// Route on `X-Route-Tier` to the `tier` label with no rules yet,
+    /// round-robin within the pool
+    pub fn new() -> Self {
+        Self::with_config(
+            DEFAULT_HEADER,
+            DEFAULT_LABEL,
+            HashMap::new(),
+            None,
+            Arc::new(RoundRobinPolicy::new()),
+        )
+    }
+
+    pub fn with_config(
+        header: &str,
+        label: &str,
+        routes: HashMap<String, String>,
+        default_route: Option<String>,
+        sub_policy: Arc<dyn LoadBalancingPolicy>,
+    ) -> Self {
+        Self {
+            header: header.to_lowercase(),
+            label: label.to_string(),
+            routes,
+            default_route,
+            sub_policy,
+        }
+    }
+
+    /// Label value of the pool serving a request, None when any worker may
+    fn pool(&self, headers: Option<&RequestHeaders>) -> Option<&str> {
+        headers
+            .and_then(|headers| headers.get(&self.header))
+            .and_then(|value| self.routes.get(value.trim()))
+            .or(self.default_route.as_ref())
+            .map(String::as_str)
+    }
+}
+
+impl LoadBalancingPolicy for HeaderRoutePolicy {
+    fn select_worker_with_headers(
+        &self,
+        workers: &[Arc<dyn Worker>],
+        request_text: Option<&str>,
+        headers: Option<&RequestHeaders>,
+    ) -> Option<usize> {
+        let Some(pool) = self.pool(headers) else {
+            return self
+                .sub_policy
+                .select_worker_with_headers(workers, request_text, headers);
+        };
+
+        // A routed request never leaves its pool, even when the pool is empty
+        let required = [(self.label.clone(), Some(pool.to_string()))];
+        let indices: Vec<usize> = (0..workers.len())
+            .filter(|&i| workers[i].has_tags(&required))
+            .collect();
+        if indices.is_empty() {
+            debug!("No workers labelled {}={}", self.label, pool);
+            return None;
+        }
+        let pool_workers: Vec<Arc<dyn Worker>> =
+            indices.iter().map(|&i| Arc::clone(&workers[i])).collect();
+        let idx =
+            self.sub_policy
+                .select_worker_with_headers(&pool_workers, request_text, headers)?;
+        Some(indices[idx])
+    }
+
+    fn on_request_complete(&self, worker_url: &str, success: bool) {
+        self.sub_policy.on_request_complete(worker_url, success);
+    }
+
+    fn name(&self) -> &'static str {
+        "header_route"
+    }
+
+    fn needs_request_text(&self) -> bool {
+        self.sub_policy.needs_request_text()
+    }
+
+    fn needs_headers(&self) -> bool {
+        true
+    }
+
+    fn needs_load_monitoring(&self) -> bool {
+        self.sub_policy.needs_load_monitoring()
+    }
+
+    fn latency_decay(&self) -> Option<f64> {
+        self.sub_policy.latency_decay()
+    }
+
+    fn update_loads(&self, loads: &HashMap<String, isize>) {
+        self.sub_policy.update_loads(loads);
+    }
+
+    fn reset(&self) {
+        self.sub_policy.reset();
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_cache_aware(&self) -> Option<&CacheAwarePolicy> {
+        self.sub_policy.as_cache_aware()
+    }
+
+    fn as_consistent_hash(&self) -> Option<&ConsistentHashPolicy> {
+        self.sub_policy.as_consistent_hash()
+    }
+
+    fn requires_initialization(&self) -> bool {
+        self.sub_policy.requires_initialization()
+    }
+
+    fn init_workers(&self, workers: &[Arc<dyn Worker>]) {
+        self.sub_policy.init_workers(workers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{BasicWorker, WorkerType};
+
+    fn worker(url: &str, tier: Option<&str>) -> Arc<dyn Worker> {
+        let mut worker = BasicWorker::new(url.to_string(), WorkerType::Regular);
+        if let Some(tier) = tier {
+            worker = worker.with_labels(HashMap::from([("tier".to_string(), tier.to_string())]));
+        }
+        Arc::new(worker)
+    }
+
+    fn headers(tier: &str) -> RequestHeaders {
+        HashMap::from([("x-route-tier".to_string(), tier.to_string())])
+    }
+
+    fn policy(default_route: Option<&str>) -> HeaderRoutePolicy {
+        HeaderRoutePolicy::with_config(
+            "X-Route-Tier",
+            "tier",
+            HashMap::from([
+                ("premium".to_string(), "premium".to_string()),
+                ("gold".to_string(), "premium".to_string()),
+                ("batch".to_string(), "batch".to_string()),
+            ]),
+            default_route.map(str::to_string),
+            Arc::new(RoundRobinPolicy::new()),
+        )
+    }
+
+    #[test]
+    fn test_routed_header_selects_pool() {
+        let workers = vec![
+            worker("http://w1:8000", Some("standard")),
+            worker("http://w2:8000", Some("premium")),
+            worker("http://w3:8000", None),
+            worker("http://w4:8000", Some("premium")),
+        ];
+        let policy = policy(Some("standard"));
+
+        for value in ["premium", "gold"] {
+            for _ in 0..4 {
+                let idx = policy
+                    .select_worker_with_headers(&workers, None, Some(&headers(value)))
+                    .unwrap();
+                assert!(idx == 1 || idx == 3);
+            }
+        }
+
+        // Unrouted values and requests without the header use the default pool
+        assert_eq!(
+            policy.select_worker_with_headers(&workers, None, Some(&headers("free"))),
+            Some(0)
+        );
+        assert_eq!(
+            policy.select_worker_with_headers(&workers, None, None),
+            Some(0)
+        );
+
+        // A pool without workers does not spill over to other workers
+        assert_eq!(
+            policy.select_worker_with_headers(&workers, None, Some(&headers("batch"))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unrouted_request_uses_all_workers_without_default() {
+        let workers = vec![
+            worker("http://w1:8000", Some("premium")),
+            worker("http://w2:8000", None),
+        ];
+        let policy = policy(None);
+
+        let mut selected: Vec<usize> = (0..4)
+            .filter_map(|_| policy.select_worker_with_headers(&workers, None, None))
+            .collect();
+        selected.sort_unstable();
+        selected.dedup();
+        assert_eq!(selected, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_exposes_wrapped_policy() {
+        let wrap = |sub_policy: Arc<dyn LoadBalancingPolicy>| {
+            HeaderRoutePolicy::with_config("X-Route-Tier", "tier", HashMap::new(), None, sub_policy)
+        };
+
+        let cache_aware = wrap(Arc::new(CacheAwarePolicy::new()));
+        assert!(cache_aware.as_cache_aware().is_some());
+        assert!(cache_aware.as_consistent_hash().is_none());
+
+        let consistent_hash = wrap(Arc::new(ConsistentHashPolicy::new()));
+        assert!(consistent_hash.as_consistent_hash().is_some());
+        assert!(consistent_hash.as_cache_aware().is_none());
+
+        let round_robin = wrap(Arc::new(RoundRobinPolicy::new()));
+        assert!(round_robin.as_cache_aware().is_none());
+        assert!(round_robin.as_consistent_hash().is_none());
+    }
+
+    #[test]
+    fn test_cache_aware_pools_keep_each_others_prefixes() {
+        let workers = vec![
+            worker("http://w1:8000", Some("premium")),
+            worker("http://w2:8000", Some("batch")),
+            worker("http://w3:8000", Some("premium")),
+        ];
+        let policy = HeaderRoutePolicy::with_config(
+            "X-Route-Tier",
+            "tier",
+            HashMap::from([
+                ("premium".to_string(), "premium".to_string()),
+                ("batch".to_string(), "batch".to_string()),
+            ]),
+            None,
+            Arc::new(CacheAwarePolicy::new()),
+        );
+        policy.init_workers(&workers);
+
+        let text = Some("the same long prompt sent from both tiers");
+        let premium = policy
+            .select_worker_with_headers(&workers, text, Some(&headers("premium")))
+            .unwrap();
+        assert!(premium == 0 || premium == 2);
+
+        // The batch pool cannot use the premium worker's prefix, and must not
+        // evict it from the shared tree either
+        for _ in 0..3 {
+            assert_eq!(
+                policy.select_worker_with_headers(&workers, text, Some(&headers("batch"))),
+                Some(1)
+            );
+            assert_eq!(
+                policy.select_worker_with_headers(&workers, text, Some(&headers("premium"))),
+                Some(premium)
+            );
+        }
+    }
+}
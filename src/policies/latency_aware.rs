@@ -0,0 +1,169 @@
+//! Latency-aware load balancing policy
+
+use super::{get_healthy_worker_indices, LoadBalancingPolicy, RequestHeaders};
+use crate::core::Worker;
+use crate::metrics::RouterMetrics;
+use rand::Rng;
+use std::sync::Arc;
+
+/// Weight kept by the previous average when a new latency sample arrives
+pub const DEFAULT_LATENCY_DECAY: f64 = 0.8;
+
+/// Latency-aware selection policy
+///
+/// Routes to the healthy worker with the lowest moving average latency, which
+/// picks up throughput differences between workers (e.g. mixed GPU
+/// generations) that load counts alone cannot see. Workers without a sample
+/// yet are tried first so every worker gets measured; ties go to the less
+/// loaded worker.
+#[derive(Debug)]
+pub struct LatencyAwarePolicy {
+    decay: f64,
+}
+
+impl LatencyAwarePolicy {
+    pub fn new() -> Self {
+        Self::with_decay(DEFAULT_LATENCY_DECAY)
+    }
+
+    pub fn with_decay(decay: f64) -> Self {
+        Self { decay }
+    }
+}
+
+impl LoadBalancingPolicy for LatencyAwarePolicy {
+    fn select_worker_with_headers(
+        &self,
+        workers: &[Arc<dyn Worker>],
+        _request_text: Option<&str>,
+        _headers: Option<&RequestHeaders>,
+    ) -> Option<usize> {
+        let healthy_indices = get_healthy_worker_indices(workers);
+
+        if healthy_indices.is_empty() {
+            return None;
+        }
+
+        let unmeasured: Vec<usize> = healthy_indices
+            .iter()
+            .copied()
+            .filter(|&idx| workers[idx].ewma_latency().is_none())
+            .collect();
+
+        let selected_idx = if !unmeasured.is_empty() {
+            unmeasured[rand::rng().random_range(0..unmeasured.len())]
+        } else {
+            healthy_indices.into_iter().min_by(|&a, &b| {
+                let (wa, wb) = (&workers[a], &workers[b]);
+                wa.ewma_latency()
+                    .cmp(&wb.ewma_latency())
+                    .then_with(|| wa.load().cmp(&wb.load()))
+            })?
+        };
+
+        workers[selected_idx].increment_processed();
+        RouterMetrics::record_processed_request(workers[selected_idx].url());
+        RouterMetrics::record_policy_decision(self.name(), workers[selected_idx].url());
+
+        Some(selected_idx)
+    }
+
+    fn name(&self) -> &'static str {
+        "latency_aware"
+    }
+
+    fn latency_decay(&self) -> Option<f64> {
+        Some(self.decay)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl Default for LatencyAwarePolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{BasicWorker, WorkerType};
+    use std::time::Duration;
+
+    fn workers(urls: &[&str]) -> Vec<Arc<dyn Worker>> {
+        urls.iter()
+            .map(|url| {
+                Arc::new(BasicWorker::new(url.to_string(), WorkerType::Regular)) as Arc<dyn Worker>
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_prefers_lowest_latency() {
+        let policy = LatencyAwarePolicy::new();
+        let workers = workers(&["http://w1:8000", "http://w2:8000", "http://w3:8000"]);
+        workers[0].record_latency(Duration::from_millis(300), 0.8);
+        workers[1].record_latency(Duration::from_millis(100), 0.8);
+        workers[2].record_latency(Duration::from_millis(200), 0.8);
+
+        for _ in 0..10 {
+            assert_eq!(policy.select_worker(&workers, None), Some(1));
+        }
+
+        // Enough slow samples move traffic elsewhere
+        for _ in 0..20 {
+            workers[1].record_latency(Duration::from_secs(1), 0.8);
+        }
+        assert_eq!(policy.select_worker(&workers, None), Some(2));
+    }
+
+    #[test]
+    fn test_unmeasured_workers_tried_first() {
+        let policy = LatencyAwarePolicy::new();
+        let workers = workers(&["http://w1:8000", "http://w2:8000"]);
+        workers[0].record_latency(Duration::from_millis(10), 0.8);
+
+        assert_eq!(policy.select_worker(&workers, None), Some(1));
+    }
+
+    #[test]
+    fn test_ties_go_to_less_loaded_worker() {
+        let policy = LatencyAwarePolicy::new();
+        let workers = workers(&["http://w1:8000", "http://w2:8000"]);
+        for worker in &workers {
+            worker.record_latency(Duration::from_millis(100), 0.8);
+        }
+        workers[0].increment_load();
+
+        assert_eq!(policy.select_worker(&workers, None), Some(1));
+    }
+
+    #[test]
+    fn test_skips_unhealthy_workers() {
+        let policy = LatencyAwarePolicy::new();
+        let workers = workers(&["http://w1:8000", "http://w2:8000"]);
+        workers[0].record_latency(Duration::from_millis(10), 0.8);
+        workers[1].record_latency(Duration::from_millis(500), 0.8);
+        workers[0].set_healthy(false);
+
+        assert_eq!(policy.select_worker(&workers, None), Some(1));
+
+        workers[1].set_healthy(false);
+        assert_eq!(policy.select_worker(&workers, None), None);
+    }
+
+    #[test]
+    fn test_reports_configured_decay() {
+        assert_eq!(
+            LatencyAwarePolicy::new().latency_decay(),
+            Some(DEFAULT_LATENCY_DECAY)
+        );
+        assert_eq!(
+            LatencyAwarePolicy::with_decay(0.5).latency_decay(),
+            Some(0.5)
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Load-weighted random load balancing policy
+
+use super::{get_healthy_worker_indices, LoadBalancingPolicy, RequestHeaders};
+use crate::core::Worker;
+use crate::metrics::RouterMetrics;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Added to every load so idle workers get a finite weight
+const LOAD_EPSILON: f64 = 1.0;
+
+/// Load-weighted random selection policy
+///
+/// Picks a healthy worker at random with probability inversely proportional
+/// to its load. Unlike power-of-two this never completely starves a busy
+/// worker, which keeps load reports flowing and smooths out stale readings.
+#[derive(Debug)]
+pub struct LoadWeightedRandomPolicy {
+    /// Cached load information from external monitoring
+    cached_loads: RwLock<HashMap<String, isize>>,
+}
+
+impl LoadWeightedRandomPolicy {
+    pub fn new() -> Self {
+        Self {
+            cached_loads: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn get_worker_load(&self, worker: &dyn Worker) -> isize {
+        // First check cached loads (from external monitoring)
+        if let Ok(loads) = self.cached_loads.read() {
+            if let Some(&load) = loads.get(worker.url()) {
+                return load;
+            }
+        }
+
+        // Fall back to local load counter
+        worker.load() as isize
+    }
+
+    /// Selection weight for a worker; failed load reads (negative) count as idle
+    fn worker_weight(&self, worker: &dyn Worker) -> f64 {
+        1.0 / (self.get_worker_load(worker).max(0) as f64 + LOAD_EPSILON)
+    }
+}
+
+impl LoadBalancingPolicy for LoadWeightedRandomPolicy {
+    fn select_worker_with_headers(
+        &self,
+        workers: &[Arc<dyn Worker>],
+        _request_text: Option<&str>,
+        _headers: Option<&RequestHeaders>,
+    ) -> Option<usize> {
+        let healthy_indices = get_healthy_worker_indices(workers);
+
+        if healthy_indices.is_empty() {
+            return None;
+        }
+
+        let weights: Vec<f64> = healthy_indices
+            .iter()
+            .map(|&idx| self.worker_weight(workers[idx].as_ref()))
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        let mut point = rand::rng().random_range(0.0..total);
+        let mut selected_idx = *healthy_indices.last()?;
+        for (&idx, &weight) in healthy_indices.iter().zip(weights.iter()) {
+            if point < weight {
+                selected_idx = idx;
+                break;
+            }
+            point -= weight;
+        }
+
+        workers[selected_idx].increment_processed();
+        RouterMetrics::record_processed_request(workers[selected_idx].url());
+        RouterMetrics::record_policy_decision(self.name(), workers[selected_idx].url());
+
+        Some(selected_idx)
+    }
+
+    fn name(&self) -> &'static str {
+        "load_weighted_random"
+    }
+
+    fn needs_load_monitoring(&self) -> bool {
+        true
+    }
+
+    fn update_loads(&self, loads: &HashMap<String, isize>) {
+        if let Ok(mut cached) = self.cached_loads.write() {
+            *cached = loads.clone();
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl Default for LoadWeightedRandomPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{BasicWorker, WorkerType};
+
+    fn workers(urls: &[&str]) -> Vec<Arc<dyn Worker>> {
+        urls.iter()
+            .map(|url| {
+                Arc::new(BasicWorker::new(url.to_string(), WorkerType::Regular)) as Arc<dyn Worker>
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_prefers_less_loaded_workers() {
+        let policy = LoadWeightedRandomPolicy::new();
+        let workers = workers(&["http://w1:8000", "http://w2:8000"]);
+
+        // Weights are 1/10 and 1/1, so w2 should get ~90% of the traffic
+        let mut loads = HashMap::new();
+        loads.insert("http://w1:8000".to_string(), 9);
+        loads.insert("http://w2:8000".to_string(), 0);
+        policy.update_loads(&loads);
+
+        let mut counts = [0; 2];
+        for _ in 0..1000 {
+            counts[policy.select_worker(&workers, None).unwrap()] += 1;
+        }
+
+        assert!(counts[1] > 800, "counts: {:?}", counts);
+        // The busy worker still receives some traffic
+        assert!(counts[0] > 0, "counts: {:?}", counts);
+    }
+
+    #[test]
+    fn test_falls_back_to_local_load() {
+        let policy = LoadWeightedRandomPolicy::new();
+        let workers = workers(&["http://w1:8000", "http://w2:8000"]);
+        for _ in 0..19 {
+            workers[0].increment_load();
+        }
+
+        let mut counts = [0; 2];
+        for _ in 0..1000 {
+            counts[policy.select_worker(&workers, None).unwrap()] += 1;
+        }
+
+        assert!(counts[1] > counts[0] * 5, "counts: {:?}", counts);
+    }
+
+    #[test]
+    fn test_negative_loads_treated_as_idle() {
+        let policy = LoadWeightedRandomPolicy::new();
+        let workers = workers(&["http://w1:8000"]);
+
+        let mut loads = HashMap::new();
+        loads.insert("http://w1:8000".to_string(), -1);
+        policy.update_loads(&loads);
+
+        assert_eq!(policy.worker_weight(workers[0].as_ref()), 1.0);
+        assert_eq!(policy.select_worker(&workers, None), Some(0));
+    }
+
+    #[test]
+    fn test_skips_unhealthy_workers() {
+        let policy = LoadWeightedRandomPolicy::new();
+        let workers = workers(&["http://w1:8000", "http://w2:8000"]);
+        workers[0].set_healthy(false);
+
+        for _ in 0..20 {
+            assert_eq!(policy.select_worker(&workers, None), Some(1));
+        }
+
+        workers[1].set_healthy(false);
+        assert_eq!(policy.select_worker(&workers, None), None);
+    }
+
+    #[test]
+    fn test_needs_load_monitoring() {
+        assert!(LoadWeightedRandomPolicy::new().needs_load_monitoring());
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides a unified abstraction for routing policies that work
 //! across both regular and prefill-decode (PD) routing modes.
 
+use crate::config::ColdStartPolicy;
 use crate::core::Worker;
 use std::collections::HashMap;
 use std::fmt::Debug;
@@ -11,18 +12,26 @@ use std::sync::Arc;
 mod cache_aware;
 mod consistent_hash;
 mod factory;
+mod header_route;
+mod latency_aware;
+mod load_weighted_random;
 mod power_of_two;
 mod random;
 mod registry;
 mod round_robin;
+mod weighted_round_robin;
 
 pub use cache_aware::CacheAwarePolicy;
-pub use consistent_hash::ConsistentHashPolicy;
+pub use consistent_hash::{ConsistentHashPolicy, MintedSession};
 pub use factory::PolicyFactory;
+pub use header_route::HeaderRoutePolicy;
+pub use latency_aware::{LatencyAwarePolicy, DEFAULT_LATENCY_DECAY};
+pub use load_weighted_random::LoadWeightedRandomPolicy;
 pub use power_of_two::PowerOfTwoPolicy;
 pub use random::RandomPolicy;
 pub use registry::PolicyRegistry;
 pub use round_robin::RoundRobinPolicy;
+pub use weighted_round_robin::WeightedRoundRobinPolicy;
 
 /// HTTP headers passed to policies for routing decisions
 /// Key is lowercase header name, value is header value
@@ -57,6 +66,21 @@ pub trait LoadBalancingPolicy: Send + Sync + Debug {
         headers: Option<&RequestHeaders>,
     ) -> Option<usize>;
 
+    /// Select a single worker using the request's token ids when available
+    ///
+    /// Callers with a tokenizer (e.g., gRPC mode) can pass token ids so prefix-aware
+    /// policies match on token boundaries instead of characters. Default implementation
+    /// ignores the tokens and falls back to text-based selection.
+    fn select_worker_with_tokens(
+        &self,
+        workers: &[Arc<dyn Worker>],
+        request_text: Option<&str>,
+        _token_ids: Option<&[u32]>,
+        headers: Option<&RequestHeaders>,
+    ) -> Option<usize> {
+        self.select_worker_with_headers(workers, request_text, headers)
+    }
+
     /// Select a pair of workers (prefill and decode) for PD routing
     ///
     /// Returns indices of (prefill_worker, decode_worker) from their respective arrays.
@@ -106,6 +130,22 @@ pub trait LoadBalancingPolicy: Send + Sync + Debug {
         false // Default: most policies don't need headers
     }
 
+    /// Check if this policy routes on worker load reports
+    ///
+    /// The background load monitor only polls workers while a policy needs it.
+    fn needs_load_monitoring(&self) -> bool {
+        false // Default: most policies don't use load information
+    }
+
+    /// Weight kept by the previous average when folding request latencies
+    /// into a worker's moving average
+    ///
+    /// Latency-aware policies return their configured decay; routers use a
+    /// default for everything else.
+    fn latency_decay(&self) -> Option<f64> {
+        None
+    }
+
     /// Update worker load information
     ///
     /// This is called periodically with current load information for load-aware policies.
@@ -123,6 +163,18 @@ pub trait LoadBalancingPolicy: Send + Sync + Debug {
     /// Get as Any for downcasting
     fn as_any(&self) -> &dyn std::any::Any;
 
+    /// The cache-aware policy doing the selection, this one or a wrapped
+    /// sub-policy, whose prefix tree follows worker changes
+    fn as_cache_aware(&self) -> Option<&CacheAwarePolicy> {
+        self.as_any().downcast_ref()
+    }
+
+    /// The consistent hashing policy doing the selection, this one or a
+    /// wrapped sub-policy
+    fn as_consistent_hash(&self) -> Option<&ConsistentHashPolicy> {
+        self.as_any().downcast_ref()
+    }
+
     /// Returns true if this policy requires init_workers() to be called before use.
     /// Override to return true for stateful policies like cache-aware routing.
     fn requires_initialization(&self) -> bool {
@@ -145,6 +197,7 @@ pub struct CacheAwareConfig {
     pub balance_rel_threshold: f32,
     pub eviction_interval_secs: u64,
     pub max_tree_size: usize,
+    pub cold_start_policy: ColdStartPolicy,
 }
 
 impl Default for CacheAwareConfig {
@@ -155,16 +208,18 @@ impl Default for CacheAwareConfig {
             balance_rel_threshold: 1.1,
             eviction_interval_secs: 30,
             max_tree_size: 10000,
+            cold_start_policy: ColdStartPolicy::default(),
         }
     }
 }
 
-/// Helper function to filter healthy workers and return their indices
+/// Helper function to filter available workers (healthy, not draining, circuit
+/// not open) and return their indices
 pub(crate) fn get_healthy_worker_indices(workers: &[Arc<dyn Worker>]) -> Vec<usize> {
     workers
         .iter()
         .enumerate()
-        .filter(|(_, w)| w.is_healthy() && w.circuit_breaker().can_execute())
+        .filter(|(_, w)| w.is_available())
         .map(|(idx, _)| idx)
         .collect()
 }
@@ -10,7 +10,9 @@ use tracing::info;
 
 /// Power-of-two choices policy
 ///
-/// Randomly selects two workers and routes to the one with lower load.
+/// Randomly selects two distinct workers and routes to the one with lower load.
+/// Equal loads are broken by the processed request count, then at random, so
+/// low or coarsely reported load does not keep favoring one worker.
 /// This provides good load distribution with minimal coordination overhead.
 #[derive(Debug)]
 pub struct PowerOfTwoPolicy {
@@ -55,14 +57,13 @@ impl LoadBalancingPolicy for PowerOfTwoPolicy {
             return Some(healthy_indices[0]);
         }
 
-        // Select two random workers
+        // Select two distinct random workers: draw the second from the
+        // remaining n - 1 slots and skip over the first
         let mut rng = rand::rng();
         let idx1 = rng.random_range(0..healthy_indices.len());
-        let mut idx2 = rng.random_range(0..healthy_indices.len());
-
-        // Ensure we pick two different workers
-        while idx2 == idx1 {
-            idx2 = rng.random_range(0..healthy_indices.len());
+        let mut idx2 = rng.random_range(0..healthy_indices.len() - 1);
+        if idx2 >= idx1 {
+            idx2 += 1;
         }
 
         let worker_idx1 = healthy_indices[idx1];
@@ -72,11 +73,13 @@ impl LoadBalancingPolicy for PowerOfTwoPolicy {
         let load1 = self.get_worker_load(workers[worker_idx1].as_ref());
         let load2 = self.get_worker_load(workers[worker_idx2].as_ref());
 
-        // Log selection for debugging
-        let selected_idx = if load1 <= load2 {
-            worker_idx1
-        } else {
-            worker_idx2
+        let processed1 = workers[worker_idx1].processed_requests();
+        let processed2 = workers[worker_idx2].processed_requests();
+        let selected_idx = match load1.cmp(&load2).then(processed1.cmp(&processed2)) {
+            std::cmp::Ordering::Less => worker_idx1,
+            std::cmp::Ordering::Greater => worker_idx2,
+            std::cmp::Ordering::Equal if rng.random_bool(0.5) => worker_idx1,
+            std::cmp::Ordering::Equal => worker_idx2,
         };
 
         info!(
@@ -100,6 +103,10 @@ impl LoadBalancingPolicy for PowerOfTwoPolicy {
         "power_of_two"
     }
 
+    fn needs_load_monitoring(&self) -> bool {
+        true
+    }
+
     fn update_loads(&self, loads: &HashMap<String, isize>) {
         if let Ok(mut cached) = self.cached_loads.write() {
             *cached = loads.clone();
@@ -188,6 +195,29 @@ mod tests {
         assert!(w2_selected > 35); // Should win most of the time
     }
 
+    #[test]
+    fn test_power_of_two_breaks_ties_by_processed_count() {
+        let policy = PowerOfTwoPolicy::new();
+        let workers: Vec<Arc<dyn Worker>> = vec![
+            Arc::new(BasicWorker::new(
+                "http://w1:8000".to_string(),
+                WorkerType::Regular,
+            )),
+            Arc::new(BasicWorker::new(
+                "http://w2:8000".to_string(),
+                WorkerType::Regular,
+            )),
+        ];
+
+        // With equal load every pick goes to the worker that has served fewer requests
+        let mut selected_counts = [0; 2];
+        for _ in 0..100 {
+            let idx = policy.select_worker(&workers, None).unwrap();
+            selected_counts[idx] += 1;
+        }
+        assert_eq!(selected_counts, [50, 50]);
+    }
+
     #[test]
     fn test_power_of_two_single_worker() {
         let policy = PowerOfTwoPolicy::new();
@@ -5,10 +5,12 @@
 /// All subsequent workers of the same model use the established policy.
 /// When the last worker of a model is removed, the policy mapping is cleaned up.
 use super::{
-    CacheAwareConfig, CacheAwarePolicy, ConsistentHashPolicy, LoadBalancingPolicy,
-    PowerOfTwoPolicy, RandomPolicy, RoundRobinPolicy,
+    CacheAwareConfig, CacheAwarePolicy, ConsistentHashPolicy, HeaderRoutePolicy,
+    LatencyAwarePolicy, LoadBalancingPolicy, LoadWeightedRandomPolicy, PowerOfTwoPolicy,
+    RandomPolicy, RoundRobinPolicy, WeightedRoundRobinPolicy,
 };
 use crate::config::types::PolicyConfig;
+use crate::core::Worker;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use tracing::{debug, info, warn};
@@ -22,14 +24,17 @@ pub struct PolicyRegistry {
     /// Model ID -> Worker count for cleanup tracking
     model_worker_counts: Arc<RwLock<HashMap<String, usize>>>,
 
-    /// Default policy instance (cached)
-    default_policy: Arc<dyn LoadBalancingPolicy>,
+    /// Default policy instance (cached, replaceable at runtime)
+    default_policy: Arc<RwLock<Arc<dyn LoadBalancingPolicy>>>,
 
     /// Prefill policy for PD mode
     prefill_policy: Arc<RwLock<Option<Arc<dyn LoadBalancingPolicy>>>>,
 
     /// Decode policy for PD mode
     decode_policy: Arc<RwLock<Option<Arc<dyn LoadBalancingPolicy>>>>,
+
+    /// Model ID -> Policy configured at startup, used when the model's first worker is added
+    model_policy_configs: Arc<HashMap<String, PolicyConfig>>,
 }
 
 impl PolicyRegistry {
@@ -40,12 +45,22 @@ impl PolicyRegistry {
         Self {
             model_policies: Arc::new(RwLock::new(HashMap::new())),
             model_worker_counts: Arc::new(RwLock::new(HashMap::new())),
-            default_policy,
+            default_policy: Arc::new(RwLock::new(default_policy)),
             prefill_policy: Arc::new(RwLock::new(None)),
             decode_policy: Arc::new(RwLock::new(None)),
+            model_policy_configs: Arc::new(HashMap::new()),
         }
     }
 
+    /// Use the given policies for these models instead of the default
+    ///
+    /// Each model gets its own policy instance when its first worker is added,
+    /// taking precedence over any policy hint the worker carries.
+    pub fn with_model_policies(mut self, model_policies: HashMap<String, PolicyConfig>) -> Self {
+        self.model_policy_configs = Arc::new(model_policies);
+        self
+    }
+
     /// Called when a worker is added
     /// Returns the policy that should be used for this worker's model
     pub fn on_worker_added(
@@ -139,7 +154,7 @@ impl PolicyRegistry {
 
     /// Get the default policy
     pub fn get_default_policy(&self) -> Arc<dyn LoadBalancingPolicy> {
-        Arc::clone(&self.default_policy)
+        Arc::clone(&self.default_policy.read().unwrap())
     }
 
     /// Get policy for a model, or default if not found
@@ -154,15 +169,21 @@ impl PolicyRegistry {
         model_id: &str,
         policy_hint: Option<&str>,
     ) -> Arc<dyn LoadBalancingPolicy> {
-        // 1. Check policy hint from worker
+        // 1. Check policy configured for this model
+        if let Some(config) = self.model_policy_configs.get(model_id) {
+            debug!("Using configured policy for model {}", model_id);
+            return Self::create_policy_from_config(config);
+        }
+
+        // 2. Check policy hint from worker
         if let Some(policy_type) = policy_hint {
             debug!("Using policy hint '{}' for model {}", policy_type, model_id);
             return self.create_policy_from_type(policy_type);
         }
 
-        // 2. Use default policy
+        // 3. Use default policy
         debug!("Using default policy for model {}", model_id);
-        Arc::clone(&self.default_policy)
+        self.get_default_policy()
     }
 
     /// Create a policy from a type string
@@ -172,9 +193,13 @@ impl PolicyRegistry {
             "random" => Arc::new(RandomPolicy::new()),
             "cache_aware" => Arc::new(CacheAwarePolicy::new()),
             "power_of_two" => Arc::new(PowerOfTwoPolicy::new()),
+            "load_weighted_random" => Arc::new(LoadWeightedRandomPolicy::new()),
+            "latency_aware" => Arc::new(LatencyAwarePolicy::new()),
+            "weighted_round_robin" => Arc::new(WeightedRoundRobinPolicy::new()),
+            "header_route" => Arc::new(HeaderRoutePolicy::new()),
             _ => {
                 warn!("Unknown policy type '{}', using default", policy_type);
-                Arc::clone(&self.default_policy)
+                self.get_default_policy()
             }
         }
     }
@@ -190,6 +215,7 @@ impl PolicyRegistry {
                 balance_rel_threshold,
                 eviction_interval_secs,
                 max_tree_size,
+                cold_start_policy,
             } => {
                 let cache_config = CacheAwareConfig {
                     cache_threshold: *cache_threshold,
@@ -197,11 +223,117 @@ impl PolicyRegistry {
                     balance_rel_threshold: *balance_rel_threshold,
                     eviction_interval_secs: *eviction_interval_secs,
                     max_tree_size: *max_tree_size,
+                    cold_start_policy: *cold_start_policy,
                 };
                 Arc::new(CacheAwarePolicy::with_config(cache_config))
             }
             PolicyConfig::PowerOfTwo { .. } => Arc::new(PowerOfTwoPolicy::new()),
-            PolicyConfig::ConsistentHash { .. } => Arc::new(ConsistentHashPolicy::new()),
+            PolicyConfig::LoadWeightedRandom { .. } => Arc::new(LoadWeightedRandomPolicy::new()),
+            PolicyConfig::LatencyAware { decay } => {
+                Arc::new(LatencyAwarePolicy::with_decay(*decay))
+            }
+            PolicyConfig::ConsistentHash {
+                virtual_nodes,
+                weight_source,
+                session_cookie,
+                overload_factor,
+                hash_client_ip,
+            } => Arc::new(
+                ConsistentHashPolicy::with_config(
+                    *virtual_nodes,
+                    *weight_source,
+                    session_cookie.clone(),
+                )
+                .with_overload_factor(*overload_factor)
+                .with_client_ip_hashing(*hash_client_ip),
+            ),
+            PolicyConfig::WeightedRoundRobin { weight_source } => {
+                Arc::new(WeightedRoundRobinPolicy::with_weight_source(*weight_source))
+            }
+            PolicyConfig::HeaderRoute {
+                header,
+                label,
+                routes,
+                default_route,
+                sub_policy,
+            } => Arc::new(HeaderRoutePolicy::with_config(
+                header,
+                label,
+                routes.clone(),
+                default_route.clone(),
+                Self::create_policy_from_config(sub_policy),
+            )),
+        }
+    }
+
+    /// Replace the policy used by a model at runtime
+    ///
+    /// The new policy is reset and, if stateful, initialized with `workers`
+    /// before it becomes visible. In-flight requests keep the instance they
+    /// already hold; subsequent lookups see the replacement.
+    /// Returns the previous policy for the model, if any.
+    pub fn replace_policy(
+        &self,
+        model_id: &str,
+        policy: Arc<dyn LoadBalancingPolicy>,
+        workers: &[Arc<dyn Worker>],
+    ) -> Option<Arc<dyn LoadBalancingPolicy>> {
+        Self::prepare_policy(&policy, workers);
+
+        let previous = self
+            .model_policies
+            .write()
+            .unwrap()
+            .insert(model_id.to_string(), Arc::clone(&policy));
+
+        info!(
+            "Replaced policy for model {}: {} -> {}",
+            model_id,
+            previous.as_ref().map(|p| p.name()).unwrap_or("none"),
+            policy.name()
+        );
+        previous
+    }
+
+    /// Replace the default policy at runtime
+    ///
+    /// Models that were still sharing the previous default instance are moved
+    /// to the new one; models with an explicitly assigned policy keep it.
+    /// Returns the previous default policy.
+    pub fn replace_default_policy(
+        &self,
+        policy: Arc<dyn LoadBalancingPolicy>,
+        workers: &[Arc<dyn Worker>],
+    ) -> Arc<dyn LoadBalancingPolicy> {
+        Self::prepare_policy(&policy, workers);
+
+        let previous = {
+            let mut default_policy = self.default_policy.write().unwrap();
+            std::mem::replace(&mut *default_policy, Arc::clone(&policy))
+        };
+
+        {
+            let mut policies = self.model_policies.write().unwrap();
+            for model_policy in policies.values_mut() {
+                if Arc::ptr_eq(model_policy, &previous) {
+                    *model_policy = Arc::clone(&policy);
+                }
+            }
+        }
+
+        info!(
+            "Replaced default policy: {} -> {}",
+            previous.name(),
+            policy.name()
+        );
+        previous
+    }
+
+    /// Reset a policy and initialize it with workers if it requires it
+    fn prepare_policy(policy: &Arc<dyn LoadBalancingPolicy>, workers: &[Arc<dyn Worker>]) {
+        policy.reset();
+        if policy.requires_initialization() {
+            policy.init_workers(workers);
         }
     }
 
@@ -214,6 +346,45 @@ impl PolicyRegistry {
             .collect()
     }
 
+    /// Distinct registered policies that route on worker load reports
+    ///
+    /// Covers the default, per-model, and PD prefill/decode policies. Load
+    /// monitors call this on every tick so runtime policy swaps are picked up.
+    pub fn load_monitored_policies(&self) -> Vec<Arc<dyn LoadBalancingPolicy>> {
+        self.distinct_policies()
+            .into_iter()
+            .filter(|policy| policy.needs_load_monitoring())
+            .collect()
+    }
+
+    /// Clear the prefix trees of every registered cache-aware policy
+    ///
+    /// Called when the workers' caches are flushed so the router's view of
+    /// what each worker has cached matches what it actually holds.
+    pub fn clear_cache_trees(&self) {
+        for policy in self.distinct_policies() {
+            if let Some(cache_aware) = policy.as_cache_aware() {
+                cache_aware.clear_tree();
+            }
+        }
+    }
+
+    /// Default, per-model, and PD policies, each shared instance listed once
+    fn distinct_policies(&self) -> Vec<Arc<dyn LoadBalancingPolicy>> {
+        let mut candidates = vec![self.get_default_policy()];
+        candidates.extend(self.model_policies.read().unwrap().values().cloned());
+        candidates.extend(self.prefill_policy.read().unwrap().iter().cloned());
+        candidates.extend(self.decode_policy.read().unwrap().iter().cloned());
+
+        let mut policies: Vec<Arc<dyn LoadBalancingPolicy>> = Vec::new();
+        for policy in candidates {
+            if !policies.iter().any(|p| Arc::ptr_eq(p, &policy)) {
+                policies.push(policy);
+            }
+        }
+        policies
+    }
+
     /// Get worker counts per model
     pub fn get_worker_counts(&self) -> HashMap<String, usize> {
         self.model_worker_counts.read().unwrap().clone()
@@ -263,7 +434,7 @@ impl std::fmt::Debug for PolicyRegistry {
         f.debug_struct("PolicyRegistry")
             .field("model_policies", &self.model_policies)
             .field("model_worker_counts", &self.model_worker_counts)
-            .field("default_policy", &self.default_policy.name())
+            .field("default_policy", &self.get_default_policy().name())
             .finish()
     }
 }
@@ -319,6 +490,99 @@ mod tests {
         assert_eq!(registry.get_worker_counts().get("llama-3"), None);
     }
 
+    #[test]
+    fn test_configured_model_policies() {
+        let registry = PolicyRegistry::new(PolicyConfig::RoundRobin).with_model_policies(
+            [
+                ("rag-model".to_string(), PolicyConfig::Random),
+                (
+                    "chat-model".to_string(),
+                    PolicyConfig::LatencyAware { decay: 0.5 },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        // Configured policies win over worker hints
+        assert_eq!(
+            registry
+                .on_worker_added("rag-model", Some("cache_aware"))
+                .name(),
+            "random"
+        );
+        registry.on_worker_added("chat-model", None);
+        assert_eq!(
+            registry.get_policy_or_default("chat-model").name(),
+            "latency_aware"
+        );
+        assert_eq!(
+            registry.on_worker_added("other-model", None).name(),
+            "round_robin"
+        );
+
+        // The configuration outlives the model's workers
+        registry.on_worker_removed("rag-model");
+        assert!(registry.get_policy("rag-model").is_none());
+        assert_eq!(registry.on_worker_added("rag-model", None).name(), "random");
+    }
+
+    #[test]
+    fn test_replace_policy() {
+        let registry = PolicyRegistry::new(PolicyConfig::RoundRobin);
+        registry.on_worker_added("llama-3", None);
+
+        let previous = registry.replace_policy("llama-3", Arc::new(RandomPolicy::new()), &[]);
+        assert_eq!(previous.unwrap().name(), "round_robin");
+        assert_eq!(registry.get_policy("llama-3").unwrap().name(), "random");
+
+        // Default is untouched by a per-model swap
+        assert_eq!(registry.get_default_policy().name(), "round_robin");
+
+        // Worker bookkeeping still cleans up the replaced mapping
+        registry.on_worker_removed("llama-3");
+        assert!(registry.get_policy("llama-3").is_none());
+    }
+
+    #[test]
+    fn test_replace_default_policy() {
+        let registry = PolicyRegistry::new(PolicyConfig::RoundRobin);
+        registry.on_worker_added("shares-default", None);
+        registry.on_worker_added("explicit", Some("random"));
+
+        let previous = registry.replace_default_policy(Arc::new(PowerOfTwoPolicy::new()), &[]);
+        assert_eq!(previous.name(), "round_robin");
+        assert_eq!(registry.get_default_policy().name(), "power_of_two");
+
+        // Models on the old default follow the swap, explicit ones do not
+        let mappings = registry.get_all_mappings();
+        assert_eq!(mappings.get("shares-default").unwrap(), "power_of_two");
+        assert_eq!(mappings.get("explicit").unwrap(), "random");
+
+        // New models pick up the new default
+        let policy = registry.on_worker_added("new-model", None);
+        assert_eq!(policy.name(), "power_of_two");
+    }
+
+    #[test]
+    fn test_load_monitored_policies() {
+        let registry = PolicyRegistry::new(PolicyConfig::RoundRobin);
+        registry.on_worker_added("shares-default", None);
+        assert!(registry.load_monitored_policies().is_empty());
+
+        // A per-model load-aware policy is enough to need monitoring
+        registry.on_worker_added("balanced", Some("power_of_two"));
+        let policies = registry.load_monitored_policies();
+        assert_eq!(policies.len(), 1);
+        assert_eq!(policies[0].name(), "power_of_two");
+
+        // PD policies count too, and shared instances are reported once
+        let decode: Arc<dyn LoadBalancingPolicy> = Arc::new(PowerOfTwoPolicy::new());
+        registry.set_prefill_policy(Arc::clone(&decode));
+        registry.set_decode_policy(decode);
+        assert_eq!(registry.load_monitored_policies().len(), 2);
+    }
+
     #[test]
     fn test_default_policy() {
         let registry = PolicyRegistry::new(PolicyConfig::RoundRobin);
@@ -0,0 +1,270 @@
+//! Weighted round-robin load balancing policy
+
+use super::{get_healthy_worker_indices, LoadBalancingPolicy, RequestHeaders};
+use crate::config::WeightSource;
+use crate::core::Worker;
+use crate::metrics::RouterMetrics;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Weighted round-robin selection policy
+///
+/// Uses smooth weighted round-robin: every selection adds each healthy worker's
+/// weight to its running score, picks the highest score, and subtracts the total
+/// weight from the winner. Over a full cycle each worker receives traffic in
+/// proportion to its weight, and heavy workers are interleaved rather than
+/// picked in bursts.
+///
+/// Weights are read from the workers on every selection, so label changes and
+/// worker additions/removals take effect immediately.
+#[derive(Debug, Default)]
+pub struct WeightedRoundRobinPolicy {
+    weight_source: WeightSource,
+    /// Running scores keyed by worker URL
+    current_weights: Mutex<HashMap<String, f64>>,
+}
+
+impl WeightedRoundRobinPolicy {
+    pub fn new() -> Self {
+        Self::with_weight_source(WeightSource::default())
+    }
+
+    pub fn with_weight_source(weight_source: WeightSource) -> Self {
+        Self {
+            weight_source,
+            current_weights: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn weight_source(&self) -> WeightSource {
+        self.weight_source
+    }
+
+    /// Weight for a worker; non-positive weights are treated as zero
+    fn worker_weight(&self, worker: &dyn Worker) -> f64 {
+        let weight = match self.weight_source {
+            WeightSource::Priority => worker.priority() as f64,
+            WeightSource::Cost => worker.cost() as f64,
+        };
+        if weight.is_finite() && weight > 0.0 {
+            weight
+        } else {
+            0.0
+        }
+    }
+}
+
+impl LoadBalancingPolicy for WeightedRoundRobinPolicy {
+    fn select_worker_with_headers(
+        &self,
+        workers: &[Arc<dyn Worker>],
+        _request_text: Option<&str>,
+        _headers: Option<&RequestHeaders>,
+    ) -> Option<usize> {
+        let healthy_indices = get_healthy_worker_indices(workers);
+
+        if healthy_indices.is_empty() {
+            return None;
+        }
+
+        let mut weights: Vec<f64> = healthy_indices
+            .iter()
+            .map(|&idx| self.worker_weight(workers[idx].as_ref()))
+            .collect();
+        let mut total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            // No usable weights: treat all healthy workers equally
+            weights.iter_mut().for_each(|w| *w = 1.0);
+            total = weights.len() as f64;
+        }
+
+        let selected_idx = {
+            let mut current = self.current_weights.lock().unwrap();
+
+            // Drop state for workers that are gone or currently ineligible
+            current.retain(|url, _| healthy_indices.iter().any(|&idx| workers[idx].url() == url));
+
+            let mut best: Option<(usize, f64)> = None;
+            for (&idx, &weight) in healthy_indices.iter().zip(weights.iter()) {
+                let score = current.entry(workers[idx].url().to_string()).or_insert(0.0);
+                *score += weight;
+                if best.is_none_or(|(_, best_score)| *score > best_score) {
+                    best = Some((idx, *score));
+                }
+            }
+
+            let (idx, _) = best?;
+            if let Some(score) = current.get_mut(workers[idx].url()) {
+                *score -= total;
+            }
+            idx
+        };
+
+        let worker = workers[selected_idx].url();
+        RouterMetrics::record_processed_request(worker);
+        RouterMetrics::record_policy_decision(self.name(), worker);
+        Some(selected_idx)
+    }
+
+    fn name(&self) -> &'static str {
+        "weighted_round_robin"
+    }
+
+    fn reset(&self) {
+        self.current_weights.lock().unwrap().clear();
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{BasicWorker, WorkerType};
+
+    fn worker_with_labels(url: &str, labels: &[(&str, &str)]) -> Arc<dyn Worker> {
+        let labels = labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Arc::new(BasicWorker::new(url.to_string(), WorkerType::Regular).with_labels(labels))
+    }
+
+    fn count_selections(
+        policy: &WeightedRoundRobinPolicy,
+        workers: &[Arc<dyn Worker>],
+        rounds: usize,
+    ) -> Vec<usize> {
+        let mut counts = vec![0; workers.len()];
+        for _ in 0..rounds {
+            let idx = policy.select_worker(workers, None).unwrap();
+            counts[idx] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_weighted_by_priority() {
+        let policy = WeightedRoundRobinPolicy::new();
+        let workers = vec![
+            worker_with_labels("http://w1:8000", &[("priority", "1")]),
+            worker_with_labels("http://w2:8000", &[("priority", "2")]),
+        ];
+
+        // One full cycle is 3 selections; 2 of every 3 go to w2
+        assert_eq!(count_selections(&policy, &workers, 300), vec![100, 200]);
+    }
+
+    #[test]
+    fn test_weighted_by_cost() {
+        let policy = WeightedRoundRobinPolicy::with_weight_source(WeightSource::Cost);
+        let workers = vec![
+            worker_with_labels("http://w1:8000", &[("priority", "10"), ("cost", "3.0")]),
+            worker_with_labels("http://w2:8000", &[("priority", "90"), ("cost", "1.0")]),
+        ];
+
+        assert_eq!(count_selections(&policy, &workers, 400), vec![300, 100]);
+    }
+
+    #[test]
+    fn test_equal_weights_behave_like_round_robin() {
+        let policy = WeightedRoundRobinPolicy::new();
+        let workers: Vec<Arc<dyn Worker>> = vec![
+            Arc::new(BasicWorker::new(
+                "http://w1:8000".to_string(),
+                WorkerType::Regular,
+            )),
+            Arc::new(BasicWorker::new(
+                "http://w2:8000".to_string(),
+                WorkerType::Regular,
+            )),
+            Arc::new(BasicWorker::new(
+                "http://w3:8000".to_string(),
+                WorkerType::Regular,
+            )),
+        ];
+
+        assert_eq!(policy.select_worker(&workers, None), Some(0));
+        assert_eq!(policy.select_worker(&workers, None), Some(1));
+        assert_eq!(policy.select_worker(&workers, None), Some(2));
+        assert_eq!(policy.select_worker(&workers, None), Some(0));
+    }
+
+    #[test]
+    fn test_smooth_interleaving() {
+        let policy = WeightedRoundRobinPolicy::new();
+        let workers = vec![
+            worker_with_labels("http://w1:8000", &[("priority", "5")]),
+            worker_with_labels("http://w2:8000", &[("priority", "1")]),
+            worker_with_labels("http://w3:8000", &[("priority", "1")]),
+        ];
+
+        // Classic smooth WRR sequence for weights {5, 1, 1}
+        let sequence: Vec<usize> = (0..7)
+            .map(|_| policy.select_worker(&workers, None).unwrap())
+            .collect();
+        assert_eq!(sequence, vec![0, 0, 1, 0, 2, 0, 0]);
+    }
+
+    #[test]
+    fn test_skips_unhealthy_and_open_circuits() {
+        let policy = WeightedRoundRobinPolicy::new();
+        let workers = vec![
+            worker_with_labels("http://w1:8000", &[("priority", "1")]),
+            worker_with_labels("http://w2:8000", &[("priority", "10")]),
+            worker_with_labels("http://w3:8000", &[("priority", "10")]),
+        ];
+
+        workers[1].set_healthy(false);
+        workers[2].circuit_breaker().force_open();
+
+        for _ in 0..5 {
+            assert_eq!(policy.select_worker(&workers, None), Some(0));
+        }
+
+        workers[0].set_healthy(false);
+        assert_eq!(policy.select_worker(&workers, None), None);
+    }
+
+    #[test]
+    fn test_weights_follow_worker_set_changes() {
+        let policy = WeightedRoundRobinPolicy::new();
+        let mut workers = vec![
+            worker_with_labels("http://w1:8000", &[("priority", "1")]),
+            worker_with_labels("http://w2:8000", &[("priority", "1")]),
+        ];
+        assert_eq!(count_selections(&policy, &workers, 10), vec![5, 5]);
+
+        workers.push(worker_with_labels("http://w3:8000", &[("priority", "2")]));
+        assert_eq!(count_selections(&policy, &workers, 40), vec![10, 10, 20]);
+
+        workers.remove(0);
+        assert_eq!(count_selections(&policy, &workers, 30), vec![10, 20]);
+    }
+
+    #[test]
+    fn test_zero_weights_fall_back_to_rotation() {
+        let policy = WeightedRoundRobinPolicy::with_weight_source(WeightSource::Cost);
+        let workers = vec![
+            worker_with_labels("http://w1:8000", &[("cost", "0")]),
+            worker_with_labels("http://w2:8000", &[("cost", "0")]),
+        ];
+
+        assert_eq!(count_selections(&policy, &workers, 10), vec![5, 5]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let policy = WeightedRoundRobinPolicy::new();
+        let workers = vec![
+            worker_with_labels("http://w1:8000", &[("priority", "1")]),
+            worker_with_labels("http://w2:8000", &[("priority", "1")]),
+        ];
+
+        assert_eq!(policy.select_worker(&workers, None), Some(0));
+        policy.reset();
+        assert_eq!(policy.select_worker(&workers, None), Some(0));
+    }
+}
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 
+use crate::protocols::validation::{utils::validate_sampling_params, ValidationError};
+
 // # Protocol Specifications
 //
 // This module contains all protocol definitions for OpenAI and VLLM APIs.
@@ -44,7 +46,10 @@ use std::collections::HashMap;
 // 7. **OPENAI SPEC - Embeddings API**
 //    - Request structures
 //
-// 8. **COMMON**
+// 8. **OPENAI SPEC - Batch API**
+//    - Request/Batch/Output structures
+//
+// 9. **COMMON**
 //    - GenerationRequest trait
 //    - StringOrArray & LoRAPath types
 //    - Helper functions
@@ -535,6 +540,56 @@ impl GenerationRequest for ChatCompletionRequest {
         Some(&self.model)
     }
 
+    fn set_model(&mut self, model: String) {
+        self.model = model;
+    }
+
+    fn validate_sampling_params(&self) -> Result<(), ValidationError> {
+        validate_sampling_params(self)
+    }
+
+    fn validate_structured_output(&self) -> Result<(), ValidationError> {
+        self.validate_guided_decoding()
+    }
+
+    fn max_tokens(&self) -> Option<u32> {
+        // Workers honor max_completion_tokens over the deprecated max_tokens
+        self.max_completion_tokens.or(self.max_tokens)
+    }
+
+    fn set_max_tokens(&mut self, max_tokens: u32) {
+        if self.max_completion_tokens.is_some() {
+            self.max_completion_tokens = Some(max_tokens);
+        }
+        if self.max_tokens.is_some() || self.max_completion_tokens.is_none() {
+            self.max_tokens = Some(max_tokens);
+        }
+    }
+
+    fn prompt_chars(&self) -> usize {
+        self.messages
+            .iter()
+            .map(|message| match message {
+                ChatMessage::System { content, .. }
+                | ChatMessage::Tool { content, .. }
+                | ChatMessage::Function { content, .. } => content.chars().count(),
+                ChatMessage::User { content, .. } => match content {
+                    UserMessageContent::Text(text) => text.chars().count(),
+                    UserMessageContent::Parts(parts) => parts
+                        .iter()
+                        .map(|part| match part {
+                            ContentPart::Text { text } => text.chars().count(),
+                            ContentPart::ImageUrl { .. } => 0,
+                        })
+                        .sum(),
+                },
+                ChatMessage::Assistant { content, .. } => {
+                    content.as_deref().map_or(0, |text| text.chars().count())
+                }
+            })
+            .sum()
+    }
+
     fn extract_text_for_routing(&self) -> String {
         // Use session_id from session_params for session-based routing
         if let Some(ref session_params) = self.session_params {
@@ -550,6 +605,14 @@ impl GenerationRequest for ChatCompletionRequest {
         // Return empty string if no session_id - let routing policy handle this case
         String::new()
     }
+
+    fn is_deterministic(&self) -> bool {
+        self.temperature == Some(0.0) && self.n.unwrap_or(1) == 1
+    }
+
+    fn load_weight(&self) -> usize {
+        self.n.unwrap_or(1).max(1) as usize
+    }
 }
 
 // ============= Regular Response =============
@@ -756,9 +819,37 @@ impl GenerationRequest for CompletionRequest {
         Some(&self.model)
     }
 
+    fn set_model(&mut self, model: String) {
+        self.model = model;
+    }
+
+    fn max_tokens(&self) -> Option<u32> {
+        self.max_tokens
+    }
+
+    fn set_max_tokens(&mut self, max_tokens: u32) {
+        self.max_tokens = Some(max_tokens);
+    }
+
+    fn validate_sampling_params(&self) -> Result<(), ValidationError> {
+        validate_sampling_params(self)
+    }
+
+    fn validate_structured_output(&self) -> Result<(), ValidationError> {
+        self.validate_guided_decoding()
+    }
+
     fn extract_text_for_routing(&self) -> String {
         self.prompt.extract_text_for_routing()
     }
+
+    fn is_deterministic(&self) -> bool {
+        self.temperature == Some(0.0) && self.n.unwrap_or(1) == 1
+    }
+
+    fn load_weight(&self) -> usize {
+        self.n.unwrap_or(1).max(self.best_of.unwrap_or(1)).max(1) as usize
+    }
 }
 
 // ============= Regular Response =============
@@ -1333,6 +1424,22 @@ impl GenerationRequest for ResponsesRequest {
         self.model.as_deref()
     }
 
+    fn set_model(&mut self, model: String) {
+        self.model = Some(model);
+    }
+
+    fn max_tokens(&self) -> Option<u32> {
+        self.max_output_tokens
+    }
+
+    fn set_max_tokens(&mut self, max_tokens: u32) {
+        self.max_output_tokens = Some(max_tokens);
+    }
+
+    fn validate_sampling_params(&self) -> Result<(), ValidationError> {
+        validate_sampling_params(self)
+    }
+
     fn extract_text_for_routing(&self) -> String {
         match &self.input {
             ResponseInput::Text(text) => text.clone(),
@@ -1427,6 +1534,31 @@ pub struct ResponsesResponse {
     pub tools: Vec<ResponseTool>,
 }
 
+/// Sort order for list endpoints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Query parameters for listing the input items of a stored response
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ResponseInputItemsParams {
+    /// Return items after the item with this ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+
+    /// Maximum number of items to return (1-100, default 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+
+    /// Order of the returned items
+    #[serde(default)]
+    pub order: ListOrder,
+}
+
 fn default_object_type() -> String {
     "response".to_string()
 }
@@ -1932,6 +2064,23 @@ impl GenerationRequest for GenerateRequest {
         None
     }
 
+    fn validate_sampling_params(&self) -> Result<(), ValidationError> {
+        match &self.sampling_params {
+            Some(params) => validate_sampling_params(params),
+            None => Ok(()),
+        }
+    }
+
+    /// Token IDs are not text and do not count towards the prompt length
+    fn prompt_chars(&self) -> usize {
+        match (&self.text, &self.prompt) {
+            (Some(text), _) => text.chars().count(),
+            (None, Some(StringOrArray::String(s))) => s.chars().count(),
+            (None, Some(StringOrArray::Array(v))) => v.iter().map(|s| s.chars().count()).sum(),
+            (None, None) => 0,
+        }
+    }
+
     fn extract_text_for_routing(&self) -> String {
         // Check fields in priority order: text, prompt, inputs
         if let Some(ref text) = self.text {
@@ -1963,6 +2112,20 @@ impl GenerationRequest for GenerateRequest {
         // No text input found
         String::new()
     }
+
+    fn is_deterministic(&self) -> bool {
+        self.sampling_params
+            .as_ref()
+            .is_some_and(|params| params.temperature == Some(0.0))
+    }
+
+    fn load_weight(&self) -> usize {
+        self.parameters
+            .as_ref()
+            .and_then(|params| params.best_of)
+            .unwrap_or(1)
+            .max(1) as usize
+    }
 }
 
 // ==================================================================
@@ -2083,6 +2246,10 @@ impl GenerationRequest for RerankRequest {
         Some(&self.model)
     }
 
+    fn set_model(&mut self, model: String) {
+        self.model = model;
+    }
+
     fn is_stream(&self) -> bool {
         false // Reranking doesn't support streaming
     }
@@ -2207,6 +2374,10 @@ impl GenerationRequest for EmbeddingRequest {
         Some(&self.model)
     }
 
+    fn set_model(&mut self, model: String) {
+        self.model = model;
+    }
+
     fn extract_text_for_routing(&self) -> String {
         // Best effort: extract text content for routing decisions
         match &self.input {
@@ -2221,6 +2392,98 @@ impl GenerationRequest for EmbeddingRequest {
     }
 }
 
+// ==================================================================
+// =            OPENAI SPEC - Batch API                             =
+// ==================================================================
+
+/// Batch creation request
+///
+/// The router has no Files API, so the requests of a batch are passed inline
+/// in `requests` (one entry per line of an OpenAI batch input file) instead
+/// of through `input_file_id`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BatchCreateRequest {
+    /// Endpoint every request of the batch is sent to
+    pub endpoint: String,
+
+    /// Time frame within which the batch should be processed (only "24h")
+    pub completion_window: String,
+
+    /// Not supported; requests are passed in `requests`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_file_id: Option<String>,
+
+    /// Router extension: the requests of the batch
+    #[serde(default)]
+    pub requests: Vec<BatchRequestInput>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// One request of a batch, in the format of a batch input file line
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BatchRequestInput {
+    /// Caller-chosen ID matching the request to its output line
+    pub custom_id: String,
+    pub method: String,
+    pub url: String,
+    pub body: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    Validating,
+    Failed,
+    InProgress,
+    Finalizing,
+    Completed,
+    Expired,
+    Cancelling,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BatchRequestCounts {
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+}
+
+/// Batch object
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Batch {
+    pub id: String,
+    pub object: String, // "batch"
+    pub endpoint: String,
+    pub completion_window: String,
+    pub status: BatchStatus,
+    pub created_at: i64,
+    pub in_progress_at: Option<i64>,
+    /// End of the completion window
+    pub expires_at: Option<i64>,
+    pub completed_at: Option<i64>,
+    pub expired_at: Option<i64>,
+    pub cancelled_at: Option<i64>,
+    pub request_counts: BatchRequestCounts,
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// One line of a batch output: the response a request of the batch received
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BatchRequestOutput {
+    pub id: String,
+    pub custom_id: String,
+    pub response: BatchResponse,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BatchResponse {
+    pub status_code: u16,
+    pub body: Value,
+}
+
 // ==================================================================
 // =            COMMON                                              =
 // ==================================================================
@@ -2238,8 +2501,54 @@ pub trait GenerationRequest: Send + Sync {
     /// Get the model name if specified
     fn get_model(&self) -> Option<&str>;
 
+    /// Replace the requested model, e.g. with the served model an alias maps to
+    ///
+    /// A no-op for requests without a model field.
+    fn set_model(&mut self, _model: String) {}
+
     /// Extract text content for routing decisions
     fn extract_text_for_routing(&self) -> String;
+
+    /// Length of the prompt text in characters, checked against
+    /// `max_prompt_chars` before routing
+    fn prompt_chars(&self) -> usize {
+        self.extract_text_for_routing().chars().count()
+    }
+
+    /// Whether identical requests produce identical outputs (greedy decoding,
+    /// single choice), which makes the response safe to cache
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    /// Check sampling parameters that a worker would reject, so the request
+    /// can fail fast without a round-trip
+    fn validate_sampling_params(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    /// Check structured output constraints (malformed JSON schemas,
+    /// conflicting constraint kinds) that a worker would reject
+    fn validate_structured_output(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    /// Output token limit the client asked for, None when unlimited or not
+    /// supported by the request type
+    fn max_tokens(&self) -> Option<u32> {
+        None
+    }
+
+    /// Set the output token limit, e.g. to the configured default
+    ///
+    /// A no-op for requests without an output token limit.
+    fn set_max_tokens(&mut self, _max_tokens: u32) {}
+
+    /// How many sequences the worker decodes for this request (`n` choices
+    /// or `best_of` candidates), used to weight its share of worker load
+    fn load_weight(&self) -> usize {
+        1
+    }
 }
 
 /// Helper type for string or array of strings
@@ -3523,4 +3832,123 @@ mod tests {
             _ => panic!("Expected Assistant message"),
         }
     }
+
+    // ==================================================================
+    // =            DETERMINISTIC REQUEST TESTS                         =
+    // ==================================================================
+
+    #[test]
+    fn test_completion_is_deterministic() {
+        let request = |extra: serde_json::Value| -> CompletionRequest {
+            let mut body = serde_json::json!({"model": "m", "prompt": "hi"});
+            body.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(body).unwrap()
+        };
+
+        assert!(request(serde_json::json!({"temperature": 0.0})).is_deterministic());
+        assert!(request(serde_json::json!({"temperature": 0.0, "n": 1})).is_deterministic());
+        assert!(!request(serde_json::json!({"temperature": 0.0, "n": 2})).is_deterministic());
+        assert!(!request(serde_json::json!({"temperature": 0.7})).is_deterministic());
+        // The default temperature samples
+        assert!(!request(serde_json::json!({})).is_deterministic());
+    }
+
+    #[test]
+    fn test_chat_and_generate_are_deterministic() {
+        let chat: ChatCompletionRequest = serde_json::from_value(serde_json::json!({
+            "model": "m",
+            "messages": [{"role": "user", "content": "hi"}],
+            "temperature": 0.0
+        }))
+        .unwrap();
+        assert!(chat.is_deterministic());
+
+        let generate: GenerateRequest = serde_json::from_value(serde_json::json!({
+            "text": "hi",
+            "sampling_params": {"temperature": 0.0}
+        }))
+        .unwrap();
+        assert!(generate.is_deterministic());
+
+        let generate: GenerateRequest =
+            serde_json::from_value(serde_json::json!({"text": "hi"})).unwrap();
+        assert!(!generate.is_deterministic());
+    }
+
+    #[test]
+    fn test_load_weight_counts_parallel_samples() {
+        let completion = |extra: serde_json::Value| -> CompletionRequest {
+            let mut body = serde_json::json!({"model": "m", "prompt": "hi"});
+            body.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(body).unwrap()
+        };
+        assert_eq!(completion(serde_json::json!({})).load_weight(), 1);
+        assert_eq!(completion(serde_json::json!({"n": 4})).load_weight(), 4);
+        assert_eq!(
+            completion(serde_json::json!({"n": 2, "best_of": 5})).load_weight(),
+            5
+        );
+        assert_eq!(completion(serde_json::json!({"n": 0})).load_weight(), 1);
+
+        let chat: ChatCompletionRequest = serde_json::from_value(serde_json::json!({
+            "model": "m",
+            "messages": [{"role": "user", "content": "hi"}],
+            "n": 3
+        }))
+        .unwrap();
+        assert_eq!(chat.load_weight(), 3);
+
+        let generate: GenerateRequest = serde_json::from_value(serde_json::json!({
+            "text": "hi",
+            "parameters": {"best_of": 2}
+        }))
+        .unwrap();
+        assert_eq!(generate.load_weight(), 2);
+    }
+
+    #[test]
+    fn test_max_tokens_accessors() {
+        let chat = |extra: serde_json::Value| -> ChatCompletionRequest {
+            let mut body = serde_json::json!({
+                "model": "m",
+                "messages": [{"role": "user", "content": "hi"}]
+            });
+            body.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(body).unwrap()
+        };
+
+        let mut req = chat(serde_json::json!({}));
+        assert_eq!(req.max_tokens(), None);
+        req.set_max_tokens(256);
+        assert_eq!(req.max_tokens, Some(256));
+        assert_eq!(req.max_completion_tokens, None);
+
+        let mut req = chat(serde_json::json!({"max_tokens": 100, "max_completion_tokens": 50}));
+        assert_eq!(req.max_tokens(), Some(50));
+        req.set_max_tokens(20);
+        assert_eq!(req.max_tokens, Some(20));
+        assert_eq!(req.max_completion_tokens, Some(20));
+
+        let mut req = chat(serde_json::json!({"max_completion_tokens": 50}));
+        req.set_max_tokens(20);
+        assert_eq!(req.max_tokens, None);
+        assert_eq!(req.max_completion_tokens, Some(20));
+
+        let mut completion: CompletionRequest =
+            serde_json::from_value(serde_json::json!({"model": "m", "prompt": "hi"})).unwrap();
+        completion.set_max_tokens(64);
+        assert_eq!(completion.max_tokens(), Some(64));
+
+        let mut responses: ResponsesRequest =
+            serde_json::from_value(serde_json::json!({"input": "hi"})).unwrap();
+        assert_eq!(responses.max_tokens(), None);
+        responses.set_max_tokens(64);
+        assert_eq!(responses.max_output_tokens, Some(64));
+    }
 }
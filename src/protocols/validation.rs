@@ -2,11 +2,14 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::fmt::Display;
 
 // Import types from spec module
 use crate::protocols::spec::{
-    ChatCompletionRequest, ChatMessage, ResponseFormat, StringOrArray, UserMessageContent,
+    ChatCompletionRequest, ChatMessage, CompletionRequest, ResponseFormat, ResponsesRequest,
+    SamplingParams, StringOrArray, StructuredOutputsParams, UserMessageContent,
 };
 
 /// Validation constants for OpenAI API parameters
@@ -29,15 +32,26 @@ pub mod constants {
     /// Top logprobs range for chat completions: 0 to 20
     pub const TOP_LOGPROBS_RANGE: (u32, u32) = (0, 20);
 
+    /// Logit bias range per token: -100 to 100 (OpenAI spec)
+    pub const LOGIT_BIAS_RANGE: (f32, f32) = (-100.0, 100.0);
+
     /// Maximum number of stop sequences allowed
     pub const MAX_STOP_SEQUENCES: usize = 4;
 
+    /// Type names allowed in a JSON schema `type` keyword
+    pub const JSON_SCHEMA_TYPES: &[&str] = &[
+        "null", "boolean", "object", "array", "number", "string", "integer",
+    ];
+
     /// VLLM-specific validation constants
     pub mod vllm {
         /// Min-p range: 0.0 to 1.0 (VLLM extension)
         pub const MIN_P_RANGE: (f32, f32) = (0.0, 1.0);
 
-        /// Top-k minimum value: -1 to disable, otherwise positive
+        /// Temperature minimum: 0.0 is greedy; VLLM sets no upper bound
+        pub const TEMPERATURE_MIN: f32 = 0.0;
+
+        /// Top-k minimum value: -1 or 0 to disable, otherwise positive
         pub const TOP_K_MIN: i32 = -1;
 
         /// Repetition penalty range: 0.0 to 2.0 (VLLM extension)
@@ -138,6 +152,19 @@ impl Display for ValidationError {
 
 impl std::error::Error for ValidationError {}
 
+impl ValidationError {
+    /// The request parameter the error is about, if it names a single one
+    pub fn parameter(&self) -> Option<&str> {
+        match self {
+            ValidationError::OutOfRange { parameter, .. }
+            | ValidationError::InvalidValue { parameter, .. }
+            | ValidationError::MissingRequired { parameter }
+            | ValidationError::TooManyItems { parameter, .. } => Some(parameter),
+            ValidationError::ConflictingParameters { .. } | ValidationError::Custom(_) => None,
+        }
+    }
+}
+
 /// Core validation utility functions
 pub mod utils {
     use super::*;
@@ -179,6 +206,22 @@ pub mod utils {
         }
     }
 
+    /// Validate that a value is not below `min`
+    pub fn validate_min<T>(value: T, min: T, param_name: &str) -> Result<T, ValidationError>
+    where
+        T: PartialOrd + Display + Copy,
+    {
+        if value >= min {
+            Ok(value)
+        } else {
+            Err(ValidationError::InvalidValue {
+                parameter: param_name.to_string(),
+                value: value.to_string(),
+                reason: format!("must be at least {}", min),
+            })
+        }
+    }
+
     /// Validate that an array doesn't exceed maximum length
     pub fn validate_max_items<T>(
         items: &[T],
@@ -210,13 +253,13 @@ pub mod utils {
 
     /// Validate top_k parameter (VLLM extension)
     pub fn validate_top_k(top_k: i32) -> Result<i32, ValidationError> {
-        if top_k == constants::vllm::TOP_K_MIN || top_k > 0 {
+        if top_k >= constants::vllm::TOP_K_MIN {
             Ok(top_k)
         } else {
             Err(ValidationError::InvalidValue {
                 parameter: "top_k".to_string(),
                 value: top_k.to_string(),
-                reason: "must be -1 (disabled) or positive".to_string(),
+                reason: "must be -1 or 0 (disabled) or positive".to_string(),
             })
         }
     }
@@ -253,6 +296,79 @@ pub mod utils {
             )?;
         }
 
+        if let Some(logit_bias) = request.get_logit_bias() {
+            validate_logit_bias(logit_bias)?;
+        }
+
+        Ok(())
+    }
+
+    /// Validate logit_bias: token ID keys with biases between -100 and 100
+    pub fn validate_logit_bias(logit_bias: &HashMap<String, f32>) -> Result<(), ValidationError> {
+        for (token, bias) in logit_bias {
+            if token.parse::<u32>().is_err() {
+                return Err(ValidationError::InvalidValue {
+                    parameter: "logit_bias".to_string(),
+                    value: token.clone(),
+                    reason: "keys must be token IDs".to_string(),
+                });
+            }
+            validate_range(
+                *bias,
+                &constants::LOGIT_BIAS_RANGE,
+                &format!("logit_bias.{}", token),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Validate the sampling parameters a worker would reject outright
+    ///
+    /// A subset of [`validate_common_request_params`] that is cheap enough to
+    /// run on every request before routing: temperature, top_p, penalties,
+    /// logit_bias, top_k and n. Ranges follow VLLM rather than the OpenAI
+    /// spec: temperature has no upper bound, and `n` is only required to be
+    /// positive, since workers accept more than 10 choices.
+    pub fn validate_sampling_params<T>(request: &T) -> Result<(), ValidationError>
+    where
+        T: SamplingOptionsProvider + VLLMExtensionsProvider + CompletionCountProvider + ?Sized,
+    {
+        if let Some(temp) = request.get_temperature() {
+            validate_min(temp, constants::vllm::TEMPERATURE_MIN, "temperature")?;
+        }
+
+        if let Some(top_p) = request.get_top_p() {
+            validate_range(top_p, &constants::TOP_P_RANGE, "top_p")?;
+        }
+
+        if let Some(freq_penalty) = request.get_frequency_penalty() {
+            validate_range(
+                freq_penalty,
+                &constants::FREQUENCY_PENALTY_RANGE,
+                "frequency_penalty",
+            )?;
+        }
+
+        if let Some(pres_penalty) = request.get_presence_penalty() {
+            validate_range(
+                pres_penalty,
+                &constants::PRESENCE_PENALTY_RANGE,
+                "presence_penalty",
+            )?;
+        }
+
+        if let Some(logit_bias) = request.get_logit_bias() {
+            validate_logit_bias(logit_bias)?;
+        }
+
+        if let Some(top_k) = request.get_top_k() {
+            validate_top_k(top_k)?;
+        }
+
+        if let Some(n) = request.get_n() {
+            validate_positive(n, "n")?;
+        }
+
         Ok(())
     }
 
@@ -380,7 +496,7 @@ pub mod utils {
     pub fn validate_vllm_extensions<T: VLLMExtensionsProvider + ?Sized>(
         request: &T,
     ) -> Result<(), ValidationError> {
-        // Validate top_k (-1 to disable, or positive)
+        // Validate top_k (-1 or 0 to disable, or positive)
         if let Some(top_k) = request.get_top_k() {
             validate_top_k(top_k)?;
         }
@@ -415,6 +531,211 @@ pub mod utils {
         Ok(())
     }
 
+    /// Validate that at most one structured output constraint is active
+    pub fn validate_single_structured_constraint(
+        constraints: &[(&str, bool)],
+    ) -> Result<(), ValidationError> {
+        let mut active = constraints.iter().filter(|(_, is_active)| *is_active);
+        if let (Some((first, _)), Some((second, _))) = (active.next(), active.next()) {
+            return Err(ValidationError::ConflictingParameters {
+                parameter1: first.to_string(),
+                parameter2: second.to_string(),
+                reason: "only one structured output constraint can be used at a time".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Parse a JSON schema sent as a string
+    pub fn parse_json_schema(text: &str, param_name: &str) -> Result<Value, ValidationError> {
+        serde_json::from_str(text).map_err(|e| ValidationError::InvalidValue {
+            parameter: param_name.to_string(),
+            value: "string".to_string(),
+            reason: format!("not valid JSON: {}", e),
+        })
+    }
+
+    /// Validate that `schema` is a well-formed JSON schema
+    ///
+    /// A structural check of the keywords structured output backends compile,
+    /// not validation against the full meta-schema: every subschema is an
+    /// object or a boolean, `type` names JSON schema types, and keywords that
+    /// hold subschemas, property names or bounds have the matching shape.
+    /// Unknown keywords are left to the worker.
+    pub fn validate_json_schema(schema: &Value, param_name: &str) -> Result<(), ValidationError> {
+        let result = if schema.is_object() {
+            check_subschema(schema, "#")
+        } else {
+            Err(("#".to_string(), "schema must be an object"))
+        };
+        result.map_err(|(location, reason)| ValidationError::InvalidValue {
+            parameter: param_name.to_string(),
+            value: location,
+            reason: reason.to_string(),
+        })
+    }
+
+    /// Check one subschema; errors carry the keyword's location and the problem
+    fn check_subschema(schema: &Value, location: &str) -> Result<(), (String, &'static str)> {
+        let keywords = match schema {
+            Value::Bool(_) => return Ok(()),
+            Value::Object(keywords) => keywords,
+            _ => {
+                return Err((
+                    location.to_string(),
+                    "schema must be an object or a boolean",
+                ))
+            }
+        };
+
+        for (keyword, value) in keywords {
+            let at = format!("{}/{}", location, keyword);
+            let problem = match keyword.as_str() {
+                "type" => {
+                    let is_type = |t: &Value| {
+                        t.as_str()
+                            .is_some_and(|t| constants::JSON_SCHEMA_TYPES.contains(&t))
+                    };
+                    let valid = match value {
+                        Value::Array(types) => !types.is_empty() && types.iter().all(is_type),
+                        single => is_type(single),
+                    };
+                    (!valid).then_some("must be a JSON schema type or a non-empty array of types")
+                }
+                "properties" | "patternProperties" | "$defs" | "definitions"
+                | "dependentSchemas" => match value.as_object() {
+                    Some(schemas) => {
+                        for (name, subschema) in schemas {
+                            check_subschema(subschema, &format!("{}/{}", at, name))?;
+                        }
+                        None
+                    }
+                    None => Some("must be an object of schemas"),
+                },
+                "allOf" | "anyOf" | "oneOf" | "prefixItems" => match value.as_array() {
+                    Some(schemas) if !schemas.is_empty() => {
+                        for (i, subschema) in schemas.iter().enumerate() {
+                            check_subschema(subschema, &format!("{}/{}", at, i))?;
+                        }
+                        None
+                    }
+                    _ => Some("must be a non-empty array of schemas"),
+                },
+                // Draft 4-7 allowed an array of schemas for tuple items
+                "items" if value.is_array() => {
+                    for (i, subschema) in value.as_array().into_iter().flatten().enumerate() {
+                        check_subschema(subschema, &format!("{}/{}", at, i))?;
+                    }
+                    None
+                }
+                "items"
+                | "additionalItems"
+                | "additionalProperties"
+                | "contains"
+                | "not"
+                | "propertyNames"
+                | "if"
+                | "then"
+                | "else"
+                | "unevaluatedItems"
+                | "unevaluatedProperties" => {
+                    check_subschema(value, &at)?;
+                    None
+                }
+                "required" => match value.as_array() {
+                    Some(names) if names.iter().all(Value::is_string) => None,
+                    _ => Some("must be an array of property names"),
+                },
+                "enum" => match value.as_array() {
+                    Some(values) if !values.is_empty() => None,
+                    _ => Some("must be a non-empty array"),
+                },
+                "minimum" | "maximum" => (!value.is_number()).then_some("must be a number"),
+                // Draft 4 used booleans modifying minimum and maximum
+                "exclusiveMinimum" | "exclusiveMaximum" => {
+                    (!value.is_number() && !value.is_boolean()).then_some("must be a number")
+                }
+                "multipleOf" => (!value.as_f64().is_some_and(|n| n > 0.0))
+                    .then_some("must be a number greater than 0"),
+                "minLength" | "maxLength" | "minItems" | "maxItems" | "minProperties"
+                | "maxProperties" | "minContains" | "maxContains" => {
+                    (!value.is_u64()).then_some("must be a non-negative integer")
+                }
+                "pattern" | "format" | "$ref" => (!value.is_string()).then_some("must be a string"),
+                _ => None,
+            };
+            if let Some(reason) = problem {
+                return Err((at, reason));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate vLLM `structured_outputs` parameters
+    ///
+    /// Workers accept one kind of constraint per request. `json` may be a
+    /// schema or a string holding one.
+    pub fn validate_structured_outputs(
+        params: &StructuredOutputsParams,
+    ) -> Result<(), ValidationError> {
+        validate_single_structured_constraint(&structured_outputs_constraints(params))?;
+
+        if let Some(json) = &params.json {
+            let parsed;
+            let schema = match json {
+                Value::String(text) => {
+                    parsed = parse_json_schema(text, "structured_outputs.json")?;
+                    &parsed
+                }
+                schema => schema,
+            };
+            validate_json_schema(schema, "structured_outputs.json")?;
+        }
+
+        let empty = [
+            (
+                "structured_outputs.regex",
+                params.regex.as_ref().is_some_and(String::is_empty),
+            ),
+            (
+                "structured_outputs.choice",
+                params.choice.as_ref().is_some_and(Vec::is_empty),
+            ),
+            (
+                "structured_outputs.grammar",
+                params.grammar.as_ref().is_some_and(String::is_empty),
+            ),
+        ];
+        if let Some((parameter, _)) = empty.iter().find(|(_, is_empty)| *is_empty) {
+            return Err(ValidationError::InvalidValue {
+                parameter: parameter.to_string(),
+                value: "empty".to_string(),
+                reason: "structured output constraint cannot be empty".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Constraint kinds of `structured_outputs`, with whether each is set
+    pub fn structured_outputs_constraints(
+        params: &StructuredOutputsParams,
+    ) -> [(&'static str, bool); 6] {
+        [
+            ("structured_outputs.json", params.json.is_some()),
+            ("structured_outputs.regex", params.regex.is_some()),
+            ("structured_outputs.choice", params.choice.is_some()),
+            ("structured_outputs.grammar", params.grammar.is_some()),
+            (
+                "structured_outputs.json_object",
+                params.json_object == Some(true),
+            ),
+            (
+                "structured_outputs.structural_tag",
+                params.structural_tag.is_some(),
+            ),
+        ]
+    }
+
     /// Validate that an array is not empty
     pub fn validate_non_empty_array<T>(
         items: &[T],
@@ -469,6 +790,11 @@ pub trait SamplingOptionsProvider {
 
     /// Get presence penalty parameter
     fn get_presence_penalty(&self) -> Option<f32>;
+
+    /// Get logit bias parameter, keyed by token ID
+    fn get_logit_bias(&self) -> Option<&HashMap<String, f32>> {
+        None
+    }
 }
 
 /// Trait for validating stop conditions
@@ -554,6 +880,9 @@ impl SamplingOptionsProvider for ChatCompletionRequest {
     fn get_presence_penalty(&self) -> Option<f32> {
         self.presence_penalty
     }
+    fn get_logit_bias(&self) -> Option<&HashMap<String, f32>> {
+        self.logit_bias.as_ref()
+    }
 }
 
 impl StopConditionsProvider for ChatCompletionRequest {
@@ -650,10 +979,39 @@ impl ChatCompletionRequest {
                     reason: "JSON schema name cannot be empty".to_string(),
                 });
             }
+            utils::validate_json_schema(&json_schema.schema, "response_format.json_schema.schema")?;
         }
         Ok(())
     }
 
+    /// Validate structured output constraints a worker would reject
+    ///
+    /// JSON schemas must be well-formed, and at most one kind of constraint
+    /// may be requested across `response_format`, `regex`, `ebnf` and
+    /// `structured_outputs`.
+    pub fn validate_guided_decoding(&self) -> Result<(), ValidationError> {
+        self.validate_response_format()?;
+        if let Some(params) = &self.structured_outputs {
+            utils::validate_structured_outputs(params)?;
+        }
+
+        let has_json_format = matches!(
+            self.response_format,
+            Some(ResponseFormat::JsonObject | ResponseFormat::JsonSchema { .. })
+        );
+        let has_structured_outputs = self.structured_outputs.as_ref().is_some_and(|params| {
+            utils::structured_outputs_constraints(params)
+                .iter()
+                .any(|(_, is_active)| *is_active)
+        });
+        utils::validate_single_structured_constraint(&[
+            ("response_format", has_json_format),
+            ("regex", self.regex.is_some()),
+            ("ebnf", self.ebnf.is_some()),
+            ("structured_outputs", has_structured_outputs),
+        ])
+    }
+
     /// Validate chat API specific logprobs requirements
     pub fn validate_chat_logprobs(&self) -> Result<(), ValidationError> {
         // In chat API, if logprobs=true, top_logprobs must be specified
@@ -754,6 +1112,137 @@ impl ValidatableRequest for ChatCompletionRequest {
     }
 }
 
+// ==================================================================
+// =            OPENAI COMPLETION VALIDATION                        =
+// ==================================================================
+
+impl SamplingOptionsProvider for CompletionRequest {
+    fn get_temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+    fn get_top_p(&self) -> Option<f32> {
+        self.top_p
+    }
+    fn get_frequency_penalty(&self) -> Option<f32> {
+        self.frequency_penalty
+    }
+    fn get_presence_penalty(&self) -> Option<f32> {
+        self.presence_penalty
+    }
+    fn get_logit_bias(&self) -> Option<&HashMap<String, f32>> {
+        self.logit_bias.as_ref()
+    }
+}
+
+impl VLLMExtensionsProvider for CompletionRequest {
+    fn get_top_k(&self) -> Option<i32> {
+        self.top_k
+    }
+
+    fn get_min_p(&self) -> Option<f32> {
+        self.min_p
+    }
+
+    fn get_repetition_penalty(&self) -> Option<f32> {
+        self.repetition_penalty
+    }
+}
+
+impl CompletionCountProvider for CompletionRequest {
+    fn get_n(&self) -> Option<u32> {
+        self.n
+    }
+}
+
+impl CompletionRequest {
+    /// Validate structured output constraints a worker would reject
+    ///
+    /// `json_schema` must hold a well-formed JSON schema, and at most one of
+    /// `json_schema`, `regex` and `ebnf` may be set.
+    pub fn validate_guided_decoding(&self) -> Result<(), ValidationError> {
+        utils::validate_single_structured_constraint(&[
+            ("json_schema", self.json_schema.is_some()),
+            ("regex", self.regex.is_some()),
+            ("ebnf", self.ebnf.is_some()),
+        ])?;
+        if let Some(text) = &self.json_schema {
+            let schema = utils::parse_json_schema(text, "json_schema")?;
+            utils::validate_json_schema(&schema, "json_schema")?;
+        }
+        Ok(())
+    }
+}
+
+// ==================================================================
+// =            OPENAI RESPONSES VALIDATION                         =
+// ==================================================================
+
+impl SamplingOptionsProvider for ResponsesRequest {
+    fn get_temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+    fn get_top_p(&self) -> Option<f32> {
+        self.top_p
+    }
+    fn get_frequency_penalty(&self) -> Option<f32> {
+        Some(self.frequency_penalty)
+    }
+    fn get_presence_penalty(&self) -> Option<f32> {
+        Some(self.presence_penalty)
+    }
+}
+
+impl VLLMExtensionsProvider for ResponsesRequest {
+    fn get_top_k(&self) -> Option<i32> {
+        Some(self.top_k)
+    }
+
+    fn get_min_p(&self) -> Option<f32> {
+        Some(self.min_p)
+    }
+
+    fn get_repetition_penalty(&self) -> Option<f32> {
+        Some(self.repetition_penalty)
+    }
+}
+
+impl CompletionCountProvider for ResponsesRequest {}
+
+// ==================================================================
+// =            VLLM GENERATE VALIDATION                            =
+// ==================================================================
+
+impl SamplingOptionsProvider for SamplingParams {
+    fn get_temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+    fn get_top_p(&self) -> Option<f32> {
+        self.top_p
+    }
+    fn get_frequency_penalty(&self) -> Option<f32> {
+        self.frequency_penalty
+    }
+    fn get_presence_penalty(&self) -> Option<f32> {
+        self.presence_penalty
+    }
+}
+
+impl VLLMExtensionsProvider for SamplingParams {
+    fn get_top_k(&self) -> Option<i32> {
+        self.top_k
+    }
+
+    fn get_min_p(&self) -> Option<f32> {
+        self.min_p
+    }
+
+    fn get_repetition_penalty(&self) -> Option<f32> {
+        self.repetition_penalty
+    }
+}
+
+impl CompletionCountProvider for SamplingParams {}
+
 #[cfg(test)]
 mod tests {
     use super::constants::*;
@@ -826,7 +1315,7 @@ mod tests {
     fn test_vllm_top_k_validation() {
         assert!(validate_top_k(-1).is_ok()); // Disabled
         assert!(validate_top_k(50).is_ok()); // Valid positive
-        assert!(validate_top_k(0).is_err()); // Invalid
+        assert!(validate_top_k(0).is_ok()); // Disabled
         assert!(validate_top_k(-5).is_err()); // Invalid
     }
 
@@ -855,6 +1344,95 @@ mod tests {
         assert!(request.validate().is_err());
     }
 
+    #[test]
+    fn test_sampling_params_validation() {
+        let params = SamplingParams {
+            temperature: Some(0.7),
+            top_p: Some(0.9),
+            top_k: Some(-1),
+            ..Default::default()
+        };
+        assert!(validate_sampling_params(&params).is_ok());
+
+        let params = SamplingParams {
+            temperature: Some(-1.0),
+            ..Default::default()
+        };
+        let err = validate_sampling_params(&params).unwrap_err();
+        assert_eq!(err.parameter(), Some("temperature"));
+
+        let params = SamplingParams {
+            top_p: Some(2.0),
+            ..Default::default()
+        };
+        let err = validate_sampling_params(&params).unwrap_err();
+        assert_eq!(err.parameter(), Some("top_p"));
+
+        let params = SamplingParams {
+            top_k: Some(-2),
+            ..Default::default()
+        };
+        let err = validate_sampling_params(&params).unwrap_err();
+        assert_eq!(err.parameter(), Some("top_k"));
+
+        // VLLM accepts temperatures above the OpenAI maximum and top_k=0
+        let params = SamplingParams {
+            temperature: Some(3.0),
+            top_k: Some(0),
+            ..Default::default()
+        };
+        assert!(validate_sampling_params(&params).is_ok());
+    }
+
+    #[test]
+    fn test_logit_bias_validation() {
+        assert!(validate_logit_bias(&HashMap::from([("50256".to_string(), -100.0)])).is_ok());
+
+        let err = validate_logit_bias(&HashMap::from([("50256".to_string(), 101.0)])).unwrap_err();
+        assert_eq!(err.parameter(), Some("logit_bias.50256"));
+
+        let err = validate_logit_bias(&HashMap::from([("hello".to_string(), 1.0)])).unwrap_err();
+        assert_eq!(err.parameter(), Some("logit_bias"));
+    }
+
+    #[test]
+    fn test_json_schema_validation() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "minLength": 1},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "age": {"type": ["integer", "null"], "minimum": 0}
+            },
+            "required": ["name"],
+            "additionalProperties": false
+        });
+        assert!(validate_json_schema(&schema, "schema").is_ok());
+
+        let err = validate_json_schema(
+            &serde_json::json!({"properties": {"age": {"type": "int"}}}),
+            "schema",
+        )
+        .unwrap_err();
+        assert_eq!(err.parameter(), Some("schema"));
+        assert!(err.to_string().contains("#/properties/age/type"));
+
+        for invalid in [
+            serde_json::json!(["not", "a", "schema"]),
+            serde_json::json!({"properties": ["name"]}),
+            serde_json::json!({"required": [1]}),
+            serde_json::json!({"anyOf": []}),
+            serde_json::json!({"items": 5}),
+            serde_json::json!({"maxLength": -1}),
+        ] {
+            assert!(
+                validate_json_schema(&invalid, "schema").is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
     #[test]
     fn test_valid_request() {
         let request = MockRequest {
@@ -968,9 +1546,11 @@ mod tests {
             request.min_p = Some(0.1);
             request.repetition_penalty = Some(1.2);
             assert!(request.validate().is_ok());
+            request.top_k = Some(0); // Also disabled
+            assert!(request.validate().is_ok());
 
             // Invalid parameters
-            request.top_k = Some(0); // Invalid
+            request.top_k = Some(-2); // Invalid
             assert!(request.validate().is_err());
         }
 
@@ -1066,6 +1646,60 @@ mod tests {
             assert!(request.validate().is_ok());
         }
 
+        #[test]
+        fn test_guided_decoding_validation() {
+            use crate::protocols::spec::{JsonSchemaFormat, StructuredOutputsParams};
+
+            let mut request = create_valid_chat_request();
+            request.response_format = Some(ResponseFormat::JsonSchema {
+                json_schema: JsonSchemaFormat {
+                    name: "person".to_string(),
+                    schema: serde_json::json!({"type": "object"}),
+                    strict: None,
+                },
+            });
+            assert!(request.validate_guided_decoding().is_ok());
+
+            request.response_format = Some(ResponseFormat::JsonSchema {
+                json_schema: JsonSchemaFormat {
+                    name: "person".to_string(),
+                    schema: serde_json::json!({"type": "objekt"}),
+                    strict: None,
+                },
+            });
+            let err = request.validate_guided_decoding().unwrap_err();
+            assert_eq!(err.parameter(), Some("response_format.json_schema.schema"));
+
+            // structured_outputs.json may be a schema string
+            request.response_format = None;
+            request.structured_outputs = Some(StructuredOutputsParams {
+                json: Some(serde_json::json!("{\"type\": \"string\"}")),
+                ..Default::default()
+            });
+            assert!(request.validate_guided_decoding().is_ok());
+            request.structured_outputs = Some(StructuredOutputsParams {
+                json: Some(serde_json::json!("{\"type\": ")),
+                ..Default::default()
+            });
+            let err = request.validate_guided_decoding().unwrap_err();
+            assert_eq!(err.parameter(), Some("structured_outputs.json"));
+
+            // One kind of constraint per request
+            request.structured_outputs = Some(StructuredOutputsParams {
+                regex: Some("[a-z]+".to_string()),
+                choice: Some(vec!["yes".to_string(), "no".to_string()]),
+                ..Default::default()
+            });
+            assert!(request.validate_guided_decoding().is_err());
+            request.structured_outputs = Some(StructuredOutputsParams {
+                choice: Some(vec!["yes".to_string(), "no".to_string()]),
+                ..Default::default()
+            });
+            assert!(request.validate_guided_decoding().is_ok());
+            request.response_format = Some(ResponseFormat::JsonObject);
+            assert!(request.validate_guided_decoding().is_err());
+        }
+
         #[test]
         fn test_stop_sequences_validation() {
             let mut request = create_valid_chat_request();
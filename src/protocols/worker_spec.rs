@@ -40,7 +40,7 @@ pub struct WorkerConfigRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chat_template: Option<String>,
 
-    /// Additional labels (optional)
+    /// Additional labels (optional), matched by the X-Router-Require-Tags header
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub labels: HashMap<String, String>,
 }
@@ -69,6 +69,16 @@ pub struct WorkerInfo {
     /// Whether the worker is healthy
     pub is_healthy: bool,
 
+    /// Whether the worker can be selected for requests
+    pub is_enabled: bool,
+
+    /// Whether the worker is warming up before it is selected
+    pub is_warming: bool,
+
+    /// Model version last reported by the worker
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_version: Option<String>,
+
     /// Current load on the worker
     pub load: usize,
 
@@ -93,7 +103,7 @@ pub struct WorkerListResponse {
     /// List of workers
     pub workers: Vec<WorkerInfo>,
 
-    /// Total count
+    /// Number of workers matching the filters, before paging
     pub total: usize,
 
     /// Statistics
@@ -118,6 +128,95 @@ pub struct WorkerTypeStats {
     pub decode: usize,
 }
 
+/// Router-side snapshot of a worker, built from in-memory state only
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerSnapshot {
+    /// Worker URL
+    pub url: String,
+
+    /// Model ID this worker serves
+    pub model_id: String,
+
+    /// Worker type
+    pub worker_type: String,
+
+    /// Whether the worker is healthy
+    pub is_healthy: bool,
+
+    /// Whether the worker is draining before removal
+    pub is_draining: bool,
+
+    /// Whether the worker is warming up before it is selected
+    pub is_warming: bool,
+
+    /// Whether the worker can be selected for requests
+    pub is_enabled: bool,
+
+    /// Whether the worker is ejected as an error rate outlier
+    pub is_ejected: bool,
+
+    /// Model version last reported by the worker
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_version: Option<String>,
+
+    /// In-flight requests tracked by the router
+    pub load: usize,
+
+    /// Requests routed to this worker so far
+    pub processed_requests: usize,
+
+    /// Circuit breaker state (closed, open or half_open)
+    pub circuit_breaker_state: String,
+
+    /// Worker priority
+    pub priority: u32,
+
+    /// Worker cost factor
+    pub cost: f32,
+}
+
+/// Worker snapshot list response
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerSnapshotResponse {
+    /// Snapshot of every registered worker
+    pub workers: Vec<WorkerSnapshot>,
+
+    /// Total count
+    pub total: usize,
+}
+
+/// Circuit breaker state and counters for one worker
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerSnapshot {
+    /// Worker URL
+    pub url: String,
+
+    /// Circuit breaker state (closed, open or half_open)
+    pub state: String,
+
+    /// Failures since the last success
+    pub consecutive_failures: u32,
+
+    /// Successes since the last failure
+    pub consecutive_successes: u32,
+
+    /// Failures recorded over the worker's lifetime
+    pub total_failures: u64,
+
+    /// Successes recorded over the worker's lifetime
+    pub total_successes: u64,
+
+    /// Probe requests currently admitted while half-open
+    pub half_open_in_flight: u32,
+
+    /// Seconds since the last recorded failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secs_since_last_failure: Option<f64>,
+
+    /// Seconds since the state last changed
+    pub secs_since_last_state_change: f64,
+}
+
 /// Worker update request
 #[derive(Debug, Clone, Deserialize)]
 pub struct WorkerUpdateRequest {
@@ -151,6 +250,31 @@ pub struct WorkerErrorResponse {
     pub code: String,
 }
 
+/// Outcome of adding one worker in a bulk request
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkWorkerResult {
+    pub url: String,
+    pub success: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worker: Option<WorkerInfo>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<WorkerErrorResponse>,
+}
+
+/// Bulk worker registration response
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkWorkerResponse {
+    /// One result per requested worker, in request order
+    pub results: Vec<BulkWorkerResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
 /// Server info response from /get_server_info endpoint
 #[derive(Debug, Clone, Deserialize)]
 pub struct ServerInfo {
@@ -0,0 +1,805 @@
+//! OpenAI Batch API
+//!
+//! A batch is validated up front and stored in the batch storage, then its
+//! requests are dispatched in the background, at most `batch_max_concurrency`
+//! at a time, through the same router as regular requests so the configured
+//! policy spreads them across workers. The batch storage uses the history
+//! backend but keeps its entries apart from stored responses, so batches
+//! cannot be read or deleted through `/v1/responses`; with
+//! `history_backend = none` batches are refused.
+//!
+//! A running batch writes its status and counters at most once per
+//! [`PROGRESS_INTERVAL`], together with the output lines finished since the
+//! previous write as a separate chunk, so progress costs grow with the new
+//! lines only. Requests left when the completion window ends are counted as
+//! failed and the batch expires; batches still running when the shutdown
+//! drain runs out are recorded as cancelled.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use axum::{
+    body::to_bytes,
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::{stream, StreamExt};
+use serde_json::Value;
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+
+use crate::data_connector::{ResponseId, ResponseStorage, SharedResponseStorage, StoredResponse};
+use crate::middleware::InFlightGuard;
+use crate::protocols::spec::{
+    Batch, BatchCreateRequest, BatchRequestCounts, BatchRequestInput, BatchRequestOutput,
+    BatchResponse, BatchStatus, ChatCompletionRequest, CompletionRequest, EmbeddingRequest,
+    GenerationRequest,
+};
+use crate::routers::error_response::{error_body, error_response};
+use crate::routers::header_utils;
+
+/// Endpoints the requests of a batch can target
+pub const SUPPORTED_ENDPOINTS: [&str; 3] =
+    ["/v1/chat/completions", "/v1/completions", "/v1/embeddings"];
+
+/// How often a running batch writes its progress to the storage
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Metadata key of the batch object in its stored status entry
+const BATCH_METADATA_KEY: &str = "batch";
+
+/// Metadata key of the number of output chunks written so far
+const OUTPUT_CHUNKS_METADATA_KEY: &str = "output_chunks";
+
+/// Time a batch has to finish for a `completion_window`
+fn completion_window(window: &str) -> Option<Duration> {
+    (window == "24h").then_some(Duration::from_secs(24 * 60 * 60))
+}
+
+/// Storage ID of a batch's requests
+fn input_id(batch_id: &str) -> ResponseId {
+    ResponseId::from_string(format!("{}:input", batch_id))
+}
+
+/// Storage ID of the `index`-th chunk of a batch's output lines
+fn output_chunk_id(batch_id: &str, index: usize) -> ResponseId {
+    ResponseId::from_string(format!("{}:output:{}", batch_id, index))
+}
+
+/// Batch jobs running in the background of this router
+#[derive(Debug, Default)]
+pub struct BatchJobs {
+    cancelled: AtomicBool,
+    cancel: Notify,
+}
+
+impl BatchJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop every running batch, recording it as cancelled
+    pub fn cancel_all(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        self.cancel.notify_waiters();
+    }
+
+    async fn cancelled(&self) {
+        loop {
+            // Register before checking so a cancellation in between is not missed
+            let cancel = self.cancel.notified();
+            if self.cancelled.load(Ordering::Acquire) {
+                return;
+            }
+            cancel.await;
+        }
+    }
+}
+
+/// What a batch needs to run
+#[derive(Clone)]
+pub struct BatchContext {
+    /// Routes the requests of the batch
+    pub dispatcher: Arc<dyn BatchDispatch>,
+    /// Batch storage, apart from stored responses
+    pub storage: SharedResponseStorage,
+    pub jobs: Arc<BatchJobs>,
+    /// Requests of one batch dispatched at a time
+    pub concurrency: usize,
+    /// Client headers sent with every request of the batch, see [`forwarded_headers`]
+    pub headers: HeaderMap,
+}
+
+/// Headers of a batch creation request that its requests are sent with
+///
+/// Only credentials and trace context carry over. Routing headers such as a
+/// worker pin or an idempotency key describe the creation request, not each
+/// request of the batch.
+pub fn forwarded_headers(headers: &HeaderMap) -> HeaderMap {
+    headers
+        .iter()
+        .filter(|(name, _)| {
+            *name == AUTHORIZATION || header_utils::TRACE_HEADER_NAMES.contains(&name.as_str())
+        })
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+/// A validated request of a batch
+#[derive(Debug, Clone)]
+pub enum BatchItem {
+    Chat(Box<ChatCompletionRequest>),
+    Completion(Box<CompletionRequest>),
+    Embedding(EmbeddingRequest),
+}
+
+impl BatchItem {
+    fn parse(endpoint: &str, body: Value) -> Result<Self, serde_json::Error> {
+        Ok(match endpoint {
+            "/v1/chat/completions" => Self::Chat(Box::new(serde_json::from_value(body)?)),
+            "/v1/completions" => Self::Completion(Box::new(serde_json::from_value(body)?)),
+            _ => Self::Embedding(serde_json::from_value(body)?),
+        })
+    }
+
+    fn request(&self) -> &dyn GenerationRequest {
+        match self {
+            Self::Chat(req) => req.as_ref(),
+            Self::Completion(req) => req.as_ref(),
+            Self::Embedding(req) => req,
+        }
+    }
+}
+
+/// Sends the requests of a batch to the workers
+#[async_trait]
+pub trait BatchDispatch: Send + Sync {
+    async fn dispatch(&self, item: BatchItem, headers: &HeaderMap) -> Response;
+}
+
+/// OpenAI-style 400 naming the offending parameter
+fn invalid(message: String, param: &str) -> Response {
+    let status = StatusCode::BAD_REQUEST;
+    (status, Json(error_body(status, message, None, Some(param)))).into_response()
+}
+
+/// Response for every batch endpoint when the router keeps no history
+pub fn storage_disabled() -> Response {
+    error_response(
+        StatusCode::NOT_IMPLEMENTED,
+        "Batches need a history backend to record their results; the router runs with history_backend none",
+    )
+}
+
+fn not_found(batch_id: &str) -> Response {
+    error_response(
+        StatusCode::NOT_FOUND,
+        format!("No batch found with id '{}'", batch_id),
+    )
+}
+
+fn storage_error(operation: &str, batch_id: &str, e: impl std::fmt::Display) -> Response {
+    error!("Failed to {} batch {}: {}", operation, batch_id, e);
+    error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        format!("Failed to {} batch: {}", operation, e),
+    )
+}
+
+/// Check a batch creation request and parse each of its requests
+#[allow(clippy::result_large_err)]
+fn validate(request: &BatchCreateRequest) -> Result<Vec<BatchItem>, Response> {
+    if request.input_file_id.is_some() {
+        return Err(invalid(
+            "Input files are not supported by the router; pass the requests inline in 'requests'"
+                .to_string(),
+            "input_file_id",
+        ));
+    }
+    if !SUPPORTED_ENDPOINTS.contains(&request.endpoint.as_str()) {
+        return Err(invalid(
+            format!(
+                "Unsupported endpoint '{}', expected one of {:?}",
+                request.endpoint, SUPPORTED_ENDPOINTS
+            ),
+            "endpoint",
+        ));
+    }
+    if completion_window(&request.completion_window).is_none() {
+        return Err(invalid(
+            format!(
+                "Unsupported completion_window '{}', expected '24h'",
+                request.completion_window
+            ),
+            "completion_window",
+        ));
+    }
+    if request.requests.is_empty() {
+        return Err(invalid(
+            "A batch needs at least one request".to_string(),
+            "requests",
+        ));
+    }
+
+    let mut custom_ids = HashSet::new();
+    let mut items = Vec::with_capacity(request.requests.len());
+    for (i, line) in request.requests.iter().enumerate() {
+        if !custom_ids.insert(line.custom_id.as_str()) {
+            return Err(invalid(
+                format!("Duplicate custom_id '{}'", line.custom_id),
+                &format!("requests[{}].custom_id", i),
+            ));
+        }
+        if !line.method.eq_ignore_ascii_case("POST") {
+            return Err(invalid(
+                format!("Unsupported method '{}', expected POST", line.method),
+                &format!("requests[{}].method", i),
+            ));
+        }
+        if line.url != request.endpoint {
+            return Err(invalid(
+                format!(
+                    "Request url '{}' does not match the batch endpoint '{}'",
+                    line.url, request.endpoint
+                ),
+                &format!("requests[{}].url", i),
+            ));
+        }
+
+        let param = format!("requests[{}].body", i);
+        let item = BatchItem::parse(&request.endpoint, line.body.clone())
+            .map_err(|e| invalid(format!("Invalid request body: {}", e), &param))?;
+        if item.request().is_stream() {
+            return Err(invalid(
+                "Streaming is not supported in batches".to_string(),
+                &param,
+            ));
+        }
+        if let Err(e) = item.request().validate_sampling_params() {
+            return Err(invalid(e.to_string(), &param));
+        }
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// Write the status entry of a batch, naming how many output chunks it has
+async fn store_status(
+    storage: &dyn ResponseStorage,
+    batch: &Batch,
+    output_chunks: usize,
+) -> Result<(), String> {
+    let batch_json = serde_json::to_value(batch).map_err(|e| e.to_string())?;
+    let mut stored = StoredResponse::new(String::new(), String::new(), None);
+    stored.id = ResponseId::from_string(batch.id.clone());
+    stored.metadata = HashMap::from([
+        (BATCH_METADATA_KEY.to_string(), batch_json),
+        (OUTPUT_CHUNKS_METADATA_KEY.to_string(), output_chunks.into()),
+    ]);
+    storage
+        .store_response(stored)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Write one entry holding `input` or `output` under `id`
+async fn store_entry(
+    storage: &dyn ResponseStorage,
+    id: ResponseId,
+    input: String,
+    output: String,
+) -> Result<(), String> {
+    let mut stored = StoredResponse::new(input, output, None);
+    stored.id = id;
+    storage
+        .store_response(stored)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Read the status of a stored batch and its number of output chunks
+async fn load(
+    storage: &dyn ResponseStorage,
+    batch_id: &str,
+) -> Result<Option<(Batch, usize)>, String> {
+    let id = ResponseId::from_string(batch_id.to_string());
+    let Some(stored) = storage.get_response(&id).await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let Some(batch) = stored.metadata.get(BATCH_METADATA_KEY) else {
+        return Ok(None);
+    };
+    let batch = serde_json::from_value(batch.clone()).map_err(|e| e.to_string())?;
+    let output_chunks = stored
+        .metadata
+        .get(OUTPUT_CHUNKS_METADATA_KEY)
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+    Ok(Some((batch, output_chunks)))
+}
+
+/// Read the output lines of a batch from its chunks
+async fn load_output(
+    storage: &dyn ResponseStorage,
+    batch_id: &str,
+    output_chunks: usize,
+) -> Result<String, String> {
+    let mut output = String::new();
+    for index in 0..output_chunks {
+        let id = output_chunk_id(batch_id, index);
+        match storage.get_response(&id).await.map_err(|e| e.to_string())? {
+            Some(chunk) => output.push_str(&chunk.output),
+            None => return Err(format!("output chunk {} is missing", index)),
+        }
+    }
+    Ok(output)
+}
+
+/// Validate and store a batch, then start running it in the background
+///
+/// `in_flight` keeps the batch counted in the shutdown drain until it ends.
+pub async fn create_batch(
+    ctx: BatchContext,
+    in_flight: InFlightGuard,
+    request: BatchCreateRequest,
+) -> Response {
+    let items = match validate(&request) {
+        Ok(items) => items,
+        Err(response) => return response,
+    };
+    let window = completion_window(&request.completion_window).unwrap_or_default();
+
+    let now = chrono::Utc::now().timestamp();
+    let batch = Batch {
+        id: format!("batch_{}", uuid::Uuid::new_v4().simple()),
+        object: "batch".to_string(),
+        endpoint: request.endpoint,
+        completion_window: request.completion_window,
+        status: BatchStatus::InProgress,
+        created_at: now,
+        in_progress_at: Some(now),
+        expires_at: Some(now + window.as_secs() as i64),
+        completed_at: None,
+        expired_at: None,
+        cancelled_at: None,
+        request_counts: BatchRequestCounts {
+            total: items.len(),
+            ..Default::default()
+        },
+        metadata: request.metadata,
+    };
+    let input = match serde_json::to_string(&request.requests) {
+        Ok(input) => input,
+        Err(e) => return storage_error("store", &batch.id, e),
+    };
+    let stored = async {
+        store_entry(
+            ctx.storage.as_ref(),
+            input_id(&batch.id),
+            input,
+            String::new(),
+        )
+        .await?;
+        store_status(ctx.storage.as_ref(), &batch, 0).await
+    };
+    if let Err(e) = stored.await {
+        return storage_error("store", &batch.id, e);
+    }
+
+    info!(
+        "Created batch {} with {} requests to {}",
+        batch.id,
+        items.len(),
+        batch.endpoint
+    );
+    let deadline = Instant::now() + window;
+    let running = batch.clone();
+    tokio::spawn(async move {
+        run_batch(ctx, running, request.requests, items, deadline).await;
+        drop(in_flight);
+    });
+    Json(batch).into_response()
+}
+
+/// Output lines of a running batch, written to the storage in chunks
+struct OutputWriter {
+    /// Lines finished since the last chunk was written
+    pending: String,
+    /// Chunks written so far
+    chunks: usize,
+}
+
+impl OutputWriter {
+    /// Write the pending lines as a new chunk, then the batch status
+    ///
+    /// Lines that could not be written stay pending for the next attempt.
+    async fn flush(&mut self, storage: &dyn ResponseStorage, batch: &Batch) {
+        if !self.pending.is_empty() {
+            let id = output_chunk_id(&batch.id, self.chunks);
+            match store_entry(storage, id, String::new(), self.pending.clone()).await {
+                Ok(()) => {
+                    self.chunks += 1;
+                    self.pending.clear();
+                }
+                Err(e) => {
+                    error!("Failed to record output of batch {}: {}", batch.id, e);
+                }
+            }
+        }
+        if let Err(e) = store_status(storage, batch, self.chunks).await {
+            error!("Failed to record progress of batch {}: {}", batch.id, e);
+        }
+    }
+}
+
+/// Dispatch every request of a batch and record the responses in request order
+///
+/// Stops early when `deadline` passes or the batch jobs are cancelled.
+async fn run_batch(
+    ctx: BatchContext,
+    mut batch: Batch,
+    lines: Vec<BatchRequestInput>,
+    items: Vec<BatchItem>,
+    deadline: Instant,
+) {
+    let mut responses = stream::iter(lines.into_iter().zip(items))
+        .map(|(line, item)| {
+            let dispatcher = ctx.dispatcher.clone();
+            let headers = &ctx.headers;
+            async move { (line.custom_id, dispatcher.dispatch(item, headers).await) }
+        })
+        .buffered(ctx.concurrency.max(1));
+
+    let mut output = OutputWriter {
+        pending: String::new(),
+        chunks: 0,
+    };
+    let mut last_update = Instant::now();
+    let deadline = tokio::time::Instant::from_std(deadline);
+    let outcome = loop {
+        let next = tokio::select! {
+            next = responses.next() => next,
+            _ = tokio::time::sleep_until(deadline) => break BatchStatus::Expired,
+            _ = ctx.jobs.cancelled() => break BatchStatus::Cancelled,
+        };
+        let Some((custom_id, response)) = next else {
+            break BatchStatus::Completed;
+        };
+
+        let status = response.status();
+        let (status_code, body) = match to_bytes(response.into_body(), usize::MAX).await {
+            Ok(bytes) => (
+                status.as_u16(),
+                serde_json::from_slice(&bytes).unwrap_or_else(|_| {
+                    Value::String(String::from_utf8_lossy(&bytes).into_owned())
+                }),
+            ),
+            Err(e) => {
+                let status = StatusCode::BAD_GATEWAY;
+                let message = format!("Failed to read worker response: {}", e);
+                (status.as_u16(), error_body(status, message, None, None))
+            }
+        };
+        if (200..300).contains(&status_code) {
+            batch.request_counts.completed += 1;
+        } else {
+            batch.request_counts.failed += 1;
+        }
+
+        let line = BatchRequestOutput {
+            id: format!("batch_req_{}", uuid::Uuid::new_v4().simple()),
+            custom_id,
+            response: BatchResponse { status_code, body },
+        };
+        if let Ok(line) = serde_json::to_string(&line) {
+            output.pending.push_str(&line);
+            output.pending.push('\n');
+        }
+
+        if last_update.elapsed() >= PROGRESS_INTERVAL {
+            output.flush(ctx.storage.as_ref(), &batch).await;
+            last_update = Instant::now();
+        }
+    };
+    // Requests still being dispatched are abandoned
+    drop(responses);
+
+    let now = chrono::Utc::now().timestamp();
+    let counts = &mut batch.request_counts;
+    match outcome {
+        BatchStatus::Expired => {
+            let unfinished = counts.total - counts.completed - counts.failed;
+            counts.failed += unfinished;
+            batch.expired_at = Some(now);
+            warn!(
+                "Batch {} expired with {} requests unfinished",
+                batch.id, unfinished
+            );
+        }
+        BatchStatus::Cancelled => {
+            batch.cancelled_at = Some(now);
+            warn!(
+                "Batch {} cancelled by shutdown after {} of {} requests",
+                batch.id,
+                counts.completed + counts.failed,
+                counts.total
+            );
+        }
+        _ => {
+            batch.completed_at = Some(now);
+            info!(
+                "Batch {} completed: {} succeeded, {} failed",
+                batch.id, counts.completed, counts.failed
+            );
+        }
+    }
+    batch.status = outcome;
+    output.flush(ctx.storage.as_ref(), &batch).await;
+}
+
+/// Current state of a batch
+pub async fn get_batch(storage: &dyn ResponseStorage, batch_id: &str) -> Response {
+    match load(storage, batch_id).await {
+        Ok(Some((batch, _))) => Json(batch).into_response(),
+        Ok(None) => not_found(batch_id),
+        Err(e) => storage_error("read", batch_id, e),
+    }
+}
+
+/// Output lines of a batch as JSONL, one per finished request so far
+pub async fn get_batch_output(storage: &dyn ResponseStorage, batch_id: &str) -> Response {
+    let output_chunks = match load(storage, batch_id).await {
+        Ok(Some((_, output_chunks))) => output_chunks,
+        Ok(None) => return not_found(batch_id),
+        Err(e) => return storage_error("read", batch_id, e),
+    };
+    match load_output(storage, batch_id, output_chunks).await {
+        Ok(output) => ([(CONTENT_TYPE, "application/jsonl")], output).into_response(),
+        Err(e) => storage_error("read", batch_id, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_connector::MemoryResponseStorage;
+    use crate::middleware::InFlightRequests;
+    use serde_json::json;
+
+    /// Answers chat requests with their model name, failing model "bad"
+    struct EchoDispatcher;
+
+    #[async_trait]
+    impl BatchDispatch for EchoDispatcher {
+        async fn dispatch(&self, item: BatchItem, _headers: &HeaderMap) -> Response {
+            let model = item.request().get_model().unwrap_or_default().to_string();
+            if model == "bad" {
+                return error_response(StatusCode::BAD_REQUEST, "bad model");
+            }
+            Json(json!({"model": model})).into_response()
+        }
+    }
+
+    fn batch_request(models: &[&str]) -> BatchCreateRequest {
+        serde_json::from_value(json!({
+            "endpoint": "/v1/chat/completions",
+            "completion_window": "24h",
+            "requests": models.iter().enumerate().map(|(i, model)| json!({
+                "custom_id": format!("req-{}", i),
+                "method": "POST",
+                "url": "/v1/chat/completions",
+                "body": {"model": model, "messages": [{"role": "user", "content": "hi"}]},
+            })).collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    async fn error_param(response: Response) -> Value {
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        body["error"]["param"].clone()
+    }
+
+    #[tokio::test]
+    async fn test_validate_batch_request() {
+        assert_eq!(validate(&batch_request(&["a", "b"])).unwrap().len(), 2);
+
+        let mut request = batch_request(&["a"]);
+        request.endpoint = "/v1/responses".to_string();
+        assert_eq!(
+            error_param(validate(&request).unwrap_err()).await,
+            "endpoint"
+        );
+
+        let mut request = batch_request(&["a", "b"]);
+        request.requests[1].custom_id = "req-0".to_string();
+        assert_eq!(
+            error_param(validate(&request).unwrap_err()).await,
+            "requests[1].custom_id"
+        );
+
+        let mut request = batch_request(&["a"]);
+        request.requests[0].url = "/v1/completions".to_string();
+        assert_eq!(
+            error_param(validate(&request).unwrap_err()).await,
+            "requests[0].url"
+        );
+
+        let mut request = batch_request(&["a"]);
+        request.requests[0].body["stream"] = json!(true);
+        assert_eq!(
+            error_param(validate(&request).unwrap_err()).await,
+            "requests[0].body"
+        );
+
+        let mut request = batch_request(&["a"]);
+        request.input_file_id = Some("file-abc".to_string());
+        assert_eq!(
+            error_param(validate(&request).unwrap_err()).await,
+            "input_file_id"
+        );
+    }
+
+    fn batch_context(dispatcher: Arc<dyn BatchDispatch>) -> BatchContext {
+        BatchContext {
+            dispatcher,
+            storage: Arc::new(MemoryResponseStorage::new()),
+            jobs: Arc::new(BatchJobs::new()),
+            concurrency: 2,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Poll a batch until it leaves `in_progress`
+    async fn wait_finished(storage: &dyn ResponseStorage, batch_id: &str) -> (Batch, String) {
+        for _ in 0..100 {
+            let (batch, chunks) = load(storage, batch_id).await.unwrap().unwrap();
+            if batch.status != BatchStatus::InProgress {
+                let output = load_output(storage, batch_id, chunks).await.unwrap();
+                return (batch, output);
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("batch did not finish");
+    }
+
+    /// Never answers, so the batch can only end by expiry or cancellation
+    struct StuckDispatcher;
+
+    #[async_trait]
+    impl BatchDispatch for StuckDispatcher {
+        async fn dispatch(&self, _item: BatchItem, _headers: &HeaderMap) -> Response {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_runs_to_completion() {
+        let ctx = batch_context(Arc::new(EchoDispatcher));
+        let storage = ctx.storage.clone();
+        let in_flight = Arc::new(InFlightRequests::new());
+        let response =
+            create_batch(ctx, in_flight.track(), batch_request(&["a", "bad", "c"])).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let batch: Batch = serde_json::from_slice(&body).unwrap();
+        assert_eq!(batch.request_counts.total, 3);
+        assert_eq!(batch.expires_at, Some(batch.created_at + 24 * 60 * 60));
+
+        let (stored, output) = wait_finished(storage.as_ref(), &batch.id).await;
+        assert_eq!(stored.status, BatchStatus::Completed);
+        assert_eq!(stored.request_counts.completed, 2);
+        assert_eq!(stored.request_counts.failed, 1);
+        assert!(stored.completed_at.is_some());
+
+        // Output lines keep the order of the requests
+        let lines: Vec<BatchRequestOutput> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let custom_ids: Vec<&str> = lines.iter().map(|l| l.custom_id.as_str()).collect();
+        assert_eq!(custom_ids, ["req-0", "req-1", "req-2"]);
+        assert_eq!(lines[0].response.body, json!({"model": "a"}));
+        assert_eq!(lines[1].response.status_code, 400);
+
+        // The job stays in the shutdown drain until it has recorded its end
+        tokio::time::timeout(Duration::from_secs(1), in_flight.wait_idle())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_batch(storage.as_ref(), "batch_missing").await.status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_output_is_written_in_chunks() {
+        let storage = MemoryResponseStorage::new();
+        let batch: Batch = serde_json::from_value(json!({
+            "id": "batch_chunks",
+            "object": "batch",
+            "endpoint": "/v1/chat/completions",
+            "completion_window": "24h",
+            "status": "in_progress",
+            "created_at": 0,
+            "request_counts": {"total": 3, "completed": 0, "failed": 0},
+        }))
+        .unwrap();
+
+        let mut output = OutputWriter {
+            pending: "a\n".to_string(),
+            chunks: 0,
+        };
+        output.flush(&storage, &batch).await;
+        // A flush without new lines only updates the status
+        output.flush(&storage, &batch).await;
+        output.pending.push_str("b\nc\n");
+        output.flush(&storage, &batch).await;
+
+        let (_, chunks) = load(&storage, "batch_chunks").await.unwrap().unwrap();
+        assert_eq!(chunks, 2);
+        assert_eq!(
+            load_output(&storage, "batch_chunks", chunks).await.unwrap(),
+            "a\nb\nc\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_expires_after_completion_window() {
+        let ctx = batch_context(Arc::new(StuckDispatcher));
+        let storage = ctx.storage.clone();
+        let request = batch_request(&["a", "b"]);
+        let items = validate(&request).unwrap();
+        let batch: Batch = serde_json::from_value(json!({
+            "id": "batch_expiring",
+            "object": "batch",
+            "endpoint": "/v1/chat/completions",
+            "completion_window": "24h",
+            "status": "in_progress",
+            "created_at": 0,
+            "request_counts": {"total": 2, "completed": 0, "failed": 0},
+        }))
+        .unwrap();
+
+        let deadline = Instant::now() + Duration::from_millis(20);
+        run_batch(ctx, batch, request.requests, items, deadline).await;
+
+        let (stored, output) = wait_finished(storage.as_ref(), "batch_expiring").await;
+        assert_eq!(stored.status, BatchStatus::Expired);
+        assert!(stored.expired_at.is_some());
+        assert_eq!(stored.request_counts.failed, 2);
+        assert!(output.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_batch_is_recorded() {
+        let ctx = batch_context(Arc::new(StuckDispatcher));
+        let storage = ctx.storage.clone();
+        let jobs = ctx.jobs.clone();
+        let response = create_batch(
+            ctx,
+            Arc::new(InFlightRequests::new()).track(),
+            batch_request(&["a"]),
+        )
+        .await;
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let batch: Batch = serde_json::from_slice(&body).unwrap();
+
+        jobs.cancel_all();
+        let (stored, _) = wait_finished(storage.as_ref(), &batch.id).await;
+        assert_eq!(stored.status, BatchStatus::Cancelled);
+        assert!(stored.cancelled_at.is_some());
+        assert_eq!(stored.request_counts.failed, 0);
+    }
+}
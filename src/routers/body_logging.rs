@@ -0,0 +1,254 @@
+//! Request and response body logging for debugging
+//!
+//! Off by default and enabled per route, in regular HTTP mode only. Bodies,
+//! including the events of streamed responses, are logged at trace level
+//! after the configured fields are replaced with a placeholder, so prompts and
+//! completions stay out of the logs while parameters like `model` and
+//! `temperature` remain visible.
+
+use crate::config::BodyLoggingConfig;
+use serde_json::Value;
+use tracing::{trace, warn};
+
+/// Placeholder written over redacted fields
+pub const REDACTED: &str = "[REDACTED]";
+
+/// One step of a redaction path
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// `key`: the field of an object
+    Field(String),
+    /// `key[]`: every element of the array in the field
+    Each(String),
+}
+
+/// Logs bodies of selected routes with configured fields redacted
+#[derive(Debug, Clone)]
+pub struct BodyLogger {
+    routes: Vec<String>,
+    redact: Vec<Vec<Segment>>,
+}
+
+impl BodyLogger {
+    pub fn new(config: &BodyLoggingConfig) -> Self {
+        warn!(
+            "Body logging is enabled for {:?}; request and response bodies are logged at trace level and may contain user data outside the redacted fields {:?}",
+            config.routes, config.redact_fields
+        );
+        Self {
+            routes: config.routes.clone(),
+            redact: config
+                .redact_fields
+                .iter()
+                .map(|field| parse_path(field))
+                .collect(),
+        }
+    }
+
+    /// Whether bodies of `route` are logged
+    pub fn enabled_for(&self, route: &str) -> bool {
+        self.routes.iter().any(|r| r == route)
+    }
+
+    /// Copy of `body` with every configured field redacted
+    pub fn redacted(&self, body: &Value) -> Value {
+        let mut body = body.clone();
+        for path in &self.redact {
+            redact(&mut body, path);
+        }
+        body
+    }
+
+    /// Redacted JSON events of a server-sent event stream
+    ///
+    /// The `[DONE]` marker and events that are not JSON are left out.
+    pub fn redacted_events(&self, body: &[u8]) -> Vec<Value> {
+        body.split(|b| *b == b'\n')
+            .filter_map(|line| line.strip_prefix(b"data:"))
+            .filter_map(|data| serde_json::from_slice::<Value>(data.trim_ascii()).ok())
+            .map(|event| self.redacted(&event))
+            .collect()
+    }
+
+    /// Log the body sent to a worker
+    pub fn log_request(&self, route: &str, worker_url: &str, body: &Value) {
+        trace!(
+            route,
+            worker_url,
+            body = %self.redacted(body),
+            "Request body"
+        );
+    }
+
+    /// Log the body received from a worker
+    ///
+    /// Bodies that are not JSON cannot be redacted and are logged by size only.
+    pub fn log_response(&self, route: &str, worker_url: &str, status: u16, body: &[u8]) {
+        match serde_json::from_slice::<Value>(body) {
+            Ok(body) => trace!(
+                route,
+                worker_url,
+                status,
+                body = %self.redacted(&body),
+                "Response body"
+            ),
+            Err(_) => trace!(
+                route,
+                worker_url,
+                status,
+                "Response body ({} bytes, not JSON)",
+                body.len()
+            ),
+        }
+    }
+
+    /// Log the events of a response streamed from a worker
+    pub fn log_stream(&self, route: &str, worker_url: &str, status: u16, body: &[u8]) {
+        let events = Value::Array(self.redacted_events(body));
+        trace!(route, worker_url, status, events = %events, "Response stream");
+    }
+}
+
+fn parse_path(field: &str) -> Vec<Segment> {
+    field
+        .split('.')
+        .map(|segment| match segment.strip_suffix("[]") {
+            Some(name) => Segment::Each(name.to_string()),
+            None => Segment::Field(segment.to_string()),
+        })
+        .collect()
+}
+
+/// Replace the value at `path` with [`REDACTED`]; missing fields are ignored
+fn redact(value: &mut Value, path: &[Segment]) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    let Value::Object(map) = value else {
+        return;
+    };
+    match first {
+        Segment::Field(name) => {
+            let Some(field) = map.get_mut(name) else {
+                return;
+            };
+            if rest.is_empty() {
+                *field = Value::String(REDACTED.to_string());
+            } else {
+                redact(field, rest);
+            }
+        }
+        Segment::Each(name) => {
+            let Some(Value::Array(items)) = map.get_mut(name) else {
+                return;
+            };
+            for item in items {
+                if rest.is_empty() {
+                    *item = Value::String(REDACTED.to_string());
+                } else {
+                    redact(item, rest);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::default_body_redact_fields;
+    use serde_json::json;
+
+    fn logger(redact_fields: &[&str]) -> BodyLogger {
+        BodyLogger::new(&BodyLoggingConfig {
+            routes: vec!["/v1/chat/completions".to_string()],
+            redact_fields: redact_fields.iter().map(|f| f.to_string()).collect(),
+        })
+    }
+
+    #[test]
+    fn test_redacts_stream_events() {
+        let logger = logger(&["choices[].delta.content"]);
+        let body = concat!(
+            "data: {\"choices\": [{\"delta\": {\"content\": \"Hel\"}}]}\n\n",
+            ": keep-alive\n\n",
+            "data: {\"choices\": [{\"delta\": {\"content\": \"lo\"}, \"finish_reason\": \"stop\"}]}\n\n",
+            "data: [DONE]\n\n"
+        );
+
+        assert_eq!(
+            logger.redacted_events(body.as_bytes()),
+            vec![
+                json!({"choices": [{"delta": {"content": REDACTED}}]}),
+                json!({"choices": [{"delta": {"content": REDACTED}, "finish_reason": "stop"}]}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redacts_array_element_fields() {
+        let logger = logger(&["messages[].content"]);
+        let body = json!({
+            "model": "llama-3",
+            "temperature": 0.2,
+            "messages": [
+                {"role": "system", "content": "You are helpful"},
+                {"role": "user", "content": "My SSN is 123-45-6789"},
+            ],
+        });
+
+        assert_eq!(
+            logger.redacted(&body),
+            json!({
+                "model": "llama-3",
+                "temperature": 0.2,
+                "messages": [
+                    {"role": "system", "content": REDACTED},
+                    {"role": "user", "content": REDACTED},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_redacts_nested_fields_and_ignores_missing_ones() {
+        let logger = logger(&["choices[].message.content", "prompt", "usage.missing"]);
+        let body = json!({
+            "choices": [{"index": 0, "message": {"role": "assistant", "content": "hi"}}],
+            "usage": {"completion_tokens": 1},
+        });
+
+        let redacted = logger.redacted(&body);
+        assert_eq!(redacted["choices"][0]["message"]["content"], REDACTED);
+        assert_eq!(redacted["choices"][0]["message"]["role"], "assistant");
+        assert_eq!(redacted["usage"], json!({"completion_tokens": 1}));
+        assert!(redacted.get("prompt").is_none());
+    }
+
+    #[test]
+    fn test_default_fields_cover_prompts_and_completions() {
+        let defaults = default_body_redact_fields();
+        let defaults: Vec<&str> = defaults.iter().map(String::as_str).collect();
+        let logger = logger(&defaults);
+
+        let request =
+            logger.redacted(&json!({"model": "m", "prompt": ["a", "b"], "max_tokens": 8}));
+        assert_eq!(
+            request,
+            json!({"model": "m", "prompt": REDACTED, "max_tokens": 8})
+        );
+
+        let response = logger.redacted(&json!({"choices": [{"text": "secret", "index": 0}]}));
+        assert_eq!(
+            response["choices"][0],
+            json!({"text": REDACTED, "index": 0})
+        );
+    }
+
+    #[test]
+    fn test_enabled_only_for_configured_routes() {
+        let logger = logger(&[]);
+        assert!(logger.enabled_for("/v1/chat/completions"));
+        assert!(!logger.enabled_for("/v1/completions"));
+    }
+}
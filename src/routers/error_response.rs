@@ -0,0 +1,106 @@
+//! OpenAI-compatible error responses
+//!
+//! OpenAI SDKs parse error bodies of the form
+//! `{"error": {"message", "type", "param", "code"}}` and fail on plain text,
+//! so errors produced by the router itself use this shape. Error responses
+//! relayed from a worker are passed through unchanged.
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+
+/// OpenAI error type for a status code
+pub fn error_type(status: StatusCode) -> &'static str {
+    match status {
+        StatusCode::UNAUTHORIZED => "authentication_error",
+        StatusCode::FORBIDDEN => "permission_error",
+        StatusCode::TOO_MANY_REQUESTS => "rate_limit_error",
+        s if s.is_client_error() => "invalid_request_error",
+        _ => "server_error",
+    }
+}
+
+/// Error body with an optional machine-readable code and offending parameter
+pub fn error_body(
+    status: StatusCode,
+    message: impl Into<String>,
+    code: Option<&str>,
+    param: Option<&str>,
+) -> Value {
+    json!({
+        "error": {
+            "message": message.into(),
+            "type": error_type(status),
+            "param": param,
+            "code": code,
+        }
+    })
+}
+
+/// Error response with `message`, typed after `status`
+pub fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(error_body(status, message, None, None))).into_response()
+}
+
+/// Error response that also carries a machine-readable `code`
+pub fn error_response_with_code(
+    status: StatusCode,
+    message: impl Into<String>,
+    code: &str,
+) -> Response {
+    (status, Json(error_body(status, message, Some(code), None))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    #[tokio::test]
+    async fn test_error_response_shape() {
+        let response = error_response_with_code(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "No available workers",
+            "no_available_workers",
+        );
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers()["content-type"],
+            "application/json",
+            "OpenAI clients only parse JSON error bodies"
+        );
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "error": {
+                    "message": "No available workers",
+                    "type": "server_error",
+                    "param": null,
+                    "code": "no_available_workers",
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_error_type_by_status() {
+        assert_eq!(error_type(StatusCode::BAD_REQUEST), "invalid_request_error");
+        assert_eq!(
+            error_type(StatusCode::PAYLOAD_TOO_LARGE),
+            "invalid_request_error"
+        );
+        assert_eq!(error_type(StatusCode::UNAUTHORIZED), "authentication_error");
+        assert_eq!(
+            error_type(StatusCode::TOO_MANY_REQUESTS),
+            "rate_limit_error"
+        );
+        assert_eq!(error_type(StatusCode::NOT_FOUND), "invalid_request_error");
+        assert_eq!(error_type(StatusCode::BAD_GATEWAY), "server_error");
+    }
+}
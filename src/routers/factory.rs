@@ -250,8 +250,9 @@ impl RouterFactory {
             .cloned()
             .ok_or_else(|| "OpenAI mode requires at least one worker URL".to_string())?;
 
-        let router =
-            OpenAIRouter::new(base_url, Some(ctx.router_config.circuit_breaker.clone())).await?;
+        let router = OpenAIRouter::new(base_url, Some(ctx.router_config.circuit_breaker.clone()))
+            .await?
+            .with_stream_buffer_size(ctx.router_config.stream_buffer_size);
 
         Ok(Box::new(router))
     }
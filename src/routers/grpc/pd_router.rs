@@ -76,6 +76,7 @@ impl GrpcPDRouter {
             success_threshold: circuit_breaker_config.success_threshold,
             timeout_duration: Duration::from_secs(circuit_breaker_config.timeout_duration_secs),
             window_duration: Duration::from_secs(circuit_breaker_config.window_duration_secs),
+            half_open_max_concurrent: circuit_breaker_config.half_open_max_concurrent,
         };
 
         // Create gRPC clients for prefill workers
@@ -132,6 +133,9 @@ impl GrpcPDRouter {
                     endpoint: ctx.router_config.health_check.endpoint.clone(),
                     failure_threshold: ctx.router_config.health_check.failure_threshold,
                     success_threshold: ctx.router_config.health_check.success_threshold,
+                    method: ctx.router_config.health_check.method,
+                    expected_body: ctx.router_config.health_check.expected_body.clone(),
+                    generation_probe: ctx.router_config.health_check.generation_probe,
                 });
                 Arc::new(worker) as Arc<dyn Worker>
             })
@@ -153,23 +157,20 @@ impl GrpcPDRouter {
                     endpoint: ctx.router_config.health_check.endpoint.clone(),
                     failure_threshold: ctx.router_config.health_check.failure_threshold,
                     success_threshold: ctx.router_config.health_check.success_threshold,
+                    method: ctx.router_config.health_check.method,
+                    expected_body: ctx.router_config.health_check.expected_body.clone(),
+                    generation_probe: ctx.router_config.health_check.generation_probe,
                 });
                 Arc::new(worker) as Arc<dyn Worker>
             })
             .collect();
 
         // Initialize policies with workers if needed
-        if let Some(cache_aware) = prefill_policy
-            .as_any()
-            .downcast_ref::<crate::policies::CacheAwarePolicy>()
-        {
+        if let Some(cache_aware) = prefill_policy.as_cache_aware() {
             cache_aware.init_workers(&prefill_workers);
         }
 
-        if let Some(cache_aware) = decode_policy
-            .as_any()
-            .downcast_ref::<crate::policies::CacheAwarePolicy>()
-        {
+        if let Some(cache_aware) = decode_policy.as_cache_aware() {
             cache_aware.init_workers(&decode_workers);
         }
 
@@ -67,6 +67,7 @@ impl GrpcRouter {
             success_threshold: circuit_breaker_config.success_threshold,
             timeout_duration: Duration::from_secs(circuit_breaker_config.timeout_duration_secs),
             window_duration: Duration::from_secs(circuit_breaker_config.window_duration_secs),
+            half_open_max_concurrent: circuit_breaker_config.half_open_max_concurrent,
         };
 
         // Create gRPC clients for each worker
@@ -106,6 +107,9 @@ impl GrpcRouter {
                     endpoint: ctx.router_config.health_check.endpoint.clone(),
                     failure_threshold: ctx.router_config.health_check.failure_threshold,
                     success_threshold: ctx.router_config.health_check.success_threshold,
+                    method: ctx.router_config.health_check.method,
+                    expected_body: ctx.router_config.health_check.expected_body.clone(),
+                    generation_probe: ctx.router_config.health_check.generation_probe,
                 })
                 .with_grpc_client(client);
 
@@ -116,10 +120,7 @@ impl GrpcRouter {
         }
 
         // Initialize policy with workers if needed
-        if let Some(cache_aware) = policy
-            .as_any()
-            .downcast_ref::<crate::policies::CacheAwarePolicy>()
-        {
+        if let Some(cache_aware) = policy.as_cache_aware() {
             cache_aware.init_workers(&workers);
         }
 
@@ -1,6 +1,7 @@
+use crate::config::{HeaderPolicyConfig, UpstreamAuthConfig};
 use axum::body::Body;
 use axum::extract::Request;
-use axum::http::HeaderMap;
+use axum::http::{header, HeaderMap};
 
 /// Copy request headers to a Vec of name-value string pairs
 /// Used for forwarding headers to backend workers
@@ -52,6 +53,40 @@ fn should_forward_header(name: &str) -> bool {
     )
 }
 
+/// Whether a request body is JSON, going by its Content-Type
+///
+/// A missing Content-Type is treated as JSON, since clients of the JSON routes
+/// commonly leave it out.
+pub fn is_json_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return true;
+    };
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+/// Set the Content-Type of a request whose body is forwarded unchanged
+///
+/// The client's header is kept as-is so a multipart boundary survives; JSON is
+/// assumed when the client sent none.
+pub fn forward_content_type(
+    request: reqwest::RequestBuilder,
+    headers: Option<&HeaderMap>,
+) -> reqwest::RequestBuilder {
+    match headers.and_then(|h| h.get(header::CONTENT_TYPE)) {
+        Some(content_type) => request.header(header::CONTENT_TYPE, content_type),
+        None => request.header(header::CONTENT_TYPE, "application/json"),
+    }
+}
+
 /// Header names for W3C Trace Context (OpenTelemetry) propagation
 pub const TRACE_HEADER_NAMES: &[&str] = &["traceparent", "tracestate", "baggage"];
 
@@ -95,3 +130,284 @@ pub fn propagate_headers(
     }
     request
 }
+
+/// Set the Authorization header of an upstream request according to the auth mode
+///
+/// Passthrough copies the client's header, Inject sends the router's bearer
+/// token, and None sends nothing. Callers must not copy the client's
+/// Authorization header themselves.
+pub fn apply_upstream_auth(
+    request: reqwest::RequestBuilder,
+    auth: &UpstreamAuthConfig,
+    headers: Option<&HeaderMap>,
+) -> reqwest::RequestBuilder {
+    match auth {
+        UpstreamAuthConfig::Passthrough => {
+            match headers.and_then(|h| h.get(header::AUTHORIZATION)) {
+                Some(value) => request.header(header::AUTHORIZATION, value),
+                None => request,
+            }
+        }
+        UpstreamAuthConfig::Inject { key } => request.bearer_auth(key),
+        UpstreamAuthConfig::None => request,
+    }
+}
+
+/// Filter applied to client request headers before they are sent to workers
+#[derive(Debug, Clone)]
+pub struct HeaderForwardPolicy {
+    /// Lowercased allow list; empty allows every header not denied
+    allow: Vec<String>,
+    /// Lowercased deny list
+    deny: Vec<String>,
+    /// Authorization sent in place of the client's header
+    auth: UpstreamAuthConfig,
+}
+
+impl HeaderForwardPolicy {
+    pub fn new(config: &HeaderPolicyConfig) -> Self {
+        let lowercase = |names: &[String]| names.iter().map(|n| n.to_ascii_lowercase()).collect();
+        Self {
+            allow: lowercase(&config.allow),
+            deny: lowercase(&config.deny),
+            auth: UpstreamAuthConfig::Passthrough,
+        }
+    }
+
+    /// Use the given auth mode for the Authorization header
+    pub fn with_upstream_auth(mut self, auth: UpstreamAuthConfig) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Whether a header may be forwarded (name matched case-insensitively)
+    pub fn allows(&self, name: &str) -> bool {
+        let name = name.to_ascii_lowercase();
+        if self.deny.contains(&name) {
+            return false;
+        }
+        self.allow.is_empty()
+            || self.allow.contains(&name)
+            || TRACE_HEADER_NAMES.contains(&name.as_str())
+    }
+
+    /// Add the permitted client headers to an upstream request
+    ///
+    /// Content-Type and Content-Length are never copied since the router sets
+    /// them for the body it sends, nor Accept-Encoding since the router's
+    /// client negotiates compression with workers itself. Headers named in the client's `Connection`
+    /// header are hop-by-hop and dropped as well. Authorization follows the
+    /// upstream auth mode; in passthrough mode it is still subject to the
+    /// allow and deny lists.
+    pub fn apply(
+        &self,
+        mut request: reqwest::RequestBuilder,
+        headers: Option<&HeaderMap>,
+    ) -> reqwest::RequestBuilder {
+        if let UpstreamAuthConfig::Inject { key } = &self.auth {
+            request = request.bearer_auth(key);
+        }
+        let Some(headers) = headers else {
+            return request;
+        };
+
+        let connection_listed: Vec<String> = headers
+            .get_all(header::CONNECTION)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|name| name.trim().to_ascii_lowercase())
+            .collect();
+
+        for (name, value) in headers {
+            if name == header::CONTENT_TYPE
+                || name == header::CONTENT_LENGTH
+                || name == header::ACCEPT_ENCODING
+                || (name == header::AUTHORIZATION && self.auth != UpstreamAuthConfig::Passthrough)
+                || connection_listed.iter().any(|n| n == name.as_str())
+                || !self.allows(name.as_str())
+            {
+                continue;
+            }
+            request = request.header(name, value);
+        }
+        request
+    }
+}
+
+impl Default for HeaderForwardPolicy {
+    fn default() -> Self {
+        Self::new(&HeaderPolicyConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn forwarded(policy: &HeaderForwardPolicy, headers: &HeaderMap) -> HeaderMap {
+        let request = policy
+            .apply(
+                reqwest::Client::new().get("http://worker:8000/"),
+                Some(headers),
+            )
+            .build()
+            .unwrap();
+        request.headers().clone()
+    }
+
+    fn client_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("host", "router:3000"),
+            ("connection", "keep-alive, x-hop"),
+            ("x-hop", "1"),
+            ("content-type", "application/json"),
+            ("accept-encoding", "gzip, br"),
+            ("authorization", "Bearer secret"),
+            ("x-tenant-id", "acme"),
+            ("traceparent", "00-abc-def-01"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_default_policy_drops_hop_by_hop() {
+        let headers = forwarded(&HeaderForwardPolicy::default(), &client_headers());
+
+        assert!(headers.get("host").is_none());
+        assert!(headers.get("connection").is_none());
+        assert!(headers.get("x-hop").is_none());
+        assert!(headers.get("content-type").is_none());
+        assert!(headers.get("accept-encoding").is_none());
+        assert_eq!(headers.get("authorization").unwrap(), "Bearer secret");
+        assert_eq!(headers.get("x-tenant-id").unwrap(), "acme");
+        assert_eq!(headers.get("traceparent").unwrap(), "00-abc-def-01");
+    }
+
+    #[test]
+    fn test_allow_list_keeps_trace_headers() {
+        let policy = HeaderForwardPolicy::new(&HeaderPolicyConfig {
+            allow: vec!["X-Tenant-Id".to_string()],
+            ..Default::default()
+        });
+        let headers = forwarded(&policy, &client_headers());
+
+        assert_eq!(headers.get("x-tenant-id").unwrap(), "acme");
+        assert_eq!(headers.get("traceparent").unwrap(), "00-abc-def-01");
+        assert!(headers.get("authorization").is_none());
+    }
+
+    #[test]
+    fn test_deny_wins_over_allow() {
+        let mut config = HeaderPolicyConfig {
+            allow: vec!["authorization".to_string()],
+            ..Default::default()
+        };
+        config.deny.push("Authorization".to_string());
+        config.deny.push("traceparent".to_string());
+        let policy = HeaderForwardPolicy::new(&config);
+
+        assert!(!policy.allows("authorization"));
+        assert!(!policy.allows("traceparent"));
+    }
+
+    #[test]
+    fn test_upstream_auth_modes() {
+        let inject =
+            HeaderForwardPolicy::default().with_upstream_auth(UpstreamAuthConfig::Inject {
+                key: "sk-router".to_string(),
+            });
+        let headers = forwarded(&inject, &client_headers());
+        assert_eq!(headers.get_all("authorization").iter().count(), 1);
+        assert_eq!(headers.get("authorization").unwrap(), "Bearer sk-router");
+        assert_eq!(headers.get("x-tenant-id").unwrap(), "acme");
+
+        let none = HeaderForwardPolicy::default().with_upstream_auth(UpstreamAuthConfig::None);
+        let headers = forwarded(&none, &client_headers());
+        assert!(headers.get("authorization").is_none());
+        assert_eq!(headers.get("x-tenant-id").unwrap(), "acme");
+
+        // Injection applies even when there are no client headers
+        let request = inject
+            .apply(reqwest::Client::new().get("http://worker:8000/"), None)
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.headers().get("authorization").unwrap(),
+            "Bearer sk-router"
+        );
+    }
+
+    #[test]
+    fn test_apply_upstream_auth() {
+        let build = |auth: &UpstreamAuthConfig| {
+            apply_upstream_auth(
+                reqwest::Client::new().get("http://worker:8000/"),
+                auth,
+                Some(&client_headers()),
+            )
+            .build()
+            .unwrap()
+            .headers()
+            .get("authorization")
+            .cloned()
+        };
+
+        assert_eq!(
+            build(&UpstreamAuthConfig::Passthrough).unwrap(),
+            "Bearer secret"
+        );
+        assert_eq!(
+            build(&UpstreamAuthConfig::Inject {
+                key: "sk-router".to_string()
+            })
+            .unwrap(),
+            "Bearer sk-router"
+        );
+        assert!(build(&UpstreamAuthConfig::None).is_none());
+    }
+
+    #[test]
+    fn test_is_json_content_type() {
+        let with = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(value));
+            headers
+        };
+        assert!(is_json_content_type(&HeaderMap::new()));
+        assert!(is_json_content_type(&with("application/json")));
+        assert!(is_json_content_type(&with(
+            "Application/JSON; charset=utf-8"
+        )));
+        assert!(is_json_content_type(&with("application/merge-patch+json")));
+        assert!(!is_json_content_type(&with(
+            "multipart/form-data; boundary=abc"
+        )));
+        assert!(!is_json_content_type(&with("text/plain")));
+    }
+
+    #[test]
+    fn test_forward_content_type_keeps_boundary() {
+        let content_type = |headers: Option<&HeaderMap>| {
+            forward_content_type(reqwest::Client::new().post("http://worker:8000/"), headers)
+                .build()
+                .unwrap()
+                .headers()[header::CONTENT_TYPE]
+                .clone()
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("multipart/form-data; boundary=abc"),
+        );
+        assert_eq!(
+            content_type(Some(&headers)),
+            "multipart/form-data; boundary=abc"
+        );
+        assert_eq!(content_type(None), "application/json");
+    }
+}
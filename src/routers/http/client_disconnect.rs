@@ -0,0 +1,94 @@
+//! Stop reading a worker's stream once the client has gone away
+//!
+//! Streaming responses are forwarded by a spawned task through a bounded
+//! channel. A disconnected client used to be noticed only on the next send,
+//! so a worker that was slow between chunks kept generating for nobody.
+//! Racing each upstream read against the channel closing lets the task
+//! return right away; dropping the upstream response then closes the
+//! connection, which is how the worker learns to abort the request.
+
+use crate::metrics::RouterMetrics;
+use futures_util::{Stream, StreamExt};
+use tokio::sync::mpsc;
+use tracing::debug;
+
+/// Outcome of waiting for the next upstream chunk
+#[derive(Debug)]
+pub enum UpstreamRead<T> {
+    Chunk(T),
+    /// The upstream stream ended
+    Ended,
+    /// The client disconnected before the next chunk arrived
+    ClientGone,
+}
+
+/// Read the next upstream chunk, giving up as soon as the client disconnects
+pub async fn next_chunk<S, T>(
+    stream: &mut S,
+    tx: &mpsc::Sender<T>,
+    route: &str,
+) -> UpstreamRead<S::Item>
+where
+    S: Stream + Unpin,
+{
+    tokio::select! {
+        biased;
+        _ = tx.closed() => {
+            client_disconnected(route);
+            UpstreamRead::ClientGone
+        }
+        chunk = stream.next() => match chunk {
+            Some(chunk) => UpstreamRead::Chunk(chunk),
+            None => UpstreamRead::Ended,
+        },
+    }
+}
+
+/// Record a client that disconnected mid-stream
+pub fn client_disconnected(route: &str) {
+    debug!(
+        "Client disconnected from {} stream, closing upstream request",
+        route
+    );
+    RouterMetrics::record_client_disconnect(route);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_next_chunk_returns_when_client_disconnects() {
+        let (tx, rx) = mpsc::channel::<()>(1);
+        let mut stream = futures_util::stream::pending::<u32>();
+
+        drop(rx);
+        let read = tokio::time::timeout(
+            Duration::from_secs(1),
+            next_chunk(&mut stream, &tx, "/v1/completions"),
+        )
+        .await
+        .expect("a pending upstream must not keep a disconnected stream alive");
+        assert!(matches!(read, UpstreamRead::ClientGone));
+    }
+
+    #[tokio::test]
+    async fn test_next_chunk_reads_until_end() {
+        let (tx, _rx) = mpsc::channel::<()>(1);
+        let mut stream = futures_util::stream::iter([1, 2]);
+
+        assert!(matches!(
+            next_chunk(&mut stream, &tx, "/generate").await,
+            UpstreamRead::Chunk(1)
+        ));
+        assert!(matches!(
+            next_chunk(&mut stream, &tx, "/generate").await,
+            UpstreamRead::Chunk(2)
+        ));
+        assert!(matches!(
+            next_chunk(&mut stream, &tx, "/generate").await,
+            UpstreamRead::Ended
+        ));
+    }
+}
@@ -0,0 +1,232 @@
+//! Single-flight coalescing for identical concurrent requests
+//!
+//! Embeddings are deterministic, so when the same request is already in
+//! flight a second caller can wait for that upstream call instead of sending
+//! its own. The first caller (the leader) buffers the response and hands a
+//! copy to every caller that joined while it was running. Nothing is kept
+//! once the call completes; this is not a cache. Session cookies are never
+//! handed to followers, since they belong to the leader's caller.
+
+use crate::routers::http::response_cache::{CacheKey, CachedResponse};
+use axum::{
+    body::to_bytes,
+    http::{header::SET_COOKIE, StatusCode},
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use tokio::sync::watch;
+use tracing::{debug, warn};
+
+type Flight = watch::Receiver<Option<CachedResponse>>;
+
+/// Tracks the upstream calls currently in flight, keyed by request
+#[derive(Debug, Default)]
+pub struct RequestCoalescer {
+    in_flight: Mutex<HashMap<CacheKey, Flight>>,
+}
+
+/// Removes the leader's entry when its call finishes or is cancelled
+struct FlightGuard<'a> {
+    coalescer: &'a RequestCoalescer,
+    key: CacheKey,
+}
+
+impl Drop for FlightGuard<'_> {
+    fn drop(&mut self) {
+        self.coalescer.in_flight.lock().unwrap().remove(&self.key);
+    }
+}
+
+impl RequestCoalescer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `send` for `key`, or share the result of an identical call in flight
+    ///
+    /// If the leader is cancelled before it responds, waiting callers fall
+    /// back to sending their own request.
+    pub async fn run<F, Fut>(&self, key: CacheKey, send: F) -> Response
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Response>,
+    {
+        let joined = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&key) {
+                Some(flight) => Err(flight.clone()),
+                None => {
+                    let (tx, rx) = watch::channel(None);
+                    in_flight.insert(key, rx);
+                    Ok(tx)
+                }
+            }
+        };
+
+        let tx = match joined {
+            Ok(tx) => tx,
+            Err(mut flight) => {
+                let shared = match flight.wait_for(Option::is_some).await {
+                    Ok(shared) => shared.clone(),
+                    Err(_) => None,
+                };
+                return match shared {
+                    Some(shared) => {
+                        debug!("Coalesced request with an identical one in flight");
+                        shared.into_response()
+                    }
+                    None => send().await,
+                };
+            }
+        };
+
+        let _guard = FlightGuard {
+            coalescer: self,
+            key,
+        };
+        let (parts, body) = send().await.into_parts();
+        let shared = match to_bytes(body, usize::MAX).await {
+            Ok(body) => CachedResponse {
+                status: parts.status,
+                headers: parts.headers,
+                body,
+            },
+            Err(e) => {
+                warn!("Failed to buffer response for coalesced callers: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to read response body: {}", e),
+                )
+                    .into_response();
+            }
+        };
+        let mut for_followers = shared.clone();
+        for_followers.headers.remove(SET_COOKIE);
+        // Followers only wait while the sender is alive, so ignoring "no receivers" is fine
+        let _ = tx.send(Some(for_followers));
+        shared.into_response()
+    }
+
+    /// Number of distinct calls currently in flight
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    async fn body_of(response: Response) -> String {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_identical_requests_share_one_call() {
+        let coalescer = Arc::new(RequestCoalescer::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..5)
+            .map(|_| {
+                let coalescer = Arc::clone(&coalescer);
+                let calls = Arc::clone(&calls);
+                tokio::spawn(async move {
+                    let response = coalescer
+                        .run([1; 32], || async {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(100)).await;
+                            (StatusCode::OK, "embedding").into_response()
+                        })
+                        .await;
+                    assert_eq!(response.status(), StatusCode::OK);
+                    body_of(response).await
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), "embedding");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(coalescer.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_followers_do_not_receive_leader_cookie() {
+        let coalescer = Arc::new(RequestCoalescer::new());
+        let send = || async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            ([(SET_COOKIE, "session=leader")], "embedding").into_response()
+        };
+
+        let (leader, follower) = tokio::join!(coalescer.run([4; 32], send), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            coalescer
+                .run([4; 32], || async { "unused".into_response() })
+                .await
+        });
+        assert_eq!(leader.headers()[SET_COOKIE], "session=leader");
+        assert!(follower.headers().get(SET_COOKIE).is_none());
+        assert_eq!(body_of(follower).await, "embedding");
+    }
+
+    #[tokio::test]
+    async fn test_different_keys_are_not_coalesced() {
+        let coalescer = RequestCoalescer::new();
+        let calls = AtomicUsize::new(0);
+        let send = |body: &'static str| {
+            let calls = &calls;
+            move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                body.into_response()
+            }
+        };
+
+        let (a, b) = tokio::join!(
+            coalescer.run([1; 32], send("a")),
+            coalescer.run([2; 32], send("b"))
+        );
+        assert_eq!(body_of(a).await, "a");
+        assert_eq!(body_of(b).await, "b");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_follower_sends_its_own_request_when_leader_is_cancelled() {
+        let coalescer = Arc::new(RequestCoalescer::new());
+
+        let leader = {
+            let coalescer = Arc::clone(&coalescer);
+            tokio::spawn(async move {
+                coalescer
+                    .run([3; 32], || async {
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                        "leader".into_response()
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let follower = {
+            let coalescer = Arc::clone(&coalescer);
+            tokio::spawn(async move {
+                coalescer
+                    .run([3; 32], || async { "follower".into_response() })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        leader.abort();
+
+        assert_eq!(body_of(follower.await.unwrap()).await, "follower");
+        assert_eq!(coalescer.in_flight(), 0);
+    }
+}
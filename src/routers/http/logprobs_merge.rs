@@ -18,6 +18,10 @@ use tracing::debug;
 /// 4. choices[].logprobs.text_offset - text offsets (with adjustment)
 /// 5. choices[].logprobs.top_logprobs - alternative tokens with logprobs
 ///
+/// For Chat Completions API the per-choice logprobs are a list of entries in
+/// choices[].logprobs.content, which is merged the same way (see
+/// [`merge_chat_content_logprobs`]).
+///
 /// # Arguments
 /// * `prefill_json` - The prefill response JSON
 /// * `decode_json` - The decode response JSON (will be modified in place)
@@ -260,6 +264,14 @@ pub fn merge_logprobs_in_json(prefill_json: &Value, decode_json: &mut Value) ->
                                 );
                                 merged = true;
                             }
+
+                            // 3.3. Merge content entries (Chat Completions API)
+                            if merge_chat_content_logprobs(
+                                prefill_logprobs_obj,
+                                decode_logprobs_obj,
+                            ) {
+                                merged = true;
+                            }
                         }
                     }
                 }
@@ -270,6 +282,38 @@ pub fn merge_logprobs_in_json(prefill_json: &Value, decode_json: &mut Value) ->
     merged
 }
 
+/// Merge chat logprobs entries: [prefill_PROMPT_entries_only] + [decode_ALL_entries].
+///
+/// Chat Completions report logprobs as `logprobs.content`, one
+/// `{token, logprob, bytes, top_logprobs}` entry per token. Prefill runs with
+/// max_tokens=1, so its last entry is the output token that decode generates
+/// again; any entries before it (echoed prompt) are prepended to decode's.
+///
+/// Returns whether both sides had a `content` array to merge.
+fn merge_chat_content_logprobs(
+    prefill_logprobs: &serde_json::Map<String, Value>,
+    decode_logprobs: &mut serde_json::Map<String, Value>,
+) -> bool {
+    let (Some(prefill_content), Some(decode_content)) = (
+        prefill_logprobs.get("content").and_then(|v| v.as_array()),
+        decode_logprobs.get("content").and_then(|v| v.as_array()),
+    ) else {
+        return false;
+    };
+
+    let prefill_prompt_only = &prefill_content[..prefill_content.len().saturating_sub(1)];
+    let mut merged_content = prefill_prompt_only.to_vec();
+    merged_content.extend(decode_content.iter().cloned());
+    debug!(
+        "[LOGPROBS MERGE] Merged chat content: {} prompt + {} all = {} total",
+        prefill_prompt_only.len(),
+        decode_content.len(),
+        merged_content.len()
+    );
+    decode_logprobs.insert("content".to_string(), Value::Array(merged_content));
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -354,4 +398,58 @@ mod tests {
 
         assert_eq!(decode_json["prompt_logprobs"], json!([null, -0.5, -1.2]));
     }
+
+    #[test]
+    fn test_merge_chat_completions_content_logprobs() {
+        let entry = |token: &str, logprob: f64| json!({"token": token, "logprob": logprob, "bytes": null, "top_logprobs": []});
+        let prefill_json = json!({
+            "prompt_logprobs": [null, {"1": {"logprob": -0.5}}],
+            "choices": [{
+                "message": {"role": "assistant", "content": "Hi Hello"},
+                "logprobs": {"content": [entry("Hi", -0.1), entry(" Hello", -0.3)]}
+            }]
+        });
+
+        let mut decode_json = json!({
+            "prompt_logprobs": null,
+            "choices": [{
+                "message": {"role": "assistant", "content": "Hi Hello there"},
+                "logprobs": {"content": [entry(" Hello", -0.3), entry(" there", -0.7)]}
+            }]
+        });
+
+        let merged = merge_logprobs_in_json(&prefill_json, &mut decode_json);
+        assert!(merged);
+
+        assert_eq!(
+            decode_json["prompt_logprobs"],
+            prefill_json["prompt_logprobs"]
+        );
+        // Prefill's echoed prompt entry is kept, its output token is not duplicated
+        assert_eq!(
+            decode_json["choices"][0]["logprobs"]["content"],
+            json!([
+                entry("Hi", -0.1),
+                entry(" Hello", -0.3),
+                entry(" there", -0.7)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge_chat_content_without_prompt_entries_keeps_decode() {
+        let prefill_json = json!({
+            "choices": [{"logprobs": {"content": [{"token": "A", "logprob": -0.2}]}}]
+        });
+        let mut decode_json = json!({
+            "choices": [{"logprobs": {"content": [
+                {"token": "A", "logprob": -0.2},
+                {"token": "B", "logprob": -0.9}
+            ]}}]
+        });
+        let expected = decode_json.clone();
+
+        assert!(merge_logprobs_in_json(&prefill_json, &mut decode_json));
+        assert_eq!(decode_json, expected);
+    }
 }
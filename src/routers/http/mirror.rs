@@ -0,0 +1,116 @@
+//! Shadow traffic for testing worker builds
+//!
+//! A sampled share of non-streaming generation requests is copied to a
+//! shadow worker outside the routed fleet, so a new build sees production
+//! traffic without serving anyone. The copy is sent in the background
+//! alongside the primary request, which never waits for it; its response is
+//! read to the end and dropped, and only its outcome and latency are recorded.
+
+use crate::config::TrafficMirrorConfig;
+use crate::metrics::RouterMetrics;
+use rand::Rng;
+use reqwest::RequestBuilder;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Sampling and delivery of mirrored requests
+#[derive(Debug)]
+pub struct TrafficMirror {
+    worker_urls: Vec<String>,
+    fraction: f64,
+    timeout: Duration,
+}
+
+impl TrafficMirror {
+    pub fn new(config: &TrafficMirrorConfig) -> Self {
+        Self {
+            worker_urls: config.worker_urls.clone(),
+            fraction: config.fraction.clamp(0.0, 1.0),
+            timeout: Duration::from_secs(config.timeout_secs),
+        }
+    }
+
+    /// Shadow worker a request is mirrored to, None when it is not sampled
+    pub fn sample(&self) -> Option<&str> {
+        let mut rng = rand::rng();
+        if self.worker_urls.is_empty() || !rng.random_bool(self.fraction) {
+            return None;
+        }
+        let idx = rng.random_range(0..self.worker_urls.len());
+        Some(&self.worker_urls[idx])
+    }
+
+    /// Send a request to a shadow worker in the background and discard the response
+    pub fn send(&self, request: RequestBuilder, route: &str, shadow_url: &str) {
+        let request = request.timeout(self.timeout);
+        let route = route.to_string();
+        let shadow_url = shadow_url.to_string();
+        tokio::spawn(async move {
+            let start = Instant::now();
+            let success = match request.send().await {
+                Ok(res) => {
+                    let status = res.status();
+                    match res.bytes().await {
+                        Ok(_) => {
+                            debug!(
+                                "Shadow worker {} answered {} with {}",
+                                shadow_url, route, status
+                            );
+                            status.is_success()
+                        }
+                        Err(e) => {
+                            debug!(
+                                "Failed to read shadow response from {} for {}: {}",
+                                shadow_url, route, e
+                            );
+                            false
+                        }
+                    }
+                }
+                Err(e) => {
+                    debug!(
+                        "Shadow request to {} for {} failed: {}",
+                        shadow_url, route, e
+                    );
+                    false
+                }
+            };
+            RouterMetrics::record_mirror_request(&route, success, start.elapsed());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mirror(fraction: f64) -> TrafficMirror {
+        TrafficMirror::new(&TrafficMirrorConfig {
+            worker_urls: vec![
+                "http://shadow1:8000".to_string(),
+                "http://shadow2:8000".to_string(),
+            ],
+            fraction,
+            timeout_secs: 60,
+        })
+    }
+
+    #[test]
+    fn test_sample_picks_shadow_worker() {
+        let mirror = mirror(1.0);
+        let mut sampled: Vec<&str> = (0..50).filter_map(|_| mirror.sample()).collect();
+        assert_eq!(sampled.len(), 50);
+        sampled.sort_unstable();
+        sampled.dedup();
+        assert_eq!(sampled, vec!["http://shadow1:8000", "http://shadow2:8000"]);
+    }
+
+    #[test]
+    fn test_sample_respects_fraction() {
+        assert!((0..50).all(|_| mirror(0.0).sample().is_none()));
+
+        let mirror = mirror(0.5);
+        let sampled = (0..1000).filter(|_| mirror.sample().is_some()).count();
+        assert!((300..700).contains(&sampled));
+    }
+}
@@ -1,10 +1,17 @@
 //! HTTP router implementations
 
+pub mod client_disconnect;
+pub mod coalesce;
 pub mod dp_utils;
 pub mod logprobs_merge;
+pub mod mirror;
 pub mod openai_router;
 pub mod pd_router;
 pub mod pd_types;
+pub mod response_cache;
 pub mod router;
+pub mod stored_responses;
+pub mod stream_model;
+pub mod stream_usage;
 pub mod vllm_pd_router;
 pub mod vllm_service_discovery;
@@ -1,10 +1,14 @@
 //! OpenAI router implementation (reqwest-based)
 
+use super::client_disconnect::{client_disconnected, next_chunk, UpstreamRead};
 use crate::config::CircuitBreakerConfig;
-use crate::core::{CircuitBreaker, CircuitBreakerConfig as CoreCircuitBreakerConfig};
+use crate::core::{
+    CircuitBreaker, CircuitBreakerConfig as CoreCircuitBreakerConfig, WorkerResponse,
+};
 use crate::protocols::spec::{
     ChatCompletionRequest, CompletionRequest, GenerateRequest, RerankRequest,
 };
+use crate::routers::error_response::error_response;
 use async_trait::async_trait;
 use axum::{
     body::Body,
@@ -12,7 +16,6 @@ use axum::{
     http::{header::CONTENT_TYPE, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
 };
-use futures_util::StreamExt;
 use std::{
     any::Any,
     sync::atomic::{AtomicBool, Ordering},
@@ -29,6 +32,8 @@ pub struct OpenAIRouter {
     circuit_breaker: CircuitBreaker,
     /// Health status
     healthy: AtomicBool,
+    /// Chunks buffered per streaming response
+    stream_buffer_size: usize,
 }
 
 impl OpenAIRouter {
@@ -51,18 +56,27 @@ impl OpenAIRouter {
                 success_threshold: cb.success_threshold,
                 timeout_duration: std::time::Duration::from_secs(cb.timeout_duration_secs),
                 window_duration: std::time::Duration::from_secs(cb.window_duration_secs),
+                half_open_max_concurrent: cb.half_open_max_concurrent,
             })
             .unwrap_or_default();
 
-        let circuit_breaker = CircuitBreaker::with_config(core_cb_config);
+        let circuit_breaker =
+            CircuitBreaker::with_config(core_cb_config).for_worker(base_url.clone());
 
         Ok(Self {
             client,
             base_url,
             circuit_breaker,
             healthy: AtomicBool::new(true),
+            stream_buffer_size: 64,
         })
     }
+
+    /// Set how many chunks are buffered per streaming response
+    pub fn with_stream_buffer_size(mut self, size: usize) -> Self {
+        self.stream_buffer_size = size;
+        self
+    }
 }
 
 #[async_trait]
@@ -156,30 +170,28 @@ impl super::super::RouterTrait for OpenAIRouter {
                         if let Some(ct) = content_type {
                             response.headers_mut().insert(CONTENT_TYPE, ct);
                         }
+                        response.extensions_mut().insert(WorkerResponse);
                         response
                     }
-                    Err(e) => (
+                    Err(e) => error_response(
                         StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Failed to read upstream response: {}", e),
-                    )
-                        .into_response(),
+                    ),
                 }
             }
-            Err(e) => (
+            Err(e) => error_response(
                 StatusCode::BAD_GATEWAY,
                 format!("Failed to contact upstream: {}", e),
-            )
-                .into_response(),
+            ),
         }
     }
 
     async fn get_model_info(&self, _req: Request<Body>) -> Response {
         // Not directly supported without model param; return 501
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "get_model_info not implemented for OpenAI router",
         )
-            .into_response()
     }
 
     async fn route_generate(
@@ -189,11 +201,10 @@ impl super::super::RouterTrait for OpenAIRouter {
         _model_id: Option<&str>,
     ) -> Response {
         // Generate endpoint is VLLM-specific, not supported for OpenAI backend
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Generate endpoint not supported for OpenAI backend",
         )
-            .into_response()
     }
 
     async fn route_chat(
@@ -202,19 +213,14 @@ impl super::super::RouterTrait for OpenAIRouter {
         body: &ChatCompletionRequest,
         _model_id: Option<&str>,
     ) -> Response {
-        if !self.circuit_breaker.can_execute() {
-            return (StatusCode::SERVICE_UNAVAILABLE, "Circuit breaker open").into_response();
-        }
-
         // Serialize request body, removing VLLM-only fields
         let mut payload = match serde_json::to_value(body) {
             Ok(v) => v,
             Err(e) => {
-                return (
+                return error_response(
                     StatusCode::BAD_REQUEST,
                     format!("Failed to serialize request: {}", e),
-                )
-                    .into_response();
+                );
             }
         };
         if let Some(obj) = payload.as_object_mut() {
@@ -256,15 +262,19 @@ impl super::super::RouterTrait for OpenAIRouter {
             req = req.header("Accept", "text/event-stream");
         }
 
+        // Claims a probe slot when half-open; every path below gives it back
+        if !self.circuit_breaker.try_acquire() {
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "Circuit breaker open");
+        }
+
         let resp = match req.send().await {
             Ok(r) => r,
             Err(e) => {
                 self.circuit_breaker.record_failure();
-                return (
+                return error_response(
                     StatusCode::SERVICE_UNAVAILABLE,
                     format!("Failed to contact upstream: {}", e),
-                )
-                    .into_response();
+                );
             }
         };
 
@@ -282,44 +292,54 @@ impl super::super::RouterTrait for OpenAIRouter {
                     if let Some(ct) = content_type {
                         response.headers_mut().insert(CONTENT_TYPE, ct);
                     }
+                    response.extensions_mut().insert(WorkerResponse);
                     response
                 }
                 Err(e) => {
                     self.circuit_breaker.record_failure();
-                    (
+                    error_response(
                         StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Failed to read response: {}", e),
                     )
-                        .into_response()
                 }
             }
         } else {
+            // Streaming outcomes are not tracked; free the probe slot
+            self.circuit_breaker.release();
+
             // Stream SSE bytes to client
             let stream = resp.bytes_stream();
-            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            let (tx, rx) = tokio::sync::mpsc::channel(self.stream_buffer_size);
             tokio::spawn(async move {
                 let mut s = stream;
-                while let Some(chunk) = s.next().await {
+                loop {
+                    // Returning drops the upstream stream, closing the connection
+                    let chunk = match next_chunk(&mut s, &tx, "/v1/chat/completions").await {
+                        UpstreamRead::Chunk(chunk) => chunk,
+                        UpstreamRead::Ended | UpstreamRead::ClientGone => break,
+                    };
                     match chunk {
                         Ok(bytes) => {
-                            if tx.send(Ok(bytes)).is_err() {
+                            if tx.send(Ok(bytes)).await.is_err() {
+                                client_disconnected("/v1/chat/completions");
                                 break;
                             }
                         }
                         Err(e) => {
-                            let _ = tx.send(Err(format!("Stream error: {}", e)));
+                            let _ = tx.send(Err(format!("Stream error: {}", e))).await;
                             break;
                         }
                     }
                 }
             });
             let mut response = Response::new(Body::from_stream(
-                tokio_stream::wrappers::UnboundedReceiverStream::new(rx),
+                tokio_stream::wrappers::ReceiverStream::new(rx),
             ));
             *response.status_mut() = status;
             response
                 .headers_mut()
                 .insert(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
+            response.extensions_mut().insert(WorkerResponse);
             response
         }
     }
@@ -331,11 +351,10 @@ impl super::super::RouterTrait for OpenAIRouter {
         _model_id: Option<&str>,
     ) -> Response {
         // Completion endpoint not implemented for OpenAI backend
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Completion endpoint not implemented for OpenAI backend",
         )
-            .into_response()
     }
 
     async fn route_responses(
@@ -344,43 +363,38 @@ impl super::super::RouterTrait for OpenAIRouter {
         _body: &crate::protocols::spec::ResponsesRequest,
         _model_id: Option<&str>,
     ) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Responses endpoint not implemented for OpenAI router",
         )
-            .into_response()
     }
 
     async fn get_response(&self, _headers: Option<&HeaderMap>, _response_id: &str) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Responses retrieve endpoint not implemented for OpenAI router",
         )
-            .into_response()
     }
 
     async fn cancel_response(&self, _headers: Option<&HeaderMap>, _response_id: &str) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Responses cancel endpoint not implemented for OpenAI router",
         )
-            .into_response()
     }
 
     async fn flush_cache(&self) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "flush_cache not supported for OpenAI router",
         )
-            .into_response()
     }
 
     async fn get_worker_loads(&self) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "get_worker_loads not supported for OpenAI router",
         )
-            .into_response()
     }
 
     fn router_type(&self) -> &'static str {
@@ -401,11 +415,10 @@ impl super::super::RouterTrait for OpenAIRouter {
         _body: &crate::protocols::spec::EmbeddingRequest,
         _model_id: Option<&str>,
     ) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Embeddings endpoint not implemented for OpenAI backend",
         )
-            .into_response()
     }
 
     async fn route_rerank(
@@ -414,10 +427,9 @@ impl super::super::RouterTrait for OpenAIRouter {
         _body: &RerankRequest,
         _model_id: Option<&str>,
     ) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Rerank endpoint not implemented for OpenAI backend",
         )
-            .into_response()
     }
 }
@@ -1,26 +1,34 @@
 // PD (Prefill-Decode) Router Implementation
 // This module handles routing for disaggregated prefill-decode systems
+use super::client_disconnect::{client_disconnected, next_chunk, UpstreamRead};
 use super::dp_utils;
 use super::logprobs_merge;
 use super::pd_types::{api_path, PDRouterError};
-use crate::config::types::RetryConfig;
+use crate::config::types::{RetryConfig, UpstreamAuthConfig};
 use crate::core::{
-    is_retryable_status, BasicWorker, CircuitBreakerConfig, HealthConfig, RetryExecutor, Worker,
-    WorkerFactory, WorkerLoadGuard, WorkerRegistry, WorkerType,
+    is_retryable_status, model_version, upstream_tls, BasicWorker, CircuitBreakerConfig,
+    ConnectionMode, HealthConfig, RetryBudget, RetryExecutor, Worker, WorkerLoadGuard,
+    WorkerRegistry, WorkerResponse, WorkerType,
 };
 use crate::metrics::RouterMetrics;
-use crate::policies::{LoadBalancingPolicy, PolicyRegistry};
+use crate::policies::{LoadBalancingPolicy, PolicyRegistry, DEFAULT_LATENCY_DECAY};
 use crate::protocols::spec::{
-    ChatCompletionRequest, ChatMessage, CompletionRequest, GenerateRequest, RerankRequest,
-    ResponsesRequest, StringOrArray, UserMessageContent,
+    ChatCompletionRequest, ChatMessage, CompletionRequest, GenerateRequest, GenerationRequest,
+    RerankRequest, ResponsesRequest, StringOrArray, UserMessageContent,
 };
+use crate::routers::error_response::error_response;
 use crate::routers::header_utils;
-use crate::routers::{RouterTrait, WorkerManagement};
+use crate::routers::{
+    drain_result_message, model_readiness_response, worker_circuit_response,
+    worker_enabled_response, worker_stats_response, RouterTrait, WorkerManagement,
+};
 use async_trait::async_trait;
 use axum::{
     body::Body,
     extract::Request,
-    http::{header::CONTENT_TYPE, HeaderMap, HeaderValue, Method, StatusCode},
+    http::{
+        header::CONTENT_LENGTH, header::CONTENT_TYPE, HeaderMap, HeaderValue, Method, StatusCode,
+    },
     response::{IntoResponse, Response},
     Json,
 };
@@ -32,7 +40,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, error, info, warn};
 
 #[derive(Debug, Clone)]
@@ -41,13 +49,20 @@ pub struct PDRouter {
     pub policy_registry: Arc<PolicyRegistry>,
     pub worker_startup_timeout_secs: u64,
     pub worker_startup_check_interval_secs: u64,
+    pub health_config: HealthConfig,
     pub worker_loads: Arc<tokio::sync::watch::Receiver<HashMap<String, isize>>>,
     pub load_monitor_handle: Option<Arc<tokio::task::JoinHandle<()>>>,
     pub client: Client,
     // Dedicated client for prefill fire-and-forget (non-logprob) requests
     pub prefill_client: Client,
     pub retry_config: RetryConfig,
+    pub retry_budget: Option<Arc<RetryBudget>>,
     pub circuit_breaker_config: CircuitBreakerConfig,
+    pub upstream_auth: UpstreamAuthConfig,
+    /// Chunks buffered per streaming response
+    pub stream_buffer_size: usize,
+    /// Only select workers serving the model's active version
+    pub split_model_versions: bool,
     // Channel for sending prefill responses to background workers for draining
     prefill_drain_tx: mpsc::Sender<reqwest::Response>,
 }
@@ -61,6 +76,8 @@ struct PDRequestContext<'a> {
     return_logprob: bool,
     request_text: Option<String>,
     model_id: Option<&'a str>,
+    /// Load the request adds to its decode worker (`n` / `best_of` sequences)
+    decode_load_weight: usize,
 }
 
 impl PDRouter {
@@ -70,6 +87,7 @@ impl PDRouter {
             &[url.to_string()],
             self.worker_startup_timeout_secs,
             self.worker_startup_check_interval_secs,
+            &self.health_config,
         )
         .await
         .map_err(|_| PDRouterError::HealthCheckFailed {
@@ -96,7 +114,9 @@ impl PDRouter {
             // Extract base URL if DP-aware format (e.g., http://127.0.0.1:8081@0 → http://127.0.0.1:8081)
             let (base_url, _) = super::dp_utils::parse_worker_url(&worker_url);
             let url = format!("{}/{}", base_url, endpoint);
-            match self.client.post(&url).send().await {
+            let request = self.client.post(&url);
+            let request = header_utils::apply_upstream_auth(request, &self.upstream_auth, None);
+            match request.send().await {
                 Ok(res) if res.status().is_success() => {
                     results.push(format!("{} {}: OK", worker_type, worker_url));
                 }
@@ -141,7 +161,9 @@ impl PDRouter {
         let (base_url, _) = super::dp_utils::parse_worker_url(worker_url);
 
         let url = format!("{}/start_profile", base_url);
-        match self.client.post(&url).send().await {
+        let request = self.client.post(&url);
+        let request = header_utils::apply_upstream_auth(request, &self.upstream_auth, None);
+        match request.send().await {
             Ok(res) if res.status().is_success() => {
                 info!("Started profiling on {}", base_url);
             }
@@ -164,7 +186,9 @@ impl PDRouter {
         let (base_url, _) = super::dp_utils::parse_worker_url(worker_url);
 
         let url = format!("{}/stop_profile", base_url);
-        match self.client.post(&url).send().await {
+        let request = self.client.post(&url);
+        let request = header_utils::apply_upstream_auth(request, &self.upstream_auth, None);
+        match request.send().await {
             Ok(res) if res.status().is_success() => {
                 info!("Stopped profiling on {}", base_url);
             }
@@ -193,11 +217,10 @@ impl PDRouter {
         if let Some(worker_url) = first_worker_url {
             self.proxy_to_worker(worker_url, endpoint, headers).await
         } else {
-            (
+            error_response(
                 StatusCode::SERVICE_UNAVAILABLE,
-                "No prefill servers available".to_string(),
+                "No prefill servers available",
             )
-                .into_response()
         }
     }
 
@@ -212,10 +235,16 @@ impl PDRouter {
         let (base_url, _) = super::dp_utils::parse_worker_url(&worker_url);
         let url = format!("{}/{}", base_url, endpoint);
         let mut request_builder = self.client.get(&url);
+        request_builder =
+            header_utils::apply_upstream_auth(request_builder, &self.upstream_auth, None);
 
-        // Add headers if provided
+        // Add headers if provided; the client's Authorization only in passthrough mode
         if let Some(headers) = headers {
+            let passthrough = self.upstream_auth == UpstreamAuthConfig::Passthrough;
             for (name, value) in headers {
+                if !passthrough && name.eq_ignore_ascii_case("authorization") {
+                    continue;
+                }
                 request_builder = request_builder.header(name, value);
             }
         }
@@ -233,11 +262,10 @@ impl PDRouter {
                     }
                     Err(e) => {
                         error!("Failed to read response body: {}", e);
-                        (
+                        error_response(
                             StatusCode::INTERNAL_SERVER_ERROR,
                             format!("Failed to read response body: {}", e),
                         )
-                            .into_response()
                     }
                 }
             }
@@ -248,11 +276,10 @@ impl PDRouter {
             }
             Err(e) => {
                 error!("Failed to proxy request server: {}", e);
-                (
+                error_response(
                     StatusCode::INTERNAL_SERVER_ERROR,
                     format!("Failed to proxy request: {}", e),
                 )
-                    .into_response()
             }
         }
     }
@@ -270,15 +297,13 @@ impl PDRouter {
             return Err(PDRouterError::WorkerAlreadyExists { url: url.clone() });
         }
 
-        // Create Worker for the new prefill server with circuit breaker configuration
+        // Create Worker for the new prefill server with circuit breaker and health configuration
         // TODO: In IGW mode, fetch model_id from worker's /get_model_info endpoint
-        let worker = WorkerFactory::create_prefill_with_config(
-            url.clone(),
-            bootstrap_port,
-            self.circuit_breaker_config.clone(),
-        );
+        let worker = BasicWorker::new(url.clone(), WorkerType::Prefill { bootstrap_port })
+            .with_circuit_breaker_config(self.circuit_breaker_config.clone())
+            .with_health_config(self.health_config.clone());
 
-        let worker_arc: Arc<dyn Worker> = Arc::from(worker);
+        let worker_arc: Arc<dyn Worker> = Arc::new(worker);
 
         // Register the worker in the registry
         self.worker_registry.register(worker_arc.clone());
@@ -288,14 +313,9 @@ impl PDRouter {
         let policy = self.policy_registry.on_worker_added(model_id, None);
 
         // If this is a cache-aware policy, update it with all workers for this model
-        if policy.name() == "cache_aware" {
-            if let Some(cache_aware) = policy
-                .as_any()
-                .downcast_ref::<crate::policies::CacheAwarePolicy>()
-            {
-                let model_workers = self.worker_registry.get_by_model_fast(model_id);
-                cache_aware.init_workers(&model_workers);
-            }
+        if let Some(cache_aware) = policy.as_cache_aware() {
+            let model_workers = self.worker_registry.get_by_model_fast(model_id);
+            cache_aware.init_workers(&model_workers);
         }
 
         info!("Added prefill server: {}", url);
@@ -311,14 +331,13 @@ impl PDRouter {
             return Err(PDRouterError::WorkerAlreadyExists { url: url.clone() });
         }
 
-        // Create Worker for the new decode server with circuit breaker configuration
+        // Create Worker for the new decode server with circuit breaker and health configuration
         // TODO: In IGW mode, fetch model_id from worker's /get_model_info endpoint
-        let worker = WorkerFactory::create_decode_with_config(
-            url.clone(),
-            self.circuit_breaker_config.clone(),
-        );
+        let worker = BasicWorker::new(url.clone(), WorkerType::Decode)
+            .with_circuit_breaker_config(self.circuit_breaker_config.clone())
+            .with_health_config(self.health_config.clone());
 
-        let worker_arc: Arc<dyn Worker> = Arc::from(worker);
+        let worker_arc: Arc<dyn Worker> = Arc::new(worker);
 
         // Register the worker in the registry
         self.worker_registry.register(worker_arc.clone());
@@ -328,14 +347,9 @@ impl PDRouter {
         let policy = self.policy_registry.on_worker_added(model_id, None);
 
         // If this is a cache-aware policy, update it with all workers for this model
-        if policy.name() == "cache_aware" {
-            if let Some(cache_aware) = policy
-                .as_any()
-                .downcast_ref::<crate::policies::CacheAwarePolicy>()
-            {
-                let model_workers = self.worker_registry.get_by_model_fast(model_id);
-                cache_aware.init_workers(&model_workers);
-            }
+        if let Some(cache_aware) = policy.as_cache_aware() {
+            let model_workers = self.worker_registry.get_by_model_fast(model_id);
+            cache_aware.init_workers(&model_workers);
         }
 
         info!("Added decode server: {}", url);
@@ -362,13 +376,8 @@ impl PDRouter {
 
             // Get the policy for this model to update cache-aware if needed
             if let Some(policy) = self.policy_registry.get_policy(&model_id) {
-                if policy.name() == "cache_aware" {
-                    if let Some(cache_aware) = policy
-                        .as_any()
-                        .downcast_ref::<crate::policies::CacheAwarePolicy>()
-                    {
-                        cache_aware.remove_worker_by_url(url);
-                    }
+                if let Some(cache_aware) = policy.as_cache_aware() {
+                    cache_aware.remove_worker_by_url(url);
                 }
             }
         }
@@ -403,13 +412,8 @@ impl PDRouter {
 
             // Get the policy for this model to update cache-aware if needed
             if let Some(policy) = self.policy_registry.get_policy(&model_id) {
-                if policy.name() == "cache_aware" {
-                    if let Some(cache_aware) = policy
-                        .as_any()
-                        .downcast_ref::<crate::policies::CacheAwarePolicy>()
-                    {
-                        cache_aware.remove_worker_by_url(url);
-                    }
+                if let Some(cache_aware) = policy.as_cache_aware() {
+                    cache_aware.remove_worker_by_url(url);
                 }
             }
         }
@@ -437,6 +441,7 @@ impl PDRouter {
             success_threshold: circuit_breaker_config.success_threshold,
             timeout_duration: Duration::from_secs(circuit_breaker_config.timeout_duration_secs),
             window_duration: Duration::from_secs(circuit_breaker_config.window_duration_secs),
+            half_open_max_concurrent: circuit_breaker_config.half_open_max_concurrent,
         };
 
         // Automatically expand to DP-aware format when intra_node_data_parallel_size > 1
@@ -498,6 +503,17 @@ impl PDRouter {
                 (prefill_urls, decode_urls)
             };
 
+        let health_config = HealthConfig {
+            timeout_secs: ctx.router_config.health_check.timeout_secs,
+            check_interval_secs: ctx.router_config.health_check.check_interval_secs,
+            endpoint: ctx.router_config.health_check.endpoint.clone(),
+            failure_threshold: ctx.router_config.health_check.failure_threshold,
+            success_threshold: ctx.router_config.health_check.success_threshold,
+            method: ctx.router_config.health_check.method,
+            expected_body: ctx.router_config.health_check.expected_body.clone(),
+            generation_probe: ctx.router_config.health_check.generation_probe,
+        };
+
         // Register prefill workers in the registry
         for (url, port) in expanded_prefill_urls {
             let worker = BasicWorker::new(
@@ -507,13 +523,7 @@ impl PDRouter {
                 },
             )
             .with_circuit_breaker_config(core_cb_config.clone())
-            .with_health_config(HealthConfig {
-                timeout_secs: ctx.router_config.health_check.timeout_secs,
-                check_interval_secs: ctx.router_config.health_check.check_interval_secs,
-                endpoint: ctx.router_config.health_check.endpoint.clone(),
-                failure_threshold: ctx.router_config.health_check.failure_threshold,
-                success_threshold: ctx.router_config.health_check.success_threshold,
-            });
+            .with_health_config(health_config.clone());
             ctx.worker_registry.register(Arc::new(worker));
         }
 
@@ -521,13 +531,7 @@ impl PDRouter {
         for url in expanded_decode_urls {
             let worker = BasicWorker::new(url, WorkerType::Decode)
                 .with_circuit_breaker_config(core_cb_config.clone())
-                .with_health_config(HealthConfig {
-                    timeout_secs: ctx.router_config.health_check.timeout_secs,
-                    check_interval_secs: ctx.router_config.health_check.check_interval_secs,
-                    endpoint: ctx.router_config.health_check.endpoint.clone(),
-                    failure_threshold: ctx.router_config.health_check.failure_threshold,
-                    success_threshold: ctx.router_config.health_check.success_threshold,
-                });
+                .with_health_config(health_config.clone());
             ctx.worker_registry.register(Arc::new(worker));
         }
 
@@ -542,6 +546,7 @@ impl PDRouter {
                 &all_urls,
                 ctx.router_config.worker_startup_timeout_secs,
                 ctx.router_config.worker_startup_check_interval_secs,
+                &health_config,
             )
             .await?;
         }
@@ -551,42 +556,37 @@ impl PDRouter {
         // This is a temporary workaround until CacheAwarePolicy is updated to work with Arc<dyn Worker>
         // TODO: Update CacheAwarePolicy to accept Arc<dyn Worker> instead of Box<dyn Worker>
 
-        // Set up background load monitoring for power-of-two selection
+        // Set up background load monitoring for load-aware policies
         let (tx, rx) = tokio::sync::watch::channel(HashMap::new());
         let worker_loads = Arc::new(rx);
 
-        // Get policies from registry to check if we need load monitoring
-        let prefill_policy = ctx.policy_registry.get_prefill_policy();
-        let decode_policy = ctx.policy_registry.get_decode_policy();
-
-        let load_monitor_handle =
-            if prefill_policy.name() == "power_of_two" || decode_policy.name() == "power_of_two" {
-                let monitor_urls = all_urls.clone();
-                let monitor_interval = ctx.router_config.worker_startup_check_interval_secs;
-                let monitor_client = ctx.client.clone();
-                let prefill_policy_clone = Arc::clone(&prefill_policy);
-                let decode_policy_clone = Arc::clone(&decode_policy);
-
-                Some(Arc::new(tokio::spawn(async move {
-                    Self::monitor_worker_loads_with_client(
-                        monitor_urls,
-                        tx,
-                        monitor_interval,
-                        monitor_client,
-                        prefill_policy_clone,
-                        decode_policy_clone,
-                    )
-                    .await;
-                })))
-            } else {
-                None
-            };
+        // Always run the monitor; it idles while no registered policy needs
+        // worker loads, so a runtime swap to a load-aware policy is covered
+        let load_monitor_handle = {
+            let monitor_registry = Arc::clone(&ctx.worker_registry);
+            let monitor_interval = ctx.router_config.worker_startup_check_interval_secs;
+            let monitor_client = ctx.client.clone();
+            let monitor_auth = ctx.router_config.effective_upstream_auth();
+            let registry_clone = Arc::clone(&ctx.policy_registry);
+
+            Some(Arc::new(tokio::spawn(async move {
+                Self::monitor_worker_loads_with_client(
+                    monitor_registry,
+                    tx,
+                    monitor_interval,
+                    monitor_client,
+                    monitor_auth,
+                    registry_clone,
+                )
+                .await;
+            })))
+        };
 
         // Note: Health checking is now handled centrally by RouterManager
         // Individual routers no longer need to manage health checkers
 
         // Build a dedicated prefill client for fire-and-forget semantics
-        let prefill_client = reqwest::Client::builder()
+        let prefill_client = upstream_tls::client_builder()
             .pool_max_idle_per_host(0)
             .http1_only()
             .connect_timeout(Duration::from_millis(300))
@@ -670,13 +670,18 @@ impl PDRouter {
             worker_startup_check_interval_secs: ctx
                 .router_config
                 .worker_startup_check_interval_secs,
+            health_config,
             worker_loads,
             load_monitor_handle,
             client: ctx.client.clone(),
             prefill_client,
             prefill_drain_tx,
             retry_config: ctx.router_config.effective_retry_config(),
+            retry_budget: RetryBudget::from_config(&ctx.router_config.retry).map(Arc::new),
             circuit_breaker_config: core_cb_config,
+            upstream_auth: ctx.router_config.effective_upstream_auth(),
+            stream_buffer_size: ctx.router_config.stream_buffer_size,
+            split_model_versions: ctx.router_config.split_model_versions(),
         })
     }
 
@@ -684,21 +689,19 @@ impl PDRouter {
     fn handle_server_selection_error(error: String) -> Response {
         error!("Failed to select PD pair error={}", error);
         RouterMetrics::record_pd_error("server_selection");
-        (
+        error_response(
             StatusCode::SERVICE_UNAVAILABLE,
             format!("No available servers: {}", error),
         )
-            .into_response()
     }
 
     // Helper to handle serialization errors
     fn handle_serialization_error(error: impl std::fmt::Display) -> Response {
         error!("Failed to serialize request error={}", error);
-        (
+        error_response(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to serialize request",
         )
-            .into_response()
     }
 
     // Helper to determine batch size from a GenerateRequest
@@ -828,8 +831,9 @@ impl PDRouter {
         let start_time = Instant::now();
 
         let route = context.route;
-        RetryExecutor::execute_response_with_retry(
+        RetryExecutor::execute_response_with_retry_budget(
             &self.retry_config,
+            self.retry_budget.as_deref(),
             // Operation per attempt
             {
                 let original_request = original_request.clone();
@@ -872,7 +876,23 @@ impl PDRouter {
                             Err(e) => return Self::handle_serialization_error(e),
                         };
 
+                        // Claim half-open probe slots on both workers before dispatch
+                        if !prefill.circuit_breaker().try_acquire() {
+                            return Self::handle_server_selection_error(
+                                "Prefill worker circuit is half-open and at its probe limit"
+                                    .to_string(),
+                            );
+                        }
+                        if !decode.circuit_breaker().try_acquire() {
+                            prefill.circuit_breaker().release();
+                            return Self::handle_server_selection_error(
+                                "Decode worker circuit is half-open and at its probe limit"
+                                    .to_string(),
+                            );
+                        }
+
                         // Execute the actual dual dispatch
+                        let dispatch_start = Instant::now();
                         let response = self
                             .execute_dual_dispatch_internal(
                                 headers,
@@ -890,6 +910,23 @@ impl PDRouter {
                         prefill.record_outcome(not_error);
                         decode.record_outcome(not_error);
 
+                        // Both workers take part in the whole exchange, so they
+                        // share the latency sample
+                        if _status.is_success() {
+                            let latency = dispatch_start.elapsed();
+                            let decay = |policy: Arc<dyn LoadBalancingPolicy>| {
+                                policy.latency_decay().unwrap_or(DEFAULT_LATENCY_DECAY)
+                            };
+                            prefill.record_latency(
+                                latency,
+                                decay(self.policy_registry.get_prefill_policy()),
+                            );
+                            decode.record_latency(
+                                latency,
+                                decay(self.policy_registry.get_decode_policy()),
+                            );
+                        }
+
                         response
                     }
                 }
@@ -946,15 +983,19 @@ impl PDRouter {
                 context.return_logprob,
                 Some(decode_url),
                 Some(response_headers),
+                context.route,
                 prefill,
                 decode,
+                context.decode_load_weight,
             )
         } else {
             // Handle non-streaming error response
-            match res.bytes().await {
+            let mut response = match res.bytes().await {
                 Ok(error_body) => (status, error_body).into_response(),
                 Err(e) => (status, format!("Decode server error: {}", e)).into_response(),
-            }
+            };
+            response.extensions_mut().insert(WorkerResponse);
+            response
         }
     }
 
@@ -970,8 +1011,12 @@ impl PDRouter {
     ) -> Response {
         // For non-streaming: use guard for automatic load management
         // For streaming: load will be managed in create_streaming_response
+        // The prompt is prefilled once, but decode runs once per sampled sequence
         let _guard = if !context.is_stream {
-            Some(WorkerLoadGuard::new_multi(vec![prefill, decode]))
+            Some((
+                WorkerLoadGuard::new(prefill),
+                WorkerLoadGuard::with_weight(decode, context.decode_load_weight),
+            ))
         } else {
             None
         };
@@ -1068,18 +1113,23 @@ impl PDRouter {
                             context.return_logprob,
                             None,
                             Some(response_headers),
+                            context.route,
                             prefill,
                             decode,
+                            context.decode_load_weight,
                         )
                     } else {
                         // Non-streaming response with logprobs
-                        self.process_non_streaming_response(
-                            res,
-                            status,
-                            context.return_logprob,
-                            prefill_body,
-                        )
-                        .await
+                        let mut response = self
+                            .process_non_streaming_response(
+                                res,
+                                status,
+                                context.return_logprob,
+                                prefill_body,
+                            )
+                            .await;
+                        response.extensions_mut().insert(WorkerResponse);
+                        response
                     }
                 }
                 Err(e) => {
@@ -1089,11 +1139,10 @@ impl PDRouter {
                         "Decode request failed"
                     );
                     RouterMetrics::record_pd_decode_error(decode.url());
-                    (
+                    error_response(
                         StatusCode::BAD_GATEWAY,
                         format!("Decode server error: {}", e),
                     )
-                        .into_response()
                 }
             }
         } else {
@@ -1192,8 +1241,10 @@ impl PDRouter {
                             false,
                             Some(decode_url),
                             Some(response_headers),
+                            context.route,
                             prefill,
                             decode,
+                            context.decode_load_weight,
                         )
                     } else {
                         // Non-streaming response without logprobs - direct passthrough like fast version
@@ -1206,12 +1257,15 @@ impl PDRouter {
                                     Response::new(axum::body::Body::from(decode_body));
                                 *response.status_mut() = status;
                                 *response.headers_mut() = response_headers;
+                                response.extensions_mut().insert(WorkerResponse);
                                 response
                             }
                             Err(e) => {
                                 error!("Failed to read decode response: {}", e);
-                                (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read response")
-                                    .into_response()
+                                error_response(
+                                    StatusCode::INTERNAL_SERVER_ERROR,
+                                    "Failed to read response",
+                                )
                             }
                         }
                     }
@@ -1223,11 +1277,10 @@ impl PDRouter {
                         "Decode request failed"
                     );
                     RouterMetrics::record_pd_decode_error(decode.url());
-                    (
+                    error_response(
                         StatusCode::BAD_GATEWAY,
                         format!("Decode server error: {}", e),
                     )
-                        .into_response()
                 }
             }
         }
@@ -1248,7 +1301,7 @@ impl PDRouter {
         model_id: Option<&str>,
     ) -> Result<(Arc<dyn Worker>, Arc<dyn Worker>), String> {
         // Get workers from registry - filter by model if provided
-        let prefill_workers = if let Some(model) = model_id {
+        let mut prefill_workers = if let Some(model) = model_id {
             // Get model-specific workers and filter for prefill type
             self.worker_registry
                 .get_by_model_fast(model)
@@ -1259,7 +1312,7 @@ impl PDRouter {
             self.worker_registry.get_prefill_workers()
         };
 
-        let decode_workers = if let Some(model) = model_id {
+        let mut decode_workers = if let Some(model) = model_id {
             // Get model-specific workers and filter for decode type
             self.worker_registry
                 .get_by_model_fast(model)
@@ -1270,6 +1323,17 @@ impl PDRouter {
             self.worker_registry.get_decode_workers()
         };
 
+        // Prefill and decode must agree on the version too, so pick it across both
+        if self.split_model_versions {
+            let available = prefill_workers
+                .iter()
+                .chain(&decode_workers)
+                .filter(|w| w.is_available());
+            let active = model_version::active_versions(available);
+            model_version::retain_versions(&mut prefill_workers, &active);
+            model_version::retain_versions(&mut decode_workers, &active);
+        }
+
         // Select workers using helper function
         // Use separate policies for prefill and decode to avoid counter conflicts
         let prefill_policy = self.policy_registry.get_prefill_policy();
@@ -1307,10 +1371,11 @@ impl PDRouter {
             ));
         }
 
-        // Filter available workers (healthy + circuit breaker not open)
+        // Filter available workers (healthy + circuit breaker not open), skipping
+        // gRPC workers that share the registry in IGW mode
         let available_workers: Vec<Arc<dyn Worker>> = workers
             .iter()
-            .filter(|w| w.is_available())
+            .filter(|w| w.is_available() && w.speaks(&ConnectionMode::Http))
             .cloned()
             .collect();
 
@@ -1338,23 +1403,35 @@ impl PDRouter {
 
     // Background task to monitor worker loads with shared client
     async fn monitor_worker_loads_with_client(
-        worker_urls: Vec<String>,
+        worker_registry: Arc<WorkerRegistry>,
         tx: tokio::sync::watch::Sender<HashMap<String, isize>>,
         interval_secs: u64,
         client: Client,
-        prefill_policy: Arc<dyn LoadBalancingPolicy>,
-        decode_policy: Arc<dyn LoadBalancingPolicy>,
+        auth: UpstreamAuthConfig,
+        policy_registry: Arc<PolicyRegistry>,
     ) {
         loop {
+            let policies = policy_registry.load_monitored_policies();
+            if policies.is_empty() {
+                if tx.is_closed() {
+                    info!("Load monitor receiver dropped, shutting down monitor task");
+                    break;
+                }
+                tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+                continue;
+            }
+
             let mut loads = HashMap::new();
 
-            let futures: Vec<_> = worker_urls
-                .iter()
+            // Read the registry each tick so added and removed workers are tracked
+            let futures: Vec<_> = worker_registry
+                .get_all_urls()
+                .into_iter()
                 .map(|url| {
                     let client = client.clone();
-                    let url = url.clone();
+                    let auth = &auth;
                     async move {
-                        let load = get_worker_load(&client, &url).await.unwrap_or(0);
+                        let load = get_worker_load(&client, auth, &url).await.unwrap_or(0);
                         (url, load)
                     }
                 })
@@ -1368,9 +1445,10 @@ impl PDRouter {
 
             debug!("Worker loads updated: {:?}", loads);
 
-            // Update both policies with current loads
-            prefill_policy.update_loads(&loads);
-            decode_policy.update_loads(&loads);
+            // Update every load-aware policy with current loads
+            for policy in &policies {
+                policy.update_loads(&loads);
+            }
 
             // Check if receiver is still active
             if tx.send(loads).is_err() {
@@ -1392,18 +1470,21 @@ impl PDRouter {
         return_logprob: bool,
         decode_url: Option<String>,
         headers: Option<HeaderMap>,
+        route: &'static str,
         prefill: &dyn Worker,
         decode: &dyn Worker,
+        decode_load_weight: usize,
     ) -> Response {
         // For streaming, increment load now - will be decremented when streaming completes
         prefill.increment_load();
-        decode.increment_load();
+        decode.increment_load_by(decode_load_weight);
 
         // Store URLs to find workers later for decrementing
         let prefill_url = prefill.url().to_string();
         let decode_url_str = decode.url().to_string();
 
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        // Bounded so a slow client stops the decode stream from being read ahead
+        let (tx, rx) = tokio::sync::mpsc::channel(self.stream_buffer_size);
 
         // Clone the registry for the spawned task
         let registry = self.worker_registry.clone();
@@ -1413,7 +1494,12 @@ impl PDRouter {
             let mut stream_completed = false;
 
             futures_util::pin_mut!(stream);
-            while let Some(chunk_result) = stream.next().await {
+            loop {
+                // Returning drops the decode stream, closing the connection to the worker
+                let chunk_result = match next_chunk(&mut stream, &tx, route).await {
+                    UpstreamRead::Chunk(chunk_result) => chunk_result,
+                    UpstreamRead::Ended | UpstreamRead::ClientGone => break,
+                };
                 match chunk_result {
                     Ok(chunk) => {
                         // Check for stream end marker to decrement load early
@@ -1430,7 +1516,8 @@ impl PDRouter {
                             chunk
                         };
 
-                        if tx.send(Ok(result)).is_err() {
+                        if tx.send(Ok(result)).await.is_err() {
+                            client_disconnected(route);
                             break;
                         }
 
@@ -1445,7 +1532,7 @@ impl PDRouter {
                             error!("Stream error from decode server {}: {}", url, e);
                             RouterMetrics::record_pd_stream_error(url);
                         }
-                        let _ = tx.send(Err(format!("Stream error: {}", e)));
+                        let _ = tx.send(Err(format!("Stream error: {}", e))).await;
                         break;
                     }
                 }
@@ -1463,7 +1550,7 @@ impl PDRouter {
 
             // Find and decrement decode worker
             if let Some(worker) = registry.get_by_url(&decode_url_str) {
-                worker.decrement_load();
+                worker.decrement_load_by(decode_load_weight);
                 debug!(
                     "Decremented load for decode worker: {} (stream_completed: {})",
                     decode_url_str, stream_completed
@@ -1471,7 +1558,7 @@ impl PDRouter {
             }
         });
 
-        let stream = UnboundedReceiverStream::new(rx);
+        let stream = ReceiverStream::new(rx);
         let body = Body::from_stream(stream);
 
         let mut response = Response::new(body);
@@ -1481,6 +1568,7 @@ impl PDRouter {
         let mut headers = headers.unwrap_or_default();
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
         *response.headers_mut() = headers;
+        response.extensions_mut().insert(WorkerResponse);
 
         response
     }
@@ -1498,8 +1586,10 @@ impl PDRouter {
             Ok(decode_body) => decode_body,
             Err(e) => {
                 error!("Failed to read decode response: {}", e);
-                return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read response")
-                    .into_response();
+                return error_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to read response",
+                );
             }
         };
 
@@ -1551,14 +1641,13 @@ impl PDRouter {
                 );
 
                 // Return error immediately - don't wait for decode to timeout
-                return Err((
+                return Err(error_response(
                     StatusCode::BAD_GATEWAY,
                     format!(
                         "Prefill server error: {}. This will cause decode timeout.",
                         e
                     ),
-                )
-                    .into_response());
+                ));
             }
         };
 
@@ -1635,14 +1724,13 @@ impl PDRouter {
         if connection_close {
             request = request.header("Connection", "close");
         }
+        request = header_utils::apply_upstream_auth(request, &self.upstream_auth, headers);
         if let Some(headers) = headers {
             for (name, value) in headers.iter() {
                 let name_lc = name.as_str().to_ascii_lowercase();
                 // Whitelist important end-to-end headers, skip hop-by-hop
-                let forward = matches!(
-                    name_lc.as_str(),
-                    "authorization" | "x-request-id" | "x-correlation-id"
-                ) || name_lc.starts_with("x-request-id-");
+                let forward = matches!(name_lc.as_str(), "x-request-id" | "x-correlation-id")
+                    || name_lc.starts_with("x-request-id-");
                 if forward {
                     if let Ok(val) = value.to_str() {
                         request = request.header(name, val);
@@ -1653,6 +1741,27 @@ impl PDRouter {
         request
     }
 
+    /// Pick the decode worker that requests bypassing the prefill stage go to
+    #[allow(clippy::result_large_err)]
+    fn select_proxy_decode_worker(&self) -> Result<Arc<dyn Worker>, Response> {
+        let decode_workers = self.worker_registry.get_decode_workers();
+        if decode_workers.is_empty() {
+            return Err(error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "No decode workers available",
+            ));
+        }
+
+        let decode_policy = self.policy_registry.get_decode_policy();
+        match decode_policy.select_worker(&decode_workers, None) {
+            Some(idx) => Ok(decode_workers[idx].clone()),
+            None => Err(error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Decode policy failed to select a worker",
+            )),
+        }
+    }
+
     // Helper to merge logprobs from prefill and decode responses
     // This is now a thin wrapper around the utility function in logprobs_merge module
     fn merge_logprobs_in_json(prefill_json: &Value, decode_json: &mut Value) -> bool {
@@ -1725,8 +1834,16 @@ impl PDRouter {
 
 // Helper functions
 
-async fn get_worker_load(client: &Client, worker_url: &str) -> Option<isize> {
-    match client.get(format!("{}/get_load", worker_url)).send().await {
+async fn get_worker_load(
+    client: &Client,
+    auth: &UpstreamAuthConfig,
+    worker_url: &str,
+) -> Option<isize> {
+    let request = client.get(format!("{}/get_load", worker_url));
+    match header_utils::apply_upstream_auth(request, auth, None)
+        .send()
+        .await
+    {
         Ok(res) if res.status().is_success() => match res.bytes().await {
             Ok(bytes) => match serde_json::from_slice::<Value>(&bytes) {
                 Ok(data) => data
@@ -1785,6 +1902,19 @@ impl WorkerManagement for PDRouter {
         }
     }
 
+    async fn drain_worker(&self, worker_url: &str, timeout: Duration) -> Result<String, String> {
+        let worker = self
+            .worker_registry
+            .get_by_url(worker_url)
+            .ok_or_else(|| format!("Worker {} not found", worker_url))?;
+
+        info!("Draining worker {} (timeout {:?})", worker_url, timeout);
+        let in_flight = crate::core::drain_workers(std::slice::from_ref(&worker), timeout).await;
+        WorkerManagement::remove_worker(self, worker_url);
+
+        Ok(drain_result_message(worker_url, in_flight))
+    }
+
     fn get_worker_urls(&self) -> Vec<String> {
         self.worker_registry.get_all_urls()
     }
@@ -1818,11 +1948,10 @@ impl RouterTrait for PDRouter {
         if all_healthy {
             (StatusCode::OK, "All servers healthy").into_response()
         } else {
-            (
+            error_response(
                 StatusCode::SERVICE_UNAVAILABLE,
                 format!("Unhealthy servers: {:?}", unhealthy_servers),
             )
-                .into_response()
         }
     }
 
@@ -1834,11 +1963,10 @@ impl RouterTrait for PDRouter {
         let (prefill, decode) = match self.select_pd_pair(None, None).await {
             Ok(pair) => pair,
             Err(e) => {
-                return (
+                return error_response(
                     StatusCode::SERVICE_UNAVAILABLE,
                     format!("No healthy worker pair available: {}", e),
-                )
-                    .into_response();
+                );
             }
         };
 
@@ -1848,11 +1976,14 @@ impl RouterTrait for PDRouter {
         let (decode_base_url, _) = super::dp_utils::parse_worker_url(decode.url());
 
         let prefill_url = format!("{}/health_generate", prefill_base_url);
+        let auth = |request| header_utils::apply_upstream_auth(request, &self.upstream_auth, None);
         let (prefill_result, decode_result) = tokio::join!(
-            self.client.get(&prefill_url).send(),
-            self.client
-                .get(format!("{}/health_generate", decode_base_url))
-                .send()
+            auth(self.client.get(&prefill_url)).send(),
+            auth(
+                self.client
+                    .get(format!("{}/health_generate", decode_base_url))
+            )
+            .send()
         );
 
         // Check results
@@ -1904,11 +2035,10 @@ impl RouterTrait for PDRouter {
             )
                 .into_response()
         } else {
-            (
+            error_response(
                 StatusCode::SERVICE_UNAVAILABLE,
                 format!("Health generate failed: {:?}", errors),
             )
-                .into_response()
         }
     }
 
@@ -1973,6 +2103,7 @@ impl RouterTrait for PDRouter {
             return_logprob,
             request_text,
             model_id,
+            decode_load_weight: body.load_weight(),
         };
 
         // Execute with retry and bootstrap injection
@@ -2014,6 +2145,7 @@ impl RouterTrait for PDRouter {
             return_logprob,
             request_text,
             model_id,
+            decode_load_weight: body.load_weight(),
         };
 
         // Execute with retry and bootstrap injection
@@ -2048,6 +2180,7 @@ impl RouterTrait for PDRouter {
             return_logprob,
             request_text,
             model_id,
+            decode_load_weight: body.load_weight(),
         };
 
         // Execute with retry and bootstrap injection
@@ -2060,27 +2193,24 @@ impl RouterTrait for PDRouter {
         _body: &ResponsesRequest,
         _model_id: Option<&str>,
     ) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Responses endpoint not implemented for PD router",
         )
-            .into_response()
     }
 
     async fn get_response(&self, _headers: Option<&HeaderMap>, _response_id: &str) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Responses retrieve endpoint not implemented for PD router",
         )
-            .into_response()
     }
 
     async fn cancel_response(&self, _headers: Option<&HeaderMap>, _response_id: &str) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Responses cancel endpoint not implemented for PD router",
         )
-            .into_response()
     }
 
     async fn route_embeddings(
@@ -2089,11 +2219,10 @@ impl RouterTrait for PDRouter {
         _body: &crate::protocols::spec::EmbeddingRequest,
         _model_id: Option<&str>,
     ) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Embeddings endpoint not implemented for PD router",
         )
-            .into_response()
     }
 
     async fn route_rerank(
@@ -2117,6 +2246,7 @@ impl RouterTrait for PDRouter {
             return_logprob: false,
             request_text: req_text,
             model_id,
+            decode_load_weight: 1,
         };
 
         // Execute with retry and bootstrap injection
@@ -2138,6 +2268,9 @@ impl RouterTrait for PDRouter {
             .process_workers(WorkerType::Decode, "Decode", "flush_cache")
             .await;
 
+        // Even a partial flush leaves the prefix trees stale, so always clear them
+        self.policy_registry.clear_cache_trees();
+
         // Combine results and errors
         let mut results = prefill_results;
         results.extend(decode_results);
@@ -2169,7 +2302,7 @@ impl RouterTrait for PDRouter {
         // Process prefill workers
         let prefill_urls = self.get_prefill_worker_urls();
         for worker_url in prefill_urls {
-            match get_worker_load(&self.client, &worker_url).await {
+            match get_worker_load(&self.client, &self.upstream_auth, &worker_url).await {
                 Some(load) => {
                     loads.insert(format!("prefill_{}", worker_url), load);
                 }
@@ -2182,7 +2315,7 @@ impl RouterTrait for PDRouter {
         // Process decode workers
         let decode_urls = self.get_decode_worker_urls();
         for worker_url in decode_urls {
-            match get_worker_load(&self.client, &worker_url).await {
+            match get_worker_load(&self.client, &self.upstream_auth, &worker_url).await {
                 Some(load) => {
                     loads.insert(format!("decode_{}", worker_url), load);
                 }
@@ -2204,6 +2337,26 @@ impl RouterTrait for PDRouter {
         "pd"
     }
 
+    fn get_worker_stats(&self) -> Response {
+        worker_stats_response(&self.worker_registry)
+    }
+
+    fn get_worker_circuit(&self, worker_url: &str) -> Response {
+        worker_circuit_response(&self.worker_registry, worker_url, false)
+    }
+
+    fn reset_worker_circuit(&self, worker_url: &str) -> Response {
+        worker_circuit_response(&self.worker_registry, worker_url, true)
+    }
+
+    fn set_worker_enabled(&self, worker_url: &str, enabled: bool) -> Response {
+        worker_enabled_response(&self.worker_registry, worker_url, enabled)
+    }
+
+    fn model_readiness(&self, model_id: &str) -> Response {
+        model_readiness_response(&self.worker_registry, model_id, 1)
+    }
+
     fn readiness(&self) -> Response {
         // PD router is ready if it has at least one healthy prefill AND one healthy decode worker
         let prefill_workers = self.worker_registry.get_prefill_workers();
@@ -2264,15 +2417,14 @@ impl RouterTrait for PDRouter {
         headers: Option<&HeaderMap>,
         path: &str,
         method: &Method,
-        body: serde_json::Value,
+        body: bytes::Bytes,
     ) -> Response {
         // Only handle POST requests for inference
         if *method != Method::POST {
-            return (
+            return error_response(
                 StatusCode::METHOD_NOT_ALLOWED,
                 "Only POST requests are supported for transparent proxy",
-            )
-                .into_response();
+            );
         }
 
         debug!(
@@ -2280,31 +2432,10 @@ impl RouterTrait for PDRouter {
             method, path
         );
 
-        // Get decode workers (for transparent proxy, we just forward to decode)
-        let decode_workers = self.worker_registry.get_decode_workers();
-
-        if decode_workers.is_empty() {
-            return (
-                StatusCode::SERVICE_UNAVAILABLE,
-                "No decode workers available",
-            )
-                .into_response();
-        }
-
-        // Select a decode worker using policy
-        let decode_policy = self.policy_registry.get_decode_policy();
-        let decode_idx = match decode_policy.select_worker(&decode_workers, None) {
-            Some(idx) => idx,
-            None => {
-                return (
-                    StatusCode::SERVICE_UNAVAILABLE,
-                    "Decode policy failed to select a worker".to_string(),
-                )
-                    .into_response();
-            }
+        let decode_worker = match self.select_proxy_decode_worker() {
+            Ok(worker) => worker,
+            Err(response) => return response,
         };
-
-        let decode_worker = &decode_workers[decode_idx];
         let url = decode_worker.endpoint_url(path);
 
         debug!("PDRouter transparent proxy: forwarding to {}", url);
@@ -2317,10 +2448,13 @@ impl RouterTrait for PDRouter {
 
         // Propagate headers
         request_builder = header_utils::propagate_trace_headers(request_builder, headers);
+        request_builder =
+            header_utils::apply_upstream_auth(request_builder, &self.upstream_auth, headers);
 
-        // Add JSON body if not null
-        if !body.is_null() {
-            request_builder = request_builder.json(&body);
+        // Forward the body unchanged
+        if !body.is_empty() {
+            request_builder = header_utils::forward_content_type(request_builder, headers);
+            request_builder = request_builder.body(body);
         }
 
         // Send request
@@ -2342,18 +2476,71 @@ impl RouterTrait for PDRouter {
 
                 match response_builder.body(body) {
                     Ok(response) => response,
-                    Err(e) => (
+                    Err(e) => error_response(
                         StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Failed to build response: {}", e),
-                    )
-                        .into_response(),
+                    ),
                 }
             }
-            Err(e) => (
+            Err(e) => error_response(
                 StatusCode::BAD_GATEWAY,
                 format!("Backend request failed: {}", e),
-            )
-                .into_response(),
+            ),
+        }
+    }
+
+    /// Forward a body that is not JSON to a decode worker as-is
+    ///
+    /// Such requests skip the prefill stage, like transparent proxy requests.
+    async fn route_raw(
+        &self,
+        headers: Option<&HeaderMap>,
+        path: &str,
+        method: &Method,
+        body: bytes::Bytes,
+    ) -> Response {
+        if *method != Method::POST {
+            return error_response(
+                StatusCode::METHOD_NOT_ALLOWED,
+                "Only POST requests are supported for non-JSON bodies",
+            );
+        }
+
+        let decode_worker = match self.select_proxy_decode_worker() {
+            Ok(worker) => worker,
+            Err(response) => return response,
+        };
+        let url = decode_worker.endpoint_url(path);
+        debug!(
+            "PDRouter: forwarding {} byte body for {} to {}",
+            body.len(),
+            path,
+            url
+        );
+
+        let mut request_builder = self.client.post(&url);
+        request_builder = dp_utils::add_dp_rank_header(request_builder, decode_worker.dp_rank());
+        request_builder = header_utils::propagate_trace_headers(request_builder, headers);
+        request_builder =
+            header_utils::apply_upstream_auth(request_builder, &self.upstream_auth, headers);
+        request_builder = header_utils::forward_content_type(request_builder, headers);
+
+        match request_builder.body(body).send().await {
+            Ok(response) => {
+                let status = StatusCode::from_u16(response.status().as_u16())
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                let mut response_headers =
+                    header_utils::preserve_response_headers(response.headers());
+                response_headers.remove(CONTENT_LENGTH);
+                let mut proxied = Response::new(Body::from_stream(response.bytes_stream()));
+                *proxied.status_mut() = status;
+                *proxied.headers_mut() = response_headers;
+                proxied
+            }
+            Err(e) => error_response(
+                StatusCode::BAD_GATEWAY,
+                format!("Backend request failed: {}", e),
+            ),
         }
     }
 }
@@ -2373,13 +2560,18 @@ mod tests {
             policy_registry,
             worker_startup_timeout_secs: 5,
             worker_startup_check_interval_secs: 1,
+            health_config: HealthConfig::default(),
             worker_loads: Arc::new(tokio::sync::watch::channel(HashMap::new()).1),
             load_monitor_handle: None,
             client: Client::new(),
             prefill_client: Client::new(),
             prefill_drain_tx: mpsc::channel(100).0,
             retry_config: RetryConfig::default(),
+            retry_budget: None,
             circuit_breaker_config: CircuitBreakerConfig::default(),
+            upstream_auth: UpstreamAuthConfig::Passthrough,
+            stream_buffer_size: 64,
+            split_model_versions: false,
         }
     }
 
@@ -2694,7 +2886,8 @@ mod tests {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
         let stream = tokio_stream::wrappers::UnboundedReceiverStream::new(rx);
 
-        // Call create_streaming_response which should increment load
+        // Call create_streaming_response which should increment load; the
+        // request samples 3 sequences, which only the decode worker feels
         let _response = router.create_streaming_response(
             stream.map(Ok),
             StatusCode::OK,
@@ -2702,13 +2895,15 @@ mod tests {
             false,
             None,
             None,
+            "/generate",
             prefill_ref.as_ref(),
             decode_ref.as_ref(),
+            3,
         );
 
         // Load should be incremented immediately
         assert_eq!(prefill_ref.load(), 1);
-        assert_eq!(decode_ref.load(), 1);
+        assert_eq!(decode_ref.load(), 3);
 
         // Send some data through the stream
         tx.send(bytes::Bytes::from("test data")).unwrap();
@@ -2716,9 +2911,9 @@ mod tests {
         // Give time for the spawned task to process
         sleep(Duration::from_millis(10)).await;
 
-        // Load should still be 1 (streaming in progress)
+        // Load should be unchanged while streaming is in progress
         assert_eq!(prefill_ref.load(), 1);
-        assert_eq!(decode_ref.load(), 1);
+        assert_eq!(decode_ref.load(), 3);
 
         // Close the stream
         drop(tx);
@@ -2765,4 +2960,47 @@ mod tests {
         let workers = router.worker_registry.get_prefill_workers();
         assert_eq!(workers.len(), 5);
     }
+
+    /// Worker that only answers requests carrying `Bearer sk-upstream`
+    async fn spawn_authenticated_worker() -> String {
+        async fn authorized(headers: HeaderMap) -> Response {
+            match headers.get("authorization") {
+                Some(value) if value == "Bearer sk-upstream" => {
+                    Json(serde_json::json!({"load": 7})).into_response()
+                }
+                _ => StatusCode::UNAUTHORIZED.into_response(),
+            }
+        }
+        let app = axum::Router::new()
+            .route("/get_load", axum::routing::get(authorized))
+            .route("/flush_cache", axum::routing::post(authorized));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_requests_carry_upstream_auth() {
+        let url = spawn_authenticated_worker().await;
+        let mut router = create_test_pd_router();
+        router
+            .worker_registry
+            .register(Arc::new(BasicWorker::new(url.clone(), WorkerType::Decode)));
+
+        assert_eq!(
+            get_worker_load(&router.client, &router.upstream_auth, &url).await,
+            None
+        );
+        assert_ne!(router.flush_cache().await.status(), StatusCode::OK);
+
+        router.upstream_auth = UpstreamAuthConfig::Inject {
+            key: "sk-upstream".to_string(),
+        };
+        assert_eq!(
+            get_worker_load(&router.client, &router.upstream_auth, &url).await,
+            Some(7)
+        );
+        assert_eq!(router.flush_cache().await.status(), StatusCode::OK);
+    }
 }
@@ -0,0 +1,286 @@
+//! In-router cache for deterministic, non-streaming completions
+//!
+//! Requests with greedy decoding and a single choice produce the same output
+//! for the same input, so their responses can be served from memory without
+//! selecting a worker. Entries expire after a fixed TTL and the least recently
+//! used entry is evicted once the cache is full.
+
+use crate::config::ResponseCacheConfig;
+use axum::{
+    body::{to_bytes, Body},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use ring::digest;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// SHA-256 digest of the route, model, and normalized request body
+pub type CacheKey = [u8; 32];
+
+/// A cached upstream response
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl IntoResponse for CachedResponse {
+    fn into_response(self) -> Response {
+        let mut response = Response::new(Body::from(self.body));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+
+struct CacheEntry {
+    response: CachedResponse,
+    inserted_at: Instant,
+    /// Position in the LRU order
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheInner {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Keys ordered from least to most recently used
+    lru: BTreeMap<u64, CacheKey>,
+    tick: u64,
+}
+
+impl CacheInner {
+    fn touch(&mut self, key: &CacheKey) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.lru.remove(&entry.last_used);
+            entry.last_used = tick;
+            self.lru.insert(tick, *key);
+        }
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.lru.remove(&entry.last_used);
+        }
+    }
+}
+
+/// LRU response cache with a per-entry TTL
+pub struct ResponseCache {
+    max_entries: usize,
+    ttl: Duration,
+    inner: Mutex<CacheInner>,
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("max_entries", &self.max_entries)
+            .field("ttl", &self.ttl)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl ResponseCache {
+    pub fn new(config: &ResponseCacheConfig) -> Self {
+        Self {
+            max_entries: config.max_entries.max(1),
+            ttl: Duration::from_secs(config.ttl_secs),
+            inner: Mutex::new(CacheInner::default()),
+        }
+    }
+
+    /// Build the cache key for a request
+    ///
+    /// The body is serialized through `serde_json::Value`, whose object keys
+    /// are sorted, so field order in the client's JSON does not matter.
+    /// Returns None if the request cannot be serialized.
+    pub fn request_key<T: serde::Serialize>(
+        route: &str,
+        model_id: Option<&str>,
+        request: &T,
+    ) -> Option<CacheKey> {
+        let body = serde_json::to_value(request)
+            .and_then(|value| serde_json::to_vec(&value))
+            .ok()?;
+
+        let mut ctx = digest::Context::new(&digest::SHA256);
+        // Length-prefix each part so different splits cannot collide
+        for part in [route.as_bytes(), model_id.unwrap_or("").as_bytes(), &body] {
+            ctx.update(&(part.len() as u64).to_le_bytes());
+            ctx.update(part);
+        }
+        let mut key = [0u8; 32];
+        key.copy_from_slice(ctx.finish().as_ref());
+        Some(key)
+    }
+
+    /// Look up a cached response, dropping it if it has expired
+    pub fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
+        let mut inner = self.inner.lock().unwrap();
+        let expired = inner.entries.get(key)?.inserted_at.elapsed() >= self.ttl;
+        if expired {
+            inner.remove(key);
+            return None;
+        }
+        inner.touch(key);
+        inner.entries.get(key).map(|entry| entry.response.clone())
+    }
+
+    /// Insert a response, evicting the least recently used entry when full
+    pub fn insert(&self, key: CacheKey, response: CachedResponse) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.remove(&key);
+        while inner.entries.len() >= self.max_entries {
+            let Some((_, oldest)) = inner.lru.pop_first() else {
+                break;
+            };
+            inner.entries.remove(&oldest);
+        }
+
+        inner.tick += 1;
+        let tick = inner.tick;
+        inner.lru.insert(tick, key);
+        inner.entries.insert(
+            key,
+            CacheEntry {
+                response,
+                inserted_at: Instant::now(),
+                last_used: tick,
+            },
+        );
+    }
+
+    /// Buffer a successful response into the cache and hand back an equivalent one
+    pub async fn store(&self, key: CacheKey, response: Response) -> Response {
+        let (parts, body) = response.into_parts();
+        match to_bytes(body, usize::MAX).await {
+            Ok(body) => {
+                self.insert(
+                    key,
+                    CachedResponse {
+                        status: parts.status,
+                        headers: parts.headers.clone(),
+                        body: body.clone(),
+                    },
+                );
+                Response::from_parts(parts, Body::from(body))
+            }
+            Err(e) => {
+                warn!("Failed to buffer response for caching: {}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to read response body: {}", e),
+                )
+                    .into_response()
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cache(max_entries: usize, ttl_secs: u64) -> ResponseCache {
+        ResponseCache::new(&ResponseCacheConfig {
+            max_entries,
+            ttl_secs,
+        })
+    }
+
+    fn cached(body: &'static str) -> CachedResponse {
+        CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    fn key(n: u8) -> CacheKey {
+        [n; 32]
+    }
+
+    #[test]
+    fn test_request_key_ignores_field_order() {
+        let a: serde_json::Value =
+            serde_json::from_str(r#"{"prompt": "hi", "temperature": 0}"#).unwrap();
+        let b: serde_json::Value =
+            serde_json::from_str(r#"{"temperature": 0, "prompt": "hi"}"#).unwrap();
+        let c = json!({"prompt": "bye", "temperature": 0});
+
+        let key_a = ResponseCache::request_key("/v1/completions", Some("m"), &a);
+        assert_eq!(
+            key_a,
+            ResponseCache::request_key("/v1/completions", Some("m"), &b)
+        );
+        assert_ne!(
+            key_a,
+            ResponseCache::request_key("/v1/completions", Some("m"), &c)
+        );
+        assert_ne!(
+            key_a,
+            ResponseCache::request_key("/v1/completions", Some("other"), &a)
+        );
+        assert_ne!(
+            key_a,
+            ResponseCache::request_key("/v1/chat/completions", Some("m"), &a)
+        );
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = cache(2, 60);
+        cache.insert(key(1), cached("one"));
+        cache.insert(key(2), cached("two"));
+
+        // Touch 1 so 2 becomes the eviction candidate
+        assert!(cache.get(&key(1)).is_some());
+        cache.insert(key(3), cached("three"));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key(1)).unwrap().body, "one");
+        assert!(cache.get(&key(2)).is_none());
+        assert_eq!(cache.get(&key(3)).unwrap().body, "three");
+    }
+
+    #[test]
+    fn test_reinsert_replaces_entry() {
+        let cache = cache(2, 60);
+        cache.insert(key(1), cached("old"));
+        cache.insert(key(1), cached("new"));
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&key(1)).unwrap().body, "new");
+    }
+
+    #[test]
+    fn test_expired_entries_are_dropped() {
+        let cache = ResponseCache {
+            max_entries: 4,
+            ttl: Duration::from_millis(10),
+            inner: Mutex::new(CacheInner::default()),
+        };
+        cache.insert(key(1), cached("one"));
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert!(cache.get(&key(1)).is_none());
+        assert!(cache.is_empty());
+    }
+}
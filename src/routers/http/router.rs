@@ -1,35 +1,288 @@
-use crate::config::types::RetryConfig;
+use crate::config::types::{RetryConfig, TrafficSplitPool, WorkerWarmupConfig};
 use crate::core::{
-    is_retryable_status, BasicWorker, CircuitBreakerConfig, HealthConfig, RetryExecutor, Worker,
-    WorkerRegistry, WorkerType,
+    connection_failure_response, is_retryable_response, is_retryable_status, model_version,
+    upstream_tls, BasicWorker, CircuitBreakerConfig, ConnectionMode, HealthConfig,
+    OwnedWorkerLoadGuard, RetryBudget, RetryExecutor, Worker, WorkerRateLimiter, WorkerRegistry,
+    WorkerResponse, WorkerType,
 };
+use crate::data_connector::SharedResponseStorage;
+use crate::logging::{log_access, AccessLogEntry};
 use crate::metrics::RouterMetrics;
-use crate::policies::{LoadBalancingPolicy, PolicyRegistry};
+use crate::policies::{
+    normalize_model_key, LoadBalancingPolicy, MintedSession, PolicyRegistry, DEFAULT_LATENCY_DECAY,
+};
 use crate::protocols::spec::{
     ChatCompletionRequest, CompletionRequest, EmbeddingRequest, GenerateRequest, GenerationRequest,
-    RerankRequest, RerankResponse, RerankResult, ResponsesRequest,
+    RerankRequest, RerankResponse, RerankResult, ResponseInputItemsParams, ResponsesRequest,
 };
-use crate::routers::header_utils;
+use crate::routers::body_logging::BodyLogger;
+use crate::routers::error_response::{error_response, error_response_with_code};
+use crate::routers::header_utils::{self, HeaderForwardPolicy};
+use crate::routers::http::client_disconnect::{client_disconnected, next_chunk, UpstreamRead};
+use crate::routers::http::coalesce::RequestCoalescer;
 use crate::routers::http::dp_utils;
-use crate::routers::{RouterTrait, WorkerManagement};
+use crate::routers::http::mirror::TrafficMirror;
+use crate::routers::http::response_cache::ResponseCache;
+use crate::routers::http::stored_responses;
+use crate::routers::{
+    drain_result_message, model_readiness_response, worker_circuit_response,
+    worker_enabled_response, worker_stats_response, RouterTrait, WorkerManagement,
+};
 use axum::body::to_bytes;
 use axum::{
     body::Body,
     extract::Request,
     http::{
-        header::CONTENT_LENGTH, header::CONTENT_TYPE, HeaderMap, HeaderValue, Method, StatusCode,
+        header::AUTHORIZATION, header::CONTENT_LENGTH, header::CONTENT_TYPE, header::COOKIE,
+        header::SET_COOKIE, HeaderMap, HeaderValue, Method, StatusCode,
     },
     response::{IntoResponse, Response},
     Json,
 };
-use futures_util::StreamExt;
+use rand::Rng;
 use reqwest::Client;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, error, info, warn};
 
+/// Model id for dynamically added workers that do not report a model
+const DEFAULT_MODEL_ID: &str = "default";
+
+/// Tokens generated by each warmup completion
+const WARMUP_MAX_TOKENS: u32 = 16;
+
+/// Request header naming the worker to route to when worker pinning is enabled
+const TARGET_WORKER_HEADER: &str = "x-router-target-worker";
+
+/// Request header setting the generation timeout in seconds, when allowed by
+/// `max_request_timeout_secs`
+const TIMEOUT_HEADER: &str = "x-router-timeout-secs";
+
+/// Request header listing tags (`key=value` or `key`, comma-separated) a worker must carry
+const REQUIRE_TAGS_HEADER: &str = "x-router-require-tags";
+
+/// Response header naming the fallback model that served a request
+const FALLBACK_MODEL_HEADER: &str = "x-router-fallback-model";
+/// Response header naming the traffic split pool that served a request
+const TRAFFIC_POOL_HEADER: &str = "x-router-traffic-pool";
+/// Response headers naming the worker and policy that served a request and
+/// how often it was retried, when `expose_routing_headers` is enabled
+const WORKER_HEADER: &str = "x-router-worker";
+const POLICY_HEADER: &str = "x-router-policy";
+const RETRIES_HEADER: &str = "x-router-retries";
+
+/// Routes whose requests are safe to send twice; hedging is limited to these
+const HEDGEABLE_ROUTES: &[&str] = &[
+    "/generate",
+    "/v1/chat/completions",
+    "/v1/completions",
+    "/v1/embeddings",
+    "/v1/rerank",
+];
+
+/// Worker and policy chosen by the latest attempt of a routed request
+#[derive(Debug, Default)]
+struct RoutingDecision {
+    worker_url: Option<String>,
+    policy: Option<&'static str>,
+    retries: u32,
+}
+
+/// Whether an SSE chunk carries a `data:` frame with content, used to detect
+/// the first token of a streaming response
+fn has_data_frame(chunk: &[u8]) -> bool {
+    chunk.split(|&b| b == b'\n').any(|line| {
+        line.strip_prefix(b"data:").is_some_and(|payload| {
+            let payload = payload.trim_ascii();
+            !payload.is_empty() && payload != b"[DONE]"
+        })
+    })
+}
+
+/// Tracks how an upstream SSE stream ended so truncation can be detected
+#[derive(Debug, Default)]
+struct SseTermination {
+    done: bool,
+    error_frame: bool,
+    /// Trailing bytes of an SSE line split across upstream chunks
+    partial: Vec<u8>,
+}
+
+impl SseTermination {
+    fn observe(&mut self, chunk: &[u8]) {
+        self.partial.extend_from_slice(chunk);
+        let Some(last_newline) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return;
+        };
+        let complete: Vec<u8> = self.partial.drain(..=last_newline).collect();
+        for line in complete.split(|&b| b == b'\n') {
+            self.observe_line(line);
+        }
+    }
+
+    /// Check any line still buffered when the upstream stream ends
+    fn finish(&mut self) {
+        let rest = std::mem::take(&mut self.partial);
+        self.observe_line(&rest);
+    }
+
+    fn observe_line(&mut self, line: &[u8]) {
+        let line = line.trim_ascii();
+        if let Some(event) = line.strip_prefix(b"event:") {
+            // /v1/responses streams end on a terminal event instead of [DONE]
+            match event.trim_ascii() {
+                b"error" | b"response.failed" => self.error_frame = true,
+                b"response.completed" | b"response.incomplete" => self.done = true,
+                _ => {}
+            }
+        } else if let Some(payload) = line.strip_prefix(b"data:") {
+            let payload = payload.trim_ascii();
+            if payload == b"[DONE]" {
+                self.done = true;
+            } else if payload.starts_with(b"{\"error\"") {
+                self.error_frame = true;
+            }
+        }
+    }
+
+    /// The stream has ended without a terminal marker or an error frame
+    fn is_truncated(&self) -> bool {
+        !self.done && !self.error_frame
+    }
+}
+
+/// SSE frame sent to the client when the upstream stream ends abnormally
+fn stream_truncated_frame(reason: &str) -> bytes::Bytes {
+    let error = serde_json::json!({
+        "error": {
+            "message": format!("Upstream stream ended before completion: {}", reason),
+            "type": "stream_truncated",
+            "code": StatusCode::BAD_GATEWAY.as_u16(),
+        }
+    });
+    bytes::Bytes::from(format!("data: {}\n\n", error))
+}
+
+/// Await a request to a worker, warning once it has gone `threshold` without
+/// completing
+///
+/// Slow requests are logged and counted but left running, since a long
+/// prefill can be legitimate; the generation timeout still bounds them.
+async fn watch_slow_request<F: std::future::Future>(
+    send: F,
+    threshold: Option<Duration>,
+    route: &str,
+    worker_url: &str,
+) -> F::Output {
+    let Some(threshold) = threshold else {
+        return send.await;
+    };
+    tokio::pin!(send);
+    match tokio::time::timeout(threshold, &mut send).await {
+        Ok(output) => output,
+        Err(_) => {
+            warn!(
+                "No response headers from worker_url={} route={} after {:?}, still waiting",
+                worker_url, route, threshold
+            );
+            RouterMetrics::record_slow_request(route, worker_url);
+            send.await
+        }
+    }
+}
+
+/// SSE comment sent while a stream is idle; clients ignore comment lines
+const KEEP_ALIVE_FRAME: &[u8] = b": keep-alive\n\n";
+
+/// Forward an upstream SSE stream to the client channel
+///
+/// Records time-to-first-token, calls `on_done` when the `[DONE]` marker is
+/// seen, and, if the upstream ends without `[DONE]` or an error frame, sends
+/// the client an error frame and records the truncation. The channel is
+/// bounded, so a client that reads slowly also slows the upstream read.
+/// With `keep_alive` set, a comment frame is sent whenever the upstream has
+/// been silent that long, e.g. during a long prefill.
+async fn forward_sse_stream<S, E>(
+    mut stream: S,
+    tx: tokio::sync::mpsc::Sender<Result<bytes::Bytes, String>>,
+    route: &str,
+    worker_url: &str,
+    request_start: Instant,
+    keep_alive: Option<Duration>,
+    mut on_done: impl FnMut(),
+) where
+    S: futures_util::Stream<Item = Result<bytes::Bytes, E>> + Unpin,
+    E: std::fmt::Display,
+{
+    let mut termination = SseTermination::default();
+    let mut first_token_seen = false;
+    let mut failure = None;
+
+    loop {
+        let read = match keep_alive {
+            Some(interval) => {
+                match tokio::time::timeout(interval, next_chunk(&mut stream, &tx, route)).await {
+                    Ok(read) => read,
+                    Err(_) => {
+                        let frame = bytes::Bytes::from_static(KEEP_ALIVE_FRAME);
+                        if tx.send(Ok(frame)).await.is_err() {
+                            client_disconnected(route);
+                            return;
+                        }
+                        continue;
+                    }
+                }
+            }
+            None => next_chunk(&mut stream, &tx, route).await,
+        };
+        let chunk = match read {
+            UpstreamRead::Chunk(chunk) => chunk,
+            UpstreamRead::Ended => break,
+            // Dropping the stream closes the worker connection
+            UpstreamRead::ClientGone => return,
+        };
+        match chunk {
+            Ok(bytes) => {
+                if !first_token_seen && has_data_frame(&bytes) {
+                    first_token_seen = true;
+                    RouterMetrics::record_ttft(route, request_start.elapsed());
+                }
+                let was_done = termination.done;
+                termination.observe(&bytes);
+                if termination.done && !was_done {
+                    on_done();
+                }
+                if tx.send(Ok(bytes)).await.is_err() {
+                    // Client went away; nobody to report truncation to
+                    client_disconnected(route);
+                    return;
+                }
+            }
+            Err(e) => {
+                failure = Some(e.to_string());
+                break;
+            }
+        }
+    }
+
+    let was_done = termination.done;
+    termination.finish();
+    if termination.done && !was_done {
+        on_done();
+    }
+    if termination.is_truncated() {
+        let reason = failure.unwrap_or_else(|| "connection closed".to_string());
+        warn!(
+            "Stream from {} for {} ended before completion: {}",
+            worker_url, route, reason
+        );
+        RouterMetrics::record_stream_truncated(route, worker_url);
+        let _ = tx.send(Ok(stream_truncated_frame(&reason))).await;
+    }
+}
+
 /// Regular router that uses injected load balancing policies
 #[derive(Debug)]
 pub struct Router {
@@ -41,7 +294,50 @@ pub struct Router {
     intra_node_data_parallel_size: usize,
     api_key: Option<String>,
     retry_config: RetryConfig,
+    retry_budget: Option<Arc<RetryBudget>>,
     circuit_breaker_config: CircuitBreakerConfig,
+    /// Health check settings for workers added at runtime
+    health_config: HealthConfig,
+    hedge_delay: Option<Duration>,
+    /// Copies a share of requests to shadow workers (None = disabled)
+    traffic_mirror: Option<TrafficMirror>,
+    /// Timeout for short proxied calls (health, load, server and model info)
+    proxy_timeout: Duration,
+    /// Timeout for generation requests, which may stream for a long time
+    generation_timeout: Duration,
+    /// Cap on the generation timeout set by the timeout header (None = header ignored)
+    max_request_timeout: Option<Duration>,
+    /// Warn when a worker has not answered with headers after this long (None = disabled)
+    slow_request_threshold: Option<Duration>,
+    /// Cache for deterministic non-streaming responses
+    response_cache: Option<Arc<ResponseCache>>,
+    header_policy: HeaderForwardPolicy,
+    /// Debug logging of request and response bodies (None = disabled)
+    body_logger: Option<BodyLogger>,
+    /// Honor the X-Router-Target-Worker header
+    allow_worker_pinning: bool,
+    /// Report the worker, policy and retries of a request in response headers
+    expose_routing_headers: bool,
+    /// Only select workers serving the model's active version
+    split_model_versions: bool,
+    /// Route by the request's model field when no model ID is given
+    strict_model_routing: bool,
+    min_healthy_workers: usize,
+    /// Models tried in order when a model has no available workers
+    model_fallbacks: HashMap<String, Vec<String>>,
+    /// Weighted worker pools requests for a model are split across
+    traffic_splits: HashMap<String, Vec<TrafficSplitPool>>,
+    /// Chunks buffered per streaming response
+    stream_buffer_size: usize,
+    /// Idle time after which a keep-alive comment is sent on a stream
+    stream_keepalive: Option<Duration>,
+    /// Per-worker request rate limits (None = disabled)
+    worker_rate_limiter: Option<Arc<WorkerRateLimiter>>,
+    /// Warmup sent to workers added at runtime (None = disabled)
+    worker_warmup: Option<WorkerWarmupConfig>,
+    /// Shares one upstream call between identical concurrent embedding requests
+    embeddings_coalescer: RequestCoalescer,
+    response_storage: SharedResponseStorage,
     _worker_loads: Arc<tokio::sync::watch::Receiver<HashMap<String, isize>>>,
     _load_monitor_handle: Option<Arc<tokio::task::JoinHandle<()>>>,
 }
@@ -56,16 +352,49 @@ impl Router {
         // Update active workers gauge
         RouterMetrics::set_active_workers(worker_urls.len());
 
+        let health_config = HealthConfig {
+            timeout_secs: ctx.router_config.health_check.timeout_secs,
+            check_interval_secs: ctx.router_config.health_check.check_interval_secs,
+            endpoint: ctx.router_config.health_check.endpoint.clone(),
+            failure_threshold: ctx.router_config.health_check.failure_threshold,
+            success_threshold: ctx.router_config.health_check.success_threshold,
+            method: ctx.router_config.health_check.method,
+            expected_body: ctx.router_config.health_check.expected_body.clone(),
+            generation_probe: ctx.router_config.health_check.generation_probe,
+        };
+
         // Wait for workers to be healthy (skip if empty - for service discovery mode)
         if !worker_urls.is_empty() {
             Self::wait_for_healthy_workers(
                 &worker_urls,
                 ctx.router_config.worker_startup_timeout_secs,
                 ctx.router_config.worker_startup_check_interval_secs,
+                &health_config,
             )
             .await?;
         }
 
+        let header_policy = HeaderForwardPolicy::new(&ctx.router_config.header_policy)
+            .with_upstream_auth(ctx.router_config.effective_upstream_auth());
+
+        // Resolve the model each worker serves so per-model policies and
+        // strict model routing apply to startup workers too
+        let mut model_ids = HashMap::new();
+        for url in &worker_urls {
+            let model_id = Self::fetch_model_id(&header_policy, &ctx.client, url).await;
+            model_ids.insert(url.clone(), model_id);
+        }
+
+        if ctx.router_config.strict_model_routing
+            && !model_ids.is_empty()
+            && model_ids.values().all(|model| model == DEFAULT_MODEL_ID)
+        {
+            return Err(
+                "strict_model_routing is enabled but no worker reported the model it serves"
+                    .to_string(),
+            );
+        }
+
         // Automatically expand to DP-aware workers when intra_node_data_parallel_size > 1
         let worker_urls = if ctx.router_config.intra_node_data_parallel_size > 1 {
             // worker address now in the format of "http://host:port@dp_rank"
@@ -87,22 +416,22 @@ impl Router {
             success_threshold: circuit_breaker_config.success_threshold,
             timeout_duration: Duration::from_secs(circuit_breaker_config.timeout_duration_secs),
             window_duration: Duration::from_secs(circuit_breaker_config.window_duration_secs),
+            half_open_max_concurrent: circuit_breaker_config.half_open_max_concurrent,
         };
 
-        // Register workers in the registry
-        // In IGW mode, we need to fetch model info from workers
+        // Register workers in the registry under the model they serve
         for url in &worker_urls {
-            // TODO: In IGW mode, fetch model_id from worker's /get_model_info endpoint
-            // For now, create worker without model_id
+            let base_url = match dp_utils::extract_dp_rank(url) {
+                Ok((prefix, _)) if ctx.router_config.intra_node_data_parallel_size > 1 => prefix,
+                _ => url.as_str(),
+            };
+            let model_id = model_ids
+                .get(base_url)
+                .map_or(DEFAULT_MODEL_ID, String::as_str);
             let worker = BasicWorker::new(url.clone(), WorkerType::Regular)
+                .with_labels(Self::model_labels(model_id))
                 .with_circuit_breaker_config(core_cb_config.clone())
-                .with_health_config(HealthConfig {
-                    timeout_secs: ctx.router_config.health_check.timeout_secs,
-                    check_interval_secs: ctx.router_config.health_check.check_interval_secs,
-                    endpoint: ctx.router_config.health_check.endpoint.clone(),
-                    failure_threshold: ctx.router_config.health_check.failure_threshold,
-                    success_threshold: ctx.router_config.health_check.success_threshold,
-                });
+                .with_health_config(health_config.clone());
 
             let worker_arc = Arc::new(worker);
             ctx.worker_registry.register(worker_arc.clone());
@@ -113,41 +442,38 @@ impl Router {
 
             // If this is a cache-aware policy and it's the first worker for this model,
             // initialize it with the worker
-            if policy.name() == "cache_aware" {
-                if let Some(cache_aware) = policy
-                    .as_any()
-                    .downcast_ref::<crate::policies::CacheAwarePolicy>()
-                {
-                    let worker_dyn: Arc<dyn Worker> = worker_arc.clone();
-                    cache_aware.init_workers(std::slice::from_ref(&worker_dyn));
-                }
+            if let Some(cache_aware) = policy.as_cache_aware() {
+                let worker_dyn: Arc<dyn Worker> = worker_arc.clone();
+                cache_aware.init_workers(std::slice::from_ref(&worker_dyn));
             }
         }
 
-        // Setup load monitoring for PowerOfTwo policy
+        // Setup load monitoring for load-aware policies
         let (tx, rx) = tokio::sync::watch::channel(HashMap::new());
         let worker_loads = Arc::new(rx);
 
-        // Check if default policy is power_of_two for load monitoring
-        let default_policy = ctx.policy_registry.get_default_policy();
-        let load_monitor_handle = if default_policy.name() == "power_of_two" {
-            let monitor_urls = worker_urls.clone();
+        // Always run the monitor; it idles while no registered policy uses
+        // worker loads, so a runtime swap to a load-aware policy is covered
+        let load_monitor_handle = {
+            let monitor_registry = Arc::clone(&ctx.worker_registry);
             let monitor_interval = ctx.router_config.worker_startup_check_interval_secs;
-            let policy_clone = default_policy.clone();
+            let registry_clone = Arc::clone(&ctx.policy_registry);
             let client_clone = ctx.client.clone();
+            let header_policy = header_policy.clone();
+            let load_timeout = Duration::from_secs(ctx.router_config.proxy_timeout_secs);
 
             Some(Arc::new(tokio::spawn(async move {
                 Self::monitor_worker_loads(
-                    monitor_urls,
+                    monitor_registry,
                     tx,
                     monitor_interval,
-                    policy_clone,
+                    registry_clone,
                     client_clone,
+                    header_policy,
+                    load_timeout,
                 )
                 .await;
             })))
-        } else {
-            None
         };
 
         Ok(Router {
@@ -161,7 +487,58 @@ impl Router {
             intra_node_data_parallel_size: ctx.router_config.intra_node_data_parallel_size,
             api_key: ctx.router_config.api_key.clone(),
             retry_config: ctx.router_config.effective_retry_config(),
+            retry_budget: RetryBudget::from_config(&ctx.router_config.retry).map(Arc::new),
             circuit_breaker_config: core_cb_config,
+            health_config,
+            hedge_delay: ctx
+                .router_config
+                .hedging
+                .as_ref()
+                .map(|h| Duration::from_millis(h.hedge_delay_ms)),
+            traffic_mirror: ctx
+                .router_config
+                .traffic_mirror
+                .as_ref()
+                .map(TrafficMirror::new),
+            proxy_timeout: Duration::from_secs(ctx.router_config.proxy_timeout_secs),
+            generation_timeout: Duration::from_secs(
+                ctx.router_config.effective_generation_timeout_secs(),
+            ),
+            max_request_timeout: ctx
+                .router_config
+                .max_request_timeout_secs
+                .map(Duration::from_secs),
+            slow_request_threshold: ctx
+                .router_config
+                .slow_request_threshold_secs
+                .map(Duration::from_secs),
+            response_cache: ctx
+                .router_config
+                .response_cache
+                .as_ref()
+                .map(|cfg| Arc::new(ResponseCache::new(cfg))),
+            header_policy,
+            body_logger: ctx.router_config.body_logging.as_ref().map(BodyLogger::new),
+            allow_worker_pinning: ctx.router_config.allow_worker_pinning,
+            expose_routing_headers: ctx.router_config.expose_routing_headers,
+            split_model_versions: ctx.router_config.split_model_versions(),
+            strict_model_routing: ctx.router_config.strict_model_routing,
+            min_healthy_workers: ctx.router_config.min_healthy_workers,
+            model_fallbacks: ctx.router_config.model_fallbacks.clone(),
+            traffic_splits: ctx.router_config.model_traffic_splits.clone(),
+            stream_buffer_size: ctx.router_config.stream_buffer_size,
+            stream_keepalive: ctx
+                .router_config
+                .stream_keepalive_secs
+                .map(Duration::from_secs),
+            worker_rate_limiter: ctx
+                .router_config
+                .worker_rate_limit
+                .as_ref()
+                .map(|cfg| Arc::new(WorkerRateLimiter::new(cfg))),
+            worker_warmup: ctx.router_config.worker_warmup.clone(),
+            embeddings_coalescer: RequestCoalescer::new(),
+            response_storage: Arc::clone(&ctx.response_storage),
             _worker_loads: worker_loads,
             _load_monitor_handle: load_monitor_handle,
         })
@@ -185,6 +562,7 @@ impl Router {
         worker_urls: &[String],
         worker_startup_timeout_secs: u64,
         worker_startup_check_interval_secs: u64,
+        health_config: &HealthConfig,
     ) -> Result<(), String> {
         if worker_urls.is_empty() {
             return Err(
@@ -197,6 +575,7 @@ impl Router {
             worker_urls,
             worker_startup_timeout_secs,
             worker_startup_check_interval_secs,
+            health_config,
         )
         .await
     }
@@ -205,6 +584,7 @@ impl Router {
         worker_urls: &[String],
         worker_startup_timeout_secs: u64,
         worker_startup_check_interval_secs: u64,
+        health_config: &HealthConfig,
     ) -> Result<(), String> {
         // Extract unique base URLs (hosts) for health checks
         // This deduplicates DP-aware URLs like http://host:8081@0, @1, @2, @3
@@ -239,7 +619,7 @@ impl Router {
         );
 
         let start_time = std::time::Instant::now();
-        let client = reqwest::Client::builder()
+        let client = upstream_tls::client_builder()
             .timeout(Duration::from_secs(2))
             .build()
             .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
@@ -261,17 +641,18 @@ impl Router {
             for base_url in &unique_hosts_vec {
                 let client_clone = client.clone();
                 let url_clone = base_url.clone();
+                let health_config = health_config.clone();
 
                 let check_health = tokio::spawn(async move {
-                    let health_url = format!("{}/health", url_clone);
-                    match client_clone.get(&health_url).send().await {
-                        Ok(res) => {
-                            if res.status().is_success() {
-                                None
-                            } else {
-                                Some((url_clone, format!("status: {}", res.status())))
-                            }
-                        }
+                    match health_config
+                        .request(&client_clone, &url_clone)
+                        .send()
+                        .await
+                    {
+                        Ok(res) => match health_config.check_response(res).await {
+                            Ok(()) => None,
+                            Err(reason) => Some((url_clone, reason)),
+                        },
                         Err(_) => Some((url_clone, "not ready".to_string())),
                     }
                 });
@@ -352,18 +733,20 @@ impl Router {
                 Ok((worker_url_prefix, _dp_rank)) => worker_url_prefix,
                 Err(e) => {
                     error!("Failed to extract dp_rank for health check: {}", e);
-                    return (
+                    return error_response(
                         StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Failed to extract dp_rank: {}", e),
-                    )
-                        .into_response();
+                    );
                 }
             }
         } else {
             worker_url
         };
 
-        let request_builder = self.client.get(format!("{}/health", health_url));
+        let request_builder = self
+            .client
+            .get(format!("{}/health", health_url))
+            .timeout(self.proxy_timeout);
 
         let response = match request_builder.send().await {
             Ok(res) => {
@@ -378,11 +761,10 @@ impl Router {
                             error = %e,
                             "Failed to read health response body"
                         );
-                        (
+                        error_response(
                             StatusCode::INTERNAL_SERVER_ERROR,
                             format!("Failed to read response body: {}", e),
                         )
-                            .into_response()
                     }
                 }
             }
@@ -392,11 +774,10 @@ impl Router {
                     error = %e,
                     "Failed to send health request to worker"
                 );
-                (
+                error_response(
                     StatusCode::INTERNAL_SERVER_ERROR,
                     format!("Failed to send request to worker {}: {}", health_url, e),
                 )
-                    .into_response()
             }
         };
 
@@ -406,17 +787,15 @@ impl Router {
 
     // Helper method to proxy GET requests to the first available worker
     async fn proxy_get_request(&self, req: Request<Body>, endpoint: &str) -> Response {
-        let headers = header_utils::copy_request_headers(&req);
-
         match self.select_first_worker() {
             Ok(worker_url) => {
-                let mut request_builder = self.client.get(format!("{}/{}", worker_url, endpoint));
-                for (name, value) in headers {
-                    let name_lc = name.to_lowercase();
-                    if name_lc != "content-type" && name_lc != "content-length" {
-                        request_builder = request_builder.header(name, value);
-                    }
-                }
+                let request_builder = self
+                    .client
+                    .get(format!("{}/{}", worker_url, endpoint))
+                    .timeout(self.proxy_timeout);
+                let request_builder = self
+                    .header_policy
+                    .apply(request_builder, Some(req.headers()));
 
                 match request_builder.send().await {
                     Ok(res) => {
@@ -434,21 +813,19 @@ impl Router {
                                 *response.headers_mut() = response_headers;
                                 response
                             }
-                            Err(e) => (
+                            Err(e) => error_response(
                                 StatusCode::INTERNAL_SERVER_ERROR,
                                 format!("Failed to read response: {}", e),
-                            )
-                                .into_response(),
+                            ),
                         }
                     }
-                    Err(e) => (
+                    Err(e) => error_response(
                         StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Request failed: {}", e),
-                    )
-                        .into_response(),
+                    ),
                 }
             }
-            Err(e) => (StatusCode::SERVICE_UNAVAILABLE, e).into_response(),
+            Err(e) => error_response(StatusCode::SERVICE_UNAVAILABLE, e),
         }
     }
 
@@ -468,12 +845,17 @@ impl Router {
         })
     }
 
-    /// Select worker for a specific model considering circuit breaker state
-    fn select_worker_for_model(
+    /// Select worker for a specific model considering circuit breaker state,
+    /// skipping workers whose URL is in `exclude`
+    ///
+    /// Used on retries so a failed worker is not picked again. Falls back to the
+    /// excluded workers only when no other available worker remains.
+    fn select_worker_for_model_excluding(
         &self,
         model_id: Option<&str>,
         text: Option<&str>,
         headers: Option<&HeaderMap>,
+        exclude: &HashSet<String>,
     ) -> Option<Arc<dyn Worker>> {
         // Get workers for the specified model (O(1) lookup if model_id is provided)
         let workers = match model_id {
@@ -481,15 +863,34 @@ impl Router {
             None => self.worker_registry.get_all(),
         };
 
-        let available: Vec<Arc<dyn Worker>> = workers
+        let required_tags = Self::required_tags(headers);
+        let mut available: Vec<Arc<dyn Worker>> = workers
             .iter()
-            .filter(|w| w.is_available())
+            .filter(|w| {
+                w.is_available() && w.speaks(&ConnectionMode::Http) && w.has_tags(&required_tags)
+            })
             .cloned()
             .collect();
+        if self.split_model_versions {
+            model_version::retain_active_version(&mut available);
+        }
         if available.is_empty() {
             return None;
         }
 
+        if !exclude.is_empty() {
+            let untried: Vec<Arc<dyn Worker>> = available
+                .iter()
+                .filter(|w| !exclude.contains(w.url()))
+                .cloned()
+                .collect();
+            if !untried.is_empty() {
+                available = untried;
+            } else {
+                debug!("All available workers already tried, allowing reselection");
+            }
+        }
+
         // Get the appropriate policy for this model
         let policy = match model_id {
             Some(model) => self.policy_registry.get_policy_or_default(model),
@@ -499,8 +900,230 @@ impl Router {
         // Convert headers for policies that need them (e.g., consistent_hash)
         let request_headers = Self::headers_to_request_headers(headers);
 
-        let idx = policy.select_worker_with_headers(&available, text, request_headers.as_ref())?;
-        Some(available[idx].clone())
+        loop {
+            let idx =
+                policy.select_worker_with_headers(&available, text, request_headers.as_ref())?;
+            let Some(limiter) = &self.worker_rate_limiter else {
+                return Some(available[idx].clone());
+            };
+            if limiter.try_acquire(available[idx].url()) {
+                return Some(available.swap_remove(idx));
+            }
+
+            // Over its rate limit: let the policy choose among the rest
+            let worker = available.remove(idx);
+            debug!("Worker {} is over its rate limit, skipping", worker.url());
+            RouterMetrics::record_worker_rate_limited(worker.url());
+            if available.is_empty() {
+                return None;
+            }
+        }
+    }
+
+    /// Tags named by the X-Router-Require-Tags header
+    fn required_tags(headers: Option<&HeaderMap>) -> Vec<(String, Option<String>)> {
+        headers
+            .and_then(|h| h.get(REQUIRE_TAGS_HEADER))
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(|tag| match tag.split_once('=') {
+                        Some((key, value)) => {
+                            (key.trim().to_string(), Some(value.trim().to_string()))
+                        }
+                        None => (tag.to_string(), None),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Resolve the worker named by the X-Router-Target-Worker header
+    ///
+    /// Returns None when pinning is disabled or the header is absent, and an
+    /// error response when the named worker cannot serve the model.
+    #[allow(clippy::result_large_err)]
+    fn pinned_worker(
+        &self,
+        headers: Option<&HeaderMap>,
+        model_id: Option<&str>,
+    ) -> Result<Option<Arc<dyn Worker>>, Response> {
+        if !self.allow_worker_pinning {
+            return Ok(None);
+        }
+        let Some(value) = headers.and_then(|h| h.get(TARGET_WORKER_HEADER)) else {
+            return Ok(None);
+        };
+        let target = value.to_str().unwrap_or("").trim().trim_end_matches('/');
+
+        let workers = match model_id {
+            Some(model) => self.worker_registry.get_by_model_fast(model),
+            None => self.worker_registry.get_all(),
+        };
+        let Some(worker) = workers.into_iter().find(|w| w.url() == target) else {
+            let for_model = model_id
+                .map(|model| format!(" for model '{}'", model))
+                .unwrap_or_default();
+            return Err(error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Unknown target worker '{}'{}", target, for_model),
+            ));
+        };
+
+        if !worker.speaks(&ConnectionMode::Http) {
+            return Err(error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Target worker {} is not an HTTP worker", target),
+            ));
+        }
+        if !worker.is_available() {
+            return Err(error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("Target worker {} is not available", target),
+            ));
+        }
+        Ok(Some(worker))
+    }
+
+    /// Model label for per-model metrics
+    ///
+    /// Only models some worker serves get their own label, so clients naming
+    /// arbitrary models cannot create unbounded metric series.
+    fn metrics_model_label<'a>(&self, model_id: Option<&'a str>) -> &'a str {
+        match model_id {
+            Some(model) if !self.worker_registry.get_by_model_fast(model).is_empty() => {
+                normalize_model_key(model)
+            }
+            Some(_) => "other",
+            None => "default",
+        }
+    }
+
+    /// Whether some HTTP worker serving `model` can take a request
+    fn has_available_worker(&self, model: &str) -> bool {
+        self.worker_registry
+            .get_by_model_fast(model)
+            .iter()
+            .any(|w| w.is_available() && w.speaks(&ConnectionMode::Http))
+    }
+
+    /// First model in the fallback chain with an available worker, when the
+    /// requested model has none
+    fn fallback_model(&self, model_id: Option<&str>) -> Option<&str> {
+        let model_id = model_id?;
+        let chain = self.model_fallbacks.get(model_id)?;
+        if self.has_available_worker(model_id) {
+            return None;
+        }
+        chain
+            .iter()
+            .find(|fallback| self.has_available_worker(fallback))
+            .map(String::as_str)
+    }
+
+    /// Pool of the model's traffic split chosen for one request, by weight
+    ///
+    /// Pools without an available worker are skipped so a drained canary does
+    /// not fail its share of requests. When no pool has one, the choice is
+    /// made among all pools and the request fails or falls back as usual.
+    fn traffic_split_pool(&self, model_id: Option<&str>) -> Option<&str> {
+        let pools = self.traffic_splits.get(model_id?)?;
+        let weighted = |pool: &&TrafficSplitPool| pool.weight > 0;
+        let mut candidates: Vec<&TrafficSplitPool> = pools
+            .iter()
+            .filter(weighted)
+            .filter(|pool| self.has_available_worker(&pool.model))
+            .collect();
+        if candidates.is_empty() {
+            candidates = pools.iter().filter(weighted).collect();
+        }
+
+        let total: u64 = candidates.iter().map(|pool| u64::from(pool.weight)).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut pick = rand::rng().random_range(0..total);
+        candidates
+            .into_iter()
+            .find(|pool| {
+                let weight = u64::from(pool.weight);
+                if pick < weight {
+                    return true;
+                }
+                pick -= weight;
+                false
+            })
+            .map(|pool| pool.model.as_str())
+    }
+
+    /// Reject a request when too few of the model's workers are healthy
+    ///
+    /// Keeps a degraded fleet from concentrating all traffic on the few
+    /// survivors; readiness reports the same condition to load balancers.
+    #[allow(clippy::result_large_err)]
+    fn check_min_healthy_workers(&self, model_id: Option<&str>) -> Result<(), Response> {
+        if self.min_healthy_workers <= 1 {
+            return Ok(());
+        }
+        let workers = match model_id {
+            Some(model) => self.worker_registry.get_by_model_fast(model),
+            None => self.worker_registry.get_all(),
+        };
+        let healthy = workers.iter().filter(|w| w.is_healthy()).count();
+        if healthy >= self.min_healthy_workers {
+            return Ok(());
+        }
+
+        let for_model = model_id
+            .map(|model| format!(" for model '{}'", model))
+            .unwrap_or_default();
+        Err(error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!(
+                "Only {} of {} workers{} are healthy, at least {} required",
+                healthy,
+                workers.len(),
+                for_model,
+                self.min_healthy_workers
+            ),
+        ))
+    }
+
+    /// Reject a request for a model no worker is registered under when strict
+    /// model routing is enabled, rather than letting it reach another model
+    #[allow(clippy::result_large_err)]
+    fn check_model_served(&self, model_id: Option<&str>) -> Result<(), Response> {
+        let Some(model) = model_id.filter(|_| self.strict_model_routing) else {
+            return Ok(());
+        };
+        if !self.worker_registry.get_by_model_fast(model).is_empty() {
+            return Ok(());
+        }
+        Err(error_response_with_code(
+            StatusCode::NOT_FOUND,
+            format!("The model '{}' is not served by any worker", model),
+            "model_not_found",
+        ))
+    }
+
+    /// Mint a sticky-session cookie when the model's policy is consistent hashing
+    /// with cookie minting enabled and the request carries no session identifier
+    fn mint_session_cookie(
+        &self,
+        model_id: Option<&str>,
+        text: &str,
+        headers: Option<&HeaderMap>,
+    ) -> Option<MintedSession> {
+        let policy = match model_id {
+            Some(model) => self.policy_registry.get_policy_or_default(model),
+            None => self.policy_registry.get_default_policy(),
+        };
+        let policy = policy.as_consistent_hash()?;
+        let request_headers = Self::headers_to_request_headers(headers);
+        policy.mint_session(Some(text), request_headers.as_ref())
     }
 
     pub async fn route_typed_request<T: GenerationRequest + serde::Serialize + Clone>(
@@ -512,80 +1135,258 @@ impl Router {
     ) -> Response {
         let start = Instant::now();
         let is_stream = typed_req.is_stream();
+        // Requests sampling several sequences count as that many on the worker
+        let load_weight = typed_req.load_weight();
         let text = typed_req.extract_text_for_routing();
+        // Workers that already failed this request with a retryable error
+        let tried_workers: std::sync::Mutex<HashSet<String>> = Default::default();
+        // Details of the latest attempt, reported in the access log
+        let decision: std::sync::Mutex<RoutingDecision> = Default::default();
+        // Requests without a model to route by are spread over all workers,
+        // unless strict model routing takes the model from the request itself
+        let model_id = match model_id {
+            None if self.strict_model_routing => typed_req.get_model(),
+            model_id => model_id,
+        };
+
+        // A pinned request bypasses the policy and always goes to its worker
+        let pinned = match self.pinned_worker(headers, model_id) {
+            Ok(pinned) => pinned,
+            Err(response) => {
+                RouterMetrics::record_request_error(route, "invalid_target_worker");
+                RouterMetrics::record_model_request_error(
+                    route,
+                    self.metrics_model_label(model_id),
+                    "invalid_target_worker",
+                );
+                return response;
+            }
+        };
+
+        // Shadow workers get the request as the client sent it; pinned
+        // requests are for debugging one worker and are not copied
+        if !is_stream && pinned.is_none() {
+            self.mirror_request(headers, typed_req, route);
+        }
+
+        // A model with a traffic split is served by one of its pools, chosen
+        // by weight, with the request's model rewritten to the pool's
+        let split_req;
+        let (typed_req, model_id, traffic_pool) = match self.traffic_split_pool(model_id) {
+            Some(pool) if pinned.is_none() => {
+                RouterMetrics::record_traffic_split(model_id.unwrap_or_default(), pool);
+                let mut req = typed_req.clone();
+                req.set_model(pool.to_string());
+                split_req = req;
+                (&split_req, Some(pool), Some(pool))
+            }
+            _ => (typed_req, model_id, None),
+        };
+
+        // A model without available workers is served by the first model in
+        // its fallback chain that has one, with the request's model rewritten
+        let fallback_req;
+        let (typed_req, model_id, fallback) = match self.fallback_model(model_id) {
+            Some(fallback) if pinned.is_none() => {
+                warn!(
+                    "No available workers for model '{}', falling back to '{}'",
+                    model_id.unwrap_or_default(),
+                    fallback
+                );
+                let mut req = typed_req.clone();
+                req.set_model(fallback.to_string());
+                fallback_req = req;
+                (&fallback_req, Some(fallback), Some(fallback))
+            }
+            _ => (typed_req, model_id, None),
+        };
+        let model_label = self.metrics_model_label(model_id);
+
+        if let Err(response) = self.check_model_served(model_id) {
+            RouterMetrics::record_request_error(route, "model_not_found");
+            RouterMetrics::record_model_request_error(route, model_label, "model_not_found");
+            return response;
+        }
+
+        // Deterministic non-streaming requests may be answered from the cache
+        // without selecting a worker. The client's credentials are part of the
+        // key, as for embeddings, so callers never share a cached response.
+        let cache_key = match &self.response_cache {
+            Some(_) if pinned.is_none() && !is_stream && typed_req.is_deterministic() => {
+                let authorization = headers
+                    .and_then(|h| h.get(AUTHORIZATION))
+                    .and_then(|v| v.to_str().ok());
+                ResponseCache::request_key(route, model_id, &(typed_req, authorization))
+            }
+            _ => None,
+        };
+        if let (Some(cache), Some(key)) = (&self.response_cache, &cache_key) {
+            if let Some(cached) = cache.get(key) {
+                RouterMetrics::record_response_cache_hit(route);
+                let response = cached.into_response();
+                log_access(&AccessLogEntry {
+                    route,
+                    model_id,
+                    worker_url: None,
+                    policy: None,
+                    retries: 0,
+                    status: response.status().as_u16(),
+                    latency_ms: start.elapsed().as_millis() as u64,
+                    stream: false,
+                });
+                return response;
+            }
+            RouterMetrics::record_response_cache_miss(route);
+        }
+
+        // Pinned requests are for debugging a specific worker and skip the guard
+        if pinned.is_none() {
+            if let Err(response) = self.check_min_healthy_workers(model_id) {
+                RouterMetrics::record_request_error(route, "insufficient_healthy_workers");
+                RouterMetrics::record_model_request_error(
+                    route,
+                    model_label,
+                    "insufficient_healthy_workers",
+                );
+                return response;
+            }
+        }
+
+        // A minted session cookie is used for selection right away, so the first
+        // request lands on the same worker as the follow-ups that carry it
+        let minted_session = self.mint_session_cookie(model_id, &text, headers);
+        let session_headers = minted_session.as_ref().and_then(|session| {
+            let mut session_headers = headers.cloned().unwrap_or_default();
+            let cookie = match session_headers.get(COOKIE).and_then(|v| v.to_str().ok()) {
+                Some(existing) => format!("{}; {}", existing, session.cookie),
+                None => session.cookie.clone(),
+            };
+            session_headers.insert(COOKIE, HeaderValue::from_str(&cookie).ok()?);
+            Some(session_headers)
+        });
+        let selection_headers = session_headers.as_ref().or(headers);
 
-        let response = RetryExecutor::execute_response_with_retry(
+        let retry_on = self.retry_config.retry_on;
+        let mut response = RetryExecutor::execute_response_with_retry_budget(
             &self.retry_config,
+            self.retry_budget.as_deref(),
             // operation per attempt
             |_: u32| async {
-                let worker = match self.select_worker_for_model(model_id, Some(&text), headers) {
+                let exclude = tried_workers.lock().unwrap().clone();
+                let worker = match pinned.clone().or_else(|| {
+                    self.select_worker_for_model_excluding(
+                        model_id,
+                        Some(&text),
+                        selection_headers,
+                        &exclude,
+                    )
+                }) {
                     Some(w) => w,
                     None => {
                         RouterMetrics::record_request_error(route, "no_available_workers");
-                        return (
+                        RouterMetrics::record_model_request_error(
+                            route,
+                            model_label,
+                            "no_available_workers",
+                        );
+                        return error_response_with_code(
                             StatusCode::SERVICE_UNAVAILABLE,
-                            "No available workers (all circuits open or unhealthy)",
-                        )
-                            .into_response();
+                            "No available workers (all circuits open, unhealthy or rate limited)",
+                            "no_available_workers",
+                        );
                     }
                 };
 
-                // Optional load tracking for cache-aware policy
-                // Get the policy for this model to check if it's cache-aware
+                // A half-open circuit only admits a limited number of probes;
+                // treat a lost race for the last slot as a retryable failure
+                if !worker.circuit_breaker().try_acquire() {
+                    tried_workers
+                        .lock()
+                        .unwrap()
+                        .insert(worker.url().to_string());
+                    return error_response(
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "Worker circuit is half-open and at its probe limit",
+                    );
+                }
+
+                // Get the policy for this model (reported in the access log)
                 let policy = match model_id {
                     Some(model) => self.policy_registry.get_policy_or_default(model),
                     None => self.policy_registry.get_default_policy(),
                 };
 
-                let load_incremented = if policy.name() == "cache_aware" {
-                    worker.increment_load();
-                    RouterMetrics::set_running_requests(worker.url(), worker.load());
-                    true
-                } else {
-                    false
-                };
+                {
+                    let mut decision = decision.lock().unwrap();
+                    decision.worker_url = Some(worker.url().to_string());
+                    decision.policy = Some(if pinned.is_some() {
+                        "pinned"
+                    } else {
+                        policy.name()
+                    });
+                }
 
-                // Keep a clone for potential cleanup on retry
-                let worker_for_cleanup = if load_incremented {
-                    Some(worker.clone())
-                } else {
-                    None
-                };
+                // Track in-flight requests so load-aware policies and worker
+                // draining can see them
+                let load = OwnedWorkerLoadGuard::with_weight(worker.clone(), load_weight);
+                let attempt_start = Instant::now();
 
-                let response = self
-                    .send_typed_request(
-                        headers,
-                        typed_req,
-                        route,
-                        worker.url(),
-                        is_stream,
-                        load_incremented,
-                    )
-                    .await;
+                let hedge_delay = self.hedge_delay.filter(|_| {
+                    pinned.is_none() && !is_stream && HEDGEABLE_ROUTES.contains(&route)
+                });
+                let (response, worker) = match hedge_delay {
+                    Some(delay) => {
+                        self.send_hedged_request(
+                            headers, typed_req, route, worker, model_id, &text, &exclude, delay,
+                            load,
+                        )
+                        .await
+                    }
+                    None => {
+                        let response = self
+                            .send_typed_request(
+                                headers,
+                                typed_req,
+                                route,
+                                worker.url(),
+                                is_stream,
+                                load,
+                            )
+                            .await;
+                        (response, worker)
+                    }
+                };
+                decision.lock().unwrap().worker_url = Some(worker.url().to_string());
 
                 // Client errors (4xx) are not worker failures - only server errors (5xx)
                 // should count against the circuit breaker. This matches pd_router.rs behavior.
                 let status = response.status();
                 worker.record_outcome(status.is_success() || status.is_client_error());
 
-                // For retryable failures, we need to decrement load since send_typed_request
-                // won't have done it (it only decrements on success or non-retryable failures)
-                if is_retryable_status(response.status()) && load_incremented {
-                    if let Some(cleanup_worker) = worker_for_cleanup {
-                        cleanup_worker.decrement_load();
-                        RouterMetrics::set_running_requests(
-                            cleanup_worker.url(),
-                            cleanup_worker.load(),
-                        );
-                    }
+                // Streaming responses return once headers arrive, so their
+                // sample is time to first byte rather than the full generation
+                if status.is_success() {
+                    worker.record_latency(
+                        attempt_start.elapsed(),
+                        policy.latency_decay().unwrap_or(DEFAULT_LATENCY_DECAY),
+                    );
+                }
+
+                let retryable = is_retryable_response(&response, retry_on);
+                if retryable {
+                    tried_workers
+                        .lock()
+                        .unwrap()
+                        .insert(worker.url().to_string());
                 }
 
                 response
             },
             // should_retry predicate
-            |res, _attempt| is_retryable_status(res.status()),
+            |res, _attempt| is_retryable_response(res, retry_on),
             // on_backoff hook
             |delay, attempt| {
+                decision.lock().unwrap().retries = attempt;
                 RouterMetrics::record_retry(route);
                 RouterMetrics::record_retry_backoff_duration(delay, attempt);
             },
@@ -598,13 +1399,88 @@ impl Router {
             let duration = start.elapsed();
             RouterMetrics::record_request(route);
             RouterMetrics::record_generate_duration(duration);
-        } else if !is_retryable_status(response.status()) {
+            RouterMetrics::record_model_request(route, model_label, duration);
+        } else if !is_retryable_response(&response, retry_on) {
             RouterMetrics::record_request_error(route, "non_retryable_error");
+            RouterMetrics::record_model_request_error(route, model_label, "non_retryable_error");
+        }
+
+        let decision = decision.into_inner().unwrap();
+        log_access(&AccessLogEntry {
+            route,
+            model_id,
+            worker_url: decision.worker_url.as_deref(),
+            policy: decision.policy,
+            retries: decision.retries,
+            status: response.status().as_u16(),
+            latency_ms: start.elapsed().as_millis() as u64,
+            stream: is_stream,
+        });
+
+        if let (Some(cache), Some(key)) = (&self.response_cache, cache_key) {
+            if response.status() == StatusCode::OK {
+                response = cache.store(key, response).await;
+            }
+        }
+
+        if let Some(set_cookie) =
+            minted_session.and_then(|session| HeaderValue::from_str(&session.set_cookie).ok())
+        {
+            response.headers_mut().append(SET_COOKIE, set_cookie);
+        }
+
+        if let Some(fallback) = fallback.and_then(|model| HeaderValue::from_str(model).ok()) {
+            response
+                .headers_mut()
+                .insert(FALLBACK_MODEL_HEADER, fallback);
+        }
+        if let Some(pool) = traffic_pool.and_then(|model| HeaderValue::from_str(model).ok()) {
+            response.headers_mut().insert(TRAFFIC_POOL_HEADER, pool);
+        }
+        if self.expose_routing_headers {
+            Self::insert_routing_headers(&mut response, &decision);
         }
 
         response
     }
 
+    /// Add the worker, policy and retry count of a routed request to its response
+    fn insert_routing_headers(response: &mut Response, decision: &RoutingDecision) {
+        let headers = response.headers_mut();
+        if let Some(worker) = decision
+            .worker_url
+            .as_deref()
+            .and_then(|url| HeaderValue::from_str(url).ok())
+        {
+            headers.insert(WORKER_HEADER, worker);
+        }
+        if let Some(policy) = decision.policy {
+            headers.insert(POLICY_HEADER, HeaderValue::from_static(policy));
+        }
+        headers.insert(RETRIES_HEADER, HeaderValue::from(decision.retries));
+    }
+
+    /// Copy a sampled request to a shadow worker without waiting for it
+    fn mirror_request<T: serde::Serialize>(
+        &self,
+        headers: Option<&HeaderMap>,
+        typed_req: &T,
+        route: &str,
+    ) {
+        let Some(mirror) = &self.traffic_mirror else {
+            return;
+        };
+        let Some(shadow_url) = mirror.sample() else {
+            return;
+        };
+        let request = self
+            .client
+            .post(format!("{}{}", shadow_url, route))
+            .json(typed_req);
+        let request = self.header_policy.apply(request, headers);
+        mirror.send(request, route, shadow_url);
+    }
+
     // Helper: return base worker URL (strips DP suffix when enabled)
     fn worker_base_url(&self, worker_url: &str) -> String {
         if self.intra_node_data_parallel_size > 1 {
@@ -626,7 +1502,7 @@ impl Router {
         // Eventually, we need to have router to manage the chat history with a proper database, will update this implementation accordingly.
         let worker_urls = self.get_worker_urls();
         if worker_urls.is_empty() {
-            return (StatusCode::SERVICE_UNAVAILABLE, "No available workers").into_response();
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "No available workers");
         }
 
         let mut last_response: Option<Response> = None;
@@ -638,22 +1514,14 @@ impl Router {
                 Method::GET => self.client.get(url),
                 Method::POST => self.client.post(url),
                 _ => {
-                    return (
+                    return error_response(
                         StatusCode::METHOD_NOT_ALLOWED,
                         "Unsupported method for simple routing",
                     )
-                        .into_response()
                 }
             };
 
-            if let Some(hdrs) = headers {
-                for (name, value) in hdrs {
-                    let name_lc = name.as_str().to_lowercase();
-                    if name_lc != "content-type" && name_lc != "content-length" {
-                        request_builder = request_builder.header(name, value);
-                    }
-                }
-            }
+            request_builder = self.header_policy.apply(request_builder, headers);
 
             match request_builder.send().await {
                 Ok(res) => {
@@ -671,30 +1539,24 @@ impl Router {
                             last_response = Some(response);
                         }
                         Err(e) => {
-                            last_response = Some(
-                                (
-                                    StatusCode::INTERNAL_SERVER_ERROR,
-                                    format!("Failed to read response: {}", e),
-                                )
-                                    .into_response(),
-                            );
+                            last_response = Some(error_response(
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                format!("Failed to read response: {}", e),
+                            ));
                         }
                     }
                 }
                 Err(e) => {
-                    last_response = Some(
-                        (
-                            StatusCode::INTERNAL_SERVER_ERROR,
-                            format!("Request failed: {}", e),
-                        )
-                            .into_response(),
-                    );
-                }
+                    last_response = Some(error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        format!("Request failed: {}", e),
+                    ));
+                }
             }
         }
 
         last_response
-            .unwrap_or_else(|| (StatusCode::BAD_GATEWAY, "No worker response").into_response())
+            .unwrap_or_else(|| error_response(StatusCode::BAD_GATEWAY, "No worker response"))
     }
 
     // Route a GET request with provided headers to a specific endpoint
@@ -713,6 +1575,113 @@ impl Router {
             .await
     }
 
+    /// Send a non-streaming request, hedging it to a second worker if the
+    /// primary has not answered within `hedge_delay`
+    ///
+    /// The first response that is not a retryable failure wins; a failed copy
+    /// is only returned if the other copy fails too. The cancelled copy has its
+    /// load counter and circuit breaker probe slot released here, since
+    /// `send_typed_request` never gets to do it. Returns the response along
+    /// with the worker that produced it. The caller must have already added
+    /// `load_weight` to the primary's load.
+    #[allow(clippy::too_many_arguments)]
+    async fn send_hedged_request<T: serde::Serialize>(
+        &self,
+        headers: Option<&HeaderMap>,
+        typed_req: &T,
+        route: &str,
+        primary: Arc<dyn Worker>,
+        model_id: Option<&str>,
+        text: &str,
+        exclude: &HashSet<String>,
+        hedge_delay: Duration,
+        primary_load: OwnedWorkerLoadGuard,
+    ) -> (Response, Arc<dyn Worker>) {
+        let load_weight = primary_load.weight();
+        let primary_url = primary.url().to_string();
+        let primary_fut =
+            self.send_typed_request(headers, typed_req, route, &primary_url, false, primary_load);
+        tokio::pin!(primary_fut);
+
+        tokio::select! {
+            response = &mut primary_fut => return (response, primary),
+            _ = tokio::time::sleep(hedge_delay) => {}
+        }
+
+        let mut backup_exclude = exclude.clone();
+        backup_exclude.insert(primary_url.clone());
+        let backup = match self.select_worker_for_model_excluding(
+            model_id,
+            Some(text),
+            headers,
+            &backup_exclude,
+        ) {
+            Some(w) if w.url() != primary_url && w.circuit_breaker().try_acquire() => w,
+            _ => {
+                debug!(
+                    "No backup worker available for hedged request route={}",
+                    route
+                );
+                return (primary_fut.await, primary);
+            }
+        };
+
+        debug!(
+            "Hedging request route={} primary={} backup={}",
+            route,
+            primary_url,
+            backup.url()
+        );
+        let backup_load = OwnedWorkerLoadGuard::with_weight(backup.clone(), load_weight);
+
+        let backup_url = backup.url().to_string();
+        let backup_fut =
+            self.send_typed_request(headers, typed_req, route, &backup_url, false, backup_load);
+        tokio::pin!(backup_fut);
+
+        let (response, winner, loser, label) = tokio::select! {
+            response = &mut primary_fut => {
+                if is_retryable_status(response.status()) {
+                    primary.record_outcome(false);
+                    (backup_fut.await, backup, None, "backup")
+                } else {
+                    (response, primary, Some(backup), "primary")
+                }
+            }
+            response = &mut backup_fut => {
+                if is_retryable_status(response.status()) {
+                    backup.record_outcome(false);
+                    (primary_fut.await, primary, None, "primary")
+                } else {
+                    (response, backup, Some(primary), "backup")
+                }
+            }
+        };
+
+        // The loser's load is released when its request future is dropped
+        if let Some(loser) = loser {
+            loser.circuit_breaker().release();
+        }
+        RouterMetrics::record_hedged_request(route, label);
+
+        (response, winner)
+    }
+
+    /// Generation timeout of one request: the timeout header's value clamped
+    /// to the configured maximum, or the router-wide timeout
+    fn request_generation_timeout(&self, headers: Option<&HeaderMap>) -> Duration {
+        let Some(max) = self.max_request_timeout else {
+            return self.generation_timeout;
+        };
+        headers
+            .and_then(|h| h.get(TIMEOUT_HEADER))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .filter(|timeout| !timeout.is_zero())
+            .map_or(self.generation_timeout, |timeout| timeout.min(max))
+    }
+
     // Send typed request directly without conversion
     async fn send_typed_request<T: serde::Serialize>(
         &self,
@@ -721,18 +1690,18 @@ impl Router {
         route: &str,
         worker_url: &str,
         is_stream: bool,
-        load_incremented: bool, // Whether load was incremented for this request
+        load: OwnedWorkerLoadGuard, // Held until the response body has been forwarded
     ) -> Response {
+        let request_start = Instant::now();
         let (mut request_builder, extracted_dp_rank) = if self.intra_node_data_parallel_size > 1 {
             let (worker_url_prefix, dp_rank) = match dp_utils::extract_dp_rank(worker_url) {
                 Ok(tup) => tup,
                 Err(e) => {
                     error!("Failed to extract dp_rank: {}", e);
-                    return (
+                    return error_response(
                         StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Failed to extract dp_rank: {}", e),
-                    )
-                        .into_response();
+                    );
                 }
             };
 
@@ -740,11 +1709,10 @@ impl Router {
             let json_val = match serde_json::to_value(typed_req) {
                 Ok(j) => j,
                 Err(e) => {
-                    return (
+                    return error_response(
                         StatusCode::BAD_REQUEST,
                         format!("Convert into serde_json::Value failed: {}", e),
-                    )
-                        .into_response();
+                    );
                 }
             };
 
@@ -765,47 +1733,42 @@ impl Router {
             ) // Use json() directly with typed request
         };
 
-        // Copy all headers from original request if provided
-        if let Some(headers) = headers {
-            for (name, value) in headers {
-                // Skip Content-Type and Content-Length as .json() sets them
-                if *name != CONTENT_TYPE && *name != CONTENT_LENGTH {
-                    request_builder = request_builder.header(name, value);
-                }
+        let body_logger = self
+            .body_logger
+            .as_ref()
+            .filter(|logger| logger.enabled_for(route));
+        if let Some(logger) = body_logger {
+            if let Ok(body) = serde_json::to_value(typed_req) {
+                logger.log_request(route, worker_url, &body);
             }
         }
 
+        // Copy permitted headers from original request; .json() sets the content headers
+        request_builder = self.header_policy.apply(request_builder, headers);
+
         // Add X-data-parallel-rank header for DP-aware routing
         if let Some(dp_rank) = extracted_dp_rank {
             request_builder = request_builder.header("X-data-parallel-rank", dp_rank.to_string());
         }
 
-        let res = match request_builder.send().await {
-            Ok(res) => res,
-            Err(e) => {
-                error!(
-                    "Failed to send typed request worker_url={} route={} error={}",
-                    worker_url, route, e
-                );
-
-                // Decrement load on error if it was incremented
-                if load_incremented {
-                    if let Some(worker) = self.worker_registry.get_by_url(worker_url) {
-                        worker.decrement_load();
-                        RouterMetrics::set_running_requests(worker_url, worker.load());
-                    }
+        let send = request_builder
+            .timeout(self.request_generation_timeout(headers))
+            .send();
+        let res =
+            match watch_slow_request(send, self.slow_request_threshold, route, worker_url).await {
+                Ok(res) => res,
+                Err(e) => {
+                    error!(
+                        "Failed to send typed request worker_url={} route={} error={}",
+                        worker_url, route, e
+                    );
+                    return connection_failure_response(&e);
                 }
-
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    format!("Request failed: {}", e),
-                )
-                    .into_response();
-            }
-        };
+            };
 
         let status = StatusCode::from_u16(res.status().as_u16())
             .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        RouterMetrics::record_worker_response(worker_url, status.as_u16());
 
         if !is_stream {
             // For non-streaming requests, preserve headers
@@ -813,132 +1776,88 @@ impl Router {
 
             let response = match res.bytes().await {
                 Ok(body) => {
+                    if let Some(logger) = body_logger {
+                        logger.log_response(route, worker_url, status.as_u16(), &body);
+                    }
                     let mut response = Response::new(axum::body::Body::from(body));
                     *response.status_mut() = status;
                     *response.headers_mut() = response_headers;
+                    response.extensions_mut().insert(WorkerResponse);
                     response
                 }
                 Err(e) => {
-                    // IMPORTANT: Decrement load on error before returning
-                    if load_incremented {
-                        if let Some(worker) = self.worker_registry.get_by_url(worker_url) {
-                            worker.decrement_load();
-                            RouterMetrics::set_running_requests(worker_url, worker.load());
-                        }
-                    }
-
                     let error_msg = format!("Failed to get response body: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, error_msg).into_response()
+                    error_response(StatusCode::INTERNAL_SERVER_ERROR, error_msg)
                 }
             };
 
-            // Decrement load counter for non-streaming requests if it was incremented
-            if load_incremented {
-                if let Some(worker) = self.worker_registry.get_by_url(worker_url) {
-                    worker.decrement_load();
-                    RouterMetrics::set_running_requests(worker_url, worker.load());
-                }
-            }
+            // The whole body has been read; the worker is done with it
+            drop(load);
 
             response
-        } else if load_incremented {
-            // For streaming with load tracking, we need to manually decrement when done
-            let registry = Arc::clone(&self.worker_registry);
-            let worker_url = worker_url.to_string();
-
+        } else {
             // Preserve headers for streaming response
             let mut response_headers = header_utils::preserve_response_headers(res.headers());
             // Ensure we set the correct content-type for SSE
             response_headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
 
-            let stream = res.bytes_stream();
-            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-
-            // Spawn task to forward stream and detect completion
-            tokio::spawn(async move {
-                let mut stream = stream;
-                let mut decremented = false;
-                while let Some(chunk) = stream.next().await {
-                    match chunk {
-                        Ok(bytes) => {
-                            // Check for stream end marker
-                            if bytes
-                                .as_ref()
-                                .windows(12)
-                                .any(|window| window == b"data: [DONE]")
-                            {
-                                if let Some(worker) = registry.get_by_url(&worker_url) {
-                                    worker.decrement_load();
-                                    RouterMetrics::set_running_requests(&worker_url, worker.load());
-                                    decremented = true;
-                                }
-                            }
-                            if tx.send(Ok(bytes)).is_err() {
-                                break;
-                            }
-                        }
-                        Err(e) => {
-                            let _ = tx.send(Err(format!("Stream error: {}", e)));
-                            break;
-                        }
-                    }
-                }
-                if !decremented {
-                    if let Some(worker) = registry.get_by_url(&worker_url) {
-                        worker.decrement_load();
-                        RouterMetrics::set_running_requests(&worker_url, worker.load());
-                    }
+            // With body logging on, the relayed events are kept to log at the end
+            let logged = body_logger.map(|logger| (logger.clone(), Arc::default()));
+            let captured: Option<Arc<std::sync::Mutex<Vec<u8>>>> =
+                logged.as_ref().map(|(_, captured)| Arc::clone(captured));
+            let stream = futures_util::StreamExt::inspect(res.bytes_stream(), move |chunk| {
+                if let (Some(captured), Ok(bytes)) = (&captured, chunk) {
+                    captured.lock().unwrap().extend_from_slice(bytes);
                 }
             });
+            let (tx, rx) = tokio::sync::mpsc::channel(self.stream_buffer_size);
+            let keep_alive = self.stream_keepalive;
+            let route = route.to_string();
+            let worker_url = worker_url.to_string();
 
-            let stream = UnboundedReceiverStream::new(rx);
-            let body = Body::from_stream(stream);
-
-            let mut response = Response::new(body);
-            *response.status_mut() = status;
-            *response.headers_mut() = response_headers;
-            response
-        } else {
-            // For requests without load tracking, just stream
-            // Preserve headers for streaming response
-            let mut response_headers = header_utils::preserve_response_headers(res.headers());
-            // Ensure we set the correct content-type for SSE
-            response_headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
-
-            let stream = res.bytes_stream();
-            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-
-            // Spawn task to forward stream
+            // Spawn task to forward stream and detect completion. The load is
+            // released at [DONE], or when forwarding stops for any reason.
             tokio::spawn(async move {
-                let mut stream = stream;
-                while let Some(chunk) = stream.next().await {
-                    match chunk {
-                        Ok(bytes) => {
-                            if tx.send(Ok(bytes)).is_err() {
-                                break;
-                            }
-                        }
-                        Err(e) => {
-                            let _ = tx.send(Err(format!("Stream error: {}", e)));
-                            break;
-                        }
-                    }
+                let mut load = Some(load);
+                forward_sse_stream(
+                    stream,
+                    tx,
+                    &route,
+                    &worker_url,
+                    request_start,
+                    keep_alive,
+                    || drop(load.take()),
+                )
+                .await;
+                if let Some((logger, captured)) = logged {
+                    let body = captured.lock().unwrap();
+                    logger.log_stream(&route, &worker_url, status.as_u16(), &body);
                 }
             });
 
-            let stream = UnboundedReceiverStream::new(rx);
+            let stream = ReceiverStream::new(rx);
             let body = Body::from_stream(stream);
 
             let mut response = Response::new(body);
             *response.status_mut() = status;
             *response.headers_mut() = response_headers;
+            response.extensions_mut().insert(WorkerResponse);
             response
         }
     }
 
     pub async fn add_worker(&self, worker_url: &str) -> Result<String, String> {
+        self.add_worker_with_tags(worker_url, HashMap::new()).await
+    }
+
+    /// Add a worker once it is healthy, storing `tags` among its labels
+    pub async fn add_worker_with_tags(
+        &self,
+        worker_url: &str,
+        tags: HashMap<String, String>,
+    ) -> Result<String, String> {
         let start_time = std::time::Instant::now();
-        let client = reqwest::Client::builder()
+        let client = upstream_tls::client_builder()
             .timeout(Duration::from_secs(self.worker_startup_timeout_secs))
             .build()
             .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
@@ -958,6 +1877,11 @@ impl Router {
             match client.get(format!("{}/health", worker_url)).send().await {
                 Ok(res) => {
                     if res.status().is_success() {
+                        let model_id = self.fetch_worker_model_id(&client, worker_url).await;
+                        let mut labels = tags.clone();
+                        labels.extend(Self::model_labels(&model_id));
+                        let mut added: Vec<Arc<dyn Worker>> = Vec::new();
+
                         if self.intra_node_data_parallel_size > 1 {
                             // Expand worker URL into multiple DP-aware URLs based on configured intra_node_data_parallel_size
                             // (e.g., "http://host:8000" → "http://host:8000@0", "@1", etc.)
@@ -976,31 +1900,29 @@ impl Router {
                                     warn!("Worker {} already exists", dp_url);
                                     continue;
                                 }
-                                info!("Added worker: {}", dp_url);
-                                // TODO: In IGW mode, fetch model_id from worker's /get_model_info endpoint
+                                info!("Added worker: {} (model {})", dp_url, model_id);
                                 let new_worker =
                                     BasicWorker::new(dp_url.to_string(), WorkerType::Regular)
+                                        .with_labels(labels.clone())
                                         .with_circuit_breaker_config(
                                             self.circuit_breaker_config.clone(),
-                                        );
+                                        )
+                                        .with_health_config(self.health_config.clone());
 
-                                let worker_arc = Arc::new(new_worker);
+                                let worker_arc: Arc<dyn Worker> = Arc::new(new_worker);
+                                worker_arc.set_warming(self.worker_warmup.is_some());
                                 self.worker_registry.register(worker_arc.clone());
+                                added.push(worker_arc.clone());
 
                                 // Notify PolicyRegistry about the new worker
                                 let model_id = worker_arc.model_id();
                                 let policy = self.policy_registry.on_worker_added(model_id, None);
 
                                 // If this is a cache-aware policy, update it with all workers for this model
-                                if policy.name() == "cache_aware" {
-                                    if let Some(cache_aware) = policy
-                                        .as_any()
-                                        .downcast_ref::<crate::policies::CacheAwarePolicy>(
-                                    ) {
-                                        let model_workers =
-                                            self.worker_registry.get_by_model_fast(model_id);
-                                        cache_aware.init_workers(&model_workers);
-                                    }
+                                if let Some(cache_aware) = policy.as_cache_aware() {
+                                    let model_workers =
+                                        self.worker_registry.get_by_model_fast(model_id);
+                                    cache_aware.init_workers(&model_workers);
                                 }
 
                                 worker_added = true;
@@ -1012,38 +1934,45 @@ impl Router {
                             if self.worker_registry.get_by_url(worker_url).is_some() {
                                 return Err(format!("Worker {} already exists", worker_url));
                             }
-                            info!("Added worker: {}", worker_url);
+                            info!("Added worker: {} (model {})", worker_url, model_id);
 
-                            // TODO: In IGW mode, fetch model_id from worker's /get_model_info endpoint
                             let new_worker =
                                 BasicWorker::new(worker_url.to_string(), WorkerType::Regular)
+                                    .with_labels(labels.clone())
                                     .with_circuit_breaker_config(
                                         self.circuit_breaker_config.clone(),
-                                    );
+                                    )
+                                    .with_health_config(self.health_config.clone());
 
-                            let worker_arc = Arc::new(new_worker);
+                            let worker_arc: Arc<dyn Worker> = Arc::new(new_worker);
+                            worker_arc.set_warming(self.worker_warmup.is_some());
                             self.worker_registry.register(worker_arc.clone());
+                            added.push(worker_arc.clone());
 
                             // Notify PolicyRegistry about the new worker
                             let model_id = worker_arc.model_id();
                             let policy = self.policy_registry.on_worker_added(model_id, None);
 
                             // If this is a cache-aware policy, add this worker to it
-                            if policy.name() == "cache_aware" {
-                                if let Some(cache_aware) = policy
-                                    .as_any()
-                                    .downcast_ref::<crate::policies::CacheAwarePolicy>(
-                                ) {
-                                    // Get all workers for this model
-                                    let model_workers =
-                                        self.worker_registry.get_by_model_fast(model_id);
-                                    cache_aware.init_workers(&model_workers);
-                                }
+                            if let Some(cache_aware) = policy.as_cache_aware() {
+                                // Get all workers for this model
+                                let model_workers =
+                                    self.worker_registry.get_by_model_fast(model_id);
+                                cache_aware.init_workers(&model_workers);
                             }
                         }
 
                         RouterMetrics::set_active_workers(self.worker_registry.get_all().len());
 
+                        // Listed as warming but not selected until warmup finishes
+                        if let Some(warmup) = &self.worker_warmup {
+                            self.warm_up_worker(&client, worker_url, &model_id, warmup)
+                                .await;
+                            for worker in &added {
+                                worker.set_warming(false);
+                            }
+                        }
+
                         return Ok(format!("Successfully added worker: {}", worker_url));
                     } else {
                         debug!(
@@ -1099,6 +2028,9 @@ impl Router {
                     if self.worker_registry.remove_by_url(w.url()).is_some() {
                         info!("Removed worker: {}", w.url());
                         removed_workers.push(w.url().to_string());
+                        if let Some(limiter) = &self.worker_rate_limiter {
+                            limiter.remove(w.url());
+                        }
 
                         // Notify PolicyRegistry about the removed worker
                         self.policy_registry.on_worker_removed(&model_id);
@@ -1116,10 +2048,7 @@ impl Router {
                 if let Some(worker) = self.worker_registry.get_by_url(dp_url) {
                     let model_id = worker.model_id();
                     if let Some(policy) = self.policy_registry.get_policy(model_id) {
-                        if let Some(cache_aware) = policy
-                            .as_any()
-                            .downcast_ref::<crate::policies::CacheAwarePolicy>()
-                        {
+                        if let Some(cache_aware) = policy.as_cache_aware() {
                             cache_aware.remove_worker_by_url(dp_url);
                             info!("Removed worker from cache-aware tree: {}", dp_url);
                         }
@@ -1137,6 +2066,9 @@ impl Router {
 
             if self.worker_registry.remove_by_url(worker_url).is_some() {
                 info!("Removed worker: {}", worker_url);
+                if let Some(limiter) = &self.worker_rate_limiter {
+                    limiter.remove(worker_url);
+                }
 
                 // Notify PolicyRegistry about the removed worker
                 self.policy_registry.on_worker_removed(&model_id);
@@ -1146,10 +2078,7 @@ impl Router {
 
             // If the model is using cache aware policy, remove the worker from the tree
             if let Some(policy) = self.policy_registry.get_policy(&model_id) {
-                if let Some(cache_aware) = policy
-                    .as_any()
-                    .downcast_ref::<crate::policies::CacheAwarePolicy>()
-                {
+                if let Some(cache_aware) = policy.as_cache_aware() {
                     cache_aware.remove_worker_by_url(worker_url);
                     info!("Removed worker from cache-aware tree: {}", worker_url);
                 }
@@ -1157,6 +2086,41 @@ impl Router {
         }
     }
 
+    /// Workers registered for a URL (all DP ranks when data parallelism is enabled)
+    fn workers_for_url(&self, worker_url: &str) -> Vec<Arc<dyn Worker>> {
+        if self.intra_node_data_parallel_size > 1 {
+            let worker_url_prefix = format!("{}@", worker_url);
+            self.worker_registry
+                .get_all()
+                .into_iter()
+                .filter(|w| w.url().starts_with(&worker_url_prefix))
+                .collect()
+        } else {
+            self.worker_registry
+                .get_by_url(worker_url)
+                .into_iter()
+                .collect()
+        }
+    }
+
+    /// Stop routing to a worker, wait for its in-flight requests, then remove it
+    pub async fn drain_worker(
+        &self,
+        worker_url: &str,
+        timeout: Duration,
+    ) -> Result<String, String> {
+        let workers = self.workers_for_url(worker_url);
+        if workers.is_empty() {
+            return Err(format!("Worker {} not found", worker_url));
+        }
+
+        info!("Draining worker {} (timeout {:?})", worker_url, timeout);
+        let in_flight = crate::core::drain_workers(&workers, timeout).await;
+        self.remove_worker(worker_url);
+
+        Ok(drain_result_message(worker_url, in_flight))
+    }
+
     async fn get_worker_load(&self, worker_url: &str) -> Option<isize> {
         let worker_url = if self.intra_node_data_parallel_size > 1 {
             // Need to extract the URL from "http://host:port@dp_rank"
@@ -1172,12 +2136,11 @@ impl Router {
             worker_url
         };
 
-        match self
+        let request = self
             .client
             .get(format!("{}/get_load", worker_url))
-            .send()
-            .await
-        {
+            .timeout(self.proxy_timeout);
+        match self.header_policy.apply(request, None).send().await {
             Ok(res) if res.status().is_success() => match res.bytes().await {
                 Ok(bytes) => match serde_json::from_slice::<serde_json::Value>(&bytes) {
                     Ok(data) => data
@@ -1211,27 +2174,39 @@ impl Router {
 
     // Background task to monitor worker loads
     async fn monitor_worker_loads(
-        worker_urls: Vec<String>,
+        worker_registry: Arc<WorkerRegistry>,
         tx: tokio::sync::watch::Sender<HashMap<String, isize>>,
         interval_secs: u64,
-        policy: Arc<dyn LoadBalancingPolicy>,
+        policy_registry: Arc<PolicyRegistry>,
         client: Client,
+        header_policy: HeaderForwardPolicy,
+        load_timeout: Duration,
     ) {
         let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
 
         loop {
             interval.tick().await;
 
+            let policies = policy_registry.load_monitored_policies();
+            if policies.is_empty() {
+                continue;
+            }
+
+            // Read the registry each tick so added and removed workers are tracked
             let mut loads = HashMap::new();
-            for url in &worker_urls {
-                if let Some(load) = Self::get_worker_load_static(&client, url).await {
+            for url in &worker_registry.get_all_urls() {
+                if let Some(load) =
+                    Self::get_worker_load_static(&client, &header_policy, url, load_timeout).await
+                {
                     loads.insert(url.clone(), load);
                 }
             }
 
             if !loads.is_empty() {
-                // Update policy with new loads
-                policy.update_loads(&loads);
+                // Update every load-aware policy with new loads
+                for policy in &policies {
+                    policy.update_loads(&loads);
+                }
 
                 // Send to watchers
                 if let Err(e) = tx.send(loads) {
@@ -1242,7 +2217,12 @@ impl Router {
     }
 
     // Static version of get_worker_load for use in monitoring task
-    async fn get_worker_load_static(client: &reqwest::Client, worker_url: &str) -> Option<isize> {
+    async fn get_worker_load_static(
+        client: &reqwest::Client,
+        header_policy: &HeaderForwardPolicy,
+        worker_url: &str,
+        timeout: Duration,
+    ) -> Option<isize> {
         let worker_url = if worker_url.contains("@") {
             // Need to extract the URL from "http://host:port@dp_rank"
             let (worker_url_prefix, _dp_rank) = match dp_utils::extract_dp_rank(worker_url) {
@@ -1257,7 +2237,10 @@ impl Router {
             worker_url
         };
 
-        match client.get(format!("{}/get_load", worker_url)).send().await {
+        let request = client
+            .get(format!("{}/get_load", worker_url))
+            .timeout(timeout);
+        match header_policy.apply(request, None).send().await {
             Ok(res) if res.status().is_success() => match res.bytes().await {
                 Ok(bytes) => match serde_json::from_slice::<serde_json::Value>(&bytes) {
                     Ok(data) => data
@@ -1289,6 +2272,241 @@ impl Router {
         }
     }
 
+    /// Look up the model served by a worker via its /get_model_info endpoint
+    ///
+    /// Falls back to `DEFAULT_MODEL_ID` when the endpoint is unreachable or
+    /// does not report a model, so the worker is still routable.
+    async fn fetch_worker_model_id(&self, client: &Client, worker_url: &str) -> String {
+        Self::fetch_model_id(&self.header_policy, client, worker_url).await
+    }
+
+    async fn fetch_model_id(
+        header_policy: &HeaderForwardPolicy,
+        client: &Client,
+        worker_url: &str,
+    ) -> String {
+        let request =
+            header_policy.apply(client.get(format!("{}/get_model_info", worker_url)), None);
+
+        let model_id = match request.send().await {
+            Ok(res) if res.status().is_success() => match res.json::<serde_json::Value>().await {
+                Ok(info) => Self::parse_model_id(&info),
+                Err(e) => {
+                    debug!("Failed to parse model info from {}: {}", worker_url, e);
+                    None
+                }
+            },
+            Ok(res) => {
+                debug!(
+                    "Worker {} returned status {} for /get_model_info",
+                    worker_url,
+                    res.status()
+                );
+                None
+            }
+            Err(e) => {
+                debug!("Failed to fetch model info from {}: {}", worker_url, e);
+                None
+            }
+        };
+
+        model_id.unwrap_or_else(|| {
+            warn!(
+                "No model reported by worker {}, registering it under model '{}'",
+                worker_url, DEFAULT_MODEL_ID
+            );
+            DEFAULT_MODEL_ID.to_string()
+        })
+    }
+
+    /// Send the configured warmup requests to a new worker
+    ///
+    /// Each round sends a completion and a chat completion, so the chat
+    /// template path is warm too. The worker is registered but marked as
+    /// warming meanwhile, so no user request reaches it. Warmup is best
+    /// effort: a route that fails is logged and skipped for the remaining
+    /// rounds, and the worker is added anyway.
+    async fn warm_up_worker(
+        &self,
+        client: &Client,
+        worker_url: &str,
+        model_id: &str,
+        warmup: &WorkerWarmupConfig,
+    ) {
+        let start = Instant::now();
+        let mut completion = serde_json::json!({
+            "prompt": warmup.prompt,
+            "max_tokens": WARMUP_MAX_TOKENS,
+            "temperature": 0,
+        });
+        let mut chat = serde_json::json!({
+            "messages": [{"role": "user", "content": warmup.prompt}],
+            "max_tokens": WARMUP_MAX_TOKENS,
+            "temperature": 0,
+        });
+        if model_id != DEFAULT_MODEL_ID {
+            completion["model"] = serde_json::Value::from(model_id);
+            chat["model"] = serde_json::Value::from(model_id);
+        }
+        let mut routes = vec![
+            ("/v1/completions", completion),
+            ("/v1/chat/completions", chat),
+        ];
+
+        for i in 0..warmup.requests {
+            let mut failed = Vec::new();
+            for (route, body) in &routes {
+                let request = self.header_policy.apply(
+                    client.post(format!("{}{}", worker_url, route)).json(body),
+                    None,
+                );
+                match request.send().await {
+                    Ok(res) if res.status().is_success() => {
+                        // Read the body so the generation runs to completion
+                        let _ = res.bytes().await;
+                    }
+                    Ok(res) => {
+                        warn!(
+                            "Warmup request {} to {}{} returned status {}, skipping the rest on this route",
+                            i + 1,
+                            worker_url,
+                            route,
+                            res.status()
+                        );
+                        failed.push(*route);
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Warmup request {} to {}{} failed, skipping the rest on this route: {}",
+                            i + 1,
+                            worker_url,
+                            route,
+                            e
+                        );
+                        failed.push(*route);
+                    }
+                }
+            }
+            routes.retain(|(route, _)| !failed.contains(route));
+            if routes.is_empty() {
+                return;
+            }
+        }
+        info!(
+            "Warmed up worker {} with {} rounds in {:?}",
+            worker_url,
+            warmup.requests,
+            start.elapsed()
+        );
+    }
+
+    /// Extract the served model name from a /get_model_info response
+    ///
+    /// Prefers `served_model_name` and falls back to `model_path`, which is
+    /// what the worker serves under when no explicit name is configured.
+    fn parse_model_id(info: &serde_json::Value) -> Option<String> {
+        let served_name = match info.get("served_model_name") {
+            // Workers may report several aliases; the first one is canonical
+            Some(serde_json::Value::Array(names)) => names.first().and_then(|n| n.as_str()),
+            Some(name) => name.as_str(),
+            None => None,
+        };
+
+        served_name
+            .or_else(|| info.get("model_path").and_then(|p| p.as_str()))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Fetch `/v1/models` from every healthy worker and merge the results
+    ///
+    /// Workers that fail or return an unparsable list are skipped with a
+    /// warning so one bad worker does not hide the models the rest serve.
+    async fn aggregate_models(&self, headers: &HeaderMap) -> Response {
+        let mut worker_urls: Vec<String> = Vec::new();
+        for worker in self.worker_registry.get_all() {
+            if !worker.is_healthy() {
+                continue;
+            }
+            // DP ranks of a worker share one server
+            let url = match dp_utils::extract_dp_rank(worker.url()) {
+                Ok((prefix, _)) if self.intra_node_data_parallel_size > 1 => prefix.to_string(),
+                _ => worker.url().to_string(),
+            };
+            if !worker_urls.contains(&url) {
+                worker_urls.push(url);
+            }
+        }
+
+        if worker_urls.is_empty() {
+            return error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "No healthy workers available",
+            );
+        }
+
+        let requests = worker_urls.iter().map(|worker_url| {
+            let request = self
+                .client
+                .get(format!("{}/v1/models", worker_url))
+                .timeout(self.proxy_timeout);
+            let request = self.header_policy.apply(request, Some(headers));
+            async move {
+                match request.send().await {
+                    Ok(res) if res.status().is_success() => res
+                        .json::<serde_json::Value>()
+                        .await
+                        .map_err(|e| e.to_string()),
+                    Ok(res) => Err(format!("status {}", res.status())),
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+        });
+        let results = futures_util::future::join_all(requests).await;
+
+        let mut lists = Vec::new();
+        for (worker_url, result) in worker_urls.iter().zip(results) {
+            match result {
+                Ok(list) => lists.push(list),
+                Err(e) => warn!("Skipping models from worker {}: {}", worker_url, e),
+            }
+        }
+
+        if lists.is_empty() {
+            return error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Failed to fetch models from any worker",
+            );
+        }
+
+        Json(Self::merge_model_lists(&lists)).into_response()
+    }
+
+    /// Merge OpenAI model lists, keeping the first entry seen for each model ID
+    fn merge_model_lists(lists: &[serde_json::Value]) -> serde_json::Value {
+        let mut seen = HashSet::new();
+        let data: Vec<serde_json::Value> = lists
+            .iter()
+            .filter_map(|list| list.get("data").and_then(|d| d.as_array()))
+            .flatten()
+            .filter(|model| match model.get("id").and_then(|id| id.as_str()) {
+                Some(id) => seen.insert(id.to_string()),
+                None => false,
+            })
+            .cloned()
+            .collect();
+
+        serde_json::json!({
+            "object": "list",
+            "data": data,
+        })
+    }
+
+    fn model_labels(model_id: &str) -> HashMap<String, String> {
+        HashMap::from([("model_id".to_string(), model_id.to_string())])
+    }
+
     async fn build_rerank_response(
         req: &RerankRequest,
         response: Response,
@@ -1317,10 +2535,22 @@ impl WorkerManagement for Router {
         Router::add_worker(self, worker_url).await
     }
 
+    async fn add_worker_with_tags(
+        &self,
+        worker_url: &str,
+        tags: HashMap<String, String>,
+    ) -> Result<String, String> {
+        Router::add_worker_with_tags(self, worker_url, tags).await
+    }
+
     fn remove_worker(&self, worker_url: &str) {
         Router::remove_worker(self, worker_url)
     }
 
+    async fn drain_worker(&self, worker_url: &str, timeout: Duration) -> Result<String, String> {
+        Router::drain_worker(self, worker_url, timeout).await
+    }
+
     fn get_worker_urls(&self) -> Vec<String> {
         Router::get_worker_urls(self)
     }
@@ -1343,11 +2573,10 @@ impl RouterTrait for Router {
         if unhealthy_servers.is_empty() {
             (StatusCode::OK, "All servers healthy").into_response()
         } else {
-            (
+            error_response(
                 StatusCode::SERVICE_UNAVAILABLE,
                 format!("Unhealthy servers: {:?}", unhealthy_servers),
             )
-                .into_response()
         }
     }
 
@@ -1360,7 +2589,7 @@ impl RouterTrait for Router {
     }
 
     async fn get_models(&self, req: Request<Body>) -> Response {
-        self.proxy_get_request(req, "v1/models").await
+        self.aggregate_models(req.headers()).await
     }
 
     async fn get_model_info(&self, req: Request<Body>) -> Response {
@@ -1403,7 +2632,10 @@ impl RouterTrait for Router {
         body: &ResponsesRequest,
         model_id: Option<&str>,
     ) -> Response {
-        self.route_typed_request(headers, body, "/v1/responses", model_id)
+        let response = self
+            .route_typed_request(headers, body, "/v1/responses", model_id)
+            .await;
+        stored_responses::store_response_result(self.response_storage.as_ref(), body, response)
             .await
     }
 
@@ -1417,6 +2649,24 @@ impl RouterTrait for Router {
         self.route_post_empty_request(headers, &endpoint).await
     }
 
+    async fn delete_response(&self, _headers: Option<&HeaderMap>, response_id: &str) -> Response {
+        stored_responses::delete_stored_response(self.response_storage.as_ref(), response_id).await
+    }
+
+    async fn list_response_input_items(
+        &self,
+        _headers: Option<&HeaderMap>,
+        response_id: &str,
+        params: &ResponseInputItemsParams,
+    ) -> Response {
+        stored_responses::list_stored_input_items(
+            self.response_storage.as_ref(),
+            response_id,
+            params,
+        )
+        .await
+    }
+
     async fn route_embeddings(
         &self,
         headers: Option<&HeaderMap>,
@@ -1425,9 +2675,20 @@ impl RouterTrait for Router {
     ) -> Response {
         // Record embeddings-specific metrics in addition to general request metrics
         let start = Instant::now();
-        let res = self
-            .route_typed_request(headers, body, "/v1/embeddings", model_id)
-            .await;
+        // The client's credentials are part of the key so callers never share
+        // a response their own request would not have received
+        let authorization = headers
+            .and_then(|h| h.get(AUTHORIZATION))
+            .and_then(|v| v.to_str().ok());
+        let key = ResponseCache::request_key("/v1/embeddings", model_id, &(body, authorization));
+        let send = || self.route_typed_request(headers, body, "/v1/embeddings", model_id);
+        // A pinned request must be answered by its own worker, not the leader's
+        let pinned = self.allow_worker_pinning
+            && headers.is_some_and(|h| h.contains_key(TARGET_WORKER_HEADER));
+        let res = match key.filter(|_| !pinned) {
+            Some(key) => self.embeddings_coalescer.run(key, send).await,
+            None => send().await,
+        };
 
         // Embedding specific metrics
         if res.status().is_success() {
@@ -1448,7 +2709,7 @@ impl RouterTrait for Router {
         model_id: Option<&str>,
     ) -> Response {
         if let Err(e) = body.validate() {
-            return (StatusCode::BAD_REQUEST, e).into_response();
+            return error_response(StatusCode::BAD_REQUEST, e);
         }
         let response = self
             .route_typed_request(headers, body, "/v1/rerank", model_id)
@@ -1458,11 +2719,10 @@ impl RouterTrait for Router {
                 Ok(rerank_response) => rerank_response,
                 Err(e) => {
                     error!("Failed to build rerank response: {}", e);
-                    return (
+                    return error_response(
                         StatusCode::INTERNAL_SERVER_ERROR,
-                        "Failed to build rerank response".to_string(),
-                    )
-                        .into_response();
+                        "Failed to build rerank response",
+                    );
                 }
             }
         } else {
@@ -1483,24 +2743,29 @@ impl RouterTrait for Router {
                     Ok(tup) => tup,
                     Err(e) => {
                         error!("Failed to extract dp_rank: {}", e);
-                        return (
+                        return error_response(
                             StatusCode::INTERNAL_SERVER_ERROR,
                             format!("Failed to extract dp_rank: {}", e),
-                        )
-                            .into_response();
+                        );
                     }
                 };
                 worker_url_prefix
             } else {
                 worker_url
             };
-            let request_builder = self.client.post(format!("{}/flush_cache", worker_url));
+            let request_builder = self.header_policy.apply(
+                self.client.post(format!("{}/flush_cache", worker_url)),
+                None,
+            );
             tasks.push(request_builder.send());
         }
 
         // Wait for all responses
         let results = futures_util::future::join_all(tasks).await;
 
+        // Even a partial flush leaves the prefix trees stale, so always clear them
+        self.policy_registry.clear_cache_trees();
+
         // Check if all succeeded
         let all_success = results.iter().all(|r| {
             r.as_ref()
@@ -1511,11 +2776,10 @@ impl RouterTrait for Router {
         if all_success {
             (StatusCode::OK, "Cache flushed on all servers").into_response()
         } else {
-            (
+            error_response(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Cache flush failed on one or more servers",
             )
-                .into_response()
         }
     }
 
@@ -1542,13 +2806,33 @@ impl RouterTrait for Router {
         "regular"
     }
 
-    fn readiness(&self) -> Response {
-        // Regular router is ready if it has at least one healthy worker
+    fn get_worker_stats(&self) -> Response {
+        worker_stats_response(&self.worker_registry)
+    }
+
+    fn get_worker_circuit(&self, worker_url: &str) -> Response {
+        worker_circuit_response(&self.worker_registry, worker_url, false)
+    }
+
+    fn reset_worker_circuit(&self, worker_url: &str) -> Response {
+        worker_circuit_response(&self.worker_registry, worker_url, true)
+    }
+
+    fn set_worker_enabled(&self, worker_url: &str, enabled: bool) -> Response {
+        worker_enabled_response(&self.worker_registry, worker_url, enabled)
+    }
+
+    fn model_readiness(&self, model_id: &str) -> Response {
+        model_readiness_response(&self.worker_registry, model_id, self.min_healthy_workers)
+    }
+
+    fn readiness(&self) -> Response {
+        // Regular router is ready once enough workers are healthy (at least one)
         let workers = self.worker_registry.get_all();
         let healthy_count = workers.iter().filter(|w| w.is_healthy()).count();
         let total_workers = workers.len();
 
-        if healthy_count > 0 {
+        if healthy_count >= self.min_healthy_workers.max(1) {
             Json(serde_json::json!({
                 "status": "ready",
                 "healthy_workers": healthy_count,
@@ -1556,11 +2840,20 @@ impl RouterTrait for Router {
             }))
             .into_response()
         } else {
+            let reason = if healthy_count == 0 {
+                "no healthy workers available".to_string()
+            } else {
+                format!(
+                    "{} healthy workers, at least {} required",
+                    healthy_count, self.min_healthy_workers
+                )
+            };
             (
                 StatusCode::SERVICE_UNAVAILABLE,
                 Json(serde_json::json!({
                     "status": "not_ready",
-                    "reason": "no healthy workers available",
+                    "reason": reason,
+                    "healthy_workers": healthy_count,
                     "total_workers": total_workers
                 })),
             )
@@ -1575,29 +2868,24 @@ impl RouterTrait for Router {
         headers: Option<&HeaderMap>,
         path: &str,
         method: &Method,
-        body: serde_json::Value,
+        body: bytes::Bytes,
     ) -> Response {
         debug!("Transparent proxy: routing {} {} to backend", method, path);
 
         // Select a worker
         let workers = self.worker_registry.get_all();
         if workers.is_empty() {
-            return (
-                StatusCode::SERVICE_UNAVAILABLE,
-                "No workers available".to_string(),
-            )
-                .into_response();
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "No workers available");
         }
 
         let policy = self.policy_registry.get_default_policy();
         let worker_idx = match policy.select_worker(&workers, None) {
             Some(idx) => idx,
             None => {
-                return (
+                return error_response(
                     StatusCode::SERVICE_UNAVAILABLE,
-                    "Failed to select a worker".to_string(),
-                )
-                    .into_response();
+                    "Failed to select a worker",
+                );
             }
         };
 
@@ -1615,28 +2903,23 @@ impl RouterTrait for Router {
             Method::PATCH => self.client.patch(&url),
             Method::HEAD => self.client.head(&url),
             _ => {
-                return (
+                return error_response(
                     StatusCode::METHOD_NOT_ALLOWED,
                     format!("Method {} not supported", method),
-                )
-                    .into_response();
+                );
             }
         };
 
         // Add X-data-parallel-rank header for DP-aware routing
         request_builder = dp_utils::add_dp_rank_header(request_builder, worker.dp_rank());
 
-        // Propagate headers
-        request_builder = header_utils::propagate_trace_headers(request_builder, headers);
+        // Propagate permitted headers and set Authorization per the upstream auth mode
+        request_builder = self.header_policy.apply(request_builder, headers);
 
-        // Add JSON body if not null/empty
-        if !body.is_null() {
-            request_builder = request_builder.json(&body);
-        }
-
-        // Add authorization if configured
-        if let Some(ref key) = self.api_key {
-            request_builder = request_builder.header("Authorization", format!("Bearer {}", key));
+        // Forward the body unchanged; GETs and the like usually have none
+        if !body.is_empty() {
+            request_builder = header_utils::forward_content_type(request_builder, headers);
+            request_builder = request_builder.body(body);
         }
 
         // Send request
@@ -1657,18 +2940,96 @@ impl RouterTrait for Router {
 
                 match response_builder.body(body) {
                     Ok(response) => response,
-                    Err(e) => (
+                    Err(e) => error_response(
                         StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Failed to build response: {}", e),
-                    )
-                        .into_response(),
+                    ),
                 }
             }
-            Err(e) => (
+            Err(e) => error_response(
                 StatusCode::BAD_GATEWAY,
                 format!("Backend request failed: {}", e),
-            )
-                .into_response(),
+            ),
+        }
+    }
+
+    /// Forward a body that is not JSON to a selected worker as-is
+    ///
+    /// Multipart form fields are not inspected, so the worker is picked
+    /// without a model and the request is not retried.
+    async fn route_raw(
+        &self,
+        headers: Option<&HeaderMap>,
+        path: &str,
+        method: &Method,
+        body: bytes::Bytes,
+    ) -> Response {
+        let Some(worker) =
+            self.select_worker_for_model_excluding(None, None, headers, &HashSet::new())
+        else {
+            RouterMetrics::record_request_error(path, "no_available_workers");
+            return error_response_with_code(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "No available workers",
+                "no_available_workers",
+            );
+        };
+        let url = worker.endpoint_url(path);
+        debug!(
+            "Forwarding {} {} body of {} bytes to {}",
+            method,
+            path,
+            body.len(),
+            url
+        );
+
+        let mut request_builder = match *method {
+            Method::POST => self.client.post(&url),
+            Method::PUT => self.client.put(&url),
+            Method::PATCH => self.client.patch(&url),
+            _ => {
+                return error_response(
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    format!("Method {} not supported", method),
+                );
+            }
+        };
+        request_builder = dp_utils::add_dp_rank_header(request_builder, worker.dp_rank());
+        request_builder = self.header_policy.apply(request_builder, headers);
+        request_builder = header_utils::forward_content_type(request_builder, headers);
+
+        // Held until the response body is fully relayed or dropped
+        let load = OwnedWorkerLoadGuard::with_weight(worker.clone(), 1);
+        let result = request_builder
+            .timeout(self.generation_timeout)
+            .body(body)
+            .send()
+            .await;
+
+        match result {
+            Ok(res) => {
+                let status = StatusCode::from_u16(res.status().as_u16())
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                worker.record_outcome(status.is_success() || status.is_client_error());
+                let response_headers = header_utils::preserve_response_headers(res.headers());
+                let stream = futures_util::StreamExt::map(res.bytes_stream(), move |chunk| {
+                    let _ = &load;
+                    chunk
+                });
+                let mut response = Response::new(Body::from_stream(stream));
+                *response.status_mut() = status;
+                *response.headers_mut() = response_headers;
+                response.headers_mut().remove(CONTENT_LENGTH);
+                response
+            }
+            Err(e) => {
+                worker.record_outcome(false);
+                RouterMetrics::record_request_error(path, "request_failed");
+                error_response(
+                    StatusCode::BAD_GATEWAY,
+                    format!("Backend request failed: {}", e),
+                )
+            }
         }
     }
 }
@@ -1676,6 +3037,7 @@ impl RouterTrait for Router {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures_util::StreamExt;
     use std::collections::HashMap;
 
     fn create_test_regular_router() -> Router {
@@ -1701,7 +3063,31 @@ mod tests {
             api_key: None,
             client: Client::new(),
             retry_config: RetryConfig::default(),
+            retry_budget: None,
             circuit_breaker_config: CircuitBreakerConfig::default(),
+            health_config: HealthConfig::default(),
+            hedge_delay: None,
+            traffic_mirror: None,
+            proxy_timeout: Duration::from_secs(10),
+            generation_timeout: Duration::from_secs(1800),
+            max_request_timeout: None,
+            slow_request_threshold: None,
+            response_cache: None,
+            header_policy: HeaderForwardPolicy::default(),
+            body_logger: None,
+            allow_worker_pinning: false,
+            expose_routing_headers: false,
+            split_model_versions: false,
+            strict_model_routing: false,
+            min_healthy_workers: 1,
+            model_fallbacks: HashMap::new(),
+            traffic_splits: HashMap::new(),
+            stream_buffer_size: 64,
+            stream_keepalive: None,
+            worker_rate_limiter: None,
+            worker_warmup: None,
+            embeddings_coalescer: RequestCoalescer::new(),
+            response_storage: Arc::new(crate::data_connector::MemoryResponseStorage::new()),
             _worker_loads: Arc::new(rx),
             _load_monitor_handle: None,
         }
@@ -1728,10 +3114,605 @@ mod tests {
         assert!(url == "http://worker1:8080" || url == "http://worker2:8080");
     }
 
+    #[test]
+    fn test_select_worker_excluding_skips_tried_workers() {
+        let router = create_test_regular_router();
+        let exclude: HashSet<String> = ["http://worker1:8080".to_string()].into();
+
+        for _ in 0..4 {
+            let worker = router
+                .select_worker_for_model_excluding(None, None, None, &exclude)
+                .unwrap();
+            assert_eq!(worker.url(), "http://worker2:8080");
+        }
+    }
+
+    #[test]
+    fn test_select_worker_excluding_falls_back_when_all_tried() {
+        let router = create_test_regular_router();
+        let exclude: HashSet<String> = [
+            "http://worker1:8080".to_string(),
+            "http://worker2:8080".to_string(),
+        ]
+        .into();
+
+        let worker = router.select_worker_for_model_excluding(None, None, None, &exclude);
+        assert!(worker.is_some());
+
+        // Unhealthy workers are still never selected, even as a fallback
+        for w in router.worker_registry.get_all() {
+            w.set_healthy(false);
+        }
+        assert!(router
+            .select_worker_for_model_excluding(None, None, None, &exclude)
+            .is_none());
+    }
+
+    #[test]
+    fn test_select_worker_skips_rate_limited_workers() {
+        let mut router = create_test_regular_router();
+        router.worker_rate_limiter = Some(Arc::new(WorkerRateLimiter::new(
+            &crate::config::WorkerRateLimitConfig {
+                requests_per_second: 1,
+                burst: Some(1),
+            },
+        )));
+        let exclude = HashSet::new();
+
+        let first = router
+            .select_worker_for_model_excluding(None, None, None, &exclude)
+            .unwrap();
+        let second = router
+            .select_worker_for_model_excluding(None, None, None, &exclude)
+            .unwrap();
+        assert_ne!(first.url(), second.url());
+
+        // Both workers have spent their burst
+        assert!(router
+            .select_worker_for_model_excluding(None, None, None, &exclude)
+            .is_none());
+    }
+
+    #[test]
+    fn test_split_model_versions_selects_active_version() {
+        let mut router = create_test_regular_router();
+        let worker3 = BasicWorker::new("http://worker3:8080".to_string(), WorkerType::Regular);
+        router.worker_registry.register(Arc::new(worker3));
+        for worker in router.worker_registry.get_all() {
+            let version = if worker.url() == "http://worker1:8080" {
+                "v1"
+            } else {
+                "v2"
+            };
+            worker.set_model_version(Some(version.to_string()));
+        }
+
+        router.split_model_versions = true;
+        for _ in 0..6 {
+            let worker = router
+                .select_worker_for_model_excluding(None, None, None, &HashSet::new())
+                .unwrap();
+            assert_eq!(worker.model_version().as_deref(), Some("v2"));
+        }
+    }
+
+    #[test]
+    fn test_pinned_worker() {
+        let mut router = create_test_regular_router();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            TARGET_WORKER_HEADER,
+            HeaderValue::from_static("http://worker2:8080/"),
+        );
+
+        // Ignored unless pinning is enabled
+        assert!(router
+            .pinned_worker(Some(&headers), None)
+            .unwrap()
+            .is_none());
+
+        router.allow_worker_pinning = true;
+        assert!(router.pinned_worker(None, None).unwrap().is_none());
+        let worker = router.pinned_worker(Some(&headers), None).unwrap().unwrap();
+        assert_eq!(worker.url(), "http://worker2:8080");
+
+        headers.insert(
+            TARGET_WORKER_HEADER,
+            HeaderValue::from_static("http://worker3:8080"),
+        );
+        let response = router.pinned_worker(Some(&headers), None).unwrap_err();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        headers.insert(
+            TARGET_WORKER_HEADER,
+            HeaderValue::from_static("http://worker1:8080"),
+        );
+        router.worker_registry.get_all()[0].set_healthy(false);
+        router.worker_registry.get_all()[1].set_healthy(false);
+        let response = router.pinned_worker(Some(&headers), None).unwrap_err();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn test_required_tags_filter_workers() {
+        let router = create_test_regular_router();
+        let tagged = BasicWorker::new("http://worker3:8080".to_string(), WorkerType::Regular)
+            .with_labels(HashMap::from([
+                ("model_id".to_string(), "unknown".to_string()),
+                ("quant".to_string(), "fp8".to_string()),
+            ]));
+        router.worker_registry.register(Arc::new(tagged));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            REQUIRE_TAGS_HEADER,
+            HeaderValue::from_static(" quant=fp8, "),
+        );
+        assert_eq!(
+            Router::required_tags(Some(&headers)),
+            vec![("quant".to_string(), Some("fp8".to_string()))]
+        );
+        for _ in 0..4 {
+            let worker = router
+                .select_worker_for_model_excluding(
+                    Some("unknown"),
+                    None,
+                    Some(&headers),
+                    &HashSet::new(),
+                )
+                .unwrap();
+            assert_eq!(worker.url(), "http://worker3:8080");
+        }
+
+        headers.insert(REQUIRE_TAGS_HEADER, HeaderValue::from_static("quant=fp16"));
+        assert!(router
+            .select_worker_for_model_excluding(
+                Some("unknown"),
+                None,
+                Some(&headers),
+                &HashSet::new()
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_grpc_workers_skipped_in_selection() {
+        let router = create_test_regular_router();
+        for url in router.worker_registry.get_all_urls() {
+            router.worker_registry.remove_by_url(&url);
+        }
+        let grpc = BasicWorker::with_connection_mode(
+            "grpc://worker3:9000".to_string(),
+            WorkerType::Regular,
+            ConnectionMode::Grpc { port: None },
+        );
+        router.worker_registry.register(Arc::new(grpc));
+
+        assert!(router
+            .select_worker_for_model_excluding(Some("unknown"), None, None, &HashSet::new())
+            .is_none());
+
+        router.worker_registry.register(Arc::new(BasicWorker::new(
+            "http://worker4:8080".to_string(),
+            WorkerType::Regular,
+        )));
+        for _ in 0..4 {
+            let worker = router
+                .select_worker_for_model_excluding(Some("unknown"), None, None, &HashSet::new())
+                .unwrap();
+            assert_eq!(worker.url(), "http://worker4:8080");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_strict_model_routing_rejects_unserved_model() {
+        let mut router = create_test_regular_router();
+        let body: crate::protocols::spec::CompletionRequest =
+            serde_json::from_value(serde_json::json!({"model": "llama", "prompt": "hi"})).unwrap();
+
+        // Off by default: a model no worker serves is routed across all workers
+        assert!(router.check_model_served(Some("llama")).is_ok());
+
+        router.strict_model_routing = true;
+        assert!(router.check_model_served(Some("unknown")).is_ok());
+        assert!(router.check_model_served(None).is_ok());
+
+        // The request's model is used when the handler passes none
+        let response = router
+            .route_typed_request(None, &body, "/v1/completions", None)
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "model_not_found");
+    }
+
+    /// Worker whose streams hold back `[DONE]` until `release` is notified
+    /// and whose non-streaming completions always fail with a 500
+    async fn spawn_stream_and_fail_worker(release: Arc<tokio::sync::Notify>) -> String {
+        let app = axum::Router::new().route(
+            "/v1/completions",
+            axum::routing::post(move |body: axum::Json<serde_json::Value>| {
+                let release = release.clone();
+                async move {
+                    if body["stream"] != true {
+                        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                    }
+                    let first = futures_util::stream::once(async {
+                        Ok::<_, std::convert::Infallible>(bytes::Bytes::from_static(
+                            b"data: {\"text\": \"hi\"}\n\n",
+                        ))
+                    });
+                    let done = futures_util::stream::once(async move {
+                        release.notified().await;
+                        Ok(bytes::Bytes::from_static(b"data: [DONE]\n\n"))
+                    });
+                    Response::builder()
+                        .header(CONTENT_TYPE, "text/event-stream")
+                        .body(Body::from_stream(first.chain(done)))
+                        .unwrap()
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_stream_despite_retried_failures() {
+        let release = Arc::new(tokio::sync::Notify::new());
+        let url = spawn_stream_and_fail_worker(release.clone()).await;
+
+        let mut router = create_test_regular_router();
+        router.worker_registry = Arc::new(WorkerRegistry::new());
+        router
+            .worker_registry
+            .register(Arc::new(BasicWorker::new(url.clone(), WorkerType::Regular)));
+        router.retry_config = RetryConfig {
+            max_retries: 3,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 1,
+            ..Default::default()
+        };
+        let router = Arc::new(router);
+        let worker = router.worker_registry.get_by_url(&url).unwrap();
+
+        let request = |stream: bool| -> crate::protocols::spec::CompletionRequest {
+            serde_json::from_value(
+                serde_json::json!({"model": "m", "prompt": "hi", "stream": stream}),
+            )
+            .unwrap()
+        };
+
+        let stream = router
+            .route_typed_request(None, &request(true), "/v1/completions", None)
+            .await;
+        assert_eq!(stream.status(), StatusCode::OK);
+        assert_eq!(worker.load(), 1);
+
+        // Every attempt of the retried request releases only its own load
+        let failed = router
+            .route_typed_request(None, &request(false), "/v1/completions", None)
+            .await;
+        assert_eq!(failed.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(worker.load(), 1);
+
+        let drain = tokio::spawn({
+            let router = router.clone();
+            let url = url.clone();
+            async move { router.drain_worker(&url, Duration::from_secs(5)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!drain.is_finished(), "drain waits for the open stream");
+
+        release.notify_one();
+        let body = to_bytes(stream.into_body(), usize::MAX).await.unwrap();
+        assert!(body.ends_with(b"data: [DONE]\n\n"));
+        let message = drain.await.unwrap().unwrap();
+        assert!(message.contains("drained and removed"));
+        assert_eq!(worker.load(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_load_monitor_follows_runtime_policy_swap() {
+        let app = axum::Router::new().route(
+            "/get_load",
+            axum::routing::get(|| async { axum::Json(serde_json::json!({"load": 7})) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let worker_registry = Arc::new(WorkerRegistry::new());
+        let policy_registry = Arc::new(PolicyRegistry::new(
+            crate::config::types::PolicyConfig::RoundRobin,
+        ));
+        let (tx, mut rx) = tokio::sync::watch::channel(HashMap::new());
+        tokio::spawn(Router::monitor_worker_loads(
+            Arc::clone(&worker_registry),
+            tx,
+            1,
+            Arc::clone(&policy_registry),
+            Client::new(),
+            HeaderForwardPolicy::default(),
+            Duration::from_secs(1),
+        ));
+
+        // Added after the monitor started, so it is only found through the registry
+        worker_registry.register(Arc::new(BasicWorker::new(url.clone(), WorkerType::Regular)));
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(
+            !rx.has_changed().unwrap(),
+            "no loads are polled while no policy uses them"
+        );
+
+        let power_of_two: Arc<dyn LoadBalancingPolicy> =
+            Arc::new(crate::policies::PowerOfTwoPolicy::new());
+        policy_registry.replace_default_policy(power_of_two, &worker_registry.get_all());
+        tokio::time::timeout(Duration::from_secs(5), rx.changed())
+            .await
+            .expect("loads are polled after the swap")
+            .unwrap();
+        assert_eq!(rx.borrow().get(&url), Some(&7));
+    }
+
+    #[tokio::test]
+    async fn test_pinned_embeddings_are_not_coalesced() {
+        let mut router = create_test_regular_router();
+        router.worker_registry = Arc::new(WorkerRegistry::new());
+        router.allow_worker_pinning = true;
+        let mut urls = Vec::new();
+        for _ in 0..2 {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let name = url.clone();
+            let app = axum::Router::new().route(
+                "/v1/embeddings",
+                axum::routing::post(move || {
+                    let name = name.clone();
+                    async move {
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        axum::Json(serde_json::json!({ "worker": name }))
+                    }
+                }),
+            );
+            tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+            router
+                .worker_registry
+                .register(Arc::new(BasicWorker::new(url.clone(), WorkerType::Regular)));
+            urls.push(url);
+        }
+
+        let request: EmbeddingRequest =
+            serde_json::from_value(serde_json::json!({"model": "m", "input": "hi"})).unwrap();
+        let pinned_to = |url: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(TARGET_WORKER_HEADER, HeaderValue::from_str(url).unwrap());
+            headers
+        };
+        let (first, second) = (pinned_to(&urls[0]), pinned_to(&urls[1]));
+        let (a, b) = tokio::join!(
+            router.route_embeddings(Some(&first), &request, None),
+            router.route_embeddings(Some(&second), &request, None)
+        );
+
+        for (response, url) in [(a, &urls[0]), (b, &urls[1])] {
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["worker"], url.as_str());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warmup_requests_reach_worker_before_it_is_selectable() {
+        let mut router = create_test_regular_router();
+        router.worker_registry = Arc::new(WorkerRegistry::new());
+        router.worker_warmup = Some(WorkerWarmupConfig {
+            requests: 2,
+            prompt: "Hello".to_string(),
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        // Each warmup request records whether the worker was selectable then
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let record = {
+            let registry = router.worker_registry.clone();
+            let received = received.clone();
+            let url = url.clone();
+            move |uri: axum::http::Uri| {
+                let worker = registry.get_by_url(&url);
+                received.lock().unwrap().push((
+                    uri.path().to_string(),
+                    worker.as_ref().is_some_and(|w| w.is_warming()),
+                    worker.as_ref().is_some_and(|w| w.is_available()),
+                ));
+                async { axum::Json(serde_json::json!({"choices": []})) }
+            }
+        };
+        let app = axum::Router::new()
+            .route("/health", axum::routing::get(|| async { "OK" }))
+            .route("/v1/completions", axum::routing::post(record.clone()))
+            .route("/v1/chat/completions", axum::routing::post(record));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        router.add_worker(&url).await.unwrap();
+
+        let received = received.lock().unwrap().clone();
+        let warming = |path: &str| (path.to_string(), true, false);
+        assert_eq!(
+            received,
+            vec![
+                warming("/v1/completions"),
+                warming("/v1/chat/completions"),
+                warming("/v1/completions"),
+                warming("/v1/chat/completions"),
+            ]
+        );
+        let worker = router.worker_registry.get_by_url(&url).unwrap();
+        assert!(!worker.is_warming());
+        assert!(worker.is_available());
+    }
+
+    #[tokio::test]
+    async fn test_raw_request_holds_load_until_body_completes() {
+        let release = Arc::new(tokio::sync::Notify::new());
+        let app = axum::Router::new().route(
+            "/v1/audio/transcriptions",
+            axum::routing::post({
+                let release = release.clone();
+                move || async move {
+                    let first = futures_util::stream::once(async {
+                        Ok::<_, std::convert::Infallible>(bytes::Bytes::from_static(b"{\"text\": "))
+                    });
+                    let rest = futures_util::stream::once(async move {
+                        release.notified().await;
+                        Ok(bytes::Bytes::from_static(b"\"hi\"}"))
+                    });
+                    Body::from_stream(first.chain(rest))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut router = create_test_regular_router();
+        router.worker_registry = Arc::new(WorkerRegistry::new());
+        router
+            .worker_registry
+            .register(Arc::new(BasicWorker::new(url.clone(), WorkerType::Regular)));
+        let worker = router.worker_registry.get_by_url(&url).unwrap();
+
+        let response = router
+            .route_raw(
+                None,
+                "/v1/audio/transcriptions",
+                &Method::POST,
+                bytes::Bytes::from_static(b"audio"),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(worker.load(), 1, "load is held while the body streams");
+
+        release.notify_one();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"{\"text\": \"hi\"}");
+        assert_eq!(worker.load(), 0);
+    }
+
+    #[test]
+    fn test_request_generation_timeout_header() {
+        let mut router = create_test_regular_router();
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(TIMEOUT_HEADER, HeaderValue::from_str(value).unwrap());
+            headers
+        };
+
+        // Ignored unless a maximum is configured
+        assert_eq!(
+            router.request_generation_timeout(Some(&headers("5"))),
+            Duration::from_secs(1800)
+        );
+
+        router.max_request_timeout = Some(Duration::from_secs(600));
+        assert_eq!(
+            router.request_generation_timeout(Some(&headers("5"))),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            router.request_generation_timeout(Some(&headers("2.5"))),
+            Duration::from_millis(2500)
+        );
+        assert_eq!(
+            router.request_generation_timeout(Some(&headers("3600"))),
+            Duration::from_secs(600)
+        );
+        for invalid in ["0", "-1", "soon"] {
+            assert_eq!(
+                router.request_generation_timeout(Some(&headers(invalid))),
+                Duration::from_secs(1800)
+            );
+        }
+        assert_eq!(
+            router.request_generation_timeout(None),
+            Duration::from_secs(1800)
+        );
+    }
+
+    #[test]
+    fn test_metrics_model_label_is_bounded_by_served_models() {
+        // Test workers carry no model label and serve "unknown"
+        let router = create_test_regular_router();
+        assert_eq!(router.metrics_model_label(Some("unknown")), "default");
+        assert_eq!(router.metrics_model_label(None), "default");
+        assert_eq!(router.metrics_model_label(Some("made-up-model")), "other");
+    }
+
+    #[test]
+    fn test_fallback_model_used_only_without_available_workers() {
+        // Test workers carry no model label and serve "unknown"
+        let mut router = create_test_regular_router();
+        router.model_fallbacks = HashMap::from([
+            (
+                "llama-70b".to_string(),
+                vec!["llama-8b".to_string(), "unknown".to_string()],
+            ),
+            ("unknown".to_string(), vec!["llama-8b".to_string()]),
+        ]);
+
+        assert_eq!(router.fallback_model(Some("llama-70b")), Some("unknown"));
+        assert_eq!(router.fallback_model(Some("unknown")), None);
+        assert_eq!(router.fallback_model(Some("mistral")), None);
+        assert_eq!(router.fallback_model(None), None);
+
+        for worker in router.worker_registry.get_all() {
+            worker.set_healthy(false);
+        }
+        assert_eq!(router.fallback_model(Some("llama-70b")), None);
+    }
+
+    #[test]
+    fn test_traffic_split_pool_skips_pools_without_workers() {
+        // Test workers carry no model label and serve "unknown"
+        let mut router = create_test_regular_router();
+        let pool = |model: &str, weight| TrafficSplitPool {
+            model: model.to_string(),
+            weight,
+        };
+        router.traffic_splits = HashMap::from([
+            (
+                "llama".to_string(),
+                vec![pool("unknown", 1), pool("llama-canary", 99)],
+            ),
+            (
+                "mistral".to_string(),
+                vec![pool("mistral-v1", 0), pool("mistral-v2", 3)],
+            ),
+        ]);
+
+        for _ in 0..20 {
+            assert_eq!(router.traffic_split_pool(Some("llama")), Some("unknown"));
+            // Without available workers in any pool, zero weights still never win
+            assert_eq!(
+                router.traffic_split_pool(Some("mistral")),
+                Some("mistral-v2")
+            );
+        }
+        assert_eq!(router.traffic_split_pool(Some("unknown")), None);
+        assert_eq!(router.traffic_split_pool(None), None);
+    }
+
     #[tokio::test]
     async fn test_wait_for_healthy_workers_empty_list() {
         // Empty list will return error immediately
-        let result = Router::wait_for_healthy_workers(&[], 1, 1).await;
+        let result = Router::wait_for_healthy_workers(&[], 1, 1, &HealthConfig::default()).await;
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("no workers provided"));
     }
@@ -1739,9 +3720,345 @@ mod tests {
     #[tokio::test]
     async fn test_wait_for_healthy_workers_invalid_urls() {
         // This test will timeout quickly since the URLs are invalid
-        let result =
-            Router::wait_for_healthy_workers(&["http://nonexistent:8080".to_string()], 1, 1).await;
+        let result = Router::wait_for_healthy_workers(
+            &["http://nonexistent:8080".to_string()],
+            1,
+            1,
+            &HealthConfig::default(),
+        )
+        .await;
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Timeout"));
     }
+
+    #[test]
+    fn test_has_data_frame() {
+        assert!(has_data_frame(b"data: {\"text\": \"hi\"}\n\n"));
+        assert!(has_data_frame(b": keep-alive\n\ndata: {}\n\n"));
+        assert!(!has_data_frame(b"data: [DONE]\n\n"));
+        assert!(!has_data_frame(b"data: \n\n"));
+        assert!(!has_data_frame(b": keep-alive\n\n"));
+    }
+
+    #[test]
+    fn test_sse_termination() {
+        let mut termination = SseTermination::default();
+        termination.observe(b"data: {\"text\": \"hi\"}\n\n");
+        assert!(termination.is_truncated());
+        termination.observe(b"data: [DONE]\n\n");
+        assert!(!termination.is_truncated());
+
+        let mut termination = SseTermination::default();
+        termination.observe(b"data: {\"error\": {\"message\": \"oom\"}}\n\n");
+        assert!(!termination.is_truncated());
+
+        let mut termination = SseTermination::default();
+        termination.observe(b"event: error\ndata: {}\n\n");
+        assert!(!termination.is_truncated());
+    }
+
+    #[test]
+    fn test_sse_termination_split_across_chunks() {
+        let mut termination = SseTermination::default();
+        termination.observe(b"data: {\"text\": \"hi\"}\n\ndata: [DO");
+        assert!(termination.is_truncated());
+        termination.observe(b"NE]\n\n");
+        assert!(!termination.is_truncated());
+
+        // A final line without a trailing newline still counts
+        let mut termination = SseTermination::default();
+        termination.observe(b"data: [DONE]");
+        assert!(termination.is_truncated());
+        termination.finish();
+        assert!(!termination.is_truncated());
+
+        // A partial [DONE] at the end of the stream does not
+        let mut termination = SseTermination::default();
+        termination.observe(b"data: [DO");
+        termination.finish();
+        assert!(termination.is_truncated());
+    }
+
+    #[test]
+    fn test_sse_termination_responses_stream() {
+        let mut termination = SseTermination::default();
+        termination.observe(
+            b"event: response.output_text.delta\ndata: {\"type\":\"response.output_text.delta\"}\n\n",
+        );
+        assert!(termination.is_truncated());
+        termination.observe(b"event: response.comp");
+        termination.observe(b"leted\ndata: {\"type\":\"response.completed\"}\n\n");
+        assert!(!termination.is_truncated());
+
+        let mut termination = SseTermination::default();
+        termination.observe(b"event: response.failed\ndata: {\"type\":\"response.failed\"}\n\n");
+        assert!(!termination.is_truncated());
+    }
+
+    async fn forward_chunks(
+        chunks: Vec<Result<bytes::Bytes, String>>,
+    ) -> (Vec<Result<bytes::Bytes, String>>, usize) {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(chunks.len() + 1);
+        let mut done_calls = 0;
+        forward_sse_stream(
+            futures_util::stream::iter(chunks),
+            tx,
+            "/v1/completions",
+            "http://w1:8000",
+            Instant::now(),
+            None,
+            || done_calls += 1,
+        )
+        .await;
+
+        let mut received = Vec::new();
+        while let Ok(item) = rx.try_recv() {
+            received.push(item);
+        }
+        (received, done_calls)
+    }
+
+    #[tokio::test]
+    async fn test_forward_sse_stream_applies_backpressure() {
+        let pulled = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = pulled.clone();
+        let chunks: Vec<Result<bytes::Bytes, String>> = (0..4)
+            .map(|i| Ok(bytes::Bytes::from(format!("data: {}\n\n", i))))
+            .chain(std::iter::once(Ok(bytes::Bytes::from_static(
+                b"data: [DONE]\n\n",
+            ))))
+            .collect();
+        let stream = futures_util::stream::iter(chunks).inspect(move |_| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let forward = tokio::spawn(async move {
+            forward_sse_stream(
+                stream,
+                tx,
+                "/generate",
+                "http://w1:8000",
+                Instant::now(),
+                None,
+                || {},
+            )
+            .await;
+        });
+
+        // One chunk fills the buffer and the next waits for room, so the
+        // upstream is not read any further while the client is not reading
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(pulled.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let mut received = 0;
+        while rx.recv().await.is_some() {
+            received += 1;
+        }
+        forward.await.unwrap();
+        assert_eq!(received, 5);
+    }
+
+    #[tokio::test]
+    async fn test_forward_sse_stream_stops_when_client_disconnects() {
+        let first = futures_util::stream::iter([Ok::<_, String>(bytes::Bytes::from_static(
+            b"data: {\"text\": \"a\"}\n\n",
+        ))]);
+        // The worker never sends another chunk
+        let stream = first.chain(futures_util::stream::pending());
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let forward = tokio::spawn(async move {
+            forward_sse_stream(
+                stream,
+                tx,
+                "/generate",
+                "http://w1:8000",
+                Instant::now(),
+                None,
+                || {},
+            )
+            .await;
+        });
+        assert!(rx.recv().await.is_some());
+
+        drop(rx);
+        tokio::time::timeout(Duration::from_secs(1), forward)
+            .await
+            .expect("forwarding must stop once the client is gone")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watch_slow_request_keeps_waiting_past_threshold() {
+        let slow = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            "response"
+        };
+        let output = watch_slow_request(
+            slow,
+            Some(Duration::from_millis(5)),
+            "/v1/completions",
+            "http://worker1",
+        )
+        .await;
+        assert_eq!(output, "response", "slow requests are not aborted");
+
+        let output = watch_slow_request(async { 1 }, None, "/v1/completions", "http://w").await;
+        assert_eq!(output, 1);
+    }
+
+    #[tokio::test]
+    async fn test_forward_sse_stream_sends_keep_alive_while_idle() {
+        // The worker stays silent during prefill, then finishes
+        let stream = Box::pin(futures_util::stream::once(async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok::<_, String>(bytes::Bytes::from_static(b"data: {\"text\": \"a\"}\n\n"))
+        }))
+        .chain(futures_util::stream::iter([Ok(bytes::Bytes::from_static(
+            b"data: [DONE]\n\n",
+        ))]));
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+        let mut done_calls = 0;
+        forward_sse_stream(
+            stream,
+            tx,
+            "/v1/completions",
+            "http://w1:8000",
+            Instant::now(),
+            Some(Duration::from_millis(30)),
+            || done_calls += 1,
+        )
+        .await;
+
+        let mut received = Vec::new();
+        while let Ok(item) = rx.try_recv() {
+            received.push(item.unwrap());
+        }
+        let (keep_alives, frames): (Vec<_>, Vec<_>) = received
+            .iter()
+            .partition(|frame| frame.as_ref() == KEEP_ALIVE_FRAME);
+        assert!(!keep_alives.is_empty());
+        assert_eq!(
+            received.last().unwrap().as_ref(),
+            b"data: [DONE]\n\n",
+            "keep-alives must not be mistaken for the end of the stream"
+        );
+        assert_eq!(frames.len(), 2);
+        assert_eq!(done_calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_forward_sse_stream_complete() {
+        let (received, done_calls) = forward_chunks(vec![
+            Ok(bytes::Bytes::from_static(b"data: {\"text\": \"a\"}\n\n")),
+            Ok(bytes::Bytes::from_static(b"data: [DONE]\n\n")),
+        ])
+        .await;
+
+        assert_eq!(done_calls, 1);
+        assert_eq!(received.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_forward_sse_stream_reports_truncation() {
+        // Upstream closes cleanly but never sends [DONE]
+        let (received, done_calls) = forward_chunks(vec![Ok(bytes::Bytes::from_static(
+            b"data: {\"text\": \"a\"}\n\n",
+        ))])
+        .await;
+
+        assert_eq!(done_calls, 0);
+        assert_eq!(received.len(), 2);
+        let frame = received[1].as_ref().unwrap();
+        let payload: serde_json::Value = serde_json::from_slice(
+            frame
+                .strip_prefix(b"data: ")
+                .unwrap()
+                .strip_suffix(b"\n\n")
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(payload["error"]["type"], "stream_truncated");
+
+        // Upstream connection error mid-stream
+        let (received, _) = forward_chunks(vec![
+            Ok(bytes::Bytes::from_static(b"data: {\"text\": \"a\"}\n\n")),
+            Err("connection reset".to_string()),
+        ])
+        .await;
+
+        assert_eq!(received.len(), 2);
+        let frame = String::from_utf8(received[1].as_ref().unwrap().to_vec()).unwrap();
+        assert!(frame.contains("connection reset"));
+    }
+
+    #[tokio::test]
+    async fn test_forward_sse_stream_passes_upstream_errors_through() {
+        let (received, _) = forward_chunks(vec![Ok(bytes::Bytes::from_static(
+            b"data: {\"error\": {\"message\": \"bad request\"}}\n\n",
+        ))])
+        .await;
+
+        // The upstream error frame is forwarded without an extra truncation frame
+        assert_eq!(received.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_model_id() {
+        let info = serde_json::json!({
+            "served_model_name": "llama-3",
+            "model_path": "/models/meta-llama/Llama-3-8B"
+        });
+        assert_eq!(Router::parse_model_id(&info).as_deref(), Some("llama-3"));
+
+        let info = serde_json::json!({"served_model_name": ["alias-a", "alias-b"]});
+        assert_eq!(Router::parse_model_id(&info).as_deref(), Some("alias-a"));
+
+        let info = serde_json::json!({"model_path": "meta-llama/Llama-3-8B"});
+        assert_eq!(
+            Router::parse_model_id(&info).as_deref(),
+            Some("meta-llama/Llama-3-8B")
+        );
+
+        assert_eq!(Router::parse_model_id(&serde_json::json!({})), None);
+        assert_eq!(
+            Router::parse_model_id(&serde_json::json!({"model_path": ""})),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge_model_lists() {
+        let lists = vec![
+            serde_json::json!({
+                "object": "list",
+                "data": [
+                    {"id": "llama-3", "object": "model", "owned_by": "worker-a"},
+                    {"id": "qwen", "object": "model"}
+                ]
+            }),
+            serde_json::json!({
+                "object": "list",
+                "data": [
+                    {"id": "llama-3", "object": "model", "owned_by": "worker-b"},
+                    {"id": "mistral", "object": "model"},
+                    {"object": "model"}
+                ]
+            }),
+            serde_json::json!({"error": "not a model list"}),
+        ];
+
+        let merged = Router::merge_model_lists(&lists);
+        assert_eq!(merged["object"], "list");
+        let ids: Vec<&str> = merged["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["llama-3", "qwen", "mistral"]);
+        assert_eq!(merged["data"][0]["owned_by"], "worker-a");
+    }
 }
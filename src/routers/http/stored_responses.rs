@@ -0,0 +1,341 @@
+//! Responses API endpoints served from the router's response storage
+//!
+//! Non-streaming results of `POST /v1/responses` are stored as they pass
+//! through the router. Deleting a response and listing its input items only
+//! need what the router has stored, so they are answered locally instead of
+//! being proxied to a worker.
+
+use crate::data_connector::{ResponseId, ResponseStorage, StoredResponse};
+use crate::protocols::spec::{
+    ListOrder, ResponseInput, ResponseInputItemsParams, ResponsesRequest,
+};
+use crate::routers::error_response::error_body;
+use axum::{
+    body::{to_bytes, Body},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+use tracing::{error, warn};
+
+const DEFAULT_INPUT_ITEMS_LIMIT: usize = 20;
+const MAX_INPUT_ITEMS_LIMIT: usize = 100;
+
+/// OpenAI-style error response naming the offending parameter
+fn error_response(status: StatusCode, message: String, param: Option<&str>) -> Response {
+    (status, Json(error_body(status, message, None, param))).into_response()
+}
+
+fn not_found(response_id: &str) -> Response {
+    error_response(
+        StatusCode::NOT_FOUND,
+        format!("No response found with id '{}'", response_id),
+        None,
+    )
+}
+
+fn storage_error(operation: &str, response_id: &str, e: impl std::fmt::Display) -> Response {
+    error!("Failed to {} response {}: {}", operation, response_id, e);
+    error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        format!("Failed to {} response: {}", operation, e),
+        None,
+    )
+}
+
+/// Store the result of a Responses API request the client asked to keep
+///
+/// Only complete, successful, non-streaming results are stored, under the ID
+/// the worker gave them. The response is passed on unchanged; a storage
+/// failure is logged rather than failing a request that already succeeded.
+pub async fn store_response_result(
+    storage: &dyn ResponseStorage,
+    request: &ResponsesRequest,
+    response: Response,
+) -> Response {
+    if !request.store || request.stream || response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Failed to read Responses API result: {}", e);
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to read response body: {}", e),
+                None,
+            );
+        }
+    };
+
+    match serde_json::from_slice::<Value>(&bytes) {
+        Ok(result) if result["id"].is_string() => {
+            if let Err(e) = storage
+                .store_response(stored_response(request, &result))
+                .await
+            {
+                warn!("Failed to store response {}: {}", result["id"], e);
+            }
+        }
+        _ => warn!("Responses API result has no id, not storing it"),
+    }
+
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+fn stored_response(request: &ResponsesRequest, result: &Value) -> StoredResponse {
+    let input = match &request.input {
+        ResponseInput::Text(text) => text.clone(),
+        ResponseInput::Items(items) => serde_json::to_string(items).unwrap_or_default(),
+    };
+    let previous = request
+        .previous_response_id
+        .clone()
+        .map(ResponseId::from_string);
+
+    let mut stored = StoredResponse::new(input, result["output"].to_string(), previous);
+    stored.id = ResponseId::from_string(result["id"].as_str().unwrap_or_default().to_string());
+    stored.instructions = request.instructions.clone();
+    stored.metadata = request.metadata.clone().unwrap_or_default();
+    stored.user = request.user.clone();
+    stored.model = result["model"]
+        .as_str()
+        .map(str::to_string)
+        .or_else(|| request.model.clone());
+    stored
+}
+
+/// Delete a stored response and return the OpenAI deletion object
+pub async fn delete_stored_response(storage: &dyn ResponseStorage, response_id: &str) -> Response {
+    let id = ResponseId::from_string(response_id.to_string());
+    match storage.get_response(&id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return not_found(response_id),
+        Err(e) => return storage_error("read", response_id, e),
+    }
+
+    if let Err(e) = storage.delete_response(&id).await {
+        return storage_error("delete", response_id, e);
+    }
+
+    Json(json!({
+        "id": response_id,
+        "object": "response",
+        "deleted": true,
+    }))
+    .into_response()
+}
+
+/// List the input items of a stored response as an OpenAI list object
+pub async fn list_stored_input_items(
+    storage: &dyn ResponseStorage,
+    response_id: &str,
+    params: &ResponseInputItemsParams,
+) -> Response {
+    let limit = params.limit.unwrap_or(DEFAULT_INPUT_ITEMS_LIMIT);
+    if !(1..=MAX_INPUT_ITEMS_LIMIT).contains(&limit) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "limit must be between 1 and {}, got {}",
+                MAX_INPUT_ITEMS_LIMIT, limit
+            ),
+            Some("limit"),
+        );
+    }
+
+    let id = ResponseId::from_string(response_id.to_string());
+    let stored = match storage.get_response(&id).await {
+        Ok(Some(stored)) => stored,
+        Ok(None) => return not_found(response_id),
+        Err(e) => return storage_error("read", response_id, e),
+    };
+
+    let mut items = input_items(&stored);
+    if params.order == ListOrder::Desc {
+        items.reverse();
+    }
+
+    let start = match &params.after {
+        Some(after) => match items.iter().position(|item| item["id"] == after.as_str()) {
+            Some(pos) => pos + 1,
+            None => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("No input item with id '{}'", after),
+                    Some("after"),
+                )
+            }
+        },
+        None => 0,
+    };
+
+    let remaining = &items[start..];
+    let page = &remaining[..limit.min(remaining.len())];
+    Json(json!({
+        "object": "list",
+        "data": page,
+        "first_id": page.first().map(|item| item["id"].clone()),
+        "last_id": page.last().map(|item| item["id"].clone()),
+        "has_more": remaining.len() > page.len(),
+    }))
+    .into_response()
+}
+
+/// Input items of a stored response in the order they were sent
+///
+/// Structured input is stored as a JSON array of items; anything else is
+/// plain text, which the Responses API treats as a single user message.
+/// Items without an ID get a stable one derived from the response ID so they
+/// can be used as pagination cursors.
+fn input_items(stored: &StoredResponse) -> Vec<Value> {
+    let items = match serde_json::from_str::<Vec<Value>>(&stored.input) {
+        Ok(items) => items,
+        Err(_) => vec![json!({
+            "type": "message",
+            "role": "user",
+            "content": [{"type": "input_text", "text": stored.input}],
+        })],
+    };
+
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(idx, mut item)| {
+            if let Some(obj) = item.as_object_mut() {
+                obj.entry("id")
+                    .or_insert_with(|| json!(format!("{}_input_{}", stored.id.0, idx)));
+            }
+            item
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_connector::MemoryResponseStorage;
+    use axum::body::to_bytes;
+
+    async fn body_json(response: Response) -> Value {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    async fn store(storage: &MemoryResponseStorage, input: &str) -> String {
+        let response = StoredResponse::new(input.to_string(), "output".to_string(), None);
+        storage.store_response(response).await.unwrap().0
+    }
+
+    fn params(
+        limit: Option<usize>,
+        order: ListOrder,
+        after: Option<&str>,
+    ) -> ResponseInputItemsParams {
+        ResponseInputItemsParams {
+            after: after.map(|s| s.to_string()),
+            limit,
+            order,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_stored_response() {
+        let storage = MemoryResponseStorage::new();
+        let id = store(&storage, "hello").await;
+
+        let response = delete_stored_response(&storage, &id).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+        assert_eq!(body["id"], id.as_str());
+        assert_eq!(body["object"], "response");
+        assert_eq!(body["deleted"], true);
+
+        let response = delete_stored_response(&storage, &id).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = body_json(response).await;
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+    }
+
+    #[tokio::test]
+    async fn test_text_input_is_single_user_message() {
+        let storage = MemoryResponseStorage::new();
+        let id = store(&storage, "hello").await;
+
+        let response =
+            list_stored_input_items(&storage, &id, &ResponseInputItemsParams::default()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+
+        assert_eq!(body["object"], "list");
+        assert_eq!(body["has_more"], false);
+        let data = body["data"].as_array().unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["role"], "user");
+        assert_eq!(data[0]["content"][0]["text"], "hello");
+        assert_eq!(body["first_id"], data[0]["id"]);
+    }
+
+    #[tokio::test]
+    async fn test_input_items_pagination() {
+        let storage = MemoryResponseStorage::new();
+        let input = json!([
+            {"id": "msg_1", "type": "message", "role": "user", "content": []},
+            {"id": "msg_2", "type": "message", "role": "assistant", "content": []},
+            {"id": "msg_3", "type": "message", "role": "user", "content": []},
+        ]);
+        let id = store(&storage, &input.to_string()).await;
+
+        // Default order is newest first
+        let body = body_json(
+            list_stored_input_items(&storage, &id, &params(Some(2), ListOrder::Desc, None)).await,
+        )
+        .await;
+        assert_eq!(body["first_id"], "msg_3");
+        assert_eq!(body["last_id"], "msg_2");
+        assert_eq!(body["has_more"], true);
+
+        let body = body_json(
+            list_stored_input_items(
+                &storage,
+                &id,
+                &params(Some(2), ListOrder::Desc, Some("msg_2")),
+            )
+            .await,
+        )
+        .await;
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["first_id"], "msg_1");
+        assert_eq!(body["has_more"], false);
+
+        let body = body_json(
+            list_stored_input_items(&storage, &id, &params(None, ListOrder::Asc, None)).await,
+        )
+        .await;
+        assert_eq!(body["first_id"], "msg_1");
+        assert_eq!(body["last_id"], "msg_3");
+    }
+
+    #[tokio::test]
+    async fn test_input_items_rejects_bad_params() {
+        let storage = MemoryResponseStorage::new();
+        let id = store(&storage, "hello").await;
+
+        let response =
+            list_stored_input_items(&storage, &id, &params(Some(0), ListOrder::Desc, None)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response =
+            list_stored_input_items(&storage, &id, &params(None, ListOrder::Desc, Some("nope")))
+                .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response =
+            list_stored_input_items(&storage, "missing", &ResponseInputItemsParams::default())
+                .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
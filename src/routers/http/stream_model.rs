@@ -0,0 +1,192 @@
+//! Model name normalization for streaming responses
+//!
+//! A worker names the model it loaded in every chunk it streams, which is
+//! not the name the client asked for when the request went through a model
+//! alias, a fallback or a traffic split. OpenAI clients expect the `model`
+//! of each chunk to match their request, so the router can rewrite it.
+//! Every `data:` frame naming a different model is parsed and re-serialized,
+//! which costs CPU per chunk; the rewrite is off unless configured.
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use serde_json::Value;
+
+/// Rewrites the `model` field of every SSE `data:` frame
+#[derive(Debug)]
+pub struct ModelRewriter {
+    model: String,
+    /// Trailing bytes of an SSE line split across upstream chunks
+    partial: Vec<u8>,
+}
+
+impl ModelRewriter {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            partial: Vec::new(),
+        }
+    }
+
+    /// Process one upstream chunk and return the bytes to forward
+    ///
+    /// Only whole lines are forwarded; an incomplete trailing line is held
+    /// until the rest of it arrives.
+    pub fn observe(&mut self, chunk: &[u8]) -> Bytes {
+        self.partial.extend_from_slice(chunk);
+        let Some(last_newline) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Bytes::new();
+        };
+        let complete: Vec<u8> = self.partial.drain(..=last_newline).collect();
+
+        let mut out = Vec::with_capacity(complete.len());
+        for line in complete.split_inclusive(|&b| b == b'\n') {
+            self.process_line(line, &mut out);
+        }
+        Bytes::from(out)
+    }
+
+    /// Flush any bytes still buffered when the upstream stream ends
+    pub fn finish(&mut self) -> Bytes {
+        let rest = std::mem::take(&mut self.partial);
+        let mut out = Vec::with_capacity(rest.len());
+        if !rest.is_empty() {
+            self.process_line(&rest, &mut out);
+        }
+        Bytes::from(out)
+    }
+
+    fn process_line(&self, line: &[u8], out: &mut Vec<u8>) {
+        match self.rewrite(line) {
+            Some(rewritten) => out.extend_from_slice(&rewritten),
+            None => out.extend_from_slice(line),
+        }
+    }
+
+    /// The line with its frame's model replaced, None to forward it unchanged
+    fn rewrite(&self, line: &[u8]) -> Option<Vec<u8>> {
+        let payload = line.trim_ascii().strip_prefix(b"data:")?.trim_ascii();
+        // Cheap check first so frames without a model are never parsed
+        if !payload.starts_with(b"{") || !contains(payload, b"\"model\"") {
+            return None;
+        }
+        let mut frame: Value = serde_json::from_slice(payload).ok()?;
+        let model = frame.get_mut("model")?;
+        if model.as_str() == Some(self.model.as_str()) {
+            return None;
+        }
+        *model = Value::String(self.model.clone());
+
+        let mut rewritten = b"data: ".to_vec();
+        rewritten.extend_from_slice(&serde_json::to_vec(&frame).ok()?);
+        // Keep the line ending the worker sent
+        rewritten.extend_from_slice(&line[line.trim_ascii_end().len()..]);
+        Some(rewritten)
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// Wrap an SSE stream so every frame names `model`
+pub fn rewrite_model<S, E>(stream: S, model: String) -> impl Stream<Item = Result<Bytes, E>>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+{
+    let state = (stream, ModelRewriter::new(model), false);
+    futures_util::stream::unfold(state, |(mut stream, mut rewriter, ended)| async move {
+        if ended {
+            return None;
+        }
+        match stream.next().await {
+            Some(Ok(chunk)) => {
+                let out = rewriter.observe(&chunk);
+                Some((Ok(out), (stream, rewriter, false)))
+            }
+            Some(Err(e)) => Some((Err(e), (stream, rewriter, true))),
+            None => {
+                let rest = rewriter.finish();
+                Some((Ok(rest), (stream, rewriter, true)))
+            }
+        }
+    })
+    .filter(|item| {
+        let keep = !matches!(item, Ok(bytes) if bytes.is_empty());
+        async move { keep }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chunk(model: &str) -> String {
+        format!(
+            "data: {}\n\n",
+            json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion.chunk",
+                "model": model,
+                "choices": [{"index": 0, "delta": {"content": "Hi"}}],
+            })
+        )
+    }
+
+    fn models(output: &[u8]) -> Vec<String> {
+        String::from_utf8_lossy(output)
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .filter_map(|payload| serde_json::from_str::<Value>(payload).ok())
+            .filter_map(|frame| frame["model"].as_str().map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn test_rewrites_model_of_every_frame() {
+        let input = format!(
+            "{}{}: keep-alive\n\ndata: [DONE]\n\n",
+            chunk("llama-3.1-70b-instruct-awq"),
+            chunk("llama-3.1-70b-instruct-awq")
+        );
+        let mut rewriter = ModelRewriter::new("gpt-4o");
+        let output = rewriter.observe(input.as_bytes());
+
+        assert_eq!(models(&output), vec!["gpt-4o", "gpt-4o"]);
+        let output = String::from_utf8(output.to_vec()).unwrap();
+        assert!(output.contains("\n\n: keep-alive\n\ndata: [DONE]\n\n"));
+        assert!(output.ends_with("data: [DONE]\n\n"));
+    }
+
+    #[test]
+    fn test_matching_and_model_less_frames_pass_through() {
+        let input = format!(
+            "{}data: {{\"error\": {{\"message\": \"boom\"}}}}\n\n",
+            chunk("gpt-4o")
+        );
+        let mut rewriter = ModelRewriter::new("gpt-4o");
+        assert_eq!(rewriter.observe(input.as_bytes()), Bytes::from(input));
+    }
+
+    #[tokio::test]
+    async fn test_handles_frames_split_across_chunks() {
+        let input = chunk("internal");
+        let (head, tail) = input.as_bytes().split_at(20);
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::copy_from_slice(head)),
+            Ok(Bytes::copy_from_slice(tail)),
+            Ok(Bytes::from("data: [DONE]")),
+        ];
+        let output: Vec<Bytes> =
+            rewrite_model(futures_util::stream::iter(chunks), "gpt-4o".to_string())
+                .map(|chunk| chunk.unwrap())
+                .collect()
+                .await;
+
+        let output = output.concat();
+        assert_eq!(models(&output), vec!["gpt-4o"]);
+        assert!(output.ends_with(b"data: [DONE]"));
+    }
+}
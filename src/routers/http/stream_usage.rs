@@ -0,0 +1,313 @@
+//! Streaming usage reporting for PD disaggregation
+//!
+//! OpenAI clients that set `stream_options.include_usage` expect a final
+//! chunk carrying token usage right before `data: [DONE]`. In PD mode the
+//! prefill stage is sent without `stream_options`, and a decode worker is not
+//! guaranteed to report usage, so the router fills in the chunk itself when
+//! the decode stream ends without one.
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use serde_json::{json, Value};
+use tracing::debug;
+
+/// Whether a request asks for a usage chunk at the end of its stream
+pub fn wants_stream_usage(request: &Value) -> bool {
+    let streaming = request
+        .get("stream")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    streaming
+        && request
+            .pointer("/stream_options/include_usage")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+}
+
+/// Prompt token count reported in a (non-streaming) prefill response
+pub fn prompt_tokens(prefill_response: &Value) -> Option<u64> {
+    prefill_response
+        .pointer("/usage/prompt_tokens")
+        .and_then(|v| v.as_u64())
+}
+
+/// Rewrites an SSE stream so it ends with a usage chunk before `[DONE]`
+///
+/// Completion tokens are counted as one per choice per chunk that carries
+/// generated content, which matches vLLM's default of streaming every token.
+/// If the worker sends its own usage chunk the stream is passed through
+/// unchanged. Nothing is added to a stream that ends without `[DONE]`.
+#[derive(Debug, Default)]
+pub struct UsageInjector {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    usage_seen: bool,
+    done: bool,
+    /// Trailing bytes of an SSE line split across upstream chunks
+    partial: Vec<u8>,
+    /// Fields copied from the worker's chunks into the synthesized one
+    id: Option<Value>,
+    object: Option<Value>,
+    created: Option<Value>,
+    model: Option<Value>,
+}
+
+impl UsageInjector {
+    pub fn new(prompt_tokens: Option<u64>) -> Self {
+        Self {
+            prompt_tokens: prompt_tokens.unwrap_or(0),
+            ..Default::default()
+        }
+    }
+
+    /// Process one upstream chunk and return the bytes to forward
+    ///
+    /// Only whole lines are forwarded; an incomplete trailing line is held
+    /// until the rest of it arrives.
+    pub fn observe(&mut self, chunk: &[u8]) -> Bytes {
+        self.partial.extend_from_slice(chunk);
+        let Some(last_newline) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Bytes::new();
+        };
+        let complete: Vec<u8> = self.partial.drain(..=last_newline).collect();
+
+        let mut out = Vec::with_capacity(complete.len());
+        for line in complete.split_inclusive(|&b| b == b'\n') {
+            self.process_line(line, &mut out);
+        }
+        Bytes::from(out)
+    }
+
+    /// Flush any bytes still buffered when the upstream stream ends
+    ///
+    /// A final line without a trailing newline is forwarded as the worker
+    /// sent it, with usage still added if that line is `[DONE]`.
+    pub fn finish(&mut self) -> Bytes {
+        let rest = std::mem::take(&mut self.partial);
+        let mut out = Vec::with_capacity(rest.len());
+        if !rest.is_empty() {
+            self.process_line(&rest, &mut out);
+        }
+        Bytes::from(out)
+    }
+
+    fn process_line(&mut self, line: &[u8], out: &mut Vec<u8>) {
+        if let Some(payload) = line.trim_ascii().strip_prefix(b"data:") {
+            let payload = payload.trim_ascii();
+            if payload == b"[DONE]" {
+                if !self.done && !self.usage_seen {
+                    out.extend_from_slice(&self.usage_frame());
+                }
+                self.done = true;
+            } else {
+                self.inspect(payload);
+            }
+        }
+        out.extend_from_slice(line);
+    }
+
+    fn inspect(&mut self, payload: &[u8]) {
+        let Ok(chunk) = serde_json::from_slice::<Value>(payload) else {
+            return;
+        };
+        if chunk.get("usage").is_some_and(|usage| !usage.is_null()) {
+            self.usage_seen = true;
+        }
+        for (field, slot) in [
+            ("id", &mut self.id),
+            ("object", &mut self.object),
+            ("created", &mut self.created),
+            ("model", &mut self.model),
+        ] {
+            if let Some(value) = chunk.get(field) {
+                *slot = Some(value.clone());
+            }
+        }
+        if let Some(choices) = chunk.get("choices").and_then(|c| c.as_array()) {
+            self.completion_tokens += choices.iter().filter(|c| has_content(c)).count() as u64;
+        }
+    }
+
+    fn usage_frame(&self) -> Bytes {
+        debug!(
+            "Worker stream had no usage chunk, adding one ({} prompt, {} completion tokens)",
+            self.prompt_tokens, self.completion_tokens
+        );
+        let chunk = json!({
+            "id": self.id,
+            "object": self.object,
+            "created": self.created,
+            "model": self.model,
+            "choices": [],
+            "usage": {
+                "prompt_tokens": self.prompt_tokens,
+                "completion_tokens": self.completion_tokens,
+                "total_tokens": self.prompt_tokens + self.completion_tokens,
+            },
+        });
+        Bytes::from(format!("data: {}\n\n", chunk))
+    }
+}
+
+/// Whether a streamed choice carries generated output (chat delta or completion text)
+fn has_content(choice: &Value) -> bool {
+    let non_empty = |v: Option<&Value>| match v {
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(a)) => !a.is_empty(),
+        _ => false,
+    };
+    let delta = choice.get("delta");
+    non_empty(choice.get("text"))
+        || non_empty(delta.and_then(|d| d.get("content")))
+        || non_empty(delta.and_then(|d| d.get("reasoning_content")))
+        || non_empty(delta.and_then(|d| d.get("tool_calls")))
+}
+
+/// Wrap a decode stream so that it reports usage before `[DONE]`
+pub fn inject_usage<S, E>(
+    stream: S,
+    prompt_tokens: Option<u64>,
+) -> impl Stream<Item = Result<Bytes, E>>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+{
+    let state = (stream, UsageInjector::new(prompt_tokens), false);
+    futures_util::stream::unfold(state, |(mut stream, mut injector, ended)| async move {
+        if ended {
+            return None;
+        }
+        match stream.next().await {
+            Some(Ok(chunk)) => {
+                let out = injector.observe(&chunk);
+                Some((Ok(out), (stream, injector, false)))
+            }
+            Some(Err(e)) => Some((Err(e), (stream, injector, true))),
+            None => {
+                let rest = injector.finish();
+                Some((Ok(rest), (stream, injector, true)))
+            }
+        }
+    })
+    .filter(|item| {
+        let keep = !matches!(item, Ok(bytes) if bytes.is_empty());
+        async move { keep }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chat_chunk(content: &str) -> String {
+        format!(
+            "data: {}\n\n",
+            json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion.chunk",
+                "created": 1700000000,
+                "model": "llama",
+                "choices": [{"index": 0, "delta": {"content": content}}],
+                "usage": null,
+            })
+        )
+    }
+
+    fn usage_of(output: &str) -> Option<Value> {
+        output
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .filter_map(|payload| serde_json::from_str::<Value>(payload).ok())
+            .find_map(|chunk| chunk.get("usage").filter(|u| !u.is_null()).cloned())
+    }
+
+    #[test]
+    fn test_wants_stream_usage() {
+        assert!(wants_stream_usage(&json!({
+            "stream": true,
+            "stream_options": {"include_usage": true}
+        })));
+        assert!(!wants_stream_usage(&json!({"stream": true})));
+        assert!(!wants_stream_usage(&json!({
+            "stream": false,
+            "stream_options": {"include_usage": true}
+        })));
+    }
+
+    #[test]
+    fn test_prompt_tokens_from_prefill_response() {
+        let prefill = json!({"usage": {"prompt_tokens": 12, "completion_tokens": 1}});
+        assert_eq!(prompt_tokens(&prefill), Some(12));
+        assert_eq!(prompt_tokens(&json!({})), None);
+    }
+
+    #[test]
+    fn test_synthesizes_usage_before_done() {
+        let mut injector = UsageInjector::new(Some(7));
+        let mut output = String::new();
+        for chunk in [chat_chunk("Hel"), chat_chunk("lo"), chat_chunk("")] {
+            output.push_str(&String::from_utf8_lossy(
+                &injector.observe(chunk.as_bytes()),
+            ));
+        }
+        output.push_str(&String::from_utf8_lossy(
+            &injector.observe(b"data: [DONE]\n\n"),
+        ));
+
+        let usage_pos = output.find("\"usage\":{").unwrap();
+        assert!(usage_pos < output.find("[DONE]").unwrap());
+        let usage = usage_of(&output).unwrap();
+        assert_eq!(usage["prompt_tokens"], 7);
+        assert_eq!(usage["completion_tokens"], 2);
+        assert_eq!(usage["total_tokens"], 9);
+        assert!(output.contains("\"object\":\"chat.completion.chunk\""));
+    }
+
+    #[test]
+    fn test_worker_usage_passes_through() {
+        let worker_usage = format!(
+            "data: {}\n\n",
+            json!({
+                "id": "chatcmpl-1",
+                "choices": [],
+                "usage": {"prompt_tokens": 3, "completion_tokens": 1, "total_tokens": 4},
+            })
+        );
+        let input = format!("{}{}data: [DONE]\n\n", chat_chunk("Hi"), worker_usage);
+
+        let mut injector = UsageInjector::new(Some(99));
+        let output = injector.observe(input.as_bytes());
+        assert_eq!(output, Bytes::from(input));
+    }
+
+    #[test]
+    fn test_handles_frames_split_across_chunks() {
+        let input = format!("{}data: [DONE]\n\n", chat_chunk("Hi"));
+        let (head, tail) = input.as_bytes().split_at(input.len() - 6);
+
+        let mut injector = UsageInjector::new(Some(1));
+        let mut output = injector.observe(head).to_vec();
+        output.extend_from_slice(&injector.observe(tail));
+        output.extend_from_slice(&injector.finish());
+
+        let output = String::from_utf8(output).unwrap();
+        let usage = usage_of(&output).unwrap();
+        assert_eq!(usage["completion_tokens"], 1);
+        assert!(output.ends_with("data: [DONE]\n\n"));
+    }
+
+    #[tokio::test]
+    async fn test_truncated_stream_gets_no_usage() {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::from(chat_chunk("Hi"))),
+            Ok(Bytes::from("data: {\"id\"")),
+        ];
+        let output: Vec<Bytes> = inject_usage(futures_util::stream::iter(chunks), Some(5))
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+
+        let output = String::from_utf8(output.concat()).unwrap();
+        assert!(usage_of(&output).is_none());
+        assert!(output.ends_with("data: {\"id\""));
+    }
+}
@@ -5,9 +5,16 @@ use super::dp_utils;
 use super::logprobs_merge;
 use super::pd_router::PDRouter;
 use super::pd_types::PDRouterError;
-use super::vllm_service_discovery::{ServiceRegistry, ServiceType};
-use crate::core::{BasicWorker, Worker, WorkerType};
-use crate::policies::PolicyRegistry;
+use super::stream_usage;
+use super::vllm_service_discovery::{DiscoveryHealth, ServiceRegistry, ServiceType};
+use crate::config::RetryOn;
+use crate::core::{
+    is_retryable_status, model_version, BasicWorker, Worker, WorkerResponse, WorkerType,
+};
+use crate::metrics::RouterMetrics;
+use crate::policies::{LoadBalancingPolicy, PolicyRegistry};
+use crate::protocols::spec::GenerationRequest;
+use crate::routers::error_response::error_response;
 use crate::routers::{RouterTrait, WorkerManagement};
 use async_trait::async_trait;
 use axum::{
@@ -15,6 +22,7 @@ use axum::{
     extract::Request,
     http::{HeaderMap, Method, StatusCode},
     response::{IntoResponse, Response},
+    Json,
 };
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -23,6 +31,11 @@ use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+/// Worker label holding the topology zone used for prefill/decode affinity
+const ZONE_LABEL: &str = "zone";
+
+type WorkerList = Vec<Arc<dyn Worker>>;
+
 /// vLLM PD Router that extends PDRouter with vLLM-specific request handling
 #[derive(Debug)]
 pub struct VllmPDRouter {
@@ -44,6 +57,10 @@ pub struct VllmPDRouter {
     profiling_tasks: Arc<Mutex<HashMap<String, tokio::task::AbortHandle>>>,
     /// Intra-node data parallel size for DP-aware routing (automatically enabled when > 1)
     intra_node_data_parallel_size: usize,
+    /// Prefer decode workers in the same zone as the selected prefill worker
+    zone_affinity: bool,
+    /// Seconds without a registration after which discovery is reported stale
+    discovery_stale_secs: u64,
 }
 
 impl VllmPDRouter {
@@ -152,15 +169,43 @@ impl VllmPDRouter {
         }
         // Force non-streaming for prefill to get JSON response with kv_transfer_params
         request["stream"] = json!(false);
-        // Remove stream_options since we're setting stream=false
+        // Remove stream_options since we're setting stream=false; the decode
+        // request keeps them, and usage is filled in there if the worker omits it
         if let Some(obj) = request.as_object_mut() {
             obj.remove("stream_options");
         }
         request
     }
 
+    /// Prefill and decode workers of the registry, for direct URL mode
+    ///
+    /// When version skew splits traffic, both sets are narrowed to each
+    /// model's active version, picked across prefill and decode so the pair
+    /// agrees.
+    fn registry_workers(&self) -> (WorkerList, WorkerList) {
+        let mut prefill_workers = self.pd_router.worker_registry.get_prefill_workers();
+        let mut decode_workers = self.pd_router.worker_registry.get_decode_workers();
+        if self.pd_router.split_model_versions {
+            let available = prefill_workers
+                .iter()
+                .chain(&decode_workers)
+                .filter(|w| w.is_available());
+            let active = model_version::active_versions(available);
+            model_version::retain_versions(&mut prefill_workers, &active);
+            model_version::retain_versions(&mut decode_workers, &active);
+        }
+        (prefill_workers, decode_workers)
+    }
+
     /// Convert service discovery instances to Worker objects for policy selection
-    fn instances_to_workers(instances: &[(String, String)]) -> Vec<Arc<dyn Worker>> {
+    ///
+    /// The zone an instance registered with becomes its `zone` label so pair
+    /// selection can see it.
+    fn instances_to_workers(
+        &self,
+        instances: &[(String, String)],
+        service_type: ServiceType,
+    ) -> Vec<Arc<dyn Worker>> {
         instances
             .iter()
             .map(|(http_addr, _zmq_addr)| {
@@ -170,34 +215,71 @@ impl VllmPDRouter {
                     } else {
                         format!("http://{}", http_addr)
                     };
-                Arc::new(BasicWorker::new(full_url, WorkerType::Regular)) as Arc<dyn Worker>
+                let mut worker = BasicWorker::new(full_url, WorkerType::Regular)
+                    .with_health_config(self.pd_router.health_config.clone());
+                if let Some(zone) = self
+                    .service_registry
+                    .get_zone(http_addr, service_type.clone())
+                {
+                    worker = worker.with_labels(HashMap::from([(ZONE_LABEL.to_string(), zone)]));
+                }
+                Arc::new(worker) as Arc<dyn Worker>
             })
             .collect()
     }
 
-    /// Select worker using policy-based load balancing
-    fn select_worker_with_policy(
-        &self,
-        instances: &[(String, String)],
-        is_prefill: bool,
+    /// Select a prefill/decode pair for one request
+    ///
+    /// The prefill worker is picked first. With zone affinity the decode
+    /// policy then only sees healthy decode workers labeled with the same
+    /// zone, so KV cache transfers stay local; it falls back to the whole
+    /// decode pool when that zone has none or the prefill worker has no zone.
+    fn select_worker_pair(
+        prefill_policy: &dyn LoadBalancingPolicy,
+        decode_policy: &dyn LoadBalancingPolicy,
+        prefill_workers: &[Arc<dyn Worker>],
+        decode_workers: &[Arc<dyn Worker>],
         request_text: Option<&str>,
-    ) -> Option<usize> {
-        if instances.is_empty() {
-            return None;
+        zone_affinity: bool,
+    ) -> Result<(usize, usize), &'static str> {
+        let prefill_idx = prefill_policy
+            .select_worker(prefill_workers, request_text)
+            .ok_or("Prefill policy failed to select a worker")?;
+
+        let zone = zone_affinity
+            .then(|| {
+                prefill_workers[prefill_idx]
+                    .metadata()
+                    .labels
+                    .get(ZONE_LABEL)
+            })
+            .flatten();
+        if let Some(zone) = zone {
+            let same_zone: Vec<usize> = (0..decode_workers.len())
+                .filter(|&idx| {
+                    let worker = &decode_workers[idx];
+                    worker.is_healthy() && worker.metadata().labels.get(ZONE_LABEL) == Some(zone)
+                })
+                .collect();
+            if !same_zone.is_empty() {
+                let candidates: Vec<Arc<dyn Worker>> = same_zone
+                    .iter()
+                    .map(|&idx| decode_workers[idx].clone())
+                    .collect();
+                if let Some(idx) = decode_policy.select_worker(&candidates, request_text) {
+                    return Ok((prefill_idx, same_zone[idx]));
+                }
+            }
+            debug!(
+                "No decode worker available in zone {}, selecting across zones",
+                zone
+            );
         }
 
-        // Convert instances to workers for policy selection
-        let workers = Self::instances_to_workers(instances);
-
-        // Get the appropriate policy
-        let policy = if is_prefill {
-            self.policy_registry.get_prefill_policy()
-        } else {
-            self.policy_registry.get_decode_policy()
-        };
-
-        // Use policy to select worker
-        policy.select_worker(&workers, request_text)
+        let decode_idx = decode_policy
+            .select_worker(decode_workers, request_text)
+            .ok_or("Decode policy failed to select a worker")?;
+        Ok((prefill_idx, decode_idx))
     }
 
     /// Process vLLM request using pure service discovery
@@ -224,43 +306,32 @@ impl VllmPDRouter {
         );
 
         if prefill_instances.is_empty() || decode_instances.is_empty() {
-            return (
-                axum::http::StatusCode::SERVICE_UNAVAILABLE,
+            return error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
                 format!(
                     "No workers available via service discovery: {} prefill, {} decode",
                     prefill_instances.len(),
                     decode_instances.len()
                 ),
-            )
-                .into_response();
+            );
         }
 
         // Use policy-based load balancing to select prefill and decode workers
         let request_text = serde_json::to_string(&request_json).ok();
         let request_str = request_text.as_deref();
 
-        let prefill_idx =
-            match self.select_worker_with_policy(&prefill_instances, true, request_str) {
-                Some(idx) => idx,
-                None => {
-                    return (
-                        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-                        "Prefill policy failed to select a worker".to_string(),
-                    )
-                        .into_response();
-                }
-            };
-
-        let decode_idx = match self.select_worker_with_policy(&decode_instances, false, request_str)
-        {
-            Some(idx) => idx,
-            None => {
-                return (
-                    axum::http::StatusCode::SERVICE_UNAVAILABLE,
-                    "Decode policy failed to select a worker".to_string(),
-                )
-                    .into_response();
-            }
+        let prefill_workers = self.instances_to_workers(&prefill_instances, ServiceType::Prefill);
+        let decode_workers = self.instances_to_workers(&decode_instances, ServiceType::Decode);
+        let (prefill_idx, decode_idx) = match Self::select_worker_pair(
+            self.policy_registry.get_prefill_policy().as_ref(),
+            self.policy_registry.get_decode_policy().as_ref(),
+            &prefill_workers,
+            &decode_workers,
+            request_str,
+            self.zone_affinity,
+        ) {
+            Ok(pair) => pair,
+            Err(message) => return error_response(StatusCode::SERVICE_UNAVAILABLE, message),
         };
 
         let (prefill_http, prefill_zmq) = &prefill_instances[prefill_idx];
@@ -296,11 +367,10 @@ impl VllmPDRouter {
             }
             Err(e) => {
                 debug!("Two-stage processing failed: {}", e);
-                (
-                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                error_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
                     format!("Request processing failed: {}", e),
                 )
-                    .into_response()
             }
         }
     }
@@ -391,6 +461,11 @@ impl VllmPDRouter {
         // Propagate trace headers and add X-data-parallel-rank header using shared utilities
         prefill_request_builder =
             header_utils::propagate_trace_headers(prefill_request_builder, headers);
+        prefill_request_builder = header_utils::apply_upstream_auth(
+            prefill_request_builder,
+            &self.pd_router.upstream_auth,
+            headers,
+        );
         prefill_request_builder =
             dp_utils::add_dp_rank_header(prefill_request_builder, prefill_dp_rank);
         if let Some(rank) = prefill_dp_rank {
@@ -483,6 +558,11 @@ impl VllmPDRouter {
         // Propagate trace headers and add X-data-parallel-rank header using shared utilities
         decode_request_builder =
             header_utils::propagate_trace_headers(decode_request_builder, headers);
+        decode_request_builder = header_utils::apply_upstream_auth(
+            decode_request_builder,
+            &self.pd_router.upstream_auth,
+            headers,
+        );
         decode_request_builder =
             dp_utils::add_dp_rank_header(decode_request_builder, decode_dp_rank);
         if let Some(rank) = decode_dp_rank {
@@ -509,6 +589,7 @@ impl VllmPDRouter {
 
         // Check if logprobs merging is needed
         let needs_logprobs = request_json.get("logprobs").is_some()
+            || request_json.get("prompt_logprobs").is_some()
             || request_json
                 .get("echo")
                 .and_then(|v| v.as_bool())
@@ -546,7 +627,9 @@ impl VllmPDRouter {
             let merged_body = serde_json::to_vec(&decode_json)
                 .map_err(|e| format!("Failed to serialize merged response: {}", e))?;
 
-            let mut response_builder = axum::http::Response::builder().status(status);
+            let mut response_builder = axum::http::Response::builder()
+                .status(status)
+                .extension(WorkerResponse);
             for (name, value) in headers.iter() {
                 response_builder = response_builder.header(name, value);
             }
@@ -565,12 +648,23 @@ impl VllmPDRouter {
 
             let status = decode_response.status();
             let headers = decode_response.headers().clone();
-            let body = decode_response
+            let mut body = decode_response
                 .bytes()
                 .await
                 .map_err(|e| format!("Failed to read decode response: {}", e))?;
 
-            let mut response_builder = axum::http::Response::builder().status(status);
+            if status.is_success() && stream_usage::wants_stream_usage(&request_json) {
+                let mut injector = stream_usage::UsageInjector::new(stream_usage::prompt_tokens(
+                    &prefill_response_json,
+                ));
+                let mut with_usage = injector.observe(&body).to_vec();
+                with_usage.extend_from_slice(&injector.finish());
+                body = with_usage.into();
+            }
+
+            let mut response_builder = axum::http::Response::builder()
+                .status(status)
+                .extension(WorkerResponse);
             for (name, value) in headers.iter() {
                 response_builder = response_builder.header(name, value);
             }
@@ -583,18 +677,146 @@ impl VllmPDRouter {
         }
     }
 
+    /// Pick another decode worker for a request whose decode stage failed
+    ///
+    /// Workers already tried and unavailable workers are excluded; the decode
+    /// policy chooses among the rest, preferring those in `zone` when any of
+    /// them is left.
+    fn select_fallback_decode_worker(
+        decode_policy: &dyn LoadBalancingPolicy,
+        decode_workers: &[Arc<dyn Worker>],
+        zone: Option<&str>,
+        tried: &[String],
+        request_text: Option<&str>,
+    ) -> Option<Arc<dyn Worker>> {
+        let mut candidates: Vec<Arc<dyn Worker>> = decode_workers
+            .iter()
+            .filter(|worker| worker.is_available() && !tried.iter().any(|url| url == worker.url()))
+            .cloned()
+            .collect();
+        if let Some(zone) = zone {
+            let same_zone: Vec<Arc<dyn Worker>> = candidates
+                .iter()
+                .filter(|worker| {
+                    worker.metadata().labels.get(ZONE_LABEL).map(String::as_str) == Some(zone)
+                })
+                .cloned()
+                .collect();
+            if !same_zone.is_empty() {
+                candidates = same_zone;
+            }
+        }
+        let idx = decode_policy.select_worker(&candidates, request_text)?;
+        Some(candidates[idx].clone())
+    }
+
+    /// Send the decode stage of a request to one decode worker
+    ///
+    /// The worker's load counts the request until the response headers
+    /// arrive. Returns the response together with the URL it came from.
+    async fn send_decode_request(
+        &self,
+        decode_worker: &Arc<dyn Worker>,
+        decode_request: &Value,
+        path: &str,
+        request_id: &str,
+        headers: Option<&HeaderMap>,
+        decode_load_weight: usize,
+    ) -> Result<(reqwest::Response, String), PDRouterError> {
+        // Extract base URL and dp_rank if intra_node_data_parallel_size > 1
+        let (decode_base_url, decode_dp_rank) = if self.intra_node_data_parallel_size > 1 {
+            match dp_utils::extract_dp_rank(decode_worker.url()) {
+                Ok((base, rank)) => (base.to_string(), Some(rank)),
+                Err(e) => {
+                    return Err(PDRouterError::NetworkError {
+                        message: format!(
+                            "Failed to extract dp_rank from decode worker URL {}: {}",
+                            decode_worker.url(),
+                            e
+                        ),
+                    });
+                }
+            }
+        } else {
+            (decode_worker.url().to_string(), None)
+        };
+
+        let decode_url = format!("{}{}", decode_base_url, path);
+
+        debug!(
+            "🚀 vLLM Stage 2 - Decode: {} with request_id: {}",
+            decode_url, request_id
+        );
+        if let Some(rank) = decode_dp_rank {
+            debug!("📤 Decode request headers: Authorization=Bearer [REDACTED], X-Request-Id={}, X-data-parallel-rank={}", request_id, rank);
+        } else {
+            debug!(
+                "📤 Decode request headers: Authorization=Bearer [REDACTED], X-Request-Id={}",
+                request_id
+            );
+        }
+        debug!(
+            "📤 Decode request payload: {}",
+            serde_json::to_string_pretty(decode_request).unwrap_or_default()
+        );
+
+        // Start profiling on decode server
+        self.start_profiling(&decode_base_url).await;
+
+        let mut decode_request_builder = self
+            .pd_router
+            .client
+            .post(&decode_url)
+            .header("Content-Type", "application/json")
+            .header("X-Request-Id", request_id);
+
+        // Propagate trace headers and set Authorization per the upstream auth mode
+        decode_request_builder =
+            header_utils::propagate_trace_headers(decode_request_builder, headers);
+        decode_request_builder = header_utils::apply_upstream_auth(
+            decode_request_builder,
+            &self.pd_router.upstream_auth,
+            headers,
+        );
+
+        // Add X-data-parallel-rank header if intra_node_data_parallel_size > 1
+        if let Some(rank) = decode_dp_rank {
+            decode_request_builder =
+                decode_request_builder.header("X-data-parallel-rank", rank.to_string());
+        }
+
+        decode_worker.increment_load_by(decode_load_weight);
+        let decode_response = decode_request_builder.json(decode_request).send().await;
+        // Decode phase complete: decrement decode load
+        decode_worker.decrement_load_by(decode_load_weight);
+
+        let decode_response = decode_response.map_err(|e| PDRouterError::NetworkError {
+            message: format!("Decode request failed to {}: {}", decode_url, e),
+        })?;
+
+        // Stop profiling on decode server after response received
+        self.stop_profiling(&decode_base_url).await;
+
+        Ok((decode_response, decode_url))
+    }
+
     /// Two-stage request processing for vLLM disaggregated mode
     ///
     /// This function handles fine-grained load tracking: the prefill worker's load is only
     /// incremented during the prefill phase, and the decode worker's load is only incremented
     /// during the decode phase. This accurately reflects the sequential nature of PD disaggregation.
+    /// The decode phase counts `decode_load_weight` times, once per sampled sequence.
+    /// A failed decode stage is retried on another worker from `decode_workers`.
+    #[allow(clippy::too_many_arguments)]
     async fn process_vllm_two_stage_request(
         &self,
         original_request: Value,
         prefill_worker: Arc<dyn Worker>,
         decode_worker: Arc<dyn Worker>,
+        decode_workers: &[Arc<dyn Worker>],
         path: &str,
         headers: Option<&HeaderMap>,
+        decode_load_weight: usize,
     ) -> Result<Response, PDRouterError> {
         debug!("ENTERED process_vllm_two_stage_request method");
         debug!(
@@ -617,7 +839,7 @@ impl VllmPDRouter {
         debug!("  📋 Our Request ID format: ___prefill_addr_{{http_addr}}___decode_addr_{{http_addr}}_{{uuid}}");
         debug!("  📋 vLLM Proxy headers: Authorization: Bearer $OPENAI_API_KEY, X-Request-Id: {{request_id}}");
         debug!(
-            "  📋 Our headers: Authorization: Bearer $OPENAI_API_KEY, X-Request-Id: {{request_id}}"
+            "  📋 Our headers: Authorization per upstream auth mode, X-Request-Id: {{request_id}}"
         );
 
         // Stage 1: Prepare prefill request with max_tokens=1 and kv_transfer_params
@@ -681,18 +903,16 @@ impl VllmPDRouter {
             .client
             .post(&prefill_url)
             .header("Content-Type", "application/json")
-            .header(
-                "Authorization",
-                format!(
-                    "Bearer {}",
-                    std::env::var("OPENAI_API_KEY").unwrap_or_default()
-                ),
-            )
             .header("X-Request-Id", &request_id);
 
-        // Propagate trace headers
+        // Propagate trace headers and set Authorization per the upstream auth mode
         prefill_request_builder =
             header_utils::propagate_trace_headers(prefill_request_builder, headers);
+        prefill_request_builder = header_utils::apply_upstream_auth(
+            prefill_request_builder,
+            &self.pd_router.upstream_auth,
+            headers,
+        );
 
         // Add X-data-parallel-rank header if intra_node_data_parallel_size > 1
         if let Some(rank) = prefill_dp_rank {
@@ -767,9 +987,8 @@ impl VllmPDRouter {
         // Stop profiling on prefill server after its work is done
         self.stop_profiling(&prefill_base_url).await;
 
-        // Prefill phase complete: decrement prefill load, increment decode load
+        // Prefill phase complete: decrement prefill load
         prefill_worker.decrement_load();
-        decode_worker.increment_load();
 
         debug!("✅ vLLM Stage 1 completed, starting Stage 2 - Decode");
 
@@ -780,87 +999,67 @@ impl VllmPDRouter {
             debug!("Added kv_transfer_params to decode request");
         }
 
-        // Extract base URL and dp_rank if intra_node_data_parallel_size > 1
-        let (decode_base_url, decode_dp_rank) = if self.intra_node_data_parallel_size > 1 {
-            match dp_utils::extract_dp_rank(decode_worker.url()) {
-                Ok((base, rank)) => (base.to_string(), Some(rank)),
-                Err(e) => {
-                    decode_worker.decrement_load();
-                    return Err(PDRouterError::NetworkError {
-                        message: format!(
-                            "Failed to extract dp_rank from decode worker URL {}: {}",
-                            decode_worker.url(),
-                            e
-                        ),
-                    });
+        // The prefill work is already done, so a retryable decode failure moves
+        // the request to another decode worker instead of failing it
+        let max_decode_attempts = self.pd_router.retry_config.max_retries.max(1) as usize;
+        let request_text = serde_json::to_string(&original_request).ok();
+        let zone = self
+            .zone_affinity
+            .then(|| prefill_worker.metadata().labels.get(ZONE_LABEL).cloned())
+            .flatten();
+        let mut tried = vec![decode_worker.url().to_string()];
+        let mut decode_worker = decode_worker;
+        let (decode_response, decode_url) = loop {
+            let attempt = self
+                .send_decode_request(
+                    &decode_worker,
+                    &decode_request,
+                    path,
+                    &request_id,
+                    headers,
+                    decode_load_weight,
+                )
+                .await;
+            let retryable = match &attempt {
+                Ok((response, _)) => {
+                    self.pd_router.retry_config.retry_on == RetryOn::All
+                        && is_retryable_status(response.status())
                 }
+                Err(_) => true,
+            };
+            if !retryable || tried.len() >= max_decode_attempts {
+                break attempt?;
             }
-        } else {
-            (decode_worker.url().to_string(), None)
-        };
-
-        let decode_url = format!("{}{}", decode_base_url, path);
-
-        debug!(
-            "🚀 vLLM Stage 2 - Decode: {} with request_id: {}",
-            decode_url, request_id
-        );
-        if let Some(rank) = decode_dp_rank {
-            debug!("📤 Decode request headers: Authorization=Bearer [REDACTED], X-Request-Id={}, X-data-parallel-rank={}", request_id, rank);
-        } else {
-            debug!(
-                "📤 Decode request headers: Authorization=Bearer [REDACTED], X-Request-Id={}",
-                request_id
-            );
-        }
-        debug!(
-            "📤 Decode request payload: {}",
-            serde_json::to_string_pretty(&decode_request).unwrap_or_default()
-        );
-
-        // Start profiling on decode server
-        self.start_profiling(&decode_base_url).await;
+            let Some(next_worker) = Self::select_fallback_decode_worker(
+                self.policy_registry.get_decode_policy().as_ref(),
+                decode_workers,
+                zone.as_deref(),
+                &tried,
+                request_text.as_deref(),
+            ) else {
+                break attempt?;
+            };
 
-        let mut decode_request_builder = self
-            .pd_router
-            .client
-            .post(&decode_url)
-            .header("Content-Type", "application/json")
-            .header(
-                "Authorization",
-                format!(
-                    "Bearer {}",
-                    std::env::var("OPENAI_API_KEY").unwrap_or_default()
+            match &attempt {
+                Ok((response, _)) => warn!(
+                    "Decode worker {} returned {}, retrying decode on {}",
+                    decode_worker.url(),
+                    response.status(),
+                    next_worker.url()
+                ),
+                Err(e) => warn!(
+                    "Decode worker {} failed ({}), retrying decode on {}",
+                    decode_worker.url(),
+                    e,
+                    next_worker.url()
                 ),
-            )
-            .header("X-Request-Id", &request_id);
-
-        // Propagate trace headers
-        decode_request_builder =
-            header_utils::propagate_trace_headers(decode_request_builder, headers);
-
-        // Add X-data-parallel-rank header if intra_node_data_parallel_size > 1
-        if let Some(rank) = decode_dp_rank {
-            decode_request_builder =
-                decode_request_builder.header("X-data-parallel-rank", rank.to_string());
-        }
-
-        let decode_response = match decode_request_builder.json(&decode_request).send().await {
-            Ok(resp) => resp,
-            Err(e) => {
-                decode_worker.decrement_load();
-                return Err(PDRouterError::NetworkError {
-                    message: format!("Decode request failed to {}: {}", decode_url, e),
-                });
             }
+            RouterMetrics::record_pd_decode_error(decode_worker.url());
+            RouterMetrics::record_retry(path);
+            tried.push(next_worker.url().to_string());
+            decode_worker = next_worker;
         };
 
-        // Stop profiling on decode server after response received
-        self.stop_profiling(&decode_base_url).await;
-
-        // Decode phase complete: decrement decode load
-        decode_worker.decrement_load();
-
         let status = decode_response.status();
         let headers = decode_response.headers().clone();
 
@@ -869,6 +1068,7 @@ impl VllmPDRouter {
 
         // Check if logprobs merging is needed
         let needs_logprobs = original_request.get("logprobs").is_some()
+            || original_request.get("prompt_logprobs").is_some()
             || original_request
                 .get("echo")
                 .and_then(|v| v.as_bool())
@@ -915,7 +1115,7 @@ impl VllmPDRouter {
                     message: format!("Failed to serialize merged response: {}", e),
                 })?;
 
-            let mut response_builder = Response::builder().status(status);
+            let mut response_builder = Response::builder().status(status).extension(WorkerResponse);
             for (key, value) in headers.iter() {
                 if key != "transfer-encoding" && key != "content-length" {
                     response_builder = response_builder.header(key, value);
@@ -934,14 +1134,22 @@ impl VllmPDRouter {
                 is_streaming, needs_logprobs
             );
 
-            let mut response_builder = Response::builder().status(status);
+            let mut response_builder = Response::builder().status(status).extension(WorkerResponse);
             for (key, value) in headers.iter() {
                 if key != "transfer-encoding" && key != "content-length" {
                     response_builder = response_builder.header(key, value);
                 }
             }
 
-            let body = Body::from_stream(decode_response.bytes_stream());
+            let body = if status.is_success() && stream_usage::wants_stream_usage(&original_request)
+            {
+                Body::from_stream(stream_usage::inject_usage(
+                    decode_response.bytes_stream(),
+                    stream_usage::prompt_tokens(&prefill_response_json),
+                ))
+            } else {
+                Body::from_stream(decode_response.bytes_stream())
+            };
             response_builder
                 .body(body)
                 .map_err(|e| PDRouterError::NetworkError {
@@ -984,13 +1192,15 @@ impl VllmPDRouter {
             Ok(Self {
                 pd_router,
                 service_registry: Arc::new(service_registry),
-                http_client: reqwest::Client::new(),
+                http_client: ctx.client.clone(),
                 policy_registry: ctx.policy_registry.clone(),
                 use_discovery: true,
                 enable_profiling: ctx.router_config.enable_profiling,
                 profile_timeout_secs: ctx.router_config.profile_timeout_secs,
                 profiling_tasks: Arc::new(Mutex::new(HashMap::new())),
                 intra_node_data_parallel_size: ctx.router_config.intra_node_data_parallel_size,
+                zone_affinity: ctx.router_config.pd_zone_affinity,
+                discovery_stale_secs: ctx.router_config.discovery_stale_secs,
             })
         } else {
             // Direct URL mode (same as PDRouter)
@@ -1026,17 +1236,61 @@ impl VllmPDRouter {
             Ok(Self {
                 pd_router,
                 service_registry: Arc::new(service_registry),
-                http_client: reqwest::Client::new(),
+                http_client: ctx.client.clone(),
                 policy_registry: ctx.policy_registry.clone(),
                 use_discovery: false,
                 enable_profiling: ctx.router_config.enable_profiling,
                 profile_timeout_secs: ctx.router_config.profile_timeout_secs,
                 profiling_tasks: Arc::new(Mutex::new(HashMap::new())),
                 intra_node_data_parallel_size: ctx.router_config.intra_node_data_parallel_size,
+                zone_affinity: ctx.router_config.pd_zone_affinity,
+                discovery_stale_secs: ctx.router_config.discovery_stale_secs,
             })
         }
     }
 
+    /// Readiness in discovery mode
+    ///
+    /// Ready when discovery has registered at least one prefill and one decode
+    /// instance and is not stale. A stale listener keeps serving the last
+    /// known instances, but the router reports not ready so load balancers
+    /// can prefer replicas with live discovery.
+    fn discovery_readiness(health: &DiscoveryHealth, stale_secs: u64) -> Response {
+        let mut reasons = Vec::new();
+        if !health.connected {
+            reasons.push("service discovery listener is not connected".to_string());
+        } else if health.is_stale(stale_secs) {
+            reasons.push(format!(
+                "no service discovery registration for {}s",
+                health.silent_secs
+            ));
+        }
+        if health.prefill_instances == 0 {
+            reasons.push("no prefill instances discovered".to_string());
+        }
+        if health.decode_instances == 0 {
+            reasons.push("no decode instances discovered".to_string());
+        }
+
+        if reasons.is_empty() {
+            Json(json!({
+                "status": "ready",
+                "discovery": health,
+            }))
+            .into_response()
+        } else {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({
+                    "status": "not_ready",
+                    "reason": reasons.join(", "),
+                    "discovery": health,
+                })),
+            )
+                .into_response()
+        }
+    }
+
     /// Add a prefill server to the router
     /// Delegates to the underlying PDRouter
     pub async fn add_prefill_server(
@@ -1135,11 +1389,10 @@ impl RouterTrait for VllmPDRouter {
                     json
                 }
                 Err(e) => {
-                    return (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    return error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Serialization error: {}", e),
                     )
-                        .into_response()
                 }
             };
 
@@ -1160,17 +1413,15 @@ impl RouterTrait for VllmPDRouter {
                     json
                 }
                 Err(e) => {
-                    return (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    return error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Serialization error: {}", e),
                     )
-                        .into_response()
                 }
             };
 
             // Get prefill and decode workers from worker_registry
-            let prefill_workers = self.pd_router.worker_registry.get_prefill_workers();
-            let decode_workers = self.pd_router.worker_registry.get_decode_workers();
+            let (prefill_workers, decode_workers) = self.registry_workers();
 
             info!(
                 "Found {} prefill workers, {} decode workers from worker_registry",
@@ -1179,15 +1430,14 @@ impl RouterTrait for VllmPDRouter {
             );
 
             if prefill_workers.is_empty() || decode_workers.is_empty() {
-                return (
-                    axum::http::StatusCode::SERVICE_UNAVAILABLE,
+                return error_response(
+                    StatusCode::SERVICE_UNAVAILABLE,
                     format!(
                         "No workers available: {} prefill, {} decode",
                         prefill_workers.len(),
                         decode_workers.len()
                     ),
-                )
-                    .into_response();
+                );
             }
 
             // Select workers using policy
@@ -1197,25 +1447,17 @@ impl RouterTrait for VllmPDRouter {
             let prefill_policy = self.policy_registry.get_prefill_policy();
             let decode_policy = self.policy_registry.get_decode_policy();
 
-            let prefill_idx = match prefill_policy.select_worker(&prefill_workers, request_str) {
-                Some(idx) => idx,
-                None => {
-                    return (
-                        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-                        "Prefill policy failed to select a worker".to_string(),
-                    )
-                        .into_response();
-                }
-            };
-
-            let decode_idx = match decode_policy.select_worker(&decode_workers, request_str) {
-                Some(idx) => idx,
-                None => {
-                    return (
-                        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-                        "Decode policy failed to select a worker".to_string(),
-                    )
-                        .into_response();
+            let (prefill_idx, decode_idx) = match Self::select_worker_pair(
+                prefill_policy.as_ref(),
+                decode_policy.as_ref(),
+                &prefill_workers,
+                &decode_workers,
+                request_str,
+                self.zone_affinity,
+            ) {
+                Ok(pair) => pair,
+                Err(message) => {
+                    return error_response(StatusCode::SERVICE_UNAVAILABLE, message);
                 }
             };
 
@@ -1238,8 +1480,10 @@ impl RouterTrait for VllmPDRouter {
                     request_json,
                     prefill_worker.clone(),
                     decode_worker.clone(),
+                    &decode_workers,
                     "/v1/chat/completions",
                     headers,
+                    body.load_weight(),
                 )
                 .await
             {
@@ -1249,11 +1493,10 @@ impl RouterTrait for VllmPDRouter {
                 }
                 Err(e) => {
                     info!("Two-stage processing failed: {}", e);
-                    (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Request processing failed: {}", e),
                     )
-                        .into_response()
                 }
             };
             resp
@@ -1285,11 +1528,10 @@ impl RouterTrait for VllmPDRouter {
                     json
                 }
                 Err(e) => {
-                    return (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    return error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Serialization error: {}", e),
                     )
-                        .into_response()
                 }
             };
 
@@ -1310,17 +1552,15 @@ impl RouterTrait for VllmPDRouter {
                     json
                 }
                 Err(e) => {
-                    return (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    return error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Serialization error: {}", e),
                     )
-                        .into_response()
                 }
             };
 
             // Get prefill and decode workers from worker_registry
-            let prefill_workers = self.pd_router.worker_registry.get_prefill_workers();
-            let decode_workers = self.pd_router.worker_registry.get_decode_workers();
+            let (prefill_workers, decode_workers) = self.registry_workers();
 
             info!(
                 "Found {} prefill workers, {} decode workers from worker_registry",
@@ -1329,15 +1569,14 @@ impl RouterTrait for VllmPDRouter {
             );
 
             if prefill_workers.is_empty() || decode_workers.is_empty() {
-                return (
-                    axum::http::StatusCode::SERVICE_UNAVAILABLE,
+                return error_response(
+                    StatusCode::SERVICE_UNAVAILABLE,
                     format!(
                         "No workers available: {} prefill, {} decode",
                         prefill_workers.len(),
                         decode_workers.len()
                     ),
-                )
-                    .into_response();
+                );
             }
 
             // Select workers using policy
@@ -1347,25 +1586,17 @@ impl RouterTrait for VllmPDRouter {
             let prefill_policy = self.policy_registry.get_prefill_policy();
             let decode_policy = self.policy_registry.get_decode_policy();
 
-            let prefill_idx = match prefill_policy.select_worker(&prefill_workers, request_str) {
-                Some(idx) => idx,
-                None => {
-                    return (
-                        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-                        "Prefill policy failed to select a worker".to_string(),
-                    )
-                        .into_response();
-                }
-            };
-
-            let decode_idx = match decode_policy.select_worker(&decode_workers, request_str) {
-                Some(idx) => idx,
-                None => {
-                    return (
-                        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-                        "Decode policy failed to select a worker".to_string(),
-                    )
-                        .into_response();
+            let (prefill_idx, decode_idx) = match Self::select_worker_pair(
+                prefill_policy.as_ref(),
+                decode_policy.as_ref(),
+                &prefill_workers,
+                &decode_workers,
+                request_str,
+                self.zone_affinity,
+            ) {
+                Ok(pair) => pair,
+                Err(message) => {
+                    return error_response(StatusCode::SERVICE_UNAVAILABLE, message);
                 }
             };
 
@@ -1388,8 +1619,10 @@ impl RouterTrait for VllmPDRouter {
                     request_json,
                     prefill_worker.clone(),
                     decode_worker.clone(),
+                    &decode_workers,
                     "/v1/completions",
                     headers,
+                    body.load_weight(),
                 )
                 .await
             {
@@ -1399,11 +1632,10 @@ impl RouterTrait for VllmPDRouter {
                 }
                 Err(e) => {
                     info!("Two-stage processing failed: {}", e);
-                    (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
                         format!("Request processing failed: {}", e),
                     )
-                        .into_response()
                 }
             };
             resp
@@ -1462,7 +1694,33 @@ impl RouterTrait for VllmPDRouter {
     }
 
     fn readiness(&self) -> Response {
-        self.pd_router.readiness()
+        if self.use_discovery {
+            Self::discovery_readiness(&self.service_registry.health(), self.discovery_stale_secs)
+        } else {
+            self.pd_router.readiness()
+        }
+    }
+
+    fn get_worker_circuit(&self, worker_url: &str) -> Response {
+        self.pd_router.get_worker_circuit(worker_url)
+    }
+
+    fn reset_worker_circuit(&self, worker_url: &str) -> Response {
+        self.pd_router.reset_worker_circuit(worker_url)
+    }
+
+    fn set_worker_enabled(&self, worker_url: &str, enabled: bool) -> Response {
+        self.pd_router.set_worker_enabled(worker_url, enabled)
+    }
+
+    async fn route_raw(
+        &self,
+        headers: Option<&HeaderMap>,
+        path: &str,
+        method: &Method,
+        body: bytes::Bytes,
+    ) -> Response {
+        self.pd_router.route_raw(headers, path, method, body).await
     }
 
     /// Route a transparent proxy request through the P/D disaggregation pipeline
@@ -1472,15 +1730,14 @@ impl RouterTrait for VllmPDRouter {
         headers: Option<&HeaderMap>,
         path: &str,
         method: &Method,
-        body: serde_json::Value,
+        body: bytes::Bytes,
     ) -> Response {
         // Only handle POST requests for inference
         if *method != Method::POST {
-            return (
+            return error_response(
                 StatusCode::METHOD_NOT_ALLOWED,
                 "Only POST requests are supported for transparent proxy",
-            )
-                .into_response();
+            );
         }
 
         debug!(
@@ -1488,27 +1745,40 @@ impl RouterTrait for VllmPDRouter {
             method, path
         );
 
-        // Body is already a serde_json::Value, use it directly
-        let request_json = body;
+        // Only JSON bodies can go through the P/D pipeline; others skip prefill
+        if !headers.is_none_or(header_utils::is_json_content_type) {
+            return self.pd_router.route_raw(headers, path, method, body).await;
+        }
+        let request_json: Value = if body.is_empty() {
+            Value::Null
+        } else {
+            match serde_json::from_slice(&body) {
+                Ok(json) => json,
+                Err(e) => {
+                    return error_response(
+                        StatusCode::BAD_REQUEST,
+                        format!("Invalid JSON body: {}", e),
+                    )
+                }
+            }
+        };
 
         if self.use_discovery {
             // Discovery mode - use vLLM-specific two-stage processing
             self.process_vllm_request(request_json, path, headers).await
         } else {
             // Direct URL mode - use worker registry
-            let prefill_workers = self.pd_router.worker_registry.get_prefill_workers();
-            let decode_workers = self.pd_router.worker_registry.get_decode_workers();
+            let (prefill_workers, decode_workers) = self.registry_workers();
 
             if prefill_workers.is_empty() || decode_workers.is_empty() {
-                return (
+                return error_response(
                     StatusCode::SERVICE_UNAVAILABLE,
                     format!(
                         "No workers available: {} prefill, {} decode",
                         prefill_workers.len(),
                         decode_workers.len()
                     ),
-                )
-                    .into_response();
+                );
             }
 
             // Select workers using policy
@@ -1518,25 +1788,17 @@ impl RouterTrait for VllmPDRouter {
             let prefill_policy = self.policy_registry.get_prefill_policy();
             let decode_policy = self.policy_registry.get_decode_policy();
 
-            let prefill_idx = match prefill_policy.select_worker(&prefill_workers, request_str) {
-                Some(idx) => idx,
-                None => {
-                    return (
-                        StatusCode::SERVICE_UNAVAILABLE,
-                        "Prefill policy failed to select a worker".to_string(),
-                    )
-                        .into_response();
-                }
-            };
-
-            let decode_idx = match decode_policy.select_worker(&decode_workers, request_str) {
-                Some(idx) => idx,
-                None => {
-                    return (
-                        StatusCode::SERVICE_UNAVAILABLE,
-                        "Decode policy failed to select a worker".to_string(),
-                    )
-                        .into_response();
+            let (prefill_idx, decode_idx) = match Self::select_worker_pair(
+                prefill_policy.as_ref(),
+                decode_policy.as_ref(),
+                &prefill_workers,
+                &decode_workers,
+                request_str,
+                self.zone_affinity,
+            ) {
+                Ok(pair) => pair,
+                Err(message) => {
+                    return error_response(StatusCode::SERVICE_UNAVAILABLE, message);
                 }
             };
 
@@ -1555,17 +1817,18 @@ impl RouterTrait for VllmPDRouter {
                     request_json,
                     prefill_worker.clone(),
                     decode_worker.clone(),
+                    &decode_workers,
                     path,
                     headers,
+                    1,
                 )
                 .await
             {
                 Ok(response) => response,
-                Err(e) => (
+                Err(e) => error_response(
                     StatusCode::INTERNAL_SERVER_ERROR,
                     format!("Transparent proxy request failed: {}", e),
-                )
-                    .into_response(),
+                ),
             }
         }
     }
@@ -1586,3 +1849,224 @@ impl WorkerManagement for VllmPDRouter {
         self.pd_router.get_worker_urls()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policies::{RandomPolicy, RoundRobinPolicy};
+
+    fn workers(specs: &[(&str, Option<&str>)], worker_type: WorkerType) -> Vec<Arc<dyn Worker>> {
+        specs
+            .iter()
+            .map(|(url, zone)| {
+                let mut worker = BasicWorker::new(url.to_string(), worker_type.clone());
+                if let Some(zone) = zone {
+                    worker = worker
+                        .with_labels(HashMap::from([(ZONE_LABEL.to_string(), zone.to_string())]));
+                }
+                Arc::new(worker) as Arc<dyn Worker>
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_worker_pair_prefers_same_zone() {
+        let prefill = workers(&[("http://p1:8000", Some("rack-b"))], WorkerType::Regular);
+        let decode = workers(
+            &[
+                ("http://d1:8000", Some("rack-a")),
+                ("http://d2:8000", Some("rack-b")),
+                ("http://d3:8000", None),
+            ],
+            WorkerType::Regular,
+        );
+
+        for _ in 0..20 {
+            let pair = VllmPDRouter::select_worker_pair(
+                &RandomPolicy::new(),
+                &RandomPolicy::new(),
+                &prefill,
+                &decode,
+                None,
+                true,
+            );
+            assert_eq!(pair, Ok((0, 1)));
+        }
+    }
+
+    #[test]
+    fn test_select_worker_pair_falls_back_across_zones() {
+        let prefill = workers(&[("http://p1:8000", Some("rack-b"))], WorkerType::Regular);
+        let decode = workers(
+            &[
+                ("http://d1:8000", Some("rack-a")),
+                ("http://d2:8000", Some("rack-b")),
+            ],
+            WorkerType::Regular,
+        );
+        decode[1].set_healthy(false);
+
+        let pair = VllmPDRouter::select_worker_pair(
+            &RandomPolicy::new(),
+            &RandomPolicy::new(),
+            &prefill,
+            &decode,
+            None,
+            true,
+        );
+        assert_eq!(pair, Ok((0, 0)));
+
+        decode[0].set_healthy(false);
+        let pair = VllmPDRouter::select_worker_pair(
+            &RandomPolicy::new(),
+            &RandomPolicy::new(),
+            &prefill,
+            &decode,
+            None,
+            true,
+        );
+        assert_eq!(pair, Err("Decode policy failed to select a worker"));
+    }
+
+    #[test]
+    fn test_select_worker_pair_ignores_zones_when_disabled() {
+        let prefill = workers(&[("http://p1:8000", Some("rack-a"))], WorkerType::Regular);
+        let decode = workers(
+            &[
+                ("http://d1:8000", Some("rack-a")),
+                ("http://d2:8000", Some("rack-b")),
+            ],
+            WorkerType::Regular,
+        );
+        let decode_policy = RoundRobinPolicy::new();
+
+        let picks: Vec<usize> = (0..4)
+            .map(|_| {
+                VllmPDRouter::select_worker_pair(
+                    &RoundRobinPolicy::new(),
+                    &decode_policy,
+                    &prefill,
+                    &decode,
+                    None,
+                    false,
+                )
+                .unwrap()
+                .1
+            })
+            .collect();
+        assert!(picks.contains(&1), "picks: {:?}", picks);
+    }
+
+    #[test]
+    fn test_fallback_decode_worker_skips_tried_and_unavailable() {
+        let decode = workers(
+            &[
+                ("http://d1:8000", None),
+                ("http://d2:8000", None),
+                ("http://d3:8000", None),
+            ],
+            WorkerType::Regular,
+        );
+        decode[2].set_healthy(false);
+        let tried = vec!["http://d1:8000".to_string()];
+
+        for _ in 0..10 {
+            let worker = VllmPDRouter::select_fallback_decode_worker(
+                &RandomPolicy::new(),
+                &decode,
+                None,
+                &tried,
+                None,
+            )
+            .unwrap();
+            assert_eq!(worker.url(), "http://d2:8000");
+        }
+
+        let tried = vec!["http://d1:8000".to_string(), "http://d2:8000".to_string()];
+        assert!(VllmPDRouter::select_fallback_decode_worker(
+            &RandomPolicy::new(),
+            &decode,
+            None,
+            &tried,
+            None
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_fallback_decode_worker_prefers_zone() {
+        let decode = workers(
+            &[
+                ("http://d1:8000", Some("a")),
+                ("http://d2:8000", Some("b")),
+                ("http://d3:8000", Some("a")),
+                ("http://d4:8000", Some("b")),
+            ],
+            WorkerType::Regular,
+        );
+        let tried = vec!["http://d1:8000".to_string()];
+
+        for _ in 0..10 {
+            let worker = VllmPDRouter::select_fallback_decode_worker(
+                &RandomPolicy::new(),
+                &decode,
+                Some("a"),
+                &tried,
+                None,
+            )
+            .unwrap();
+            assert_eq!(worker.url(), "http://d3:8000");
+        }
+
+        // Other zones are used once the prefill worker's zone is exhausted
+        let tried = vec!["http://d1:8000".to_string(), "http://d3:8000".to_string()];
+        let worker = VllmPDRouter::select_fallback_decode_worker(
+            &RandomPolicy::new(),
+            &decode,
+            Some("a"),
+            &tried,
+            None,
+        )
+        .unwrap();
+        assert_eq!(worker.metadata().labels[ZONE_LABEL], "b");
+
+        // Only the narrowed list passed in is considered
+        let worker = VllmPDRouter::select_fallback_decode_worker(
+            &RandomPolicy::new(),
+            &decode[..2],
+            Some("a"),
+            &tried,
+            None,
+        )
+        .unwrap();
+        assert_eq!(worker.url(), "http://d2:8000");
+    }
+
+    #[test]
+    fn test_discovery_readiness() {
+        let mut health = DiscoveryHealth {
+            connected: true,
+            last_update: Some(1_700_000_000),
+            silent_secs: 2,
+            prefill_instances: 1,
+            decode_instances: 2,
+        };
+        let response = VllmPDRouter::discovery_readiness(&health, 30);
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Silent discovery keeps its instances but is no longer ready
+        health.silent_secs = 31;
+        let response = VllmPDRouter::discovery_readiness(&health, 30);
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        health.silent_secs = 2;
+        health.connected = false;
+        let response = VllmPDRouter::discovery_readiness(&health, 30);
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        health.connected = true;
+        health.decode_instances = 0;
+        let response = VllmPDRouter::discovery_readiness(&health, 30);
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
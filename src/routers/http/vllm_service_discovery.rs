@@ -3,14 +3,30 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::broadcast;
 use tracing::{debug, info, warn};
 
+use crate::metrics::RouterMetrics;
+
 /// Default ping timeout in seconds
 const DEFAULT_PING_SECONDS: u64 = 5;
 
+/// Delay before the first restart of a failed listener
+const RECONNECT_BACKOFF_INITIAL: Duration = Duration::from_secs(1);
+
+/// Longest delay between listener restarts
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
 /// Service type for registration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ServiceType {
@@ -34,21 +50,62 @@ pub struct ServiceRegistration {
     pub service_type: String, // "P" or "D"
     pub http_address: String,
     pub zmq_address: String,
+    /// Topology zone (e.g. rack or availability zone) the instance runs in
+    #[serde(default)]
+    pub zone: Option<String>,
 }
 
 /// Service instance with expiration timestamp
 #[derive(Debug, Clone)]
 pub struct ServiceInstance {
     pub zmq_address: String,
+    pub zone: Option<String>,
     pub expires_at: u64, // Unix timestamp
 }
 
+/// Why a listener session ended
+enum ListenerExit {
+    Shutdown,
+    /// Socket error; `bound` is whether the socket was bound before it failed
+    Failed {
+        bound: bool,
+        error: String,
+    },
+}
+
+/// Health of the discovery listener, as reported by readiness
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiscoveryHealth {
+    /// Whether the listener socket is currently bound
+    pub connected: bool,
+    /// Unix time of the last registration, None before the first one
+    pub last_update: Option<u64>,
+    /// Seconds since the last registration, or since the listener started
+    /// when none has arrived yet
+    pub silent_secs: u64,
+    pub prefill_instances: usize,
+    pub decode_instances: usize,
+}
+
+impl DiscoveryHealth {
+    /// Whether the listener is down or has been silent for more than `stale_secs`
+    pub fn is_stale(&self, stale_secs: u64) -> bool {
+        !self.connected || self.silent_secs > stale_secs
+    }
+}
+
 /// Service registry maintaining prefill and decode instances
 #[derive(Debug)]
 pub struct ServiceRegistry {
     prefill_instances: Arc<Mutex<HashMap<String, ServiceInstance>>>,
     decode_instances: Arc<Mutex<HashMap<String, ServiceInstance>>>,
     shutdown_tx: Option<broadcast::Sender<()>>,
+    /// Unix time of the last registration (0 = none yet)
+    last_update: Arc<AtomicU64>,
+    /// Unix time the listener was started
+    listening_since: AtomicU64,
+    /// Whether the listener socket is currently bound
+    connected: Arc<AtomicBool>,
 }
 
 impl Default for ServiceRegistry {
@@ -64,10 +121,17 @@ impl ServiceRegistry {
             prefill_instances: Arc::new(Mutex::new(HashMap::new())),
             decode_instances: Arc::new(Mutex::new(HashMap::new())),
             shutdown_tx: None,
+            last_update: Arc::new(AtomicU64::new(0)),
+            listening_since: AtomicU64::new(0),
+            connected: Arc::new(AtomicBool::new(false)),
         }
     }
 
     /// Start the ZMQ service discovery listener
+    ///
+    /// The listener runs until shutdown. When binding or receiving fails the
+    /// socket is recreated after an exponential backoff, so a transient
+    /// error does not leave the router with a frozen worker set.
     pub async fn start_listener(&mut self, bind_address: &str) -> Result<(), String> {
         info!(
             "Starting vLLM service discovery listener on {}",
@@ -76,80 +140,149 @@ impl ServiceRegistry {
 
         let (shutdown_tx, mut shutdown_rx) = broadcast::channel(1);
         self.shutdown_tx = Some(shutdown_tx);
+        self.listening_since.store(unix_now(), Ordering::Relaxed);
 
         let prefill_instances = Arc::clone(&self.prefill_instances);
         let decode_instances = Arc::clone(&self.decode_instances);
+        let last_update = Arc::clone(&self.last_update);
+        let connected = Arc::clone(&self.connected);
         let bind_addr = bind_address.to_string();
 
         tokio::spawn(async move {
-            // Initialize ZMQ context and socket
-            let context = zmq::Context::new();
-            let router_socket = context.socket(zmq::ROUTER).unwrap();
+            let mut backoff = RECONNECT_BACKOFF_INITIAL;
+            loop {
+                let exit = Self::run_listener(
+                    &bind_addr,
+                    &mut shutdown_rx,
+                    &prefill_instances,
+                    &decode_instances,
+                    &last_update,
+                    &connected,
+                )
+                .await;
+                connected.store(false, Ordering::Relaxed);
+
+                let (bound, error) = match exit {
+                    ListenerExit::Shutdown => {
+                        info!("Service discovery shutting down");
+                        break;
+                    }
+                    ListenerExit::Failed { bound, error } => (bound, error),
+                };
+                if bound {
+                    backoff = RECONNECT_BACKOFF_INITIAL;
+                }
+                warn!(
+                    "Service discovery listener on {} failed: {}; restarting in {:?}",
+                    bind_addr, error, backoff
+                );
+                RouterMetrics::record_discovery_reconnect();
 
-            if let Err(e) = router_socket.bind(&format!("tcp://{}", bind_addr)) {
-                warn!("Failed to bind ZMQ socket to {}: {}", bind_addr, e);
-                return;
+                tokio::select! {
+                    _ = shutdown_rx.recv() => {
+                        info!("Service discovery shutting down");
+                        break;
+                    }
+                    _ = tokio::time::sleep(backoff) => {}
+                }
+                backoff = (backoff * 2).min(RECONNECT_BACKOFF_MAX);
             }
+        });
 
-            info!("ZMQ service discovery bound to tcp://{}", bind_addr);
+        Ok(())
+    }
 
-            // Set non-blocking mode for graceful shutdown
-            router_socket.set_rcvtimeo(1000).unwrap(); // 1 second timeout
+    /// Bind a ZMQ socket and handle registrations until shutdown or a socket error
+    async fn run_listener(
+        bind_addr: &str,
+        shutdown_rx: &mut broadcast::Receiver<()>,
+        prefill_instances: &Arc<Mutex<HashMap<String, ServiceInstance>>>,
+        decode_instances: &Arc<Mutex<HashMap<String, ServiceInstance>>>,
+        last_update: &AtomicU64,
+        connected: &AtomicBool,
+    ) -> ListenerExit {
+        let failed = |bound: bool, error: zmq::Error| ListenerExit::Failed {
+            bound,
+            error: error.to_string(),
+        };
 
-            loop {
-                // Check for shutdown signal
-                if shutdown_rx.try_recv().is_ok() {
-                    info!("Service discovery shutting down");
-                    break;
-                }
+        // Initialize ZMQ context and socket
+        let context = zmq::Context::new();
+        let router_socket = match context.socket(zmq::ROUTER) {
+            Ok(socket) => socket,
+            Err(e) => return failed(false, e),
+        };
+        if let Err(e) = router_socket.bind(&format!("tcp://{}", bind_addr)) {
+            return failed(false, e);
+        }
+        // 1 second timeout for graceful shutdown
+        if let Err(e) = router_socket.set_rcvtimeo(1000) {
+            return failed(false, e);
+        }
 
-                // Try to receive a message
-                match router_socket.recv_multipart(zmq::DONTWAIT) {
-                    Ok(message_parts) => {
-                        if message_parts.len() >= 2 {
-                            let remote_address = message_parts[0].clone();
-                            let message_data = &message_parts[1];
-
-                            Self::handle_registration_message(
-                                message_data,
-                                &remote_address,
-                                &prefill_instances,
-                                &decode_instances,
-                            )
-                            .await;
+        info!("ZMQ service discovery bound to tcp://{}", bind_addr);
+        connected.store(true, Ordering::Relaxed);
+
+        loop {
+            // Check for shutdown signal
+            if shutdown_rx.try_recv().is_ok() {
+                return ListenerExit::Shutdown;
+            }
+
+            // Try to receive a message
+            match router_socket.recv_multipart(zmq::DONTWAIT) {
+                Ok(message_parts) => {
+                    if message_parts.len() >= 2 {
+                        let remote_address = message_parts[0].clone();
+                        let message_data = &message_parts[1];
+
+                        let registered = Self::handle_registration_message(
+                            message_data,
+                            &remote_address,
+                            prefill_instances,
+                            decode_instances,
+                        )
+                        .await;
+                        if registered {
+                            let now = unix_now();
+                            last_update.store(now, Ordering::Relaxed);
+                            RouterMetrics::set_discovery_last_update(now);
                         }
                     }
-                    Err(zmq::Error::EAGAIN) => {
-                        // No message available, continue
-                        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
-                    }
-                    Err(e) => {
-                        warn!("ZMQ receive error: {}", e);
-                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                    }
                 }
-
-                // Clean up expired instances periodically
-                Self::cleanup_expired_instances(&prefill_instances, &decode_instances).await;
+                Err(zmq::Error::EAGAIN) => {
+                    // No message available, continue
+                    tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+                }
+                Err(zmq::Error::EINTR) => {}
+                Err(e) => return failed(true, e),
             }
-        });
 
-        Ok(())
+            // Clean up expired instances periodically
+            Self::cleanup_expired_instances(
+                prefill_instances,
+                decode_instances,
+                last_update.load(Ordering::Relaxed),
+            )
+            .await;
+        }
     }
 
     /// Handle incoming service registration message
+    ///
+    /// Returns whether the message registered an instance.
     async fn handle_registration_message(
         message_data: &[u8],
         remote_address: &[u8],
         prefill_instances: &Arc<Mutex<HashMap<String, ServiceInstance>>>,
         decode_instances: &Arc<Mutex<HashMap<String, ServiceInstance>>>,
-    ) {
+    ) -> bool {
         // Parse MessagePack data
         let data: ServiceRegistration = match rmp_serde::from_slice(message_data) {
             Ok(data) => data,
             Err(e) => {
                 warn!("Failed to parse service registration: {}", e);
-                return;
+                return false;
             }
         };
 
@@ -160,6 +293,7 @@ impl ServiceRegistry {
 
         let instance = ServiceInstance {
             zmq_address: data.zmq_address.clone(),
+            zone: data.zone.clone(),
             expires_at: current_time + DEFAULT_PING_SECONDS,
         };
 
@@ -205,19 +339,27 @@ impl ServiceRegistry {
                     "Unknown service type '{}' from {}",
                     data.service_type, remote_addr_str
                 );
+                return false;
             }
         }
+        true
     }
 
     /// Clean up expired service instances
+    ///
+    /// Instances only expire while others keep registering. When every
+    /// registration stops at once, the discovery source is more likely down
+    /// than every worker, so the last known instances keep serving and
+    /// readiness reports discovery as stale instead.
     async fn cleanup_expired_instances(
         prefill_instances: &Arc<Mutex<HashMap<String, ServiceInstance>>>,
         decode_instances: &Arc<Mutex<HashMap<String, ServiceInstance>>>,
+        last_update: u64,
     ) {
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let current_time = unix_now();
+        if current_time >= last_update + DEFAULT_PING_SECONDS {
+            return;
+        }
 
         // Clean prefill instances
         {
@@ -256,6 +398,13 @@ impl ServiceRegistry {
                 }
             }
         }
+
+        let (prefill_count, decode_count) = (
+            prefill_instances.lock().unwrap().len(),
+            decode_instances.lock().unwrap().len(),
+        );
+        RouterMetrics::set_discovery_instances("prefill", prefill_count);
+        RouterMetrics::set_discovery_instances("decode", decode_count);
     }
 
     /// Register a service manually (fallback mode)
@@ -263,6 +412,7 @@ impl ServiceRegistry {
         &self,
         http_address: String,
         zmq_address: String,
+        zone: Option<String>,
         service_type: ServiceType,
     ) {
         let current_time = SystemTime::now()
@@ -272,6 +422,7 @@ impl ServiceRegistry {
 
         let instance = ServiceInstance {
             zmq_address: zmq_address.clone(),
+            zone,
             expires_at: current_time + DEFAULT_PING_SECONDS,
         };
 
@@ -308,6 +459,19 @@ impl ServiceRegistry {
             .map(|instance| instance.zmq_address.clone())
     }
 
+    /// Get the topology zone an instance registered with, if any
+    pub fn get_zone(&self, http_address: &str, service_type: ServiceType) -> Option<String> {
+        let instances = match service_type {
+            ServiceType::Prefill => &self.prefill_instances,
+            ServiceType::Decode => &self.decode_instances,
+        };
+
+        let guard = instances.lock().unwrap();
+        guard
+            .get(http_address)
+            .and_then(|instance| instance.zone.clone())
+    }
+
     /// Get all available prefill instances
     pub fn get_prefill_instances(&self) -> Vec<(String, String)> {
         let guard = self.prefill_instances.lock().unwrap();
@@ -333,6 +497,20 @@ impl ServiceRegistry {
         (prefill_count, decode_count)
     }
 
+    /// Listener health: connection state, time since the last registration and instance counts
+    pub fn health(&self) -> DiscoveryHealth {
+        let last_update = self.last_update.load(Ordering::Relaxed);
+        let since = last_update.max(self.listening_since.load(Ordering::Relaxed));
+        let (prefill_instances, decode_instances) = self.get_instance_counts();
+        DiscoveryHealth {
+            connected: self.connected.load(Ordering::Relaxed),
+            last_update: (last_update > 0).then_some(last_update),
+            silent_secs: unix_now().saturating_sub(since),
+            prefill_instances,
+            decode_instances,
+        }
+    }
+
     /// Shutdown the service discovery
     pub fn shutdown(&self) {
         if let Some(ref tx) = self.shutdown_tx {
@@ -346,3 +524,86 @@ impl Drop for ServiceRegistry {
         self.shutdown();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registration_zone_is_optional() {
+        let mut message = HashMap::new();
+        message.insert("type", "P");
+        message.insert("http_address", "10.0.0.1:8000");
+        message.insert("zmq_address", "10.0.0.1:21001");
+        let bytes = rmp_serde::to_vec_named(&message).unwrap();
+        let data: ServiceRegistration = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(data.zone, None);
+
+        message.insert("zone", "rack-a");
+        let bytes = rmp_serde::to_vec_named(&message).unwrap();
+        let data: ServiceRegistration = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(data.zone.as_deref(), Some("rack-a"));
+    }
+
+    #[test]
+    fn test_get_zone() {
+        let registry = ServiceRegistry::new();
+        registry.register_service(
+            "10.0.0.1:8000".to_string(),
+            "10.0.0.1:21001".to_string(),
+            Some("rack-a".to_string()),
+            ServiceType::Prefill,
+        );
+        registry.register_service(
+            "10.0.0.2:8000".to_string(),
+            "10.0.0.2:21001".to_string(),
+            None,
+            ServiceType::Decode,
+        );
+
+        assert_eq!(
+            registry.get_zone("10.0.0.1:8000", ServiceType::Prefill),
+            Some("rack-a".to_string())
+        );
+        assert_eq!(
+            registry.get_zone("10.0.0.1:8000", ServiceType::Decode),
+            None
+        );
+        assert_eq!(
+            registry.get_zone("10.0.0.2:8000", ServiceType::Decode),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_instances_kept_while_discovery_is_silent() {
+        let prefill = Arc::new(Mutex::new(HashMap::new()));
+        let decode = Arc::new(Mutex::new(HashMap::new()));
+        let expired = ServiceInstance {
+            zmq_address: "10.0.0.1:21001".to_string(),
+            zone: None,
+            expires_at: 0,
+        };
+        prefill
+            .lock()
+            .unwrap()
+            .insert("10.0.0.1:8000".to_string(), expired);
+
+        // Nothing registered recently: the source is down, keep the instance
+        ServiceRegistry::cleanup_expired_instances(&prefill, &decode, 0).await;
+        assert_eq!(prefill.lock().unwrap().len(), 1);
+
+        // Other instances still register: the silent one is gone
+        ServiceRegistry::cleanup_expired_instances(&prefill, &decode, unix_now()).await;
+        assert!(prefill.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_health_before_listener_starts() {
+        let registry = ServiceRegistry::new();
+        let health = registry.health();
+        assert!(!health.connected);
+        assert_eq!(health.last_update, None);
+        assert!(health.is_stale(30));
+    }
+}
@@ -0,0 +1,242 @@
+//! Idempotency keys for the stateful Responses API
+//!
+//! Generation requests are safe to retry, but a retried `POST /v1/responses`
+//! stores a second response. Clients that send an `Idempotency-Key` header
+//! get the first successful response replayed to every retry with the same
+//! key, credentials, and body until the key expires. A retry that arrives
+//! while the original request is still running is rejected with 409 rather
+//! than sent to a worker a second time.
+
+use crate::config::{IdempotencyConfig, ResponseCacheConfig};
+use crate::routers::error_response::error_response_with_code;
+use crate::routers::http::response_cache::{CacheKey, ResponseCache};
+use axum::{
+    http::{header::AUTHORIZATION, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// Request header carrying the client's idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set on replayed responses
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Longest accepted idempotency key
+const MAX_KEY_LEN: usize = 255;
+
+/// Body fields the router fills in per request when the client omits them
+const GENERATED_FIELDS: &[&str] = &["request_id"];
+
+/// Responses remembered per idempotency key
+#[derive(Debug)]
+pub struct IdempotencyCache {
+    responses: ResponseCache,
+    /// Keys whose original request has not finished yet
+    in_flight: Mutex<HashSet<CacheKey>>,
+}
+
+impl IdempotencyCache {
+    pub fn new(config: &IdempotencyConfig) -> Self {
+        Self {
+            responses: ResponseCache::new(&ResponseCacheConfig {
+                max_entries: config.max_keys,
+                ttl_secs: config.ttl_secs,
+            }),
+            in_flight: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Cache key of a request, or None when it carries no idempotency key
+    ///
+    /// Keys are scoped to the route and the caller's credentials, and the
+    /// body is part of the key, so reusing a key for a different request
+    /// never replays an unrelated response. Fields the router generates per
+    /// request are left out, or no retry would ever match.
+    #[allow(clippy::result_large_err)]
+    pub fn request_key<T: serde::Serialize>(
+        route: &str,
+        headers: &HeaderMap,
+        body: &T,
+    ) -> Result<Option<CacheKey>, Response> {
+        let Some(key) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+            return Ok(None);
+        };
+        let key = match key.to_str() {
+            Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LEN => key,
+            _ => {
+                return Err(error_response_with_code(
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "Idempotency-Key must be 1 to {} visible ASCII characters",
+                        MAX_KEY_LEN
+                    ),
+                    "invalid_idempotency_key",
+                ))
+            }
+        };
+        let authorization = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+        let mut body = serde_json::to_value(body).unwrap_or_default();
+        if let Some(fields) = body.as_object_mut() {
+            for field in GENERATED_FIELDS {
+                fields.remove(*field);
+            }
+        }
+        Ok(ResponseCache::request_key(
+            route,
+            Some(key),
+            &(authorization, body),
+        ))
+    }
+
+    /// Start handling a request with an idempotency key
+    ///
+    /// Returns the replayed response when the key has already succeeded, or
+    /// a 409 while another request with the key is running. Otherwise the
+    /// returned claim must be finished with the upstream response.
+    #[allow(clippy::result_large_err)]
+    pub fn begin(self: &Arc<Self>, key: CacheKey) -> Result<IdempotencyClaim, Response> {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if let Some(cached) = self.responses.get(&key) {
+            let mut response = cached.into_response();
+            response
+                .headers_mut()
+                .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+            return Err(response);
+        }
+        if !in_flight.insert(key) {
+            return Err(error_response_with_code(
+                StatusCode::CONFLICT,
+                "A request with this Idempotency-Key is already in progress",
+                "idempotency_key_in_use",
+            ));
+        }
+        Ok(IdempotencyClaim {
+            cache: Arc::clone(self),
+            key,
+        })
+    }
+}
+
+/// A request holding an idempotency key until its response is known
+///
+/// Dropping the claim without finishing it releases the key, so the client
+/// can retry.
+#[derive(Debug)]
+pub struct IdempotencyClaim {
+    cache: Arc<IdempotencyCache>,
+    key: CacheKey,
+}
+
+impl IdempotencyClaim {
+    /// Remember a successful response for later retries and return it
+    ///
+    /// Failed responses are not kept; retrying them reaches a worker again.
+    pub async fn finish(self, response: Response) -> Response {
+        if response.status().is_success() {
+            self.cache.responses.store(self.key, response).await
+        } else {
+            response
+        }
+    }
+}
+
+impl Drop for IdempotencyClaim {
+    fn drop(&mut self) {
+        self.cache.in_flight.lock().unwrap().remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use serde_json::json;
+
+    fn cache() -> Arc<IdempotencyCache> {
+        Arc::new(IdempotencyCache::new(&IdempotencyConfig::default()))
+    }
+
+    fn headers(key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_str(key).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_request_key_scoping() {
+        let body = json!({"input": "hi"});
+        let route = "/v1/responses";
+
+        assert_eq!(
+            IdempotencyCache::request_key(route, &HeaderMap::new(), &body).unwrap(),
+            None
+        );
+        let key = IdempotencyCache::request_key(route, &headers("abc"), &body).unwrap();
+        assert!(key.is_some());
+        assert_eq!(
+            key,
+            IdempotencyCache::request_key(route, &headers("abc"), &body).unwrap()
+        );
+
+        // Generated fields do not count, a different body or caller does
+        assert_eq!(
+            key,
+            IdempotencyCache::request_key(
+                route,
+                &headers("abc"),
+                &json!({"input": "hi", "request_id": "resp-123"})
+            )
+            .unwrap()
+        );
+        let other_body = json!({"input": "bye"});
+        assert_ne!(
+            key,
+            IdempotencyCache::request_key(route, &headers("abc"), &other_body).unwrap()
+        );
+        let mut other_caller = headers("abc");
+        other_caller.insert(AUTHORIZATION, HeaderValue::from_static("Bearer other"));
+        assert_ne!(
+            key,
+            IdempotencyCache::request_key(route, &other_caller, &body).unwrap()
+        );
+
+        let response = IdempotencyCache::request_key(route, &headers(""), &body).unwrap_err();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_successful_response_is_replayed() {
+        let cache = cache();
+        let key = [1u8; 32];
+
+        let claim = cache.begin(key).unwrap();
+        // A concurrent retry is rejected while the original is running
+        let conflict = cache.begin(key).unwrap_err();
+        assert_eq!(conflict.status(), StatusCode::CONFLICT);
+
+        let response = claim.finish(Response::new(Body::from("stored"))).await;
+        assert!(response.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+
+        let replayed = cache.begin(key).unwrap_err();
+        assert_eq!(replayed.status(), StatusCode::OK);
+        assert_eq!(replayed.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        let body = to_bytes(replayed.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "stored");
+    }
+
+    #[tokio::test]
+    async fn test_failed_response_releases_key() {
+        let cache = cache();
+        let key = [2u8; 32];
+
+        let claim = cache.begin(key).unwrap();
+        let failed = (StatusCode::SERVICE_UNAVAILABLE, "busy").into_response();
+        claim.finish(failed).await;
+
+        assert!(cache.begin(key).is_ok());
+    }
+}
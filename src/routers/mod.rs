@@ -6,18 +6,31 @@ use axum::{
     extract::Request,
     http::{HeaderMap, Method, StatusCode},
     response::{IntoResponse, Response},
+    Json,
 };
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::time::Duration;
 
+use crate::core::{CircuitState, WorkerRegistry, WorkerType};
 use crate::protocols::spec::{
     ChatCompletionRequest, CompletionRequest, EmbeddingRequest, GenerateRequest, RerankRequest,
-    ResponsesRequest,
+    ResponseInputItemsParams, ResponsesRequest,
 };
+use crate::protocols::worker_spec::{
+    CircuitBreakerSnapshot, WorkerApiResponse, WorkerErrorResponse, WorkerSnapshot,
+    WorkerSnapshotResponse,
+};
+use error_response::error_response;
 
+pub mod batches;
+pub mod body_logging;
+pub mod error_response;
 pub mod factory;
 pub mod grpc;
 pub mod header_utils;
 pub mod http;
+pub mod idempotency;
 pub mod router_manager;
 
 pub use factory::RouterFactory;
@@ -33,13 +46,201 @@ pub trait WorkerManagement: Send + Sync {
     /// Add a worker to the router
     async fn add_worker(&self, worker_url: &str) -> Result<String, String>;
 
+    /// Add a worker carrying the given tags
+    ///
+    /// Routers that don't store tags only accept an empty set.
+    async fn add_worker_with_tags(
+        &self,
+        worker_url: &str,
+        tags: HashMap<String, String>,
+    ) -> Result<String, String> {
+        if !tags.is_empty() {
+            return Err(format!(
+                "Worker tags are not supported by this router (worker {})",
+                worker_url
+            ));
+        }
+        self.add_worker(worker_url).await
+    }
+
     /// Remove a worker from the router
     fn remove_worker(&self, worker_url: &str);
 
+    /// Gracefully remove a worker: stop selecting it for new requests, wait for its
+    /// in-flight requests to finish (or `timeout` to elapse), then remove it
+    async fn drain_worker(&self, worker_url: &str, _timeout: Duration) -> Result<String, String> {
+        Err(format!(
+            "Draining worker {} is not supported by this router",
+            worker_url
+        ))
+    }
+
     /// Get all worker URLs
     fn get_worker_urls(&self) -> Vec<String>;
 }
 
+/// Result message for a completed drain, given the requests still in flight
+pub(crate) fn drain_result_message(worker_url: &str, in_flight: usize) -> String {
+    if in_flight == 0 {
+        format!("Worker {} drained and removed", worker_url)
+    } else {
+        tracing::warn!(
+            "Drain timeout for worker {}, removed with {} in-flight requests",
+            worker_url,
+            in_flight
+        );
+        format!(
+            "Worker {} removed after drain timeout with {} in-flight requests",
+            worker_url, in_flight
+        )
+    }
+}
+
+/// Readiness response for a model: ready if it has at least `min_healthy_workers`
+/// healthy workers (and always at least one)
+pub(crate) fn model_readiness_response(
+    worker_registry: &WorkerRegistry,
+    model_id: &str,
+    min_healthy_workers: usize,
+) -> Response {
+    let workers = worker_registry.get_by_model_fast(model_id);
+    let healthy_count = workers.iter().filter(|w| w.is_healthy()).count();
+    let total_workers = workers.len();
+
+    if healthy_count >= min_healthy_workers.max(1) {
+        Json(serde_json::json!({
+            "status": "ready",
+            "model_id": model_id,
+            "healthy_workers": healthy_count,
+            "total_workers": total_workers
+        }))
+        .into_response()
+    } else {
+        let reason = if healthy_count == 0 {
+            "no healthy workers available for model".to_string()
+        } else {
+            format!(
+                "{} healthy workers for model, at least {} required",
+                healthy_count, min_healthy_workers
+            )
+        };
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({
+                "status": "not_ready",
+                "model_id": model_id,
+                "reason": reason,
+                "healthy_workers": healthy_count,
+                "total_workers": total_workers
+            })),
+        )
+            .into_response()
+    }
+}
+
+/// Snapshot of every worker in the registry, without contacting the workers
+pub(crate) fn worker_stats_response(worker_registry: &WorkerRegistry) -> Response {
+    let workers: Vec<WorkerSnapshot> = worker_registry
+        .get_all()
+        .iter()
+        .map(|worker| WorkerSnapshot {
+            url: worker.url().to_string(),
+            model_id: worker.model_id().to_string(),
+            worker_type: match worker.worker_type() {
+                WorkerType::Regular => "regular".to_string(),
+                WorkerType::Prefill { .. } => "prefill".to_string(),
+                WorkerType::Decode => "decode".to_string(),
+            },
+            is_healthy: worker.is_healthy(),
+            is_draining: worker.is_draining(),
+            is_warming: worker.is_warming(),
+            is_enabled: worker.is_enabled(),
+            is_ejected: worker.is_ejected(),
+            model_version: worker.model_version(),
+            load: worker.load(),
+            processed_requests: worker.processed_requests(),
+            circuit_breaker_state: match worker.circuit_breaker().state() {
+                CircuitState::Closed => "closed".to_string(),
+                CircuitState::Open => "open".to_string(),
+                CircuitState::HalfOpen => "half_open".to_string(),
+            },
+            priority: worker.priority(),
+            cost: worker.cost(),
+        })
+        .collect();
+
+    Json(WorkerSnapshotResponse {
+        total: workers.len(),
+        workers,
+    })
+    .into_response()
+}
+
+/// Build the circuit breaker view of a worker, optionally resetting it first
+pub(crate) fn worker_circuit_response(
+    worker_registry: &WorkerRegistry,
+    worker_url: &str,
+    reset: bool,
+) -> Response {
+    let Some(worker) = worker_registry.get_by_url(worker_url) else {
+        let error = WorkerErrorResponse {
+            error: format!("Worker {worker_url} not found"),
+            code: "WORKER_NOT_FOUND".to_string(),
+        };
+        return (StatusCode::NOT_FOUND, Json(error)).into_response();
+    };
+
+    if reset {
+        let previous = worker.circuit_breaker().state();
+        worker.reset_circuit_breaker();
+        tracing::info!(
+            "Circuit breaker for worker {} manually reset (was {})",
+            worker_url,
+            previous.as_str()
+        );
+    }
+
+    let circuit_breaker = worker.circuit_breaker();
+    let stats = circuit_breaker.stats();
+    Json(CircuitBreakerSnapshot {
+        url: worker.url().to_string(),
+        state: stats.state.as_str().to_string(),
+        consecutive_failures: stats.consecutive_failures,
+        consecutive_successes: stats.consecutive_successes,
+        total_failures: stats.total_failures,
+        total_successes: stats.total_successes,
+        half_open_in_flight: circuit_breaker.half_open_in_flight(),
+        secs_since_last_failure: stats.time_since_last_failure.map(|d| d.as_secs_f64()),
+        secs_since_last_state_change: stats.time_since_last_state_change.as_secs_f64(),
+    })
+    .into_response()
+}
+
+/// Enable or disable routing to a worker, keeping it registered
+pub(crate) fn worker_enabled_response(
+    worker_registry: &WorkerRegistry,
+    worker_url: &str,
+    enabled: bool,
+) -> Response {
+    let Some(worker) = worker_registry.get_by_url(worker_url) else {
+        let error = WorkerErrorResponse {
+            error: format!("Worker {worker_url} not found"),
+            code: "WORKER_NOT_FOUND".to_string(),
+        };
+        return (StatusCode::NOT_FOUND, Json(error)).into_response();
+    };
+
+    worker.set_enabled(enabled);
+    let state = if enabled { "enabled" } else { "disabled" };
+    tracing::info!("Worker {} {}", worker_url, state);
+    Json(WorkerApiResponse {
+        success: true,
+        message: format!("Worker {worker_url} {state}"),
+        worker: None,
+    })
+    .into_response()
+}
+
 /// Core trait for all router implementations
 ///
 /// This trait provides a unified interface for routing requests,
@@ -104,11 +305,10 @@ pub trait RouterTrait: Send + Sync + Debug + WorkerManagement {
 
     /// Delete a response by id
     async fn delete_response(&self, _headers: Option<&HeaderMap>, _response_id: &str) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Responses delete endpoint not implemented",
         )
-            .into_response()
     }
 
     /// List input items of a response by id
@@ -116,12 +316,12 @@ pub trait RouterTrait: Send + Sync + Debug + WorkerManagement {
         &self,
         _headers: Option<&HeaderMap>,
         _response_id: &str,
+        _params: &ResponseInputItemsParams,
     ) -> Response {
-        (
+        error_response(
             StatusCode::NOT_IMPLEMENTED,
             "Responses list input items endpoint not implemented",
         )
-            .into_response()
     }
 
     /// Route embedding requests (OpenAI-compatible /v1/embeddings)
@@ -145,6 +345,37 @@ pub trait RouterTrait: Send + Sync + Debug + WorkerManagement {
     /// Get worker loads (for monitoring)
     async fn get_worker_loads(&self) -> Response;
 
+    /// Get the router's in-memory view of each worker (for monitoring)
+    ///
+    /// Unlike `get_worker_loads`, this never contacts the workers.
+    fn get_worker_stats(&self) -> Response {
+        error_response(StatusCode::NOT_IMPLEMENTED, "Worker stats not supported")
+    }
+
+    /// Get a worker's circuit breaker state and counters
+    fn get_worker_circuit(&self, _worker_url: &str) -> Response {
+        error_response(
+            StatusCode::NOT_IMPLEMENTED,
+            "Circuit breaker inspection not supported",
+        )
+    }
+
+    /// Force a worker's circuit breaker closed and clear its failure counts
+    fn reset_worker_circuit(&self, _worker_url: &str) -> Response {
+        error_response(
+            StatusCode::NOT_IMPLEMENTED,
+            "Circuit breaker reset not supported",
+        )
+    }
+
+    /// Include or exclude a worker from selection without removing it
+    fn set_worker_enabled(&self, _worker_url: &str, _enabled: bool) -> Response {
+        error_response(
+            StatusCode::NOT_IMPLEMENTED,
+            "Disabling workers not supported",
+        )
+    }
+
     /// Get router type name
     fn router_type(&self) -> &'static str;
 
@@ -162,17 +393,43 @@ pub trait RouterTrait: Send + Sync + Debug + WorkerManagement {
     /// Server readiness check - is the server ready to handle requests
     fn readiness(&self) -> Response;
 
+    /// Readiness check scoped to a single model
+    ///
+    /// Routers that don't track workers per model fall back to aggregate readiness.
+    fn model_readiness(&self, _model_id: &str) -> Response {
+        self.readiness()
+    }
+
     /// Route a transparent proxy request (any path/body)
     /// Used for catch-all routing of unmatched paths
+    /// The body is passed as received and may be empty or of any content type
     /// Returns the response from the backend or an error response
     async fn route_transparent(
         &self,
         _headers: Option<&HeaderMap>,
         _path: &str,
         _method: &Method,
-        _body: serde_json::Value,
+        _body: bytes::Bytes,
     ) -> Response {
         // Default: not supported - return 404
-        (StatusCode::NOT_FOUND, "Not Found").into_response()
+        error_response(StatusCode::NOT_FOUND, "Not Found")
+    }
+
+    /// Route a request whose body is forwarded without parsing it
+    ///
+    /// Used for bodies that are not JSON, such as the multipart/form-data
+    /// uploads of `/v1/audio/transcriptions`. The client's Content-Type,
+    /// including any multipart boundary, is sent to the worker unchanged.
+    async fn route_raw(
+        &self,
+        _headers: Option<&HeaderMap>,
+        _path: &str,
+        _method: &Method,
+        _body: bytes::Bytes,
+    ) -> Response {
+        error_response(
+            StatusCode::NOT_IMPLEMENTED,
+            "Routing non-JSON request bodies is not supported",
+        )
     }
 }
@@ -5,16 +5,22 @@
 //! - Multi-Router Mode (enable_igw=true): RouterManager coordinates everything
 
 use crate::config::RouterConfig;
-use crate::core::{CircuitBreakerConfig, Worker, WorkerFactory, WorkerRegistry, WorkerType};
+use crate::core::{
+    BasicWorker, CircuitBreakerConfig, ConnectionMode, Worker, WorkerFactory, WorkerFilter,
+    WorkerRegistry, WorkerType,
+};
 use crate::protocols::spec::{
     ChatCompletionRequest, CompletionRequest, EmbeddingRequest, GenerateRequest, RerankRequest,
-    ResponsesRequest,
+    ResponseInputItemsParams, ResponsesRequest,
 };
 use crate::protocols::worker_spec::{
     ServerInfo, WorkerApiResponse, WorkerConfigRequest, WorkerErrorResponse, WorkerInfo,
     WorkerListResponse, WorkerStats, WorkerTypeStats,
 };
-use crate::routers::{RouterTrait, WorkerManagement};
+use crate::routers::{
+    drain_result_message, model_readiness_response, worker_circuit_response,
+    worker_enabled_response, worker_stats_response, RouterTrait, WorkerManagement,
+};
 use async_trait::async_trait;
 use axum::{
     body::Body,
@@ -24,6 +30,7 @@ use axum::{
 };
 use dashmap::DashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{info, warn};
 
 /// Router identifier
@@ -111,18 +118,27 @@ impl RouterManager {
         let workers = self.worker_registry.get_by_model(model_id);
 
         if !workers.is_empty() {
+            // Requests reach the manager over HTTP, so HTTP workers win when a
+            // model is served both ways; gRPC routers only get all-gRPC models
+            let (mode, protocol) = if workers.iter().any(|w| w.speaks(&ConnectionMode::Http)) {
+                (ConnectionMode::Http, "http")
+            } else {
+                (ConnectionMode::Grpc { port: None }, "grpc")
+            };
+
             // Determine router based on worker types
             let has_pd_workers = workers.iter().any(|w| {
-                matches!(
-                    w.worker_type(),
-                    WorkerType::Prefill { .. } | WorkerType::Decode
-                )
+                w.speaks(&mode)
+                    && matches!(
+                        w.worker_type(),
+                        WorkerType::Prefill { .. } | WorkerType::Decode
+                    )
             });
 
             let router_id = if has_pd_workers {
-                RouterId::new("http-pd".to_string())
+                RouterId::new(format!("{}-pd", protocol))
             } else {
-                RouterId::new("http-regular".to_string())
+                RouterId::new(format!("{}-regular", protocol))
             };
 
             // Return the router if it exists
@@ -199,23 +215,42 @@ impl RouterManager {
             labels.insert("chat_template".to_string(), chat_template);
         }
 
-        let worker = match config.worker_type.as_deref() {
-            Some("prefill") => WorkerFactory::create_prefill_with_labels(
-                config.url.clone(),
-                config.bootstrap_port,
-                labels.clone(),
-                CircuitBreakerConfig::default(),
-            ),
-            Some("decode") => WorkerFactory::create_decode_with_labels(
-                config.url.clone(),
-                labels.clone(),
-                CircuitBreakerConfig::default(),
-            ),
-            _ => WorkerFactory::create_regular_with_labels(
-                config.url.clone(),
-                labels.clone(),
-                CircuitBreakerConfig::default(),
-            ),
+        let worker_type = match config.worker_type.as_deref() {
+            Some("prefill") => WorkerType::Prefill {
+                bootstrap_port: config.bootstrap_port,
+            },
+            Some("decode") => WorkerType::Decode,
+            _ => WorkerType::Regular,
+        };
+
+        let worker = if config.url.starts_with("grpc://") {
+            Box::new(
+                BasicWorker::with_connection_mode(
+                    config.url.clone(),
+                    worker_type,
+                    ConnectionMode::Grpc { port: None },
+                )
+                .with_labels(labels.clone()),
+            )
+        } else {
+            match config.worker_type.as_deref() {
+                Some("prefill") => WorkerFactory::create_prefill_with_labels(
+                    config.url.clone(),
+                    config.bootstrap_port,
+                    labels.clone(),
+                    CircuitBreakerConfig::default(),
+                ),
+                Some("decode") => WorkerFactory::create_decode_with_labels(
+                    config.url.clone(),
+                    labels.clone(),
+                    CircuitBreakerConfig::default(),
+                ),
+                _ => WorkerFactory::create_regular_with_labels(
+                    config.url.clone(),
+                    labels.clone(),
+                    CircuitBreakerConfig::default(),
+                ),
+            }
         };
 
         // Register worker
@@ -228,9 +263,14 @@ impl RouterManager {
         let policy = self.policy_registry.on_worker_added(&model_id, policy_hint);
 
         // Log which type of router would handle this worker (for debugging)
+        let protocol = if worker_arc.speaks(&ConnectionMode::Http) {
+            "http"
+        } else {
+            "grpc"
+        };
         let expected_router = match config.worker_type.as_deref() {
-            Some("prefill") | Some("decode") => "http-pd",
-            _ => "http-regular",
+            Some("prefill") | Some("decode") => format!("{}-pd", protocol),
+            _ => format!("{}-regular", protocol),
         };
 
         info!(
@@ -290,16 +330,24 @@ impl RouterManager {
         }
     }
 
-    /// List all workers
-    pub fn list_workers(&self) -> WorkerListResponse {
-        let workers = self.worker_registry.get_all_with_ids();
+    /// List workers matching `filter`, skipping `offset` and returning at most `limit`
+    ///
+    /// `total` counts every match, so clients can page through the rest.
+    pub fn list_workers(
+        &self,
+        filter: &WorkerFilter,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> WorkerListResponse {
+        let workers = self.worker_registry.get_filtered_with_ids(filter);
+        let total = workers.len();
         let worker_infos: Vec<WorkerInfo> = workers
             .iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
             .map(|(id, w)| self.worker_to_info(id.as_str(), w))
             .collect();
 
-        let total = worker_infos.len();
-
         // Get stats from the worker registry
         let registry_stats = self.worker_registry.stats();
 
@@ -365,6 +413,9 @@ impl RouterManager {
                 WorkerType::Decode => "decode".to_string(),
             },
             is_healthy: worker.is_healthy(),
+            is_enabled: worker.is_enabled(),
+            is_warming: worker.is_warming(),
+            model_version: worker.model_version(),
             load: worker.load(),
             connection_mode: format!("{:?}", worker.connection_mode()),
             tokenizer_path: worker.tokenizer_path().map(|s| s.to_string()),
@@ -485,6 +536,21 @@ impl WorkerManagement for RouterManager {
         let _ = self.remove_worker_from_registry(worker_url);
     }
 
+    /// Drain a worker shared by all routers, then remove it from the registry
+    async fn drain_worker(&self, worker_url: &str, timeout: Duration) -> Result<String, String> {
+        let worker = self
+            .worker_registry
+            .get_by_url(worker_url)
+            .ok_or_else(|| format!("Worker {} not found", worker_url))?;
+
+        info!("Draining worker {} (timeout {:?})", worker_url, timeout);
+        let in_flight = crate::core::drain_workers(std::slice::from_ref(&worker), timeout).await;
+        self.remove_worker_from_registry(worker_url)
+            .map_err(|e| e.error)?;
+
+        Ok(drain_result_message(worker_url, in_flight))
+    }
+
     /// Get all worker URLs from the registry
     fn get_worker_urls(&self) -> Vec<String> {
         self.worker_registry.get_all_urls()
@@ -658,6 +724,7 @@ impl RouterTrait for RouterManager {
         &self,
         _headers: Option<&HeaderMap>,
         _response_id: &str,
+        _params: &ResponseInputItemsParams,
     ) -> Response {
         (
             StatusCode::NOT_IMPLEMENTED,
@@ -780,6 +847,26 @@ impl RouterTrait for RouterManager {
         "manager"
     }
 
+    fn get_worker_stats(&self) -> Response {
+        worker_stats_response(&self.worker_registry)
+    }
+
+    fn get_worker_circuit(&self, worker_url: &str) -> Response {
+        worker_circuit_response(&self.worker_registry, worker_url, false)
+    }
+
+    fn reset_worker_circuit(&self, worker_url: &str) -> Response {
+        worker_circuit_response(&self.worker_registry, worker_url, true)
+    }
+
+    fn set_worker_enabled(&self, worker_url: &str, enabled: bool) -> Response {
+        worker_enabled_response(&self.worker_registry, worker_url, enabled)
+    }
+
+    fn model_readiness(&self, model_id: &str) -> Response {
+        model_readiness_response(&self.worker_registry, model_id, 1)
+    }
+
     /// Server readiness check - check if any router is ready
     fn readiness(&self) -> Response {
         if self.routers.is_empty() {
@@ -796,7 +883,7 @@ impl RouterTrait for RouterManager {
         headers: Option<&HeaderMap>,
         path: &str,
         method: &Method,
-        body: serde_json::Value,
+        body: bytes::Bytes,
     ) -> Response {
         // Select router based on headers (no model info available for transparent proxy)
         let router = self.select_router_for_request(headers, None);
@@ -812,6 +899,27 @@ impl RouterTrait for RouterManager {
                 .into_response()
         }
     }
+
+    async fn route_raw(
+        &self,
+        headers: Option<&HeaderMap>,
+        path: &str,
+        method: &Method,
+        body: bytes::Bytes,
+    ) -> Response {
+        // Multipart bodies are not parsed, so there is no model to route by
+        let router = self.select_router_for_request(headers, None);
+
+        if let Some(router) = router {
+            router.route_raw(headers, path, method, body).await
+        } else {
+            (
+                StatusCode::NOT_FOUND,
+                "No router available for this request",
+            )
+                .into_response()
+        }
+    }
 }
 
 // Note: get_first_available_router removed - we now properly handle
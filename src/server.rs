@@ -1,56 +1,95 @@
 use crate::{
-    config::{ConnectionMode, HistoryBackend, RouterConfig},
-    core::{WorkerRegistry, WorkerType},
-    data_connector::{MemoryResponseStorage, NoOpResponseStorage, SharedResponseStorage},
-    logging::{self, LoggingConfig},
-    metrics::{self, PrometheusConfig},
-    middleware::{self, QueuedRequest, TokenBucket},
-    policies::PolicyRegistry,
+    config::{ConnectionMode, HistoryBackend, RouterConfig, RoutingMode},
+    core::{
+        model_version, outlier_detection, upstream_tls, AdaptiveConcurrencyLimiter, WorkerFilter,
+        WorkerRegistry, WorkerType,
+    },
+    data_connector::{
+        MemoryResponseStorage, NoOpResponseStorage, RedisResponseStorage, SharedResponseStorage,
+    },
+    logging::{self, LogLevelHandle, LoggingConfig},
+    metrics::{self, PrometheusConfig, RouterMetrics},
+    middleware::{self, InFlightRequests, QueuedRequest, TokenBucket},
+    policies::{PolicyFactory, PolicyRegistry},
     protocols::{
         spec::{
-            ChatCompletionRequest, CompletionRequest, EmbeddingRequest, GenerateRequest,
-            RerankRequest, ResponsesRequest, V1RerankReqInput,
+            BatchCreateRequest, ChatCompletionRequest, CompletionRequest, EmbeddingRequest,
+            GenerateRequest, GenerationRequest, RerankRequest, ResponseInputItemsParams,
+            ResponsesRequest, V1RerankReqInput,
+        },
+        validation::ValidationError,
+        worker_spec::{
+            BulkWorkerResponse, BulkWorkerResult, WorkerApiResponse, WorkerConfigRequest,
+            WorkerErrorResponse,
         },
-        worker_spec::{WorkerApiResponse, WorkerConfigRequest, WorkerErrorResponse},
     },
     routers::{
+        batches::{self, BatchContext, BatchDispatch, BatchItem, BatchJobs},
+        error_response::{error_body, error_response, error_response_with_code},
+        http::stream_model,
+        idempotency::{IdempotencyCache, IdempotencyClaim, IDEMPOTENT_REPLAYED_HEADER},
         router_manager::{RouterId, RouterManager},
         RouterFactory, RouterTrait,
     },
     service_discovery::{start_service_discovery, ServiceDiscoveryConfig},
     tokenizer::{factory as tokenizer_factory, traits::Tokenizer},
 };
+use async_trait::async_trait;
 use axum::{
-    extract::{Path, Query, Request, State},
+    body::Body,
+    extract::{DefaultBodyLimit, Path, Query, Request, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     routing::{delete, get, post},
     serve, Json, Router,
 };
+use futures_util::StreamExt;
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::json;
 use std::{
     collections::HashMap,
+    future::IntoFuture,
     sync::atomic::{AtomicBool, Ordering},
     sync::Arc,
     time::Duration,
 };
-use tokio::{net::TcpListener, signal, spawn, sync::RwLock};
-use tracing::{error, info, warn, Level};
+use tokio::{
+    net::TcpListener,
+    signal, spawn,
+    sync::{OwnedSemaphorePermit, RwLock, Semaphore},
+};
+use tower_http::compression::CompressionLayer;
+use tracing::{debug, error, info, warn, Level};
 
 #[derive(Clone)]
 pub struct AppContext {
     pub client: Client,
     pub router_config: RouterConfig,
     pub rate_limiter: Arc<TokenBucket>,
+    /// Latency-driven limit applied ahead of `rate_limiter` (None = fixed limit)
+    pub adaptive_limiter: Option<Arc<AdaptiveConcurrencyLimiter>>,
+    /// Concurrency limiters for models with a dedicated limit in `model_concurrency_limits`
+    pub model_rate_limiters: Arc<HashMap<String, Arc<TokenBucket>>>,
     pub tokenizer: Option<Arc<dyn Tokenizer>>,
     pub worker_registry: Arc<WorkerRegistry>,
     pub policy_registry: Arc<PolicyRegistry>,
     pub router_manager: Option<Arc<RouterManager>>,
     pub response_storage: SharedResponseStorage,
+    /// Batches and their results, apart from stored responses (None with no history backend)
+    pub batch_storage: Option<SharedResponseStorage>,
+    /// Background batch jobs, cancelled when the shutdown drain runs out
+    pub batch_jobs: Arc<BatchJobs>,
     pub api_key_cache: Arc<RwLock<HashMap<String, bool>>>,
     pub api_key_validation_urls: Arc<Vec<String>>,
+    /// Runtime log filter control (None when this process did not install the subscriber)
+    pub log_level: Option<LogLevelHandle>,
+    /// Slots for concurrent streaming responses (None = unlimited)
+    pub stream_limiter: Option<Arc<Semaphore>>,
+    /// Inference requests not yet finished, drained on shutdown
+    pub in_flight: Arc<InFlightRequests>,
+    /// Responses replayed to retries with the same Idempotency-Key (None = disabled)
+    pub idempotency_cache: Option<Arc<IdempotencyCache>>,
 }
 
 impl AppContext {
@@ -63,6 +102,17 @@ impl AppContext {
     ) -> Result<Self, String> {
         let rate_limit_tokens = rate_limit_tokens_per_second.unwrap_or(max_concurrent_requests);
         let rate_limiter = Arc::new(TokenBucket::new(max_concurrent_requests, rate_limit_tokens));
+        let adaptive_limiter = router_config.adaptive_concurrency.as_ref().map(|config| {
+            Arc::new(AdaptiveConcurrencyLimiter::new(
+                config,
+                max_concurrent_requests,
+            ))
+        });
+        let model_rate_limiters = router_config
+            .model_concurrency_limits
+            .iter()
+            .map(|(model_id, &limit)| (model_id.clone(), Arc::new(TokenBucket::new(limit, limit))))
+            .collect();
 
         // Initialize gRPC-specific components only when in gRPC mode
         let tokenizer = if router_config.connection_mode == ConnectionMode::Grpc {
@@ -87,27 +137,64 @@ impl AppContext {
         };
 
         let worker_registry = Arc::new(WorkerRegistry::new());
-        let policy_registry = Arc::new(PolicyRegistry::new(router_config.policy.clone()));
+        let policy_registry = Arc::new(
+            PolicyRegistry::new(router_config.policy.clone())
+                .with_model_policies(router_config.model_policies.clone()),
+        );
 
         let router_manager = None;
 
+        let stream_limiter = router_config
+            .max_concurrent_streams
+            .map(|limit| Arc::new(Semaphore::new(limit)));
+        let idempotency_cache = router_config
+            .idempotency
+            .as_ref()
+            .map(|config| Arc::new(IdempotencyCache::new(config)));
+
         // Initialize response storage based on configuration
-        let response_storage: SharedResponseStorage = match router_config.history_backend {
-            HistoryBackend::Memory => Arc::new(MemoryResponseStorage::new()),
-            HistoryBackend::None => Arc::new(NoOpResponseStorage::new()),
+        let (response_storage, batch_storage): (
+            SharedResponseStorage,
+            Option<SharedResponseStorage>,
+        ) = match &router_config.history_backend {
+            HistoryBackend::Memory => (
+                Arc::new(MemoryResponseStorage::with_config(
+                    &router_config.memory_history,
+                )),
+                Some(Arc::new(MemoryResponseStorage::with_config(
+                    &router_config.memory_history,
+                ))),
+            ),
+            HistoryBackend::None => (Arc::new(NoOpResponseStorage::new()), None),
+            HistoryBackend::Redis { url } => {
+                let storage = RedisResponseStorage::new(url)
+                    .map_err(|e| format!("Failed to create Redis response storage: {e}"))?;
+                let batch_storage = RedisResponseStorage::new(url)
+                    .map_err(|e| format!("Failed to create Redis response storage: {e}"))?
+                    .with_namespace("batch");
+                (Arc::new(storage), Some(Arc::new(batch_storage)))
+            }
         };
 
         Ok(Self {
             client,
             router_config,
             rate_limiter,
+            adaptive_limiter,
+            model_rate_limiters: Arc::new(model_rate_limiters),
             tokenizer,
             worker_registry,
             policy_registry,
             router_manager,
             response_storage,
+            batch_storage,
+            batch_jobs: Arc::new(BatchJobs::new()),
             api_key_cache: Arc::new(RwLock::new(HashMap::new())),
             api_key_validation_urls: Arc::new(api_key_validation_urls),
+            log_level: None,
+            stream_limiter,
+            in_flight: Arc::new(InFlightRequests::new()),
+            idempotency_cache,
         })
     }
 }
@@ -143,31 +230,40 @@ async fn transparent_proxy_handler(State(state): State<Arc<AppState>>, req: Requ
     let body_bytes = match axum::body::to_bytes(req.into_body(), usize::MAX).await {
         Ok(bytes) => bytes,
         Err(e) => {
-            return (
+            return error_response(
                 StatusCode::BAD_REQUEST,
                 format!("Failed to read request body: {}", e),
             )
-                .into_response()
         }
     };
 
-    // Parse body as JSON
-    let body_json: serde_json::Value = if body_bytes.is_empty() {
-        serde_json::Value::Null
-    } else {
-        match serde_json::from_slice(&body_bytes) {
-            Ok(json) => json,
-            Err(e) => {
-                return (StatusCode::BAD_REQUEST, format!("Invalid JSON body: {}", e))
-                    .into_response()
-            }
+    // Route through transparent proxy; the body is forwarded unparsed so
+    // bodiless GETs and non-JSON payloads pass through
+    state
+        .router
+        .route_transparent(Some(&headers), &path, &method, body_bytes)
+        .await
+}
+
+// Audio endpoints take multipart/form-data uploads, which are forwarded without parsing
+async fn v1_audio(State(state): State<Arc<AppState>>, req: Request) -> Response {
+    let (parts, body) = req.into_parts();
+    if let Err(response) = authorize_request(&state, &parts.headers).await {
+        return response;
+    }
+
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Failed to read request body: {}", e),
+            )
         }
     };
-
-    // Route through transparent proxy
     state
         .router
-        .route_transparent(Some(&headers), &path, &method, body_json)
+        .route_raw(Some(&parts.headers), parts.uri.path(), &parts.method, body)
         .await
 }
 
@@ -181,13 +277,24 @@ async fn liveness(State(state): State<Arc<AppState>>, req: Request) -> Response
     state.router.liveness()
 }
 
-async fn readiness(State(state): State<Arc<AppState>>, req: Request) -> Response {
-    let headers = req.headers().clone();
+#[derive(Deserialize)]
+struct ReadinessQuery {
+    model: Option<String>,
+}
+
+async fn readiness(
+    State(state): State<Arc<AppState>>,
+    Query(ReadinessQuery { model }): Query<ReadinessQuery>,
+    headers: http::HeaderMap,
+) -> Response {
     if let Err(response) = authorize_request(&state, &headers).await {
         return response;
     }
 
-    state.router.readiness()
+    match model.as_deref() {
+        Some(model_id) => state.router.model_readiness(model_id),
+        None => state.router.readiness(),
+    }
 }
 
 async fn health(State(state): State<Arc<AppState>>, req: Request) -> Response {
@@ -223,7 +330,57 @@ async fn v1_models(State(state): State<Arc<AppState>>, req: Request) -> Response
         return response;
     }
 
-    state.router.get_models(req).await
+    let response = state.router.get_models(req).await;
+    let config = &state.context.router_config;
+    if config.advertise_model_aliases && !config.model_aliases.is_empty() {
+        return advertise_model_aliases(response, &config.model_aliases).await;
+    }
+    response
+}
+
+/// Append an entry per alias to a `/v1/models` list, copied from its target model
+///
+/// Aliases whose target is not listed are left out, so clients only see
+/// names that can currently be served.
+async fn advertise_model_aliases(
+    response: Response,
+    aliases: &HashMap<String, String>,
+) -> Response {
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to read response body: {}", e),
+            );
+        }
+    };
+    let mut models: serde_json::Value = match serde_json::from_slice(&bytes) {
+        Ok(models) => models,
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+
+    if let Some(data) = models.get_mut("data").and_then(|data| data.as_array_mut()) {
+        let mut alias_entries: Vec<_> = aliases
+            .iter()
+            .filter_map(|(alias, model_id)| {
+                let mut entry = data
+                    .iter()
+                    .find(|entry| entry.get("id").and_then(|id| id.as_str()) == Some(model_id))?
+                    .clone();
+                entry["id"] = json!(alias);
+                Some(entry)
+            })
+            .collect();
+        alias_entries.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+        data.extend(alias_entries);
+    }
+
+    Json(models).into_response()
 }
 
 async fn get_model_info(State(state): State<Arc<AppState>>, req: Request) -> Response {
@@ -237,58 +394,319 @@ async fn get_model_info(State(state): State<Arc<AppState>>, req: Request) -> Res
 
 // Generation endpoints
 // The RouterTrait now accepts optional headers and typed body directly
+/// Rewrite a model alias from `model_aliases` to the model the workers serve
+///
+/// Runs before routing, so workers only ever see their own model ID.
+fn resolve_model_alias(state: &AppState, body: &mut impl GenerationRequest) {
+    let aliases = &state.context.router_config.model_aliases;
+    let Some((alias, model_id)) = body
+        .get_model()
+        .and_then(|model| aliases.get_key_value(model))
+    else {
+        return;
+    };
+    debug!("Resolved model alias {} to {}", alias, model_id);
+    body.set_model(model_id.clone());
+}
+
+/// Apply `default_max_tokens` and `max_tokens_limit` to a request
+///
+/// Requests without an output token limit get the default (or the limit when
+/// no default is set), and larger limits are clamped down to the limit.
+fn apply_max_tokens_policy(state: &AppState, body: &mut impl GenerationRequest) {
+    let config = &state.context.router_config;
+    let max_tokens = match (body.max_tokens(), config.max_tokens_limit) {
+        (Some(requested), Some(limit)) if requested > limit => {
+            debug!("Clamping max_tokens {} to limit {}", requested, limit);
+            limit
+        }
+        (Some(_), _) => return,
+        (None, limit) => match config.default_max_tokens.or(limit) {
+            Some(default) => default.min(limit.unwrap_or(u32::MAX)),
+            None => return,
+        },
+    };
+    body.set_max_tokens(max_tokens);
+}
+
+/// Model the client named in a streaming request, kept before alias
+/// resolution when streamed chunks are rewritten to it
+fn requested_stream_model(state: &AppState, body: &impl GenerationRequest) -> Option<String> {
+    if !state.context.router_config.rewrite_stream_model || !body.is_stream() {
+        return None;
+    }
+    body.get_model().map(str::to_string)
+}
+
+/// Rewrite the `model` of every chunk of a successful streaming response
+fn rewrite_stream_model(response: Response, model: Option<String>) -> Response {
+    let is_event_stream = response
+        .headers()
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    let Some(model) = model.filter(|_| response.status().is_success() && is_event_stream) else {
+        return response;
+    };
+    let (parts, body) = response.into_parts();
+    let body = stream_model::rewrite_model(body.into_data_stream(), model);
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+/// Model to route a request by: its `model` field, when some worker serves it
+///
+/// Requests naming a model no worker is registered under (including every
+/// request when workers carry no model id) are routed across all workers.
+fn routing_model<'a>(state: &AppState, body: &'a impl GenerationRequest) -> Option<&'a str> {
+    body.get_model().filter(|model| {
+        !state
+            .context
+            .worker_registry
+            .get_by_model_fast(model)
+            .is_empty()
+    })
+}
+
+/// Reject out-of-range sampling parameters with 400 before routing
+#[allow(clippy::result_large_err)]
+fn check_sampling_params(body: &impl GenerationRequest, route: &str) -> Result<(), Response> {
+    body.validate_sampling_params()
+        .map_err(|e| invalid_request(route, "invalid_sampling_params", e))
+}
+
+/// Reject malformed JSON schemas and conflicting structured output
+/// constraints with 400 before routing
+#[allow(clippy::result_large_err)]
+fn check_structured_output(body: &impl GenerationRequest, route: &str) -> Result<(), Response> {
+    body.validate_structured_output()
+        .map_err(|e| invalid_request(route, "invalid_structured_output", e))
+}
+
+/// 400 response for a request that failed validation, recorded under `error_type`
+fn invalid_request(route: &str, error_type: &str, e: ValidationError) -> Response {
+    debug!("Rejecting request to {}: {}", route, e);
+    RouterMetrics::record_request_error(route, error_type);
+    (
+        StatusCode::BAD_REQUEST,
+        Json(error_body(
+            StatusCode::BAD_REQUEST,
+            e.to_string(),
+            None,
+            e.parameter(),
+        )),
+    )
+        .into_response()
+}
+
+/// Reject prompts longer than `max_prompt_chars` with 400 before routing
+#[allow(clippy::result_large_err)]
+fn check_prompt_length(
+    state: &AppState,
+    body: &impl GenerationRequest,
+    route: &str,
+) -> Result<(), Response> {
+    let Some(max_chars) = state.context.router_config.max_prompt_chars else {
+        return Ok(());
+    };
+    let chars = body.prompt_chars();
+    if chars <= max_chars {
+        return Ok(());
+    }
+    debug!(
+        "Rejecting request to {}: prompt of {} characters exceeds {}",
+        route, chars, max_chars
+    );
+    RouterMetrics::record_request_error(route, "prompt_too_long");
+    Err(error_response_with_code(
+        StatusCode::BAD_REQUEST,
+        format!(
+            "Prompt is {} characters long, exceeding the maximum of {}",
+            chars, max_chars
+        ),
+        "prompt_too_long",
+    ))
+}
+
+/// Claim the request's Idempotency-Key, or answer a retry without routing it
+///
+/// Streaming responses are not buffered, so streaming requests always reach
+/// a worker.
+#[allow(clippy::result_large_err)]
+fn claim_idempotency_key<T: GenerationRequest + serde::Serialize>(
+    state: &AppState,
+    headers: &http::HeaderMap,
+    body: &T,
+    route: &str,
+) -> Result<Option<IdempotencyClaim>, Response> {
+    let Some(cache) = &state.context.idempotency_cache else {
+        return Ok(None);
+    };
+    if body.is_stream() {
+        return Ok(None);
+    }
+    let Some(key) = IdempotencyCache::request_key(route, headers, body)? else {
+        return Ok(None);
+    };
+    cache.begin(key).map(Some).inspect_err(|response| {
+        if response.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER) {
+            RouterMetrics::record_idempotent_replay(route);
+        }
+    })
+}
+
+/// Take a streaming slot for a streaming request
+///
+/// Fails with 503 when `max_concurrent_streams` responses are already
+/// streaming. Non-streaming requests and unlimited routers get no permit.
+#[allow(clippy::result_large_err)]
+fn acquire_stream_permit(
+    state: &AppState,
+    body: &impl GenerationRequest,
+    route: &str,
+) -> Result<Option<OwnedSemaphorePermit>, Response> {
+    let Some(limiter) = &state.context.stream_limiter else {
+        return Ok(None);
+    };
+    if !body.is_stream() {
+        return Ok(None);
+    }
+    match limiter.clone().try_acquire_owned() {
+        Ok(permit) => Ok(Some(permit)),
+        Err(_) => {
+            warn!(
+                "Rejecting streaming request to {}: stream limit reached",
+                route
+            );
+            RouterMetrics::record_request_error(route, "stream_limit_reached");
+            Err(error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Too many concurrent streaming requests, please retry later",
+            ))
+        }
+    }
+}
+
+/// Keep a streaming slot until the response body is finished or dropped
+fn hold_stream_permit(response: Response, permit: Option<OwnedSemaphorePermit>) -> Response {
+    let Some(permit) = permit else {
+        return response;
+    };
+    let (parts, body) = response.into_parts();
+    let body = body.into_data_stream().map(move |chunk| {
+        let _slot = &permit;
+        chunk
+    });
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
 async fn generate(
     State(state): State<Arc<AppState>>,
     headers: http::HeaderMap,
-    Json(body): Json<GenerateRequest>,
+    Json(mut body): Json<GenerateRequest>,
 ) -> Response {
     if let Err(response) = authorize_request(&state, &headers).await {
         return response;
     }
+    resolve_model_alias(&state, &mut body);
+    if let Err(response) = check_sampling_params(&body, "/generate") {
+        return response;
+    }
+    if let Err(response) = check_prompt_length(&state, &body, "/generate") {
+        return response;
+    }
+    let permit = match acquire_stream_permit(&state, &body, "/generate") {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
 
-    state
+    let response = state
         .router
-        .route_generate(Some(&headers), &body, None)
-        .await
+        .route_generate(Some(&headers), &body, routing_model(&state, &body))
+        .await;
+    hold_stream_permit(response, permit)
 }
 
 async fn v1_chat_completions(
     State(state): State<Arc<AppState>>,
     headers: http::HeaderMap,
-    Json(body): Json<ChatCompletionRequest>,
+    Json(mut body): Json<ChatCompletionRequest>,
 ) -> Response {
     if let Err(response) = authorize_request(&state, &headers).await {
         return response;
     }
+    let requested_model = requested_stream_model(&state, &body);
+    resolve_model_alias(&state, &mut body);
+    apply_max_tokens_policy(&state, &mut body);
+    if let Err(response) = check_sampling_params(&body, "/v1/chat/completions") {
+        return response;
+    }
+    if let Err(response) = check_structured_output(&body, "/v1/chat/completions") {
+        return response;
+    }
+    if let Err(response) = check_prompt_length(&state, &body, "/v1/chat/completions") {
+        return response;
+    }
+    let permit = match acquire_stream_permit(&state, &body, "/v1/chat/completions") {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
 
-    state.router.route_chat(Some(&headers), &body, None).await
+    let response = state
+        .router
+        .route_chat(Some(&headers), &body, routing_model(&state, &body))
+        .await;
+    let response = rewrite_stream_model(response, requested_model);
+    hold_stream_permit(response, permit)
 }
 
 async fn v1_completions(
     State(state): State<Arc<AppState>>,
     headers: http::HeaderMap,
-    Json(body): Json<CompletionRequest>,
+    Json(mut body): Json<CompletionRequest>,
 ) -> Response {
     if let Err(response) = authorize_request(&state, &headers).await {
         return response;
     }
+    let requested_model = requested_stream_model(&state, &body);
+    resolve_model_alias(&state, &mut body);
+    apply_max_tokens_policy(&state, &mut body);
+    if let Err(response) = check_sampling_params(&body, "/v1/completions") {
+        return response;
+    }
+    if let Err(response) = check_structured_output(&body, "/v1/completions") {
+        return response;
+    }
+    if let Err(response) = check_prompt_length(&state, &body, "/v1/completions") {
+        return response;
+    }
+    let permit = match acquire_stream_permit(&state, &body, "/v1/completions") {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
 
-    state
+    let response = state
         .router
-        .route_completion(Some(&headers), &body, None)
-        .await
+        .route_completion(Some(&headers), &body, routing_model(&state, &body))
+        .await;
+    let response = rewrite_stream_model(response, requested_model);
+    hold_stream_permit(response, permit)
 }
 
 async fn rerank(
     State(state): State<Arc<AppState>>,
     headers: http::HeaderMap,
-    Json(body): Json<RerankRequest>,
+    Json(mut body): Json<RerankRequest>,
 ) -> Response {
     if let Err(response) = authorize_request(&state, &headers).await {
         return response;
     }
+    resolve_model_alias(&state, &mut body);
 
-    state.router.route_rerank(Some(&headers), &body, None).await
+    let model_id = routing_model(&state, &body);
+    state
+        .router
+        .route_rerank(Some(&headers), &body, model_id)
+        .await
 }
 
 async fn v1_rerank(
@@ -300,42 +718,147 @@ async fn v1_rerank(
         return response;
     }
 
+    let mut body: RerankRequest = body.into();
+    resolve_model_alias(&state, &mut body);
     state
         .router
-        .route_rerank(Some(&headers), &body.into(), None)
+        .route_rerank(Some(&headers), &body, routing_model(&state, &body))
         .await
 }
 
 async fn v1_responses(
     State(state): State<Arc<AppState>>,
     headers: http::HeaderMap,
-    Json(body): Json<ResponsesRequest>,
+    Json(mut body): Json<ResponsesRequest>,
 ) -> Response {
     if let Err(response) = authorize_request(&state, &headers).await {
         return response;
     }
+    resolve_model_alias(&state, &mut body);
+    apply_max_tokens_policy(&state, &mut body);
+    if let Err(response) = check_sampling_params(&body, "/v1/responses") {
+        return response;
+    }
+    if let Err(response) = check_prompt_length(&state, &body, "/v1/responses") {
+        return response;
+    }
+    let claim = match claim_idempotency_key(&state, &headers, &body, "/v1/responses") {
+        Ok(claim) => claim,
+        Err(response) => return response,
+    };
+    let permit = match acquire_stream_permit(&state, &body, "/v1/responses") {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
 
-    state
+    let response = state
         .router
-        .route_responses(Some(&headers), &body, None)
-        .await
+        .route_responses(Some(&headers), &body, routing_model(&state, &body))
+        .await;
+    let response = match claim {
+        Some(claim) => claim.finish(response).await,
+        None => response,
+    };
+    hold_stream_permit(response, permit)
 }
 
 async fn v1_embeddings(
     State(state): State<Arc<AppState>>,
     headers: http::HeaderMap,
-    Json(body): Json<EmbeddingRequest>,
+    Json(mut body): Json<EmbeddingRequest>,
 ) -> Response {
     if let Err(response) = authorize_request(&state, &headers).await {
         return response;
     }
+    resolve_model_alias(&state, &mut body);
 
     state
         .router
-        .route_embeddings(Some(&headers), &body, None)
+        .route_embeddings(Some(&headers), &body, routing_model(&state, &body))
         .await
 }
 
+#[async_trait]
+impl BatchDispatch for AppState {
+    async fn dispatch(&self, item: BatchItem, headers: &http::HeaderMap) -> Response {
+        match item {
+            BatchItem::Chat(mut body) => {
+                resolve_model_alias(self, body.as_mut());
+                apply_max_tokens_policy(self, body.as_mut());
+                self.router
+                    .route_chat(Some(headers), &body, routing_model(self, body.as_ref()))
+                    .await
+            }
+            BatchItem::Completion(mut body) => {
+                resolve_model_alias(self, body.as_mut());
+                apply_max_tokens_policy(self, body.as_mut());
+                self.router
+                    .route_completion(Some(headers), &body, routing_model(self, body.as_ref()))
+                    .await
+            }
+            BatchItem::Embedding(mut body) => {
+                resolve_model_alias(self, &mut body);
+                self.router
+                    .route_embeddings(Some(headers), &body, routing_model(self, &body))
+                    .await
+            }
+        }
+    }
+}
+
+async fn v1_batches_create(
+    State(state): State<Arc<AppState>>,
+    headers: http::HeaderMap,
+    Json(body): Json<BatchCreateRequest>,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    let Some(storage) = state.context.batch_storage.clone() else {
+        return batches::storage_disabled();
+    };
+    let in_flight = state.context.in_flight.track();
+    let ctx = BatchContext {
+        storage,
+        jobs: state.context.batch_jobs.clone(),
+        concurrency: state.context.router_config.batch_max_concurrency,
+        headers: batches::forwarded_headers(&headers),
+        dispatcher: state,
+    };
+    batches::create_batch(ctx, in_flight, body).await
+}
+
+async fn v1_batches_get(
+    State(state): State<Arc<AppState>>,
+    Path(batch_id): Path<String>,
+    headers: http::HeaderMap,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    match &state.context.batch_storage {
+        Some(storage) => batches::get_batch(storage.as_ref(), &batch_id).await,
+        None => batches::storage_disabled(),
+    }
+}
+
+async fn v1_batches_output(
+    State(state): State<Arc<AppState>>,
+    Path(batch_id): Path<String>,
+    headers: http::HeaderMap,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    match &state.context.batch_storage {
+        Some(storage) => batches::get_batch_output(storage.as_ref(), &batch_id).await,
+        None => batches::storage_disabled(),
+    }
+}
+
 async fn v1_responses_get(
     State(state): State<Arc<AppState>>,
     Path(response_id): Path<String>,
@@ -375,7 +898,6 @@ async fn v1_responses_delete(
         return response;
     }
 
-    // Python server does not support this yet
     state
         .router
         .delete_response(Some(&headers), &response_id)
@@ -385,16 +907,16 @@ async fn v1_responses_delete(
 async fn v1_responses_list_input_items(
     State(state): State<Arc<AppState>>,
     Path(response_id): Path<String>,
+    Query(params): Query<ResponseInputItemsParams>,
     headers: http::HeaderMap,
 ) -> Response {
     if let Err(response) = authorize_request(&state, &headers).await {
         return response;
     }
 
-    // Python server does not support this yet
     state
         .router
-        .list_response_input_items(Some(&headers), &response_id)
+        .list_response_input_items(Some(&headers), &response_id, &params)
         .await
 }
 
@@ -426,13 +948,16 @@ async fn authorize_request(
         .strip_prefix("Bearer ")
         .map(str::trim)
         .filter(|value| !value.is_empty())
-        .ok_or_else(|| (StatusCode::UNAUTHORIZED, AUTH_FAILURE_MESSAGE).into_response())?;
+        .ok_or_else(|| error_response(StatusCode::UNAUTHORIZED, AUTH_FAILURE_MESSAGE))?;
 
     if let Some(valid) = state.context.api_key_cache.read().await.get(token).copied() {
         if valid {
             return Ok(());
         }
-        return Err((StatusCode::UNAUTHORIZED, AUTH_FAILURE_MESSAGE).into_response());
+        return Err(error_response(
+            StatusCode::UNAUTHORIZED,
+            AUTH_FAILURE_MESSAGE,
+        ));
     }
 
     let mut validated = false;
@@ -468,7 +993,10 @@ async fn authorize_request(
     if validated {
         Ok(())
     } else {
-        Err((StatusCode::UNAUTHORIZED, AUTH_FAILURE_MESSAGE).into_response())
+        Err(error_response(
+            StatusCode::UNAUTHORIZED,
+            AUTH_FAILURE_MESSAGE,
+        ))
     }
 }
 
@@ -483,7 +1011,7 @@ async fn add_worker(
 
     match state.router.add_worker(&url).await {
         Ok(message) => (StatusCode::OK, message).into_response(),
-        Err(error) => (StatusCode::BAD_REQUEST, error).into_response(),
+        Err(error) => error_response(StatusCode::BAD_REQUEST, error),
     }
 }
 
@@ -529,6 +1057,248 @@ async fn get_loads(State(state): State<Arc<AppState>>, headers: http::HeaderMap)
     state.router.get_worker_loads().await
 }
 
+// ---------- Policy management endpoints ----------
+
+#[derive(Deserialize)]
+struct SetPolicyRequest {
+    /// Model to switch; the default policy is replaced when omitted
+    model_id: Option<String>,
+    policy: String,
+}
+
+/// GET /policies - Show the default policy, per-model policies and available policies
+async fn get_policies(State(state): State<Arc<AppState>>, headers: http::HeaderMap) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    let policy_registry = &state.context.policy_registry;
+    Json(json!({
+        "default_policy": policy_registry.get_default_policy().name(),
+        "models": policy_registry.get_all_mappings(),
+        "available_policies": PolicyFactory::available_policies(),
+    }))
+    .into_response()
+}
+
+/// POST /policies - Switch the policy for a model (or the default) at runtime
+async fn set_policy(
+    State(state): State<Arc<AppState>>,
+    headers: http::HeaderMap,
+    Json(request): Json<SetPolicyRequest>,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    // Reuse the configured tuning parameters when switching back to the startup policy
+    let configured = &state.context.router_config.policy;
+    let policy = if configured.name() == request.policy {
+        Some(PolicyFactory::create_from_config(configured))
+    } else {
+        PolicyFactory::create_by_name(&request.policy)
+    };
+    let Some(policy) = policy else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("Unknown policy '{}'", request.policy),
+                "code": "UNKNOWN_POLICY",
+                "available_policies": PolicyFactory::available_policies(),
+            })),
+        )
+            .into_response();
+    };
+
+    let policy_registry = &state.context.policy_registry;
+    let worker_registry = &state.context.worker_registry;
+    let previous = match request.model_id.as_deref() {
+        Some(model_id) => {
+            if !policy_registry.get_worker_counts().contains_key(model_id) {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": format!("Model {model_id} has no registered workers"),
+                        "code": "MODEL_NOT_FOUND",
+                    })),
+                )
+                    .into_response();
+            }
+            let workers = worker_registry.get_by_model_fast(model_id);
+            policy_registry
+                .replace_policy(model_id, Arc::clone(&policy), &workers)
+                .map(|p| p.name())
+        }
+        None => {
+            let workers = worker_registry.get_all();
+            Some(
+                policy_registry
+                    .replace_default_policy(Arc::clone(&policy), &workers)
+                    .name(),
+            )
+        }
+    };
+
+    Json(json!({
+        "success": true,
+        "model_id": request.model_id,
+        "policy": policy.name(),
+        "previous_policy": previous,
+    }))
+    .into_response()
+}
+
+// ---------- Tokenizer endpoints ----------
+
+/// Fields other than `prompt` (e.g. `model`) are ignored: the router serves a single tokenizer
+#[derive(Deserialize)]
+struct TokenizeRequest {
+    prompt: String,
+}
+
+#[derive(Deserialize)]
+struct DetokenizeRequest {
+    tokens: Vec<u32>,
+}
+
+fn tokenizer_unavailable() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "No tokenizer is loaded; tokenization is only served in gRPC mode",
+            "code": "TOKENIZER_NOT_AVAILABLE",
+        })),
+    )
+        .into_response()
+}
+
+fn tokenizer_error(error: anyhow::Error) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": error.to_string(),
+            "code": "TOKENIZATION_FAILED",
+        })),
+    )
+        .into_response()
+}
+
+/// POST /tokenize - Encode a prompt with the router's tokenizer
+async fn tokenize(
+    State(state): State<Arc<AppState>>,
+    headers: http::HeaderMap,
+    Json(request): Json<TokenizeRequest>,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    let Some(tokenizer) = &state.context.tokenizer else {
+        return tokenizer_unavailable();
+    };
+    match tokenizer.encode(&request.prompt) {
+        Ok(encoding) => {
+            let tokens = encoding.token_ids();
+            Json(json!({
+                "count": tokens.len(),
+                "tokens": tokens,
+            }))
+            .into_response()
+        }
+        Err(error) => tokenizer_error(error),
+    }
+}
+
+/// POST /detokenize - Decode token ids with the router's tokenizer
+async fn detokenize(
+    State(state): State<Arc<AppState>>,
+    headers: http::HeaderMap,
+    Json(request): Json<DetokenizeRequest>,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    let Some(tokenizer) = &state.context.tokenizer else {
+        return tokenizer_unavailable();
+    };
+    match tokenizer.decode(&request.tokens, false) {
+        Ok(prompt) => Json(json!({ "prompt": prompt })).into_response(),
+        Err(error) => tokenizer_error(error),
+    }
+}
+
+// ---------- Log level endpoints ----------
+
+#[derive(Deserialize)]
+struct SetLogLevelRequest {
+    level: String,
+    /// Tracing target (module path) to change; the default targets are changed when omitted
+    target: Option<String>,
+}
+
+fn log_level_unavailable() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({
+            "error": "Log level control is unavailable because logging was initialized outside the router",
+            "code": "LOG_LEVEL_UNAVAILABLE",
+        })),
+    )
+        .into_response()
+}
+
+/// GET /admin/log_level - Show the log filter currently in effect
+async fn get_log_level(State(state): State<Arc<AppState>>, headers: http::HeaderMap) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    match &state.context.log_level {
+        Some(handle) => Json(json!({ "filter": handle.current() })).into_response(),
+        None => log_level_unavailable(),
+    }
+}
+
+/// POST /admin/log_level - Change the log level for a target (or the default targets) at runtime
+async fn set_log_level(
+    State(state): State<Arc<AppState>>,
+    headers: http::HeaderMap,
+    Json(request): Json<SetLogLevelRequest>,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    let Some(handle) = &state.context.log_level else {
+        return log_level_unavailable();
+    };
+    match handle.set_level(&request.level, request.target.as_deref()) {
+        Ok(filter) => {
+            info!(
+                "Log level set to {} for {}",
+                request.level,
+                request.target.as_deref().unwrap_or("default targets")
+            );
+            Json(json!({
+                "success": true,
+                "level": request.level,
+                "target": request.target,
+                "filter": filter,
+            }))
+            .into_response()
+        }
+        Err(error) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": error,
+                "code": "INVALID_LOG_LEVEL",
+            })),
+        )
+            .into_response(),
+    }
+}
+
 // ---------- Worker management endpoints (RESTful) ----------
 
 /// POST /workers - Add a new worker with full configuration
@@ -541,49 +1311,153 @@ async fn create_worker(
         return response;
     }
 
+    match add_worker_from_config(&state, config).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+    }
+}
+
+/// Health-check and register one worker, as POST /workers does
+async fn add_worker_from_config(
+    state: &AppState,
+    config: WorkerConfigRequest,
+) -> Result<WorkerApiResponse, WorkerErrorResponse> {
     // Check if we have a RouterManager (enable_igw=true)
     if let Some(router_manager) = &state.router_manager {
         // Call RouterManager's add_worker method directly with the full config
-        match router_manager.add_worker(config).await {
-            Ok(response) => (StatusCode::OK, Json(response)).into_response(),
-            Err(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
-        }
+        router_manager.add_worker(config).await
     } else {
         // In single router mode, use the router's add_worker with basic config
-        match state.router.add_worker(&config.url).await {
-            Ok(message) => {
-                let response = WorkerApiResponse {
-                    success: true,
-                    message,
-                    worker: None,
-                };
-                (StatusCode::OK, Json(response)).into_response()
+        state
+            .router
+            .add_worker_with_tags(&config.url, config.labels)
+            .await
+            .map(|message| WorkerApiResponse {
+                success: true,
+                message,
+                worker: None,
+            })
+            .map_err(|error| WorkerErrorResponse {
+                error,
+                code: "ADD_WORKER_FAILED".to_string(),
+            })
+    }
+}
+
+/// POST /workers/bulk - Add several workers at once
+///
+/// Workers are health-checked concurrently, so bring-up takes as long as the
+/// slowest worker rather than the sum of all of them. Each worker succeeds or
+/// fails on its own; results are returned in request order.
+async fn create_workers_bulk(
+    State(state): State<Arc<AppState>>,
+    headers: http::HeaderMap,
+    Json(configs): Json<Vec<WorkerConfigRequest>>,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    let results: Vec<BulkWorkerResult> =
+        futures_util::future::join_all(configs.into_iter().map(|config| {
+            let state = Arc::clone(&state);
+            async move {
+                let url = config.url.clone();
+                match add_worker_from_config(&state, config).await {
+                    Ok(response) => BulkWorkerResult {
+                        url,
+                        success: true,
+                        message: Some(response.message),
+                        worker: response.worker,
+                        error: None,
+                    },
+                    Err(error) => BulkWorkerResult {
+                        url,
+                        success: false,
+                        message: None,
+                        worker: None,
+                        error: Some(error),
+                    },
+                }
             }
-            Err(error) => {
-                let error_response = WorkerErrorResponse {
-                    error,
-                    code: "ADD_WORKER_FAILED".to_string(),
-                };
-                (StatusCode::BAD_REQUEST, Json(error_response)).into_response()
+        }))
+        .await;
+
+    let succeeded = results.iter().filter(|r| r.success).count();
+    let response = BulkWorkerResponse {
+        succeeded,
+        failed: results.len() - succeeded,
+        results,
+    };
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+#[derive(Deserialize)]
+struct WorkerListQuery {
+    #[serde(rename = "type")]
+    worker_type: Option<String>,
+    healthy: Option<bool>,
+    model: Option<String>,
+    limit: Option<usize>,
+    #[serde(default)]
+    offset: usize,
+}
+
+impl WorkerListQuery {
+    #[allow(clippy::result_large_err)]
+    fn filter(&self) -> Result<WorkerFilter, Response> {
+        let worker_type = match self.worker_type.as_deref() {
+            None => None,
+            Some("regular") => Some(WorkerType::Regular),
+            Some("prefill") => Some(WorkerType::Prefill {
+                bootstrap_port: None,
+            }),
+            Some("decode") => Some(WorkerType::Decode),
+            Some(other) => {
+                return Err(error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "Invalid worker type '{}', expected regular, prefill or decode",
+                        other
+                    ),
+                ))
             }
-        }
+        };
+        Ok(WorkerFilter {
+            model_id: self.model.clone(),
+            worker_type,
+            healthy: self.healthy,
+        })
     }
 }
 
+/// GET /workers - List workers, optionally filtered by type, health and model and paged
 async fn list_workers_rest(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<WorkerListQuery>,
     headers: http::HeaderMap,
 ) -> Response {
     if let Err(response) = authorize_request(&state, &headers).await {
         return response;
     }
 
+    let filter = match query.filter() {
+        Ok(filter) => filter,
+        Err(response) => return response,
+    };
+
     if let Some(router_manager) = &state.router_manager {
-        let response = router_manager.list_workers();
+        let response = router_manager.list_workers(&filter, query.offset, query.limit);
         Json(response).into_response()
     } else {
         // In single router mode, get detailed worker info from registry
-        let workers = state.context.worker_registry.get_all();
+        let matched = state.context.worker_registry.get_filtered_with_ids(&filter);
+        let workers: Vec<_> = matched
+            .iter()
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .map(|(_, worker)| worker)
+            .collect();
         let response = serde_json::json!({
             "workers": workers.iter().map(|worker| {
                 let mut worker_info = serde_json::json!({
@@ -595,10 +1469,12 @@ async fn list_workers_rest(
                         WorkerType::Decode => "decode",
                     },
                     "is_healthy": worker.is_healthy(),
+                    "is_warming": worker.is_warming(),
                     "load": worker.load(),
                     "connection_mode": format!("{:?}", worker.connection_mode()),
                     "priority": worker.priority(),
                     "cost": worker.cost(),
+                    "metadata": worker.metadata().labels,
                 });
 
                 // Add bootstrap_port for Prefill workers
@@ -608,7 +1484,7 @@ async fn list_workers_rest(
 
                 worker_info
             }).collect::<Vec<_>>(),
-            "total": workers.len(),
+            "total": matched.len(),
             "stats": {
                 "prefill_count": state.context.worker_registry.get_prefill_workers().len(),
                 "decode_count": state.context.worker_registry.get_decode_workers().len(),
@@ -619,6 +1495,18 @@ async fn list_workers_rest(
     }
 }
 
+/// GET /workers/stats - Router's in-memory view of every worker
+async fn get_worker_stats(
+    State(state): State<Arc<AppState>>,
+    headers: http::HeaderMap,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    state.router.get_worker_stats()
+}
+
 /// GET /workers/{url} - Get specific worker info
 async fn get_worker(
     State(state): State<Arc<AppState>>,
@@ -685,6 +1573,98 @@ async fn delete_worker(
     }
 }
 
+fn default_drain_timeout_secs() -> u64 {
+    30
+}
+
+#[derive(Deserialize)]
+struct DrainQuery {
+    #[serde(default = "default_drain_timeout_secs")]
+    timeout_secs: u64,
+}
+
+/// POST /workers/{url}/drain - Stop routing to a worker and remove it once idle
+async fn drain_worker(
+    State(state): State<Arc<AppState>>,
+    Path(url): Path<String>,
+    Query(DrainQuery { timeout_secs }): Query<DrainQuery>,
+    headers: http::HeaderMap,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+
+    match state
+        .router
+        .drain_worker(&url, Duration::from_secs(timeout_secs))
+        .await
+    {
+        Ok(message) => {
+            let response = WorkerApiResponse {
+                success: true,
+                message,
+                worker: None,
+            };
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(error) => {
+            let error = WorkerErrorResponse {
+                error,
+                code: "DRAIN_FAILED".to_string(),
+            };
+            (StatusCode::BAD_REQUEST, Json(error)).into_response()
+        }
+    }
+}
+
+/// GET /workers/{url}/circuit - Circuit breaker state and counters for a worker
+async fn get_worker_circuit(
+    State(state): State<Arc<AppState>>,
+    Path(url): Path<String>,
+    headers: http::HeaderMap,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+    state.router.get_worker_circuit(&url)
+}
+
+/// POST /workers/{url}/circuit/reset - Force a worker's circuit breaker closed
+async fn reset_worker_circuit(
+    State(state): State<Arc<AppState>>,
+    Path(url): Path<String>,
+    headers: http::HeaderMap,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+    state.router.reset_worker_circuit(&url)
+}
+
+/// POST /workers/{url}/disable - Stop selecting a worker without removing it
+async fn disable_worker(
+    State(state): State<Arc<AppState>>,
+    Path(url): Path<String>,
+    headers: http::HeaderMap,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+    state.router.set_worker_enabled(&url, false)
+}
+
+/// POST /workers/{url}/enable - Resume selecting a disabled worker
+async fn enable_worker(
+    State(state): State<Arc<AppState>>,
+    Path(url): Path<String>,
+    headers: http::HeaderMap,
+) -> Response {
+    if let Err(response) = authorize_request(&state, &headers).await {
+        return response;
+    }
+    state.router.set_worker_enabled(&url, true)
+}
+
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
@@ -692,6 +1672,8 @@ pub struct ServerConfig {
     pub max_payload_size: usize,
     pub log_dir: Option<String>,
     pub log_level: Option<String>,
+    /// Emit JSON logs, including structured access logs
+    pub log_json: bool,
     pub service_discovery_config: Option<ServiceDiscoveryConfig>,
     pub prometheus_config: Option<PrometheusConfig>,
     pub request_timeout_secs: u64,
@@ -706,15 +1688,25 @@ pub fn build_app(
     cors_allowed_origins: Vec<String>,
     enable_transparent_proxy: bool,
 ) -> Router {
-    // Create routes
-    let protected_routes = Router::new()
-        .route("/generate", post(generate))
-        .route("/v1/chat/completions", post(v1_chat_completions))
-        .route("/v1/completions", post(v1_completions))
-        .route("/rerank", post(rerank))
-        .route("/v1/rerank", post(v1_rerank))
+    // Body limits wrap concurrency limiting so oversized requests are
+    // rejected before they take a slot or get buffered
+    let payload_limits = app_state.context.router_config.payload_limits.clone();
+    let limited = |routes: Router<Arc<AppState>>, limit: Option<usize>| {
+        let routes = routes
+            .route_layer(axum::middleware::from_fn_with_state(
+                app_state.clone(),
+                middleware::concurrency_limit_middleware,
+            ))
+            .route_layer(axum::middleware::from_fn_with_state(
+                app_state.clone(),
+                middleware::in_flight_middleware,
+            ));
+        with_body_limit(routes, limit.unwrap_or(max_payload_size))
+    };
+
+    // Create routes, each inference group with its own body size limit
+    let responses_routes = Router::new()
         .route("/v1/responses", post(v1_responses))
-        .route("/v1/embeddings", post(v1_embeddings))
         .route("/v1/responses/{response_id}", get(v1_responses_get))
         .route(
             "/v1/responses/{response_id}/cancel",
@@ -725,9 +1717,47 @@ pub fn build_app(
             "/v1/responses/{response_id}/input",
             get(v1_responses_list_input_items),
         )
-        .route_layer(axum::middleware::from_fn_with_state(
-            app_state.clone(),
-            middleware::concurrency_limit_middleware,
+        .route(
+            "/v1/responses/{response_id}/input_items",
+            get(v1_responses_list_input_items),
+        );
+
+    let protected_routes = Router::new()
+        .merge(limited(
+            Router::new().route("/generate", post(generate)),
+            payload_limits.generate,
+        ))
+        .merge(limited(
+            Router::new().route("/v1/chat/completions", post(v1_chat_completions)),
+            payload_limits.chat,
+        ))
+        .merge(limited(
+            Router::new().route("/v1/completions", post(v1_completions)),
+            payload_limits.completions,
+        ))
+        .merge(limited(
+            Router::new()
+                .route("/rerank", post(rerank))
+                .route("/v1/rerank", post(v1_rerank)),
+            payload_limits.rerank,
+        ))
+        .merge(limited(
+            Router::new().route("/v1/embeddings", post(v1_embeddings)),
+            payload_limits.embeddings,
+        ))
+        .merge(limited(responses_routes, payload_limits.responses))
+        .merge(limited(
+            Router::new()
+                .route("/v1/audio/transcriptions", post(v1_audio))
+                .route("/v1/audio/translations", post(v1_audio)),
+            None,
+        ))
+        .merge(limited(
+            Router::new()
+                .route("/v1/batches", post(v1_batches_create))
+                .route("/v1/batches/{batch_id}", get(v1_batches_get))
+                .route("/v1/batches/{batch_id}/output", get(v1_batches_output)),
+            None,
         ));
 
     let public_routes = Router::new()
@@ -737,32 +1767,51 @@ pub fn build_app(
         .route("/health_generate", get(health_generate))
         .route("/v1/models", get(v1_models))
         .route("/get_model_info", get(get_model_info))
-        .route("/get_server_info", get(get_server_info));
+        .route("/get_server_info", get(get_server_info))
+        .route("/tokenize", post(tokenize))
+        .route("/detokenize", post(detokenize));
 
     let admin_routes = Router::new()
         .route("/add_worker", post(add_worker))
         .route("/remove_worker", post(remove_worker))
         .route("/list_workers", get(list_workers))
         .route("/flush_cache", post(flush_cache))
-        .route("/get_loads", get(get_loads));
+        .route("/get_loads", get(get_loads))
+        .route("/policies", get(get_policies).post(set_policy))
+        .route("/admin/log_level", get(get_log_level).post(set_log_level));
 
     // Worker management routes
     let worker_routes = Router::new()
         .route("/workers", post(create_worker))
         .route("/workers", get(list_workers_rest))
+        .route("/workers/bulk", post(create_workers_bulk))
+        .route("/workers/stats", get(get_worker_stats))
         .route("/workers/{url}", get(get_worker))
-        .route("/workers/{url}", delete(delete_worker));
-
-    // Build base app with all routes and middleware
-    let base_app = Router::new()
-        .merge(protected_routes)
+        .route("/workers/{url}", delete(delete_worker))
+        .route("/workers/{url}/drain", post(drain_worker))
+        .route("/workers/{url}/disable", post(disable_worker))
+        .route("/workers/{url}/enable", post(enable_worker))
+        .route("/workers/{url}/circuit", get(get_worker_circuit))
+        .route("/workers/{url}/circuit/reset", post(reset_worker_circuit));
+
+    // Management routes share the global body size limit
+    let management_routes = Router::new()
         .merge(public_routes)
         .merge(admin_routes)
         .merge(worker_routes)
-        // Request body size limiting
         .layer(tower_http::limit::RequestBodyLimitLayer::new(
             max_payload_size,
-        ))
+        ));
+
+    // Build base app with all routes and middleware
+    let mut base_app = Router::new()
+        .merge(protected_routes)
+        .merge(management_routes);
+    // The default predicate skips event streams and bodies too small to gain
+    if app_state.context.router_config.enable_response_compression {
+        base_app = base_app.layer(CompressionLayer::new().gzip(true).br(true));
+    }
+    let base_app = base_app
         .layer(middleware::create_logging_layer())
         .layer(middleware::RequestIdLayer::new(request_id_headers))
         .layer(create_cors_layer(cors_allowed_origins));
@@ -777,6 +1826,22 @@ pub fn build_app(
     }
 }
 
+/// Limit request bodies on a group of routes to `limit` bytes
+///
+/// axum's 2MB extractor default is disabled so the limit can be raised as
+/// well as lowered.
+fn with_body_limit(routes: Router<Arc<AppState>>, limit: usize) -> Router<Arc<AppState>> {
+    routes
+        .layer(DefaultBodyLimit::disable())
+        .layer(tower_http::limit::RequestBodyLimitLayer::new(limit))
+        .layer(axum::middleware::from_fn(move |request, next| {
+            middleware::body_limit_middleware(limit, request, next)
+        }))
+}
+
+/// Time cancelled batches get to record their state once the drain has run out
+const BATCH_CANCEL_GRACE: Duration = Duration::from_secs(5);
+
 pub async fn startup(config: ServerConfig) -> Result<(), Box<dyn std::error::Error>> {
     println!("DEBUG: Server startup function called");
 
@@ -784,7 +1849,7 @@ pub async fn startup(config: ServerConfig) -> Result<(), Box<dyn std::error::Err
     static LOGGING_INITIALIZED: AtomicBool = AtomicBool::new(false);
 
     println!("DEBUG: Initializing logging");
-    let _log_guard = if !LOGGING_INITIALIZED.swap(true, Ordering::SeqCst) {
+    let log_guard = if !LOGGING_INITIALIZED.swap(true, Ordering::SeqCst) {
         Some(logging::init_logging(LoggingConfig {
             level: config
                 .log_level
@@ -797,7 +1862,7 @@ pub async fn startup(config: ServerConfig) -> Result<(), Box<dyn std::error::Err
                     }
                 })
                 .unwrap_or(Level::INFO),
-            json_format: false,
+            json_format: config.log_json,
             log_dir: config.log_dir.clone(),
             colorize: true,
             log_file_name: "vllm-router".to_string(),
@@ -808,6 +1873,19 @@ pub async fn startup(config: ServerConfig) -> Result<(), Box<dyn std::error::Err
     };
     println!("DEBUG: Logging initialized");
 
+    // Embedders may build the config without the CLI or Python entry points,
+    // which validate it already; fail before anything is started
+    config.router_config.validate()?;
+    if matches!(&config.router_config.mode, RoutingMode::Regular { worker_urls } if worker_urls.is_empty())
+        && !config.router_config.enable_igw
+        && config
+            .service_discovery_config
+            .as_ref()
+            .is_none_or(|sd| !sd.enabled)
+    {
+        warn!("No worker URLs configured and service discovery is disabled; requests fail until workers are added through /workers");
+    }
+
     // Initialize prometheus metrics exporter
     println!("DEBUG: Initializing Prometheus metrics");
     if let Some(prometheus_config) = config.prometheus_config {
@@ -824,27 +1902,34 @@ pub async fn startup(config: ServerConfig) -> Result<(), Box<dyn std::error::Err
         config.max_payload_size / (1024 * 1024)
     );
 
+    if let Some(tls) = &config.router_config.upstream_tls {
+        upstream_tls::configure(tls)?;
+    }
+
     println!("DEBUG: Creating HTTP client");
-    let client = Client::builder()
+    let client = upstream_tls::client_builder()
         .pool_idle_timeout(Some(Duration::from_secs(50)))
         .pool_max_idle_per_host(500)
         .timeout(Duration::from_secs(config.request_timeout_secs))
         .connect_timeout(Duration::from_secs(10))
         .tcp_nodelay(true)
         .tcp_keepalive(Some(Duration::from_secs(30)))
+        .gzip(config.router_config.enable_upstream_compression)
+        .brotli(config.router_config.enable_upstream_compression)
         .build()
         .expect("Failed to create HTTP client");
     println!("DEBUG: HTTP client created");
 
     // Create the application context with all dependencies
     println!("DEBUG: Creating AppContext");
-    let app_context = AppContext::new(
+    let mut app_context = AppContext::new(
         config.router_config.clone(),
         client.clone(),
         config.router_config.max_concurrent_requests,
         config.router_config.rate_limit_tokens_per_second,
         config.router_config.api_key_validation_urls.clone(),
     )?;
+    app_context.log_level = log_guard.as_ref().and_then(|guard| guard.level_handle());
     println!("DEBUG: AppContext created");
 
     let app_context = Arc::new(app_context);
@@ -930,11 +2015,44 @@ pub async fn startup(config: ServerConfig) -> Result<(), Box<dyn std::error::Err
         config.router_config.health_check.check_interval_secs
     );
 
+    let _model_version_checker = config
+        .router_config
+        .model_version_check
+        .as_ref()
+        .map(|check| {
+            info!(
+                "Started model version checker with {}s interval, {:?} on mismatch",
+                check.check_interval_secs, check.on_mismatch
+            );
+            model_version::start_model_version_checker(
+                app_context.worker_registry.clone(),
+                check.check_interval_secs,
+            )
+        });
+
+    let _outlier_detector = config
+        .router_config
+        .outlier_detection
+        .as_ref()
+        .map(|outlier| {
+            info!(
+                "Started outlier detector with {}s interval, ejecting for {}s at first",
+                outlier.interval_secs, outlier.base_ejection_secs
+            );
+            outlier_detection::start_outlier_detector(
+                app_context.worker_registry.clone(),
+                outlier.clone(),
+            )
+        });
+
     // Set up concurrency limiter with queue if configured
     let (limiter, processor) = middleware::ConcurrencyLimiter::new(
         app_context.rate_limiter.clone(),
         config.router_config.queue_size,
         Duration::from_secs(config.router_config.queue_timeout_secs),
+        config.router_config.queue_fairness,
+        config.router_config.queue_weights.clone(),
+        Duration::from_secs(config.router_config.queue_priority_aging_secs),
     );
 
     // Start queue processor if enabled
@@ -1005,10 +2123,51 @@ pub async fn startup(config: ServerConfig) -> Result<(), Box<dyn std::error::Err
     let addr = format!("{}:{}", config.host, config.port);
     let listener = TcpListener::bind(&addr).await?;
     info!("Starting server on {}", addr);
-    serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+
+    // Stop accepting connections on the signal, then give requests that are
+    // queued or still streaming until the drain deadline to finish
+    let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+    let mut server = serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = stop_rx.await;
+        })
+        .into_future();
+    tokio::select! {
+        result = &mut server => {
+            return result.map_err(|e| Box::new(e) as Box<dyn std::error::Error>);
+        }
+        _ = shutdown_signal() => {}
+    }
+    let _ = stop_tx.send(());
+
+    let in_flight = app_context.in_flight.clone();
+    let deadline = Duration::from_secs(config.router_config.shutdown_drain_timeout_secs);
+    info!(
+        "Draining {} in-flight requests (deadline {}s)",
+        in_flight.count(),
+        deadline.as_secs()
+    );
+    let drained = tokio::time::timeout(deadline, async {
+        in_flight.wait_idle().await;
+        server.await
+    })
+    .await;
+    match drained {
+        Ok(result) => {
+            result.map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            info!("All in-flight requests finished, shutting down");
+        }
+        Err(_) => {
+            warn!(
+                "Drain deadline of {}s elapsed with {} requests still in flight, shutting down",
+                deadline.as_secs(),
+                in_flight.count()
+            );
+            // Give running batches a moment to record that they were cut off
+            app_context.batch_jobs.cancel_all();
+            let _ = tokio::time::timeout(BATCH_CANCEL_GRACE, in_flight.wait_idle()).await;
+        }
+    }
 
     Ok(())
 }
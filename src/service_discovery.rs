@@ -595,6 +595,8 @@ mod tests {
             client: reqwest::Client::new(),
             router_config: router_config.clone(),
             rate_limiter: Arc::new(TokenBucket::new(1000, 1000)),
+            adaptive_limiter: None,
+            model_rate_limiters: Arc::new(HashMap::new()),
             worker_registry: Arc::new(crate::core::WorkerRegistry::new()),
             policy_registry: Arc::new(crate::policies::PolicyRegistry::new(
                 router_config.policy.clone(),
@@ -602,8 +604,14 @@ mod tests {
             tokenizer: None,      // HTTP mode doesn't need tokenizer
             router_manager: None, // Test doesn't need router manager
             response_storage: Arc::new(crate::data_connector::MemoryResponseStorage::new()),
+            batch_storage: None,
+            batch_jobs: Arc::new(crate::routers::batches::BatchJobs::new()),
             api_key_cache: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
             api_key_validation_urls: Arc::new(Vec::new()),
+            log_level: None,
+            stream_limiter: None,
+            idempotency_cache: None,
+            in_flight: Arc::new(crate::middleware::InFlightRequests::new()),
         });
 
         let router = Router::new(vec![], &app_context).await.unwrap();
@@ -3,24 +3,29 @@ mod common;
 use axum::{
     body::Body,
     extract::Request,
-    http::{header::CONTENT_TYPE, StatusCode},
+    http::{
+        header::{CONTENT_LENGTH, CONTENT_TYPE},
+        StatusCode,
+    },
 };
 use common::mock_worker::{HealthStatus, MockWorker, MockWorkerConfig, WorkerType};
-use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
 use tower::ServiceExt;
 use vllm_router_rs::config::{
-    CircuitBreakerConfig, ConnectionMode, PolicyConfig, RetryConfig, RouterConfig, RoutingMode,
+    AdaptiveConcurrencyConfig, CircuitBreakerConfig, ConnectionMode, HedgingConfig, HistoryBackend,
+    IdempotencyConfig, PolicyConfig, ResponseCacheConfig, RetryConfig, RouterConfig, RoutingMode,
+    SessionCookieConfig, WorkerWarmupConfig,
 };
 use vllm_router_rs::routers::{RouterFactory, RouterTrait};
+use vllm_router_rs::server::AppContext;
 
 /// Test context that manages mock workers
 struct TestContext {
     workers: Vec<MockWorker>,
     router: Arc<dyn RouterTrait>,
-    client: Client,
     config: RouterConfig,
+    app_context: Arc<AppContext>,
 }
 
 impl TestContext {
@@ -35,6 +40,10 @@ impl TestContext {
             port: 3002,
             max_payload_size: 256 * 1024 * 1024,
             request_timeout_secs: 600,
+            proxy_timeout_secs: 10,
+            generation_timeout_secs: None,
+            max_request_timeout_secs: None,
+            slow_request_threshold_secs: None,
             worker_startup_timeout_secs: 1,
             worker_startup_check_interval_secs: 1,
             discovery: None,
@@ -49,9 +58,49 @@ impl TestContext {
             queue_size: 0,
             queue_timeout_secs: 60,
             rate_limit_tokens_per_second: None,
+            model_concurrency_limits: std::collections::HashMap::new(),
+            adaptive_concurrency: None,
+            model_policies: std::collections::HashMap::new(),
+            queue_fairness: vllm_router_rs::config::QueueFairness::Fifo,
+            queue_weights: std::collections::HashMap::new(),
+            queue_priority_aging_secs: 10,
+            model_aliases: std::collections::HashMap::new(),
+            model_fallbacks: std::collections::HashMap::new(),
+            model_traffic_splits: std::collections::HashMap::new(),
+            discovery_stale_secs: 30,
+            advertise_model_aliases: false,
+            rewrite_stream_model: false,
+            strict_model_routing: false,
+            enable_response_compression: false,
+            enable_upstream_compression: false,
+            max_concurrent_streams: None,
+            stream_buffer_size: 64,
+            stream_keepalive_secs: None,
+            shutdown_drain_timeout_secs: 30,
+            batch_max_concurrency: 8,
             cors_allowed_origins: vec![],
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            hedging: None,
+            traffic_mirror: None,
+            worker_rate_limit: None,
+            worker_warmup: None,
+            model_version_check: None,
+            outlier_detection: None,
+            response_cache: None,
+            idempotency: None,
+            header_policy: Default::default(),
+            body_logging: None,
+            allow_worker_pinning: false,
+            expose_routing_headers: false,
+            upstream_auth: None,
+            upstream_tls: None,
+            pd_zone_affinity: false,
+            min_healthy_workers: 1,
+            payload_limits: vllm_router_rs::config::PayloadLimitsConfig::default(),
+            max_prompt_chars: None,
+            default_max_tokens: None,
+            max_tokens_limit: None,
             disable_retries: false,
             disable_circuit_breaker: false,
             health_check: vllm_router_rs::config::HealthCheckConfig::default(),
@@ -59,7 +108,8 @@ impl TestContext {
             connection_mode: ConnectionMode::Http,
             model_path: None,
             tokenizer_path: None,
-            history_backend: vllm_router_rs::config::HistoryBackend::Memory,
+            history_backend: HistoryBackend::Memory,
+            memory_history: vllm_router_rs::config::MemoryHistoryConfig::default(),
             enable_profiling: false,
             profile_timeout_secs: 30,
         };
@@ -96,11 +146,6 @@ impl TestContext {
             }
         }
 
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(config.request_timeout_secs))
-            .build()
-            .unwrap();
-
         // Create app context
         let app_context = common::create_test_context(config.clone());
 
@@ -116,15 +161,15 @@ impl TestContext {
         Self {
             workers,
             router,
-            client,
             config,
+            app_context,
         }
     }
 
     async fn create_app(&self) -> axum::Router {
-        common::test_app::create_test_app(
+        common::test_app::create_test_app_with_context(
             Arc::clone(&self.router),
-            self.client.clone(),
+            Arc::clone(&self.app_context),
             &self.config,
         )
     }
@@ -201,6 +246,53 @@ mod health_tests {
         ctx.shutdown().await;
     }
 
+    #[tokio::test]
+    async fn test_readiness_per_model() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18008,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // Startup workers are registered under the model they report
+        let req = Request::builder()
+            .method("GET")
+            .uri("/readiness?model=mock-model-path")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["model_id"], "mock-model-path");
+        assert_eq!(body_json["healthy_workers"], 1);
+        assert_eq!(body_json["total_workers"], 1);
+
+        // A model without workers is not ready even though the router is
+        let req = Request::builder()
+            .method("GET")
+            .uri("/readiness?model=llama-3")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["status"], "not_ready");
+        assert_eq!(body_json["total_workers"], 0);
+
+        ctx.shutdown().await;
+    }
+
     #[tokio::test]
     async fn test_health_endpoint_details() {
         let ctx = TestContext::new(vec![
@@ -272,6 +364,82 @@ mod health_tests {
 
         ctx.shutdown().await;
     }
+
+    #[tokio::test]
+    async fn test_health_check_expected_body() {
+        use vllm_router_rs::core::{BasicWorker, HealthConfig, Worker};
+
+        // A degraded mock worker answers /health with 200 and {"status": "degraded"}
+        let mut mock = MockWorker::new(MockWorkerConfig {
+            port: 18311,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Degraded,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        });
+        let url = mock.start().await.unwrap();
+
+        let status_only = BasicWorker::new(url.clone(), vllm_router_rs::core::WorkerType::Regular);
+        assert!(status_only.check_health_async().await.is_ok());
+
+        let body_checked = BasicWorker::new(url, vllm_router_rs::core::WorkerType::Regular)
+            .with_health_config(HealthConfig {
+                failure_threshold: 1,
+                expected_body: Some("$.status=healthy".to_string()),
+                ..Default::default()
+            });
+        assert!(body_checked.check_health_async().await.is_err());
+        assert!(!body_checked.is_healthy());
+
+        mock.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_health_check_generation_probe() {
+        use vllm_router_rs::core::{BasicWorker, HealthConfig, Worker};
+
+        // /health answers 200 but every generation request fails
+        let mut mock = MockWorker::new(MockWorkerConfig {
+            port: 18312,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 1.0,
+        });
+        let url = mock.start().await.unwrap();
+
+        let http_only = BasicWorker::new(url.clone(), vllm_router_rs::core::WorkerType::Regular);
+        assert!(http_only.check_health_async().await.is_ok());
+
+        let probed = BasicWorker::new(url, vllm_router_rs::core::WorkerType::Regular)
+            .with_health_config(HealthConfig {
+                failure_threshold: 1,
+                generation_probe: true,
+                ..Default::default()
+            });
+        assert!(probed.check_health_async().await.is_err());
+        assert!(!probed.is_healthy());
+
+        mock.stop().await;
+
+        // A worker that generates passes the probe
+        let mut mock = MockWorker::new(MockWorkerConfig {
+            port: 18313,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        });
+        let url = mock.start().await.unwrap();
+        let probed = BasicWorker::new(url, vllm_router_rs::core::WorkerType::Regular)
+            .with_health_config(HealthConfig {
+                generation_probe: true,
+                ..Default::default()
+            });
+        assert!(probed.check_health_async().await.is_ok());
+
+        mock.stop().await;
+    }
 }
 
 #[cfg(test)]
@@ -355,6 +523,131 @@ mod generation_tests {
         ctx.shutdown().await;
     }
 
+    #[tokio::test]
+    async fn test_multipart_bodies_forwarded_unchanged() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18321,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+        let content_type = "multipart/form-data; boundary=router-test";
+        let body = "--router-test\r\n\
+            Content-Disposition: form-data; name=\"model\"\r\n\r\n\
+            whisper\r\n\
+            --router-test\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"a.wav\"\r\n\
+            Content-Type: audio/wav\r\n\r\n\
+            RIFF\r\n\
+            --router-test--\r\n";
+
+        // The dedicated audio route and the transparent proxy both skip JSON parsing
+        for uri in ["/v1/audio/transcriptions", "/v1/uploads"] {
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(CONTENT_TYPE, content_type)
+                .body(Body::from(body))
+                .unwrap();
+
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK, "{uri}");
+            let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let echoed: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(echoed["content_type"], content_type, "{uri}");
+            assert_eq!(echoed["body"], body, "{uri}");
+        }
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_transparent_proxy_passes_non_json_and_empty_bodies() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18322,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let get = Request::builder()
+            .method("GET")
+            .uri("/v1/uploads")
+            .body(Body::empty())
+            .unwrap();
+        let post_text = Request::builder()
+            .method("POST")
+            .uri("/v1/uploads")
+            .header(CONTENT_TYPE, "text/plain")
+            .body(Body::from("not { json"))
+            .unwrap();
+
+        for (req, content_type, body) in [(get, "", ""), (post_text, "text/plain", "not { json")] {
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let echoed: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(echoed["content_type"], content_type);
+            assert_eq!(echoed["body"], body);
+        }
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_streaming_request_in_flight_until_body_finished() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18320,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+        let in_flight = ctx.app_context.in_flight.clone();
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/generate")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({"text": "Stream test", "stream": true}).to_string(),
+            ))
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            in_flight.count(),
+            1,
+            "a stream is in flight until its body is sent"
+        );
+
+        axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(in_flight.count(), 0);
+        tokio::time::timeout(std::time::Duration::from_secs(1), in_flight.wait_idle())
+            .await
+            .expect("drain completes once the stream is finished");
+
+        ctx.shutdown().await;
+    }
+
     #[tokio::test]
     async fn test_generate_with_worker_failure() {
         let ctx = TestContext::new(vec![MockWorkerConfig {
@@ -571,374 +864,355 @@ mod model_info_tests {
     }
 
     #[tokio::test]
-    async fn test_model_info_with_no_workers() {
-        let ctx = TestContext::new(vec![]).await;
-        let app = ctx.create_app().await;
-
-        // Test server info with no workers
-        let req = Request::builder()
-            .method("GET")
-            .uri("/get_server_info")
-            .body(Body::empty())
-            .unwrap();
-        let resp = app.clone().oneshot(req).await.unwrap();
-        // Router may return various error codes when no workers
-        assert!(
-            resp.status() == StatusCode::OK
-                || resp.status() == StatusCode::SERVICE_UNAVAILABLE
-                || resp.status() == StatusCode::NOT_FOUND
-                || resp.status() == StatusCode::INTERNAL_SERVER_ERROR,
-            "Unexpected status code: {:?}",
-            resp.status()
-        );
-
-        // Test model info with no workers
-        let req = Request::builder()
-            .method("GET")
-            .uri("/get_model_info")
-            .body(Body::empty())
-            .unwrap();
-        let resp = app.clone().oneshot(req).await.unwrap();
-        // Router may return various error codes when no workers
-        assert!(
-            resp.status() == StatusCode::OK
-                || resp.status() == StatusCode::SERVICE_UNAVAILABLE
-                || resp.status() == StatusCode::NOT_FOUND
-                || resp.status() == StatusCode::INTERNAL_SERVER_ERROR,
-            "Unexpected status code: {:?}",
-            resp.status()
-        );
-
-        // Test v1/models with no workers
-        let req = Request::builder()
-            .method("GET")
-            .uri("/v1/models")
-            .body(Body::empty())
-            .unwrap();
-        let resp = app.oneshot(req).await.unwrap();
-        // Router may return various error codes when no workers
-        assert!(
-            resp.status() == StatusCode::OK
-                || resp.status() == StatusCode::SERVICE_UNAVAILABLE
-                || resp.status() == StatusCode::NOT_FOUND
-                || resp.status() == StatusCode::INTERNAL_SERVER_ERROR,
-            "Unexpected status code: {:?}",
-            resp.status()
-        );
-
-        ctx.shutdown().await;
-    }
-
-    #[tokio::test]
-    async fn test_model_info_with_multiple_workers() {
+    async fn test_v1_models_merges_across_workers() {
         let ctx = TestContext::new(vec![
             MockWorkerConfig {
-                port: 18204,
+                port: 18613,
                 worker_type: WorkerType::Regular,
                 health_status: HealthStatus::Healthy,
                 response_delay_ms: 0,
                 fail_rate: 0.0,
             },
             MockWorkerConfig {
-                port: 18205,
+                port: 18614,
                 worker_type: WorkerType::Regular,
                 health_status: HealthStatus::Healthy,
                 response_delay_ms: 0,
                 fail_rate: 0.0,
             },
+            MockWorkerConfig {
+                port: 18615,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 1.0, // Always fail
+            },
         ])
         .await;
 
         let app = ctx.create_app().await;
 
-        // Test that model info is consistent across workers
-        for _ in 0..5 {
-            let req = Request::builder()
-                .method("GET")
-                .uri("/get_model_info")
-                .body(Body::empty())
-                .unwrap();
-
-            let resp = app.clone().oneshot(req).await.unwrap();
-            assert_eq!(resp.status(), StatusCode::OK);
-
-            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-                .await
-                .unwrap();
-            let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-            assert_eq!(
-                body_json.get("model_path").and_then(|v| v.as_str()),
-                Some("mock-model-path")
-            );
-        }
-
-        ctx.shutdown().await;
-    }
-
-    #[tokio::test]
-    async fn test_model_info_with_unhealthy_worker() {
-        let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18206,
-            worker_type: WorkerType::Regular,
-            health_status: HealthStatus::Healthy,
-            response_delay_ms: 0,
-            fail_rate: 1.0, // Always fail
-        }])
-        .await;
-
-        let app = ctx.create_app().await;
-
         let req = Request::builder()
             .method("GET")
-            .uri("/get_model_info")
-            .body(Body::empty())
-            .unwrap();
-
-        let resp = app.oneshot(req).await.unwrap();
-        // Worker with fail_rate: 1.0 should always return an error status
-        assert!(
-            resp.status() == StatusCode::INTERNAL_SERVER_ERROR
-                || resp.status() == StatusCode::SERVICE_UNAVAILABLE,
-            "Expected error status for always-failing worker, got: {:?}",
-            resp.status()
-        );
-
-        ctx.shutdown().await;
-    }
-}
-
-#[cfg(test)]
-mod worker_management_tests {
-    use super::*;
-
-    #[tokio::test]
-    async fn test_add_new_worker() {
-        let ctx = TestContext::new(vec![]).await;
-        let app = ctx.create_app().await;
-
-        // Start a mock worker
-        let mut worker = MockWorker::new(MockWorkerConfig {
-            port: 18301,
-            worker_type: WorkerType::Regular,
-            health_status: HealthStatus::Healthy,
-            response_delay_ms: 0,
-            fail_rate: 0.0,
-        });
-        let url = worker.start().await.unwrap();
-
-        // Add the worker
-        let req = Request::builder()
-            .method("POST")
-            .uri(format!("/add_worker?url={}", url))
-            .body(Body::empty())
-            .unwrap();
-
-        let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
-
-        // List workers to verify
-        let req = Request::builder()
-            .method("GET")
-            .uri("/list_workers")
+            .uri("/v1/models")
             .body(Body::empty())
             .unwrap();
 
+        // The failing worker is skipped and the shared model listed once
         let resp = app.oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
-
         let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
             .await
             .unwrap();
         let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-        let workers = body_json["urls"].as_array().unwrap();
-        assert!(workers.iter().any(|w| w.as_str().unwrap() == url));
+        assert_eq!(body_json["object"], "list");
+        let models = body_json["data"].as_array().unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0]["id"], "mock-model");
 
-        worker.stop().await;
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_remove_existing_worker() {
-        let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18302,
-            worker_type: WorkerType::Regular,
-            health_status: HealthStatus::Healthy,
-            response_delay_ms: 0,
-            fail_rate: 0.0,
-        }])
+    async fn test_v1_models_advertises_aliases() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            model_aliases: [
+                ("gpt-4o".to_string(), "mock-model".to_string()),
+                ("unserved".to_string(), "missing-model".to_string()),
+            ]
+            .into(),
+            advertise_model_aliases: true,
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18210,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
         .await;
 
         let app = ctx.create_app().await;
 
-        // Get the worker URL
         let req = Request::builder()
             .method("GET")
-            .uri("/list_workers")
+            .uri("/v1/models")
             .body(Body::empty())
             .unwrap();
         let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
         let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
             .await
             .unwrap();
         let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-        let workers = body_json["urls"].as_array().unwrap();
-        let worker_url = workers[0].as_str().unwrap();
+        let ids: Vec<_> = body_json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|model| model["id"].as_str().unwrap())
+            .collect();
+        // Aliases of models no worker serves are not listed
+        assert_eq!(ids, vec!["mock-model", "gpt-4o"]);
 
-        // Remove the worker
+        // Requests naming the alias are served by the target model's workers
+        let payload = json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "Hello"}],
+            "stream": false
+        });
         let req = Request::builder()
             .method("POST")
-            .uri(format!("/remove_worker?url={}", worker_url))
-            .body(Body::empty())
+            .uri("/v1/chat/completions")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
             .unwrap();
-
-        let resp = app.clone().oneshot(req).await.unwrap();
+        let resp = app.oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
 
-        // Verify it's removed
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_stream_model_rewritten_to_requested_alias() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            model_aliases: [("gpt-4o".to_string(), "mock-model".to_string())].into(),
+            rewrite_stream_model: true,
+            strict_model_routing: false,
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18327,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let payload = json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "Hello"}],
+            "stream": true
+        });
         let req = Request::builder()
-            .method("GET")
-            .uri("/list_workers")
-            .body(Body::empty())
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
             .unwrap();
         let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
         let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
             .await
             .unwrap();
-        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-        let workers = body_json["urls"].as_array().unwrap();
-        assert!(workers.is_empty());
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        // The worker names its own model; every chunk names the alias instead
+        let models: Vec<String> = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .filter_map(|payload| serde_json::from_str::<serde_json::Value>(payload).ok())
+            .map(|chunk| chunk["model"].as_str().unwrap().to_string())
+            .collect();
+        assert!(!models.is_empty());
+        assert!(models.iter().all(|model| model == "gpt-4o"));
+        assert!(body.contains("data: [DONE]"));
 
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_add_worker_invalid_url() {
-        let ctx = TestContext::new(vec![]).await;
-        let app = ctx.create_app().await;
+    async fn test_mirrored_request_does_not_delay_response() {
+        use vllm_router_rs::config::TrafficMirrorConfig;
 
-        // Invalid URL format
-        let req = Request::builder()
-            .method("POST")
-            .uri("/add_worker?url=not-a-valid-url")
-            .body(Body::empty())
-            .unwrap();
-
-        let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        // A slow, failing shadow worker must not affect the client
+        let mut shadow = MockWorker::new(MockWorkerConfig {
+            port: 18329,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 3000,
+            fail_rate: 1.0,
+        });
+        let shadow_url = shadow.start().await.unwrap();
 
-        // Missing URL parameter
-        let req = Request::builder()
-            .method("POST")
-            .uri("/add_worker")
-            .body(Body::empty())
-            .unwrap();
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            traffic_mirror: Some(TrafficMirrorConfig {
+                worker_urls: vec![shadow_url],
+                fraction: 1.0,
+                timeout_secs: 10,
+            }),
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18328,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
 
-        let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let app = ctx.create_app().await;
 
-        // Empty URL
+        let payload = json!({
+            "model": "mock-model",
+            "messages": [{"role": "user", "content": "Hello"}],
+            "stream": false
+        });
         let req = Request::builder()
             .method("POST")
-            .uri("/add_worker?url=")
-            .body(Body::empty())
+            .uri("/v1/chat/completions")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
             .unwrap();
-
+        let start = std::time::Instant::now();
         let resp = app.oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
 
         ctx.shutdown().await;
+        shadow.stop().await;
     }
 
     #[tokio::test]
-    async fn test_add_duplicate_worker() {
-        // Start a mock worker
-        let mut worker = MockWorker::new(MockWorkerConfig {
-            port: 18303,
-            worker_type: WorkerType::Regular,
-            health_status: HealthStatus::Healthy,
-            response_delay_ms: 0,
-            fail_rate: 0.0,
-        });
-        let url = worker.start().await.unwrap();
+    async fn test_response_compression_follows_accept_encoding() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            enable_response_compression: true,
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18211,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
 
-        let ctx = TestContext::new(vec![]).await;
         let app = ctx.create_app().await;
 
-        // Add worker first time
         let req = Request::builder()
-            .method("POST")
-            .uri(format!("/add_worker?url={}", url))
+            .method("GET")
+            .uri("/v1/models")
+            .header("accept-encoding", "gzip")
             .body(Body::empty())
             .unwrap();
         let resp = app.clone().oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()["content-encoding"], "gzip");
 
-        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-        // Try to add same worker again
+        // Clients that do not ask for compression get a plain body
         let req = Request::builder()
-            .method("POST")
-            .uri(format!("/add_worker?url={}", url))
+            .method("GET")
+            .uri("/v1/models")
             .body(Body::empty())
             .unwrap();
         let resp = app.oneshot(req).await.unwrap();
-        // Should return error for duplicate
-        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get("content-encoding").is_none());
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(body_json["data"].is_array());
 
-        worker.stop().await;
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_add_unhealthy_worker() {
-        // Start unhealthy worker
-        let mut worker = MockWorker::new(MockWorkerConfig {
-            port: 18304,
-            worker_type: WorkerType::Regular,
-            health_status: HealthStatus::Unhealthy,
-            response_delay_ms: 0,
-            fail_rate: 0.0,
-        });
-        let url = worker.start().await.unwrap();
-
+    async fn test_model_info_with_no_workers() {
         let ctx = TestContext::new(vec![]).await;
         let app = ctx.create_app().await;
 
-        // Try to add unhealthy worker
+        // Test server info with no workers
         let req = Request::builder()
-            .method("POST")
-            .uri(format!("/add_worker?url={}", url))
+            .method("GET")
+            .uri("/get_server_info")
             .body(Body::empty())
             .unwrap();
-        let resp = app.oneshot(req).await.unwrap();
-
-        // Router should reject unhealthy workers
+        let resp = app.clone().oneshot(req).await.unwrap();
+        // Router may return various error codes when no workers
         assert!(
-            resp.status() == StatusCode::BAD_REQUEST
+            resp.status() == StatusCode::OK
                 || resp.status() == StatusCode::SERVICE_UNAVAILABLE
-        );
+                || resp.status() == StatusCode::NOT_FOUND
+                || resp.status() == StatusCode::INTERNAL_SERVER_ERROR,
+            "Unexpected status code: {:?}",
+            resp.status()
+        );
+
+        // Test model info with no workers
+        let req = Request::builder()
+            .method("GET")
+            .uri("/get_model_info")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        // Router may return various error codes when no workers
+        assert!(
+            resp.status() == StatusCode::OK
+                || resp.status() == StatusCode::SERVICE_UNAVAILABLE
+                || resp.status() == StatusCode::NOT_FOUND
+                || resp.status() == StatusCode::INTERNAL_SERVER_ERROR,
+            "Unexpected status code: {:?}",
+            resp.status()
+        );
+
+        // Test v1/models with no workers
+        let req = Request::builder()
+            .method("GET")
+            .uri("/v1/models")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        // Router may return various error codes when no workers
+        assert!(
+            resp.status() == StatusCode::OK
+                || resp.status() == StatusCode::SERVICE_UNAVAILABLE
+                || resp.status() == StatusCode::NOT_FOUND
+                || resp.status() == StatusCode::INTERNAL_SERVER_ERROR,
+            "Unexpected status code: {:?}",
+            resp.status()
+        );
 
-        worker.stop().await;
         ctx.shutdown().await;
     }
-}
-
-#[cfg(test)]
-mod router_policy_tests {
-    use super::*;
 
     #[tokio::test]
-    async fn test_random_policy() {
+    async fn test_model_info_with_multiple_workers() {
         let ctx = TestContext::new(vec![
             MockWorkerConfig {
-                port: 18801,
+                port: 18204,
                 worker_type: WorkerType::Regular,
                 health_status: HealthStatus::Healthy,
                 response_delay_ms: 0,
                 fail_rate: 0.0,
             },
             MockWorkerConfig {
-                port: 18802,
+                port: 18205,
                 worker_type: WorkerType::Regular,
                 health_status: HealthStatus::Healthy,
                 response_delay_ms: 0,
@@ -947,63 +1221,67 @@ mod router_policy_tests {
         ])
         .await;
 
-        // Send multiple requests and verify they succeed
         let app = ctx.create_app().await;
 
-        for i in 0..10 {
-            let payload = json!({
-                "text": format!("Request {}", i),
-                "stream": false
-            });
-
+        // Test that model info is consistent across workers
+        for _ in 0..5 {
             let req = Request::builder()
-                .method("POST")
-                .uri("/generate")
-                .header(CONTENT_TYPE, "application/json")
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .method("GET")
+                .uri("/get_model_info")
+                .body(Body::empty())
                 .unwrap();
 
             let resp = app.clone().oneshot(req).await.unwrap();
             assert_eq!(resp.status(), StatusCode::OK);
+
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(
+                body_json.get("model_path").and_then(|v| v.as_str()),
+                Some("mock-model-path")
+            );
         }
 
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_worker_selection() {
+    async fn test_model_info_with_unhealthy_worker() {
         let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18207,
+            port: 18206,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
             response_delay_ms: 0,
-            fail_rate: 0.0,
+            fail_rate: 1.0, // Always fail
         }])
         .await;
 
-        let _payload = json!({
-            "text": "Test selection",
-            "stream": false
-        });
+        let app = ctx.create_app().await;
 
-        // Check that router has the worker
-        let worker_urls = ctx.router.get_worker_urls();
-        assert_eq!(worker_urls.len(), 1);
-        assert!(worker_urls[0].contains("18207"));
+        let req = Request::builder()
+            .method("GET")
+            .uri("/get_model_info")
+            .body(Body::empty())
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        // Worker with fail_rate: 1.0 should always return an error status
+        assert!(
+            resp.status() == StatusCode::INTERNAL_SERVER_ERROR
+                || resp.status() == StatusCode::SERVICE_UNAVAILABLE,
+            "Expected error status for always-failing worker, got: {:?}",
+            resp.status()
+        );
 
         ctx.shutdown().await;
     }
-}
-
-#[cfg(test)]
-mod responses_endpoint_tests {
-    use super::*;
-    use reqwest::Client as HttpClient;
 
     #[tokio::test]
-    async fn test_v1_responses_non_streaming() {
+    async fn test_tokenize_without_tokenizer_returns_404() {
         let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18950,
+            port: 18208,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
             response_delay_ms: 0,
@@ -1013,175 +1291,220 @@ mod responses_endpoint_tests {
 
         let app = ctx.create_app().await;
 
-        let payload = json!({
-            "input": "Hello Responses API",
-            "model": "mock-model",
-            "stream": false
-        });
-
-        let req = Request::builder()
-            .method("POST")
-            .uri("/v1/responses")
-            .header(CONTENT_TYPE, "application/json")
-            .body(Body::from(serde_json::to_string(&payload).unwrap()))
-            .unwrap();
-
-        let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
+        for (uri, payload) in [
+            ("/tokenize", json!({"prompt": "Hello world"})),
+            ("/detokenize", json!({"tokens": [1, 2]})),
+        ] {
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap();
 
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-        assert_eq!(body_json["object"], "response");
-        assert_eq!(body_json["status"], "completed");
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body_json["code"], "TOKENIZER_NOT_AVAILABLE");
+        }
 
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_v1_responses_streaming() {
-        let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18951,
+    async fn test_tokenize_and_detokenize_with_loaded_tokenizer() {
+        let mut ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18209,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
             response_delay_ms: 0,
             fail_rate: 0.0,
         }])
         .await;
+        ctx.app_context = Arc::new(AppContext {
+            tokenizer: Some(Arc::new(
+                vllm_router_rs::tokenizer::mock::MockTokenizer::new(),
+            )),
+            ..(*ctx.app_context).clone()
+        });
 
         let app = ctx.create_app().await;
 
-        let payload = json!({
-            "input": "Hello Responses API",
-            "model": "mock-model",
-            "stream": true
-        });
-
         let req = Request::builder()
             .method("POST")
-            .uri("/v1/responses")
+            .uri("/tokenize")
             .header(CONTENT_TYPE, "application/json")
-            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .body(Body::from(
+                json!({"model": "test-model", "prompt": "Hello world"}).to_string(),
+            ))
             .unwrap();
-
         let resp = app.clone().oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["tokens"], json!([1, 2]));
+        assert_eq!(body_json["count"], 2);
 
-        // Check that content-type indicates SSE
-        let headers = resp.headers().clone();
-        let ct = headers
-            .get("content-type")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        assert!(ct.contains("text/event-stream"));
+        let req = Request::builder()
+            .method("POST")
+            .uri("/detokenize")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(json!({"tokens": [1, 2]}).to_string()))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["prompt"], "Hello world");
 
-        // We don't fully consume the stream in this test harness.
         ctx.shutdown().await;
     }
+}
+
+#[cfg(test)]
+mod worker_management_tests {
+    use super::*;
 
     #[tokio::test]
-    async fn test_v1_responses_get() {
-        let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18952,
+    async fn test_add_new_worker() {
+        let ctx = TestContext::new(vec![]).await;
+        let app = ctx.create_app().await;
+
+        // Start a mock worker
+        let mut worker = MockWorker::new(MockWorkerConfig {
+            port: 18301,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
             response_delay_ms: 0,
             fail_rate: 0.0,
-        }])
-        .await;
-
-        let app = ctx.create_app().await;
-
-        // First create a response to obtain an id
-        let resp_id = "test-get-resp-id-123";
-        let payload = json!({
-            "input": "Hello Responses API",
-            "model": "mock-model",
-            "stream": false,
-            "store": true,
-            "background": true,
-            "request_id": resp_id
         });
+        let url = worker.start().await.unwrap();
+
+        // Add the worker
         let req = Request::builder()
             .method("POST")
-            .uri("/v1/responses")
-            .header(CONTENT_TYPE, "application/json")
-            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .uri(format!("/add_worker?url={}", url))
+            .body(Body::empty())
             .unwrap();
+
         let resp = app.clone().oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
 
-        // Retrieve the response
+        // List workers to verify
         let req = Request::builder()
             .method("GET")
-            .uri(format!("/v1/responses/{}", resp_id))
+            .uri("/list_workers")
             .body(Body::empty())
             .unwrap();
-        let resp = app.clone().oneshot(req).await.unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
+
         let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
             .await
             .unwrap();
-        let get_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-        assert_eq!(get_json["object"], "response");
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let workers = body_json["urls"].as_array().unwrap();
+        assert!(workers.iter().any(|w| w.as_str().unwrap() == url));
 
+        worker.stop().await;
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_v1_responses_cancel() {
-        let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18953,
+    async fn test_added_worker_warms_up_before_selection() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 5,
+            worker_startup_check_interval_secs: 1,
+            worker_warmup: Some(WorkerWarmupConfig {
+                requests: 2,
+                prompt: "Hello".to_string(),
+            }),
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(config, vec![]).await;
+        let app = ctx.create_app().await;
+
+        // Health checks answer right away; each warmup request takes 200ms
+        let mut worker = MockWorker::new(MockWorkerConfig {
+            port: 18316,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
-            response_delay_ms: 0,
+            response_delay_ms: 200,
             fail_rate: 0.0,
-        }])
-        .await;
+        });
+        let url = worker.start().await.unwrap();
 
-        let app = ctx.create_app().await;
+        let add = tokio::spawn({
+            let app = app.clone();
+            let req = Request::builder()
+                .method("POST")
+                .uri(format!("/add_worker?url={}", url))
+                .body(Body::empty())
+                .unwrap();
+            async move { app.oneshot(req).await.unwrap() }
+        });
 
-        // First create a response to obtain an id
-        let resp_id = "test-cancel-resp-id-456";
-        let payload = json!({
-            "input": "Hello Responses API",
-            "model": "mock-model",
-            "stream": false,
-            "store": true,
-            "background": true,
-            "request_id": resp_id
-        });
-        let req = Request::builder()
-            .method("POST")
-            .uri("/v1/responses")
-            .header(CONTENT_TYPE, "application/json")
-            .body(Body::from(serde_json::to_string(&payload).unwrap()))
-            .unwrap();
-        let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
+        let stats = || async {
+            let req = Request::builder()
+                .method("GET")
+                .uri("/workers/stats")
+                .body(Body::empty())
+                .unwrap();
+            let resp = app.clone().oneshot(req).await.unwrap();
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+        let completion = || {
+            Request::builder()
+                .method("POST")
+                .uri("/v1/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    json!({"model": "mock-model-path", "prompt": "Hi"}).to_string(),
+                ))
+                .unwrap()
+        };
 
-        // Cancel the response
-        let req = Request::builder()
-            .method("POST")
-            .uri(format!("/v1/responses/{}/cancel", resp_id))
-            .body(Body::empty())
-            .unwrap();
-        let resp = app.clone().oneshot(req).await.unwrap();
+        // Listed as warming while the warmup requests run, but not selected
+        let mut listed = None;
+        for _ in 0..50 {
+            let stats = stats().await;
+            if stats["total"] == 1 {
+                listed = Some(stats);
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let listed = listed.expect("worker is listed while warming up");
+        assert_eq!(listed["workers"][0]["url"], url);
+        assert_eq!(listed["workers"][0]["is_warming"], true);
+        let resp = app.clone().oneshot(completion()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let resp = add.await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(stats().await["workers"][0]["is_warming"], false);
+        let resp = app.clone().oneshot(completion()).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let cancel_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-        assert_eq!(cancel_json["status"], "cancelled");
 
+        worker.stop().await;
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_v1_responses_delete_and_list_not_implemented() {
+    async fn test_remove_existing_worker() {
         let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18954,
+            port: 18302,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
             response_delay_ms: 0,
@@ -1191,103 +1514,112 @@ mod responses_endpoint_tests {
 
         let app = ctx.create_app().await;
 
-        // Use an arbitrary id for delete/list
-        let resp_id = "resp-test-123";
+        // Get the worker URL
+        let req = Request::builder()
+            .method("GET")
+            .uri("/list_workers")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let workers = body_json["urls"].as_array().unwrap();
+        let worker_url = workers[0].as_str().unwrap();
 
+        // Remove the worker
         let req = Request::builder()
-            .method("DELETE")
-            .uri(format!("/v1/responses/{}", resp_id))
+            .method("POST")
+            .uri(format!("/remove_worker?url={}", worker_url))
             .body(Body::empty())
             .unwrap();
+
         let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::NOT_IMPLEMENTED);
+        assert_eq!(resp.status(), StatusCode::OK);
 
+        // Verify it's removed
         let req = Request::builder()
             .method("GET")
-            .uri(format!("/v1/responses/{}/input", resp_id))
+            .uri("/list_workers")
             .body(Body::empty())
             .unwrap();
-        let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::NOT_IMPLEMENTED);
+        let resp = app.oneshot(req).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let workers = body_json["urls"].as_array().unwrap();
+        assert!(workers.is_empty());
 
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_v1_responses_get_multi_worker_fanout() {
-        // Start two mock workers
-        let ctx = TestContext::new(vec![
-            MockWorkerConfig {
-                port: 18960,
-                worker_type: WorkerType::Regular,
-                health_status: HealthStatus::Healthy,
-                response_delay_ms: 0,
-                fail_rate: 0.0,
-            },
-            MockWorkerConfig {
-                port: 18961,
-                worker_type: WorkerType::Regular,
-                health_status: HealthStatus::Healthy,
-                response_delay_ms: 0,
-                fail_rate: 0.0,
-            },
-        ])
+    async fn test_drain_worker_waits_for_in_flight_request() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18305,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 500,
+            fail_rate: 0.0,
+        }])
         .await;
 
         let app = ctx.create_app().await;
+        let worker_url = ctx.router.get_worker_urls()[0].clone();
+        let encoded_url = worker_url.replace(':', "%3A").replace('/', "%2F");
 
-        // Create a background response with a known id
-        let rid = format!("resp_{}", 18960); // arbitrary unique id
-        let payload = json!({
-            "input": "Hello Responses API",
-            "model": "mock-model",
-            "background": true,
-            "store": true,
-            "request_id": rid,
-        });
-
-        let req = Request::builder()
+        let generate = Request::builder()
             .method("POST")
-            .uri("/v1/responses")
+            .uri("/generate")
             .header(CONTENT_TYPE, "application/json")
-            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .body(Body::from(
+                serde_json::to_string(&json!({"text": "Hello", "stream": false})).unwrap(),
+            ))
+            .unwrap();
+        let drain = Request::builder()
+            .method("POST")
+            .uri(format!("/workers/{}/drain?timeout_secs=5", encoded_url))
+            .body(Body::empty())
             .unwrap();
-        let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
 
-        // Using the router, GET should succeed by fanning out across workers
+        let (generate_resp, drain_resp) = tokio::join!(app.clone().oneshot(generate), async {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            app.clone().oneshot(drain).await
+        });
+
+        // The in-flight request completes before the worker is removed
+        assert_eq!(generate_resp.unwrap().status(), StatusCode::OK);
+        let drain_resp = drain_resp.unwrap();
+        assert_eq!(drain_resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(drain_resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["success"], true);
+        assert!(body_json["message"]
+            .as_str()
+            .unwrap()
+            .contains("drained and removed"));
+        assert!(ctx.router.get_worker_urls().is_empty());
+
+        // Draining an unknown worker fails
         let req = Request::builder()
-            .method("GET")
-            .uri(format!("/v1/responses/{}", rid))
+            .method("POST")
+            .uri(format!("/workers/{}/drain", encoded_url))
             .body(Body::empty())
             .unwrap();
-        let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
-
-        // Validate only one worker holds the metadata: direct calls
-        let client = HttpClient::new();
-        let mut ok_count = 0usize;
-        for url in ctx.router.get_worker_urls() {
-            let get_url = format!("{}/v1/responses/{}", url, rid);
-            let res = client.get(get_url).send().await.unwrap();
-            if res.status() == StatusCode::OK {
-                ok_count += 1;
-            }
-        }
-        assert_eq!(ok_count, 1, "exactly one worker should store the response");
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
         ctx.shutdown().await;
     }
-}
-
-#[cfg(test)]
-mod error_tests {
-    use super::*;
 
     #[tokio::test]
-    async fn test_404_not_found() {
+    async fn test_disable_and_enable_worker() {
         let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18401,
+            port: 18324,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
             response_delay_ms: 0,
@@ -1296,27 +1628,59 @@ mod error_tests {
         .await;
 
         let app = ctx.create_app().await;
+        let worker_url = ctx.router.get_worker_urls()[0].clone();
+        let encoded_url = worker_url.replace(':', "%3A").replace('/', "%2F");
+
+        let generate = || {
+            Request::builder()
+                .method("POST")
+                .uri("/generate")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    serde_json::to_string(&json!({"text": "Hello", "stream": false})).unwrap(),
+                ))
+                .unwrap()
+        };
+        let post = |action: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/workers/{}/{}", encoded_url, action))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let resp = app.clone().oneshot(post("disable")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // A disabled worker is never selected but stays registered
+        let resp = app.clone().oneshot(generate()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(ctx.router.get_worker_urls(), vec![worker_url.clone()]);
 
-        // Test unknown endpoint
         let req = Request::builder()
             .method("GET")
-            .uri("/unknown_endpoint")
+            .uri("/workers/stats")
             .body(Body::empty())
             .unwrap();
-
         let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["workers"][0]["is_enabled"], false);
+        assert_eq!(body_json["workers"][0]["is_healthy"], true);
 
-        // Test POST to unknown endpoint
+        let resp = app.clone().oneshot(post("enable")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = app.clone().oneshot(generate()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Unknown workers are reported as such
         let req = Request::builder()
             .method("POST")
-            .uri("/api/v2/generate")
-            .header(CONTENT_TYPE, "application/json")
-            .body(Body::from(
-                serde_json::to_string(&json!({"text": "test"})).unwrap(),
-            ))
+            .uri("/workers/http%3A%2F%2Funknown%3A8000/disable")
+            .body(Body::empty())
             .unwrap();
-
         let resp = app.oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 
@@ -1324,9 +1688,9 @@ mod error_tests {
     }
 
     #[tokio::test]
-    async fn test_method_not_allowed() {
+    async fn test_worker_stats_snapshot() {
         let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18402,
+            port: 18306,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
             response_delay_ms: 0,
@@ -1335,96 +1699,113 @@ mod error_tests {
         .await;
 
         let app = ctx.create_app().await;
+        let worker_url = ctx.router.get_worker_urls()[0].clone();
 
-        // GET request to POST-only endpoint
-        let req = Request::builder()
-            .method("GET")
+        let generate = Request::builder()
+            .method("POST")
             .uri("/generate")
-            .body(Body::empty())
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                serde_json::to_string(&json!({"text": "Hello", "stream": false})).unwrap(),
+            ))
             .unwrap();
+        let resp = app.clone().oneshot(generate).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
 
-        let resp = app.clone().oneshot(req).await.unwrap();
-        // Note: Axum returns 405 for wrong methods on matched routes
-        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
-
-        // POST request to GET-only endpoint
         let req = Request::builder()
-            .method("POST")
-            .uri("/health")
-            .header(CONTENT_TYPE, "application/json")
-            .body(Body::from("{}"))
+            .method("GET")
+            .uri("/workers/stats")
+            .body(Body::empty())
             .unwrap();
-
         let resp = app.oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["total"], 1);
+        let worker = &body_json["workers"][0];
+        assert_eq!(worker["url"], worker_url);
+        assert_eq!(worker["worker_type"], "regular");
+        assert_eq!(worker["is_healthy"], true);
+        assert_eq!(worker["is_draining"], false);
+        assert_eq!(worker["load"], 0);
+        assert_eq!(worker["circuit_breaker_state"], "closed");
+        assert!(worker["processed_requests"].is_number());
+        assert!(worker["priority"].is_number());
+        assert!(worker["cost"].is_number());
 
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_payload_too_large() {
-        // Create context with small payload limit
-        let config = RouterConfig {
-            mode: RoutingMode::Regular {
-                worker_urls: vec![],
+    async fn test_list_workers_filters_and_pages() {
+        let ctx = TestContext::new(vec![
+            MockWorkerConfig {
+                port: 18314,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
             },
-            policy: PolicyConfig::Random,
-            host: "127.0.0.1".to_string(),
-            port: 3010,
-            max_payload_size: 1024, // 1KB limit
-            request_timeout_secs: 600,
-            worker_startup_timeout_secs: 1,
-            worker_startup_check_interval_secs: 1,
-            intra_node_data_parallel_size: 1,
-            api_key: None,
-            api_key_validation_urls: vec![],
-            discovery: None,
-            metrics: None,
-            log_dir: None,
-            log_level: None,
-            request_id_headers: None,
-            max_concurrent_requests: 64,
-            queue_size: 0,
-            queue_timeout_secs: 60,
-            rate_limit_tokens_per_second: None,
-            cors_allowed_origins: vec![],
-            retry: RetryConfig::default(),
-            circuit_breaker: CircuitBreakerConfig::default(),
-            disable_retries: false,
-            disable_circuit_breaker: false,
-            health_check: vllm_router_rs::config::HealthCheckConfig::default(),
-            enable_igw: false,
-            connection_mode: ConnectionMode::Http,
-            model_path: None,
-            tokenizer_path: None,
-            history_backend: vllm_router_rs::config::HistoryBackend::Memory,
-            enable_profiling: false,
-            profile_timeout_secs: 30,
-        };
-
-        let ctx = TestContext::new_with_config(
-            config,
-            vec![MockWorkerConfig {
-                port: 18403,
+            MockWorkerConfig {
+                port: 18315,
                 worker_type: WorkerType::Regular,
                 health_status: HealthStatus::Healthy,
                 response_delay_ms: 0,
                 fail_rate: 0.0,
-            }],
-        )
+            },
+        ])
         .await;
 
-        // Note: The server would have payload size middleware configured
-        // but we cannot test it directly through the test app
-        // This test is kept for documentation purposes
+        let app = ctx.create_app().await;
+        let mut urls = ctx.router.get_worker_urls();
+        urls.sort();
+
+        let list = |uri: &str| {
+            let app = app.clone();
+            let req = Request::builder()
+                .method("GET")
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            async move {
+                let resp = app.oneshot(req).await.unwrap();
+                let status = resp.status();
+                let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                (status, body)
+            }
+        };
+
+        let (status, body) = list("/workers?type=regular&healthy=true&limit=1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 2, "total counts every match before paging");
+        assert_eq!(body["workers"].as_array().unwrap().len(), 1);
+        assert_eq!(body["workers"][0]["url"], urls[0]);
+
+        let (_, body) = list("/workers?limit=1&offset=1").await;
+        assert_eq!(body["workers"][0]["url"], urls[1]);
+
+        let (_, body) = list("/workers?type=decode").await;
+        assert_eq!(body["total"], 0);
+
+        let (_, body) = list("/workers?healthy=false").await;
+        assert_eq!(body["total"], 0);
+
+        let (status, _) = list("/workers?type=gpu").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
 
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_invalid_json_payload() {
+    async fn test_worker_circuit_inspect_and_reset() {
         let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18404,
+            port: 18308,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
             response_delay_ms: 0,
@@ -1433,223 +1814,438 @@ mod error_tests {
         .await;
 
         let app = ctx.create_app().await;
+        let worker_url = ctx.router.get_worker_urls()[0].clone();
+        let encoded_url = worker_url.replace(':', "%3A").replace('/', "%2F");
 
-        // Send invalid JSON
         let req = Request::builder()
-            .method("POST")
-            .uri("/generate")
-            .header(CONTENT_TYPE, "application/json")
-            .body(Body::from("{invalid json}"))
+            .method("GET")
+            .uri(format!("/workers/{}/circuit", encoded_url))
+            .body(Body::empty())
             .unwrap();
-
         let resp = app.clone().oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["url"], worker_url);
+        assert_eq!(body_json["state"], "closed");
+        assert_eq!(body_json["consecutive_failures"], 0);
 
-        // Send empty body
         let req = Request::builder()
             .method("POST")
-            .uri("/generate")
-            .header(CONTENT_TYPE, "application/json")
+            .uri(format!("/workers/{}/circuit/reset", encoded_url))
             .body(Body::empty())
             .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["state"], "closed");
+        assert_eq!(body_json["consecutive_failures"], 0);
+        assert_eq!(body_json["consecutive_successes"], 0);
 
+        // Unknown workers are reported as not found
+        let req = Request::builder()
+            .method("POST")
+            .uri("/workers/http%3A%2F%2Funknown%3A9999/circuit/reset")
+            .body(Body::empty())
+            .unwrap();
         let resp = app.oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_missing_required_fields() {
-        let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18405,
-            worker_type: WorkerType::Regular,
-            health_status: HealthStatus::Healthy,
-            response_delay_ms: 0,
-            fail_rate: 0.0,
-        }])
-        .await;
-
+    async fn test_log_level_unavailable_without_router_logging() {
+        let ctx = TestContext::new(vec![]).await;
         let app = ctx.create_app().await;
 
-        // Missing messages in chat completion
-        let payload = json!({
-            "model": "test-model"
-            // missing "messages"
-        });
-
+        // The test app does not install the router's tracing subscriber
         let req = Request::builder()
             .method("POST")
-            .uri("/v1/chat/completions")
+            .uri("/admin/log_level")
             .header(CONTENT_TYPE, "application/json")
-            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .body(Body::from(
+                serde_json::to_string(&json!({"level": "debug"})).unwrap(),
+            ))
             .unwrap();
-
         let resp = app.oneshot(req).await.unwrap();
-        // Axum validates JSON schema - returns 422 for validation errors
-        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["code"], "LOG_LEVEL_UNAVAILABLE");
 
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_invalid_model() {
-        let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18406,
-            worker_type: WorkerType::Regular,
-            health_status: HealthStatus::Healthy,
-            response_delay_ms: 0,
-            fail_rate: 0.0,
-        }])
-        .await;
-
+    async fn test_bulk_add_workers_reports_each_result() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(config, vec![]).await;
         let app = ctx.create_app().await;
 
-        let payload = json!({
-            "model": "invalid-model-name-that-does-not-exist",
-            "messages": [{"role": "user", "content": "Hello"}],
-            "stream": false
-        });
-
+        let mut workers = Vec::new();
+        let mut urls = Vec::new();
+        for port in [18317, 18318] {
+            let mut worker = MockWorker::new(MockWorkerConfig {
+                port,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            });
+            urls.push(worker.start().await.unwrap());
+            workers.push(worker);
+        }
+        // Nothing listens here, so this worker never becomes healthy
+        let unreachable = "http://127.0.0.1:18319".to_string();
+
+        let body = json!([
+            {"url": urls[0]},
+            {"url": unreachable},
+            {"url": urls[1]},
+        ]);
         let req = Request::builder()
             .method("POST")
-            .uri("/v1/chat/completions")
+            .uri("/workers/bulk")
             .header(CONTENT_TYPE, "application/json")
-            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .body(Body::from(serde_json::to_string(&body).unwrap()))
             .unwrap();
-
         let resp = app.oneshot(req).await.unwrap();
-        // Mock worker accepts any model, but real implementation might return 400
-        assert!(resp.status().is_success() || resp.status() == StatusCode::BAD_REQUEST);
+        assert_eq!(resp.status(), StatusCode::OK);
 
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["succeeded"], 2);
+        assert_eq!(body["failed"], 1);
+        let results = body["results"].as_array().unwrap();
+        assert_eq!(results[0]["url"], urls[0]);
+        assert_eq!(results[0]["success"], true);
+        assert_eq!(results[1]["url"], unreachable);
+        assert_eq!(results[1]["success"], false);
+        assert_eq!(results[1]["error"]["code"], "ADD_WORKER_FAILED");
+        assert_eq!(results[2]["success"], true);
+
+        let mut registered = ctx.router.get_worker_urls();
+        registered.sort();
+        assert_eq!(registered, urls);
+
+        for mut worker in workers {
+            worker.stop().await;
+        }
         ctx.shutdown().await;
     }
-}
-
-#[cfg(test)]
-mod cache_tests {
-    use super::*;
 
     #[tokio::test]
-    async fn test_flush_cache() {
-        let ctx = TestContext::new(vec![MockWorkerConfig {
-            port: 18501,
+    async fn test_added_worker_uses_health_check_config() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            health_check: vllm_router_rs::config::HealthCheckConfig {
+                endpoint: "/missing_health".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(config, vec![]).await;
+
+        let mut worker = MockWorker::new(MockWorkerConfig {
+            port: 18339,
             worker_type: WorkerType::Regular,
             health_status: HealthStatus::Healthy,
             response_delay_ms: 0,
             fail_rate: 0.0,
-        }])
-        .await;
+        });
+        let url = worker.start().await.unwrap();
 
         let app = ctx.create_app().await;
-
         let req = Request::builder()
             .method("POST")
-            .uri("/flush_cache")
+            .uri(format!("/add_worker?url={}", url))
             .body(Body::empty())
             .unwrap();
-
         let resp = app.oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
 
-        // The response might be empty or contain a message
-        let body_bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        if !body_bytes.is_empty() {
-            if let Ok(body) = serde_json::from_slice::<serde_json::Value>(&body_bytes) {
-                // Check that we got a successful response with expected fields
-                assert!(body.is_object());
-                assert!(body.get("message").is_some() || body.get("status").is_some());
-            }
-        }
+        // The configured endpoint does not exist, so only a worker that
+        // received the health config fails its check
+        let added = ctx.app_context.worker_registry.get_by_url(&url).unwrap();
+        assert!(added.check_health_async().await.is_err());
 
+        worker.stop().await;
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_get_loads() {
-        let ctx = TestContext::new(vec![
-            MockWorkerConfig {
-                port: 18502,
-                worker_type: WorkerType::Regular,
-                health_status: HealthStatus::Healthy,
-                response_delay_ms: 0,
-                fail_rate: 0.0,
-            },
-            MockWorkerConfig {
-                port: 18503,
-                worker_type: WorkerType::Regular,
-                health_status: HealthStatus::Healthy,
-                response_delay_ms: 0,
-                fail_rate: 0.0,
-            },
-        ])
-        .await;
-
+    async fn test_add_worker_invalid_url() {
+        let ctx = TestContext::new(vec![]).await;
         let app = ctx.create_app().await;
 
+        // Invalid URL format
         let req = Request::builder()
-            .method("GET")
-            .uri("/get_loads")
+            .method("POST")
+            .uri("/add_worker?url=not-a-valid-url")
             .body(Body::empty())
             .unwrap();
 
-        let resp = app.oneshot(req).await.unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
+        // Missing URL parameter
+        let req = Request::builder()
+            .method("POST")
+            .uri("/add_worker")
+            .body(Body::empty())
             .unwrap();
-        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
 
-        // Verify the response contains load information
-        assert!(body_json.is_object());
-        // The exact structure depends on the implementation
-        // but should contain worker load information
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        // Empty URL
+        let req = Request::builder()
+            .method("POST")
+            .uri("/add_worker?url=")
+            .body(Body::empty())
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
         ctx.shutdown().await;
     }
 
     #[tokio::test]
-    async fn test_flush_cache_no_workers() {
-        let ctx = TestContext::new(vec![]).await;
+    async fn test_add_duplicate_worker() {
+        // Start a mock worker
+        let mut worker = MockWorker::new(MockWorkerConfig {
+            port: 18303,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        });
+        let url = worker.start().await.unwrap();
 
+        let ctx = TestContext::new(vec![]).await;
         let app = ctx.create_app().await;
 
+        // Add worker first time
         let req = Request::builder()
             .method("POST")
-            .uri("/flush_cache")
+            .uri(format!("/add_worker?url={}", url))
             .body(Body::empty())
             .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 
+        // Try to add same worker again
+        let req = Request::builder()
+            .method("POST")
+            .uri(format!("/add_worker?url={}", url))
+            .body(Body::empty())
+            .unwrap();
         let resp = app.oneshot(req).await.unwrap();
-        // Should either succeed (no-op) or return service unavailable
-        assert!(
-            resp.status() == StatusCode::OK || resp.status() == StatusCode::SERVICE_UNAVAILABLE
-        );
+        // Should return error for duplicate
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
+        worker.stop().await;
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_add_unhealthy_worker() {
+        // Start unhealthy worker
+        let mut worker = MockWorker::new(MockWorkerConfig {
+            port: 18304,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Unhealthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        });
+        let url = worker.start().await.unwrap();
+
+        let ctx = TestContext::new(vec![]).await;
+        let app = ctx.create_app().await;
+
+        // Try to add unhealthy worker
+        let req = Request::builder()
+            .method("POST")
+            .uri(format!("/add_worker?url={}", url))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+
+        // Router should reject unhealthy workers
+        assert!(
+            resp.status() == StatusCode::BAD_REQUEST
+                || resp.status() == StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        worker.stop().await;
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_added_worker_registered_under_served_model() {
+        let mut worker = MockWorker::new(MockWorkerConfig {
+            port: 18307,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        });
+        let url = worker.start().await.unwrap();
+
+        let ctx = TestContext::new(vec![]).await;
+        let app = ctx.create_app().await;
+
+        let req = Request::builder()
+            .method("POST")
+            .uri(format!("/add_worker?url={}", url))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // The mock worker reports its model via /get_model_info
+        let router = ctx
+            .router
+            .as_any()
+            .downcast_ref::<vllm_router_rs::routers::http::router::Router>()
+            .unwrap();
+        assert_eq!(
+            router.get_worker_urls_for_model(Some("mock-model-path")),
+            vec![url.clone()]
+        );
+        assert!(router.get_worker_urls_for_model(Some("unknown")).is_empty());
+
+        worker.stop().await;
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_requests_routed_by_model_field() {
+        use vllm_router_rs::core::BasicWorker;
+
+        // Startup workers are registered under the model they report
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18309,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+        let model_url = ctx.router.get_worker_urls()[0].clone();
+
+        // A second worker registered under a model no request names
+        let mut worker = MockWorker::new(MockWorkerConfig {
+            port: 18310,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        });
+        let other_url = worker.start().await.unwrap();
+        ctx.app_context.worker_registry.register(Arc::new(
+            BasicWorker::new(other_url.clone(), vllm_router_rs::core::WorkerType::Regular)
+                .with_labels(std::collections::HashMap::from([(
+                    "model_id".to_string(),
+                    "unrequested-model".to_string(),
+                )])),
+        ));
+
+        let app = ctx.create_app().await;
+
+        for _ in 0..4 {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/v1/chat/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    serde_json::to_string(&json!({
+                        "model": "mock-model-path",
+                        "messages": [{"role": "user", "content": "Hello"}],
+                        "stream": false
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap();
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+
+        // A model no worker is registered under is routed across all workers
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                serde_json::to_string(&json!({
+                    "model": "other-model",
+                    "messages": [{"role": "user", "content": "Hello"}],
+                    "stream": false
+                }))
+                .unwrap(),
+            ))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Each request records its outcome on the circuit breaker of the worker that served it
+        let mut served = Vec::new();
+        for url in [&model_url, &other_url] {
+            let encoded_url = url.replace(':', "%3A").replace('/', "%2F");
+            let req = Request::builder()
+                .method("GET")
+                .uri(format!("/workers/{}/circuit", encoded_url))
+                .body(Body::empty())
+                .unwrap();
+            let resp = app.clone().oneshot(req).await.unwrap();
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            served.push(body_json["total_successes"].as_u64().unwrap());
+        }
+        assert!(served[0] >= 4);
+        assert_eq!(served[0] + served[1], 5);
+
+        worker.stop().await;
         ctx.shutdown().await;
     }
 }
 
 #[cfg(test)]
-mod load_balancing_tests {
+mod router_policy_tests {
     use super::*;
 
     #[tokio::test]
-    async fn test_request_distribution() {
-        // Create multiple workers
+    async fn test_random_policy() {
         let ctx = TestContext::new(vec![
             MockWorkerConfig {
-                port: 18601,
+                port: 18801,
                 worker_type: WorkerType::Regular,
                 health_status: HealthStatus::Healthy,
                 response_delay_ms: 0,
                 fail_rate: 0.0,
             },
             MockWorkerConfig {
-                port: 18602,
+                port: 18802,
                 worker_type: WorkerType::Regular,
                 health_status: HealthStatus::Healthy,
                 response_delay_ms: 0,
@@ -1658,10 +2254,9 @@ mod load_balancing_tests {
         ])
         .await;
 
+        // Send multiple requests and verify they succeed
         let app = ctx.create_app().await;
 
-        // Send multiple requests and track distribution
-        let mut request_count = 0;
         for i in 0..10 {
             let payload = json!({
                 "text": format!("Request {}", i),
@@ -1676,13 +2271,2581 @@ mod load_balancing_tests {
                 .unwrap();
 
             let resp = app.clone().oneshot(req).await.unwrap();
-            if resp.status() == StatusCode::OK {
-                request_count += 1;
-            }
+            assert_eq!(resp.status(), StatusCode::OK);
         }
 
-        // With random policy, all requests should succeed
-        assert_eq!(request_count, 10);
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_worker_selection() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18207,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let _payload = json!({
+            "text": "Test selection",
+            "stream": false
+        });
+
+        // Check that router has the worker
+        let worker_urls = ctx.router.get_worker_urls();
+        assert_eq!(worker_urls.len(), 1);
+        assert!(worker_urls[0].contains("18207"));
+
+        ctx.shutdown().await;
+    }
+
+    async fn policies_request(
+        app: &axum::Router,
+        method: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, serde_json::Value) {
+        let builder = Request::builder().method(method).uri("/policies");
+        let req = match body {
+            Some(body) => builder
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&body).unwrap()))
+                .unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        };
+
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_get_policies() {
+        let ctx = TestContext::new(vec![]).await;
+        let app = ctx.create_app().await;
+
+        let (status, body) = policies_request(&app, "GET", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["default_policy"], "random");
+        assert!(body["models"].is_object());
+
+        let available = body["available_policies"].as_array().unwrap();
+        assert!(available.contains(&json!("power_of_two")));
+        assert!(available.contains(&json!("cache_aware")));
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_switch_default_policy() {
+        let ctx = TestContext::new(vec![]).await;
+        let app = ctx.create_app().await;
+
+        let (status, body) =
+            policies_request(&app, "POST", Some(json!({ "policy": "power_of_two" }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], true);
+        assert_eq!(body["policy"], "power_of_two");
+        assert_eq!(body["previous_policy"], "random");
+
+        let (_, body) = policies_request(&app, "GET", None).await;
+        assert_eq!(body["default_policy"], "power_of_two");
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_switch_policy_errors() {
+        let ctx = TestContext::new(vec![]).await;
+        let app = ctx.create_app().await;
+
+        let (status, body) =
+            policies_request(&app, "POST", Some(json!({ "policy": "fastest" }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "UNKNOWN_POLICY");
+
+        let (status, body) = policies_request(
+            &app,
+            "POST",
+            Some(json!({ "model_id": "missing-model", "policy": "round_robin" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "MODEL_NOT_FOUND");
+
+        ctx.shutdown().await;
+    }
+}
+
+#[cfg(test)]
+mod responses_endpoint_tests {
+    use super::*;
+    use reqwest::Client as HttpClient;
+
+    #[tokio::test]
+    async fn test_v1_responses_non_streaming() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18950,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let payload = json!({
+            "input": "Hello Responses API",
+            "model": "mock-model",
+            "stream": false
+        });
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/responses")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["object"], "response");
+        assert_eq!(body_json["status"], "completed");
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_responses_idempotency_key() {
+        let config = RouterConfig {
+            idempotency: Some(IdempotencyConfig::default()),
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18326,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let send = |input: &str, key: &str| {
+            let payload = json!({"input": input, "model": "mock-model", "stream": false});
+            Request::builder()
+                .method("POST")
+                .uri("/v1/responses")
+                .header(CONTENT_TYPE, "application/json")
+                .header("Idempotency-Key", key)
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+        let read = |resp: axum::response::Response| async move {
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let resp = app.clone().oneshot(send("Hello", "retry-1")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get("idempotent-replayed").is_none());
+        let first = read(resp).await;
+
+        // A retry with the same key gets the stored response back
+        let resp = app.clone().oneshot(send("Hello", "retry-1")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()["idempotent-replayed"], "true");
+        assert_eq!(read(resp).await["id"], first["id"]);
+
+        // Reusing the key for a different request reaches a worker again
+        let resp = app
+            .clone()
+            .oneshot(send("Goodbye", "retry-1"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get("idempotent-replayed").is_none());
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_responses_streaming() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18951,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let payload = json!({
+            "input": "Hello Responses API",
+            "model": "mock-model",
+            "stream": true
+        });
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/responses")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Check that content-type indicates SSE
+        let headers = resp.headers().clone();
+        let ct = headers
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        assert!(ct.contains("text/event-stream"));
+
+        // We don't fully consume the stream in this test harness.
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_responses_get() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18952,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // First create a response to obtain an id
+        let resp_id = "test-get-resp-id-123";
+        let payload = json!({
+            "input": "Hello Responses API",
+            "model": "mock-model",
+            "stream": false,
+            "store": true,
+            "background": true,
+            "request_id": resp_id
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/responses")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Retrieve the response
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/responses/{}", resp_id))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let get_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(get_json["object"], "response");
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_responses_cancel() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18953,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // First create a response to obtain an id
+        let resp_id = "test-cancel-resp-id-456";
+        let payload = json!({
+            "input": "Hello Responses API",
+            "model": "mock-model",
+            "stream": false,
+            "store": true,
+            "background": true,
+            "request_id": resp_id
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/responses")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Cancel the response
+        let req = Request::builder()
+            .method("POST")
+            .uri(format!("/v1/responses/{}/cancel", resp_id))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let cancel_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(cancel_json["status"], "cancelled");
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_responses_delete_and_list_served_from_storage() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18954,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // Unknown ids are answered from storage without reaching the worker
+        let resp_id = "resp-test-123";
+
+        let req = Request::builder()
+            .method("DELETE")
+            .uri(format!("/v1/responses/{}", resp_id))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        for path in ["input", "input_items"] {
+            let req = Request::builder()
+                .method("GET")
+                .uri(format!("/v1/responses/{}/{}?limit=5", resp_id, path))
+                .body(Body::empty())
+                .unwrap();
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        }
+
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/responses/{}/input?limit=500", resp_id))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_responses_create_list_input_items_delete() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18955,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+        let create = |store: bool| {
+            let payload = json!({
+                "input": "Tell me a joke",
+                "model": "mock-model",
+                "stream": false,
+                "store": store
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/v1/responses")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+        let body_json = |resp: axum::response::Response| async move {
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let resp = app.clone().oneshot(create(true)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp_id = body_json(resp).await["id"].as_str().unwrap().to_string();
+
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/responses/{}/input_items", resp_id))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let items = body_json(resp).await;
+        assert_eq!(items["data"][0]["role"], "user");
+        assert_eq!(items["data"][0]["content"][0]["text"], "Tell me a joke");
+
+        let delete = |id: &str| {
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/v1/responses/{}", id))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let resp = app.clone().oneshot(delete(&resp_id)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(body_json(resp).await["deleted"], true);
+        let resp = app.clone().oneshot(delete(&resp_id)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // Responses created with store=false are not kept
+        let resp = app.clone().oneshot(create(false)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp_id = body_json(resp).await["id"].as_str().unwrap().to_string();
+        let resp = app.clone().oneshot(delete(&resp_id)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_responses_get_multi_worker_fanout() {
+        // Start two mock workers
+        let ctx = TestContext::new(vec![
+            MockWorkerConfig {
+                port: 18960,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            },
+            MockWorkerConfig {
+                port: 18961,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            },
+        ])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // Create a background response with a known id
+        let rid = format!("resp_{}", 18960); // arbitrary unique id
+        let payload = json!({
+            "input": "Hello Responses API",
+            "model": "mock-model",
+            "background": true,
+            "store": true,
+            "request_id": rid,
+        });
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/responses")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Using the router, GET should succeed by fanning out across workers
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/responses/{}", rid))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Validate only one worker holds the metadata: direct calls
+        let client = HttpClient::new();
+        let mut ok_count = 0usize;
+        for url in ctx.router.get_worker_urls() {
+            let get_url = format!("{}/v1/responses/{}", url, rid);
+            let res = client.get(get_url).send().await.unwrap();
+            if res.status() == StatusCode::OK {
+                ok_count += 1;
+            }
+        }
+        assert_eq!(ok_count, 1, "exactly one worker should store the response");
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_batches() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18325,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let requests: Vec<_> = (0..3)
+            .map(|i| {
+                json!({
+                    "custom_id": format!("request-{}", i),
+                    "method": "POST",
+                    "url": "/v1/chat/completions",
+                    "body": {
+                        "model": "mock-model",
+                        "messages": [{"role": "user", "content": format!("Hello {}", i)}],
+                    },
+                })
+            })
+            .collect();
+        let payload = json!({
+            "endpoint": "/v1/chat/completions",
+            "completion_window": "24h",
+            "metadata": {"job": "nightly"},
+            "requests": requests,
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/batches")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let batch: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(batch["object"], "batch");
+        assert_eq!(batch["request_counts"]["total"], 3);
+        assert_eq!(batch["metadata"]["job"], "nightly");
+        let batch_id = batch["id"].as_str().unwrap().to_string();
+
+        // The requests run in the background; poll until the batch completes
+        let get = |uri: String| {
+            Request::builder()
+                .method("GET")
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap()
+        };
+        let mut status = serde_json::Value::Null;
+        for _ in 0..50 {
+            let resp = app
+                .clone()
+                .oneshot(get(format!("/v1/batches/{}", batch_id)))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            status = serde_json::from_slice(&body).unwrap();
+            if status["status"] == "completed" {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        }
+        assert_eq!(status["status"], "completed");
+        assert_eq!(status["request_counts"]["completed"], 3);
+        assert_eq!(status["request_counts"]["failed"], 0);
+
+        let resp = app
+            .clone()
+            .oneshot(get(format!("/v1/batches/{}/output", batch_id)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let lines: Vec<serde_json::Value> = String::from_utf8_lossy(&body)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        for (i, line) in lines.iter().enumerate() {
+            assert_eq!(line["custom_id"], format!("request-{}", i));
+            assert_eq!(line["response"]["status_code"], 200);
+            assert_eq!(line["response"]["body"]["object"], "chat.completion");
+        }
+
+        // Requests to another endpoint than the batch's are rejected up front
+        let mut payload = payload;
+        payload["requests"][1]["url"] = json!("/v1/completions");
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/batches")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = app
+            .clone()
+            .oneshot(get("/v1/batches/batch_unknown".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // Batches are kept apart from stored responses
+        let resp = app
+            .oneshot(get(format!("/v1/responses/{}", batch_id)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_batches_forward_client_credentials() {
+        // A worker that only answers requests carrying the client's token
+        let traceparents = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let chat = {
+            let traceparents = traceparents.clone();
+            move |headers: axum::http::HeaderMap| async move {
+                if headers.get("authorization").and_then(|v| v.to_str().ok())
+                    != Some("Bearer client-token")
+                {
+                    return axum::response::IntoResponse::into_response((
+                        StatusCode::UNAUTHORIZED,
+                        "missing token",
+                    ));
+                }
+                if let Some(traceparent) = headers.get("traceparent") {
+                    traceparents
+                        .lock()
+                        .unwrap()
+                        .push(traceparent.to_str().unwrap().to_string());
+                }
+                axum::response::IntoResponse::into_response(axum::Json(
+                    json!({"object": "chat.completion", "choices": []}),
+                ))
+            }
+        };
+        let worker = axum::Router::new()
+            .route("/health", axum::routing::get(|| async { "OK" }))
+            .route("/v1/chat/completions", axum::routing::post(chat));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, worker).await.unwrap() });
+
+        let config = RouterConfig {
+            mode: RoutingMode::Regular {
+                worker_urls: vec![url],
+            },
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(config, vec![]).await;
+        let app = ctx.create_app().await;
+
+        let requests: Vec<_> = (0..2)
+            .map(|i| {
+                json!({
+                    "custom_id": format!("request-{}", i),
+                    "method": "POST",
+                    "url": "/v1/chat/completions",
+                    "body": {
+                        "model": "mock-model",
+                        "messages": [{"role": "user", "content": "Hello"}],
+                    },
+                })
+            })
+            .collect();
+        let payload = json!({
+            "endpoint": "/v1/chat/completions",
+            "completion_window": "24h",
+            "requests": requests,
+        });
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/batches")
+            .header(CONTENT_TYPE, "application/json")
+            .header("authorization", "Bearer client-token")
+            .header("traceparent", traceparent)
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let batch: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let batch_id = batch["id"].as_str().unwrap().to_string();
+
+        let mut status = serde_json::Value::Null;
+        for _ in 0..50 {
+            let req = Request::builder()
+                .method("GET")
+                .uri(format!("/v1/batches/{}", batch_id))
+                .body(Body::empty())
+                .unwrap();
+            let resp = app.clone().oneshot(req).await.unwrap();
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            status = serde_json::from_slice(&body).unwrap();
+            if status["status"] == "completed" {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        }
+        assert_eq!(status["status"], "completed");
+        assert_eq!(status["request_counts"]["completed"], 2);
+        assert_eq!(status["request_counts"]["failed"], 0);
+        assert_eq!(*traceparents.lock().unwrap(), vec![traceparent; 2]);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_v1_batches_need_history_backend() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            history_backend: HistoryBackend::None,
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(config, vec![]).await;
+        let app = ctx.create_app().await;
+
+        let payload = json!({
+            "endpoint": "/v1/chat/completions",
+            "completion_window": "24h",
+            "requests": [{
+                "custom_id": "request-0",
+                "method": "POST",
+                "url": "/v1/chat/completions",
+                "body": {"model": "mock-model", "messages": [{"role": "user", "content": "Hi"}]},
+            }],
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/batches")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_IMPLEMENTED);
+
+        ctx.shutdown().await;
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_404_not_found() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18401,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // Test unknown endpoint
+        let req = Request::builder()
+            .method("GET")
+            .uri("/unknown_endpoint")
+            .body(Body::empty())
+            .unwrap();
+
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // Test POST to unknown endpoint
+        let req = Request::builder()
+            .method("POST")
+            .uri("/api/v2/generate")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                serde_json::to_string(&json!({"text": "test"})).unwrap(),
+            ))
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_method_not_allowed() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18402,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // GET request to POST-only endpoint
+        let req = Request::builder()
+            .method("GET")
+            .uri("/generate")
+            .body(Body::empty())
+            .unwrap();
+
+        let resp = app.clone().oneshot(req).await.unwrap();
+        // Note: Axum returns 405 for wrong methods on matched routes
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        // POST request to GET-only endpoint
+        let req = Request::builder()
+            .method("POST")
+            .uri("/health")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from("{}"))
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_payload_too_large() {
+        // Create context with small payload limit
+        let config = RouterConfig {
+            mode: RoutingMode::Regular {
+                worker_urls: vec![],
+            },
+            policy: PolicyConfig::Random,
+            host: "127.0.0.1".to_string(),
+            port: 3010,
+            max_payload_size: 1024, // 1KB limit
+            request_timeout_secs: 600,
+            proxy_timeout_secs: 10,
+            generation_timeout_secs: None,
+            max_request_timeout_secs: None,
+            slow_request_threshold_secs: None,
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            intra_node_data_parallel_size: 1,
+            api_key: None,
+            api_key_validation_urls: vec![],
+            discovery: None,
+            metrics: None,
+            log_dir: None,
+            log_level: None,
+            request_id_headers: None,
+            max_concurrent_requests: 64,
+            queue_size: 0,
+            queue_timeout_secs: 60,
+            rate_limit_tokens_per_second: None,
+            model_concurrency_limits: std::collections::HashMap::new(),
+            adaptive_concurrency: None,
+            model_policies: std::collections::HashMap::new(),
+            queue_fairness: vllm_router_rs::config::QueueFairness::Fifo,
+            queue_weights: std::collections::HashMap::new(),
+            queue_priority_aging_secs: 10,
+            model_aliases: std::collections::HashMap::new(),
+            model_fallbacks: std::collections::HashMap::new(),
+            model_traffic_splits: std::collections::HashMap::new(),
+            discovery_stale_secs: 30,
+            advertise_model_aliases: false,
+            rewrite_stream_model: false,
+            strict_model_routing: false,
+            enable_response_compression: false,
+            enable_upstream_compression: false,
+            max_concurrent_streams: None,
+            stream_buffer_size: 64,
+            stream_keepalive_secs: None,
+            shutdown_drain_timeout_secs: 30,
+            batch_max_concurrency: 8,
+            cors_allowed_origins: vec![],
+            retry: RetryConfig::default(),
+            circuit_breaker: CircuitBreakerConfig::default(),
+            hedging: None,
+            traffic_mirror: None,
+            worker_rate_limit: None,
+            worker_warmup: None,
+            model_version_check: None,
+            outlier_detection: None,
+            response_cache: None,
+            idempotency: None,
+            header_policy: Default::default(),
+            body_logging: None,
+            allow_worker_pinning: false,
+            expose_routing_headers: false,
+            upstream_auth: None,
+            upstream_tls: None,
+            pd_zone_affinity: false,
+            min_healthy_workers: 1,
+            payload_limits: vllm_router_rs::config::PayloadLimitsConfig::default(),
+            max_prompt_chars: None,
+            default_max_tokens: None,
+            max_tokens_limit: None,
+            disable_retries: false,
+            disable_circuit_breaker: false,
+            health_check: vllm_router_rs::config::HealthCheckConfig::default(),
+            enable_igw: false,
+            connection_mode: ConnectionMode::Http,
+            model_path: None,
+            tokenizer_path: None,
+            history_backend: vllm_router_rs::config::HistoryBackend::Memory,
+            memory_history: vllm_router_rs::config::MemoryHistoryConfig::default(),
+            enable_profiling: false,
+            profile_timeout_secs: 30,
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18403,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        // Note: The server would have payload size middleware configured
+        // but we cannot test it directly through the test app
+        // This test is kept for documentation purposes
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_invalid_json_payload() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18404,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // Send invalid JSON
+        let req = Request::builder()
+            .method("POST")
+            .uri("/generate")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from("{invalid json}"))
+            .unwrap();
+
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        // Send empty body
+        let req = Request::builder()
+            .method("POST")
+            .uri("/generate")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::empty())
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_missing_required_fields() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18405,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // Missing messages in chat completion
+        let payload = json!({
+            "model": "test-model"
+            // missing "messages"
+        });
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        // Axum validates JSON schema - returns 422 for validation errors
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_invalid_model() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18406,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let payload = json!({
+            "model": "invalid-model-name-that-does-not-exist",
+            "messages": [{"role": "user", "content": "Hello"}],
+            "stream": false
+        });
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        // Mock worker accepts any model, but real implementation might return 400
+        assert!(resp.status().is_success() || resp.status() == StatusCode::BAD_REQUEST);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_invalid_sampling_params_rejected_before_routing() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18408,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        for (uri, payload, param) in [
+            (
+                "/v1/chat/completions",
+                json!({
+                    "model": "test-model",
+                    "messages": [{"role": "user", "content": "Hello"}],
+                    "temperature": -1.0
+                }),
+                "temperature",
+            ),
+            (
+                "/v1/completions",
+                json!({"model": "test-model", "prompt": "Hello", "top_p": 2.0}),
+                "top_p",
+            ),
+            (
+                "/generate",
+                json!({"text": "Hello", "sampling_params": {"top_k": -2}}),
+                "top_k",
+            ),
+            (
+                "/v1/responses",
+                json!({"model": "test-model", "input": "Hello", "temperature": -1.0}),
+                "temperature",
+            ),
+        ] {
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap();
+
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", uri);
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body_json["error"]["type"], "invalid_request_error");
+            assert_eq!(body_json["error"]["param"], param);
+        }
+
+        // Values outside the OpenAI ranges that VLLM accepts are routed
+        for (uri, payload) in [
+            (
+                "/v1/chat/completions",
+                json!({
+                    "model": "test-model",
+                    "messages": [{"role": "user", "content": "Hello"}],
+                    "temperature": 3.0
+                }),
+            ),
+            (
+                "/generate",
+                json!({"text": "Hello", "sampling_params": {"top_k": 0}}),
+            ),
+        ] {
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap();
+
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK, "{}", uri);
+        }
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_invalid_structured_output_rejected_before_routing() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18330,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        for (uri, payload, param) in [
+            (
+                "/v1/chat/completions",
+                json!({
+                    "model": "test-model",
+                    "messages": [{"role": "user", "content": "Hello"}],
+                    "response_format": {
+                        "type": "json_schema",
+                        "json_schema": {
+                            "name": "answer",
+                            "schema": {"type": "object", "properties": {"ok": {"type": "bool"}}}
+                        }
+                    }
+                }),
+                json!("response_format.json_schema.schema"),
+            ),
+            (
+                "/v1/completions",
+                json!({"model": "test-model", "prompt": "Hello", "json_schema": "{\"type\": "}),
+                json!("json_schema"),
+            ),
+            (
+                "/v1/completions",
+                json!({
+                    "model": "test-model",
+                    "prompt": "Hello",
+                    "json_schema": "{\"type\": \"object\"}",
+                    "regex": "[a-z]+"
+                }),
+                serde_json::Value::Null,
+            ),
+        ] {
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap();
+
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", payload);
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body_json["error"]["type"], "invalid_request_error");
+            assert_eq!(body_json["error"]["param"], param);
+        }
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_stream_limit_rejects_extra_streams() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            max_concurrent_streams: Some(1),
+            ..Default::default()
+        };
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18407,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let completion = |stream: bool| {
+            let payload = json!({
+                "model": "test-model",
+                "prompt": "Hello",
+                "stream": stream
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/v1/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+
+        // The first stream holds the only slot until its body is dropped
+        let open_stream = app.clone().oneshot(completion(true)).await.unwrap();
+        assert_eq!(open_stream.status(), StatusCode::OK);
+
+        let resp = app.clone().oneshot(completion(true)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        // Rejections use the OpenAI error shape so SDKs can parse them
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["error"]["type"], "server_error");
+        assert!(body_json["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("concurrent streaming requests"));
+
+        // Non-streaming requests are not limited
+        let resp = app.clone().oneshot(completion(false)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        drop(open_stream);
+        let resp = app.clone().oneshot(completion(true)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        ctx.shutdown().await;
+    }
+}
+
+#[cfg(test)]
+mod cache_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_flush_cache() {
+        let ctx = TestContext::new(vec![MockWorkerConfig {
+            port: 18501,
+            worker_type: WorkerType::Regular,
+            health_status: HealthStatus::Healthy,
+            response_delay_ms: 0,
+            fail_rate: 0.0,
+        }])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/flush_cache")
+            .body(Body::empty())
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // The response might be empty or contain a message
+        let body_bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        if !body_bytes.is_empty() {
+            if let Ok(body) = serde_json::from_slice::<serde_json::Value>(&body_bytes) {
+                // Check that we got a successful response with expected fields
+                assert!(body.is_object());
+                assert!(body.get("message").is_some() || body.get("status").is_some());
+            }
+        }
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_loads() {
+        let ctx = TestContext::new(vec![
+            MockWorkerConfig {
+                port: 18502,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            },
+            MockWorkerConfig {
+                port: 18503,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            },
+        ])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let req = Request::builder()
+            .method("GET")
+            .uri("/get_loads")
+            .body(Body::empty())
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        // Verify the response contains load information
+        assert!(body_json.is_object());
+        // The exact structure depends on the implementation
+        // but should contain worker load information
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_flush_cache_no_workers() {
+        let ctx = TestContext::new(vec![]).await;
+
+        let app = ctx.create_app().await;
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/flush_cache")
+            .body(Body::empty())
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        // Should either succeed (no-op) or return service unavailable
+        assert!(
+            resp.status() == StatusCode::OK || resp.status() == StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_serves_deterministic_completions() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            disable_retries: true,
+            response_cache: Some(ResponseCacheConfig {
+                max_entries: 16,
+                ttl_secs: 60,
+            }),
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18504,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let completion_as = |temperature: f32, stream: bool, authorization: &str| {
+            let payload = json!({
+                "model": "test-model",
+                "prompt": "Hello",
+                "temperature": temperature,
+                "stream": stream
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/v1/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .header("authorization", authorization)
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+        let completion =
+            |temperature: f32, stream: bool| completion_as(temperature, stream, "Bearer tenant-a");
+
+        let resp = app.clone().oneshot(completion(0.0, false)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let first = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        // With the worker gone, only the cached deterministic request still succeeds
+        ctx.shutdown().await;
+
+        let resp = app.clone().oneshot(completion(0.0, false)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let second = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+
+        let resp = app.clone().oneshot(completion(0.7, false)).await.unwrap();
+        assert_ne!(resp.status(), StatusCode::OK);
+
+        let resp = app.clone().oneshot(completion(0.0, true)).await.unwrap();
+        assert_ne!(resp.status(), StatusCode::OK);
+
+        // Responses are never shared between callers with different credentials
+        let resp = app
+            .clone()
+            .oneshot(completion_as(0.0, false, "Bearer tenant-b"))
+            .await
+            .unwrap();
+        assert_ne!(resp.status(), StatusCode::OK);
+    }
+}
+
+#[cfg(test)]
+mod load_balancing_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_distribution() {
+        // Create multiple workers
+        let ctx = TestContext::new(vec![
+            MockWorkerConfig {
+                port: 18601,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            },
+            MockWorkerConfig {
+                port: 18602,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            },
+        ])
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // Send multiple requests and track distribution
+        let mut request_count = 0;
+        for i in 0..10 {
+            let payload = json!({
+                "text": format!("Request {}", i),
+                "stream": false
+            });
+
+            let req = Request::builder()
+                .method("POST")
+                .uri("/generate")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap();
+
+            let resp = app.clone().oneshot(req).await.unwrap();
+            if resp.status() == StatusCode::OK {
+                request_count += 1;
+            }
+        }
+
+        // With random policy, all requests should succeed
+        assert_eq!(request_count, 10);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_model_concurrency_limit() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            max_concurrent_requests: 64,
+            queue_size: 0,
+            model_concurrency_limits: [("limited-model".to_string(), 1)].into_iter().collect(),
+            model_policies: std::collections::HashMap::new(),
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18603,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 500,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let chat_request = |model: &str| {
+            let payload = json!({
+                "model": model,
+                "messages": [{"role": "user", "content": "Hello"}],
+                "stream": false
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/v1/chat/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+
+        // The second request for the limited model exceeds its cap, while other
+        // models still share the global limit
+        let (first, second, other) = tokio::join!(
+            app.clone().oneshot(chat_request("limited-model")),
+            async {
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                app.clone().oneshot(chat_request("limited-model")).await
+            },
+            async {
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                app.clone().oneshot(chat_request("other-model")).await
+            },
+        );
+
+        assert_eq!(first.unwrap().status(), StatusCode::OK);
+        assert_eq!(second.unwrap().status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(other.unwrap().status(), StatusCode::OK);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_concurrency_limit() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            max_concurrent_requests: 64,
+            queue_size: 0,
+            adaptive_concurrency: Some(AdaptiveConcurrencyConfig {
+                initial_limit: 1,
+                min_limit: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18334,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 500,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let chat_request = || {
+            let payload = json!({
+                "model": "test-model",
+                "messages": [{"role": "user", "content": "Hello"}],
+                "stream": false
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/v1/chat/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+
+        // The adaptive limit starts at one request, well below the global cap
+        let (first, second) = tokio::join!(app.clone().oneshot(chat_request()), async {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            app.clone().oneshot(chat_request()).await
+        });
+
+        assert_eq!(first.unwrap().status(), StatusCode::OK);
+        assert_eq!(second.unwrap().status(), StatusCode::TOO_MANY_REQUESTS);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_concurrency_limit_queues_when_enabled() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            max_concurrent_requests: 64,
+            queue_size: 4,
+            queue_timeout_secs: 5,
+            adaptive_concurrency: Some(AdaptiveConcurrencyConfig {
+                initial_limit: 1,
+                min_limit: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18335,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 300,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let chat_request = |stream: bool| {
+            let payload = json!({
+                "model": "test-model",
+                "messages": [{"role": "user", "content": "Hello"}],
+                "stream": stream
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/v1/chat/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+
+        // A stream keeps its slot until the body is read, so the second
+        // request waits for it instead of being rejected
+        let stream = app.clone().oneshot(chat_request(true)).await.unwrap();
+        assert_eq!(stream.status(), StatusCode::OK);
+        let limiter = ctx.app_context.adaptive_limiter.clone().unwrap();
+        assert_eq!(limiter.in_flight(), 1);
+
+        let (body, second) = tokio::join!(
+            axum::body::to_bytes(stream.into_body(), usize::MAX),
+            async {
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                app.clone().oneshot(chat_request(false)).await
+            }
+        );
+        assert!(body.is_ok());
+        assert_eq!(second.unwrap().status(), StatusCode::OK);
+        assert_eq!(limiter.in_flight(), 0);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_concurrency_ignores_client_errors() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            max_concurrent_requests: 64,
+            queue_size: 0,
+            adaptive_concurrency: Some(AdaptiveConcurrencyConfig {
+                initial_limit: 16,
+                min_limit: 1,
+                smoothing: 1.0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18336,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 300,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let chat_request = |temperature: f64| {
+            let payload = json!({
+                "model": "test-model",
+                "messages": [{"role": "user", "content": "Hello"}],
+                "temperature": temperature,
+                "stream": false
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/v1/chat/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+
+        // Rejected requests answer in well under a millisecond; were they
+        // sampled, every real generation would look queued and the limit
+        // would collapse
+        for _ in 0..3 {
+            for _ in 0..5 {
+                let resp = app.clone().oneshot(chat_request(-1.0)).await.unwrap();
+                assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+            }
+            let responses =
+                futures::future::join_all((0..12).map(|_| app.clone().oneshot(chat_request(0.7))))
+                    .await;
+            for resp in responses {
+                assert_eq!(resp.unwrap().status(), StatusCode::OK);
+            }
+        }
+
+        let limiter = ctx.app_context.adaptive_limiter.clone().unwrap();
+        assert!(limiter.limit() >= 16, "limit fell to {}", limiter.limit());
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_hedged_request_avoids_slow_worker() {
+        let config = RouterConfig {
+            policy: PolicyConfig::RoundRobin,
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            hedging: Some(HedgingConfig { hedge_delay_ms: 50 }),
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![
+                MockWorkerConfig {
+                    port: 18604,
+                    worker_type: WorkerType::Regular,
+                    health_status: HealthStatus::Healthy,
+                    response_delay_ms: 2000,
+                    fail_rate: 0.0,
+                },
+                MockWorkerConfig {
+                    port: 18605,
+                    worker_type: WorkerType::Regular,
+                    health_status: HealthStatus::Healthy,
+                    response_delay_ms: 0,
+                    fail_rate: 0.0,
+                },
+            ],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // Round robin makes the slow worker the primary for one of these; the
+        // backup copy to the fast worker should answer well before it does
+        for _ in 0..2 {
+            let payload = json!({
+                "text": "Hello",
+                "stream": false
+            });
+            let req = Request::builder()
+                .method("POST")
+                .uri("/generate")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap();
+
+            let start = std::time::Instant::now();
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert!(start.elapsed() < std::time::Duration::from_millis(1000));
+        }
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_generation_timeout_applies_per_request() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            disable_retries: true,
+            generation_timeout_secs: Some(1),
+            slow_request_threshold_secs: None,
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18608,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 3000,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // Info calls use the proxy timeout and are unaffected by slow generation
+        let req = Request::builder()
+            .method("GET")
+            .uri("/get_server_info")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let payload = json!({
+            "text": "Hello",
+            "stream": false
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/generate")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+
+        // The generation timeout cuts the request off well before the worker answers
+        let start = std::time::Instant::now();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert!(!resp.status().is_success());
+        assert!(start.elapsed() < std::time::Duration::from_millis(2500));
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_header_is_clamped() {
+        let config = RouterConfig {
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            disable_retries: true,
+            max_request_timeout_secs: Some(1),
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18332,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 2000,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let request = |timeout: Option<&str>| {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri("/generate")
+                .header(CONTENT_TYPE, "application/json");
+            if let Some(timeout) = timeout {
+                builder = builder.header("X-Router-Timeout-Secs", timeout);
+            }
+            builder
+                .body(Body::from(
+                    serde_json::to_string(&json!({"text": "Hello", "stream": false})).unwrap(),
+                ))
+                .unwrap()
+        };
+
+        // Without the header the default generation timeout applies
+        let resp = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // A generous header timeout is clamped to the configured maximum
+        let start = std::time::Instant::now();
+        let resp = app.clone().oneshot(request(Some("60"))).await.unwrap();
+        assert!(!resp.status().is_success());
+        assert!(start.elapsed() < std::time::Duration::from_millis(1800));
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_session_cookie_minted_for_new_sessions() {
+        let config = RouterConfig {
+            policy: PolicyConfig::ConsistentHash {
+                virtual_nodes: 160,
+                weight_source: None,
+                session_cookie: SessionCookieConfig {
+                    set_on_response: true,
+                    ..Default::default()
+                },
+                overload_factor: None,
+                hash_client_ip: false,
+            },
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![
+                MockWorkerConfig {
+                    port: 18606,
+                    worker_type: WorkerType::Regular,
+                    health_status: HealthStatus::Healthy,
+                    response_delay_ms: 0,
+                    fail_rate: 0.0,
+                },
+                MockWorkerConfig {
+                    port: 18607,
+                    worker_type: WorkerType::Regular,
+                    health_status: HealthStatus::Healthy,
+                    response_delay_ms: 0,
+                    fail_rate: 0.0,
+                },
+            ],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let generate = |cookie: Option<&str>, session_header: Option<&str>| {
+            let payload = json!({
+                "text": "Hello",
+                "stream": false
+            });
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri("/generate")
+                .header(CONTENT_TYPE, "application/json");
+            if let Some(cookie) = cookie {
+                builder = builder.header("cookie", cookie);
+            }
+            if let Some(session) = session_header {
+                builder = builder.header("x-session-id", session);
+            }
+            builder
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+
+        // A request without any session identifier gets a new cookie
+        let resp = app.clone().oneshot(generate(None, None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let set_cookie = resp
+            .headers()
+            .get("set-cookie")
+            .expect("session cookie should be set")
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(set_cookie.starts_with("router_session="));
+        assert!(set_cookie.contains("HttpOnly"));
+        let cookie = set_cookie.split(';').next().unwrap();
+
+        // Follow-ups carrying the cookie or an explicit session header are left alone
+        let resp = app
+            .clone()
+            .oneshot(generate(Some(&format!("theme=dark; {}", cookie)), None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get("set-cookie").is_none());
+
+        let resp = app
+            .clone()
+            .oneshot(generate(None, Some("explicit")))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get("set-cookie").is_none());
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_worker_pinning_header() {
+        let config = RouterConfig {
+            policy: PolicyConfig::RoundRobin,
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            allow_worker_pinning: true,
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![
+                MockWorkerConfig {
+                    port: 18609,
+                    worker_type: WorkerType::Regular,
+                    health_status: HealthStatus::Healthy,
+                    response_delay_ms: 300,
+                    fail_rate: 0.0,
+                },
+                MockWorkerConfig {
+                    port: 18610,
+                    worker_type: WorkerType::Regular,
+                    health_status: HealthStatus::Healthy,
+                    response_delay_ms: 0,
+                    fail_rate: 0.0,
+                },
+            ],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let generate = |target: &str| {
+            let payload = json!({
+                "text": "Hello",
+                "stream": false
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/generate")
+                .header(CONTENT_TYPE, "application/json")
+                .header("x-router-target-worker", target)
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+
+        // Round robin would alternate; pinning keeps every request on the fast worker
+        for _ in 0..4 {
+            let start = std::time::Instant::now();
+            let resp = app
+                .clone()
+                .oneshot(generate("http://127.0.0.1:18610"))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert!(start.elapsed() < std::time::Duration::from_millis(250));
+            // Routing headers are only exposed when enabled
+            assert!(resp.headers().get("x-router-worker").is_none());
+        }
+
+        let resp = app
+            .clone()
+            .oneshot(generate("http://127.0.0.1:18999"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_routing_headers_exposed_when_enabled() {
+        let config = RouterConfig {
+            policy: PolicyConfig::RoundRobin,
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            expose_routing_headers: true,
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18333,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let payload = json!({"text": "Hello", "stream": false});
+        let req = Request::builder()
+            .method("POST")
+            .uri("/generate")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let header = |name: &str| resp.headers().get(name).unwrap().to_str().unwrap();
+        assert_eq!(header("x-router-worker"), "http://127.0.0.1:18333");
+        assert_eq!(header("x-router-policy"), "round_robin");
+        assert_eq!(header("x-router-retries"), "0");
+
+        ctx.shutdown().await;
+    }
+
+    fn completion_request(model: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/v1/completions")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({"model": model, "prompt": "Hello", "stream": false}).to_string(),
+            ))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_model_policies_apply_to_startup_workers() {
+        let config = RouterConfig {
+            policy: PolicyConfig::RoundRobin,
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            expose_routing_headers: true,
+            model_policies: std::collections::HashMap::from([(
+                "mock-model-path".to_string(),
+                PolicyConfig::Random,
+            )]),
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18337,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        // The worker is registered under the model it reports
+        let workers = ctx.app_context.worker_registry.get_all();
+        assert_eq!(workers[0].model_id(), "mock-model-path");
+
+        let app = ctx.create_app().await;
+        let resp = app
+            .clone()
+            .oneshot(completion_request("mock-model-path"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("x-router-policy").unwrap(), "random");
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_strict_model_routing_with_startup_workers() {
+        let config = RouterConfig {
+            policy: PolicyConfig::RoundRobin,
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            strict_model_routing: true,
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18338,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+        let resp = app
+            .clone()
+            .oneshot(completion_request("mock-model-path"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = app
+            .clone()
+            .oneshot(completion_request("other-model"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_min_healthy_workers_guard() {
+        let config = RouterConfig {
+            policy: PolicyConfig::RoundRobin,
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            allow_worker_pinning: true,
+            min_healthy_workers: 2,
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18611,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let req = Request::builder()
+            .method("GET")
+            .uri("/readiness")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body_json["status"], "not_ready");
+        assert_eq!(body_json["healthy_workers"], 1);
+
+        let generate = |target: Option<&str>| {
+            let payload = json!({
+                "text": "Hello",
+                "stream": false
+            });
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri("/generate")
+                .header(CONTENT_TYPE, "application/json");
+            if let Some(target) = target {
+                builder = builder.header("x-router-target-worker", target);
+            }
+            builder
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+
+        let resp = app.clone().oneshot(generate(None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let message = String::from_utf8_lossy(&body);
+        assert!(message.contains("at least 2 required"), "{}", message);
+
+        // Pinned requests still reach the surviving worker
+        let resp = app
+            .clone()
+            .oneshot(generate(Some("http://127.0.0.1:18611")))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_max_prompt_chars() {
+        let config = RouterConfig {
+            policy: PolicyConfig::Random,
+            max_prompt_chars: Some(16),
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18323,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let request = |uri: &str, payload: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap()
+        };
+
+        // Chat prompts count the text of every message
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "/v1/chat/completions",
+                json!({
+                    "model": "mock-model",
+                    "messages": [
+                        {"role": "system", "content": "Be brief."},
+                        {"role": "user", "content": "Say hello!"},
+                    ],
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "prompt_too_long");
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "/v1/completions",
+                json!({"model": "mock-model", "prompt": "a".repeat(17)}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        // Prompts within the limit are routed
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "/v1/chat/completions",
+                json!({
+                    "model": "mock-model",
+                    "messages": [{"role": "user", "content": "Say hello!"}],
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_max_tokens_default_and_limit() {
+        let config = RouterConfig {
+            policy: PolicyConfig::Random,
+            default_max_tokens: Some(128),
+            max_tokens_limit: Some(512),
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18331,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+
+        // The mock worker reports the forwarded max_tokens as completion tokens
+        let completion_tokens = |uri: &str, payload: serde_json::Value| {
+            let app = app.clone();
+            let request = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap();
+            async move {
+                let resp = app.oneshot(request).await.unwrap();
+                assert_eq!(resp.status(), StatusCode::OK);
+                let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                body["usage"]["completion_tokens"].as_u64().unwrap()
+            }
+        };
+        let chat = |extra: serde_json::Value| {
+            let mut payload = json!({
+                "model": "mock-model",
+                "messages": [{"role": "user", "content": "Say hello!"}],
+            });
+            payload
+                .as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            payload
+        };
+
+        // Omitted max_tokens gets the default
+        assert_eq!(
+            completion_tokens("/v1/chat/completions", chat(json!({}))).await,
+            128
+        );
+        assert_eq!(
+            completion_tokens(
+                "/v1/completions",
+                json!({"model": "mock-model", "prompt": "hi"})
+            )
+            .await,
+            128
+        );
+
+        // Values within the limit are kept, larger ones are clamped
+        assert_eq!(
+            completion_tokens("/v1/chat/completions", chat(json!({"max_tokens": 300}))).await,
+            300
+        );
+        assert_eq!(
+            completion_tokens(
+                "/v1/completions",
+                json!({"model": "mock-model", "prompt": "hi", "max_tokens": 100_000})
+            )
+            .await,
+            512
+        );
+
+        ctx.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_route_payload_limits() {
+        let config = RouterConfig {
+            policy: PolicyConfig::Random,
+            max_payload_size: 1024,
+            payload_limits: vllm_router_rs::config::PayloadLimitsConfig {
+                completions: Some(64 * 1024),
+                ..Default::default()
+            },
+            worker_startup_timeout_secs: 1,
+            worker_startup_check_interval_secs: 1,
+            ..Default::default()
+        };
+
+        let ctx = TestContext::new_with_config(
+            config,
+            vec![MockWorkerConfig {
+                port: 18612,
+                worker_type: WorkerType::Regular,
+                health_status: HealthStatus::Healthy,
+                response_delay_ms: 0,
+                fail_rate: 0.0,
+            }],
+        )
+        .await;
+
+        let app = ctx.create_app().await;
+
+        let request = |uri: &str, payload: serde_json::Value, content_length: bool| {
+            let body = serde_json::to_string(&payload).unwrap();
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json");
+            if content_length {
+                builder = builder.header(CONTENT_LENGTH, body.len());
+            }
+            builder.body(Body::from(body)).unwrap()
+        };
+        let large_text = "a".repeat(4096);
+
+        // The global limit applies to routes without an override
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "/generate",
+                json!({"text": large_text, "stream": false}),
+                true,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let message = String::from_utf8_lossy(&body);
+        assert!(
+            message.contains("exceeds the 1024 byte limit for /generate"),
+            "{}",
+            message
+        );
+
+        // Bodies without a Content-Length are cut off too
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "/generate",
+                json!({"text": large_text, "stream": false}),
+                false,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // The override raises the limit for its route only
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "/v1/completions",
+                json!({"model": "test-model", "prompt": large_text, "stream": false}),
+                true,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
 
         ctx.shutdown().await;
     }
@@ -1735,6 +4898,10 @@ mod pd_mode_tests {
             port: 3011,
             max_payload_size: 256 * 1024 * 1024,
             request_timeout_secs: 600,
+            proxy_timeout_secs: 10,
+            generation_timeout_secs: None,
+            max_request_timeout_secs: None,
+            slow_request_threshold_secs: None,
             worker_startup_timeout_secs: 1,
             worker_startup_check_interval_secs: 1,
             discovery: None,
@@ -1749,9 +4916,49 @@ mod pd_mode_tests {
             queue_size: 0,
             queue_timeout_secs: 60,
             rate_limit_tokens_per_second: None,
+            model_concurrency_limits: std::collections::HashMap::new(),
+            adaptive_concurrency: None,
+            model_policies: std::collections::HashMap::new(),
+            queue_fairness: vllm_router_rs::config::QueueFairness::Fifo,
+            queue_weights: std::collections::HashMap::new(),
+            queue_priority_aging_secs: 10,
+            model_aliases: std::collections::HashMap::new(),
+            model_fallbacks: std::collections::HashMap::new(),
+            model_traffic_splits: std::collections::HashMap::new(),
+            discovery_stale_secs: 30,
+            advertise_model_aliases: false,
+            rewrite_stream_model: false,
+            strict_model_routing: false,
+            enable_response_compression: false,
+            enable_upstream_compression: false,
+            max_concurrent_streams: None,
+            stream_buffer_size: 64,
+            stream_keepalive_secs: None,
+            shutdown_drain_timeout_secs: 30,
+            batch_max_concurrency: 8,
             cors_allowed_origins: vec![],
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            hedging: None,
+            traffic_mirror: None,
+            worker_rate_limit: None,
+            worker_warmup: None,
+            model_version_check: None,
+            outlier_detection: None,
+            response_cache: None,
+            idempotency: None,
+            header_policy: Default::default(),
+            body_logging: None,
+            allow_worker_pinning: false,
+            expose_routing_headers: false,
+            upstream_auth: None,
+            upstream_tls: None,
+            pd_zone_affinity: false,
+            min_healthy_workers: 1,
+            payload_limits: vllm_router_rs::config::PayloadLimitsConfig::default(),
+            max_prompt_chars: None,
+            default_max_tokens: None,
+            max_tokens_limit: None,
             disable_retries: false,
             disable_circuit_breaker: false,
             health_check: vllm_router_rs::config::HealthCheckConfig::default(),
@@ -1760,6 +4967,7 @@ mod pd_mode_tests {
             model_path: None,
             tokenizer_path: None,
             history_backend: vllm_router_rs::config::HistoryBackend::Memory,
+            memory_history: vllm_router_rs::config::MemoryHistoryConfig::default(),
             enable_profiling: false,
             profile_timeout_secs: 30,
         };
@@ -1901,6 +5109,10 @@ mod request_id_tests {
             port: 3002,
             max_payload_size: 256 * 1024 * 1024,
             request_timeout_secs: 600,
+            proxy_timeout_secs: 10,
+            generation_timeout_secs: None,
+            max_request_timeout_secs: None,
+            slow_request_threshold_secs: None,
             worker_startup_timeout_secs: 1,
             worker_startup_check_interval_secs: 1,
             discovery: None,
@@ -1915,9 +5127,49 @@ mod request_id_tests {
             queue_size: 0,
             queue_timeout_secs: 60,
             rate_limit_tokens_per_second: None,
+            model_concurrency_limits: std::collections::HashMap::new(),
+            adaptive_concurrency: None,
+            model_policies: std::collections::HashMap::new(),
+            queue_fairness: vllm_router_rs::config::QueueFairness::Fifo,
+            queue_weights: std::collections::HashMap::new(),
+            queue_priority_aging_secs: 10,
+            model_aliases: std::collections::HashMap::new(),
+            model_fallbacks: std::collections::HashMap::new(),
+            model_traffic_splits: std::collections::HashMap::new(),
+            discovery_stale_secs: 30,
+            advertise_model_aliases: false,
+            rewrite_stream_model: false,
+            strict_model_routing: false,
+            enable_response_compression: false,
+            enable_upstream_compression: false,
+            max_concurrent_streams: None,
+            stream_buffer_size: 64,
+            stream_keepalive_secs: None,
+            shutdown_drain_timeout_secs: 30,
+            batch_max_concurrency: 8,
             cors_allowed_origins: vec![],
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            hedging: None,
+            traffic_mirror: None,
+            worker_rate_limit: None,
+            worker_warmup: None,
+            model_version_check: None,
+            outlier_detection: None,
+            response_cache: None,
+            idempotency: None,
+            header_policy: Default::default(),
+            body_logging: None,
+            allow_worker_pinning: false,
+            expose_routing_headers: false,
+            upstream_auth: None,
+            upstream_tls: None,
+            pd_zone_affinity: false,
+            min_healthy_workers: 1,
+            payload_limits: vllm_router_rs::config::PayloadLimitsConfig::default(),
+            max_prompt_chars: None,
+            default_max_tokens: None,
+            max_tokens_limit: None,
             disable_retries: false,
             disable_circuit_breaker: false,
             health_check: vllm_router_rs::config::HealthCheckConfig::default(),
@@ -1926,6 +5178,7 @@ mod request_id_tests {
             model_path: None,
             tokenizer_path: None,
             history_backend: vllm_router_rs::config::HistoryBackend::Memory,
+            memory_history: vllm_router_rs::config::MemoryHistoryConfig::default(),
             enable_profiling: false,
             profile_timeout_secs: 30,
         };
@@ -11,6 +11,7 @@ fn test_backward_compatibility_with_empty_model_id() {
         balance_rel_threshold: 1.5,
         eviction_interval_secs: 0, // Disable background eviction for testing
         max_tree_size: 100,
+        ..Default::default()
     };
 
     let policy = CacheAwarePolicy::with_config(config);
@@ -48,6 +49,7 @@ fn test_mixed_model_ids() {
         balance_rel_threshold: 1.5,
         eviction_interval_secs: 0,
         max_tree_size: 100,
+        ..Default::default()
     };
 
     let policy = CacheAwarePolicy::with_config(config);
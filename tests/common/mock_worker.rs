@@ -91,6 +91,8 @@ impl MockWorker {
             )
             .route("/flush_cache", post(flush_cache_handler))
             .route("/v1/models", get(v1_models_handler))
+            .route("/v1/audio/transcriptions", post(raw_body_handler))
+            .route("/v1/uploads", post(raw_body_handler).get(raw_body_handler))
             .with_state(config);
 
         let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
@@ -422,6 +424,12 @@ async fn chat_completions_handler(
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
 
+    // Use the whole max_tokens budget, so tests can see what was forwarded
+    let completion_tokens = payload
+        .get("max_tokens")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(5);
+
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
@@ -468,8 +476,8 @@ async fn chat_completions_handler(
             }],
             "usage": {
                 "prompt_tokens": 10,
-                "completion_tokens": 5,
-                "total_tokens": 15
+                "completion_tokens": completion_tokens,
+                "total_tokens": 10 + completion_tokens
             }
         }))
         .into_response()
@@ -505,6 +513,12 @@ async fn completions_handler(
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
 
+    // Use the whole max_tokens budget, so tests can see what was forwarded
+    let completion_tokens = payload
+        .get("max_tokens")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(5);
+
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
@@ -548,8 +562,8 @@ async fn completions_handler(
             }],
             "usage": {
                 "prompt_tokens": 10,
-                "completion_tokens": 5,
-                "total_tokens": 15
+                "completion_tokens": completion_tokens,
+                "total_tokens": 10 + completion_tokens
             }
         }))
         .into_response()
@@ -687,6 +701,20 @@ async fn flush_cache_handler(State(config): State<Arc<RwLock<MockWorkerConfig>>>
     .into_response()
 }
 
+/// Echo what arrived for a non-JSON body, so tests can check it was forwarded unchanged
+async fn raw_body_handler(headers: axum::http::HeaderMap, body: axum::body::Bytes) -> Response {
+    let content_type = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    Json(json!({
+        "text": "mock transcription",
+        "content_type": content_type,
+        "body": String::from_utf8_lossy(&body),
+    }))
+    .into_response()
+}
+
 async fn v1_models_handler(State(config): State<Arc<RwLock<MockWorkerConfig>>>) -> Response {
     let config = config.read().await;
 
@@ -26,6 +26,18 @@ pub fn create_test_app(
         .expect("Failed to create AppContext in test"),
     );
 
+    create_test_app_with_context(router, app_context, router_config)
+}
+
+/// Create a test Axum application around the context the router was built from
+///
+/// Handlers then see the same worker and policy registries as the router.
+#[allow(dead_code)]
+pub fn create_test_app_with_context(
+    router: Arc<dyn RouterTrait>,
+    app_context: Arc<AppContext>,
+    router_config: &RouterConfig,
+) -> Router {
     // Create AppState with the test router and context
     let app_state = Arc::new(AppState {
         router,
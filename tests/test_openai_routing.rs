@@ -207,6 +207,13 @@ async fn test_unsupported_endpoints() {
         .route_completion(None, &completion_request, None)
         .await;
     assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+
+    // Unsupported endpoints answer with an OpenAI-shaped error body
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error["error"]["type"], "server_error");
 }
 
 // ============= Mock Server E2E Tests =============
@@ -363,6 +370,7 @@ async fn test_openai_router_circuit_breaker() {
         success_threshold: 1,
         timeout_duration_secs: 1,
         window_duration_secs: 10,
+        half_open_max_concurrent: 1,
     };
 
     let router = OpenAIRouter::new(
@@ -156,6 +156,7 @@ mod test_pd_routing {
                     balance_rel_threshold: 1.2,
                     eviction_interval_secs: 60,
                     max_tree_size: 1000000,
+                    cold_start_policy: vllm_router_rs::config::ColdStartPolicy::default(),
                 },
             ),
         ];
@@ -168,6 +169,10 @@ mod test_pd_routing {
                 port: 3001,
                 max_payload_size: 1024 * 1024,
                 request_timeout_secs: 60,
+                proxy_timeout_secs: 10,
+                generation_timeout_secs: None,
+                max_request_timeout_secs: None,
+                slow_request_threshold_secs: None,
                 worker_startup_timeout_secs: 10,
                 worker_startup_check_interval_secs: 1,
                 intra_node_data_parallel_size: 1,
@@ -184,15 +189,56 @@ mod test_pd_routing {
                 cors_allowed_origins: vec![],
                 retry: RetryConfig::default(),
                 circuit_breaker: CircuitBreakerConfig::default(),
+                hedging: None,
+                traffic_mirror: None,
+                worker_rate_limit: None,
+                worker_warmup: None,
+                model_version_check: None,
+                outlier_detection: None,
+                response_cache: None,
+                idempotency: None,
+                header_policy: Default::default(),
+                body_logging: None,
+                allow_worker_pinning: false,
+                expose_routing_headers: false,
+                upstream_auth: None,
+                upstream_tls: None,
+                pd_zone_affinity: false,
+                min_healthy_workers: 1,
+                payload_limits: vllm_router_rs::config::PayloadLimitsConfig::default(),
+                max_prompt_chars: None,
+                default_max_tokens: None,
+                max_tokens_limit: None,
                 disable_retries: false,
                 disable_circuit_breaker: false,
                 health_check: vllm_router_rs::config::HealthCheckConfig::default(),
                 enable_igw: false,
                 rate_limit_tokens_per_second: None,
+                model_concurrency_limits: std::collections::HashMap::new(),
+                adaptive_concurrency: None,
+                model_policies: std::collections::HashMap::new(),
+                queue_fairness: vllm_router_rs::config::QueueFairness::Fifo,
+                queue_weights: std::collections::HashMap::new(),
+                queue_priority_aging_secs: 10,
+                model_aliases: std::collections::HashMap::new(),
+                model_fallbacks: std::collections::HashMap::new(),
+                model_traffic_splits: std::collections::HashMap::new(),
+                discovery_stale_secs: 30,
+                advertise_model_aliases: false,
+                rewrite_stream_model: false,
+                strict_model_routing: false,
+                enable_response_compression: false,
+                enable_upstream_compression: false,
+                max_concurrent_streams: None,
+                stream_buffer_size: 64,
+                stream_keepalive_secs: None,
+                shutdown_drain_timeout_secs: 30,
+                batch_max_concurrency: 8,
                 connection_mode: ConnectionMode::Http,
                 model_path: None,
                 tokenizer_path: None,
                 history_backend: vllm_router_rs::config::HistoryBackend::Memory,
+                memory_history: vllm_router_rs::config::MemoryHistoryConfig::default(),
                 enable_profiling: false,
                 profile_timeout_secs: 30,
             };